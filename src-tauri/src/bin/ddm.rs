@@ -0,0 +1,277 @@
+//! `ddm`: drive the containers managed by Docker DB Manager from a terminal
+//!
+//! Works on the same store as the app. Set `DDM_PASSPHRASE` when the store is passphrase protected.
+
+use docker_db_manager_lib::services::{ConnectionService, DockerCli, LocalStore};
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: ddm <command>
+
+Commands:
+  list [--json]        List managed databases and their status
+  start <name|id>      Start a managed database
+  stop <name|id>       Stop a managed database
+  create <request>     Create a database from a JSON run request file
+                       (the app shows it after its next launch, close it first)
+  presets              List the framework presets
+  snippet <name|id> [framework]
+                       Print the framework config of a database, defaults to
+                       the preset it was created for
+  query-snippet <name|id> [snippet] [variable=value...]
+                       List the saved queries and commands of a database, or
+                       print one with its variables filled in
+  help                 Show this message";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            if let Some(hint) = error.hint() {
+                eprintln!("hint: {}", hint);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[&str]) -> Result<(), AppError> {
+    match args {
+        [] | ["help"] | ["--help"] | ["-h"] => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        ["list"] => list(false),
+        ["list", "--json"] => list(true),
+        ["start", target] => start(target),
+        ["stop", target] => stop(target),
+        ["create", path] => create(path),
+        ["presets"] => presets(),
+        ["snippet", target] => snippet(target, None),
+        ["snippet", target, framework] => snippet(target, Some(framework)),
+        ["query-snippet", target] => query_snippets(target),
+        ["query-snippet", target, snippet, values @ ..] => query_snippet(target, snippet, values),
+        _ => Err(AppError::Validation(format!(
+            "Unknown command '{}'\n\n{}",
+            args.join(" "),
+            USAGE
+        ))),
+    }
+}
+
+fn open_store() -> Result<LocalStore, AppError> {
+    let store = LocalStore::default_location()?;
+    if let Ok(passphrase) = std::env::var("DDM_PASSPHRASE") {
+        store.unlock(&passphrase)?;
+    }
+    Ok(store)
+}
+
+/// Databases with their status refreshed from Docker, sorted by name
+fn databases() -> Result<Vec<DatabaseContainer>, AppError> {
+    let mut databases: Vec<DatabaseContainer> =
+        open_store()?.load_databases()?.into_values().collect();
+    let states = DockerCli::new().list_container_states()?;
+
+    for database in databases.iter_mut() {
+        match states.get(&database.name) {
+            Some(state) => {
+                database.container_id = Some(state.id.clone());
+                database.apply_docker_state(state);
+            }
+            None => {
+                database.container_id = None;
+                database.status = "missing".to_string();
+            }
+        }
+    }
+
+    databases.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(databases)
+}
+
+fn find(target: &str) -> Result<DatabaseContainer, AppError> {
+    databases()?
+        .into_iter()
+        .find(|db| db.name == target || db.id == target)
+        .ok_or_else(|| AppError::NotFound(format!("Database '{}'", target)))
+}
+
+fn list(json: bool) -> Result<(), AppError> {
+    let mut databases = databases()?;
+
+    if json {
+        // Never print credentials
+        for database in databases.iter_mut() {
+            database.stored_password = None;
+        }
+        let output = serde_json::to_string_pretty(&databases)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    println!(
+        "{:<24} {:<12} {:<10} {:>6}  STATUS",
+        "NAME", "TYPE", "VERSION", "PORT"
+    );
+    for db in &databases {
+        println!(
+            "{:<24} {:<12} {:<10} {:>6}  {}",
+            db.name, db.db_type, db.version, db.port, db.status
+        );
+    }
+    Ok(())
+}
+
+fn start(target: &str) -> Result<(), AppError> {
+    let database = find(target)?;
+    let container_id = database
+        .container_id
+        .ok_or_else(|| AppError::NotFound(format!("Container of '{}'", database.name)))?;
+
+    DockerCli::new().start_container(&container_id)?;
+    println!("Started {}", database.name);
+    Ok(())
+}
+
+fn stop(target: &str) -> Result<(), AppError> {
+    let database = find(target)?;
+    let container_id = database
+        .container_id
+        .ok_or_else(|| AppError::NotFound(format!("Container of '{}'", database.name)))?;
+
+    DockerCli::new().stop_container(&container_id, database.stop_timeout)?;
+    println!("Stopped {}", database.name);
+    Ok(())
+}
+
+fn create(path: &str) -> Result<(), AppError> {
+    let content = std::fs::read_to_string(path)?;
+    let mut request: DockerRunRequest = serde_json::from_str(&content)
+        .map_err(|e| AppError::Validation(format!("Invalid run request: {}", e)))?;
+
+    if request.metadata.enable_tls {
+        return Err(AppError::Validation(
+            "TLS databases can only be created from the app".to_string(),
+        ));
+    }
+    if request.metadata.id.is_empty() {
+        request.metadata.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    let store = open_store()?;
+    let mut databases = store.load_databases()?;
+    let taken: Vec<String> = databases.values().map(|db| db.name.clone()).collect();
+    validate_container_name(&request.name, &taken)?;
+    validate_run_request(&request, &databases.values().collect::<Vec<_>>())?;
+    for warning in run_request_warnings(&request) {
+        eprintln!("warning: {}", warning);
+    }
+    if let Some(locale) = request.metadata.locale.clone() {
+        locale.apply(
+            &request.metadata.db_type,
+            &request.metadata.version,
+            &mut request.docker_args,
+        );
+    }
+
+    let container_id = DockerCli::new().run_container(&request, &store.active_workspace()?)?;
+    let database = DatabaseContainer::from_run_request(&request, container_id);
+    databases.insert(database.id.clone(), database);
+    store.save_databases(&databases)?;
+
+    println!("Created {}", request.name);
+    Ok(())
+}
+
+fn presets() -> Result<(), AppError> {
+    println!(
+        "{:<10} {:<18} {:<12} {:<8} {:<16} USER",
+        "ID", "FRAMEWORK", "TYPE", "VERSION", "DATABASE"
+    );
+    for preset in framework_presets() {
+        println!(
+            "{:<10} {:<18} {:<12} {:<8} {:<16} {}",
+            preset.framework.id(),
+            preset.name,
+            preset.db_type,
+            preset.version,
+            preset.database_name,
+            preset.username
+        );
+    }
+    Ok(())
+}
+
+fn snippet(target: &str, framework: Option<&str>) -> Result<(), AppError> {
+    let database = find(target)?;
+    let framework = match framework {
+        Some(value) => Framework::parse(value)
+            .ok_or_else(|| AppError::Validation(format!("Unknown framework preset '{}'", value)))?,
+        None => database.framework.ok_or_else(|| {
+            AppError::Validation(format!(
+                "{} was not created for a framework, pass one of: {}",
+                database.name,
+                Framework::ALL.map(|framework| framework.id()).join(", ")
+            ))
+        })?,
+    };
+
+    print!(
+        "{}",
+        ConnectionService::new().framework_snippet(&database, framework)
+    );
+    Ok(())
+}
+
+fn query_snippets(target: &str) -> Result<(), AppError> {
+    let database = find(target)?;
+    let snippets = open_store()?.load_snippets()?.for_database(&database);
+
+    println!("{:<32} {:<8} VARIABLES", "NAME", "KIND");
+    for snippet in &snippets {
+        let kind = match snippet.kind {
+            SnippetKind::Sql => "sql",
+            SnippetKind::Command => "command",
+        };
+        println!(
+            "{:<32} {:<8} {}",
+            snippet.name,
+            kind,
+            snippet.variables().join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Print a snippet of a database, its own win over the ones of its type with the same name
+fn query_snippet(target: &str, name: &str, values: &[&str]) -> Result<(), AppError> {
+    let database = find(target)?;
+    let values = values
+        .iter()
+        .map(|value| {
+            value
+                .split_once('=')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| {
+                    AppError::Validation(format!("Expected variable=value, got '{}'", value))
+                })
+        })
+        .collect::<Result<HashMap<String, String>, AppError>>()?;
+
+    let snippet = open_store()?
+        .load_snippets()?
+        .for_database(&database)
+        .into_iter()
+        .find(|snippet| snippet.id == name || snippet.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| AppError::NotFound(format!("Snippet '{}' of {}", name, database.name)))?;
+
+    println!("{}", snippet.render(&database, &values)?);
+    Ok(())
+}
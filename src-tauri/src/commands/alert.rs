@@ -0,0 +1,61 @@
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+/// Alert rules, only the ones of `container_id` when set
+#[tauri::command]
+pub async fn list_alert_rules(
+    container_id: Option<String>,
+    app: AppHandle,
+) -> Result<Vec<AlertRule>, AppError> {
+    let mut rules = StorageService::new()
+        .load_alert_rules_from_store(&app)
+        .await?;
+    if let Some(container_id) = container_id {
+        rules.retain(|rule| rule.database_id == container_id);
+    }
+    Ok(rules)
+}
+
+/// Create a rule when its id is empty, update it otherwise
+#[tauri::command]
+pub async fn save_alert_rule(
+    rule: AlertRule,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<AlertRule, AppError> {
+    ensure_writable(&app)?;
+    if !databases.read().await.contains_key(&rule.database_id) {
+        return Err(AppError::NotFound("Container".to_string()));
+    }
+    AlertService::new().save_rule(&app, rule).await
+}
+
+#[tauri::command]
+pub async fn delete_alert_rule(rule_id: String, app: AppHandle) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    AlertService::new().delete_rule(&app, &rule_id).await
+}
+
+/// Fired alerts, newest first, only the ones of `container_id` when set
+#[tauri::command]
+pub async fn list_alert_history(
+    container_id: Option<String>,
+    limit: Option<usize>,
+    app: AppHandle,
+) -> Result<Vec<AlertEvent>, AppError> {
+    let history = StorageService::new()
+        .load_alert_history_from_store(&app)
+        .await?;
+    Ok(history
+        .into_iter()
+        .rev()
+        .filter(|alert| {
+            container_id
+                .as_ref()
+                .is_none_or(|id| alert.database_id == *id)
+        })
+        .take(limit.unwrap_or(MAX_ALERT_HISTORY))
+        .collect())
+}
@@ -0,0 +1,225 @@
+use crate::commands::database::{
+    create_container_from_docker_args, get_all_databases, start_container, stop_container,
+};
+use crate::commands::snapshot::create_snapshot;
+use crate::services::*;
+use crate::types::*;
+use serde::Serialize;
+use std::sync::{Arc, LazyLock, Mutex};
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Request, Response, Server};
+
+/// Running localhost server and its port
+pub(crate) type ServerSlot = Mutex<Option<(u16, Arc<Server>)>>;
+
+// Running API server, replaced whenever the settings change
+static SERVER: LazyLock<ServerSlot> = LazyLock::new(|| Mutex::new(None));
+// Token read from the keychain once, instead of on every request
+static TOKEN: LazyLock<Mutex<Option<String>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Start, restart or stop the local HTTP API to match `settings`
+pub fn apply_api_server(app: &AppHandle, settings: &AppSettings) -> Result<(), AppError> {
+    restart_local_server(
+        app,
+        &SERVER,
+        settings.api_enabled.then_some(settings.api_port),
+        handle_request,
+    )
+}
+
+/// Bind a server to the wanted port, replacing the one in the slot
+/// Nothing changes when the port is the same, `None` only stops it
+pub(crate) fn restart_local_server(
+    app: &AppHandle,
+    slot: &ServerSlot,
+    wanted: Option<u16>,
+    handler: fn(&AppHandle, Request),
+) -> Result<(), AppError> {
+    let mut server = slot.lock().unwrap();
+    if server.as_ref().map(|(port, _)| *port) == wanted {
+        return Ok(());
+    }
+
+    let Some(port) = wanted else {
+        if let Some((_, running)) = server.take() {
+            running.unblock();
+        }
+        return Ok(());
+    };
+
+    // Only reachable from this machine, the running server is kept when the port is taken
+    let listener =
+        Arc::new(
+            Server::http(("127.0.0.1", port)).map_err(|_| AppError::PortInUse {
+                port: i32::from(port),
+            })?,
+        );
+    api_token()?;
+    if let Some((_, running)) = server.take() {
+        running.unblock();
+    }
+
+    let worker = listener.clone();
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for request in worker.incoming_requests() {
+            handler(&app, request);
+        }
+    });
+
+    *server = Some((port, listener));
+    Ok(())
+}
+
+/// Token expected in the `Authorization` header, created on first use
+fn api_token() -> Result<String, AppError> {
+    let mut cached = TOKEN.lock().unwrap();
+    if let Some(token) = cached.as_ref() {
+        return Ok(token.clone());
+    }
+
+    let secrets_service = SecretsService::new();
+    let token = match secrets_service.get_secret(API_TOKEN_KEY)? {
+        Some(token) => token,
+        None => {
+            let token = new_token();
+            secrets_service.set_secret(API_TOKEN_KEY, &token)?;
+            token
+        }
+    };
+    *cached = Some(token.clone());
+    Ok(token)
+}
+
+fn new_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Whether the request carries the API token, answers 401 when it does not
+pub(crate) fn authorize(request: Request) -> Option<Request> {
+    let authorization = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .map(|h| h.value.as_str().to_string());
+
+    let authorized = match api_token() {
+        Ok(token) => api_token_matches(authorization.as_deref(), &token),
+        Err(_) => false,
+    };
+    if authorized {
+        return Some(request);
+    }
+
+    let _ = request.respond(json_response(
+        401,
+        &AppError::Validation("Missing or invalid API token".to_string()),
+    ));
+    None
+}
+
+pub(crate) fn read_body(request: &mut Request) -> Result<String, AppError> {
+    let mut body = String::new();
+    request.as_reader().read_to_string(&mut body)?;
+    Ok(body)
+}
+
+fn handle_request(app: &AppHandle, request: Request) {
+    let Some(mut request) = authorize(request) else {
+        return;
+    };
+
+    let Some(route) = ApiRoute::parse(request.method().as_str(), request.url()) else {
+        let _ = request.respond(json_response(
+            404,
+            &AppError::NotFound("Endpoint".to_string()),
+        ));
+        return;
+    };
+
+    let body = match read_body(&mut request) {
+        Ok(body) => body,
+        Err(error) => {
+            let _ = request.respond(json_response(400, &error));
+            return;
+        }
+    };
+
+    let response = match tauri::async_runtime::block_on(run_route(app, route, &body)) {
+        Ok(value) => json_response(200, &value),
+        Err(error) => json_response(error.http_status(), &error),
+    };
+    let _ = request.respond(response);
+}
+
+async fn run_route(
+    app: &AppHandle,
+    route: ApiRoute,
+    body: &str,
+) -> Result<serde_json::Value, AppError> {
+    let value = match route {
+        ApiRoute::ListDatabases => {
+            let databases = get_all_databases(app.state()).await?;
+            to_value(databases.iter().map(ApiDatabase::from).collect::<Vec<_>>())
+        }
+        ApiRoute::CreateDatabase => {
+            let request: DockerRunRequest = serde_json::from_str(body)
+                .map_err(|e| AppError::Validation(format!("Invalid run request: {}", e)))?;
+            let database =
+                create_container_from_docker_args(request, app.clone(), app.state()).await?;
+            to_value(ApiDatabase::from(&database))
+        }
+        ApiRoute::StartDatabase(id) => {
+            start_container(id, app.clone(), app.state()).await?;
+            serde_json::json!({ "success": true })
+        }
+        ApiRoute::StopDatabase(id) => {
+            stop_container(id, app.clone(), app.state()).await?;
+            serde_json::json!({ "success": true })
+        }
+        ApiRoute::BackupDatabase(id) => {
+            let backup: ApiBackupRequest = if body.trim().is_empty() {
+                ApiBackupRequest::default()
+            } else {
+                serde_json::from_str(body)
+                    .map_err(|e| AppError::Validation(format!("Invalid backup request: {}", e)))?
+            };
+            to_value(create_snapshot(id, backup.label, app.clone(), app.state()).await?)
+        }
+    };
+
+    Ok(value)
+}
+
+fn to_value(value: impl Serialize) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+pub(crate) fn json_response(
+    status: u16,
+    body: &impl Serialize,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let body = serde_json::to_vec(body).unwrap_or_default();
+    Response::from_data(body)
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").expect("static header"))
+}
+
+/// Token for the local HTTP API, shown so the user can copy it into scripts
+#[tauri::command]
+pub async fn get_api_token() -> Result<String, AppError> {
+    api_token()
+}
+
+/// Replace the API token, scripts using the old one stop working
+#[tauri::command]
+pub async fn regenerate_api_token() -> Result<String, AppError> {
+    let token = new_token();
+    SecretsService::new().set_secret(API_TOKEN_KEY, &token)?;
+    *TOKEN.lock().unwrap() = Some(token.clone());
+    Ok(token)
+}
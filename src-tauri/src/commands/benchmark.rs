@@ -0,0 +1,50 @@
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+/// Run pgbench, sysbench, memtier_benchmark or YCSB against a running database
+/// The run is added to the container's benchmark history
+#[tauri::command]
+pub async fn run_benchmark(
+    container_id: String,
+    params: Option<BenchmarkParams>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<BenchmarkRun, AppError> {
+    ensure_writable(&app)?;
+    let database = {
+        let db_map = databases.read().await;
+        db_map
+            .get(&container_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+    };
+
+    BenchmarkService::new()
+        .run(&app, &database, &params.unwrap_or_default())
+        .await
+}
+
+/// Benchmark runs of a container, newest first
+#[tauri::command]
+pub async fn list_benchmarks(
+    container_id: String,
+    app: AppHandle,
+) -> Result<Vec<BenchmarkRun>, AppError> {
+    let mut runs: Vec<BenchmarkRun> = StorageService::new()
+        .load_benchmarks_from_store(&app)
+        .await?
+        .into_iter()
+        .filter(|run| run.database_id == container_id)
+        .collect();
+    runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+
+    Ok(runs)
+}
+
+#[tauri::command]
+pub async fn delete_benchmark(benchmark_id: String, app: AppHandle) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    BenchmarkService::new().delete(&app, &benchmark_id).await
+}
@@ -0,0 +1,338 @@
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+/// Reload companions from the store so memory reflects what was persisted
+async fn load_companions(
+    app: &AppHandle,
+    companions: &State<'_, CompanionStore>,
+) -> Result<(), AppError> {
+    let storage_service = StorageService::new();
+    let loaded = storage_service.load_companions_from_store(app).await?;
+
+    let mut companion_map = companions.lock().unwrap();
+    *companion_map = loaded;
+
+    Ok(())
+}
+
+async fn save_companions(
+    app: &AppHandle,
+    companions: &State<'_, CompanionStore>,
+) -> Result<(), AppError> {
+    let storage_service = StorageService::new();
+    let companion_map = {
+        let map = companions.lock().unwrap();
+        map.clone()
+    };
+
+    storage_service
+        .save_companions_to_store(app, &companion_map)
+        .await
+}
+
+/// Deploy an admin tool (pgAdmin, phpMyAdmin, Mongo Express, RedisInsight)
+/// or a connection pooler (PgBouncer, ProxySQL) linked to a managed database
+#[tauri::command]
+pub async fn deploy_companion(
+    database_id: String,
+    kind: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+    companions: State<'_, CompanionStore>,
+) -> Result<CompanionContainer, AppError> {
+    ensure_writable(&app)?;
+    let companion_service = CompanionService::new();
+
+    let database = {
+        let db_map = databases.read().await;
+        db_map
+            .get(&database_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+    };
+
+    if !database.is_running() {
+        return Err(AppError::Validation(
+            "Start the database before deploying a companion".to_string(),
+        ));
+    }
+
+    load_companions(&app, &companions).await?;
+
+    // Only one instance of each tool per database
+    let existing = {
+        let companion_map = companions.lock().unwrap();
+        companion_map
+            .values()
+            .find(|c| c.database_id == database_id && c.kind == kind)
+            .cloned()
+    };
+    if let Some(mut existing) = existing {
+        if existing.status != "running" {
+            DockerService::new()
+                .start_container(&app, &existing.name)
+                .await?;
+            existing.status = "running".to_string();
+            companions
+                .lock()
+                .unwrap()
+                .insert(existing.id.clone(), existing.clone());
+            save_companions(&app, &companions).await?;
+        }
+        return Ok(existing);
+    }
+
+    let companion = companion_service.deploy(&app, &kind, &database).await?;
+
+    companions
+        .lock()
+        .unwrap()
+        .insert(companion.id.clone(), companion.clone());
+
+    if let Err(store_error) = save_companions(&app, &companions).await {
+        companions.lock().unwrap().remove(&companion.id);
+        let _ = companion_service.remove(&app, &companion).await;
+        return Err(store_error);
+    }
+
+    Ok(companion)
+}
+
+/// List companions, optionally only those linked to one database
+#[tauri::command]
+pub async fn get_companions(
+    database_id: Option<String>,
+    app: AppHandle,
+    companions: State<'_, CompanionStore>,
+) -> Result<Vec<CompanionContainer>, AppError> {
+    let docker_service = DockerService::new();
+
+    load_companions(&app, &companions).await?;
+
+    // Sync status with Docker
+    let docker_containers = docker_service.list_containers(&app).await?;
+    {
+        let mut companion_map = companions.lock().unwrap();
+        for companion in companion_map.values_mut() {
+            if let Some((docker_id, is_running)) = docker_containers.get(&companion.name) {
+                companion.container_id = Some(docker_id.clone());
+                companion.status = if *is_running {
+                    "running".to_string()
+                } else {
+                    "stopped".to_string()
+                };
+            } else {
+                companion.status = "stopped".to_string();
+                companion.container_id = None;
+            }
+        }
+    }
+
+    save_companions(&app, &companions).await?;
+
+    let companion_map = companions.lock().unwrap();
+    Ok(companion_map
+        .values()
+        .filter(|c| database_id.as_ref().is_none_or(|id| &c.database_id == id))
+        .cloned()
+        .collect())
+}
+
+/// Launch Adminer next to a SQL database and return the URL to open
+#[tauri::command]
+pub async fn deploy_adminer(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+    companions: State<'_, CompanionStore>,
+) -> Result<String, AppError> {
+    ensure_writable(&app)?;
+    let companion = deploy_companion(
+        container_id,
+        "adminer".to_string(),
+        app,
+        databases,
+        companions,
+    )
+    .await?;
+
+    Ok(companion.url)
+}
+
+#[tauri::command]
+pub async fn start_companion(
+    companion_id: String,
+    app: AppHandle,
+    companions: State<'_, CompanionStore>,
+) -> Result<(), AppError> {
+    let docker_service = DockerService::new();
+
+    load_companions(&app, &companions).await?;
+
+    let name = {
+        let companion_map = companions.lock().unwrap();
+        companion_map
+            .get(&companion_id)
+            .map(|c| c.name.clone())
+            .ok_or_else(|| AppError::NotFound("Companion".to_string()))?
+    };
+
+    docker_service.start_container(&app, &name).await?;
+
+    if let Some(companion) = companions.lock().unwrap().get_mut(&companion_id) {
+        companion.status = "running".to_string();
+    }
+
+    save_companions(&app, &companions).await
+}
+
+#[tauri::command]
+pub async fn stop_companion(
+    companion_id: String,
+    app: AppHandle,
+    companions: State<'_, CompanionStore>,
+) -> Result<(), AppError> {
+    let docker_service = DockerService::new();
+
+    load_companions(&app, &companions).await?;
+
+    let name = {
+        let companion_map = companions.lock().unwrap();
+        companion_map
+            .get(&companion_id)
+            .map(|c| c.name.clone())
+            .ok_or_else(|| AppError::NotFound("Companion".to_string()))?
+    };
+
+    docker_service.stop_container(&app, &name).await?;
+
+    if let Some(companion) = companions.lock().unwrap().get_mut(&companion_id) {
+        companion.status = "stopped".to_string();
+    }
+
+    save_companions(&app, &companions).await
+}
+
+#[tauri::command]
+pub async fn remove_companion(
+    companion_id: String,
+    app: AppHandle,
+    companions: State<'_, CompanionStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let companion_service = CompanionService::new();
+
+    load_companions(&app, &companions).await?;
+
+    let companion = {
+        let companion_map = companions.lock().unwrap();
+        companion_map
+            .get(&companion_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Companion".to_string()))?
+    };
+
+    companion_service.remove(&app, &companion).await?;
+
+    companions.lock().unwrap().remove(&companion_id);
+
+    save_companions(&app, &companions).await
+}
+
+/// Redeploy the companions of a renamed database on the network of its new name
+/// Companions reach the database by its container name, which the rename changed
+pub async fn move_companions_for_database(
+    app: &AppHandle,
+    companions: &State<'_, CompanionStore>,
+    previous_name: &str,
+    database: &DatabaseContainer,
+) -> Result<(), AppError> {
+    let companion_service = CompanionService::new();
+
+    load_companions(app, companions).await?;
+
+    let linked: Vec<CompanionContainer> = {
+        let companion_map = companions.lock().unwrap();
+        companion_map
+            .values()
+            .filter(|c| c.database_id == database.id)
+            .cloned()
+            .collect()
+    };
+
+    if linked.is_empty() {
+        return Ok(());
+    }
+
+    let mut moved = Ok(());
+    for companion in &linked {
+        if let Err(error) = companion_service.remove(app, companion).await {
+            moved = Err(error);
+            break;
+        }
+        companions.lock().unwrap().remove(&companion.id);
+
+        match companion_service
+            .deploy(app, &companion.kind, database)
+            .await
+        {
+            Ok(redeployed) => {
+                companions
+                    .lock()
+                    .unwrap()
+                    .insert(redeployed.id.clone(), redeployed);
+            }
+            Err(error) => {
+                moved = Err(error);
+                break;
+            }
+        }
+    }
+
+    // Companions left on it were removed above, the database moved to its new name
+    let _ = DockerService::new()
+        .remove_network_if_exists(app, &CompanionService::network_name(previous_name))
+        .await;
+
+    save_companions(app, companions).await?;
+    moved
+}
+
+/// Remove every companion linked to a database along with its network
+pub async fn remove_companions_for_database(
+    app: &AppHandle,
+    companions: &State<'_, CompanionStore>,
+    database: &DatabaseContainer,
+) -> Result<(), AppError> {
+    let companion_service = CompanionService::new();
+    let docker_service = DockerService::new();
+
+    load_companions(app, companions).await?;
+
+    let linked: Vec<CompanionContainer> = {
+        let companion_map = companions.lock().unwrap();
+        companion_map
+            .values()
+            .filter(|c| c.database_id == database.id)
+            .cloned()
+            .collect()
+    };
+
+    if linked.is_empty() {
+        return Ok(());
+    }
+
+    for companion in &linked {
+        companion_service.remove(app, companion).await?;
+        companions.lock().unwrap().remove(&companion.id);
+    }
+
+    // The database container is gone by now, so the network can be dropped
+    let _ = docker_service
+        .remove_network_if_exists(app, &CompanionService::network_name(&database.name))
+        .await;
+
+    save_companions(app, companions).await
+}
@@ -0,0 +1,274 @@
+use crate::commands::database::create_database_container;
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+/// Write all managed containers and settings to a JSON bundle
+/// Credentials are left out unless `include_secrets` is set
+#[tauri::command]
+pub async fn export_config(
+    path: String,
+    include_secrets: Option<bool>,
+    databases: State<'_, DatabaseStore>,
+    settings: State<'_, SettingsStore>,
+) -> Result<(), AppError> {
+    let config_service = ConfigService::new();
+
+    let bundle = {
+        let db_map = databases.read().await;
+        let current_settings = settings.lock().unwrap();
+        ConfigService::build_bundle(&db_map, &current_settings, include_secrets.unwrap_or(false))
+    };
+
+    config_service.write_bundle(Path::new(&path), &bundle)
+}
+
+/// Kubernetes manifests running a database like its local container, as YAML
+#[tauri::command]
+pub async fn export_kubernetes(
+    container_id: String,
+    databases: State<'_, DatabaseStore>,
+) -> Result<String, AppError> {
+    let database = {
+        let db_map = databases.read().await;
+        db_map
+            .get(&container_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+    };
+
+    KubernetesService::new().manifests(&database)
+}
+
+/// Archive a database's configuration, a snapshot of its data and a manifest in one file
+/// The container is stopped while its data is copied, then restarted
+#[tauri::command]
+pub async fn export_bundle(
+    container_id: String,
+    path: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<BundleExport, AppError> {
+    let database = {
+        let db_map = databases.read().await;
+        db_map
+            .get(&container_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+    };
+
+    BundleService::new()
+        .export(&app, &database, Path::new(&path))
+        .await
+}
+
+/// Recreate a database from a bundle written by `export_bundle`
+/// A name or host port taken on this machine is replaced, the result reports what moved
+#[tauri::command]
+pub async fn import_bundle(
+    path: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<BundleImport, AppError> {
+    ensure_writable(&app)?;
+    let bundle_service = BundleService::new();
+
+    let bundle = bundle_service.open(&app, Path::new(&path)).await?;
+    let result = restore_bundle(&app, &databases, &bundle_service, &bundle).await;
+    bundle_service.close(&app, &bundle).await;
+
+    result
+}
+
+async fn restore_bundle(
+    app: &AppHandle,
+    databases: &State<'_, DatabaseStore>,
+    bundle_service: &BundleService,
+    bundle: &OpenedBundle,
+) -> Result<BundleImport, AppError> {
+    let docker_service = DockerService::new();
+    let exported = &bundle.database;
+
+    let mut request = ConfigService::build_run_request(exported)?;
+    // The exporting machine may still run the original, both are kept apart here
+    request.metadata.id = uuid::Uuid::new_v4().to_string();
+    // The data is unpacked on the local daemon, the database is created next to it
+    request.metadata.host = None;
+
+    let (taken_names, taken_ports) = {
+        let db_map = databases.read().await;
+        let names: Vec<String> = db_map.values().map(|db| db.name.clone()).collect();
+        let mut ports: Vec<i32> = Vec::new();
+        for db in db_map.values() {
+            ports.push(db.port);
+            if let Some(docker_args) = &db.stored_docker_args {
+                ports.extend(docker_args.ports.iter().map(|mapping| mapping.host));
+            }
+        }
+        (names, ports)
+    };
+    let (renamed_from, remapped_ports) =
+        relocate_bundle_request(&mut request, &taken_names, |port| {
+            !taken_ports.contains(&port)
+                && u16::try_from(port)
+                    .is_ok_and(|port| std::net::TcpListener::bind(("127.0.0.1", port)).is_ok())
+        });
+
+    let volume = SnapshotService::data_volume_name(&request.name);
+    if !request
+        .docker_args
+        .volumes
+        .iter()
+        .any(|mount| mount.name == volume)
+    {
+        return Err(AppError::Validation(format!(
+            "The bundled configuration does not mount {}",
+            volume
+        )));
+    }
+    // Data left in an existing volume would mix with the bundled data
+    if docker_service.list_volumes(app).await?.contains(&volume) {
+        return Err(AppError::Validation(format!(
+            "Volume {} already exists, remove it before importing the bundle",
+            volume
+        )));
+    }
+
+    docker_service.create_volume_if_needed(app, &volume).await?;
+    if let Err(error) = bundle_service.restore_data(app, bundle, &volume).await {
+        let _ = docker_service.remove_volume_if_exists(app, &volume).await;
+        return Err(error);
+    }
+
+    let mut database = match create_database_container(app, databases, request).await {
+        Ok(database) => database,
+        Err(error) => {
+            let _ = docker_service.remove_volume_if_exists(app, &volume).await;
+            return Err(error);
+        }
+    };
+
+    ConfigService::restore_organization(&mut database, exported);
+    databases
+        .write()
+        .await
+        .insert(database.id.clone(), database.clone());
+    StorageService::new()
+        .upsert_databases_in_store(app, std::slice::from_ref(&database))
+        .await?;
+
+    Ok(BundleImport {
+        database,
+        manifest: bundle.manifest.clone(),
+        renamed_from,
+        remapped_ports,
+    })
+}
+
+/// Recreate the containers of a bundle and apply its settings
+/// Containers that already exist or cannot be recreated are reported as skipped, the settings
+/// are only applied when a container was imported or the bundle has none
+#[tauri::command]
+pub async fn import_config(
+    path: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+    settings: State<'_, SettingsStore>,
+) -> Result<ImportReport, AppError> {
+    ensure_writable(&app)?;
+    let config_service = ConfigService::new();
+    let storage_service = StorageService::new();
+
+    let bundle = config_service.read_bundle(Path::new(&path))?;
+
+    let mut report = ImportReport::default();
+
+    for exported in &bundle.databases {
+        let already_managed = {
+            let db_map = databases.read().await;
+            db_map
+                .values()
+                .any(|db| db.id == exported.id || db.name == exported.name)
+        };
+        if already_managed {
+            report.skipped.push(SkippedImport {
+                name: exported.name.clone(),
+                reason: "A container with this name already exists".to_string(),
+            });
+            continue;
+        }
+
+        let request = match ConfigService::build_run_request(exported) {
+            Ok(request) => request,
+            Err(error) => {
+                report.skipped.push(SkippedImport {
+                    name: exported.name.clone(),
+                    reason: error.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let mut database = match create_database_container(&app, &databases, request).await {
+            Ok(database) => database,
+            Err(error) => {
+                report.skipped.push(SkippedImport {
+                    name: exported.name.clone(),
+                    reason: error.to_string(),
+                });
+                continue;
+            }
+        };
+
+        ConfigService::restore_organization(&mut database, exported);
+        {
+            let mut db_map = databases.write().await;
+            db_map.insert(database.id.clone(), database.clone());
+        }
+
+        // Containers exported while stopped are imported stopped
+        if !exported.is_running() {
+            let stopped = match &database.container_id {
+                Some(real_id) => match DockerService::for_database(&app, &database) {
+                    Ok(docker_service) => docker_service.stop_container(&app, real_id).await,
+                    Err(error) => Err(error),
+                },
+                None => Ok(()),
+            };
+            match stopped {
+                Ok(()) => {
+                    database.mark_stopped();
+                    databases
+                        .write()
+                        .await
+                        .insert(database.id.clone(), database.clone());
+                    EventService::new().container_status_changed(&app, &database);
+                }
+                // The container exists, keep it running and go on with the others
+                Err(error) => report.warnings.push(SkippedImport {
+                    name: database.name.clone(),
+                    reason: format!("Imported but left running: {}", error),
+                }),
+            }
+        }
+
+        storage_service
+            .upsert_databases_in_store(&app, std::slice::from_ref(&database))
+            .await?;
+
+        report.imported.push(database);
+    }
+
+    // A bundle whose containers were all skipped leaves the current settings alone
+    if bundle.databases.is_empty() || !report.imported.is_empty() {
+        storage_service
+            .save_settings_to_store(&app, &bundle.settings)
+            .await?;
+        *settings.lock().unwrap() = bundle.settings.clone();
+        report.settings_applied = true;
+    }
+
+    Ok(report)
+}
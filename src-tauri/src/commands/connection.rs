@@ -0,0 +1,62 @@
+use crate::commands::database::find_database;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, Manager, State};
+
+/// .env block with the connection credentials of a database
+#[tauri::command]
+pub async fn get_env_snippet(
+    container_id: String,
+    preset: Option<EnvPreset>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<String, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    let host = DockerService::for_database(&app, &database)?.connection_host();
+    let pooled_port =
+        CompanionService::pooled_port(&app.state::<CompanionStore>().lock().unwrap(), &database.id);
+    Ok(ConnectionService::on_host(host)
+        .with_pooled_port(pooled_port)
+        .env_snippet(&database, preset.unwrap_or_default()))
+}
+
+/// Framework presets offered when creating a database
+#[tauri::command]
+pub async fn list_framework_presets() -> Result<Vec<FrameworkPreset>, AppError> {
+    Ok(framework_presets())
+}
+
+/// Framework config for a database, the one it was created for unless `framework` is set
+#[tauri::command]
+pub async fn get_framework_snippet(
+    container_id: String,
+    framework: Option<Framework>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<String, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    let framework = framework.or(database.framework).ok_or_else(|| {
+        AppError::Validation(format!("{} was not created for a framework", database.name))
+    })?;
+    let host = DockerService::for_database(&app, &database)?.connection_host();
+    Ok(ConnectionService::on_host(host).framework_snippet(&database, framework))
+}
+
+/// External clients installed on this machine
+#[tauri::command]
+pub async fn list_database_clients(app: AppHandle) -> Result<Vec<DatabaseClient>, AppError> {
+    Ok(ClientService::new().installed(&app).await)
+}
+
+/// Open a database in an external client
+/// Returns true when the connection string was copied for the user to paste
+#[tauri::command]
+pub async fn open_in_client(
+    container_id: String,
+    client: DatabaseClient,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<bool, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    ClientService::new().open(&app, &database, client).await
+}
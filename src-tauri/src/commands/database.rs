@@ -1,6 +1,10 @@
+use crate::commands::companion::{move_companions_for_database, remove_companions_for_database};
+use crate::commands::settings::ensure_writable;
+use crate::commands::window::close_container_windows;
 use crate::services::*;
 use crate::types::*;
-use tauri::{AppHandle, State};
+use futures_util::stream::{self, StreamExt};
+use tauri::{AppHandle, Manager, State};
 
 /// Create database container from generic Docker run request
 /// This command is database-agnostic and uses the docker args built by the frontend provider
@@ -9,151 +13,492 @@ pub async fn create_container_from_docker_args(
     request: DockerRunRequest,
     app: AppHandle,
     databases: State<'_, DatabaseStore>,
-) -> Result<DatabaseContainer, String> {
-    let docker_service = DockerService::new();
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    create_database_container(&app, &databases, request).await
+}
+
+/// Check a container name against Docker's rules and the other managed databases
+/// Returns a suggested valid name in the error so the form can offer it
+#[tauri::command]
+pub async fn check_container_name(
+    name: String,
+    container_id: Option<String>,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    ensure_name_available(&databases, &name, container_id.as_deref()).await
+}
+
+/// Copy of a managed database, shared by the commands acting on one
+pub(crate) async fn find_database(
+    databases: &DatabaseStore,
+    container_id: &str,
+) -> Result<DatabaseContainer, AppError> {
+    let db_map = databases.read().await;
+    db_map
+        .get(container_id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound("Container".to_string()))
+}
+
+/// Validate `name` against the databases other than `exclude_id`
+async fn ensure_name_available(
+    databases: &State<'_, DatabaseStore>,
+    name: &str,
+    exclude_id: Option<&str>,
+) -> Result<(), AppError> {
+    let taken: Vec<String> = {
+        let db_map = databases.read().await;
+        db_map
+            .values()
+            .filter(|db| Some(db.id.as_str()) != exclude_id)
+            .map(|db| db.name.clone())
+            .collect()
+    };
+
+    validate_container_name(name, &taken)
+}
+
+/// Validate ports, credentials and version against the databases other than `exclude_id`
+async fn ensure_request_valid(
+    databases: &State<'_, DatabaseStore>,
+    request: &DockerRunRequest,
+    exclude_id: Option<&str>,
+) -> Result<(), AppError> {
+    let db_map = databases.read().await;
+    let others: Vec<&DatabaseContainer> = db_map
+        .values()
+        .filter(|db| Some(db.id.as_str()) != exclude_id)
+        .collect();
+
+    validate_run_request(request, &others)
+}
+
+/// Labels of a database container created in the active workspace
+fn management_labels(app: &AppHandle, database_id: &str, db_type: &str) -> ManagementLabels {
+    ManagementLabels {
+        database_id: database_id.to_string(),
+        db_type: db_type.to_string(),
+        workspace: app.state::<WorkspaceStore>().lock().unwrap().active.clone(),
+    }
+}
+
+/// Validate a creation request and complete its run arguments the way creation runs them
+/// Returns the provider arguments, stored to recreate the container
+async fn prepare_create_request(
+    databases: &State<'_, DatabaseStore>,
+    request: &mut DockerRunRequest,
+) -> Result<DockerRunArgs, AppError> {
+    // Nothing is created for a name Docker would reject or another database uses
+    ensure_name_available(databases, &request.name, None).await?;
+    ensure_request_valid(databases, request, None).await?;
+
+    // Ports only listen on 127.0.0.1 unless the database is exposed to the LAN
+    request
+        .docker_args
+        .bind_ports(request.metadata.expose_to_lan);
+
+    // Encoding and collation become part of the stored provider arguments
+    if let Some(locale) = &request.metadata.locale {
+        locale.apply(
+            &request.metadata.db_type,
+            &request.metadata.version,
+            &mut request.docker_args,
+        );
+    }
+
+    if let Some(region) = &request.metadata.region {
+        region.apply(&request.metadata.db_type, &mut request.docker_args);
+    }
+
+    if let Some(tuning) = &request.metadata.tuning {
+        tuning.apply(&request.metadata.db_type, &mut request.docker_args);
+    }
+
+    // User arguments come last so they override what the provider set
+    request
+        .docker_args
+        .apply_extra_args(&request.metadata.extra_args);
+
+    // Keep the provider arguments before TLS additions, they are re-applied on recreation
+    let provider_docker_args = request.docker_args.clone();
+
+    // Mount the certificates volume and enable TLS in the server arguments
+    if request.metadata.enable_tls {
+        TlsService::new().apply_tls_to_docker_args(
+            &request.name,
+            &request.metadata.db_type,
+            &mut request.docker_args,
+        )?;
+    }
+
+    Ok(provider_docker_args)
+}
+
+/// What creating a container from `request` would run, without creating anything
+/// Fails with the same validation errors creation would
+#[tauri::command]
+pub async fn preview_container_creation(
+    mut request: DockerRunRequest,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<CreationPreview, AppError> {
+    let docker_service = DockerService::for_host_id(&app, request.metadata.host.as_deref())?;
+
+    prepare_create_request(&databases, &mut request).await?;
+
+    // The preview is shown and copied, it never carries the password
+    let mut display_docker_args = request.docker_args.clone();
+    ConfigService::mask_secrets(&mut display_docker_args, PASSWORD_MASK);
+    let args = docker_service.build_database_command(
+        &request.name,
+        &display_docker_args,
+        &management_labels(&app, &request.metadata.id, &request.metadata.db_type),
+    );
+    let display_args: Vec<String> = std::iter::once("docker".to_string())
+        .chain(args.iter().cloned())
+        .collect();
+
+    // Unknown when Docker cannot list them, creation reuses any that exist
+    let existing_volumes = docker_service.list_volumes(&app).await.unwrap_or_default();
+    let volumes = request
+        .docker_args
+        .volumes
+        .iter()
+        .map(|volume| VolumePreview {
+            name: volume.name.clone(),
+            path: volume.path.clone(),
+            exists: existing_volumes.contains(&volume.name),
+        })
+        .collect();
+
+    Ok(CreationPreview {
+        command: DockerService::shell_join(&display_args),
+        args,
+        image: docker_service
+            .resolve_image(&app, &request.docker_args.image)
+            .await,
+        volumes,
+        network: DEFAULT_NETWORK.to_string(),
+        ports: request.docker_args.ports.clone(),
+    })
+}
+
+/// Create, persist and announce a database container
+/// Shared by the create command and configuration import
+pub async fn create_database_container(
+    app: &AppHandle,
+    databases: &State<'_, DatabaseStore>,
+    mut request: DockerRunRequest,
+) -> Result<DatabaseContainer, AppError> {
+    let docker_service = DockerService::for_host_id(app, request.metadata.host.as_deref())?;
     let storage_service = StorageService::new();
+    let tls_service = TlsService::new();
+    let recovery_service = RecoveryService::new();
+
+    // Containers without their own timezone or locale follow the app default
+    let defaults = app
+        .state::<SettingsStore>()
+        .lock()
+        .unwrap()
+        .default_region
+        .clone();
+    let region = request
+        .metadata
+        .region
+        .clone()
+        .unwrap_or_default()
+        .or_defaults(&defaults);
+    request.metadata.region = (!region.is_empty()).then_some(region);
+
+    let provider_docker_args = prepare_create_request(databases, &mut request).await?;
+    // Certificates are generated and mounted from this machine
+    if request.metadata.enable_tls && request.metadata.host.is_some() {
+        return Err(AppError::Validation(
+            "TLS can only be enabled for databases on this machine".to_string(),
+        ));
+    }
+
+    // Recorded before anything exists, a crash from here on is undone at the next launch
+    // Volumes that could not be listed are not recorded, they may hold data
+    let existing_volumes = docker_service.list_volumes(app).await.ok();
+    recovery_service
+        .begin_create(
+            app,
+            PendingCreate {
+                database_id: request.metadata.id.clone(),
+                name: request.name.clone(),
+                volumes: request
+                    .docker_args
+                    .volumes
+                    .iter()
+                    .map(|volume| volume.name.clone())
+                    .filter(|name| {
+                        existing_volumes
+                            .as_ref()
+                            .is_some_and(|existing| !existing.contains(name))
+                    })
+                    .collect(),
+                started_at: chrono::Utc::now().to_rfc3339(),
+                host: request.metadata.host.clone(),
+            },
+        )
+        .await?;
 
     // Create volumes if needed
     for volume in &request.docker_args.volumes {
         docker_service
-            .create_volume_if_needed(&app, &volume.name)
+            .create_volume_if_needed(app, &volume.name)
             .await?;
     }
 
+    // Generate certificates before the server starts reading them
+    let ca_cert_path = if request.metadata.enable_tls {
+        match tls_service
+            .generate_certificates(
+                app,
+                &request.name,
+                &request.metadata.db_type,
+                &request.docker_args.image,
+            )
+            .await
+        {
+            Ok(path) => Some(path),
+            Err(error) => {
+                for volume in &request.docker_args.volumes {
+                    let _ = docker_service
+                        .remove_volume_if_exists(app, &volume.name)
+                        .await;
+                }
+                let _ = recovery_service
+                    .finish_create(app, &request.metadata.id)
+                    .await;
+                return Err(error);
+            }
+        }
+    } else {
+        None
+    };
+
     // Build Docker command from generic args
-    let docker_args =
-        docker_service.build_docker_command_from_args(&request.name, &request.docker_args);
+    let docker_args = docker_service.build_database_command(
+        &request.name,
+        &request.docker_args,
+        &management_labels(app, &request.metadata.id, &request.metadata.db_type),
+    );
 
     // Execute Docker run command
-    let real_container_id = match docker_service.run_container(&app, &docker_args).await {
+    let real_container_id = match docker_service.run_container(app, &docker_args).await {
         Ok(container_id) => container_id,
         Err(error) => {
             // Cleanup resources on error
             let _ = docker_service
-                .force_remove_container_by_name(&app, &request.name)
+                .force_remove_container_by_name(app, &request.name)
                 .await;
 
             // Cleanup volumes
             for volume in &request.docker_args.volumes {
                 let _ = docker_service
-                    .remove_volume_if_exists(&app, &volume.name)
+                    .remove_volume_if_exists(app, &volume.name)
                     .await;
             }
 
-            // Check if it's a port already in use error
-            if error.contains("port is already allocated") || error.contains("Bind for") {
-                let port_error = CreateContainerError {
-                    error_type: "PORT_IN_USE".to_string(),
-                    message: format!("Port {} is already in use", request.metadata.port),
-                    port: Some(request.metadata.port),
-                    details: Some(
-                        "You can change the port in the configuration and try again.".to_string(),
-                    ),
-                };
-                return Err(serde_json::to_string(&port_error)
-                    .unwrap_or_else(|_| "Port in use error".to_string()));
-            }
-
-            // Check if it's a container name already exists error
-            if error.contains("name is already in use") || error.contains("already exists") {
-                let name_error = CreateContainerError {
-                    error_type: "NAME_IN_USE".to_string(),
-                    message: format!(
-                        "A container with the name '{}' already exists",
-                        request.name
-                    ),
-                    port: None,
-                    details: Some("Change the container name and try again.".to_string()),
-                };
-                return Err(serde_json::to_string(&name_error)
-                    .unwrap_or_else(|_| "Name in use error".to_string()));
-            }
+            let _ = recovery_service
+                .finish_create(app, &request.metadata.id)
+                .await;
 
-            // Generic Docker error
-            let generic_error = CreateContainerError {
-                error_type: "DOCKER_ERROR".to_string(),
-                message: "Error creating container".to_string(),
-                port: None,
-                details: Some(error.to_string()),
-            };
-            return Err(serde_json::to_string(&generic_error)
-                .unwrap_or_else(|_| format!("Docker command failed: {}", error)));
+            // Map port/name conflicts to specific errors
+            return Err(error.for_run_request(&request.name, request.metadata.port));
         }
     };
 
-    // Create database object using metadata
-    let database = DatabaseContainer {
-        id: request.metadata.id.clone(),
-        name: request.name.clone(),
-        db_type: request.metadata.db_type,
-        version: request.metadata.version,
-        status: "running".to_string(),
-        port: request.metadata.port,
-        created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
-        max_connections: request.metadata.max_connections.unwrap_or(100),
-        container_id: Some(real_container_id.clone()),
-        stored_password: Some(request.metadata.password.clone()),
-        stored_username: request.metadata.username.clone(),
-        stored_database_name: request.metadata.database_name.clone(),
-        stored_persist_data: request.metadata.persist_data,
-        stored_enable_auth: request.metadata.enable_auth,
+    // Create database object using metadata, starting until the engine accepts connections
+    let mut database = DatabaseContainer {
+        ca_cert_path,
+        stored_docker_args: Some(provider_docker_args),
+        status: "starting".to_string(),
+        setup_warnings: run_request_warnings(&request),
+        ..DatabaseContainer::from_run_request(&request, real_container_id.clone())
     };
 
     // Store in memory
     databases
-        .lock()
-        .unwrap()
+        .write()
+        .await
         .insert(request.metadata.id.clone(), database.clone());
 
-    // Persist to store
-    let db_map = {
-        let map = databases.lock().unwrap();
-        map.clone()
-    };
-
     // If saving to store fails, cleanup the created container
-    if let Err(store_error) = storage_service.save_databases_to_store(&app, &db_map).await {
+    if let Err(store_error) = storage_service
+        .upsert_databases_in_store(app, std::slice::from_ref(&database))
+        .await
+    {
         // Remove from memory
-        databases.lock().unwrap().remove(&request.metadata.id);
+        databases.write().await.remove(&request.metadata.id);
 
         // Cleanup Docker resources
         let _ = docker_service
-            .remove_container(&app, &real_container_id)
+            .remove_container(app, &real_container_id)
             .await;
 
         // Cleanup volumes
         for volume in &request.docker_args.volumes {
             let _ = docker_service
-                .remove_volume_if_exists(&app, &volume.name)
+                .remove_volume_if_exists(app, &volume.name)
                 .await;
         }
+        let _ = recovery_service
+            .finish_create(app, &request.metadata.id)
+            .await;
+
+        return Err(store_error);
+    }
+
+    // Saved, from here on the entry itself tracks the container
+    if let Err(e) = recovery_service
+        .finish_create(app, &request.metadata.id)
+        .await
+    {
+        EventService::new().background_error(
+            app,
+            "recovery",
+            format!(
+                "Failed to clear pending creation of {}: {}",
+                database.name, e
+            ),
+        );
+    }
 
-        return Err(format!("Error saving configuration: {}", store_error));
+    EventService::new().container_created(app, &database);
+
+    // MySQL and friends refuse connections while their init scripts run
+    // A failed probe leaves the container in place with the state Docker reports for it
+    let ready = ReadinessService::new()
+        .wait_until_ready(app, &database)
+        .await;
+    database.status = match &ready {
+        Ok(()) => "running".to_string(),
+        Err(error) => {
+            database
+                .setup_warnings
+                .push(format!("{} did not become ready: {}", database.name, error));
+            let running = docker_service
+                .inspect_containers(app, std::slice::from_ref(&database.name))
+                .await
+                .ok()
+                .and_then(|states| states.get(&database.name).map(|state| state.running));
+            match running {
+                Some(true) => "running".to_string(),
+                Some(false) => "exited".to_string(),
+                None => "stopped".to_string(),
+            }
+        }
+    };
+    if ready.is_ok() && !request.metadata.post_create_sql.is_empty() {
+        if let Err(error) = SqlService::new()
+            .run_post_create(app, &database, &request.metadata.post_create_sql)
+            .await
+        {
+            database.setup_warnings.push(format!(
+                "Post-create SQL failed on {}: {}",
+                database.name, error
+            ));
+        }
+    }
+    if let Some(db) = databases.write().await.get_mut(&database.id) {
+        db.status = database.status.clone();
+        db.setup_warnings = database.setup_warnings.clone();
     }
+    storage_service
+        .upsert_databases_in_store(app, std::slice::from_ref(&database))
+        .await?;
+    EventService::new().container_status_changed(app, &database);
 
     Ok(database)
 }
 
+/// Reject an update another database conflicts with or the engine would refuse
+async fn ensure_update_valid(
+    databases: &State<'_, DatabaseStore>,
+    container: &DatabaseContainer,
+    request: &DockerRunRequest,
+) -> Result<(), AppError> {
+    if request.name != container.name {
+        ensure_name_available(databases, &request.name, Some(&container.id)).await?;
+    }
+    let mut effective_request = request.clone();
+    // An empty password keeps the stored one
+    if effective_request.metadata.password.is_empty() {
+        effective_request.metadata.password = container.stored_password.clone().unwrap_or_default();
+    }
+    ensure_request_valid(databases, &effective_request, Some(&container.id)).await
+}
+
+/// Which changes of an update recreate the container, lose data or apply live
+/// Nothing is changed, the request is validated the way the update validates it
+#[tauri::command]
+pub async fn preview_update(
+    container_id: String,
+    request: DockerRunRequest,
+    databases: State<'_, DatabaseStore>,
+) -> Result<UpdatePlan, AppError> {
+    let container = find_database(&databases, &container_id).await?;
+
+    ensure_update_valid(&databases, &container, &request).await?;
+    Ok(UpdatePlan::new(&container, &request))
+}
+
 /// Update database container from generic Docker run request
 /// This command is database-agnostic and uses the docker args built by the frontend provider
+/// An update that deletes data fails with DATA_LOSS unless `force` confirms it
 #[tauri::command]
 pub async fn update_container_from_docker_args(
     container_id: String,
-    request: DockerRunRequest,
+    mut request: DockerRunRequest,
+    force: Option<bool>,
     app: AppHandle,
     databases: State<'_, DatabaseStore>,
-) -> Result<DatabaseContainer, String> {
-    let docker_service = DockerService::new();
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
     let storage_service = StorageService::new();
+    let tls_service = TlsService::new();
 
     // Get current container info
-    let mut container = {
-        let db_map = databases.lock().unwrap();
-        db_map
-            .get(&container_id)
-            .cloned()
-            .ok_or("Container not found")?
-    };
+    let mut container = find_database(&databases, &container_id).await?;
+    // A database stays on the host it was created on
+    let docker_service = DockerService::for_database(&app, &container)?;
+    request.metadata.host = container.host.clone();
+
+    // Ports only listen on 127.0.0.1 unless the database is exposed to the LAN
+    request
+        .docker_args
+        .bind_ports(request.metadata.expose_to_lan);
+
+    // The locale was fixed when the data directory was initialized, a recreation keeps it
+    request.metadata.locale = container.locale.clone();
+    if let Some(locale) = &container.locale {
+        locale.apply(
+            &container.db_type,
+            &request.metadata.version,
+            &mut request.docker_args,
+        );
+    }
+
+    if let Some(region) = &request.metadata.region {
+        region.apply(&container.db_type, &mut request.docker_args);
+    }
+
+    // Re-tuning comes as new settings measured on the host again
+    if let Some(tuning) = &request.metadata.tuning {
+        tuning.apply(&container.db_type, &mut request.docker_args);
+    }
+
+    // User arguments come last so they override what the provider set
+    request
+        .docker_args
+        .apply_extra_args(&request.metadata.extra_args);
+
+    // Keep the provider arguments before TLS additions, they are re-applied on recreation
+    let provider_docker_args = request.docker_args.clone();
 
     // Capture previous name for later cleanup
     let previous_name = container.name.clone();
@@ -162,10 +507,42 @@ pub async fn update_container_from_docker_args(
     let original_status = container.status.clone();
 
     // Determine if we need to recreate the container
-    let name_changed = request.name != container.name;
-    let port_changed = request.metadata.port != container.port;
-    let persist_data_changed = request.metadata.persist_data != container.stored_persist_data;
-    let needs_recreation = name_changed || port_changed || persist_data_changed;
+    let plan = UpdatePlan::new(&container, &request);
+    let needs_recreation = plan.needs_recreation;
+    let name_changed = plan.changes(UpdateField::Name);
+    let tls_changed = plan.changes(UpdateField::EnableTls);
+
+    // The old container is removed on recreation, reject bad input before that
+    ensure_update_valid(&databases, &container, &request).await?;
+
+    if !force.unwrap_or(false) {
+        if let Some(error) = plan.data_loss_error(&container) {
+            return Err(error);
+        }
+    }
+
+    // Init variables are ignored once the data directory exists, the engine is told directly
+    // Done on the old container, the data volume carries the change over a recreation
+    let mut applied_credentials = None;
+    if plan.effect(UpdateField::Credentials) == Some(UpdateEffect::Live) {
+        if plan.requires_running {
+            return Err(AppError::Validation(format!(
+                "Start {} to change its credentials",
+                container.name
+            )));
+        }
+        let current_credentials = DatabaseCredentials::of(&container);
+        let requested_credentials =
+            DatabaseCredentials::requested(&request.metadata, &current_credentials);
+        CredentialService::new()
+            .apply(&app, &container, &requested_credentials)
+            .await?;
+        // Stored explicitly, a renamed PostgreSQL user would otherwise imply its own database
+        if !requested_credentials.database.is_empty() {
+            request.metadata.database_name = Some(requested_credentials.database.clone());
+        }
+        applied_credentials = Some(requested_credentials);
+    }
 
     // Track volumes for cleanup - define outside the if block for later access
     let old_volumes: Vec<String> = if container.stored_persist_data {
@@ -177,187 +554,253 @@ pub async fn update_container_from_docker_args(
     // Track if we need to cleanup old volumes after successful update
     let should_cleanup_old_volumes = container.stored_persist_data && !request.metadata.persist_data;
 
-    if needs_recreation {
-        // Remove old container
-        if let Some(old_id) = &container.container_id {
-            docker_service.remove_container(&app, old_id).await?;
-        }
+    // Old certificates are dropped once TLS is disabled or moved to a new name
+    let should_cleanup_old_certs =
+        container.stored_enable_tls && (!request.metadata.enable_tls || name_changed);
 
-        // Handle volume migration if needed
-        let new_volumes = &request.docker_args.volumes;
+    // Volumes the previous container uses, never cleaned up when the update fails
+    let kept_volumes = replaced_volumes(&container);
 
-        // Track if migration occurred for cleanup purposes
-        let volume_migrated =
-            name_changed && container.stored_persist_data && request.metadata.persist_data;
+    // A failed update puts the previous container and its credentials back
+    let original_container = container.clone();
+    let new_name = request.name.clone();
+    let mut replaced_id = None;
+    let mut generating_certs = false;
+    let outcome: Result<(), AppError> = async {
+        if needs_recreation {
+            // The old container is set aside under another name until the new one is saved
+            replaced_id = set_aside(&app, &docker_service, &container).await?;
+
+            // Mount certificates and regenerate them only when the volume is new
+            if request.metadata.enable_tls {
+                tls_service.apply_tls_to_docker_args(
+                    &request.name,
+                    &request.metadata.db_type,
+                    &mut request.docker_args,
+                )?;
+
+                if tls_changed || name_changed || container.ca_cert_path.is_none() {
+                    generating_certs = true;
+                    container.ca_cert_path = Some(
+                        tls_service
+                            .generate_certificates(
+                                &app,
+                                &request.name,
+                                &request.metadata.db_type,
+                                &request.docker_args.image,
+                            )
+                            .await?,
+                    );
+                }
+            } else {
+                container.ca_cert_path = None;
+            }
 
-        // Case 1: Name changed AND has persistent data -> migrate volume
-        if volume_migrated {
-            let old_volume_name = format!("{}-data", container.name);
-            let new_volume_name = format!("{}-data", request.name);
+            // Handle volume migration if needed
+            let new_volumes = &request.docker_args.volumes;
 
-            // Get data path from the provider's volume configuration
-            let data_path = if let Some(vol) = new_volumes.first() {
-                vol.path.as_str()
-            } else {
-                "/data" // fallback
-            };
+            // Track if migration occurred for cleanup purposes
+            let volume_migrated =
+                name_changed && container.stored_persist_data && request.metadata.persist_data;
+
+            // Case 1: Name changed AND has persistent data -> migrate volume
+            if volume_migrated {
+                let old_volume_name = format!("{}-data", container.name);
+                let new_volume_name = format!("{}-data", request.name);
+
+                // Get data path from the provider's volume configuration
+                let data_path = if let Some(vol) = new_volumes.first() {
+                    vol.path.as_str()
+                } else {
+                    "/data" // fallback
+                };
 
-            docker_service
-                .migrate_volume_data(&app, &old_volume_name, &new_volume_name, data_path)
-                .await?;
-        }
-        // Case 2: Enabling persistent data -> create new volume
-        else if !container.stored_persist_data && request.metadata.persist_data {
-            for volume in new_volumes {
                 docker_service
-                    .create_volume_if_needed(&app, &volume.name)
+                    .migrate_volume_data(&app, &old_volume_name, &new_volume_name, data_path)
                     .await?;
             }
-        }
-        // Case 3: Disabling persistent data -> defer cleanup until after success
-        // (old volumes will be cleaned up after successful store save to prevent data loss)
-        // Case 4: Name changed but NO persistent data -> just ensure new volumes exist if needed
-        else if name_changed && request.metadata.persist_data {
-            for volume in new_volumes {
-                docker_service
-                    .create_volume_if_needed(&app, &volume.name)
-                    .await?;
+            // Case 2: Enabling persistent data -> create new volume
+            else if !container.stored_persist_data && request.metadata.persist_data {
+                for volume in new_volumes {
+                    docker_service
+                        .create_volume_if_needed(&app, &volume.name)
+                        .await?;
+                }
             }
-        }
-
-        // Build Docker command from generic args
-        let docker_args =
-            docker_service.build_docker_command_from_args(&request.name, &request.docker_args);
-
-        // Execute Docker run command
-        let real_container_id = match docker_service.run_container(&app, &docker_args).await {
-            Ok(container_id) => container_id,
-            Err(error) => {
-                // Cleanup resources on error
-                let _ = docker_service
-                    .force_remove_container_by_name(&app, &request.name)
-                    .await;
-
-                // Cleanup new volumes if they were created
-                // Note: If volume migration occurred, the old volume still exists with original data
+            // Case 3: Disabling persistent data -> defer cleanup until after success
+            // (old volumes will be cleaned up after successful store save to prevent data loss)
+            // Case 4: Name changed but NO persistent data -> just ensure new volumes exist if needed
+            else if name_changed && request.metadata.persist_data {
                 for volume in new_volumes {
-                    let _ = docker_service
-                        .remove_volume_if_exists(&app, &volume.name)
-                        .await;
+                    docker_service
+                        .create_volume_if_needed(&app, &volume.name)
+                        .await?;
                 }
+            }
 
-                // If migration occurred, note that old volume is preserved with original data
-                // User can retry the update operation without data loss
-
-                // Check if it's a port already in use error
-                if error.contains("port is already allocated") || error.contains("Bind for") {
-                    let port_error = CreateContainerError {
-                        error_type: "PORT_IN_USE".to_string(),
-                        message: format!("Port {} is already in use", request.metadata.port),
-                        port: Some(request.metadata.port),
-                        details: Some(
-                            "You can change the port in the configuration and try again."
-                                .to_string(),
-                        ),
-                    };
-                    return Err(serde_json::to_string(&port_error)
-                        .unwrap_or_else(|_| "Port in use error".to_string()));
-                }
+            // Build Docker command from generic args
+            let docker_args = docker_service.build_database_command(
+                &request.name,
+                &request.docker_args,
+                &management_labels(&app, &container.id, &container.db_type),
+            );
+
+            // Execute Docker run command
+            let real_container_id = match docker_service.run_container(&app, &docker_args).await {
+                Ok(container_id) => container_id,
+                Err(error) => {
+                    // Cleanup resources on error
+                    let _ = docker_service
+                        .force_remove_container_by_name(&app, &request.name)
+                        .await;
 
-                // Check if it's a container name already exists error
-                if error.contains("name is already in use") || error.contains("already exists") {
-                    let name_error = CreateContainerError {
-                        error_type: "NAME_IN_USE".to_string(),
-                        message: format!(
-                            "A container with the name '{}' already exists",
-                            request.name
-                        ),
-                        port: None,
-                        details: Some("Change the container name and try again.".to_string()),
-                    };
-                    return Err(serde_json::to_string(&name_error)
-                        .unwrap_or_else(|_| "Name in use error".to_string()));
+                    // Cleanup new volumes if they were created
+                    // Note: If volume migration occurred, the old volume still exists with original data
+                    for volume in new_volumes {
+                        if !kept_volumes.contains(&volume.name) {
+                            let _ = docker_service
+                                .remove_volume_if_exists(&app, &volume.name)
+                                .await;
+                        }
+                    }
+
+                    // If migration occurred, note that old volume is preserved with original data
+                    // User can retry the update operation without data loss
+
+                    // Map port/name conflicts to specific errors
+                    return Err(error.for_run_request(&request.name, request.metadata.port));
                 }
+            };
 
-                // Generic Docker error
-                let generic_error = CreateContainerError {
-                    error_type: "DOCKER_ERROR".to_string(),
-                    message: "Error updating container".to_string(),
-                    port: None,
-                    details: Some(error.to_string()),
-                };
-                return Err(serde_json::to_string(&generic_error)
-                    .unwrap_or_else(|_| format!("Docker command failed: {}", error)));
+            // Rejoin the companion network when the container keeps its name
+            if !name_changed {
+                let _ = docker_service
+                    .connect_container_to_network(
+                        &app,
+                        &CompanionService::network_name(&request.name),
+                        &request.name,
+                    )
+                    .await;
             }
-        };
 
-        // Update container info with new values
-        container.name = request.name.clone();
-        container.port = request.metadata.port;
-        container.version = request.metadata.version;
-        container.container_id = Some(real_container_id.clone());
-        container.stored_persist_data = request.metadata.persist_data;
-        container.stored_enable_auth = request.metadata.enable_auth;
+            // Update container info with new values
+            container.name = request.name.clone();
+            container.port = request.metadata.port;
+            container.version = request.metadata.version;
+            container.container_id = Some(real_container_id.clone());
+            container.stored_persist_data = request.metadata.persist_data;
+            container.stored_enable_auth = request.metadata.enable_auth;
+            container.stored_enable_tls = request.metadata.enable_tls;
+            container.stored_expose_to_lan = request.metadata.expose_to_lan;
         
-        // If the original container was stopped, stop the new one too
-        if original_status != "running" {
-            docker_service.stop_container(&app, &real_container_id).await?;
-            container.status = original_status;
+            // If the original container was stopped, stop the new one too
+            if !is_running_status(&original_status) {
+                docker_service.stop_container(&app, &real_container_id).await?;
+                container.status = original_status;
+            } else {
+                container.status = "running".to_string();
+                container.last_started_at = Some(chrono::Utc::now().to_rfc3339());
+            }
+
+            // Only update password if a non-empty value is provided
+            if !request.metadata.password.is_empty() {
+                container.stored_password = Some(request.metadata.password.clone());
+            }
+
+            container.stored_username = request.metadata.username;
+            container.stored_database_name = request.metadata.database_name;
+
+            if let Some(max_conn) = request.metadata.max_connections {
+                container.max_connections = max_conn;
+            }
         } else {
-            container.status = "running".to_string();
-        }
+            // For non-recreating changes, just update the metadata
+            // Credentials were already changed in the running engine
+            if !request.metadata.password.is_empty() {
+                container.stored_password = Some(request.metadata.password.clone());
+            }
+            container.stored_username = request.metadata.username.clone();
+            container.stored_database_name = request.metadata.database_name.clone();
 
-        // Only update password if a non-empty value is provided
-        if !request.metadata.password.is_empty() {
-            container.stored_password = Some(request.metadata.password.clone());
+            if let Some(max_conn) = request.metadata.max_connections {
+                container.max_connections = max_conn;
+            }
         }
 
-        container.stored_username = request.metadata.username;
-        container.stored_database_name = request.metadata.database_name;
+        container.extra_args = request.metadata.extra_args.clone();
+        container.region = request.metadata.region.clone();
+        container.tuning = request.metadata.tuning;
+        container.stored_docker_args = Some(provider_docker_args);
 
-        if let Some(max_conn) = request.metadata.max_connections {
-            container.max_connections = max_conn;
+        // The certificates of the previous name are removed below, their path goes with them
+        if should_cleanup_old_certs && container.name == previous_name {
+            container.ca_cert_path = None;
         }
-    } else {
-        // For non-recreating changes, just update the metadata
-        // (currently only max_connections would fall here)
-        if let Some(max_conn) = request.metadata.max_connections {
-            container.max_connections = max_conn;
-        }
-    }
 
-    // Update in memory store
-    {
-        let mut db_map = databases.lock().unwrap();
-        db_map.insert(container.id.clone(), container.clone());
-    }
-
-    // Save to persistent store
-    let db_map = {
-        let map = databases.lock().unwrap();
-        map.clone()
-    };
+        // Update in memory store
+        {
+            let mut db_map = databases.write().await;
+            db_map.insert(container.id.clone(), container.clone());
+        }
 
-    // If saving to store fails, rollback the changes (align with create_container behavior)
-    if let Err(store_error) = storage_service.save_databases_to_store(&app, &db_map).await {
-        // Remove from memory store
-        databases.lock().unwrap().remove(&container_id);
+        // If saving to store fails, rollback the changes (align with create_container behavior)
+        if let Err(store_error) = storage_service
+            .upsert_databases_in_store(&app, std::slice::from_ref(&container))
+            .await
+        {
+            // Cleanup new Docker resources if container was recreated
+            if needs_recreation {
+                if let Some(new_id) = &container.container_id {
+                    let _ = docker_service.remove_container(&app, new_id).await;
+                }
 
-        // Cleanup new Docker resources if container was recreated
-        if needs_recreation {
-            if let Some(new_id) = &container.container_id {
-                let _ = docker_service.remove_container(&app, new_id).await;
+                // Cleanup new volumes
+                for volume in &request.docker_args.volumes {
+                    if !kept_volumes.contains(&volume.name) {
+                        let _ = docker_service
+                            .remove_volume_if_exists(&app, &volume.name)
+                            .await;
+                    }
+                }
             }
 
-            // Cleanup new volumes
-            for volume in &request.docker_args.volumes {
-                let _ = docker_service
-                    .remove_volume_if_exists(&app, &volume.name)
-                    .await;
+            return Err(store_error);
+        }
+
+        Ok(())
+    }
+    .await;
+    if let Err(error) = outcome {
+        if let Some(old_id) = &replaced_id {
+            let _ = docker_service
+                .force_remove_container_by_name(&app, &new_name)
+                .await;
+            let _ = docker_service
+                .rename_container(&app, old_id, &original_container.name)
+                .await;
+            if is_running_status(&original_container.status) {
+                let _ = docker_service.start_container(&app, old_id).await;
             }
         }
+        // Certificates made for a new name or a container that had none are not used by anyone
+        if generating_certs && (name_changed || !original_container.stored_enable_tls) {
+            let _ = tls_service.remove_certificates(&app, &new_name).await;
+        }
+        databases
+            .write()
+            .await
+            .insert(original_container.id.clone(), original_container.clone());
+        if let Some(applied) = &applied_credentials {
+            let _ = CredentialService::new()
+                .revert(&app, &original_container, applied)
+                .await;
+        }
+        return Err(error);
+    }
 
-        return Err(format!("Error saving configuration: {}", store_error));
+    // The previous container is only dropped once the new one is saved
+    if let Some(old_id) = &replaced_id {
+        let _ = docker_service.remove_container(&app, old_id).await;
     }
 
     // After successfully saving to store, cleanup old volume if migration occurred
@@ -377,53 +820,230 @@ pub async fn update_container_from_docker_args(
         }
     }
 
+    // Cleanup certificates that no longer belong to the container
+    if should_cleanup_old_certs {
+        let _ = tls_service.remove_certificates(&app, &previous_name).await;
+    }
+
+    EventService::new().container_updated(&app, &container);
+
+    // Companions still point at the previous name and network
+    if name_changed {
+        let companions = app.state::<CompanionStore>();
+        move_companions_for_database(&app, &companions, &previous_name, &container)
+            .await
+            .map_err(|error| {
+                AppError::Internal(format!(
+                    "{} was renamed but its companions could not be moved: {}",
+                    container.name, error
+                ))
+            })?;
+    }
+
     Ok(container)
 }
 
+/// Name an old container is kept under while its replacement is created
+fn replaced_name(name: &str) -> String {
+    format!("{}-ddm-replaced", name)
+}
+
+/// Stop a container and move it out of the way of its replacement
+/// Returns its id, none when Docker no longer has it, a container that cannot be moved is restarted
+async fn set_aside(
+    app: &AppHandle,
+    docker_service: &DockerService,
+    container: &DatabaseContainer,
+) -> Result<Option<String>, AppError> {
+    let Some(old_id) = &container.container_id else {
+        return Ok(None);
+    };
+
+    let _ = docker_service
+        .stop_container_with_timeout(app, old_id, container.stop_timeout)
+        .await;
+    match docker_service
+        .rename_container(app, old_id, &replaced_name(&container.name))
+        .await
+    {
+        Ok(true) => Ok(Some(old_id.clone())),
+        Ok(false) => Ok(None),
+        Err(error) => {
+            if container.is_running() {
+                let _ = docker_service.start_container(app, old_id).await;
+            }
+            Err(error)
+        }
+    }
+}
+
+/// Volumes a container mounts, its data and certificates included
+fn replaced_volumes(container: &DatabaseContainer) -> Vec<String> {
+    let mut volumes: Vec<String> = container
+        .stored_docker_args
+        .iter()
+        .flat_map(|args| args.volumes.iter().map(|volume| volume.name.clone()))
+        .collect();
+    if container.stored_persist_data {
+        volumes.push(format!("{}-data", container.name));
+    }
+    if container.stored_enable_tls {
+        volumes.push(TlsService::certs_volume_name(&container.name));
+    }
+    volumes
+}
+
+/// Live environment of a container compared with its stored configuration
+/// Flags variables changed outside the app or left behind by an incomplete update
 #[tauri::command]
-pub async fn get_all_databases(
+pub async fn get_env_drift(
+    container_id: String,
     app: AppHandle,
     databases: State<'_, DatabaseStore>,
-) -> Result<Vec<DatabaseContainer>, String> {
-    let docker_service = DockerService::new();
-    let storage_service = StorageService::new();
+) -> Result<EnvDriftReport, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    EnvDriftService::new().inspect(&app, &database).await
+}
 
-    // Load from store first
-    let loaded_databases = storage_service.load_databases_from_store(&app).await?;
+/// Recreate a container from its stored configuration, undoing drift
+/// The data volume is reused as is and the container keeps its running state
+#[tauri::command]
+pub async fn reapply_container_config(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+
+    let mut container = find_database(&databases, &container_id).await?;
+    let docker_service = DockerService::for_database(&app, &container)?;
+    let mut docker_args = container.stored_docker_args.clone().ok_or_else(|| {
+        AppError::Validation(format!(
+            "'{}' was created before its Docker arguments were stored, edit and save it first",
+            container.name
+        ))
+    })?;
+    if !container.stored_persist_data {
+        return Err(AppError::Validation(format!(
+            "{} keeps its data inside the container, recreating it would lose the data",
+            container.name
+        )));
+    }
 
-    // Update in-memory store
-    {
-        let mut db_map = databases.lock().unwrap();
-        *db_map = loaded_databases;
+    // Certificates stay in their volume, only the mount and server arguments are added
+    if container.stored_enable_tls {
+        TlsService::new().apply_tls_to_docker_args(
+            &container.name,
+            &container.db_type,
+            &mut docker_args,
+        )?;
     }
 
-    // Sync with Docker to get real status
-    let mut container_map = {
-        let db_map = databases.lock().unwrap();
-        db_map.clone()
-    };
-    docker_service
-        .sync_containers_with_docker(&app, &mut container_map)
-        .await?;
+    // The old container is set aside under another name until the new one is saved
+    let original_container = container.clone();
+    let replaced_id = set_aside(&app, &docker_service, &container).await?;
+
+    let outcome: Result<(), AppError> = async {
+        let run_args = docker_service.build_database_command(
+            &container.name,
+            &docker_args,
+            &management_labels(&app, &container.id, &container.db_type),
+        );
+        let real_container_id = docker_service
+            .run_container(&app, &run_args)
+            .await
+            .map_err(|error| error.for_run_request(&container.name, container.port))?;
+        container.container_id = Some(real_container_id.clone());
+        let _ = docker_service
+            .connect_container_to_network(
+                &app,
+                &CompanionService::network_name(&container.name),
+                &container.name,
+            )
+            .await;
 
-    // Update the database store with synced data
-    {
-        let mut db_map = databases.lock().unwrap();
-        *db_map = container_map;
+        if !container.is_running() {
+            docker_service
+                .stop_container(&app, &real_container_id)
+                .await?;
+        } else {
+            container.last_started_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+
+        databases
+            .write()
+            .await
+            .insert(container.id.clone(), container.clone());
+        StorageService::new()
+            .upsert_databases_in_store(&app, std::slice::from_ref(&container))
+            .await
     }
+    .await;
 
-    // Save updated state and return results
-    let (db_map_clone, result) = {
-        let db_map = databases.lock().unwrap();
-        let clone = db_map.clone();
-        let result = db_map.values().cloned().collect();
-        (clone, result)
-    };
-    storage_service
-        .save_databases_to_store(&app, &db_map_clone)
-        .await?;
+    // Put the previous container back in place of the one that could not be set up
+    if let Err(error) = outcome {
+        let _ = docker_service
+            .force_remove_container_by_name(&app, &original_container.name)
+            .await;
+        if let Some(old_id) = &replaced_id {
+            let _ = docker_service
+                .rename_container(&app, old_id, &original_container.name)
+                .await;
+            if original_container.is_running() {
+                let _ = docker_service.start_container(&app, old_id).await;
+            }
+        }
+        databases
+            .write()
+            .await
+            .insert(original_container.id.clone(), original_container);
+        return Err(error);
+    }
+
+    if let Some(old_id) = &replaced_id {
+        let _ = docker_service.remove_container(&app, old_id).await;
+    }
+
+    EventService::new().container_updated(&app, &container);
+    Ok(container)
+}
 
-    Ok(result)
+/// List managed databases from memory
+/// Docker reconciliation happens in the background sync worker
+#[tauri::command]
+pub async fn get_all_databases(
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<DatabaseContainer>, AppError> {
+    let db_map = databases.read().await;
+    Ok(db_map.values().cloned().collect())
+}
+
+/// Stored record of a database with its uptime, restart count and why it last exited
+#[tauri::command]
+pub async fn get_container_details(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<ContainerDetails, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    let uptime = UptimeService::new().report(&app, &database).await?;
+    let lan_warning =
+        lan_exposure_warning(database.stored_expose_to_lan, database.stored_enable_auth);
+    let tunnel_service = TunnelService::new();
+    let tunnels = StorageService::new()
+        .load_tunnels_from_store(&app)
+        .await?
+        .iter()
+        .filter(|tunnel| tunnel.database_id == database.id)
+        .map(|tunnel| tunnel_service.status(&app, tunnel))
+        .collect();
+
+    Ok(ContainerDetails {
+        database,
+        uptime,
+        lan_warning,
+        tunnels,
+    })
 }
 
 #[tauri::command]
@@ -431,132 +1051,520 @@ pub async fn start_container(
     container_id: String,
     app: AppHandle,
     databases: State<'_, DatabaseStore>,
-) -> Result<(), String> {
-    let docker_service = DockerService::new();
-    let storage_service = StorageService::new();
+) -> Result<(), AppError> {
+    start_database(&app, &databases, &container_id).await?;
+    save_and_announce_status(&app, &databases, &[container_id]).await
+}
 
-    // Get container info
-    let real_container_id = {
-        let db_map = databases.lock().unwrap();
-        db_map
-            .values()
-            .find(|db| db.id == container_id)
-            .and_then(|db| db.container_id.as_ref())
-            .cloned()
-            .ok_or("Container not found")?
-    };
+#[tauri::command]
+pub async fn stop_container(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    stop_database(&app, &databases, &container_id).await?;
+    save_and_announce_status(&app, &databases, &[container_id]).await
+}
 
+/// Start a container and update its status in memory, the caller persists
+async fn start_database(
+    app: &AppHandle,
+    databases: &State<'_, DatabaseStore>,
+    container_id: &str,
+) -> Result<(), AppError> {
+    let database = find_database(databases, container_id).await?;
+    let real_container_id = database
+        .container_id
+        .as_ref()
+        .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+
+    let docker_service = DockerService::for_database(app, &database)?;
     docker_service
-        .start_container(&app, &real_container_id)
-        .await?;
+        .start_container(app, real_container_id)
+        .await
+        .map_err(|error| docker_service.host_error(error))?;
+
+    let mut db_map = databases.write().await;
+    if let Some(db) = db_map.values_mut().find(|db| db.id == container_id) {
+        db.status = "running".to_string();
+        db.last_started_at = Some(chrono::Utc::now().to_rfc3339());
+    }
 
-    // Update status
-    {
-        let mut db_map = databases.lock().unwrap();
-        if let Some(db) = db_map.values_mut().find(|db| db.id == container_id) {
-            db.status = "running".to_string();
-        }
+    Ok(())
+}
+
+/// Stop a container and update its status in memory, the caller persists
+async fn stop_database(
+    app: &AppHandle,
+    databases: &State<'_, DatabaseStore>,
+    container_id: &str,
+) -> Result<(), AppError> {
+    let database = find_database(databases, container_id).await?;
+
+    GracefulStopService::new().stop(app, &database).await?;
+
+    let mut db_map = databases.write().await;
+    if let Some(db) = db_map.values_mut().find(|db| db.id == container_id) {
+        db.mark_stopped();
     }
 
-    let db_map = {
-        let map = databases.lock().unwrap();
-        map.clone()
+    Ok(())
+}
+
+/// Persist the databases once and emit a status change for each changed container
+async fn save_and_announce_status(
+    app: &AppHandle,
+    databases: &State<'_, DatabaseStore>,
+    container_ids: &[String],
+) -> Result<(), AppError> {
+    let changed: Vec<DatabaseContainer> = {
+        let map = databases.read().await;
+        container_ids
+            .iter()
+            .filter_map(|container_id| map.get(container_id).cloned())
+            .collect()
     };
-    storage_service
-        .save_databases_to_store(&app, &db_map)
+    StorageService::new()
+        .upsert_databases_in_store(app, &changed)
         .await?;
 
+    let event_service = EventService::new();
+    for db in &changed {
+        event_service.container_status_changed(app, db);
+    }
+
     Ok(())
 }
 
+/// Remove a container and move its configuration to the trash
+/// With `keep_snapshot` the data volume is captured first so restoring brings the data back
 #[tauri::command]
-pub async fn stop_container(
+pub async fn remove_container(
     container_id: String,
+    keep_snapshot: Option<bool>,
     app: AppHandle,
     databases: State<'_, DatabaseStore>,
-) -> Result<(), String> {
-    let docker_service = DockerService::new();
+    companions: State<'_, CompanionStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
     let storage_service = StorageService::new();
 
-    // Get container info
-    let real_container_id = {
-        let db_map = databases.lock().unwrap();
-        db_map
-            .values()
-            .find(|db| db.id == container_id)
-            .and_then(|db| db.container_id.as_ref())
-            .cloned()
-            .ok_or("Container not found")?
+    // Get container info before removing it
+    let container_info = {
+        let db_map = databases.read().await;
+        db_map.values().find(|db| db.id == container_id).cloned()
     };
 
-    docker_service
-        .stop_container(&app, &real_container_id)
-        .await?;
+    // Capture the data before the volume is removed
+    let mut final_snapshot_id = None;
+    if let Some(container) = &container_info {
+        if keep_snapshot.unwrap_or(false) && container.stored_persist_data {
+            let snapshot = SnapshotService::new()
+                .create(&app, container, Some("Before removal".to_string()))
+                .await?;
 
-    // Update status
-    {
-        let mut db_map = databases.lock().unwrap();
-        if let Some(db) = db_map.values_mut().find(|db| db.id == container_id) {
-            db.status = "stopped".to_string();
+            let mut snapshots = storage_service.load_snapshots_from_store(&app).await?;
+            snapshots.push(snapshot.clone());
+            storage_service
+                .save_snapshots_to_store(&app, &snapshots)
+                .await?;
+
+            final_snapshot_id = Some(snapshot.id);
         }
     }
 
-    let db_map = {
-        let map = databases.lock().unwrap();
-        map.clone()
-    };
+    if let Some(container) = &container_info {
+        remove_docker_resources(&app, container).await?;
+
+        // Remove linked admin tools
+        remove_companions_for_database(&app, &companions, container).await?;
+
+        // Keep the configuration restorable, snapshots and password go when the entry expires
+        let mut trash = storage_service.load_trash_from_store(&app).await?;
+        trash.retain(|entry| entry.database.id != container_id);
+        trash.push(trash_entry(container, final_snapshot_id));
+        storage_service.save_trash_to_store(&app, &trash).await?;
+
+        // Its image becomes a cleanup candidate once no other database uses it
+        let _ = ImageCleanupService::new()
+            .release(&app, std::slice::from_ref(container))
+            .await;
+    }
+
+    // Always remove from memory and store
+    databases.write().await.remove(&container_id);
+
     storage_service
-        .save_databases_to_store(&app, &db_map)
+        .delete_databases_from_store(&app, std::slice::from_ref(&container_id))
         .await?;
+    SqlJobService::new()
+        .remove_for_databases(&app, std::slice::from_ref(&container_id))
+        .await?;
+
+    EventService::new().container_removed(&app, &container_id);
+    close_container_windows(&app, &container_id);
+
+    // Applies a retention of zero days right away
+    let _ = TrashService::new().purge(&app, false).await;
+
+    Ok(())
+}
+
+/// Remove the Docker container of a database with its data volume and TLS certificates
+pub async fn remove_docker_resources(
+    app: &AppHandle,
+    container: &DatabaseContainer,
+) -> Result<(), AppError> {
+    let docker_service = DockerService::for_database(app, container)?;
+
+    if let Some(real_id) = &container.container_id {
+        docker_service
+            .remove_container_with_timeout(app, real_id, container.stop_timeout)
+            .await?;
+    }
+
+    // If the container had persistent data, remove its volume
+    if container.stored_persist_data {
+        let volume_name = format!("{}-data", container.name);
+        docker_service
+            .remove_volume_if_exists(app, &volume_name)
+            .await?;
+    }
+
+    // Remove generated TLS certificates
+    if container.stored_enable_tls {
+        TlsService::new()
+            .remove_certificates(app, &container.name)
+            .await?;
+    }
 
     Ok(())
 }
 
+fn trash_entry(
+    container: &DatabaseContainer,
+    final_snapshot_id: Option<String>,
+) -> TrashedDatabase {
+    TrashedDatabase {
+        database: DatabaseContainer {
+            status: "stopped".to_string(),
+            container_id: None,
+            ca_cert_path: None,
+            ..container.clone()
+        },
+        deleted_at: chrono::Utc::now().to_rfc3339(),
+        final_snapshot_id,
+    }
+}
+
+/// Docker side of a bulk action for one container, store updates are left to the caller
+async fn run_bulk_action(
+    app: &AppHandle,
+    container_id: &str,
+    action: BulkAction,
+) -> Result<(), AppError> {
+    let databases = app.state::<DatabaseStore>();
+
+    match action {
+        BulkAction::Start => start_database(app, &databases, container_id).await,
+        BulkAction::Stop => stop_database(app, &databases, container_id).await,
+        BulkAction::Remove => {
+            let container = find_database(&databases, container_id).await?;
+            remove_docker_resources(app, &container).await
+        }
+    }
+}
+
+/// Start, stop or remove several containers at once
+/// A few containers are handled at a time and the store is saved once at the end
 #[tauri::command]
-pub async fn remove_container(
-    container_id: String,
+pub async fn bulk_operation(
+    container_ids: Vec<String>,
+    action: BulkAction,
     app: AppHandle,
     databases: State<'_, DatabaseStore>,
-) -> Result<(), String> {
-    let docker_service = DockerService::new();
+    companions: State<'_, CompanionStore>,
+) -> Result<Vec<BulkResult>, AppError> {
+    // Starting and stopping is still allowed in read-only mode
+    if action == BulkAction::Remove {
+        ensure_writable(&app)?;
+    }
     let storage_service = StorageService::new();
 
-    // Get container info before removing it
-    let (real_container_id, container_info) = {
-        let db_map = databases.lock().unwrap();
-        let container = db_map.values().find(|db| db.id == container_id).cloned();
-        let real_id = container
-            .as_ref()
-            .and_then(|db| db.container_id.as_ref())
-            .cloned();
-        (real_id, container)
+    let mut results = Vec::with_capacity(container_ids.len());
+    let mut succeeded = Vec::new();
+
+    // A slow container only holds up its own slot, not a whole batch
+    let outcomes: Vec<(String, Result<(), AppError>)> = stream::iter(container_ids)
+        .map(|container_id| {
+            let app = app.clone();
+            async move {
+                let result = run_bulk_action(&app, &container_id, action).await;
+                (container_id, result)
+            }
+        })
+        .buffered(BULK_CONCURRENCY)
+        .collect()
+        .await;
+
+    for (container_id, result) in outcomes {
+        if result.is_ok() {
+            succeeded.push(container_id.clone());
+        }
+        results.push(BulkResult::from_result(container_id, result));
+    }
+
+    if action != BulkAction::Remove {
+        save_and_announce_status(&app, &databases, &succeeded).await?;
+        return Ok(results);
+    }
+
+    // Store updates run once, after every container is gone
+    let removed: Vec<DatabaseContainer> = {
+        let mut db_map = databases.write().await;
+        succeeded
+            .iter()
+            .filter_map(|container_id| db_map.remove(container_id))
+            .collect()
     };
 
-    // If we have a real container ID, try to remove it
-    if let Some(real_id) = real_container_id {
-        docker_service.remove_container(&app, &real_id).await?;
+    let mut trash = storage_service.load_trash_from_store(&app).await?;
+    for container in &removed {
+        let _ = remove_companions_for_database(&app, &companions, container).await;
+        trash.retain(|entry| entry.database.id != container.id);
+        trash.push(trash_entry(container, None));
     }
+    storage_service.save_trash_to_store(&app, &trash).await?;
+    let _ = ImageCleanupService::new().release(&app, &removed).await;
 
-    // If the container had persistent data, remove its volume
-    if let Some(container) = &container_info {
-        if container.stored_persist_data {
-            let volume_name = format!("{}-data", container.name);
+    let removed_ids: Vec<String> = removed.iter().map(|db| db.id.clone()).collect();
+    storage_service
+        .delete_databases_from_store(&app, &removed_ids)
+        .await?;
+    SqlJobService::new()
+        .remove_for_databases(&app, &removed_ids)
+        .await?;
+
+    let event_service = EventService::new();
+    for container in &removed {
+        event_service.container_removed(&app, &container.id);
+        close_container_windows(&app, &container.id);
+    }
+
+    let _ = TrashService::new().purge(&app, false).await;
+
+    Ok(results)
+}
+
+/// Replace the tags of a container
+#[tauri::command]
+pub async fn set_container_tags(
+    container_id: String,
+    tags: Vec<String>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    update_container_labels(&app, &databases, &container_id, |db| {
+        db.tags = DatabaseContainer::normalize_tags(tags);
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn set_container_favorite(
+    container_id: String,
+    favorite: bool,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    update_container_labels(&app, &databases, &container_id, |db| {
+        db.favorite = favorite;
+    })
+    .await
+}
+
+/// Choose whether a container is started when the app launches
+#[tauri::command]
+pub async fn set_container_auto_start(
+    container_id: String,
+    auto_start: bool,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    update_container_labels(&app, &databases, &container_id, |db| {
+        db.auto_start = auto_start;
+    })
+    .await
+}
+
+/// Opt a container out of being stopped when the app quits
+#[tauri::command]
+pub async fn set_container_keep_running_on_exit(
+    container_id: String,
+    keep_running: bool,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    update_container_labels(&app, &databases, &container_id, |db| {
+        db.keep_running_on_exit = keep_running;
+    })
+    .await
+}
+
+/// Choose how long stopping a container waits before Docker kills it, None restores Docker's default
+/// Applies from the next stop, the container is not recreated
+#[tauri::command]
+pub async fn set_container_stop_timeout(
+    container_id: String,
+    stop_timeout: Option<u32>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    validate_stop_timeout(stop_timeout)?;
+    update_container_labels(&app, &databases, &container_id, |db| {
+        db.stop_timeout = stop_timeout;
+    })
+    .await
+}
+
+/// Let the app restart the container when it exits unexpectedly, None turns the watchdog off
+/// Restarts back off exponentially and stop after the policy's attempts
+#[tauri::command]
+pub async fn set_container_watchdog(
+    container_id: String,
+    watchdog: Option<WatchdogPolicy>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    if let Some(violation) = watchdog
+        .as_ref()
+        .and_then(|policy| policy.violations().into_iter().next())
+    {
+        return Err(AppError::Validation(violation));
+    }
+    update_container_labels(&app, &databases, &container_id, |db| {
+        db.watchdog = watchdog;
+    })
+    .await
+}
+
+/// Start every auto-start container that is stopped, returns the started ids
+#[tauri::command]
+pub async fn apply_autostart(app: AppHandle) -> Result<Vec<String>, AppError> {
+    AutostartService::new().apply(&app).await
+}
+
+/// List containers with a tag and/or only favorites
+#[tauri::command]
+pub async fn filter_databases(
+    tag: Option<String>,
+    favorite: Option<bool>,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<DatabaseContainer>, AppError> {
+    let filter = DatabaseFilter {
+        tag,
+        favorite,
+        ..Default::default()
+    };
+    let page = query_databases(filter, None, None, None, databases).await?;
+
+    Ok(page.items)
+}
+
+/// List managed databases matching a filter, sorted and paginated
+/// Sorted by name unless another key is given, `total` counts every match
+#[tauri::command]
+pub async fn query_databases(
+    filter: DatabaseFilter,
+    sort: Option<DatabaseSort>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabasePage, AppError> {
+    let matches: Vec<DatabaseContainer> = {
+        let db_map = databases.read().await;
+        db_map
+            .values()
+            .filter(|db| filter.matches(db))
+            .cloned()
+            .collect()
+    };
+
+    Ok(DatabasePage::from_matches(
+        matches,
+        sort.unwrap_or_default(),
+        offset.unwrap_or(0),
+        limit,
+    ))
+}
+
+/// Fix a persistent database whose data volume was removed outside the app
+/// Recreating the volume does not bring the data back, it only lets the database start again
+#[tauri::command]
+pub async fn repair_storage(
+    container_id: String,
+    repair: StorageRepair,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+
+    if repair == StorageRepair::RecreateVolume {
+        let docker_service = DockerService::for_database(&app, &database)?;
+        for volume in database.data_volumes() {
             docker_service
-                .remove_volume_if_exists(&app, &volume_name)
+                .create_volume_if_needed(&app, &volume)
                 .await?;
         }
     }
 
-    // Always remove from memory and store
-    databases.lock().unwrap().remove(&container_id);
+    update_container_labels(&app, &databases, &container_id, |db| {
+        if repair == StorageRepair::DisablePersistence {
+            db.stored_persist_data = false;
+            if let Some(args) = db.stored_docker_args.as_mut() {
+                args.volumes.clear();
+            }
+        }
+        db.storage_state = StorageState::Ok;
+    })
+    .await
+}
 
-    let db_map = {
-        let map = databases.lock().unwrap();
-        map.clone()
-    };
-    storage_service
-        .save_databases_to_store(&app, &db_map)
-        .await?;
+/// Apply a metadata-only change to a container, persist it and notify windows
+async fn update_container_labels(
+    app: &AppHandle,
+    databases: &State<'_, DatabaseStore>,
+    container_id: &str,
+    apply: impl FnOnce(&mut DatabaseContainer),
+) -> Result<DatabaseContainer, AppError> {
+    let storage_service = StorageService::new();
 
-    Ok(())
+    // Held until the change is saved, a failed save must not undo a concurrent edit
+    let mut db_map = databases.write().await;
+    let db = db_map
+        .get_mut(container_id)
+        .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+    let previous = db.clone();
+    apply(db);
+    let container = db.clone();
+
+    if let Err(store_error) = storage_service
+        .upsert_databases_in_store(app, std::slice::from_ref(&container))
+        .await
+    {
+        db_map.insert(container_id.to_string(), previous);
+        return Err(store_error);
+    }
+    drop(db_map);
+
+    EventService::new().container_updated(app, &container);
+
+    Ok(container)
 }
@@ -1,66 +1,276 @@
+use crate::commands::settings::ensure_writable;
 use crate::services::*;
 use crate::types::*;
 use tauri::{AppHandle, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 #[tauri::command]
-pub async fn get_docker_status(app: AppHandle) -> Result<serde_json::Value, String> {
+pub async fn get_docker_status(app: AppHandle) -> Result<serde_json::Value, AppError> {
     let docker_service = DockerService::new();
-    docker_service.check_docker_status(&app).await
+    let status = docker_service.check_docker_status(&app).await?;
+
+    EventService::new().docker_status_checked(&app, &status);
+
+    Ok(status)
+}
+
+/// Launch the Docker runtime when the daemon is stopped and wait until it answers
+#[tauri::command]
+pub async fn start_docker(app: AppHandle) -> Result<serde_json::Value, AppError> {
+    DockerService::new().start_daemon(&app).await
 }
 
+/// Force an immediate reconciliation with Docker instead of waiting for the worker
 #[tauri::command]
 pub async fn sync_containers_with_docker(
     app: AppHandle,
     databases: State<'_, DatabaseStore>,
-) -> Result<Vec<DatabaseContainer>, String> {
-    let docker_service = DockerService::new();
-    let storage_service = StorageService::new();
+) -> Result<Vec<DatabaseContainer>, AppError> {
+    SyncService::new().sync_once(&app).await?;
 
-    // Sync with Docker
-    let mut container_map = {
-        let db_map = databases.lock().unwrap();
-        db_map.clone()
-    };
-    docker_service
-        .sync_containers_with_docker(&app, &mut container_map)
-        .await?;
+    let db_map = databases.read().await;
+    Ok(db_map.values().cloned().collect())
+}
 
-    // Update the database store with synced data
-    {
-        let mut db_map = databases.lock().unwrap();
-        *db_map = container_map.clone();
+/// Labelled database containers missing from the store of the active workspace
+#[tauri::command]
+pub async fn list_orphans(app: AppHandle) -> Result<Vec<OrphanContainer>, AppError> {
+    OrphanService::new().find(&app).await
+}
+
+/// Adopt orphan containers into the store or remove them, their volumes are kept
+#[tauri::command]
+pub async fn reconcile_orphans(
+    actions: Vec<OrphanAction>,
+    app: AppHandle,
+) -> Result<Vec<BulkResult>, AppError> {
+    ensure_writable(&app)?;
+    Ok(OrphanService::new().reconcile(&app, actions).await)
+}
+
+/// Ask the registry whether newer images exist, for all databases or only `container_ids`
+#[tauri::command]
+pub async fn check_image_updates(
+    container_ids: Option<Vec<String>>,
+    app: AppHandle,
+) -> Result<Vec<ImageUpdateCheck>, AppError> {
+    ImageUpdateService::new()
+        .check(&app, container_ids.as_deref())
+        .await
+}
+
+/// Remove the images of removed databases that nothing uses anymore
+/// `dry_run` only lists them with their size
+#[tauri::command]
+pub async fn cleanup_images(dry_run: bool, app: AppHandle) -> Result<ImageCleanupReport, AppError> {
+    if !dry_run {
+        ensure_writable(&app)?;
     }
+    ImageCleanupService::new().cleanup(&app, dry_run).await
+}
+
+/// Equivalent `docker run` command of a database, also copied to the clipboard
+#[tauri::command]
+pub async fn get_docker_run_command(
+    container_id: String,
+    redact_password: Option<bool>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<String, AppError> {
+    let database = {
+        let db_map = databases.read().await;
+        db_map
+            .get(&container_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+    };
+
+    let command =
+        DockerService::new().docker_run_command(&database, redact_password.unwrap_or(true))?;
 
-    // Save updated state
-    storage_service
-        .save_databases_to_store(&app, &container_map)
-        .await?;
+    app.clipboard()
+        .write_text(command.clone())
+        .map_err(|e| AppError::Internal(format!("Failed to copy to clipboard: {}", e)))?;
 
-    Ok(container_map.values().cloned().collect())
+    Ok(command)
 }
 
+/// Client for the Docker host of the database whose container is `docker_id`, an id or name
+/// Containers the app does not manage are looked up on the local daemon
+async fn docker_service_of(
+    app: &AppHandle,
+    databases: &State<'_, DatabaseStore>,
+    docker_id: &str,
+) -> Result<DockerService, AppError> {
+    let db_map = databases.read().await;
+    match db_map
+        .values()
+        .find(|db| db.container_id.as_deref() == Some(docker_id) || db.name == docker_id)
+    {
+        Some(database) => DockerService::for_database(app, database),
+        None => Ok(DockerService::new()),
+    }
+}
+
+/// Last lines of the logs, optionally limited to a time range
 #[tauri::command]
 pub async fn get_container_logs(
     app: AppHandle,
     container_id: String,
     tail_lines: Option<i32>,
-) -> Result<String, String> {
-    let docker_service = DockerService::new();
+    since: Option<String>,
+    until: Option<String>,
+    databases: State<'_, DatabaseStore>,
+) -> Result<String, AppError> {
+    let docker_service = docker_service_of(&app, &databases, &container_id).await?;
+    docker_service
+        .get_container_logs(
+            &app,
+            &container_id,
+            tail_lines,
+            since.as_deref(),
+            until.as_deref(),
+        )
+        .await
+        .map_err(|error| docker_service.host_error(error))
+}
+
+/// Logs read forward a page at a time, pass `nextCursor` back to get the next one
+#[tauri::command]
+pub async fn get_container_logs_page(
+    app: AppHandle,
+    container_id: String,
+    query: Option<LogQuery>,
+    databases: State<'_, DatabaseStore>,
+) -> Result<LogPage, AppError> {
+    let docker_service = docker_service_of(&app, &databases, &container_id).await?;
     docker_service
-        .get_container_logs(&app, &container_id, tail_lines)
+        .get_container_logs_page(&app, &container_id, &query.unwrap_or_default())
         .await
+        .map_err(|error| docker_service.host_error(error))
+}
+
+/// A page of a database's logs split into entries with their severity
+/// Reads like `get_container_logs_page`, `nextCursor` continues after the page
+#[tauri::command]
+pub async fn get_parsed_logs(
+    app: AppHandle,
+    container_id: String,
+    query: Option<LogQuery>,
+    databases: State<'_, DatabaseStore>,
+) -> Result<ParsedLogPage, AppError> {
+    let database = {
+        let db_map = databases.read().await;
+        db_map
+            .get(&container_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+    };
+
+    let docker_service = DockerService::for_database(&app, &database)?;
+    let page = docker_service
+        .get_container_logs_page(&app, &database.name, &query.unwrap_or_default())
+        .await
+        .map_err(|error| docker_service.host_error(error))?;
+    let entries = LogParserService::new().parse_lines(&database.db_type, &page.lines);
+    Ok(ParsedLogPage::new(page, entries))
+}
+
+/// Open the platform's terminal app with a shell inside the container
+#[tauri::command]
+pub async fn open_container_terminal(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let database = {
+        let db_map = databases.read().await;
+        db_map
+            .get(&container_id)
+            .filter(|db| db.container_id.is_some())
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+    };
+    let docker_container_id = database.container_id.clone().unwrap_or_default();
+
+    let mut command = TerminalService::container_shell_command(&docker_container_id);
+    command.splice(
+        1..1,
+        DockerService::for_database(&app, &database)?.host_args(),
+    );
+    TerminalService::new().open(&app, &command).await
 }
 
+/// Run a command in the container, `container_id` is the Docker id
+/// The command is added to the database's terminal history with its secrets scrubbed
 #[tauri::command]
 pub async fn execute_container_command(
     app: AppHandle,
     container_id: String,
     command: String,
     columns: Option<u16>,
-) -> Result<serde_json::Value, String> {
-    let docker_service = DockerService::new();
+    databases: State<'_, DatabaseStore>,
+) -> Result<serde_json::Value, AppError> {
+    ensure_writable(&app)?;
+    let docker_service = docker_service_of(&app, &databases, &container_id).await?;
     let cols = columns.unwrap_or(80);
-    docker_service
+    let output = docker_service
         .execute_container_command(&app, &container_id, &command, cols)
         .await
+        .map_err(|error| docker_service.host_error(error))?;
+
+    let database = {
+        let db_map = databases.read().await;
+        db_map
+            .values()
+            .find(|db| db.container_id.as_deref() == Some(container_id.as_str()))
+            .cloned()
+    };
+    if let Some(database) = database {
+        let exit_code = output["exitCode"].as_i64().unwrap_or(-1) as i32;
+        // The command ran, a history that could not be saved does not fail it
+        if let Err(error) = ExecHistoryService::new()
+            .record(&app, &database, &command, exit_code)
+            .await
+        {
+            EventService::new().background_error(
+                &app,
+                "terminal-history",
+                format!("Failed to record terminal history: {}", error),
+            );
+        }
+    }
+
+    Ok(output)
+}
+
+/// Terminal commands of a database, newest first, only the ones containing `query` when set
+#[tauri::command]
+pub async fn get_exec_history(
+    container_id: String,
+    query: Option<String>,
+    limit: Option<usize>,
+    app: AppHandle,
+) -> Result<Vec<ExecHistoryEntry>, AppError> {
+    ExecHistoryService::new()
+        .search(
+            &app,
+            &container_id,
+            query.as_deref(),
+            limit.unwrap_or(MAX_EXEC_HISTORY),
+        )
+        .await
+}
+
+#[tauri::command]
+pub async fn clear_exec_history(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    let db_map = databases.read().await.clone();
+    ExecHistoryService::new()
+        .clear(&app, &container_id, &db_map)
+        .await
 }
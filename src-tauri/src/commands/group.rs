@@ -0,0 +1,436 @@
+use crate::commands::database::{
+    create_database_container, remove_container, start_container, stop_container,
+};
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+// Commands edit the groups concurrently, the list is read and written back as a whole
+static GROUPS_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+async fn find_group(app: &AppHandle, name: &str) -> Result<ContainerGroup, AppError> {
+    StorageService::new()
+        .load_groups_from_store(app)
+        .await?
+        .into_iter()
+        .find(|g| g.name == name)
+        .ok_or_else(|| AppError::NotFound("Group".to_string()))
+}
+
+async fn group_members(
+    group: &ContainerGroup,
+    databases: &State<'_, DatabaseStore>,
+) -> Result<Vec<DatabaseContainer>, AppError> {
+    let db_map = databases.read().await;
+    GroupService::start_order(group, &db_map)
+}
+
+#[tauri::command]
+pub async fn get_groups(app: AppHandle) -> Result<Vec<ContainerGroup>, AppError> {
+    StorageService::new().load_groups_from_store(&app).await
+}
+
+#[tauri::command]
+pub async fn create_group(name: String, app: AppHandle) -> Result<ContainerGroup, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let name = GroupService::validate_name(&name)?;
+
+    let _lock = GROUPS_LOCK.lock().await;
+    let mut groups = storage_service.load_groups_from_store(&app).await?;
+    if groups.iter().any(|g| g.name == name) {
+        return Err(AppError::Validation(format!(
+            "A group named '{}' already exists",
+            name
+        )));
+    }
+
+    let group = ContainerGroup {
+        name,
+        order: Vec::new(),
+        created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        network: None,
+        dependencies: HashMap::new(),
+    };
+    groups.push(group.clone());
+
+    storage_service.save_groups_to_store(&app, &groups).await?;
+
+    Ok(group)
+}
+
+/// Drop a service created before its stack failed, without moving it to the trash
+async fn discard_stack_service(
+    app: &AppHandle,
+    databases: &State<'_, DatabaseStore>,
+    database: &DatabaseContainer,
+) {
+    let Ok(docker_service) = DockerService::for_database(app, database) else {
+        return;
+    };
+    let _ = docker_service
+        .force_remove_container_by_name(app, &database.name)
+        .await;
+    if let Some(args) = &database.stored_docker_args {
+        for volume in &args.volumes {
+            let _ = docker_service
+                .remove_volume_if_exists(app, &volume.name)
+                .await;
+        }
+    }
+    databases.write().await.remove(&database.id);
+    let _ = StorageService::new()
+        .delete_databases_from_store(app, std::slice::from_ref(&database.id))
+        .await;
+    EventService::new().container_removed(app, &database.id);
+}
+
+/// Create the services of a stack on a shared network, grouped under the stack name
+/// Services are reachable from each other by container name, `<stack>-<service>`
+/// A failure removes the services created so far
+#[tauri::command]
+pub async fn create_stack(
+    request: StackRequest,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<ContainerGroup, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let name = GroupService::validate_name(&request.name)?;
+    request.validate()?;
+    let docker_service = DockerService::for_host_id(&app, request.host())?;
+
+    if storage_service
+        .load_groups_from_store(&app)
+        .await?
+        .iter()
+        .any(|g| g.name == name)
+    {
+        return Err(AppError::Validation(format!(
+            "A group named '{}' already exists",
+            name
+        )));
+    }
+
+    let network = request.network_name();
+    docker_service
+        .create_network_if_needed(&app, &network)
+        .await?;
+
+    let mut created: Vec<DatabaseContainer> = Vec::new();
+    let mut failure = None;
+    for service in &request.services {
+        let mut service_request = service.request.clone();
+        service_request.name = request.container_name(&service.service);
+
+        let database = match create_database_container(&app, &databases, service_request).await {
+            Ok(database) => database,
+            Err(error) => {
+                failure = Some(error);
+                break;
+            }
+        };
+        let connected = docker_service
+            .connect_container_to_network(&app, &network, &database.name)
+            .await;
+        created.push(database);
+        if let Err(error) = connected {
+            failure = Some(error);
+            break;
+        }
+    }
+
+    if let Some(error) = failure {
+        for database in created.iter().rev() {
+            discard_stack_service(&app, &databases, database).await;
+        }
+        let _ = docker_service
+            .remove_network_if_exists(&app, &network)
+            .await;
+        return Err(error);
+    }
+
+    {
+        let mut db_map = databases.write().await;
+        for database in created.iter_mut() {
+            database.project = Some(name.clone());
+            if let Some(db) = db_map.get_mut(&database.id) {
+                db.project = database.project.clone();
+            }
+        }
+    }
+
+    let group = ContainerGroup {
+        name,
+        order: created.iter().map(|db| db.id.clone()).collect(),
+        created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        network: Some(network),
+        dependencies: HashMap::new(),
+    };
+
+    storage_service
+        .upsert_databases_in_store(&app, &created)
+        .await?;
+    {
+        // Read again, the services took a while to create
+        let _lock = GROUPS_LOCK.lock().await;
+        let mut groups = storage_service.load_groups_from_store(&app).await?;
+        groups.push(group.clone());
+        storage_service.save_groups_to_store(&app, &groups).await?;
+    }
+
+    for database in &created {
+        EventService::new().container_updated(&app, database);
+    }
+
+    Ok(group)
+}
+
+/// Delete a group, its containers are kept and left ungrouped
+#[tauri::command]
+pub async fn delete_group(
+    name: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+
+    let _lock = GROUPS_LOCK.lock().await;
+    let mut groups = storage_service.load_groups_from_store(&app).await?;
+    if !groups.iter().any(|g| g.name == name) {
+        return Err(AppError::NotFound("Group".to_string()));
+    }
+    groups.retain(|g| g.name != name);
+
+    let ungrouped: Vec<String> = {
+        let mut db_map = databases.write().await;
+        db_map
+            .values_mut()
+            .filter(|db| db.project.as_deref() == Some(&name))
+            .map(|db| {
+                db.project = None;
+                db.id.clone()
+            })
+            .collect()
+    };
+
+    storage_service
+        .upsert_databases_by_id(&app, &ungrouped)
+        .await?;
+    storage_service.save_groups_to_store(&app, &groups).await
+}
+
+/// Move a container into a group, creating the group if needed
+/// Passing no group removes the container from its current one
+#[tauri::command]
+pub async fn set_container_group(
+    container_id: String,
+    group: Option<String>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let group = group
+        .map(|name| GroupService::validate_name(&name))
+        .transpose()?;
+
+    let _lock = GROUPS_LOCK.lock().await;
+    let mut groups = storage_service.load_groups_from_store(&app).await?;
+    for existing in groups.iter_mut() {
+        GroupService::remove_member(existing, &container_id);
+    }
+    if let Some(name) = &group {
+        match groups.iter_mut().find(|g| &g.name == name) {
+            Some(existing) => existing.order.push(container_id.clone()),
+            None => groups.push(ContainerGroup {
+                name: name.clone(),
+                order: vec![container_id.clone()],
+                created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+                network: None,
+                dependencies: HashMap::new(),
+            }),
+        }
+    }
+
+    let container = {
+        let mut db_map = databases.write().await;
+        let db = db_map
+            .get_mut(&container_id)
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+        db.project = group;
+        db.clone()
+    };
+
+    storage_service
+        .upsert_databases_in_store(&app, std::slice::from_ref(&container))
+        .await?;
+    storage_service.save_groups_to_store(&app, &groups).await?;
+
+    EventService::new().container_updated(&app, &container);
+
+    Ok(container)
+}
+
+/// Set the start order of a group, stop and remove use the reverse order
+/// Dependencies come first whatever the order
+#[tauri::command]
+pub async fn reorder_group(
+    name: String,
+    order: Vec<String>,
+    app: AppHandle,
+) -> Result<ContainerGroup, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+
+    let _lock = GROUPS_LOCK.lock().await;
+    let mut groups = storage_service.load_groups_from_store(&app).await?;
+    let group = groups
+        .iter_mut()
+        .find(|g| g.name == name)
+        .ok_or_else(|| AppError::NotFound("Group".to_string()))?;
+    group.order = order;
+    let group = group.clone();
+
+    storage_service.save_groups_to_store(&app, &groups).await?;
+
+    Ok(group)
+}
+
+/// Set the members a container waits for when its group starts, replacing earlier ones
+/// They are stopped after it, an empty list removes its dependencies
+#[tauri::command]
+pub async fn set_container_dependencies(
+    container_id: String,
+    depends_on: Vec<String>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<ContainerGroup, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let project = databases
+        .read()
+        .await
+        .get(&container_id)
+        .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+        .project
+        .clone()
+        .ok_or_else(|| {
+            AppError::Validation("Only containers of a group have dependencies".to_string())
+        })?;
+
+    let _lock = GROUPS_LOCK.lock().await;
+    let mut groups = storage_service.load_groups_from_store(&app).await?;
+    let group = groups
+        .iter_mut()
+        .find(|g| g.name == project)
+        .ok_or_else(|| AppError::NotFound("Group".to_string()))?;
+    {
+        let db_map = databases.read().await;
+        GroupService::set_dependencies(group, &container_id, depends_on, &db_map)?;
+    }
+    let group = group.clone();
+
+    storage_service.save_groups_to_store(&app, &groups).await?;
+
+    Ok(group)
+}
+
+/// Start every container of a group after the ones it depends on, stopping at the first failure
+#[tauri::command]
+pub async fn start_group(
+    name: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    let group = find_group(&app, &name).await?;
+    let members = group_members(&group, &databases).await?;
+
+    // Recreated members lose the stack network, attach them again
+    if let Some(network) = &group.network {
+        for member in &members {
+            let docker_service = DockerService::for_database(&app, member)?;
+            docker_service
+                .create_network_if_needed(&app, network)
+                .await?;
+            docker_service
+                .connect_container_to_network(&app, network, &member.name)
+                .await?;
+        }
+    }
+
+    // Members something depends on have to accept connections before the next one starts
+    for member in members {
+        if !member.is_running() {
+            start_container(member.id.clone(), app.clone(), databases.clone()).await?;
+        }
+        if GroupService::has_dependents(&group, &member.id) {
+            ReadinessService::new()
+                .wait_until_ready(&app, &member)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Stop every container of a group in reverse start order, dependents first
+#[tauri::command]
+pub async fn stop_group(
+    name: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    let group = find_group(&app, &name).await?;
+
+    for member in group_members(&group, &databases).await?.into_iter().rev() {
+        if member.is_running() {
+            stop_container(member.id, app.clone(), databases.clone()).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove every container of a group in reverse order, then the group itself
+#[tauri::command]
+pub async fn remove_group(
+    name: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+    companions: State<'_, CompanionStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let group = find_group(&app, &name).await?;
+
+    let members = group_members(&group, &databases).await?;
+    let mut hosts: Vec<Option<String>> = members.iter().map(|db| db.host.clone()).collect();
+    hosts.dedup();
+    for member in members.into_iter().rev() {
+        remove_container(
+            member.id,
+            None,
+            app.clone(),
+            databases.clone(),
+            companions.clone(),
+        )
+        .await?;
+    }
+
+    if let Some(network) = &group.network {
+        for host in &hosts {
+            DockerService::for_host_id(&app, host.as_deref())?
+                .remove_network_if_exists(&app, network)
+                .await?;
+        }
+    }
+
+    let _lock = GROUPS_LOCK.lock().await;
+    let mut groups = storage_service.load_groups_from_store(&app).await?;
+    groups.retain(|g| g.name != name);
+    storage_service.save_groups_to_store(&app, &groups).await
+}
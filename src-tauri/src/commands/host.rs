@@ -0,0 +1,121 @@
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+/// Docker hosts with the number of databases on each, the local daemon first
+#[tauri::command]
+pub async fn list_docker_hosts(
+    settings: State<'_, SettingsStore>,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<DockerHostStatus>, AppError> {
+    let hosts = settings.lock().unwrap().docker_hosts.clone();
+    let db_map = databases.read().await;
+    Ok(DockerHostStatus::list(
+        &hosts,
+        &db_map,
+        &DockerService::unreachable_hosts(),
+    ))
+}
+
+/// Add a remote Docker host when its id is empty, update it otherwise
+#[tauri::command]
+pub async fn save_docker_host(
+    mut host: DockerHost,
+    app: AppHandle,
+    settings: State<'_, SettingsStore>,
+) -> Result<DockerHost, AppError> {
+    ensure_writable(&app)?;
+    let mut new_settings = settings.lock().unwrap().clone();
+    if host.id.is_empty() {
+        host.id = uuid::Uuid::new_v4().to_string();
+    } else if !new_settings.docker_hosts.iter().any(|h| h.id == host.id) {
+        return Err(AppError::NotFound(format!("Docker host '{}'", host.id)));
+    }
+    if let Some(violation) = host.violations().into_iter().next() {
+        return Err(AppError::Validation(violation));
+    }
+    if new_settings
+        .docker_hosts
+        .iter()
+        .any(|other| other.id != host.id && other.name == host.name)
+    {
+        return Err(AppError::Validation(format!(
+            "A Docker host named '{}' already exists",
+            host.name
+        )));
+    }
+
+    match new_settings
+        .docker_hosts
+        .iter_mut()
+        .find(|existing| existing.id == host.id)
+    {
+        Some(existing) => *existing = host.clone(),
+        None => new_settings.docker_hosts.push(host.clone()),
+    }
+    StorageService::new()
+        .save_settings_to_store(&app, &new_settings)
+        .await?;
+
+    {
+        let mut current = settings.lock().unwrap();
+        *current = new_settings;
+    }
+    // Results cached for the previous URL must not be reused
+    DockerService::invalidate_cache();
+
+    Ok(host)
+}
+
+/// Forget a remote Docker host, refused while databases still run on it
+#[tauri::command]
+pub async fn remove_docker_host(
+    host_id: String,
+    app: AppHandle,
+    settings: State<'_, SettingsStore>,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let on_host = databases
+        .read()
+        .await
+        .values()
+        .filter(|db| db.host.as_deref() == Some(host_id.as_str()))
+        .count();
+    if on_host > 0 {
+        return Err(AppError::Validation(format!(
+            "Remove the {} database(s) on this host first",
+            on_host
+        )));
+    }
+
+    let mut new_settings = settings.lock().unwrap().clone();
+    let count = new_settings.docker_hosts.len();
+    new_settings.docker_hosts.retain(|host| host.id != host_id);
+    if new_settings.docker_hosts.len() == count {
+        return Err(AppError::NotFound(format!("Docker host '{}'", host_id)));
+    }
+
+    StorageService::new()
+        .save_settings_to_store(&app, &new_settings)
+        .await?;
+
+    {
+        let mut current = settings.lock().unwrap();
+        *current = new_settings;
+    }
+
+    Ok(())
+}
+
+/// Docker version and counts of a host, to check its URL before saving it
+#[tauri::command]
+pub async fn check_docker_host(
+    host: DockerHost,
+    app: AppHandle,
+) -> Result<serde_json::Value, AppError> {
+    DockerService::on_host(&host)
+        .check_docker_status(&app)
+        .await
+}
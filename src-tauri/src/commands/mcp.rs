@@ -0,0 +1,215 @@
+use crate::commands::api::{authorize, json_response, read_body, restart_local_server, ServerSlot};
+use crate::services::*;
+use crate::types::*;
+use serde_json::{json, Value};
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tiny_http::{Request, Response};
+
+// Running MCP server, replaced whenever the settings change
+static SERVER: LazyLock<ServerSlot> = LazyLock::new(|| Mutex::new(None));
+
+/// Start, restart or stop the MCP server to match `settings`
+/// Clients connect over streamable HTTP at `/mcp` with the API token as bearer
+pub fn apply_mcp_server(app: &AppHandle, settings: &AppSettings) -> Result<(), AppError> {
+    restart_local_server(
+        app,
+        &SERVER,
+        settings.mcp_enabled.then_some(settings.mcp_port),
+        handle_request,
+    )
+}
+
+fn handle_request(app: &AppHandle, request: Request) {
+    let Some(mut request) = authorize(request) else {
+        return;
+    };
+
+    let path = request.url().split('?').next().unwrap_or_default();
+    if path.trim_end_matches('/') != "/mcp" {
+        let _ = request.respond(json_response(
+            404,
+            &AppError::NotFound("Endpoint".to_string()),
+        ));
+        return;
+    }
+
+    // Only request/response, the server never opens an event stream
+    if request.method().as_str() != "POST" {
+        let _ = request.respond(Response::empty(405));
+        return;
+    }
+
+    let message = read_body(&mut request).and_then(|body| {
+        serde_json::from_str::<JsonRpcRequest>(&body)
+            .map_err(|e| AppError::Validation(format!("Invalid JSON-RPC message: {}", e)))
+    });
+    let message = match message {
+        Ok(message) => message,
+        Err(error) => {
+            let _ = request.respond(json_response(400, &error));
+            return;
+        }
+    };
+
+    if message.id.is_none() {
+        let _ = request.respond(Response::empty(202));
+        return;
+    }
+
+    let response = tauri::async_runtime::block_on(handle_message(app, &message));
+    let _ = request.respond(json_response(200, &response));
+}
+
+async fn handle_message(app: &AppHandle, message: &JsonRpcRequest) -> Value {
+    match message.method.as_str() {
+        "initialize" => {
+            let version = negotiate_protocol_version(message.params["protocolVersion"].as_str());
+            message.result(json!({
+                "protocolVersion": version,
+                "capabilities": { "tools": {} },
+                "serverInfo": {
+                    "name": "docker-db-manager",
+                    "version": app.package_info().version.to_string(),
+                },
+            }))
+        }
+        "ping" => message.result(json!({})),
+        "tools/list" => {
+            let tools: Vec<Value> = McpTool::ALL.iter().map(McpTool::definition).collect();
+            message.result(json!({ "tools": tools }))
+        }
+        "tools/call" => {
+            let name = message.params["name"].as_str().unwrap_or_default();
+            let Some(tool) = McpTool::from_name(name) else {
+                return message.error(JSONRPC_INVALID_PARAMS, format!("Unknown tool '{}'", name));
+            };
+
+            let result = call_tool(app, tool, &message.params["arguments"]).await;
+            message.result(match result {
+                Ok(text) => mcp_tool_result(text, false),
+                Err(error) => mcp_tool_result(error.to_string(), true),
+            })
+        }
+        method => message.error(
+            JSONRPC_METHOD_NOT_FOUND,
+            format!("Method '{}' not found", method),
+        ),
+    }
+}
+
+async fn call_tool(app: &AppHandle, tool: McpTool, arguments: &Value) -> Result<String, AppError> {
+    match tool {
+        McpTool::ListDatabases => {
+            let databases: Vec<Value> = {
+                let db_map = app.state::<DatabaseStore>();
+                let db_map = db_map.read().await;
+                db_map
+                    .values()
+                    .map(|db| {
+                        json!({
+                            "id": db.id,
+                            "name": db.name,
+                            "type": db.db_type,
+                            "version": db.version,
+                            "port": db.port,
+                            "status": db.status,
+                        })
+                    })
+                    .collect()
+            };
+            Ok(serde_json::to_string_pretty(&databases).unwrap_or_default())
+        }
+        McpTool::CreateDatabase => {
+            // Same validation as a dbmanager:// link
+            let mut link = tauri::Url::parse("dbmanager://create?direct=1").expect("static url");
+            {
+                let mut query = link.query_pairs_mut();
+                query.append_pair("type", &string_argument(arguments, "type")?);
+                for key in ["name", "version"] {
+                    if let Some(value) = arguments[key].as_str() {
+                        query.append_pair(key, value);
+                    }
+                }
+                if let Some(port) = arguments["port"].as_i64() {
+                    query.append_pair("port", &port.to_string());
+                }
+            }
+
+            let DeepLinkAction::Create(prefill) = DeepLinkAction::parse(link.as_str())?;
+            DeepLinkService::new().open_creation(app, &prefill)?;
+            Ok(
+                "The creation window is open for the user to review and confirm. \
+                Call list_databases to check whether the database was created."
+                    .to_string(),
+            )
+        }
+        McpTool::GetConnectionString => {
+            let database = find_database(app, &string_argument(arguments, "database")?).await?;
+            let prompt = format!(
+                "An AI assistant wants the connection string of '{}', including its password.",
+                database.name
+            );
+            if tool.needs_approval() && !ask_approval(app, &prompt) {
+                return Err(AppError::Validation(
+                    "The user denied access to the connection string".to_string(),
+                ));
+            }
+            let host = DockerService::for_database(app, &database)?.connection_host();
+            Ok(ConnectionService::on_host(host).connection_string(&database))
+        }
+        McpTool::GetLogs => {
+            let database = find_database(app, &string_argument(arguments, "database")?).await?;
+            // Logs may show queries and the values in them
+            let prompt = format!(
+                "An AI assistant wants the latest log lines of '{}'.",
+                database.name
+            );
+            if tool.needs_approval() && !ask_approval(app, &prompt) {
+                return Err(AppError::Validation(
+                    "The user denied access to the logs".to_string(),
+                ));
+            }
+            let container_id = database
+                .container_id
+                .clone()
+                .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+            let lines = arguments["lines"].as_i64().unwrap_or(100).clamp(1, 1000) as i32;
+
+            DockerService::for_database(app, &database)?
+                .get_container_logs(app, &container_id, Some(lines), None, None)
+                .await
+        }
+    }
+}
+
+fn string_argument(arguments: &Value, key: &str) -> Result<String, AppError> {
+    arguments[key]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| AppError::Validation(format!("Missing argument '{}'", key)))
+}
+
+async fn find_database(app: &AppHandle, target: &str) -> Result<DatabaseContainer, AppError> {
+    let databases = app.state::<DatabaseStore>();
+    let db_map = databases.read().await;
+    db_map
+        .values()
+        .find(|db| db.id == target || db.name == target)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("Database '{}'", target)))
+}
+
+/// Native confirmation dialog, blocks the server thread until the user answers
+fn ask_approval(app: &AppHandle, message: &str) -> bool {
+    app.dialog()
+        .message(message)
+        .title("Allow AI assistant access?")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Allow".to_string(),
+            "Deny".to_string(),
+        ))
+        .blocking_show()
+}
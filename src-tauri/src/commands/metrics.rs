@@ -0,0 +1,24 @@
+use crate::services::MetricsHistoryService;
+use crate::types::*;
+use tauri::AppHandle;
+
+/// Recorded CPU, memory, connection and disk points of a container for charting trends
+/// `bucket_secs` averages the points, e.g. 3600 for hourly values over a week
+#[tauri::command]
+pub async fn get_metrics_history(
+    container_id: String,
+    since: Option<String>,
+    until: Option<String>,
+    bucket_secs: Option<i64>,
+    app: AppHandle,
+) -> Result<Vec<MetricsPoint>, AppError> {
+    MetricsHistoryService::new()
+        .query(
+            &app,
+            &container_id,
+            since.as_deref(),
+            until.as_deref(),
+            bucket_secs,
+        )
+        .await
+}
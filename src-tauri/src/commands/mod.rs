@@ -1,9 +1,55 @@
+pub mod alert;
+pub mod api;
 pub mod app;
+pub mod benchmark;
+pub mod companion;
+pub mod config;
+pub mod connection;
 pub mod database;
 pub mod docker;
+pub mod group;
+pub mod host;
+pub mod mcp;
+pub mod metrics;
+pub mod replication;
+pub mod schedule;
+pub mod settings;
+pub mod slow_log;
+pub mod snapshot;
+pub mod snippet;
+pub mod sql_job;
+pub mod table;
+pub mod trash;
+pub mod tray;
+pub mod tuning;
+pub mod tunnel;
 pub mod window;
+pub mod workspace;
 
+pub use alert::*;
+pub use api::*;
 pub use app::*;
+pub use benchmark::*;
+pub use companion::*;
+pub use config::*;
+pub use connection::*;
 pub use database::*;
 pub use docker::*;
+pub use group::*;
+pub use host::*;
+pub use mcp::*;
+pub use metrics::*;
+pub use replication::*;
+pub use schedule::*;
+pub use settings::*;
+pub use slow_log::*;
+pub use snapshot::*;
+pub use snippet::*;
+pub use sql_job::*;
+pub use table::*;
+pub use trash::*;
+pub use tray::*;
+pub use tuning::*;
+pub use tunnel::*;
 pub use window::*;
+pub use workspace::*;
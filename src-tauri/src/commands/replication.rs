@@ -0,0 +1,112 @@
+use crate::commands::database::find_database;
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+#[tauri::command]
+pub async fn get_logical_replication(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<LogicalReplicationStatus, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    ReplicationService::new().status(&app, &database).await
+}
+
+/// Turn logical replication of a PostgreSQL database on or off, the server restarts to apply it
+#[tauri::command]
+pub async fn set_logical_replication(
+    container_id: String,
+    request: LogicalReplicationRequest,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<LogicalReplicationStatus, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    ReplicationService::new()
+        .configure(&app, &database, &request)
+        .await
+}
+
+#[tauri::command]
+pub async fn list_publications(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<Publication>, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    ReplicationService::new()
+        .publications(&app, &database)
+        .await
+}
+
+/// Publish tables given as "schema.table", every table when `tables` is empty
+#[tauri::command]
+pub async fn create_publication(
+    container_id: String,
+    name: String,
+    tables: Option<Vec<String>>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<Publication>, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    ReplicationService::new()
+        .create_publication(&app, &database, &name, &tables.unwrap_or_default())
+        .await
+}
+
+#[tauri::command]
+pub async fn drop_publication(
+    container_id: String,
+    name: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<Publication>, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    ReplicationService::new()
+        .drop_publication(&app, &database, &name)
+        .await
+}
+
+#[tauri::command]
+pub async fn list_replication_slots(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<ReplicationSlot>, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    ReplicationService::new().slots(&app, &database).await
+}
+
+/// Create a logical replication slot, with the pgoutput plugin unless another is given
+#[tauri::command]
+pub async fn create_replication_slot(
+    container_id: String,
+    name: String,
+    plugin: Option<String>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<ReplicationSlot>, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    ReplicationService::new()
+        .create_slot(&app, &database, &name, plugin.as_deref())
+        .await
+}
+
+#[tauri::command]
+pub async fn drop_replication_slot(
+    container_id: String,
+    name: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<ReplicationSlot>, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    ReplicationService::new()
+        .drop_slot(&app, &database, &name)
+        .await
+}
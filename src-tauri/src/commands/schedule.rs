@@ -0,0 +1,87 @@
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn get_schedules(app: AppHandle) -> Result<Vec<ContainerSchedule>, AppError> {
+    StorageService::new().load_schedules_from_store(&app).await
+}
+
+/// Add a schedule, `container_ids` empty applies it to every managed container
+#[tauri::command]
+pub async fn create_schedule(
+    name: String,
+    container_ids: Vec<String>,
+    action: ScheduledAction,
+    cron: String,
+    app: AppHandle,
+) -> Result<ContainerSchedule, AppError> {
+    ensure_writable(&app)?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Schedule name cannot be empty".to_string(),
+        ));
+    }
+    SchedulerService::parse(&cron)?;
+
+    let schedule = ContainerSchedule {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        container_ids,
+        action,
+        cron: cron.trim().to_string(),
+        enabled: true,
+        created_at: chrono::Local::now().to_rfc3339(),
+        last_run_at: None,
+        last_error: None,
+    };
+
+    SchedulerService::new()
+        .update_schedules(&app, |schedules| {
+            schedules.push(schedule.clone());
+            Ok(())
+        })
+        .await?;
+
+    Ok(schedule)
+}
+
+/// Pause or resume a schedule, occurrences while paused are not caught up
+#[tauri::command]
+pub async fn set_schedule_enabled(
+    schedule_id: String,
+    enabled: bool,
+    app: AppHandle,
+) -> Result<ContainerSchedule, AppError> {
+    ensure_writable(&app)?;
+    SchedulerService::new()
+        .update_schedules(&app, |schedules| {
+            let schedule = schedules
+                .iter_mut()
+                .find(|s| s.id == schedule_id)
+                .ok_or_else(|| AppError::NotFound("Schedule".to_string()))?;
+
+            if enabled && !schedule.enabled {
+                schedule.last_run_at = Some(chrono::Local::now().to_rfc3339());
+            }
+            schedule.enabled = enabled;
+            Ok(schedule.clone())
+        })
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_schedule(schedule_id: String, app: AppHandle) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    SchedulerService::new()
+        .update_schedules(&app, |schedules| {
+            if !schedules.iter().any(|s| s.id == schedule_id) {
+                return Err(AppError::NotFound("Schedule".to_string()));
+            }
+            schedules.retain(|s| s.id != schedule_id);
+            Ok(())
+        })
+        .await
+}
@@ -0,0 +1,236 @@
+use crate::commands::api::apply_api_server;
+use crate::commands::mcp::apply_mcp_server;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_autostart::ManagerExt;
+
+/// Fails while read-only mode is on, called first by every command that creates, changes,
+/// removes or execs into containers
+pub(crate) fn ensure_writable(app: &AppHandle) -> Result<(), AppError> {
+    let settings = app.state::<SettingsStore>();
+    let settings = settings.lock().unwrap();
+    settings.ensure_writable()
+}
+
+#[tauri::command]
+pub async fn get_settings(settings: State<'_, SettingsStore>) -> Result<AppSettings, AppError> {
+    let current = settings.lock().unwrap();
+    Ok(current.clone())
+}
+
+#[tauri::command]
+pub async fn update_settings(
+    new_settings: AppSettings,
+    app: AppHandle,
+    settings: State<'_, SettingsStore>,
+) -> Result<AppSettings, AppError> {
+    let storage_service = StorageService::new();
+
+    if new_settings.sync_interval_secs == 0 || new_settings.sync_interval_secs > 3600 {
+        return Err(AppError::Validation(
+            "Sync interval must be between 1 and 3600 seconds".to_string(),
+        ));
+    }
+
+    if new_settings.trash_retention_days > 365 {
+        return Err(AppError::Validation(
+            "Trash retention must be at most 365 days".to_string(),
+        ));
+    }
+
+    if new_settings.image_update_check_hours > 24 * 30 {
+        return Err(AppError::Validation(
+            "Image update checks must run at least every 30 days".to_string(),
+        ));
+    }
+
+    if new_settings.metrics_history_interval_secs != 0
+        && !(10..=3600).contains(&new_settings.metrics_history_interval_secs)
+    {
+        return Err(AppError::Validation(
+            "Metrics history samples must be between 10 and 3600 seconds apart".to_string(),
+        ));
+    }
+
+    if !(1..=365).contains(&new_settings.metrics_history_retention_days) {
+        return Err(AppError::Validation(
+            "Metrics history must be kept between 1 and 365 days".to_string(),
+        ));
+    }
+
+    if new_settings.api_port < 1024 || new_settings.mcp_port < 1024 {
+        return Err(AppError::Validation(
+            "API and MCP ports must be between 1024 and 65535".to_string(),
+        ));
+    }
+
+    if new_settings.api_port == new_settings.mcp_port {
+        return Err(AppError::Validation(
+            "The API and MCP servers need different ports".to_string(),
+        ));
+    }
+
+    if let Some(violation) = new_settings.default_region.violations().into_iter().next() {
+        return Err(AppError::Validation(violation));
+    }
+
+    if let Some(violation) = new_settings
+        .docker_hosts
+        .iter()
+        .flat_map(DockerHost::violations)
+        .next()
+    {
+        return Err(AppError::Validation(violation));
+    }
+
+    if let Some(violation) = new_settings.share.violations().into_iter().next() {
+        return Err(AppError::Validation(violation));
+    }
+
+    // A taken port is reported before anything is saved, the running servers go back to
+    // the current ports when a later step fails
+    let previous = settings.lock().unwrap().clone();
+    let applied =
+        apply_api_server(&app, &new_settings).and_then(|_| apply_mcp_server(&app, &new_settings));
+    let saved = match applied {
+        Ok(()) => {
+            storage_service
+                .save_settings_to_store(&app, &new_settings)
+                .await
+        }
+        Err(error) => Err(error),
+    };
+    if let Err(error) = saved {
+        let _ = apply_api_server(&app, &previous);
+        let _ = apply_mcp_server(&app, &previous);
+        return Err(error);
+    }
+
+    {
+        let mut current = settings.lock().unwrap();
+        *current = new_settings.clone();
+    }
+
+    Ok(new_settings)
+}
+
+#[tauri::command]
+pub async fn get_store_encryption(app: AppHandle) -> Result<StoreEncryptionStatus, AppError> {
+    StorageService::new().encryption_status(&app).await
+}
+
+/// Encrypt the persisted store
+/// Without a passphrase the key is generated and kept in the system keychain
+#[tauri::command]
+pub async fn enable_store_encryption(
+    passphrase: Option<String>,
+    app: AppHandle,
+) -> Result<StoreEncryptionStatus, AppError> {
+    let storage_service = StorageService::new();
+
+    if passphrase.as_ref().is_some_and(|p| p.chars().count() < 8) {
+        return Err(AppError::Validation(
+            "Passphrase must be at least 8 characters".to_string(),
+        ));
+    }
+
+    storage_service.enable_encryption(&app, passphrase).await?;
+    storage_service.encryption_status(&app).await
+}
+
+#[tauri::command]
+pub async fn disable_store_encryption(app: AppHandle) -> Result<StoreEncryptionStatus, AppError> {
+    let storage_service = StorageService::new();
+
+    storage_service.disable_encryption(&app).await?;
+    storage_service.encryption_status(&app).await
+}
+
+/// Unlock a passphrase-protected store and load its contents
+#[tauri::command]
+pub async fn unlock_store(passphrase: String, app: AppHandle) -> Result<(), AppError> {
+    StorageService::new().unlock(&app, &passphrase).await?;
+    SyncService::new().initialize(&app).await
+}
+
+/// Mute or unmute desktop notifications for one container
+#[tauri::command]
+pub async fn set_container_notifications_muted(
+    container_id: String,
+    muted: bool,
+    app: AppHandle,
+    settings: State<'_, SettingsStore>,
+) -> Result<AppSettings, AppError> {
+    let mut new_settings = settings.lock().unwrap().clone();
+    new_settings
+        .muted_containers
+        .retain(|id| id != &container_id);
+    if muted {
+        new_settings.muted_containers.push(container_id);
+    }
+
+    StorageService::new()
+        .save_settings_to_store(&app, &new_settings)
+        .await?;
+
+    {
+        let mut current = settings.lock().unwrap();
+        *current = new_settings.clone();
+    }
+
+    Ok(new_settings)
+}
+
+/// How the logs window of a container was left
+#[tauri::command]
+pub async fn get_log_preferences(
+    container_id: String,
+    settings: State<'_, SettingsStore>,
+) -> Result<LogViewPreferences, AppError> {
+    Ok(settings.lock().unwrap().log_preferences_of(&container_id))
+}
+
+#[tauri::command]
+pub async fn set_log_preferences(
+    container_id: String,
+    preferences: LogViewPreferences,
+    app: AppHandle,
+    settings: State<'_, SettingsStore>,
+) -> Result<LogViewPreferences, AppError> {
+    preferences.validate()?;
+    let mut new_settings = settings.lock().unwrap().clone();
+    new_settings.set_log_preferences(&container_id, preferences.clone());
+
+    StorageService::new()
+        .save_settings_to_store(&app, &new_settings)
+        .await?;
+
+    {
+        let mut current = settings.lock().unwrap();
+        *current = new_settings;
+    }
+
+    Ok(preferences)
+}
+
+/// Whether the app is registered to launch, headless, at OS login
+#[tauri::command]
+pub async fn get_launch_at_login(app: AppHandle) -> Result<bool, AppError> {
+    app.autolaunch()
+        .is_enabled()
+        .map_err(|e| AppError::Internal(format!("Failed to read login item: {}", e)))
+}
+
+#[tauri::command]
+pub async fn set_launch_at_login(enabled: bool, app: AppHandle) -> Result<bool, AppError> {
+    let autolaunch = app.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|e| AppError::Internal(format!("Failed to update login item: {}", e)))?;
+
+    Ok(enabled)
+}
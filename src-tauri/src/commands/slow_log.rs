@@ -0,0 +1,73 @@
+use crate::commands::database::find_database;
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+/// Log statements running longer than `threshold_ms`
+#[tauri::command]
+pub async fn enable_slow_log(
+    container_id: String,
+    threshold_ms: u64,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    SlowLogService::new()
+        .enable(&app, &database, threshold_ms)
+        .await
+}
+
+#[tauri::command]
+pub async fn disable_slow_log(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    SlowLogService::new().disable(&app, &database).await
+}
+
+/// Slow statements logged since the last reset, newest first
+#[tauri::command]
+pub async fn fetch_slow_log(
+    container_id: String,
+    limit: Option<usize>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<SlowQuery>, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    SlowLogService::new()
+        .fetch(&app, &database, limit.unwrap_or(DEFAULT_SLOW_LOG_LIMIT))
+        .await
+}
+
+/// Clear the slow query log
+#[tauri::command]
+pub async fn reset_slow_log(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    SlowLogService::new().reset(&app, &database).await?;
+
+    let storage_service = StorageService::new();
+    let mut db_map = databases.write().await;
+    let db = db_map
+        .get_mut(&container_id)
+        .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+    db.slow_log_reset_at = Some(chrono::Utc::now().to_rfc3339());
+    let container = db.clone();
+    storage_service
+        .upsert_databases_in_store(&app, std::slice::from_ref(&container))
+        .await?;
+    drop(db_map);
+
+    EventService::new().container_updated(&app, &container);
+
+    Ok(container)
+}
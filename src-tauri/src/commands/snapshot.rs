@@ -0,0 +1,92 @@
+use crate::commands::database::find_database;
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+async fn find_snapshot(app: &AppHandle, snapshot_id: &str) -> Result<Snapshot, AppError> {
+    StorageService::new()
+        .load_snapshots_from_store(app)
+        .await?
+        .into_iter()
+        .find(|s| s.id == snapshot_id)
+        .ok_or_else(|| AppError::NotFound("Snapshot".to_string()))
+}
+
+/// Capture the data volume of a container into a snapshot
+/// The container is stopped during the copy so the data is consistent
+#[tauri::command]
+pub async fn create_snapshot(
+    container_id: String,
+    label: Option<String>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Snapshot, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let snapshot_service = SnapshotService::new();
+    let database = find_database(&databases, &container_id).await?;
+
+    let snapshot = snapshot_service.create(&app, &database, label).await?;
+
+    let mut snapshots = storage_service.load_snapshots_from_store(&app).await?;
+    snapshots.push(snapshot.clone());
+
+    if let Err(store_error) = storage_service
+        .save_snapshots_to_store(&app, &snapshots)
+        .await
+    {
+        let _ = snapshot_service.delete(&app, &snapshot).await;
+        return Err(store_error);
+    }
+
+    Ok(snapshot)
+}
+
+/// Snapshots of a container, newest first
+#[tauri::command]
+pub async fn list_snapshots(
+    container_id: String,
+    app: AppHandle,
+) -> Result<Vec<Snapshot>, AppError> {
+    let mut snapshots: Vec<Snapshot> = StorageService::new()
+        .load_snapshots_from_store(&app)
+        .await?
+        .into_iter()
+        .filter(|s| s.database_id == container_id)
+        .collect();
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    Ok(snapshots)
+}
+
+/// Roll a container's data back to a snapshot
+#[tauri::command]
+pub async fn restore_snapshot(
+    snapshot_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let snapshot = find_snapshot(&app, &snapshot_id).await?;
+    let database = find_database(&databases, &snapshot.database_id).await?;
+
+    SnapshotService::new()
+        .restore(&app, &database, &snapshot)
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_snapshot(snapshot_id: String, app: AppHandle) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let snapshot = find_snapshot(&app, &snapshot_id).await?;
+
+    SnapshotService::new().delete(&app, &snapshot).await?;
+
+    let mut snapshots = storage_service.load_snapshots_from_store(&app).await?;
+    snapshots.retain(|s| s.id != snapshot_id);
+    storage_service
+        .save_snapshots_to_store(&app, &snapshots)
+        .await
+}
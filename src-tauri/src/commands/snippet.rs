@@ -0,0 +1,50 @@
+use crate::commands::database::find_database;
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+/// Saved queries and commands usable with a container, its own and those of its type
+#[tauri::command]
+pub async fn list_snippets(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<Snippet>, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    SnippetService::new().list(&app, &database).await
+}
+
+/// Create or update a snippet
+#[tauri::command]
+pub async fn save_snippet(
+    snippet: Snippet,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Snippet, AppError> {
+    ensure_writable(&app)?;
+    let db_map = databases.read().await.clone();
+    SnippetService::new().save(&app, snippet, &db_map).await
+}
+
+#[tauri::command]
+pub async fn delete_snippet(snippet_id: String, app: AppHandle) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    SnippetService::new().delete(&app, &snippet_id).await
+}
+
+/// Text of a snippet ready for the console or the terminal of a container
+#[tauri::command]
+pub async fn render_snippet(
+    snippet_id: String,
+    container_id: String,
+    values: Option<HashMap<String, String>>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<String, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    SnippetService::new()
+        .render(&app, &snippet_id, &database, &values.unwrap_or_default())
+        .await
+}
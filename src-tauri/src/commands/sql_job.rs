@@ -0,0 +1,40 @@
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+/// SQL jobs of a container, or of every container when none is given
+#[tauri::command]
+pub async fn list_sql_jobs(
+    container_id: Option<String>,
+    app: AppHandle,
+) -> Result<Vec<SqlJob>, AppError> {
+    SqlJobService::new()
+        .list(&app, container_id.as_deref())
+        .await
+}
+
+/// Create or update a recurring SQL job of a PostgreSQL, MySQL or MariaDB database
+#[tauri::command]
+pub async fn save_sql_job(
+    job: SqlJob,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<SqlJob, AppError> {
+    ensure_writable(&app)?;
+    let db_map = databases.read().await.clone();
+    SqlJobService::new().save(&app, job, &db_map).await
+}
+
+#[tauri::command]
+pub async fn delete_sql_job(job_id: String, app: AppHandle) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    SqlJobService::new().delete(&app, &job_id).await
+}
+
+/// Run a job now and wait for it, the run is recorded like a scheduled one
+#[tauri::command]
+pub async fn run_sql_job(job_id: String, app: AppHandle) -> Result<SqlJob, AppError> {
+    ensure_writable(&app)?;
+    SqlJobService::new().run_now(&app, &job_id).await
+}
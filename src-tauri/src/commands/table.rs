@@ -0,0 +1,284 @@
+use crate::commands::database::find_database;
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, Manager, State};
+
+/// Tables and views of a running PostgreSQL, MySQL or MariaDB database
+#[tauri::command]
+pub async fn list_tables(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<Vec<TableInfo>, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    SqlService::new().list_tables(&app, &database).await
+}
+
+/// One page of a table's rows, sorted and filtered, with its column metadata
+#[tauri::command]
+pub async fn fetch_table_rows(
+    container_id: String,
+    query: TableRowsQuery,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<TableRowsPage, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    SqlService::new().fetch_rows(&app, &database, &query).await
+}
+
+/// Tables, columns, keys and indexes of a SQL database, `database` defaults to the one
+/// created with the container
+#[tauri::command]
+pub async fn get_schema(
+    container_id: String,
+    database: Option<String>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseSchema, AppError> {
+    let container = find_database(&databases, &container_id).await?;
+    SqlService::new()
+        .get_schema(&app, &container, database.as_deref())
+        .await
+}
+
+/// Schema of a SQL database as Mermaid or DBML text
+#[tauri::command]
+pub async fn export_schema(
+    container_id: String,
+    database: Option<String>,
+    format: SchemaFormat,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<String, AppError> {
+    let schema = get_schema(container_id, database, app, databases).await?;
+    Ok(schema.render(format))
+}
+
+/// Write a table or collection to a local CSV file, `table-export-progress` reports the
+/// bytes written while it runs
+#[tauri::command]
+pub async fn export_table(
+    container_id: String,
+    table: String,
+    path: String,
+    options: Option<TableExportOptions>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<TableExportResult, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    CsvService::new()
+        .export_table(
+            &app,
+            &database,
+            &table,
+            std::path::Path::new(&path),
+            &options.unwrap_or_default(),
+        )
+        .await
+}
+
+/// Load a local CSV file into an existing table or collection of a running database
+#[tauri::command]
+pub async fn import_csv(
+    container_id: String,
+    table: String,
+    path: String,
+    mapping: Option<CsvImportMapping>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<CsvImportReport, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    CsvService::new()
+        .import_csv(
+            &app,
+            &database,
+            &table,
+            std::path::Path::new(&path),
+            &mapping.unwrap_or_default(),
+        )
+        .await
+}
+
+/// Size of every database and table of a container, reused for a few minutes unless `refresh`
+#[tauri::command]
+pub async fn get_storage_breakdown(
+    container_id: String,
+    refresh: Option<bool>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<StorageBreakdown, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    StorageBreakdownService::new()
+        .get(&app, &database, refresh.unwrap_or(false))
+        .await
+}
+
+/// Files and sizes in the data directory of a container, `path` is relative to it
+/// Nothing is written, stopped databases are read from their volume
+#[tauri::command]
+pub async fn browse_data_dir(
+    container_id: String,
+    path: Option<String>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DataDirListing, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    DataDirService::new()
+        .list(&app, &database, path.as_deref().unwrap_or_default())
+        .await
+}
+
+/// Copy a host file or directory into a container with `docker cp`
+/// Progress is reported with `file-copy-progress`
+#[tauri::command]
+pub async fn copy_to_container(
+    container_id: String,
+    local_path: String,
+    container_path: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<FileCopyResult, AppError> {
+    ensure_writable(&app)?;
+    let database = find_database(&databases, &container_id).await?;
+    FileCopyService::new()
+        .copy_to_container(
+            &app,
+            &database,
+            std::path::Path::new(&local_path),
+            &container_path,
+        )
+        .await
+}
+
+/// Copy a file or directory out of a container with `docker cp`
+/// Progress is reported with `file-copy-progress`
+#[tauri::command]
+pub async fn copy_from_container(
+    container_id: String,
+    container_path: String,
+    local_path: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<FileCopyResult, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    FileCopyService::new()
+        .copy_from_container(
+            &app,
+            &database,
+            &container_path,
+            std::path::Path::new(&local_path),
+        )
+        .await
+}
+
+/// Run the engine's consistency checker in a running container at low priority
+/// Corruption fires the container's integrity alert rules
+/// `install_missing` lets pg_amcheck create the amcheck extension where it is missing
+#[tauri::command]
+pub async fn run_integrity_check(
+    container_id: String,
+    install_missing: Option<bool>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<IntegrityCheckRun, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    // Installing the extension writes to every database
+    let install_missing = install_missing.unwrap_or(false);
+    if install_missing {
+        ensure_writable(&app)?;
+    }
+    IntegrityService::new()
+        .check(&app, &database, install_missing, None)
+        .await
+}
+
+/// Integrity check history newest first, of one container or of all of them
+#[tauri::command]
+pub async fn list_integrity_checks(
+    container_id: Option<String>,
+    limit: Option<usize>,
+    app: AppHandle,
+) -> Result<Vec<IntegrityCheckRun>, AppError> {
+    IntegrityService::new()
+        .history(&app, container_id.as_deref(), limit)
+        .await
+}
+
+/// Guard of a database's SQL console, read-only mode keeps it locked
+fn sql_guard(app: &AppHandle, database_id: &str) -> SqlGuardStatus {
+    let (enforced, read_only) = {
+        let settings = app.state::<SettingsStore>();
+        let settings = settings.lock().unwrap();
+        (settings.sql_read_only_guard, settings.read_only)
+    };
+
+    let guard = SqlService::new().guard_status(database_id, enforced || read_only);
+    SqlGuardStatus {
+        unlocked: guard.unlocked && !read_only,
+        ..guard
+    }
+}
+
+/// Run SQL typed in the console of a PostgreSQL, MySQL or MariaDB database
+#[tauri::command]
+pub async fn run_sql(
+    container_id: String,
+    sql: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<SqlConsoleResult, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    let guard = sql_guard(&app, &database.id);
+    SqlService::new()
+        .run_console(&app, &database, &sql, guard)
+        .await
+}
+
+/// Write the rows of one console query to a local CSV or JSON file instead of returning them,
+/// `query-export-progress` reports the rows written while it runs
+#[tauri::command]
+pub async fn export_query(
+    container_id: String,
+    sql: String,
+    path: String,
+    format: Option<QueryExportFormat>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<QueryExportResult, AppError> {
+    let database = find_database(&databases, &container_id).await?;
+    let guard = sql_guard(&app, &database.id);
+    SqlService::new()
+        .export_query(
+            &app,
+            &database,
+            &sql,
+            guard,
+            format.unwrap_or_default(),
+            std::path::Path::new(&path),
+        )
+        .await
+}
+
+#[tauri::command]
+pub async fn get_sql_guard(
+    container_id: String,
+    app: AppHandle,
+) -> Result<SqlGuardStatus, AppError> {
+    Ok(sql_guard(&app, &container_id))
+}
+
+/// Allow statements that change data from the SQL console until the app quits, or lock it again
+#[tauri::command]
+pub async fn set_sql_writes_unlocked(
+    container_id: String,
+    unlocked: bool,
+    app: AppHandle,
+) -> Result<SqlGuardStatus, AppError> {
+    if unlocked {
+        ensure_writable(&app)?;
+    }
+    SqlService::new().set_writes_unlocked(&container_id, unlocked);
+    Ok(sql_guard(&app, &container_id))
+}
@@ -0,0 +1,117 @@
+use crate::commands::database::{create_database_container, remove_docker_resources};
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+/// Removed containers that can still be restored, most recent first
+#[tauri::command]
+pub async fn list_trash(app: AppHandle) -> Result<Vec<TrashedDatabase>, AppError> {
+    let mut trash = StorageService::new().load_trash_from_store(&app).await?;
+    trash.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+
+    Ok(trash)
+}
+
+/// Recreate a removed container from the trash
+/// Its data comes back only when a final snapshot was taken on removal
+#[tauri::command]
+pub async fn restore_from_trash(
+    container_id: String,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let snapshot_service = SnapshotService::new();
+
+    let mut trash = storage_service.load_trash_from_store(&app).await?;
+    let entry = trash
+        .iter()
+        .find(|entry| entry.database.id == container_id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound("Trashed container".to_string()))?;
+
+    let name_in_use = {
+        let db_map = databases.read().await;
+        db_map.values().any(|db| db.name == entry.database.name)
+    };
+    if name_in_use {
+        return Err(AppError::NameInUse {
+            name: entry.database.name.clone(),
+        });
+    }
+
+    // The password stays in the keychain while the container is in the trash
+    let mut trashed = entry.database.clone();
+    if trashed.stored_password.is_none() {
+        if let Some(key) = &trashed.stored_password_ref {
+            let password = SecretsService::new().get_secret(key)?.ok_or_else(|| {
+                AppError::Secrets(format!(
+                    "The password of {} is no longer in the keychain",
+                    trashed.name
+                ))
+            })?;
+            trashed.stored_password = Some(password.clone());
+            SecretsService::inject_password(&mut trashed, &password);
+        }
+    }
+
+    let request = ConfigService::build_run_request(&trashed)?;
+    let mut database = create_database_container(&app, &databases, request).await?;
+
+    // Keep the organization the container had
+    database.project = entry.database.project.clone();
+    database.tags = entry.database.tags.clone();
+    database.favorite = entry.database.favorite;
+    database.auto_start = entry.database.auto_start;
+    database.keep_running_on_exit = entry.database.keep_running_on_exit;
+
+    let restored: Result<(), AppError> = async {
+        if let Some(snapshot_id) = &entry.final_snapshot_id {
+            let snapshot = storage_service
+                .load_snapshots_from_store(&app)
+                .await?
+                .into_iter()
+                .find(|s| &s.id == snapshot_id);
+
+            if let Some(snapshot) = snapshot {
+                snapshot_service.restore(&app, &database, &snapshot).await?;
+            }
+        }
+
+        databases
+            .write()
+            .await
+            .insert(database.id.clone(), database.clone());
+        storage_service
+            .upsert_databases_in_store(&app, std::slice::from_ref(&database))
+            .await
+    }
+    .await;
+
+    // The trash entry stays, the recreated container goes so the restore can be retried
+    if let Err(error) = restored {
+        let _ = remove_docker_resources(&app, &database).await;
+        databases.write().await.remove(&database.id);
+        let _ = storage_service
+            .delete_databases_from_store(&app, std::slice::from_ref(&database.id))
+            .await;
+        EventService::new().container_removed(&app, &database.id);
+        return Err(error);
+    }
+
+    trash.retain(|entry| entry.database.id != container_id);
+    storage_service.save_trash_to_store(&app, &trash).await?;
+
+    EventService::new().container_updated(&app, &database);
+
+    Ok(database)
+}
+
+/// Permanently delete everything in the trash
+#[tauri::command]
+pub async fn empty_trash(app: AppHandle) -> Result<usize, AppError> {
+    ensure_writable(&app)?;
+    TrashService::new().purge(&app, true).await
+}
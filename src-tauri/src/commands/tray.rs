@@ -0,0 +1,79 @@
+use crate::commands::database::{bulk_operation, start_container, stop_container};
+use crate::commands::window::show_main_window;
+use crate::services::*;
+use crate::types::*;
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager};
+
+/// Create the tray icon, its menu lists the managed containers
+pub fn create_tray(app: &AppHandle) -> tauri::Result<()> {
+    let entries = {
+        let databases = app.state::<DatabaseStore>();
+        // Runs during setup, outside the async runtime
+        let db_map = databases.blocking_read();
+        TrayService::entries(&db_map)
+    };
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Docker DB Manager")
+        .menu(&TrayService::build_menu(app, &entries)?)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| {
+            if let Some(action) = TrayAction::from_menu_id(event.id().as_ref()) {
+                handle_tray_action(app, action);
+            }
+        });
+
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app)?;
+
+    Ok(())
+}
+
+fn handle_tray_action(app: &AppHandle, action: TrayAction) {
+    match action {
+        TrayAction::ShowWindow => show_main_window(app),
+        // Goes through the exit hook, so stop on exit still applies
+        TrayAction::Quit => app.exit(0),
+        action => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                // Errors surface through the next sync, the tray has nowhere to show them
+                let _ = run_container_action(&app, action).await;
+                let _ = TrayService::new().refresh(&app).await;
+            });
+        }
+    }
+}
+
+async fn run_container_action(app: &AppHandle, action: TrayAction) -> Result<(), AppError> {
+    match action {
+        TrayAction::Start(id) => start_container(id, app.clone(), app.state()).await,
+        TrayAction::Stop(id) => stop_container(id, app.clone(), app.state()).await,
+        TrayAction::StopAll => {
+            let running: Vec<String> = {
+                let databases = app.state::<DatabaseStore>();
+                let db_map = databases.read().await;
+                db_map
+                    .values()
+                    .filter(|db| db.is_running())
+                    .map(|db| db.id.clone())
+                    .collect()
+            };
+
+            bulk_operation(
+                running,
+                BulkAction::Stop,
+                app.clone(),
+                app.state(),
+                app.state(),
+            )
+            .await
+            .map(|_| ())
+        }
+        TrayAction::ShowWindow | TrayAction::Quit => Ok(()),
+    }
+}
@@ -0,0 +1,60 @@
+use crate::commands::database::update_container_from_docker_args;
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+/// Settings a preset gives a new database on a Docker host, the local daemon by default
+/// The returned tuning goes into the creation request as is
+#[tauri::command]
+pub async fn get_tuning_preview(
+    db_type: String,
+    preset: TuningPreset,
+    host_id: Option<String>,
+    app: AppHandle,
+) -> Result<TuningPreview, AppError> {
+    let host = DockerService::for_host_id(&app, host_id.as_deref())?
+        .host_resources(&app)
+        .await?;
+    let preview = TuningPreview::new(&db_type, TuningSettings { preset, host });
+    if preview.settings.is_empty() {
+        return Err(AppError::Validation(format!(
+            "{} has no tuning presets",
+            db_type
+        )));
+    }
+    Ok(preview)
+}
+
+/// Size the memory settings of a database for its host again, with another preset if given
+/// The container is recreated like any update of its server arguments
+#[tauri::command]
+pub async fn retune_container(
+    container_id: String,
+    preset: Option<TuningPreset>,
+    force: Option<bool>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<DatabaseContainer, AppError> {
+    ensure_writable(&app)?;
+    let mut database = {
+        let db_map = databases.read().await;
+        db_map
+            .get(&container_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?
+    };
+    let preset = preset
+        .or(database.tuning.map(|tuning| tuning.preset))
+        .ok_or_else(|| AppError::Validation("Choose a tuning preset".to_string()))?;
+    let host = DockerService::for_database(&app, &database)?
+        .host_resources(&app)
+        .await?;
+
+    // An empty password keeps the stored one
+    database.stored_password.get_or_insert_with(String::new);
+    let mut request = ConfigService::build_run_request(&database)?;
+    request.metadata.tuning = Some(TuningSettings { preset, host });
+
+    update_container_from_docker_args(container_id, request, force, app, databases).await
+}
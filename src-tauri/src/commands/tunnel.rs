@@ -0,0 +1,177 @@
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, Manager, State};
+
+// Commands edit the tunnels concurrently, the list is read and written back as a whole
+static TUNNELS_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+async fn find_tunnel(app: &AppHandle, tunnel_id: &str) -> Result<TunnelDefinition, AppError> {
+    StorageService::new()
+        .load_tunnels_from_store(app)
+        .await?
+        .into_iter()
+        .find(|tunnel| tunnel.id == tunnel_id)
+        .ok_or_else(|| AppError::NotFound("Tunnel".to_string()))
+}
+
+/// Tunnel definitions, only the ones of `container_id` when set
+#[tauri::command]
+pub async fn list_tunnels(
+    container_id: Option<String>,
+    app: AppHandle,
+) -> Result<Vec<TunnelDefinition>, AppError> {
+    let mut tunnels = StorageService::new().load_tunnels_from_store(&app).await?;
+    if let Some(container_id) = container_id {
+        tunnels.retain(|tunnel| tunnel.database_id == container_id);
+    }
+    Ok(tunnels)
+}
+
+/// Status of every tunnel, only the ones of `container_id` when set
+#[tauri::command]
+pub async fn get_tunnel_statuses(
+    container_id: Option<String>,
+    app: AppHandle,
+) -> Result<Vec<TunnelStatus>, AppError> {
+    let tunnel_service = TunnelService::new();
+    Ok(list_tunnels(container_id, app.clone())
+        .await?
+        .iter()
+        .map(|tunnel| tunnel_service.status(&app, tunnel))
+        .collect())
+}
+
+/// Create a tunnel when its id is empty, update it otherwise
+/// An open tunnel is reopened with the new definition
+#[tauri::command]
+pub async fn save_tunnel(
+    mut tunnel: TunnelDefinition,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<TunnelDefinition, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let tunnel_service = TunnelService::new();
+    tunnel.validate()?;
+    if !databases.read().await.contains_key(&tunnel.database_id) {
+        return Err(AppError::NotFound("Container".to_string()));
+    }
+
+    let _lock = TUNNELS_LOCK.lock().await;
+    let mut tunnels = storage_service.load_tunnels_from_store(&app).await?;
+    if tunnel.id.is_empty() {
+        tunnel.id = uuid::Uuid::new_v4().to_string();
+    } else if !tunnels.iter().any(|existing| existing.id == tunnel.id) {
+        return Err(AppError::NotFound("Tunnel".to_string()));
+    }
+    if let Some(other) = tunnels
+        .iter()
+        .find(|other| other.id != tunnel.id && other.local_port == tunnel.local_port)
+    {
+        return Err(AppError::Validation(format!(
+            "Local port {} is already used by the tunnel to {}",
+            tunnel.local_port, other.ssh_host
+        )));
+    }
+
+    match tunnels.iter_mut().find(|existing| existing.id == tunnel.id) {
+        Some(existing) => *existing = tunnel.clone(),
+        None => tunnels.push(tunnel.clone()),
+    }
+    storage_service
+        .save_tunnels_to_store(&app, &tunnels)
+        .await?;
+
+    if tunnel_service.status(&app, &tunnel).state != TunnelState::Stopped {
+        tunnel_service.stop(&app, &tunnel);
+        tunnel_service.start(&app, &tunnel);
+    }
+
+    Ok(tunnel)
+}
+
+/// Close a tunnel and forget its definition
+#[tauri::command]
+pub async fn delete_tunnel(tunnel_id: String, app: AppHandle) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let tunnel = find_tunnel(&app, &tunnel_id).await?;
+    TunnelService::new().stop(&app, &tunnel);
+
+    let _lock = TUNNELS_LOCK.lock().await;
+    let mut tunnels = storage_service.load_tunnels_from_store(&app).await?;
+    tunnels.retain(|tunnel| tunnel.id != tunnel_id);
+    storage_service.save_tunnels_to_store(&app, &tunnels).await
+}
+
+/// Open a tunnel, it reconnects on its own until stopped
+#[tauri::command]
+pub async fn start_tunnel(tunnel_id: String, app: AppHandle) -> Result<TunnelStatus, AppError> {
+    ensure_writable(&app)?;
+    let tunnel = find_tunnel(&app, &tunnel_id).await?;
+    Ok(TunnelService::new().start(&app, &tunnel))
+}
+
+#[tauri::command]
+pub async fn stop_tunnel(tunnel_id: String, app: AppHandle) -> Result<TunnelStatus, AppError> {
+    let tunnel = find_tunnel(&app, &tunnel_id).await?;
+    Ok(TunnelService::new().stop(&app, &tunnel))
+}
+
+fn share_settings(app: &AppHandle) -> ShareSettings {
+    app.state::<SettingsStore>().lock().unwrap().share.clone()
+}
+
+/// Whether a database is shared and on which public address
+#[tauri::command]
+pub async fn get_share_status(
+    container_id: String,
+    app: AppHandle,
+) -> Result<ShareStatus, AppError> {
+    Ok(ShareService::new().status(&container_id, share_settings(&app).tool))
+}
+
+/// Expose a running database on a public address for `minutes`, an hour by default
+/// The tunneling tool comes from the settings, the share closes on its own when it expires
+#[tauri::command]
+pub async fn start_share(
+    container_id: String,
+    minutes: Option<u32>,
+    app: AppHandle,
+    databases: State<'_, DatabaseStore>,
+) -> Result<ShareStatus, AppError> {
+    let minutes = share_duration_minutes(minutes)?;
+    let database = databases
+        .read()
+        .await
+        .get(&container_id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+
+    if database.host.is_some() {
+        return Err(AppError::Validation(
+            "Only databases on this machine can be shared".to_string(),
+        ));
+    }
+    if !database.is_running() {
+        return Err(AppError::Validation(format!(
+            "Start {} to share it",
+            database.name
+        )));
+    }
+    // Anyone with the address can connect
+    if !database.stored_enable_auth {
+        return Err(AppError::Validation(format!(
+            "Enable authentication on {} before sharing it",
+            database.name
+        )));
+    }
+
+    ShareService::new().start(&app, &database, &share_settings(&app), minutes)
+}
+
+#[tauri::command]
+pub async fn stop_share(container_id: String, app: AppHandle) -> Result<ShareStatus, AppError> {
+    Ok(ShareService::new().stop(&app, &container_id, share_settings(&app).tool))
+}
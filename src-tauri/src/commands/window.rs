@@ -1,16 +1,24 @@
-use tauri::{AppHandle, WebviewUrl, WebviewWindowBuilder};
+use crate::services::{MetricsService, WindowService, METRICS_WINDOW_PREFIX};
+use crate::types::AppError;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// Label prefix of container edit windows
+pub const EDIT_WINDOW_PREFIX: &str = "container-edit";
 
 #[tauri::command]
-pub async fn open_container_creation_window(app: AppHandle) -> Result<(), String> {
-    let mut window_builder = WebviewWindowBuilder::new(
-        &app,
-        "container-creation",
-        WebviewUrl::App("create-container.html".into()),
-    )
-    .title("Create Database")
-    .inner_size(600.0, 500.0)
-    .center()
-    .resizable(false);
+pub async fn open_container_creation_window(app: AppHandle) -> Result<(), AppError> {
+    build_creation_window(&app, "create-container.html".to_string())
+}
+
+/// Creation window at the given page URL, deep links pass prefilled values in the query
+pub fn build_creation_window(app: &AppHandle, url: String) -> Result<(), AppError> {
+    let mut window_builder =
+        WebviewWindowBuilder::new(app, "container-creation", WebviewUrl::App(url.into()))
+            .title("Create Database")
+            .min_inner_size(600.0, 500.0)
+            .resizable(true);
+    window_builder =
+        WindowService::new().place(app, window_builder, "container-creation", (600.0, 500.0));
 
     // macOS-specific styling
     #[cfg(target_os = "macos")]
@@ -24,7 +32,7 @@ pub async fn open_container_creation_window(app: AppHandle) -> Result<(), String
         .minimizable(false)
         .maximizable(false)
         .build()
-        .map_err(|e| format!("Error creating window: {}", e))?;
+        .map_err(|e| AppError::Internal(format!("Error creating window: {}", e)))?;
 
     Ok(())
 }
@@ -33,14 +41,22 @@ pub async fn open_container_creation_window(app: AppHandle) -> Result<(), String
 pub async fn open_container_edit_window(
     app: AppHandle,
     container_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    // One window per container, opening it again brings the existing one forward
+    let label = container_window_label(EDIT_WINDOW_PREFIX, &container_id);
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
     let url = format!("edit-container.html?id={}", container_id);
-    let mut window_builder =
-        WebviewWindowBuilder::new(&app, "container-edit", WebviewUrl::App(url.into()))
-            .title("Edit Container")
-            .inner_size(600.0, 500.0)
-            .center()
-            .resizable(false);
+    let mut window_builder = WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+        .title("Edit Container")
+        .min_inner_size(600.0, 500.0)
+        .resizable(true);
+    window_builder = WindowService::new().place(&app, window_builder, &label, (600.0, 500.0));
 
     // macOS-specific styling
     #[cfg(target_os = "macos")]
@@ -54,7 +70,93 @@ pub async fn open_container_edit_window(
         .minimizable(false)
         .maximizable(false)
         .build()
-        .map_err(|e| format!("Error creating window: {}", e))?;
+        .map_err(|e| AppError::Internal(format!("Error creating window: {}", e)))?;
 
     Ok(())
 }
+
+/// Resource usage dashboard of one container, or of all of them without an id
+#[tauri::command]
+pub async fn open_metrics_window(
+    app: AppHandle,
+    container_id: Option<String>,
+) -> Result<(), AppError> {
+    let (label, url) = match &container_id {
+        Some(id) => (
+            container_window_label(METRICS_WINDOW_PREFIX, id),
+            format!("metrics.html?id={}", id),
+        ),
+        None => (
+            METRICS_WINDOW_PREFIX.to_string(),
+            "metrics.html".to_string(),
+        ),
+    };
+
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
+    let mut window_builder = WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+        .title("Metrics")
+        .min_inner_size(600.0, 400.0)
+        .resizable(true);
+    window_builder = WindowService::new().place(&app, window_builder, &label, (900.0, 600.0));
+
+    // macOS-specific styling
+    #[cfg(target_os = "macos")]
+    {
+        window_builder = window_builder
+            .hidden_title(true)
+            .title_bar_style(tauri::TitleBarStyle::Overlay);
+    }
+
+    let _window = window_builder
+        .build()
+        .map_err(|e| AppError::Internal(format!("Error creating window: {}", e)))?;
+
+    MetricsService::new().start_stream(app);
+
+    Ok(())
+}
+
+/// Label of a per-container window, characters labels do not allow are replaced
+pub fn container_window_label(prefix: &str, container_id: &str) -> String {
+    let id: String = container_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}-{}", prefix, id)
+}
+
+/// Close the windows of a removed container so their labels are free again
+pub fn close_container_windows(app: &AppHandle, container_id: &str) {
+    for prefix in [EDIT_WINDOW_PREFIX, METRICS_WINDOW_PREFIX] {
+        if let Some(window) = app.get_webview_window(&container_window_label(prefix, container_id))
+        {
+            let _ = window.destroy();
+        }
+    }
+}
+
+/// Restore the saved geometry of the main window, it is still hidden at this point
+pub fn restore_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        WindowService::new().restore(&window.as_ref().window());
+    }
+}
+
+/// Show and focus the main window, it starts hidden so headless launches never flash it
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
@@ -0,0 +1,170 @@
+use crate::commands::settings::ensure_writable;
+use crate::services::*;
+use crate::types::*;
+use tauri::{AppHandle, State};
+
+#[tauri::command]
+pub async fn get_workspaces(
+    workspaces: State<'_, WorkspaceStore>,
+) -> Result<WorkspaceConfig, AppError> {
+    let config = workspaces.lock().unwrap();
+    Ok(config.clone())
+}
+
+#[tauri::command]
+pub async fn create_workspace(
+    name: String,
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceStore>,
+) -> Result<Workspace, AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+    let name = name.trim().to_string();
+
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Workspace name cannot be empty".to_string(),
+        ));
+    }
+
+    let workspace = Workspace {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+    };
+
+    let config = {
+        let mut config = workspaces.lock().unwrap();
+        if config
+            .workspaces
+            .iter()
+            .any(|w| w.name.eq_ignore_ascii_case(&workspace.name))
+        {
+            return Err(AppError::Validation(format!(
+                "A workspace named '{}' already exists",
+                workspace.name
+            )));
+        }
+        config.workspaces.push(workspace.clone());
+        config.clone()
+    };
+
+    if let Err(store_error) = storage_service
+        .save_workspaces_to_store(&app, &config)
+        .await
+    {
+        workspaces
+            .lock()
+            .unwrap()
+            .workspaces
+            .retain(|w| w.id != workspace.id);
+        return Err(store_error);
+    }
+
+    Ok(workspace)
+}
+
+/// Make a workspace active and load its containers
+#[tauri::command]
+pub async fn switch_workspace(
+    workspace_id: String,
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceStore>,
+    databases: State<'_, DatabaseStore>,
+    companions: State<'_, CompanionStore>,
+) -> Result<Vec<DatabaseContainer>, AppError> {
+    let storage_service = StorageService::new();
+
+    let (workspace, previous_active, config) = {
+        let mut config = workspaces.lock().unwrap();
+        let workspace = config
+            .workspaces
+            .iter()
+            .find(|w| w.id == workspace_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Workspace".to_string()))?;
+        let previous_active = std::mem::replace(&mut config.active, workspace_id.clone());
+        (workspace, previous_active, config.clone())
+    };
+
+    let loaded = match storage_service.load_databases_from_store(&app).await {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            workspaces.lock().unwrap().active = previous_active;
+            return Err(error);
+        }
+    };
+
+    if let Err(store_error) = storage_service
+        .save_workspaces_to_store(&app, &config)
+        .await
+    {
+        workspaces.lock().unwrap().active = previous_active;
+        return Err(store_error);
+    }
+
+    {
+        let mut db_map = databases.write().await;
+        *db_map = loaded;
+    }
+    // Companions are reloaded from the new store by their commands
+    companions.lock().unwrap().clear();
+
+    EventService::new().workspace_changed(&app, &workspace);
+
+    let db_map = databases.read().await;
+    Ok(db_map.values().cloned().collect())
+}
+
+/// Delete an empty workspace
+#[tauri::command]
+pub async fn delete_workspace(
+    workspace_id: String,
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceStore>,
+) -> Result<(), AppError> {
+    ensure_writable(&app)?;
+    let storage_service = StorageService::new();
+
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return Err(AppError::Validation(
+            "The default workspace cannot be deleted".to_string(),
+        ));
+    }
+
+    {
+        let config = workspaces.lock().unwrap();
+        if !config.workspaces.iter().any(|w| w.id == workspace_id) {
+            return Err(AppError::NotFound("Workspace".to_string()));
+        }
+        if config.active == workspace_id {
+            return Err(AppError::Validation(
+                "Switch to another workspace before deleting this one".to_string(),
+            ));
+        }
+    }
+
+    // Containers would be left unmanaged in Docker
+    let remaining = storage_service
+        .load_workspace_databases(&app, &workspace_id)
+        .await?;
+    if !remaining.is_empty() {
+        return Err(AppError::Validation(format!(
+            "Remove the {} container(s) of this workspace before deleting it",
+            remaining.len()
+        )));
+    }
+
+    let config = {
+        let mut config = workspaces.lock().unwrap();
+        config.workspaces.retain(|w| w.id != workspace_id);
+        config.clone()
+    };
+
+    storage_service
+        .save_workspaces_to_store(&app, &config)
+        .await?;
+    storage_service
+        .remove_workspace_store(&app, &workspace_id)
+        .await
+}
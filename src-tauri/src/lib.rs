@@ -3,33 +3,305 @@ pub mod services;
 pub mod types;
 
 use commands::*;
+use services::{
+    DeepLinkService, DockerService, EventService, ImageUpdateService, MetricsHistoryService,
+    NotificationService, RecoveryService, SchedulerService, ShareService, ShutdownService,
+    SyncService, TunnelService, WindowService,
+};
+use tauri::Manager;
+use tauri_plugin_autostart::MacosLauncher;
+use tauri_plugin_deep_link::DeepLinkExt;
 use types::*;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must come first, a second launch (e.g. from a deep link) is forwarded to this one
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            show_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_autostart::init(
+            MacosLauncher::LaunchAgent,
+            Some(vec![HEADLESS_ARG]),
+        ))
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .manage(DatabaseStore::default())
+        .manage(CompanionStore::default())
+        .manage(SettingsStore::default())
+        .manage(WorkspaceStore::default())
+        .setup(|app| {
+            let handle = app.handle().clone();
+
+            // Runtimes with a nonstandard socket are found before the first docker call
+            DockerService::configure_docker_host();
+
+            // Load persisted state before any window queries it
+            tauri::async_runtime::block_on(SyncService::new().initialize(&handle))?;
+            SyncService::start_worker(handle.clone());
+            SchedulerService::start_worker(handle.clone());
+            ImageUpdateService::start_worker(handle.clone());
+            MetricsHistoryService::start_worker(handle.clone());
+
+            // Containers and volumes of creations a crash cut short
+            let recovery_handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = RecoveryService::new().recover(&recovery_handle).await {
+                    NotificationService::new().startup_failed(
+                        &recovery_handle,
+                        "recovery",
+                        format!("Failed to recover interrupted creations: {}", e),
+                    );
+                }
+            });
+
+            let tunnel_handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = TunnelService::new().start_auto(&tunnel_handle).await {
+                    NotificationService::new().startup_failed(
+                        &tunnel_handle,
+                        "tunnels",
+                        format!("Failed to open tunnels: {}", e),
+                    );
+                }
+            });
+            create_tray(&handle)?;
+
+            // A taken port must not keep the app from starting
+            let settings = handle.state::<SettingsStore>().lock().unwrap().clone();
+            if let Err(e) = apply_api_server(&handle, &settings) {
+                NotificationService::new().startup_failed(
+                    &handle,
+                    "api",
+                    format!("Failed to start the HTTP API: {}", e),
+                );
+            }
+            if let Err(e) = apply_mcp_server(&handle, &settings) {
+                NotificationService::new().startup_failed(
+                    &handle,
+                    "mcp",
+                    format!("Failed to start the MCP server: {}", e),
+                );
+            }
+
+            // Linux and Windows dev builds are not installed, register the scheme at runtime
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            app.deep_link().register_all()?;
+
+            let link_handle = handle.clone();
+            app.deep_link().on_open_url(move |event| {
+                DeepLinkService::new().handle(&link_handle, event.urls());
+            });
+
+            restore_main_window(&handle);
+
+            // Containers come up from the worker, a login launch keeps the window hidden
+            if StartupMode::from_args(std::env::args()) == StartupMode::Window {
+                show_main_window(&handle);
+            }
+
+            // A link that launched the app is handled once the window is up
+            if let Some(urls) = app.deep_link().get_current()? {
+                DeepLinkService::new().handle(&handle, urls);
+            }
+
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            // Remember where the user left the window for the next time it opens
+            if let tauri::WindowEvent::CloseRequested { .. } = event {
+                if let Err(e) = WindowService::new().save(window) {
+                    EventService::new().background_error(
+                        window.app_handle(),
+                        "window",
+                        format!("Failed to save window geometry: {}", e),
+                    );
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             get_app_version,
             create_container_from_docker_args,
+            check_container_name,
             update_container_from_docker_args,
             get_all_databases,
             start_container,
             stop_container,
             remove_container,
             get_docker_status,
+            start_docker,
             sync_containers_with_docker,
+            check_image_updates,
+            list_orphans,
+            reconcile_orphans,
             get_container_logs,
+            get_container_logs_page,
             execute_container_command,
             open_container_creation_window,
-            open_container_edit_window
+            open_container_edit_window,
+            deploy_companion,
+            deploy_adminer,
+            get_companions,
+            start_companion,
+            stop_companion,
+            remove_companion,
+            get_settings,
+            update_settings,
+            export_config,
+            import_config,
+            get_store_encryption,
+            enable_store_encryption,
+            disable_store_encryption,
+            unlock_store,
+            get_workspaces,
+            create_workspace,
+            switch_workspace,
+            delete_workspace,
+            get_groups,
+            create_group,
+            delete_group,
+            set_container_group,
+            reorder_group,
+            start_group,
+            stop_group,
+            remove_group,
+            set_container_tags,
+            set_container_favorite,
+            repair_storage,
+            filter_databases,
+            query_databases,
+            create_snapshot,
+            list_snapshots,
+            restore_snapshot,
+            delete_snapshot,
+            list_trash,
+            restore_from_trash,
+            empty_trash,
+            bulk_operation,
+            set_container_auto_start,
+            apply_autostart,
+            get_launch_at_login,
+            set_launch_at_login,
+            set_container_keep_running_on_exit,
+            get_schedules,
+            create_schedule,
+            set_schedule_enabled,
+            delete_schedule,
+            set_container_notifications_muted,
+            get_api_token,
+            regenerate_api_token,
+            get_docker_run_command,
+            get_env_snippet,
+            list_database_clients,
+            open_in_client,
+            open_container_terminal,
+            open_metrics_window,
+            list_tables,
+            fetch_table_rows,
+            get_schema,
+            export_schema,
+            export_table,
+            import_csv,
+            run_benchmark,
+            list_benchmarks,
+            delete_benchmark,
+            enable_slow_log,
+            disable_slow_log,
+            fetch_slow_log,
+            reset_slow_log,
+            get_storage_breakdown,
+            get_metrics_history,
+            list_alert_rules,
+            save_alert_rule,
+            delete_alert_rule,
+            list_alert_history,
+            get_container_details,
+            create_stack,
+            list_framework_presets,
+            get_framework_snippet,
+            list_tunnels,
+            get_tunnel_statuses,
+            save_tunnel,
+            delete_tunnel,
+            start_tunnel,
+            stop_tunnel,
+            export_kubernetes,
+            preview_container_creation,
+            preview_update,
+            get_parsed_logs,
+            browse_data_dir,
+            copy_to_container,
+            copy_from_container,
+            run_integrity_check,
+            list_integrity_checks,
+            get_env_drift,
+            reapply_container_config,
+            cleanup_images,
+            export_bundle,
+            import_bundle,
+            set_container_stop_timeout,
+            set_container_watchdog,
+            get_exec_history,
+            clear_exec_history,
+            get_log_preferences,
+            set_log_preferences,
+            list_docker_hosts,
+            save_docker_host,
+            remove_docker_host,
+            check_docker_host,
+            get_share_status,
+            start_share,
+            stop_share,
+            run_sql,
+            get_sql_guard,
+            set_sql_writes_unlocked,
+            list_snippets,
+            save_snippet,
+            delete_snippet,
+            render_snippet,
+            export_query,
+            list_sql_jobs,
+            save_sql_job,
+            delete_sql_job,
+            run_sql_job,
+            get_logical_replication,
+            set_logical_replication,
+            list_publications,
+            create_publication,
+            drop_publication,
+            list_replication_slots,
+            create_replication_slot,
+            drop_replication_slot,
+            get_tuning_preview,
+            retune_container,
+            set_container_dependencies
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // Quitting waits for managed containers to stop when enabled
+            tauri::RunEvent::ExitRequested { api, .. } => {
+                WindowService::new().save_all(app);
+                if ShutdownService::new().on_exit_requested(app) {
+                    api.prevent_exit();
+                }
+            }
+            tauri::RunEvent::Exit => {
+                TunnelService::new().stop_all();
+                ShareService::new().stop_all();
+                // Samples since the last flush would be lost otherwise
+                let _ = tauri::async_runtime::block_on(MetricsHistoryService::new().flush(app));
+            }
+            // Clicking the dock icon brings back a window hidden by a headless launch
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Reopen { .. } => show_main_window(app),
+            _ => {}
+        });
 }
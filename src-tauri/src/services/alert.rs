@@ -0,0 +1,412 @@
+use crate::services::{EventService, StorageService};
+use crate::types::*;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_shell::ShellExt;
+
+// Seconds curl waits for a webhook to answer
+const WEBHOOK_TIMEOUT_SECS: &str = "10";
+
+static EVALUATOR: LazyLock<Mutex<AlertEvaluator>> =
+    LazyLock::new(|| Mutex::new(AlertEvaluator::default()));
+// Alerts fire and resolve concurrently, the history is read and written back as a whole
+static HISTORY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Change in the state of an alert rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertTransition {
+    Fired,
+    Resolved,
+}
+
+/// Tracks how long each rule's condition has held and which rules are firing
+#[derive(Debug, Default)]
+pub struct AlertEvaluator {
+    breaching_since: HashMap<String, DateTime<Utc>>,
+    firing: HashSet<String>,
+    restart_counts: HashMap<String, u32>,
+}
+
+impl AlertEvaluator {
+    /// Record whether a rule's condition holds at `now`
+    /// Fires once the condition held for the rule's duration, resolves when it stops holding
+    pub fn observe(
+        &mut self,
+        rule: &AlertRule,
+        breached: bool,
+        now: DateTime<Utc>,
+    ) -> Option<AlertTransition> {
+        if !breached {
+            self.breaching_since.remove(&rule.id);
+            return self
+                .firing
+                .remove(&rule.id)
+                .then_some(AlertTransition::Resolved);
+        }
+
+        let since = *self.breaching_since.entry(rule.id.clone()).or_insert(now);
+        let duration = Duration::seconds(rule.duration_secs as i64);
+        if !self.firing.contains(&rule.id) && now - since >= duration {
+            self.firing.insert(rule.id.clone());
+            return Some(AlertTransition::Fired);
+        }
+        None
+    }
+
+    /// Whether Docker restarted a container since it was last seen, the first sighting only
+    /// records its restart count
+    pub fn restarted(&mut self, database_id: &str, restart_count: u32) -> bool {
+        self.restart_counts
+            .insert(database_id.to_string(), restart_count)
+            .is_some_and(|previous| restart_count > previous)
+    }
+
+    /// Forget a rule that was changed or deleted
+    pub fn forget(&mut self, rule_id: &str) {
+        self.breaching_since.remove(rule_id);
+        self.firing.remove(rule_id);
+    }
+}
+
+/// Evaluates alert rules and delivers the alerts they fire
+pub struct AlertService;
+
+impl AlertService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// History with the newest `MAX_ALERT_HISTORY` alerts
+    pub fn trim_history(mut history: Vec<AlertEvent>) -> Vec<AlertEvent> {
+        let excess = history.len().saturating_sub(MAX_ALERT_HISTORY);
+        history.drain(..excess);
+        history
+    }
+
+    /// Whether any enabled rule needs metrics samples
+    pub async fn has_metric_rules(&self, app: &AppHandle) -> bool {
+        StorageService::new()
+            .load_alert_rules_from_store(app)
+            .await
+            .is_ok_and(|rules| {
+                rules
+                    .iter()
+                    .any(|rule| rule.enabled && !rule.condition.is_event())
+            })
+    }
+
+    /// Check the metric rules of every sampled database
+    pub async fn evaluate_metrics(
+        &self,
+        app: &AppHandle,
+        readings: &[(DatabaseContainer, AlertReading)],
+    ) -> Result<(), AppError> {
+        let rules = StorageService::new()
+            .load_alert_rules_from_store(app)
+            .await?;
+        let now = Utc::now();
+
+        let mut transitions = Vec::new();
+        {
+            let mut evaluator = EVALUATOR.lock().unwrap();
+            for rule in rules.iter().filter(|rule| rule.enabled) {
+                let Some((database, reading)) = readings
+                    .iter()
+                    .find(|(database, _)| database.id == rule.database_id)
+                else {
+                    continue;
+                };
+                // A value missing from this sample leaves the rule as it was
+                let Some((value, breached)) =
+                    rule.condition.check(reading, database.max_connections)
+                else {
+                    continue;
+                };
+                if let Some(transition) = evaluator.observe(rule, breached, now) {
+                    transitions.push((rule.clone(), database.clone(), value, transition));
+                }
+            }
+        }
+
+        for (rule, database, value, transition) in transitions {
+            let result = match transition {
+                AlertTransition::Fired => self.fire(app, &rule, &database, Some(value)).await,
+                AlertTransition::Resolved => self.resolve(app, &rule).await,
+            };
+            if let Err(error) = result {
+                // Fires again on the next sample rather than staying silently firing
+                if transition == AlertTransition::Fired {
+                    EVALUATOR.lock().unwrap().forget(&rule.id);
+                }
+                self.report(app, &rule, &database, &error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fire the restart rules of databases Docker restarted since the last sync
+    pub async fn check_restarts(
+        &self,
+        app: &AppHandle,
+        previous: &HashMap<String, DatabaseContainer>,
+        states: &HashMap<String, DockerContainerState>,
+    ) -> Result<(), AppError> {
+        let restarted: Vec<&DatabaseContainer> = {
+            let mut evaluator = EVALUATOR.lock().unwrap();
+            previous
+                .values()
+                .filter(|db| {
+                    states
+                        .get(&db.name)
+                        .is_some_and(|state| evaluator.restarted(&db.id, state.restart_count))
+                })
+                .collect()
+        };
+        if restarted.is_empty() {
+            return Ok(());
+        }
+
+        let rules = StorageService::new()
+            .load_alert_rules_from_store(app)
+            .await?;
+        for database in restarted {
+            for rule in rules.iter().filter(|rule| {
+                rule.enabled
+                    && rule.database_id == database.id
+                    && rule.condition == AlertCondition::Restarted
+            }) {
+                if let Err(error) = self.fire(app, rule, database, None).await {
+                    self.report(app, rule, database, &error);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fire the integrity rules of a database whose check found corruption
+    pub async fn integrity_failed(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<(), AppError> {
+        let rules = StorageService::new()
+            .load_alert_rules_from_store(app)
+            .await?;
+        for rule in rules.iter().filter(|rule| {
+            rule.enabled
+                && rule.database_id == database.id
+                && rule.condition == AlertCondition::IntegrityFailed
+        }) {
+            if let Err(error) = self.fire(app, rule, database, None).await {
+                self.report(app, rule, database, &error);
+            }
+        }
+        Ok(())
+    }
+
+    // One rule failing leaves the others to fire
+    fn report(
+        &self,
+        app: &AppHandle,
+        rule: &AlertRule,
+        database: &DatabaseContainer,
+        error: &AppError,
+    ) {
+        EventService::new().background_error(
+            app,
+            "alerts",
+            format!(
+                "Alert '{}' of {} could not be recorded: {}",
+                rule.condition.describe(),
+                database.name,
+                error
+            ),
+        );
+    }
+
+    async fn fire(
+        &self,
+        app: &AppHandle,
+        rule: &AlertRule,
+        database: &DatabaseContainer,
+        value: Option<f64>,
+    ) -> Result<(), AppError> {
+        let alert = AlertEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            rule_id: rule.id.clone(),
+            database_id: database.id.clone(),
+            database_name: database.name.clone(),
+            message: rule.condition.describe(),
+            value,
+            fired_at: Utc::now().to_rfc3339(),
+            resolved_at: None,
+        };
+
+        {
+            let _lock = HISTORY_LOCK.lock().await;
+            let storage_service = StorageService::new();
+            let mut history = storage_service.load_alert_history_from_store(app).await?;
+            history.push(alert.clone());
+            storage_service
+                .save_alert_history_to_store(app, &Self::trim_history(history))
+                .await?;
+        }
+
+        EventService::new().alert_fired(app, &alert);
+        if rule.notify {
+            self.notify(app, &rule.condition, &alert);
+        }
+        self.post_webhook(app, rule, "fired", &alert);
+        Ok(())
+    }
+
+    async fn resolve(&self, app: &AppHandle, rule: &AlertRule) -> Result<(), AppError> {
+        let alert = {
+            let _lock = HISTORY_LOCK.lock().await;
+            let storage_service = StorageService::new();
+            let mut history = storage_service.load_alert_history_from_store(app).await?;
+            let Some(alert) = history
+                .iter_mut()
+                .rev()
+                .find(|alert| alert.rule_id == rule.id && alert.resolved_at.is_none())
+            else {
+                return Ok(());
+            };
+            alert.resolved_at = Some(Utc::now().to_rfc3339());
+            let alert = alert.clone();
+            storage_service
+                .save_alert_history_to_store(app, &history)
+                .await?;
+            alert
+        };
+
+        EventService::new().alert_resolved(app, &alert);
+        self.post_webhook(app, rule, "resolved", &alert);
+        Ok(())
+    }
+
+    fn notify(&self, app: &AppHandle, condition: &AlertCondition, alert: &AlertEvent) {
+        let (enabled, muted) = {
+            let settings = app.state::<SettingsStore>();
+            let settings = settings.lock().unwrap();
+            (
+                settings.notifications_enabled,
+                settings.muted_containers.contains(&alert.database_id),
+            )
+        };
+        if !enabled || muted {
+            return;
+        }
+
+        let _ = app
+            .notification()
+            .builder()
+            .title(format!("{}: {}", alert.database_name, alert.message))
+            .body(match (alert.value, condition) {
+                (Some(value), _) => format!("Measured {:.1}.", value),
+                (None, AlertCondition::IntegrityFailed) => {
+                    "See the integrity check history for the damaged objects.".to_string()
+                }
+                (None, _) => "Docker restarted the container.".to_string(),
+            })
+            .show();
+    }
+
+    // POST through curl, which ships with macOS, Windows and most Linux distributions
+    fn post_webhook(&self, app: &AppHandle, rule: &AlertRule, state: &str, alert: &AlertEvent) {
+        let Some(url) = rule.webhook_url.clone() else {
+            return;
+        };
+        let body = serde_json::to_string(&AlertWebhookPayload {
+            state: state.to_string(),
+            alert: alert.clone(),
+        })
+        .unwrap_or_default();
+        let alert = alert.clone();
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = app
+                .shell()
+                .command("curl")
+                .args([
+                    "-sS",
+                    "-f",
+                    "-m",
+                    WEBHOOK_TIMEOUT_SECS,
+                    "-X",
+                    "POST",
+                    "-H",
+                    "Content-Type: application/json",
+                    "--data-binary",
+                    &body,
+                    &url,
+                ])
+                .output()
+                .await;
+
+            let error = match result {
+                Ok(output) if output.status.success() => return,
+                Ok(output) => String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                Err(e) => e.to_string(),
+            };
+            EventService::new().background_error(
+                &app,
+                "alert-webhook",
+                format!(
+                    "The webhook of alert '{}' on {} failed: {}",
+                    alert.message, alert.database_name, error
+                ),
+            );
+        });
+    }
+
+    /// Create a rule when its id is empty, replace the rule with its id otherwise
+    pub async fn save_rule(
+        &self,
+        app: &AppHandle,
+        mut rule: AlertRule,
+    ) -> Result<AlertRule, AppError> {
+        rule.validate()?;
+
+        let storage_service = StorageService::new();
+        let mut rules = storage_service.load_alert_rules_from_store(app).await?;
+        if rule.id.is_empty() {
+            rule.id = uuid::Uuid::new_v4().to_string();
+            rules.push(rule.clone());
+        } else {
+            let existing = rules
+                .iter_mut()
+                .find(|existing| existing.id == rule.id)
+                .ok_or_else(|| AppError::NotFound("Alert rule".to_string()))?;
+            *existing = rule.clone();
+        }
+        storage_service
+            .save_alert_rules_to_store(app, &rules)
+            .await?;
+
+        // A changed threshold starts over
+        EVALUATOR.lock().unwrap().forget(&rule.id);
+        Ok(rule)
+    }
+
+    pub async fn delete_rule(&self, app: &AppHandle, rule_id: &str) -> Result<(), AppError> {
+        let storage_service = StorageService::new();
+        let mut rules = storage_service.load_alert_rules_from_store(app).await?;
+
+        let before = rules.len();
+        rules.retain(|rule| rule.id != rule_id);
+        if rules.len() == before {
+            return Err(AppError::NotFound("Alert rule".to_string()));
+        }
+
+        storage_service
+            .save_alert_rules_to_store(app, &rules)
+            .await?;
+        EVALUATOR.lock().unwrap().forget(rule_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use crate::services::{DockerService, EventService, StorageService};
+use crate::types::*;
+use futures_util::stream::{self, StreamExt};
+use tauri::{AppHandle, Manager};
+
+/// Starts the containers flagged to run when the app launches
+pub struct AutostartService;
+
+impl AutostartService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Containers flagged for auto-start that Docker reports as stopped, by name
+    pub fn pending(databases: &[DatabaseContainer]) -> Vec<DatabaseContainer> {
+        let mut pending: Vec<DatabaseContainer> = databases
+            .iter()
+            .filter(|db| db.auto_start && !db.is_running() && db.container_id.is_some())
+            .cloned()
+            .collect();
+        pending.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        pending
+    }
+
+    /// Start pending containers, emitting `autostart-progress` as each one finishes
+    /// A failure is reported and the next container is still started
+    pub async fn apply(&self, app: &AppHandle) -> Result<Vec<String>, AppError> {
+        let event_service = EventService::new();
+        let databases = app.state::<DatabaseStore>();
+
+        let pending = {
+            let db_map = databases.read().await;
+            Self::pending(&db_map.values().cloned().collect::<Vec<_>>())
+        };
+
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let total = pending.len();
+        let mut started = Vec::new();
+
+        // Started a few at a time, progress counts containers as they finish
+        let mut results = stream::iter(pending)
+            .map(|database| {
+                let app = app.clone();
+                async move {
+                    let real_id = database.container_id.clone().unwrap_or_default();
+                    let result = match DockerService::for_database(&app, &database) {
+                        Ok(docker_service) => docker_service
+                            .start_container(&app, &real_id)
+                            .await
+                            .map_err(|error| docker_service.host_error(error)),
+                        Err(error) => Err(error),
+                    };
+                    (database, result)
+                }
+            })
+            .buffer_unordered(BULK_CONCURRENCY);
+
+        let mut current = 0;
+        while let Some((database, result)) = results.next().await {
+            current += 1;
+
+            if result.is_ok() {
+                let mut db_map = databases.write().await;
+                if let Some(db) = db_map.get_mut(&database.id) {
+                    db.status = "running".to_string();
+                    db.last_started_at = Some(chrono::Utc::now().to_rfc3339());
+                    event_service.container_status_changed(app, db);
+                }
+                started.push(database.id.clone());
+            }
+
+            event_service.autostart_progress(
+                app,
+                &AutostartProgressPayload {
+                    id: database.id.clone(),
+                    name: database.name.clone(),
+                    current,
+                    total,
+                    error: result.err().map(|e| e.to_string()),
+                },
+            );
+        }
+
+        StorageService::new()
+            .upsert_databases_by_id(app, &started)
+            .await?;
+
+        Ok(started)
+    }
+}
@@ -0,0 +1,481 @@
+use crate::services::{
+    config_file_command, mongo_login, CompanionService, DockerService, EnvFile, EventService,
+    StorageService, MONGO_PASSWORD_VAR,
+};
+use crate::types::*;
+use std::path::Path;
+use tauri::AppHandle;
+
+const SYSBENCH_IMAGE: &str = "severalnines/sysbench";
+const MEMTIER_IMAGE: &str = "redislabs/memtier_benchmark";
+const YCSB_IMAGE: &str = "pingcap/go-ycsb";
+// Prefix of the database a run creates its tables in, it is dropped after the run
+const SCRATCH_DATABASE_PREFIX: &str = "ddm_bench_";
+// Variables the helpers read their credentials from, see `credentials`
+const SYSBENCH_CONFIG_VAR: &str = "DDM_SYSBENCH_CONFIG";
+const MEMTIER_PASSWORD_VAR: &str = "DDM_MEMTIER_PASSWORD";
+const YCSB_PROPERTIES_VAR: &str = "DDM_YCSB_PROPERTIES";
+
+// Benchmarks finish concurrently, the history is read and written back as a whole
+static HISTORY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Runs load generators in helper containers sharing the database's network namespace,
+/// so they reach it on 127.0.0.1 whatever its published port
+pub struct BenchmarkService;
+
+impl BenchmarkService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    // `docker run` of a helper attached to the database container's network
+    // An empty `entrypoint` clears the image's, for helpers started through a shell
+    fn helper(
+        database: &DatabaseContainer,
+        env_file: &Path,
+        entrypoint: Option<&str>,
+        image: &str,
+        args: &[String],
+    ) -> Vec<String> {
+        let mut step = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "--network".to_string(),
+            format!("container:{}", database.name),
+            "--env-file".to_string(),
+            env_file.to_string_lossy().to_string(),
+        ];
+        if let Some(entrypoint) = entrypoint {
+            step.extend(["--entrypoint".to_string(), entrypoint.to_string()]);
+        }
+        step.push(image.to_string());
+        step.extend(args.iter().cloned());
+        step
+    }
+
+    // `docker exec` in the database container
+    fn exec(database: &DatabaseContainer, env_file: &Path, command: &[&str]) -> Vec<String> {
+        let mut step = vec![
+            "exec".to_string(),
+            "--env-file".to_string(),
+            env_file.to_string_lossy().to_string(),
+            database.name.clone(),
+        ];
+        step.extend(command.iter().map(|arg| arg.to_string()));
+        step
+    }
+
+    // Percent-encoding of a URI user or password
+    fn uri_component(value: &str) -> String {
+        value
+            .bytes()
+            .map(|byte| {
+                if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+                    (byte as char).to_string()
+                } else {
+                    format!("%{:02X}", byte)
+                }
+            })
+            .collect()
+    }
+
+    /// Name of a new database for one run, the tools never touch application data
+    pub fn scratch_database() -> String {
+        format!(
+            "{}{}",
+            SCRATCH_DATABASE_PREFIX,
+            &uuid::Uuid::new_v4().simple().to_string()[..12]
+        )
+    }
+
+    /// Variables the steps of a plan read their credentials from, passed with `--env-file`
+    pub fn credentials(
+        database: &DatabaseContainer,
+        tool: BenchmarkTool,
+    ) -> Vec<(&'static str, String)> {
+        let credentials = DatabaseCredentials::of(database);
+        let port = CompanionService::database_container_port(&database.db_type).unwrap_or_default();
+
+        match tool {
+            BenchmarkTool::Pgbench => vec![("PGPASSWORD", credentials.password)],
+            BenchmarkTool::Sysbench => vec![
+                (
+                    SYSBENCH_CONFIG_VAR,
+                    format!("mysql-password={}", credentials.password),
+                ),
+                ("MYSQL_PWD", credentials.password),
+            ],
+            BenchmarkTool::Memtier => vec![(MEMTIER_PASSWORD_VAR, credentials.password)],
+            BenchmarkTool::Ycsb => vec![
+                (
+                    YCSB_PROPERTIES_VAR,
+                    format!(
+                        "mongodb.uri=mongodb://{}:{}@127.0.0.1:{}/?authSource=admin",
+                        Self::uri_component(&credentials.username),
+                        Self::uri_component(&credentials.password),
+                        port
+                    ),
+                ),
+                (MONGO_PASSWORD_VAR, credentials.password),
+            ],
+        }
+    }
+
+    /// Steps of a benchmark, `target` is the scratch database the tool works in
+    /// Passwords come from `env_file`, written from `credentials`
+    pub fn plan(
+        database: &DatabaseContainer,
+        tool: BenchmarkTool,
+        params: &BenchmarkParams,
+        target: &str,
+        env_file: &Path,
+    ) -> Result<BenchmarkPlan, AppError> {
+        let credentials = DatabaseCredentials::of(database);
+        let port = CompanionService::database_container_port(&database.db_type)
+            .unwrap_or_default()
+            .to_string();
+        let args =
+            |values: &[&str]| -> Vec<String> { values.iter().map(|v| v.to_string()).collect() };
+
+        match tool {
+            BenchmarkTool::Pgbench => {
+                // pgbench ships with the server, the database's own image has a matching one
+                let image = database
+                    .stored_docker_args
+                    .as_ref()
+                    .map(|docker_args| docker_args.image.clone())
+                    .unwrap_or_else(|| format!("postgres:{}", database.version));
+                let connection =
+                    args(&["-h", "127.0.0.1", "-p", &port, "-U", &credentials.username]);
+                // Local socket connections are trusted by the image, no password needed
+                let psql = |sql: String| {
+                    Self::exec(
+                        database,
+                        env_file,
+                        &[
+                            "psql",
+                            "-v",
+                            "ON_ERROR_STOP=1",
+                            "-U",
+                            &credentials.username,
+                            "-d",
+                            "template1",
+                            "-c",
+                            &sql,
+                        ],
+                    )
+                };
+                let name = SqlDialect::Postgres.quote_identifier(target);
+
+                let mut initialize =
+                    args(&["pgbench", "-i", "-q", "-s", &params.scale.to_string()]);
+                initialize.extend(connection.clone());
+                initialize.push(target.to_string());
+
+                let mut run = args(&[
+                    "pgbench",
+                    "-c",
+                    &params.clients.to_string(),
+                    "-j",
+                    &params.threads.to_string(),
+                    "-T",
+                    &params.duration_secs.to_string(),
+                ]);
+                run.extend(connection);
+                run.push(target.to_string());
+
+                Ok(BenchmarkPlan {
+                    steps: vec![
+                        psql(format!("CREATE DATABASE {}", name)),
+                        Self::helper(database, env_file, None, &image, &initialize),
+                        Self::helper(database, env_file, None, &image, &run),
+                        psql(format!("DROP DATABASE IF EXISTS {}", name)),
+                    ],
+                    measured: 2,
+                })
+            }
+            BenchmarkTool::Sysbench => {
+                let client = if database.db_type == "MariaDB" {
+                    "mariadb"
+                } else {
+                    "mysql"
+                };
+                let sql = |sql: String| {
+                    Self::exec(database, env_file, &[client, "-u", "root", "-e", &sql])
+                };
+                let name = SqlDialect::MySql.quote_identifier(target);
+
+                let test = params.workload.as_deref().unwrap_or("oltp_read_write");
+                let options = [
+                    "sysbench",
+                    test,
+                    "--db-driver=mysql",
+                    "--mysql-host=127.0.0.1",
+                    &format!("--mysql-port={}", port),
+                    "--mysql-user=root",
+                    &format!("--mysql-db={}", target),
+                    "--tables=4",
+                    &format!("--table-size={}", params.scale * 1000),
+                    &format!("--threads={}", params.clients),
+                ]
+                .map(str::to_owned);
+                // sysbench reads the password from its config file, written by the shell
+                let step = |command: &[&str]| {
+                    let mut step_args: Vec<&str> = options.iter().map(String::as_str).collect();
+                    step_args.extend(command);
+                    let command =
+                        config_file_command(SYSBENCH_CONFIG_VAR, "--config-file=", &step_args);
+                    Self::helper(database, env_file, None, SYSBENCH_IMAGE, &command)
+                };
+
+                Ok(BenchmarkPlan {
+                    steps: vec![
+                        sql(format!("CREATE DATABASE {}", name)),
+                        step(&["prepare"]),
+                        step(&[
+                            &format!("--time={}", params.duration_secs),
+                            "--report-interval=0",
+                            "run",
+                        ]),
+                        sql(format!("DROP DATABASE IF EXISTS {}", name)),
+                    ],
+                    measured: 2,
+                })
+            }
+            BenchmarkTool::Memtier => {
+                // memtier's clients are per thread
+                let clients_per_thread = params.clients.div_ceil(params.threads);
+                let mut run = args(&[
+                    "-s",
+                    "127.0.0.1",
+                    "-p",
+                    &port,
+                    "-t",
+                    &params.threads.to_string(),
+                    "-c",
+                    &clients_per_thread.to_string(),
+                    &format!("--test-time={}", params.duration_secs),
+                    &format!("--key-maximum={}", params.scale * 1000),
+                    "--hide-histogram",
+                ]);
+
+                // memtier only takes the password as an argument, the helper's shell adds it so
+                // the host's docker command line does not carry it
+                let step = if database
+                    .stored_password
+                    .as_ref()
+                    .is_some_and(|p| !p.is_empty())
+                {
+                    let mut command = args(&[
+                        "sh",
+                        "-c",
+                        &format!(
+                            "exec memtier_benchmark -a \"${}\" \"$@\"",
+                            MEMTIER_PASSWORD_VAR
+                        ),
+                        "sh",
+                    ]);
+                    command.append(&mut run);
+                    Self::helper(database, env_file, Some(""), MEMTIER_IMAGE, &command)
+                } else {
+                    Self::helper(database, env_file, None, MEMTIER_IMAGE, &run)
+                };
+
+                Ok(BenchmarkPlan {
+                    steps: vec![step],
+                    measured: 0,
+                })
+            }
+            BenchmarkTool::Ycsb => {
+                let (read, update) = match params.workload.as_deref().unwrap_or("a") {
+                    "a" => ("0.5", "0.5"),
+                    "b" => ("0.95", "0.05"),
+                    "c" => ("1", "0"),
+                    other => {
+                        return Err(AppError::Validation(format!(
+                            "Unknown YCSB workload '{}', use a, b or c",
+                            other
+                        )))
+                    }
+                };
+                let properties = args(&[
+                    "mongodb",
+                    "-p",
+                    &format!("mongodb.namespace={}.usertable", target),
+                    "-p",
+                    &format!("recordcount={}", params.scale * 1000),
+                ]);
+                // The connection URI holds the password, it is read from a properties file
+                let ycsb = |command: Vec<String>| {
+                    let mut step_args = vec!["/go-ycsb"];
+                    step_args.extend(command.iter().map(String::as_str));
+                    let command = config_file_command(YCSB_PROPERTIES_VAR, "-P ", &step_args);
+                    Self::helper(database, env_file, Some(""), YCSB_IMAGE, &command)
+                };
+
+                let mut load = args(&["load"]);
+                load.extend(properties.clone());
+                load.extend(args(&["-p", &format!("threadcount={}", params.threads)]));
+
+                let mut run = args(&["run"]);
+                run.extend(properties);
+                run.extend(args(&[
+                    "-p",
+                    &format!("threadcount={}", params.clients),
+                    "-p",
+                    "operationcount=1000000000",
+                    "-p",
+                    &format!("maxexecutiontime={}", params.duration_secs),
+                    "-p",
+                    &format!("readproportion={}", read),
+                    "-p",
+                    &format!("updateproportion={}", update),
+                    "-p",
+                    "requestdistribution=zipfian",
+                ]));
+
+                let drop = Self::exec(
+                    database,
+                    env_file,
+                    &[
+                        "mongosh",
+                        "--quiet",
+                        "--eval",
+                        &format!(
+                            "{}db.getSiblingDB({}).dropDatabase()",
+                            mongo_login(&credentials.username),
+                            serde_json::Value::from(target)
+                        ),
+                    ],
+                );
+
+                Ok(BenchmarkPlan {
+                    steps: vec![ycsb(load), ycsb(run), drop],
+                    measured: 1,
+                })
+            }
+        }
+    }
+
+    /// Run a benchmark against a running database and add it to the container's history
+    /// The tools work in a scratch database, dropped whether the run succeeded or not
+    pub async fn run(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        params: &BenchmarkParams,
+    ) -> Result<BenchmarkRun, AppError> {
+        params.validate()?;
+        let tool = match params.tool {
+            Some(tool) => tool,
+            None => BenchmarkTool::for_db_type(&database.db_type).ok_or_else(|| {
+                AppError::Validation(format!("{} has no benchmark tool", database.db_type))
+            })?,
+        };
+        if !tool.supports(&database.db_type) {
+            return Err(AppError::Validation(format!(
+                "{:?} cannot benchmark {}",
+                tool, database.db_type
+            )));
+        }
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to benchmark it",
+                database.name
+            )));
+        }
+
+        let credentials = Self::credentials(database, tool);
+        let vars: Vec<(&str, &str)> = credentials
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect();
+        // Kept until every step ran
+        let env_file = EnvFile::create(&vars)?;
+        let target = Self::scratch_database();
+        let plan = Self::plan(database, tool, params, &target, env_file.path())?;
+
+        let started_at = chrono::Utc::now().to_rfc3339();
+        let docker_service = DockerService::for_database(app, database)?;
+        let mut report = String::new();
+        let mut failure = None;
+        for (index, step) in plan.steps.iter().enumerate() {
+            // Once a step failed, only the cleanup after the measurement runs
+            if failure.is_some() && index <= plan.measured {
+                continue;
+            }
+            match docker_service.run_container(app, step).await {
+                Ok(output) if index == plan.measured => report = output,
+                Ok(_) => {}
+                Err(error) if index <= plan.measured => failure = Some(error),
+                Err(error) => EventService::new().background_error(
+                    app,
+                    "benchmark",
+                    format!(
+                        "The benchmark database {} of {} could not be removed: {}",
+                        target, database.name, error
+                    ),
+                ),
+            }
+        }
+        if let Some(error) = failure {
+            return Err(error);
+        }
+
+        let run = BenchmarkRun {
+            id: uuid::Uuid::new_v4().to_string(),
+            database_id: database.id.clone(),
+            tool,
+            params: params.clone(),
+            started_at,
+            finished_at: chrono::Utc::now().to_rfc3339(),
+            metrics: BenchmarkMetrics::parse(tool, &report),
+            output: benchmark_output_tail(&report),
+        };
+
+        let _lock = HISTORY_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut history = storage_service.load_benchmarks_from_store(app).await?;
+        history.push(run.clone());
+        storage_service
+            .save_benchmarks_to_store(app, &Self::trim_history(history, &database.id))
+            .await?;
+
+        Ok(run)
+    }
+
+    /// Remove a run from the history
+    pub async fn delete(&self, app: &AppHandle, benchmark_id: &str) -> Result<(), AppError> {
+        let _lock = HISTORY_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut runs = storage_service.load_benchmarks_from_store(app).await?;
+
+        let before = runs.len();
+        runs.retain(|run| run.id != benchmark_id);
+        if runs.len() == before {
+            return Err(AppError::NotFound("Benchmark".to_string()));
+        }
+
+        storage_service.save_benchmarks_to_store(app, &runs).await
+    }
+
+    /// History with only the newest `MAX_BENCHMARK_HISTORY` runs of `database_id`
+    pub fn trim_history(history: Vec<BenchmarkRun>, database_id: &str) -> Vec<BenchmarkRun> {
+        let count = history
+            .iter()
+            .filter(|run| run.database_id == database_id)
+            .count();
+        let mut excess = count.saturating_sub(MAX_BENCHMARK_HISTORY);
+
+        history
+            .into_iter()
+            .filter(|run| {
+                if excess > 0 && run.database_id == database_id {
+                    excess -= 1;
+                    return false;
+                }
+                true
+            })
+            .collect()
+    }
+}
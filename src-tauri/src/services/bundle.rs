@@ -0,0 +1,362 @@
+use crate::services::{ConfigService, DockerService, EventService, SnapshotService};
+use crate::types::*;
+use std::path::Path;
+use tauri::AppHandle;
+
+// Work directory inside the helper container
+const BUNDLE_DIR: &str = "/bundle";
+const EXPORT_CONTEXT: &str = "Failed to export the bundle";
+const IMPORT_CONTEXT: &str = "Failed to import the bundle";
+
+// Writes the configuration and archives the data, then prints `<sha256> <size> <name>` per file
+const PACK_SCRIPT: &str = "set -e; cd /bundle
+printf '%s' \"$DDM_CONFIG\" > database.json
+tar -czf data.tar.gz -C /data .
+for file in database.json data.tar.gz; do
+  printf '%s %s %s\\n' \"$(sha256sum \"$file\" | cut -d ' ' -f1)\" \"$(stat -c %s \"$file\")\" \"$file\"
+done";
+
+// Adds the manifest and joins the files into the bundle
+const SEAL_SCRIPT: &str = "set -e; cd /bundle
+printf '%s' \"$DDM_MANIFEST\" > manifest.json
+tar -cf bundle.tar manifest.json database.json data.tar.gz
+rm data.tar.gz";
+
+// Unpacks a bundle copied into the helper, then prints `<sha256> <size> <name>` per file
+const UNPACK_SCRIPT: &str = "set -e; cd /bundle
+tar -xf bundle.tar
+rm bundle.tar
+for file in database.json data.tar.gz; do
+  if [ -f \"$file\" ]; then
+    printf '%s %s %s\\n' \"$(sha256sum \"$file\" | cut -d ' ' -f1)\" \"$(stat -c %s \"$file\")\" \"$file\"
+  fi
+done";
+
+/// Bundle unpacked in a helper container, removed with `close`
+pub struct OpenedBundle {
+    helper: String,
+    pub manifest: BundleManifest,
+    // Exported database, with its credentials
+    pub database: DatabaseContainer,
+}
+
+/// Packs a database into a single archive that can be moved to another machine
+/// The archive is built in a helper container, so only Docker is needed on the host
+/// Bundles are exported from the database's Docker host and imported on the local one
+pub struct BundleService;
+
+impl BundleService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn helper_name(database: &DatabaseContainer) -> String {
+        let short_id: String = database.id.chars().take(8).collect();
+        format!("ddm-bundle-{}", short_id)
+    }
+
+    /// Write a bundle with the configuration, a snapshot of the data and a manifest to `path`
+    /// The container is only stopped while its snapshot is taken
+    /// Credentials are included, the data holds them anyway
+    pub async fn export(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        path: &Path,
+    ) -> Result<BundleExport, AppError> {
+        if path.is_dir() {
+            return Err(AppError::Validation(format!(
+                "{} is a directory, choose a file name for the bundle",
+                path.display()
+            )));
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            if !parent.is_dir() {
+                return Err(AppError::NotFound(parent.display().to_string()));
+            }
+        }
+        if database.stored_docker_args.is_none() {
+            return Err(AppError::Validation(format!(
+                "'{}' was created before its Docker arguments were stored, edit and save it first",
+                database.name
+            )));
+        }
+
+        let snapshot_service = SnapshotService::new();
+        let snapshot = snapshot_service
+            .create(app, database, Some("Bundle export".to_string()))
+            .await?;
+        let helper = Self::helper_name(database);
+        let docker_service = DockerService::for_database(app, database)?;
+
+        let result = self
+            .pack(app, &docker_service, database, &snapshot, &helper, path)
+            .await;
+
+        let _ = docker_service
+            .stdout(app, EXPORT_CONTEXT, &["rm", "-f", &helper])
+            .await;
+        let _ = snapshot_service.delete(app, &snapshot).await;
+        let manifest = result?;
+
+        Ok(BundleExport {
+            path: path.display().to_string(),
+            manifest,
+        })
+    }
+
+    async fn pack(
+        &self,
+        app: &AppHandle,
+        docker_service: &DockerService,
+        database: &DatabaseContainer,
+        snapshot: &Snapshot,
+        helper: &str,
+        path: &Path,
+    ) -> Result<BundleManifest, AppError> {
+        // Left over by an export that was interrupted
+        let _ = docker_service
+            .stdout(app, EXPORT_CONTEXT, &["rm", "-f", helper])
+            .await;
+        docker_service
+            .stdout(
+                app,
+                EXPORT_CONTEXT,
+                &[
+                    "run",
+                    "-d",
+                    "--name",
+                    helper,
+                    "--network",
+                    "none",
+                    "-v",
+                    &format!("{}:/data:ro", snapshot.volume),
+                    "alpine:latest",
+                    "sh",
+                    "-c",
+                    &format!("mkdir -p {} && exec tail -f /dev/null", BUNDLE_DIR),
+                ],
+            )
+            .await?;
+
+        let config =
+            serde_json::to_string(&ConfigService::portable_database(database.clone(), true))?;
+        let output = docker_service
+            .stdout(
+                app,
+                EXPORT_CONTEXT,
+                &[
+                    "exec",
+                    "-e",
+                    &format!("DDM_CONFIG={}", config),
+                    helper,
+                    "sh",
+                    "-c",
+                    PACK_SCRIPT,
+                ],
+            )
+            .await?;
+        let files: Vec<BundleFile> = output.lines().filter_map(BundleFile::parse_line).collect();
+        if files.len() != 2 {
+            return Err(AppError::Internal(format!(
+                "Unexpected output while packing the bundle: {}",
+                output.trim()
+            )));
+        }
+
+        let manifest =
+            BundleManifest::new(&app.package_info().version.to_string(), database, files);
+        let manifest_json = serde_json::to_string_pretty(&manifest)?;
+        docker_service
+            .stdout(
+                app,
+                EXPORT_CONTEXT,
+                &[
+                    "exec",
+                    "-e",
+                    &format!("DDM_MANIFEST={}", manifest_json),
+                    helper,
+                    "sh",
+                    "-c",
+                    SEAL_SCRIPT,
+                ],
+            )
+            .await?;
+
+        docker_service
+            .stdout(
+                app,
+                EXPORT_CONTEXT,
+                &[
+                    "cp",
+                    &format!("{}:{}/bundle.tar", helper, BUNDLE_DIR),
+                    &path.display().to_string(),
+                ],
+            )
+            .await?;
+
+        Ok(manifest)
+    }
+
+    /// Copy a bundle into a helper container, unpack it and check it against its manifest
+    pub async fn open(&self, app: &AppHandle, path: &Path) -> Result<OpenedBundle, AppError> {
+        if !path.is_file() {
+            return Err(AppError::NotFound(path.display().to_string()));
+        }
+
+        let short_id: String = uuid::Uuid::new_v4().to_string().chars().take(8).collect();
+        let helper = format!("ddm-bundle-import-{}", short_id);
+        let docker_service = DockerService::new();
+        // The work directory is an anonymous volume, the restore container mounts it
+        docker_service
+            .stdout(
+                app,
+                IMPORT_CONTEXT,
+                &[
+                    "run",
+                    "-d",
+                    "--name",
+                    &helper,
+                    "--network",
+                    "none",
+                    "-v",
+                    BUNDLE_DIR,
+                    "alpine:latest",
+                    "tail",
+                    "-f",
+                    "/dev/null",
+                ],
+            )
+            .await?;
+
+        match self.unpack(app, &helper, path).await {
+            Ok((manifest, database)) => Ok(OpenedBundle {
+                helper,
+                manifest,
+                database,
+            }),
+            Err(error) => {
+                let _ = docker_service
+                    .stdout(app, IMPORT_CONTEXT, &["rm", "-f", "-v", &helper])
+                    .await;
+                Err(error)
+            }
+        }
+    }
+
+    async fn unpack(
+        &self,
+        app: &AppHandle,
+        helper: &str,
+        path: &Path,
+    ) -> Result<(BundleManifest, DatabaseContainer), AppError> {
+        let docker_service = DockerService::new();
+        docker_service
+            .stdout(
+                app,
+                IMPORT_CONTEXT,
+                &[
+                    "cp",
+                    &path.display().to_string(),
+                    &format!("{}:{}/bundle.tar", helper, BUNDLE_DIR),
+                ],
+            )
+            .await?;
+
+        let not_a_bundle =
+            |_| AppError::Validation(format!("{} is not a database bundle", path.display()));
+        let output = docker_service
+            .stdout(
+                app,
+                IMPORT_CONTEXT,
+                &["exec", helper, "sh", "-c", UNPACK_SCRIPT],
+            )
+            .await
+            .map_err(not_a_bundle)?;
+        let files: Vec<BundleFile> = output.lines().filter_map(BundleFile::parse_line).collect();
+
+        let manifest_json = docker_service
+            .stdout(
+                app,
+                IMPORT_CONTEXT,
+                &[
+                    "exec",
+                    helper,
+                    "cat",
+                    &format!("{}/{}", BUNDLE_DIR, BUNDLE_MANIFEST_FILE),
+                ],
+            )
+            .await
+            .map_err(not_a_bundle)?;
+        let manifest: BundleManifest = serde_json::from_str(&manifest_json).map_err(|e| {
+            AppError::Validation(format!("The bundle manifest cannot be read: {}", e))
+        })?;
+        manifest.verify(&files)?;
+
+        let config_json = docker_service
+            .stdout(
+                app,
+                IMPORT_CONTEXT,
+                &[
+                    "exec",
+                    helper,
+                    "cat",
+                    &format!("{}/{}", BUNDLE_DIR, BUNDLE_CONFIG_FILE),
+                ],
+            )
+            .await?;
+        let database: DatabaseContainer = serde_json::from_str(&config_json).map_err(|e| {
+            AppError::Validation(format!("The bundled configuration cannot be read: {}", e))
+        })?;
+
+        Ok((manifest, database))
+    }
+
+    /// Extract the bundled data into `volume`, which should be empty
+    pub async fn restore_data(
+        &self,
+        app: &AppHandle,
+        bundle: &OpenedBundle,
+        volume: &str,
+    ) -> Result<(), AppError> {
+        let docker_service = DockerService::new();
+        docker_service
+            .stdout(
+                app,
+                IMPORT_CONTEXT,
+                &[
+                    "run",
+                    "--rm",
+                    "--network",
+                    "none",
+                    "--volumes-from",
+                    &bundle.helper,
+                    "-v",
+                    &format!("{}:/data", volume),
+                    "alpine:latest",
+                    "tar",
+                    "-xzf",
+                    &format!("{}/{}", BUNDLE_DIR, BUNDLE_DATA_FILE),
+                    "-C",
+                    "/data",
+                ],
+            )
+            .await
+            .map(|_| ())
+    }
+
+    /// Remove the helper container and the unpacked files
+    pub async fn close(&self, app: &AppHandle, bundle: &OpenedBundle) {
+        let docker_service = DockerService::new();
+        if let Err(error) = docker_service
+            .stdout(app, IMPORT_CONTEXT, &["rm", "-f", "-v", &bundle.helper])
+            .await
+        {
+            EventService::new().background_error(
+                app,
+                "bundle",
+                format!("Failed to remove {}: {}", bundle.helper, error),
+            );
+        }
+    }
+}
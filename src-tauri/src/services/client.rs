@@ -0,0 +1,318 @@
+use crate::services::{
+    mongo_login, ConnectionService, DockerService, EnvFile, TerminalService, MONGO_PASSWORD_VAR,
+};
+use crate::types::*;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_shell::ShellExt;
+
+// How long the credentials of a terminal client stay on disk, the terminal runs docker by then
+const CLI_ENV_FILE_SECS: u64 = 60;
+
+/// Detects and launches external database clients
+pub struct ClientService;
+
+impl ClientService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Clients installed on this machine, the in-container shell only needs Docker
+    pub async fn installed(&self, app: &AppHandle) -> Vec<DatabaseClient> {
+        let path = DockerService::new().get_enriched_path(app).await;
+
+        DatabaseClient::ALL
+            .into_iter()
+            .filter(|client| {
+                *client == DatabaseClient::Cli || Self::locate(*client, &path).is_some()
+            })
+            .collect()
+    }
+
+    /// Open a database in a client
+    /// Returns true when something was copied for the user to paste: the connection string
+    /// for clients without connection arguments, the password for the others
+    pub async fn open(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        client: DatabaseClient,
+    ) -> Result<bool, AppError> {
+        if !client.supports(&database.db_type) {
+            return Err(AppError::Validation(format!(
+                "{:?} does not support {}",
+                client, database.db_type
+            )));
+        }
+
+        let docker_service = DockerService::for_database(app, database)?;
+        let connection = ConnectionService::on_host(docker_service.connection_host());
+
+        if client == DatabaseClient::Cli {
+            let credentials = Self::cli_credentials(database);
+            let env_file = if credentials.is_empty() {
+                None
+            } else {
+                let vars: Vec<(&str, &str)> = credentials
+                    .iter()
+                    .map(|(key, value)| (*key, value.as_str()))
+                    .collect();
+                Some(EnvFile::create(&vars)?)
+            };
+            let mut command = Self::cli_command(database, env_file.as_ref().map(EnvFile::path))
+                .ok_or_else(|| {
+                    AppError::Validation(format!("{} has no command-line client", database.db_type))
+                })?;
+            command.splice(1..1, docker_service.host_args());
+            TerminalService::new().open(app, &command).await?;
+
+            // The terminal starts on its own, the file is kept until it had time to run docker
+            if let Some(env_file) = env_file {
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(CLI_ENV_FILE_SECS)).await;
+                    drop(env_file);
+                });
+            }
+            return Ok(false);
+        }
+
+        let path = DockerService::new().get_enriched_path(app).await;
+        let location = Self::locate(client, &path)
+            .ok_or_else(|| AppError::NotFound(format!("{:?}", client)))?;
+
+        // Arguments of other processes are readable by any user, the password is pasted instead
+        let (client_args, copied) = match client {
+            DatabaseClient::TablePlus => (
+                vec![connection.client_url(database)],
+                database.stored_password.clone(),
+            ),
+            DatabaseClient::DBeaver => (
+                vec![
+                    "-con".to_string(),
+                    Self::dbeaver_connection(&connection, database)?,
+                ],
+                database.stored_password.clone(),
+            ),
+            _ => (Vec::new(), Some(connection.connection_string(database))),
+        };
+        let copied = copied.filter(|text| !text.is_empty());
+
+        if let Some(text) = &copied {
+            app.clipboard()
+                .write_text(text.clone())
+                .map_err(|e| AppError::Internal(format!("Failed to copy to clipboard: {}", e)))?;
+        }
+
+        let (program, args) = Self::launch_command(client, &location, client_args);
+        app.shell()
+            .command(&program)
+            .args(&args)
+            .env("PATH", &path)
+            .spawn()
+            .map_err(|e| AppError::Internal(format!("Failed to open {:?}: {}", client, e)))?;
+
+        Ok(copied.is_some())
+    }
+
+    /// Variables the engine's shell reads its password from
+    pub fn cli_credentials(database: &DatabaseContainer) -> Vec<(&'static str, String)> {
+        let password = database.stored_password.clone().unwrap_or_default();
+        let key = match database.db_type.as_str() {
+            "PostgreSQL" => "PGPASSWORD",
+            "MySQL" | "MariaDB" => "MYSQL_PWD",
+            "MongoDB" if database.stored_enable_auth => MONGO_PASSWORD_VAR,
+            "Redis" if !password.is_empty() => "REDISCLI_AUTH",
+            "SQLServer" => "SQLCMDPASSWORD",
+            _ => return Vec::new(),
+        };
+        vec![(key, password)]
+    }
+
+    /// The engine's shell run inside the container
+    /// Credentials come from `env_file`, written from `cli_credentials`
+    pub fn cli_command(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+    ) -> Option<Vec<String>> {
+        let container = database.container_id.clone()?;
+        let user = database.stored_username.clone();
+        let name = database.stored_database_name.clone();
+
+        let shell: Vec<String> = match database.db_type.as_str() {
+            "PostgreSQL" => vec![
+                "psql".to_string(),
+                "-U".to_string(),
+                user.unwrap_or_else(|| "postgres".to_string()),
+                "-d".to_string(),
+                name.unwrap_or_else(|| "postgres".to_string()),
+            ],
+            "MySQL" | "MariaDB" => {
+                let program = if database.db_type == "MariaDB" {
+                    "mariadb"
+                } else {
+                    "mysql"
+                };
+                let mut shell = vec![
+                    program.to_string(),
+                    "-u".to_string(),
+                    user.unwrap_or_else(|| "root".to_string()),
+                ];
+                shell.extend(name);
+                shell
+            }
+            "MongoDB" => {
+                let mut shell = vec!["mongosh".to_string()];
+                if database.stored_enable_auth {
+                    // Logs in from the environment, then stays in the shell
+                    shell.extend([
+                        "--eval".to_string(),
+                        mongo_login(&user.unwrap_or_else(|| "admin".to_string())),
+                        "--shell".to_string(),
+                    ]);
+                }
+                shell.extend(name);
+                shell
+            }
+            "Redis" => vec!["redis-cli".to_string()],
+            "SQLServer" => vec![
+                "/opt/mssql-tools18/bin/sqlcmd".to_string(),
+                "-S".to_string(),
+                "localhost".to_string(),
+                "-U".to_string(),
+                user.unwrap_or_else(|| "sa".to_string()),
+                "-C".to_string(),
+            ],
+            _ => return None,
+        };
+
+        let mut command = vec!["docker".to_string(), "exec".to_string(), "-it".to_string()];
+        if let Some(env_file) = env_file {
+            command.extend([
+                "--env-file".to_string(),
+                env_file.to_string_lossy().to_string(),
+            ]);
+        }
+        command.push(container);
+        command.extend(shell);
+        Some(command)
+    }
+
+    /// Connection spec for DBeaver's `-con` argument, without the password
+    /// DBeaver splits the spec on `|` and has no escape for it
+    pub fn dbeaver_connection(
+        connection: &ConnectionService,
+        database: &DatabaseContainer,
+    ) -> Result<String, AppError> {
+        let driver = match database.db_type.as_str() {
+            "PostgreSQL" => "postgresql",
+            "MariaDB" => "mariadb",
+            "SQLServer" => "sqlserver",
+            _ => "mysql",
+        };
+
+        let mut fields = vec![
+            ("driver", driver.to_string()),
+            ("host", connection.host().to_string()),
+            ("port", database.port.to_string()),
+            ("name", database.name.clone()),
+        ];
+        fields.extend(
+            database
+                .stored_database_name
+                .clone()
+                .map(|name| ("database", name)),
+        );
+        fields.extend(database.stored_username.clone().map(|user| ("user", user)));
+
+        if let Some((field, _)) = fields.iter().find(|(_, value)| value.contains('|')) {
+            return Err(AppError::Validation(format!(
+                "DBeaver cannot open a connection whose {} contains '|'",
+                field
+            )));
+        }
+
+        let mut spec: Vec<String> = fields
+            .into_iter()
+            .map(|(field, value)| format!("{}={}", field, value))
+            .collect();
+        spec.push("connect=true".to_string());
+        Ok(spec.join("|"))
+    }
+
+    /// Where a client is installed: an app name on macOS, an executable elsewhere
+    #[cfg(target_os = "macos")]
+    fn locate(client: DatabaseClient, _path: &str) -> Option<PathBuf> {
+        let app = match client {
+            DatabaseClient::TablePlus => "TablePlus.app",
+            DatabaseClient::DBeaver => "DBeaver.app",
+            DatabaseClient::PgAdmin => "pgAdmin 4.app",
+            DatabaseClient::Beekeeper => "Beekeeper Studio.app",
+            DatabaseClient::Cli => return None,
+        };
+
+        let mut roots = vec![PathBuf::from("/Applications")];
+        roots.extend(dirs::home_dir().map(|home| home.join("Applications")));
+        roots
+            .into_iter()
+            .map(|dir| dir.join(app))
+            .find(|candidate| candidate.exists())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn locate(client: DatabaseClient, _path: &str) -> Option<PathBuf> {
+        let candidates: &[&str] = match client {
+            DatabaseClient::TablePlus => &["TablePlus\\TablePlus.exe"],
+            DatabaseClient::DBeaver => &["DBeaver\\dbeaver.exe"],
+            DatabaseClient::PgAdmin => &["pgAdmin 4\\runtime\\pgAdmin4.exe"],
+            DatabaseClient::Beekeeper => &[
+                "Beekeeper Studio\\Beekeeper Studio.exe",
+                "beekeeper-studio\\Beekeeper Studio.exe",
+            ],
+            DatabaseClient::Cli => return None,
+        };
+
+        let roots: Vec<PathBuf> = ["ProgramFiles", "ProgramFiles(x86)"]
+            .iter()
+            .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+            .chain(dirs::data_local_dir().map(|dir| dir.join("Programs")))
+            .collect();
+
+        roots
+            .iter()
+            .flat_map(|root| candidates.iter().map(move |c| root.join(c)))
+            .find(|candidate| candidate.is_file())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn locate(client: DatabaseClient, path: &str) -> Option<PathBuf> {
+        let program = match client {
+            DatabaseClient::TablePlus => "tableplus",
+            DatabaseClient::DBeaver => "dbeaver",
+            DatabaseClient::PgAdmin => "pgadmin4",
+            DatabaseClient::Beekeeper => "beekeeper-studio",
+            DatabaseClient::Cli => return None,
+        };
+
+        TerminalService::find_in_path(program, path).then(|| PathBuf::from(program))
+    }
+
+    fn launch_command(
+        client: DatabaseClient,
+        location: &std::path::Path,
+        args: Vec<String>,
+    ) -> (String, Vec<String>) {
+        if cfg!(target_os = "macos") {
+            let mut open_args = vec!["-a".to_string(), location.display().to_string()];
+            // TablePlus receives the URL like an opened document, others as arguments
+            if client != DatabaseClient::TablePlus && !args.is_empty() {
+                open_args.push("--args".to_string());
+            }
+            open_args.extend(args);
+            ("open".to_string(), open_args)
+        } else {
+            (location.display().to_string(), args)
+        }
+    }
+}
@@ -0,0 +1,499 @@
+use crate::services::{write_private, ConnectionService, DockerService};
+use crate::types::*;
+use serde_json::json;
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+
+// Pinned, the config files `deploy` writes follow the format of these releases
+const PGBOUNCER_IMAGE: &str = "edoburu/pgbouncer:v1.24.1-p1";
+const PROXYSQL_IMAGE: &str = "proxysql/proxysql:2.7.3";
+
+pub struct CompanionService;
+
+impl CompanionService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Name of the network shared by a database and its companions
+    pub fn network_name(database_name: &str) -> String {
+        format!("{}-net", database_name)
+    }
+
+    pub fn companion_name(database_name: &str, kind: &str) -> String {
+        format!("{}-{}", database_name, kind)
+    }
+
+    /// Port the database listens on inside its container
+    pub fn database_container_port(db_type: &str) -> Option<i32> {
+        match db_type {
+            "PostgreSQL" => Some(5432),
+            "MySQL" | "MariaDB" => Some(3306),
+            "MongoDB" => Some(27017),
+            "Redis" => Some(6379),
+            "SQLServer" => Some(1433),
+            _ => None,
+        }
+    }
+
+    /// Database types each companion tool can connect to
+    pub fn supports(kind: &str, db_type: &str) -> bool {
+        match kind {
+            "pgadmin" => db_type == "PostgreSQL",
+            "phpmyadmin" => db_type == "MySQL" || db_type == "MariaDB",
+            "mongo-express" => db_type == "MongoDB",
+            "redisinsight" => db_type == "Redis",
+            "adminer" => matches!(db_type, "PostgreSQL" | "MySQL" | "MariaDB" | "SQLServer"),
+            "pgbouncer" => db_type == "PostgreSQL",
+            "proxysql" => db_type == "MySQL" || db_type == "MariaDB",
+            _ => false,
+        }
+    }
+
+    /// Whether the companion is a connection pooler clients connect through instead of a web tool
+    pub fn is_pooler(kind: &str) -> bool {
+        matches!(kind, "pgbouncer" | "proxysql")
+    }
+
+    /// Host port of the pooler deployed in front of a database, None without one
+    pub fn pooled_port(
+        companions: &HashMap<String, CompanionContainer>,
+        database_id: &str,
+    ) -> Option<i32> {
+        companions
+            .values()
+            .find(|companion| {
+                companion.database_id == database_id && Self::is_pooler(&companion.kind)
+            })
+            .map(|companion| companion.port)
+    }
+
+    /// Server connections a pooler opens, half of what the database accepts so direct clients still fit
+    pub fn pooler_pool_size(database: &DatabaseContainer) -> i32 {
+        (database.max_connections / 2).clamp(1, 100)
+    }
+
+    /// Ask the OS for a free TCP port on the loopback interface
+    pub fn find_free_port() -> Result<i32, AppError> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")
+            .map_err(|e| AppError::Io(format!("Failed to find a free port: {}", e)))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| AppError::Io(format!("Failed to find a free port: {}", e)))?
+            .port();
+
+        Ok(port as i32)
+    }
+
+    /// Build the Docker run arguments of a companion pointing at the database
+    pub fn build_companion_args(
+        kind: &str,
+        database: &DatabaseContainer,
+        host_port: i32,
+    ) -> Result<DockerRunArgs, AppError> {
+        if !Self::supports(kind, &database.db_type) {
+            return Err(AppError::Validation(format!(
+                "{} cannot be linked to a {} database",
+                kind, database.db_type
+            )));
+        }
+
+        let db_port = Self::database_container_port(&database.db_type).ok_or_else(|| {
+            AppError::Validation(format!("Unsupported database type: {}", database.db_type))
+        })?;
+        let password = database.stored_password.clone().unwrap_or_default();
+        let mut env_vars = HashMap::new();
+
+        let (image, container_port) = match kind {
+            "pgadmin" => {
+                env_vars.insert(
+                    "PGADMIN_DEFAULT_EMAIL".to_string(),
+                    "admin@example.com".to_string(),
+                );
+                env_vars.insert("PGADMIN_DEFAULT_PASSWORD".to_string(), "admin".to_string());
+                // Desktop mode skips the pgAdmin login screen
+                env_vars.insert(
+                    "PGADMIN_CONFIG_SERVER_MODE".to_string(),
+                    "False".to_string(),
+                );
+                env_vars.insert(
+                    "PGADMIN_CONFIG_MASTER_PASSWORD_REQUIRED".to_string(),
+                    "False".to_string(),
+                );
+                ("dpage/pgadmin4:latest", 80)
+            }
+            "phpmyadmin" => {
+                env_vars.insert("PMA_HOST".to_string(), database.name.clone());
+                env_vars.insert("PMA_PORT".to_string(), db_port.to_string());
+                env_vars.insert(
+                    "PMA_USER".to_string(),
+                    database
+                        .stored_username
+                        .clone()
+                        .unwrap_or_else(|| "root".to_string()),
+                );
+                env_vars.insert("PMA_PASSWORD".to_string(), password);
+                ("phpmyadmin:latest", 80)
+            }
+            "mongo-express" => {
+                let url = match (&database.stored_username, database.stored_enable_auth) {
+                    (Some(username), true) => format!(
+                        "mongodb://{}:{}@{}:{}/?authSource=admin",
+                        ConnectionService::encode(username),
+                        ConnectionService::encode(&password),
+                        database.name,
+                        db_port
+                    ),
+                    _ => format!("mongodb://{}:{}/", database.name, db_port),
+                };
+                env_vars.insert("ME_CONFIG_MONGODB_URL".to_string(), url);
+                env_vars.insert("ME_CONFIG_BASICAUTH".to_string(), "false".to_string());
+                ("mongo-express:latest", 8081)
+            }
+            "redisinsight" => {
+                env_vars.insert("RI_REDIS_HOST".to_string(), database.name.clone());
+                env_vars.insert("RI_REDIS_PORT".to_string(), db_port.to_string());
+                env_vars.insert("RI_REDIS_ALIAS".to_string(), database.name.clone());
+                if database.stored_enable_auth && !password.is_empty() {
+                    env_vars.insert("RI_REDIS_PASSWORD".to_string(), password);
+                }
+                ("redis/redisinsight:latest", 5540)
+            }
+            "adminer" => {
+                env_vars.insert("ADMINER_DEFAULT_SERVER".to_string(), database.name.clone());
+                ("adminer:latest", 8080)
+            }
+            // Both read the config files written by `deploy`
+            "pgbouncer" => (PGBOUNCER_IMAGE, 5432),
+            "proxysql" => (PROXYSQL_IMAGE, 6033),
+            _ => {
+                return Err(AppError::Validation(format!(
+                    "Unknown companion tool: {}",
+                    kind
+                )))
+            }
+        };
+
+        Ok(DockerRunArgs {
+            image: image.to_string(),
+            env_vars,
+            ports: vec![PortMapping::local(host_port, container_port)],
+            volumes: vec![],
+            command: vec![],
+            shm_size: None,
+        })
+    }
+
+    /// pgAdmin server definition so the database shows up without manual setup
+    pub fn pgadmin_servers_json(database: &DatabaseContainer) -> serde_json::Value {
+        json!({
+            "Servers": {
+                "1": {
+                    "Name": database.name,
+                    "Group": "Docker DB Manager",
+                    "Host": database.name,
+                    "Port": 5432,
+                    "MaintenanceDB": database.stored_database_name.clone().unwrap_or_else(|| "postgres".to_string()),
+                    "Username": database.stored_username.clone().unwrap_or_else(|| "postgres".to_string()),
+                    "SSLMode": "prefer"
+                }
+            }
+        })
+    }
+
+    /// PgBouncer config sending every database name to the managed server
+    /// Transaction pooling, the mode that frees the most server connections
+    pub fn pgbouncer_ini(database: &DatabaseContainer) -> String {
+        let credentials = DatabaseCredentials::of(database);
+        let pool_size = Self::pooler_pool_size(database);
+
+        [
+            "[databases]".to_string(),
+            format!("* = host={} port=5432", database.name),
+            String::new(),
+            "[pgbouncer]".to_string(),
+            "listen_addr = 0.0.0.0".to_string(),
+            "listen_port = 5432".to_string(),
+            "auth_type = scram-sha-256".to_string(),
+            "auth_file = /etc/pgbouncer/userlist.txt".to_string(),
+            format!("admin_users = {}", credentials.username),
+            "pool_mode = transaction".to_string(),
+            format!("default_pool_size = {}", pool_size),
+            format!("max_db_connections = {}", pool_size),
+            "max_client_conn = 1000".to_string(),
+            // Sent by JDBC and other drivers, PgBouncer refuses unknown startup parameters
+            "ignore_startup_parameters = extra_float_digits".to_string(),
+        ]
+        .join("\n")
+            + "\n"
+    }
+
+    /// PgBouncer auth file with the stored login, quotes in it are doubled
+    pub fn pgbouncer_userlist(database: &DatabaseContainer) -> String {
+        let credentials = DatabaseCredentials::of(database);
+        format!(
+            "\"{}\" \"{}\"\n",
+            credentials.username.replace('"', "\"\""),
+            credentials.password.replace('"', "\"\"")
+        )
+    }
+
+    /// ProxySQL config with the managed server and its stored login
+    /// The same login monitors the server, ProxySQL needs one to see it as healthy
+    pub fn proxysql_cnf(database: &DatabaseContainer) -> String {
+        let credentials = DatabaseCredentials::of(database);
+        let username = Self::libconfig_string(&credentials.username);
+        let password = Self::libconfig_string(&credentials.password);
+        let pool_size = Self::pooler_pool_size(database);
+
+        [
+            "datadir=\"/var/lib/proxysql\"".to_string(),
+            String::new(),
+            "admin_variables=".to_string(),
+            "{".to_string(),
+            "    admin_credentials=\"admin:admin\"".to_string(),
+            "    mysql_ifaces=\"127.0.0.1:6032\"".to_string(),
+            "}".to_string(),
+            String::new(),
+            "mysql_variables=".to_string(),
+            "{".to_string(),
+            "    threads=2".to_string(),
+            "    max_connections=1000".to_string(),
+            "    interfaces=\"0.0.0.0:6033\"".to_string(),
+            format!("    monitor_username={}", username),
+            format!("    monitor_password={}", password),
+            "}".to_string(),
+            String::new(),
+            "mysql_servers=".to_string(),
+            "(".to_string(),
+            format!(
+                "    {{ address=\"{}\", port=3306, hostgroup=0, max_connections={} }}",
+                database.name, pool_size
+            ),
+            ")".to_string(),
+            String::new(),
+            "mysql_users=".to_string(),
+            "(".to_string(),
+            format!(
+                "    {{ username={}, password={}, default_hostgroup=0 }}",
+                username, password
+            ),
+            ")".to_string(),
+        ]
+        .join("\n")
+            + "\n"
+    }
+
+    /// Quoted libconfig string, the format of proxysql.cnf
+    fn libconfig_string(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    /// Config files of a companion, written under the app data and mounted in the container
+    /// Returned as (file name, content, path in the container)
+    pub fn config_files(
+        kind: &str,
+        database: &DatabaseContainer,
+    ) -> Result<Vec<(&'static str, String, &'static str)>, AppError> {
+        Ok(match kind {
+            // pgAdmin reads pre-registered servers from a mounted JSON file
+            "pgadmin" => vec![(
+                "servers.json",
+                serde_json::to_string_pretty(&Self::pgadmin_servers_json(database)).map_err(
+                    |e| AppError::Internal(format!("Failed to serialize pgAdmin servers: {}", e)),
+                )?,
+                "/pgadmin4/servers.json",
+            )],
+            "pgbouncer" => vec![
+                (
+                    "pgbouncer.ini",
+                    Self::pgbouncer_ini(database),
+                    "/etc/pgbouncer/pgbouncer.ini",
+                ),
+                (
+                    "userlist.txt",
+                    Self::pgbouncer_userlist(database),
+                    "/etc/pgbouncer/userlist.txt",
+                ),
+            ],
+            "proxysql" => vec![(
+                "proxysql.cnf",
+                Self::proxysql_cnf(database),
+                "/etc/proxysql.cnf",
+            )],
+            _ => Vec::new(),
+        })
+    }
+
+    /// Address clients use to reach a companion
+    /// A pooler gets a connection URL without the password, a web tool its page
+    pub fn companion_url(kind: &str, database: &DatabaseContainer, port: i32) -> String {
+        let credentials = DatabaseCredentials::of(database);
+        match kind {
+            "adminer" => Self::adminer_login_url(database, port),
+            "pgbouncer" => format!(
+                "postgresql://{}@localhost:{}/{}",
+                ConnectionService::encode(&credentials.username),
+                port,
+                ConnectionService::encode(&credentials.database)
+            ),
+            "proxysql" => format!(
+                "mysql://{}@localhost:{}/{}",
+                ConnectionService::encode(&credentials.username),
+                port,
+                ConnectionService::encode(&credentials.database)
+            ),
+            _ => format!("http://localhost:{}", port),
+        }
+    }
+
+    /// Adminer URL with the driver, server and user already filled in
+    pub fn adminer_login_url(database: &DatabaseContainer, port: i32) -> String {
+        let driver = match database.db_type.as_str() {
+            "PostgreSQL" => "pgsql",
+            "SQLServer" => "mssql",
+            _ => "server",
+        };
+
+        let mut url = format!(
+            "http://localhost:{}/?{}={}",
+            port,
+            driver,
+            ConnectionService::encode(&database.name)
+        );
+        if let Some(username) = &database.stored_username {
+            url.push_str(&format!(
+                "&username={}",
+                ConnectionService::encode(username)
+            ));
+        }
+        if let Some(database_name) = &database.stored_database_name {
+            url.push_str(&format!("&db={}", ConnectionService::encode(database_name)));
+        }
+
+        url
+    }
+
+    /// Deploy a companion container on the database network
+    /// Companions mount files written on this machine, so they only run next to local databases
+    pub async fn deploy(
+        &self,
+        app: &AppHandle,
+        kind: &str,
+        database: &DatabaseContainer,
+    ) -> Result<CompanionContainer, AppError> {
+        if database.host.is_some() {
+            return Err(AppError::Validation(format!(
+                "{} runs on a remote Docker host, companion tools only run next to local databases",
+                database.name
+            )));
+        }
+        let docker_service = DockerService::new();
+        let network = Self::network_name(&database.name);
+        let name = Self::companion_name(&database.name, kind);
+        let port = Self::find_free_port()?;
+
+        let mut docker_args = Self::build_companion_args(kind, database, port)?;
+
+        for (file_name, content, path) in Self::config_files(kind, database)? {
+            let file_path = self.write_config_file(app, kind, &name, file_name, &content)?;
+            docker_args.volumes.push(VolumeMount {
+                name: file_path,
+                path: path.to_string(),
+            });
+        }
+
+        // Join the database to a shared network so the tool can reach it by name
+        docker_service
+            .create_network_if_needed(app, &network)
+            .await?;
+        docker_service
+            .connect_container_to_network(app, &network, &database.name)
+            .await?;
+
+        let mut args = docker_service.build_docker_command_from_args(&name, &docker_args);
+        args.splice(2..2, ["--network".to_string(), network.clone()]);
+
+        let container_id = match docker_service.run_container(app, &args).await {
+            Ok(container_id) => container_id,
+            Err(error) => {
+                let _ = docker_service
+                    .force_remove_container_by_name(app, &name)
+                    .await;
+                return Err(match error {
+                    AppError::Docker { message, .. } => AppError::from_docker_output(
+                        &format!("Failed to deploy {}", kind),
+                        &message,
+                        None,
+                    ),
+                    other => other,
+                });
+            }
+        };
+
+        Ok(CompanionContainer {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            name,
+            database_id: database.id.clone(),
+            container_id: Some(container_id),
+            status: "running".to_string(),
+            port,
+            url: Self::companion_url(kind, database, port),
+            created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        })
+    }
+
+    /// Remove a companion container and any files written for it
+    pub async fn remove(
+        &self,
+        app: &AppHandle,
+        companion: &CompanionContainer,
+    ) -> Result<(), AppError> {
+        let docker_service = DockerService::new();
+
+        docker_service
+            .force_remove_container_by_name(app, &companion.name)
+            .await?;
+
+        let config_dir = Self::config_dir(app, &companion.name)?;
+        if config_dir.exists() {
+            let _ = std::fs::remove_dir_all(&config_dir);
+        }
+
+        Ok(())
+    }
+
+    /// Pooler files hold the database password, only the current user can read them
+    fn write_config_file(
+        &self,
+        app: &AppHandle,
+        kind: &str,
+        companion_name: &str,
+        file_name: &str,
+        content: &str,
+    ) -> Result<String, AppError> {
+        let config_dir = Self::config_dir(app, companion_name)?;
+        std::fs::create_dir_all(&config_dir)
+            .map_err(|e| AppError::Io(format!("Failed to create companion directory: {}", e)))?;
+
+        let file_path = config_dir.join(file_name);
+        let written = if Self::is_pooler(kind) {
+            write_private(&file_path, content.as_bytes())
+        } else {
+            std::fs::write(&file_path, content)
+        };
+        written.map_err(|e| AppError::Io(format!("Failed to write {}: {}", file_name, e)))?;
+
+        Ok(file_path.to_string_lossy().to_string())
+    }
+
+    fn config_dir(app: &AppHandle, companion_name: &str) -> Result<std::path::PathBuf, AppError> {
+        let data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::Io(format!("Failed to resolve app data directory: {}", e)))?;
+
+        Ok(data_dir.join("companions").join(companion_name))
+    }
+}
@@ -0,0 +1,178 @@
+use crate::services::PASSWORD_PLACEHOLDER;
+use crate::types::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Shown instead of a password in commands meant to be read or copied
+pub const PASSWORD_MASK: &str = "<password>";
+
+pub struct ConfigService;
+
+impl ConfigService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Environment variables holding credentials, e.g. `POSTGRES_PASSWORD`
+    pub fn is_secret_env_var(key: &str) -> bool {
+        key.to_uppercase().contains("PASSWORD")
+    }
+
+    /// Strip every stored credential from a container, including its run arguments
+    pub fn redact_secrets(database: &mut DatabaseContainer) {
+        database.stored_password = None;
+
+        if let Some(docker_args) = &mut database.stored_docker_args {
+            Self::mask_secrets(docker_args, "");
+        }
+    }
+
+    /// Whether the run arguments lost a credential to `redact_secrets`,
+    /// or still hold the keychain placeholder of a password that was not resolved
+    /// Replace the credentials of run arguments with `mask`, found by their key or flag
+    /// Other arguments are left alone even when they contain the password's text
+    pub fn mask_secrets(docker_args: &mut DockerRunArgs, mask: &str) {
+        for (key, value) in docker_args.env_vars.iter_mut() {
+            if Self::is_secret_env_var(key) && !value.is_empty() {
+                *value = mask.to_string();
+            }
+        }
+
+        // Redis receives its password as a server argument
+        let mut mask_next = false;
+        for arg in docker_args.command.iter_mut() {
+            if mask_next && !arg.is_empty() {
+                *arg = mask.to_string();
+            }
+            mask_next = arg == "--requirepass";
+        }
+    }
+
+    fn has_redacted_secrets(docker_args: &DockerRunArgs) -> bool {
+        let redacted = |value: &str| value.is_empty() || value == PASSWORD_PLACEHOLDER;
+        let redacted_env = docker_args
+            .env_vars
+            .iter()
+            .any(|(key, value)| Self::is_secret_env_var(key) && redacted(value));
+        let redacted_arg = docker_args
+            .command
+            .windows(2)
+            .any(|pair| pair[0] == "--requirepass" && redacted(&pair[1]));
+        redacted_env || redacted_arg
+    }
+
+    /// Copy of a database without the fields that only hold on this host
+    pub fn portable_database(
+        mut database: DatabaseContainer,
+        include_secrets: bool,
+    ) -> DatabaseContainer {
+        database.container_id = None;
+        database.ca_cert_path = None;
+        database.stored_password_ref = None;
+        if !include_secrets {
+            Self::redact_secrets(&mut database);
+        }
+        database
+    }
+
+    /// Build a bundle from the current state
+    /// Host-specific fields (Docker ids, certificate paths) are dropped
+    pub fn build_bundle(
+        databases: &HashMap<String, DatabaseContainer>,
+        settings: &AppSettings,
+        include_secrets: bool,
+    ) -> ConfigBundle {
+        let mut exported: Vec<DatabaseContainer> = databases
+            .values()
+            .cloned()
+            .map(|database| Self::portable_database(database, include_secrets))
+            .collect();
+        exported.sort_by(|a, b| a.name.cmp(&b.name));
+
+        ConfigBundle {
+            version: CONFIG_BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            includes_secrets: include_secrets,
+            settings: settings.clone(),
+            databases: exported,
+        }
+    }
+
+    /// Keep the organization of an exported container on the one recreated from it
+    pub fn restore_organization(database: &mut DatabaseContainer, exported: &DatabaseContainer) {
+        database.project = exported.project.clone();
+        database.tags = exported.tags.clone();
+        database.favorite = exported.favorite;
+        database.auto_start = exported.auto_start;
+        database.keep_running_on_exit = exported.keep_running_on_exit;
+    }
+
+    /// Rebuild the run request that originally created an exported container
+    pub fn build_run_request(database: &DatabaseContainer) -> Result<DockerRunRequest, AppError> {
+        let docker_args = database.stored_docker_args.clone().ok_or_else(|| {
+            AppError::Validation(
+                "The export has no run configuration for this container".to_string(),
+            )
+        })?;
+
+        // Databases without credentials, like Redis without auth, run with an empty password
+        let password = match &database.stored_password {
+            Some(password) => password.clone(),
+            None if Self::has_redacted_secrets(&docker_args) => {
+                return Err(AppError::Validation(
+                    "Credentials were not included in the export".to_string(),
+                ))
+            }
+            None => String::new(),
+        };
+
+        Ok(DockerRunRequest {
+            name: database.name.clone(),
+            docker_args,
+            metadata: ContainerMetadata {
+                id: database.id.clone(),
+                db_type: database.db_type.clone(),
+                version: database.version.clone(),
+                port: database.port,
+                username: database.stored_username.clone(),
+                password,
+                database_name: database.stored_database_name.clone(),
+                persist_data: database.stored_persist_data,
+                enable_auth: database.stored_enable_auth,
+                max_connections: Some(database.max_connections),
+                enable_tls: database.stored_enable_tls,
+                expose_to_lan: database.stored_expose_to_lan,
+                post_create_sql: database.stored_post_create_sql.clone(),
+                framework: database.framework,
+                locale: database.locale.clone(),
+                extra_args: database.extra_args.clone(),
+                region: database.region.clone(),
+                tuning: database.tuning,
+                host: database.host.clone(),
+            },
+        })
+    }
+
+    pub fn write_bundle(&self, path: &Path, bundle: &ConfigBundle) -> Result<(), AppError> {
+        let bytes = serde_json::to_vec_pretty(bundle)?;
+        std::fs::write(path, bytes)
+            .map_err(|e| AppError::Io(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    pub fn read_bundle(&self, path: &Path) -> Result<ConfigBundle, AppError> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| AppError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+
+        let bundle: ConfigBundle = serde_json::from_slice(&bytes)
+            .map_err(|e| AppError::Validation(format!("Invalid configuration file: {}", e)))?;
+
+        if bundle.version > CONFIG_BUNDLE_VERSION {
+            return Err(AppError::Validation(format!(
+                "Configuration version {} is newer than this app supports",
+                bundle.version
+            )));
+        }
+
+        Ok(bundle)
+    }
+}
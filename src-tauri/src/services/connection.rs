@@ -0,0 +1,430 @@
+use crate::types::*;
+
+/// Connection details of a database in the formats other tools expect
+pub struct ConnectionService {
+    // Address of the remote Docker host the database runs on, None for this machine
+    host: Option<String>,
+    // Host port of the connection pooler in front of the database
+    pooled_port: Option<i32>,
+}
+
+impl ConnectionService {
+    pub fn new() -> Self {
+        Self {
+            host: None,
+            pooled_port: None,
+        }
+    }
+
+    /// Details pointing at a database published on another machine
+    pub fn on_host(host: Option<String>) -> Self {
+        Self {
+            host,
+            pooled_port: None,
+        }
+    }
+
+    /// Details that also give the address of the pooler listening on `pooled_port`
+    pub fn with_pooled_port(self, pooled_port: Option<i32>) -> Self {
+        Self {
+            pooled_port,
+            ..self
+        }
+    }
+
+    /// Address clients reach the database at
+    pub fn host(&self) -> &str {
+        self.host.as_deref().unwrap_or("localhost")
+    }
+
+    // Laravel resolves `localhost` to its socket, a local database is reached by IP
+    fn ip(&self) -> &str {
+        self.host.as_deref().unwrap_or("127.0.0.1")
+    }
+
+    /// Ready-to-paste .env block for a database
+    pub fn env_snippet(&self, database: &DatabaseContainer, preset: EnvPreset) -> String {
+        let mut lines = vec![format!(
+            "# {} ({} {})",
+            database.name, database.db_type, database.version
+        )];
+        lines.extend(
+            self.env_vars(database, preset)
+                .into_iter()
+                .map(|(key, value)| format!("{}={}", key, Self::env_value(&value))),
+        );
+        if let Some(url) = self.pooled_url(database) {
+            lines.push(format!("DATABASE_POOLED_URL={}", Self::env_value(&url)));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Configuration a framework reads its connection from, in the format of its config file
+    /// Engines the framework has no native config for get its .env block
+    pub fn framework_snippet(&self, database: &DatabaseContainer, framework: Framework) -> String {
+        let host = self.host();
+        let port = database.port;
+        let user = Self::username(database);
+        let password = database.stored_password.clone().unwrap_or_default();
+        let name = Self::database_name(database);
+        let env = || self.env_snippet(database, framework.env_preset());
+
+        let lines = match framework {
+            Framework::Django => {
+                let Some(engine) = Self::django_engine(&database.db_type) else {
+                    return env();
+                };
+                vec![
+                    "# settings.py".to_string(),
+                    "DATABASES = {".to_string(),
+                    "    \"default\": {".to_string(),
+                    format!("        \"ENGINE\": {},", Self::quoted(engine)),
+                    format!("        \"NAME\": {},", Self::quoted(&name)),
+                    format!("        \"USER\": {},", Self::quoted(&user)),
+                    format!("        \"PASSWORD\": {},", Self::quoted(&password)),
+                    format!("        \"HOST\": {},", Self::quoted(host)),
+                    format!("        \"PORT\": \"{}\",", port),
+                    "    }".to_string(),
+                    "}".to_string(),
+                ]
+            }
+            Framework::Rails => {
+                let Some(adapter) = Self::rails_adapter(&database.db_type) else {
+                    return env();
+                };
+                vec![
+                    "# config/database.yml".to_string(),
+                    "development:".to_string(),
+                    format!("  adapter: {}", adapter),
+                    format!("  host: {}", host),
+                    format!("  port: {}", port),
+                    format!("  database: {}", Self::quoted(&name)),
+                    format!("  username: {}", Self::quoted(&user)),
+                    format!("  password: {}", Self::quoted(&password)),
+                ]
+            }
+            Framework::Laravel => return env(),
+            Framework::Spring => {
+                let properties = match database.db_type.as_str() {
+                    "MongoDB" => vec![(
+                        "spring.data.mongodb.uri",
+                        self.url(database, EnvPreset::Generic),
+                    )],
+                    "Redis" => vec![
+                        ("spring.data.redis.host", host.to_string()),
+                        ("spring.data.redis.port", port.to_string()),
+                        ("spring.data.redis.password", password),
+                    ],
+                    db_type => match self.jdbc_url(db_type, port, &name) {
+                        Some(url) => vec![
+                            ("spring.datasource.url", url),
+                            ("spring.datasource.username", user),
+                            ("spring.datasource.password", password),
+                        ],
+                        None => return env(),
+                    },
+                };
+                let mut lines = vec!["# application.properties".to_string()];
+                lines.extend(
+                    properties
+                        .into_iter()
+                        .map(|(key, value)| format!("{}={}", key, value.replace('\\', "\\\\"))),
+                );
+                lines
+            }
+            Framework::Prisma => {
+                let Some(provider) = Self::prisma_provider(&database.db_type) else {
+                    return env();
+                };
+                // The schema reads the URL from the .env block above it
+                vec![
+                    env(),
+                    "// prisma/schema.prisma".to_string(),
+                    "datasource db {".to_string(),
+                    format!("  provider = \"{}\"", provider),
+                    "  url      = env(\"DATABASE_URL\")".to_string(),
+                    "}".to_string(),
+                ]
+            }
+        };
+        lines.join("\n") + "\n"
+    }
+
+    fn django_engine(db_type: &str) -> Option<&'static str> {
+        match db_type {
+            "PostgreSQL" => Some("django.db.backends.postgresql"),
+            "MySQL" | "MariaDB" => Some("django.db.backends.mysql"),
+            "SQLServer" => Some("mssql"),
+            _ => None,
+        }
+    }
+
+    fn rails_adapter(db_type: &str) -> Option<&'static str> {
+        match db_type {
+            "PostgreSQL" => Some("postgresql"),
+            "MySQL" | "MariaDB" => Some("mysql2"),
+            "SQLServer" => Some("sqlserver"),
+            _ => None,
+        }
+    }
+
+    fn prisma_provider(db_type: &str) -> Option<&'static str> {
+        match db_type {
+            "PostgreSQL" => Some("postgresql"),
+            "MySQL" | "MariaDB" => Some("mysql"),
+            "SQLServer" => Some("sqlserver"),
+            "MongoDB" => Some("mongodb"),
+            _ => None,
+        }
+    }
+
+    fn jdbc_url(&self, db_type: &str, port: i32, name: &str) -> Option<String> {
+        let host = self.host();
+        let url = match db_type {
+            "PostgreSQL" => format!("jdbc:postgresql://{}:{}/{}", host, port, name),
+            "MySQL" => format!("jdbc:mysql://{}:{}/{}", host, port, name),
+            "MariaDB" => format!("jdbc:mariadb://{}:{}/{}", host, port, name),
+            "SQLServer" => format!(
+                "jdbc:sqlserver://{}:{};databaseName={};encrypt=false",
+                host, port, name
+            ),
+            _ => return None,
+        };
+        Some(url)
+    }
+
+    /// Double-quoted string valid in both Python and YAML
+    fn quoted(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    fn env_vars(
+        &self,
+        database: &DatabaseContainer,
+        preset: EnvPreset,
+    ) -> Vec<(&'static str, String)> {
+        let host = self.host().to_string();
+        let port = database.port.to_string();
+        let user = Self::username(database);
+        let password = database.stored_password.clone().unwrap_or_default();
+        let name = Self::database_name(database);
+
+        match (database.db_type.as_str(), preset) {
+            ("Redis", EnvPreset::Laravel) => vec![
+                ("REDIS_HOST", self.ip().to_string()),
+                ("REDIS_PASSWORD", Self::or_null(password)),
+                ("REDIS_PORT", port),
+            ],
+            ("Redis", _) => vec![("REDIS_URL", self.url(database, preset))],
+            ("Elasticsearch", _) => vec![("ELASTICSEARCH_URL", self.url(database, preset))],
+            (_, EnvPreset::Laravel) => vec![
+                (
+                    "DB_CONNECTION",
+                    Self::laravel_driver(&database.db_type).to_string(),
+                ),
+                ("DB_HOST", self.ip().to_string()),
+                ("DB_PORT", port),
+                ("DB_DATABASE", name),
+                ("DB_USERNAME", user),
+                ("DB_PASSWORD", password),
+            ],
+            ("MongoDB", EnvPreset::Generic) => vec![
+                ("MONGODB_URI", self.url(database, preset)),
+                ("MONGODB_HOST", host),
+                ("MONGODB_PORT", port),
+                ("MONGODB_USER", user),
+                ("MONGODB_PASSWORD", password),
+                ("MONGODB_DATABASE", name),
+            ],
+            (_, EnvPreset::Rails) | (_, EnvPreset::Prisma) => {
+                vec![("DATABASE_URL", self.url(database, preset))]
+            }
+            (_, EnvPreset::Generic) | (_, EnvPreset::Django) => {
+                let mut vars = vec![
+                    ("DATABASE_URL", self.url(database, preset)),
+                    ("DB_HOST", host),
+                    ("DB_PORT", port),
+                    ("DB_USER", user),
+                    ("DB_PASSWORD", password),
+                    ("DB_NAME", name),
+                ];
+                if let Some(url) = self.embeddings_url(database) {
+                    vars.push(("PGVECTOR_CONNECTION_STRING", url));
+                }
+                vars
+            }
+        }
+    }
+
+    /// SQLAlchemy URL with the psycopg 3 driver, the form LangChain's PGVector store takes
+    /// None unless the database runs a pgvector image
+    pub fn embeddings_url(&self, database: &DatabaseContainer) -> Option<String> {
+        if !database.has_pgvector() {
+            return None;
+        }
+        let url = self.url(database, EnvPreset::Generic);
+        Some(url.replacen("postgresql://", "postgresql+psycopg://", 1))
+    }
+
+    /// URL through the connection pooler, None when no pooler runs in front of the database
+    pub fn pooled_url(&self, database: &DatabaseContainer) -> Option<String> {
+        let database = DatabaseContainer {
+            port: self.pooled_port?,
+            ..database.clone()
+        };
+        Some(self.url(&database, EnvPreset::Generic))
+    }
+
+    /// Connection string of a database for clients and assistants
+    /// SQL Server tools take ADO.NET strings, other engines a URL
+    pub fn connection_string(&self, database: &DatabaseContainer) -> String {
+        if database.db_type != "SQLServer" {
+            return self.url(database, EnvPreset::Generic);
+        }
+
+        format!(
+            "Server={},{};Database={};User Id={};Password={};TrustServerCertificate=True;",
+            self.host(),
+            database.port,
+            Self::ado_value(&Self::database_name(database)),
+            Self::ado_value(&Self::username(database)),
+            Self::ado_value(database.stored_password.as_deref().unwrap_or_default())
+        )
+    }
+
+    /// URL a client opens, without the password so it stays off the client's command line
+    pub fn client_url(&self, database: &DatabaseContainer) -> String {
+        let database = DatabaseContainer {
+            stored_password: None,
+            ..database.clone()
+        };
+        // SQL Server clients take the scheme Rails uses
+        let preset = if database.db_type == "SQLServer" {
+            EnvPreset::Rails
+        } else {
+            EnvPreset::Generic
+        };
+        self.url(&database, preset)
+    }
+
+    /// Connection URL with the scheme the preset's framework expects
+    fn url(&self, database: &DatabaseContainer, preset: EnvPreset) -> String {
+        let host = self.host();
+        let password = Self::encode(database.stored_password.as_deref().unwrap_or_default());
+        let user = match Self::encode(&Self::username(database)) {
+            user if password.is_empty() => user,
+            user => format!("{}:{}", user, password),
+        };
+        let name = Self::encode(&Self::database_name(database));
+        let port = database.port;
+
+        match database.db_type.as_str() {
+            "PostgreSQL" => format!("postgresql://{}@{}:{}/{}", user, host, port, name),
+            "MySQL" | "MariaDB" => {
+                let scheme = match preset {
+                    EnvPreset::Rails => "mysql2",
+                    EnvPreset::Generic if database.db_type == "MariaDB" => "mariadb",
+                    _ => "mysql",
+                };
+                format!("{}://{}@{}:{}/{}", scheme, user, host, port, name)
+            }
+            "MongoDB" => format!(
+                "mongodb://{}@{}:{}/{}?authSource=admin",
+                user, host, port, name
+            ),
+            "SQLServer" => match preset {
+                EnvPreset::Prisma => format!(
+                    "sqlserver://{}:{};database={};user={};password={};trustServerCertificate=true",
+                    host,
+                    port,
+                    Self::database_name(database),
+                    Self::username(database),
+                    database.stored_password.as_deref().unwrap_or_default()
+                ),
+                EnvPreset::Rails => format!("sqlserver://{}@{}:{}/{}", user, host, port, name),
+                _ => format!("mssql://{}@{}:{}/{}", user, host, port, name),
+            },
+            "Redis" if password.is_empty() => format!("redis://{}:{}", host, port),
+            "Redis" => format!("redis://:{}@{}:{}", password, host, port),
+            "Elasticsearch" => format!("https://elastic:{}@{}:{}", password, host, port),
+            _ => format!("{}:{}", host, port),
+        }
+    }
+
+    fn username(database: &DatabaseContainer) -> String {
+        database.stored_username.clone().unwrap_or_else(|| {
+            match database.db_type.as_str() {
+                "PostgreSQL" => "postgres",
+                "MongoDB" => "admin",
+                "SQLServer" => "sa",
+                _ => "root",
+            }
+            .to_string()
+        })
+    }
+
+    fn database_name(database: &DatabaseContainer) -> String {
+        database.stored_database_name.clone().unwrap_or_else(|| {
+            match database.db_type.as_str() {
+                "PostgreSQL" => "postgres",
+                "MongoDB" => "admin",
+                "SQLServer" => "master",
+                "MariaDB" => "mysql",
+                _ => "",
+            }
+            .to_string()
+        })
+    }
+
+    fn laravel_driver(db_type: &str) -> &'static str {
+        match db_type {
+            "PostgreSQL" => "pgsql",
+            "MariaDB" => "mariadb",
+            "SQLServer" => "sqlsrv",
+            "MongoDB" => "mongodb",
+            _ => "mysql",
+        }
+    }
+
+    fn or_null(value: String) -> String {
+        if value.is_empty() {
+            "null".to_string()
+        } else {
+            value
+        }
+    }
+
+    /// Value of an ADO.NET connection string, quoted when it holds a separator
+    fn ado_value(value: &str) -> String {
+        if value.contains([';', '=', '"', '\'']) || value.trim() != value {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// Percent-encode a URL user, password, path segment or query value
+    pub(crate) fn encode(value: &str) -> String {
+        value
+            .bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    (b as char).to_string()
+                }
+                _ => format!("%{:02X}", b),
+            })
+            .collect()
+    }
+
+    /// Quote values dotenv parsers would otherwise split or expand
+    fn env_value(value: &str) -> String {
+        let plain = value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@%?=&;,+~".contains(c));
+        if plain {
+            value.to_string()
+        } else {
+            format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+    }
+}
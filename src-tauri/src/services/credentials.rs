@@ -0,0 +1,408 @@
+use crate::services::{mongo_login, DockerService, EnvFile, MONGO_PASSWORD_VAR};
+use crate::types::*;
+use std::path::Path;
+use tauri::AppHandle;
+
+// Superuser that only lives while a PostgreSQL user is renamed, a session cannot rename itself
+const POSTGRES_RENAME_ROLE: &str = "ddm_credentials";
+// Variable a statement is piped to the client from, statements can carry the new password
+const STATEMENT_VAR: &str = "DDM_STATEMENT";
+// Variable holding the password a MongoDB user is given
+const NEW_PASSWORD_VAR: &str = "DDM_NEW_PASSWORD";
+
+/// One `docker exec` of a credential change
+/// Passwords travel in `env` and are read inside the container, never from a command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredentialCommand {
+    pub env: Vec<(&'static str, String)>,
+    // Program run in the container and its arguments
+    pub command: Vec<String>,
+}
+
+impl CredentialCommand {
+    /// Pipe `statement` into `client` through the environment
+    /// printf is a shell builtin, so the statement never becomes a process argument
+    fn piped(statement: String, mut env: Vec<(&'static str, String)>, client: &[&str]) -> Self {
+        env.push((STATEMENT_VAR, statement));
+        let mut command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("printf '%s\\n' \"${}\" | exec \"$@\"", STATEMENT_VAR),
+            "sh".to_string(),
+        ];
+        command.extend(client.iter().map(|arg| arg.to_string()));
+        Self { env, command }
+    }
+
+    /// The statement piped to the client, None for MongoDB scripts
+    pub fn statement(&self) -> Option<&str> {
+        self.env
+            .iter()
+            .find(|(key, _)| *key == STATEMENT_VAR)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// `docker exec` arguments running the command in `container`
+    /// `env_file` is written from `env`
+    pub fn exec_args(&self, container: &str, env_file: Option<&Path>) -> Vec<String> {
+        let mut args = vec!["exec".to_string()];
+        if let Some(env_file) = env_file {
+            args.extend([
+                "--env-file".to_string(),
+                env_file.to_string_lossy().to_string(),
+            ]);
+        }
+        args.push(container.to_string());
+        args.extend(self.command.iter().cloned());
+        args
+    }
+
+    async fn run(
+        &self,
+        app: &AppHandle,
+        docker_service: &DockerService,
+        container: &str,
+        context: &str,
+    ) -> Result<String, AppError> {
+        let vars: Vec<(&str, &str)> = self
+            .env
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect();
+        // Removed once the command returned
+        let env_file = EnvFile::create(&vars)?;
+        docker_service
+            .stdout(
+                app,
+                context,
+                &self.exec_args(container, Some(env_file.path())),
+            )
+            .await
+    }
+}
+
+/// Changes the login of a running database in the engine itself
+/// Init environment variables are only read on the first start of a data directory
+pub struct CredentialService;
+
+impl CredentialService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether the engine reads its credentials on every start instead of at init
+    pub fn applies_on_start(db_type: &str) -> bool {
+        DatabaseCredentials::applies_on_start(db_type)
+    }
+
+    /// Commands that move the engine from `current` to `requested`, in order
+    pub fn change_commands(
+        db_type: &str,
+        current: &DatabaseCredentials,
+        requested: &DatabaseCredentials,
+    ) -> Result<Vec<CredentialCommand>, AppError> {
+        if current == requested {
+            return Ok(Vec::new());
+        }
+
+        let commands = match db_type {
+            "PostgreSQL" => Self::postgres_commands(current, requested),
+            "MySQL" | "MariaDB" => {
+                Self::reject_rename(db_type, current, requested)?;
+                let client = if db_type == "MariaDB" {
+                    "mariadb"
+                } else {
+                    "mysql"
+                };
+                Self::mysql_commands(client, current, requested)
+            }
+            "MongoDB" => Self::mongo_commands(current, requested),
+            "SQLServer" => {
+                Self::reject_rename(db_type, current, requested)?;
+                Self::sqlserver_commands(current, requested)
+            }
+            _ => {
+                return Err(AppError::Validation(format!(
+                    "{} credentials cannot be changed on an existing container",
+                    db_type
+                )))
+            }
+        };
+
+        Ok(commands)
+    }
+
+    fn reject_rename(
+        db_type: &str,
+        current: &DatabaseCredentials,
+        requested: &DatabaseCredentials,
+    ) -> Result<(), AppError> {
+        if current.username != requested.username {
+            return Err(AppError::Validation(format!(
+                "{} only has the {} user, it cannot be renamed",
+                db_type, current.username
+            )));
+        }
+        Ok(())
+    }
+
+    fn postgres_commands(
+        current: &DatabaseCredentials,
+        requested: &DatabaseCredentials,
+    ) -> Vec<CredentialCommand> {
+        // Local socket connections are trusted by the image, no password needed
+        let psql = |user: &str, sql: String| {
+            CredentialCommand::piped(
+                sql,
+                Vec::new(),
+                &[
+                    "psql",
+                    "-v",
+                    "ON_ERROR_STOP=1",
+                    "-U",
+                    user,
+                    "-d",
+                    "template1",
+                ],
+            )
+        };
+        let mut commands = Vec::new();
+
+        if current.username != requested.username {
+            commands.push(psql(
+                &current.username,
+                format!(
+                    "CREATE ROLE {} SUPERUSER LOGIN",
+                    Self::sql_identifier(POSTGRES_RENAME_ROLE)
+                ),
+            ));
+            commands.push(psql(
+                POSTGRES_RENAME_ROLE,
+                format!(
+                    "ALTER ROLE {} RENAME TO {}",
+                    Self::sql_identifier(&current.username),
+                    Self::sql_identifier(&requested.username)
+                ),
+            ));
+            commands.push(psql(
+                &requested.username,
+                format!("DROP ROLE {}", Self::sql_identifier(POSTGRES_RENAME_ROLE)),
+            ));
+        }
+
+        // A rename clears MD5 passwords, so the password is always set again after one
+        if current.password != requested.password || current.username != requested.username {
+            commands.push(psql(
+                &requested.username,
+                format!(
+                    "ALTER ROLE {} WITH PASSWORD {}",
+                    Self::sql_identifier(&requested.username),
+                    Self::sql_literal(&requested.password)
+                ),
+            ));
+        }
+
+        if current.database != requested.database {
+            commands.push(psql(
+                &requested.username,
+                format!(
+                    "ALTER DATABASE {} RENAME TO {}",
+                    Self::sql_identifier(&current.database),
+                    Self::sql_identifier(&requested.database)
+                ),
+            ));
+        }
+
+        commands
+    }
+
+    fn mysql_commands(
+        client: &str,
+        current: &DatabaseCredentials,
+        requested: &DatabaseCredentials,
+    ) -> Vec<CredentialCommand> {
+        let mut statements = Vec::new();
+
+        // MySQL cannot rename a database, the new one is created next to the old one
+        if current.database != requested.database && !requested.database.is_empty() {
+            statements.push(format!(
+                "CREATE DATABASE IF NOT EXISTS `{}`",
+                requested.database.replace('`', "``")
+            ));
+        }
+
+        if current.password != requested.password {
+            for host in ["%", "localhost"] {
+                statements.push(format!(
+                    "ALTER USER IF EXISTS {}@'{}' IDENTIFIED BY {}",
+                    Self::sql_literal(&current.username),
+                    host,
+                    Self::sql_literal(&requested.password)
+                ));
+            }
+        }
+
+        if statements.is_empty() {
+            return Vec::new();
+        }
+
+        vec![CredentialCommand::piped(
+            format!("{};", statements.join("; ")),
+            vec![("MYSQL_PWD", current.password.clone())],
+            &[client, "-u", &current.username],
+        )]
+    }
+
+    fn mongo_commands(
+        current: &DatabaseCredentials,
+        requested: &DatabaseCredentials,
+    ) -> Vec<CredentialCommand> {
+        // Logs in as `user`, the script reads the new password from the environment
+        let mongosh = |user: &DatabaseCredentials, script: String| CredentialCommand {
+            env: vec![
+                (MONGO_PASSWORD_VAR, user.password.clone()),
+                (NEW_PASSWORD_VAR, requested.password.clone()),
+            ],
+            command: vec![
+                "mongosh".to_string(),
+                "--quiet".to_string(),
+                "--eval".to_string(),
+                format!("{}{}", mongo_login(&user.username), script),
+            ],
+        };
+        let string = |value: &str| serde_json::Value::from(value).to_string();
+
+        // Databases are created on first write, only the user needs changing
+        if current.username != requested.username {
+            vec![
+                mongosh(
+                    current,
+                    format!(
+                        "db.getSiblingDB('admin').createUser({{user: {}, pwd: process.env.{}, roles: ['root']}})",
+                        string(&requested.username),
+                        NEW_PASSWORD_VAR
+                    ),
+                ),
+                mongosh(
+                    requested,
+                    format!(
+                        "db.getSiblingDB('admin').dropUser({})",
+                        string(&current.username)
+                    ),
+                ),
+            ]
+        } else if current.password != requested.password {
+            vec![mongosh(
+                current,
+                format!(
+                    "db.getSiblingDB('admin').changeUserPassword({}, process.env.{})",
+                    string(&current.username),
+                    NEW_PASSWORD_VAR
+                ),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn sqlserver_commands(
+        current: &DatabaseCredentials,
+        requested: &DatabaseCredentials,
+    ) -> Vec<CredentialCommand> {
+        if current.password == requested.password {
+            return Vec::new();
+        }
+
+        vec![CredentialCommand::piped(
+            format!(
+                "ALTER LOGIN [{}] WITH PASSWORD = {} OLD_PASSWORD = {}",
+                current.username.replace(']', "]]"),
+                Self::sql_literal(&requested.password),
+                Self::sql_literal(&current.password)
+            ),
+            vec![("SQLCMDPASSWORD", current.password.clone())],
+            &[
+                "/opt/mssql-tools18/bin/sqlcmd",
+                "-S",
+                "localhost",
+                "-U",
+                &current.username,
+                "-C",
+                "-b",
+            ],
+        )]
+    }
+
+    fn sql_identifier(name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn sql_literal(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    /// Run the change statements in the database's container, stops at the first failure
+    /// Statements that already ran are undone, so the engine keeps the stored credentials
+    pub async fn apply(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        requested: &DatabaseCredentials,
+    ) -> Result<(), AppError> {
+        self.change(app, database, &DatabaseCredentials::of(database), requested)
+            .await
+    }
+
+    /// Move the engine back to the stored credentials after `apply` succeeded
+    /// For updates that fail after the credentials were changed
+    pub async fn revert(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        applied: &DatabaseCredentials,
+    ) -> Result<(), AppError> {
+        self.change(app, database, applied, &DatabaseCredentials::of(database))
+            .await
+    }
+
+    async fn change(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        current: &DatabaseCredentials,
+        requested: &DatabaseCredentials,
+    ) -> Result<(), AppError> {
+        let commands = Self::change_commands(&database.db_type, current, requested)?;
+        let docker_service = DockerService::for_database(app, database)?;
+
+        for (index, command) in commands.iter().enumerate() {
+            if let Err(error) = command
+                .run(
+                    app,
+                    &docker_service,
+                    &database.name,
+                    "Failed to change credentials",
+                )
+                .await
+            {
+                if index > 0 {
+                    // Undo what ran, statements of the reverse change that did not apply fail harmlessly
+                    let undo = Self::change_commands(&database.db_type, requested, current)?;
+                    for command in undo {
+                        let _ = command
+                            .run(
+                                app,
+                                &docker_service,
+                                &database.name,
+                                "Failed to restore credentials",
+                            )
+                            .await;
+                    }
+                }
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+}
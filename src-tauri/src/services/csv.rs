@@ -0,0 +1,957 @@
+use crate::services::{
+    mongo_login, mongo_tool, mongo_tools_config, DockerService, EnvFile, EventService, SqlService,
+    MONGO_PASSWORD_VAR, MONGO_TOOLS_CONFIG_VAR,
+};
+use crate::types::*;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use tauri::AppHandle;
+use tauri_plugin_shell::process::CommandEvent;
+
+// Bytes written between two progress events
+const PROGRESS_INTERVAL_BYTES: u64 = 1024 * 1024;
+// Start of an imported file read to find its header and line endings
+const HEADER_SNIFF_BYTES: u64 = 64 * 1024;
+// Warnings MySQL keeps for `SHOW WARNINGS`, the largest it allows
+const MYSQL_MAX_WARNINGS: u32 = 65535;
+
+// Outcome of a process fed from a file
+#[derive(Default)]
+struct StdinRun {
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+    // CSV records sent to the process
+    records: u64,
+}
+
+/// Moves table data between database containers and local CSV files
+/// The engine's own export tool runs in the container and its output is streamed to disk
+pub struct CsvService;
+
+impl CsvService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `COPY` of a table or view to stdout
+    pub fn postgres_export_sql(
+        schema: &str,
+        table: &str,
+        columns: &[String],
+        header: bool,
+    ) -> String {
+        let dialect = SqlDialect::Postgres;
+        let columns = if columns.is_empty() {
+            "*".to_string()
+        } else {
+            columns
+                .iter()
+                .map(|column| dialect.quote_identifier(column))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        format!(
+            "COPY (SELECT {} FROM {}) TO STDOUT WITH (FORMAT csv, HEADER {})",
+            columns,
+            dialect.qualified_name(schema, table),
+            header
+        )
+    }
+
+    /// `SELECT ... INTO OUTFILE` writing RFC 4180 CSV inside the container
+    /// MySQL does not double embedded quotes, so values are quoted by the query and written as is
+    /// NULL becomes an unquoted empty field and an empty string `""`, as PostgreSQL writes them
+    pub fn mysql_outfile_sql(schema: &str, table: &str, columns: &[String], file: &str) -> String {
+        let dialect = SqlDialect::MySql;
+        let columns: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                let column = dialect.quote_identifier(column);
+                format!(
+                    "IF({0} IS NULL, '', CONCAT('\"', REPLACE({0}, '\"', '\"\"'), '\"'))",
+                    column
+                )
+            })
+            .collect();
+
+        format!(
+            "SELECT {} FROM {} INTO OUTFILE {} CHARACTER SET utf8mb4 \
+FIELDS TERMINATED BY ',' ESCAPED BY '' LINES TERMINATED BY '\\n'",
+            columns.join(", "),
+            dialect.qualified_name(schema, table),
+            dialect.quote_literal(file)
+        )
+    }
+
+    /// File `INTO OUTFILE` may write given the server's `secure_file_priv`
+    pub fn outfile_path(secure_file_priv: &str) -> Result<String, AppError> {
+        let directory = match secure_file_priv.trim() {
+            "NULL" => {
+                return Err(AppError::Validation(
+                    "The server disables file exports (secure_file_priv is NULL)".to_string(),
+                ))
+            }
+            "" => "/tmp",
+            directory => directory.trim_end_matches('/'),
+        };
+        Ok(format!(
+            "{}/ddm-export-{}.csv",
+            directory,
+            uuid::Uuid::new_v4()
+        ))
+    }
+
+    /// Variables the MongoDB tools and shell read the password from, see `mongo_env_file`
+    pub fn mongo_credentials(database: &DatabaseContainer) -> Vec<(&'static str, String)> {
+        let password = DatabaseCredentials::of(database).password;
+        vec![
+            (MONGO_TOOLS_CONFIG_VAR, mongo_tools_config(&password)),
+            (MONGO_PASSWORD_VAR, password),
+        ]
+    }
+
+    /// Env file of `mongo_credentials`, to keep until the command returns
+    fn mongo_env_file(database: &DatabaseContainer) -> Result<EnvFile, AppError> {
+        let credentials = Self::mongo_credentials(database);
+        let vars: Vec<(&str, &str)> = credentials
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect();
+        EnvFile::create(&vars)
+    }
+
+    /// `docker exec` arguments running mongoexport on a collection
+    /// The password comes from `env_file`, written from `mongo_credentials`
+    pub fn mongoexport_args(
+        database: &DatabaseContainer,
+        env_file: &Path,
+        mongo_database: &str,
+        collection: &str,
+        fields: &[String],
+        header: bool,
+    ) -> Vec<String> {
+        let credentials = DatabaseCredentials::of(database);
+        let fields = format!("--fields={}", fields.join(","));
+        let mut tool = vec![
+            "mongoexport",
+            "--quiet",
+            "-u",
+            &credentials.username,
+            "--authenticationDatabase",
+            "admin",
+            "--db",
+            mongo_database,
+            "--collection",
+            collection,
+            "--type=csv",
+            &fields,
+        ];
+        if !header {
+            tool.push("--noHeaderLine");
+        }
+
+        let mut args = vec![
+            "exec".to_string(),
+            "--env-file".to_string(),
+            env_file.to_string_lossy().to_string(),
+            database.name.clone(),
+        ];
+        args.extend(mongo_tool(&tool));
+        args
+    }
+
+    /// Write `table` to a CSV file at `path`, emitting progress while it streams
+    pub async fn export_table(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        table: &str,
+        path: &Path,
+        options: &TableExportOptions,
+    ) -> Result<TableExportResult, AppError> {
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to export its data",
+                database.name
+            )));
+        }
+
+        let credentials = DatabaseCredentials::of(database);
+        let schema = options
+            .schema
+            .clone()
+            .filter(|schema| !schema.is_empty())
+            .unwrap_or_else(|| match database.db_type.as_str() {
+                "PostgreSQL" => "public".to_string(),
+                _ => credentials.database.clone(),
+            });
+        if schema.is_empty() {
+            return Err(AppError::Validation(
+                "Choose the database the table belongs to".to_string(),
+            ));
+        }
+
+        let progress = |bytes_written: u64, done: bool| {
+            EventService::new().table_export_progress(
+                app,
+                &TableExportProgressPayload {
+                    container_id: database.id.clone(),
+                    table: table.to_string(),
+                    bytes_written,
+                    done,
+                },
+            )
+        };
+
+        let bytes_written = match database.db_type.as_str() {
+            "PostgreSQL" => {
+                let sql =
+                    Self::postgres_export_sql(&schema, table, &options.columns, options.header);
+                let args = SqlService::client_args(database, None, None, &sql)?;
+                self.stream_to_file(app, database, &args, None, path, &progress)
+                    .await?
+            }
+            "MySQL" | "MariaDB" => {
+                let (file, columns) = self
+                    .mysql_outfile(app, database, &schema, table, options)
+                    .await?;
+
+                // INTO OUTFILE never writes a header
+                let header = options.header.then(|| csv_record(&columns));
+                let args: Vec<String> = ["exec", &database.name, "cat", &file]
+                    .iter()
+                    .map(|arg| arg.to_string())
+                    .collect();
+                let result = self
+                    .stream_to_file(app, database, &args, header, path, &progress)
+                    .await;
+
+                let _ = DockerService::for_database(app, database)?
+                    .stdout(
+                        app,
+                        "Failed to export table",
+                        &["exec", &database.name, "rm", "-f", &file],
+                    )
+                    .await;
+                result?
+            }
+            "MongoDB" => {
+                let env_file = Self::mongo_env_file(database)?;
+                let mut fields = options.columns.clone();
+                if fields.is_empty() {
+                    fields = self
+                        .mongo_fields(app, database, env_file.path(), &schema, table)
+                        .await?;
+                }
+                let args = Self::mongoexport_args(
+                    database,
+                    env_file.path(),
+                    &schema,
+                    table,
+                    &fields,
+                    options.header,
+                );
+                self.stream_to_file(app, database, &args, None, path, &progress)
+                    .await?
+            }
+            other => {
+                return Err(AppError::Validation(format!(
+                    "{} tables cannot be exported to CSV",
+                    other
+                )))
+            }
+        };
+
+        progress(bytes_written, true);
+        Ok(TableExportResult {
+            path: path.display().to_string(),
+            bytes_written,
+        })
+    }
+
+    /// Write the table to a file inside the container, returns the file and its columns
+    async fn mysql_outfile(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        schema: &str,
+        table: &str,
+        options: &TableExportOptions,
+    ) -> Result<(String, Vec<String>), AppError> {
+        let sql_service = SqlService::new();
+        let columns = if options.columns.is_empty() {
+            sql_service
+                .table_columns(app, database, schema, table)
+                .await?
+                .into_iter()
+                .map(|column| column.name)
+                .collect()
+        } else {
+            options.columns.clone()
+        };
+
+        let secure_file_priv = sql_service
+            .query_lines(app, database, "SELECT @@secure_file_priv")
+            .await?
+            .first()
+            .cloned()
+            .unwrap_or_default();
+        let file = Self::outfile_path(&secure_file_priv)?;
+
+        sql_service
+            .query_lines(
+                app,
+                database,
+                &Self::mysql_outfile_sql(schema, table, &columns, &file),
+            )
+            .await?;
+
+        Ok((file, columns))
+    }
+
+    /// Field names of the first document of a collection
+    async fn mongo_fields(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        env_file: &Path,
+        mongo_database: &str,
+        collection: &str,
+    ) -> Result<Vec<String>, AppError> {
+        let credentials = DatabaseCredentials::of(database);
+        let string = |value: &str| serde_json::Value::from(value).to_string();
+        let script = format!(
+            "{}print(Object.keys(db.getSiblingDB({}).getCollection({}).findOne() ?? {{}}).join('\\n'))",
+            mongo_login(&credentials.username),
+            string(mongo_database),
+            string(collection)
+        );
+
+        let output = DockerService::for_database(app, database)?
+            .stdout(
+                app,
+                "Failed to export table",
+                &[
+                    "exec",
+                    "--env-file",
+                    &env_file.to_string_lossy(),
+                    &database.name,
+                    "mongosh",
+                    "--quiet",
+                    "--eval",
+                    &script,
+                ],
+            )
+            .await?;
+
+        let fields: Vec<String> = output
+            .lines()
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(str::to_owned)
+            .collect();
+        if fields.is_empty() {
+            return Err(AppError::Validation(format!(
+                "{} has no documents to read fields from, list the fields to export",
+                collection
+            )));
+        }
+        Ok(fields)
+    }
+
+    /// Run docker and write its stdout to `path` as it arrives, after `header` if any
+    /// A failed export removes the partial file
+    async fn stream_to_file(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        args: &[String],
+        header: Option<String>,
+        path: &Path,
+        progress: &impl Fn(u64, bool),
+    ) -> Result<u64, AppError> {
+        let result = self
+            .write_stream(app, database, args, header, path, progress)
+            .await;
+        if result.is_err() {
+            let _ = std::fs::remove_file(path);
+        }
+        result
+    }
+
+    async fn write_stream(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        args: &[String],
+        header: Option<String>,
+        path: &Path,
+        progress: &impl Fn(u64, bool),
+    ) -> Result<u64, AppError> {
+        let write_error =
+            |e: std::io::Error| AppError::Io(format!("Failed to write {}: {}", path.display(), e));
+        let mut file = File::create(path).map_err(write_error)?;
+
+        let mut bytes_written = 0;
+        if let Some(header) = header {
+            file.write_all(header.as_bytes()).map_err(write_error)?;
+            bytes_written += header.len() as u64;
+        }
+
+        let (mut events, child) = DockerService::for_database(app, database)?
+            .command(app)
+            .await
+            .args(args)
+            .set_raw_out(true)
+            .spawn()
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to export table", &e.to_string(), None)
+            })?;
+
+        let mut stderr = String::new();
+        let mut exit_code = None;
+        let mut reported = 0;
+        while let Some(event) = events.recv().await {
+            match event {
+                CommandEvent::Stdout(chunk) => {
+                    if let Err(e) = file.write_all(&chunk) {
+                        let _ = child.kill();
+                        return Err(write_error(e));
+                    }
+                    bytes_written += chunk.len() as u64;
+                    if bytes_written - reported >= PROGRESS_INTERVAL_BYTES {
+                        reported = bytes_written;
+                        progress(bytes_written, false);
+                    }
+                }
+                CommandEvent::Stderr(chunk) => stderr.push_str(&String::from_utf8_lossy(&chunk)),
+                CommandEvent::Terminated(payload) => exit_code = payload.code,
+                _ => {}
+            }
+        }
+
+        if exit_code != Some(0) {
+            return Err(AppError::from_docker_output(
+                "Failed to export table",
+                &stderr,
+                exit_code,
+            ));
+        }
+        file.sync_all().map_err(write_error)?;
+
+        Ok(bytes_written)
+    }
+
+    /// `COPY FROM STDIN` into a table, `columns` in file order or all columns when empty
+    pub fn postgres_import_sql(
+        schema: &str,
+        table: &str,
+        columns: &[String],
+        delimiter: char,
+    ) -> String {
+        let dialect = SqlDialect::Postgres;
+        let columns = if columns.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " ({})",
+                columns
+                    .iter()
+                    .map(|column| dialect.quote_identifier(column))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+
+        format!(
+            "COPY {}{} FROM STDIN WITH (FORMAT csv, DELIMITER {})",
+            dialect.qualified_name(schema, table),
+            columns,
+            dialect.quote_literal(&delimiter.to_string())
+        )
+    }
+
+    /// `LOAD DATA LOCAL` of the client's stdin followed by the warnings it raised and their count
+    pub fn mysql_import_sql(
+        schema: &str,
+        table: &str,
+        columns: &[String],
+        delimiter: char,
+        crlf: bool,
+    ) -> String {
+        let dialect = SqlDialect::MySql;
+        let columns = if columns.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " ({})",
+                columns
+                    .iter()
+                    .map(|column| dialect.quote_identifier(column))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+
+        format!(
+            "SET SESSION max_error_count = {}; \
+LOAD DATA LOCAL INFILE '/dev/stdin' INTO TABLE {} CHARACTER SET utf8mb4 \
+FIELDS TERMINATED BY {} OPTIONALLY ENCLOSED BY '\"' ESCAPED BY '' \
+LINES TERMINATED BY '{}'{}; SHOW WARNINGS; SHOW COUNT(*) WARNINGS",
+            MYSQL_MAX_WARNINGS,
+            dialect.qualified_name(schema, table),
+            dialect.quote_literal(&delimiter.to_string()),
+            if crlf { "\\r\\n" } else { "\\n" },
+            columns
+        )
+    }
+
+    /// `docker exec` arguments running mongoimport from stdin
+    /// The password comes from `env_file`, written from `mongo_credentials`
+    pub fn mongoimport_args(
+        database: &DatabaseContainer,
+        env_file: &Path,
+        mongo_database: &str,
+        collection: &str,
+        fields: &[String],
+        delimiter: char,
+    ) -> Result<Vec<String>, AppError> {
+        let file_type = match delimiter {
+            ',' => "--type=csv",
+            '\t' => "--type=tsv",
+            _ => {
+                return Err(AppError::Validation(
+                    "MongoDB imports comma or tab separated files only".to_string(),
+                ))
+            }
+        };
+        if fields.is_empty() {
+            return Err(AppError::Validation(
+                "Name the fields of a file without a header".to_string(),
+            ));
+        }
+
+        let credentials = DatabaseCredentials::of(database);
+        let fields = format!("--fields={}", fields.join(","));
+        let mut args = vec![
+            "exec".to_string(),
+            "-i".to_string(),
+            "--env-file".to_string(),
+            env_file.to_string_lossy().to_string(),
+            database.name.clone(),
+        ];
+        args.extend(mongo_tool(&[
+            "mongoimport",
+            "-u",
+            &credentials.username,
+            "--authenticationDatabase",
+            "admin",
+            "--db",
+            mongo_database,
+            "--collection",
+            collection,
+            file_type,
+            &fields,
+        ]));
+        Ok(args)
+    }
+
+    /// Error of an aborted `COPY`, PostgreSQL counts lines from the first data line
+    /// e.g. "ERROR:  invalid input syntax for type integer: \"x\"\nCONTEXT:  COPY users, line 3, column id: \"x\""
+    pub fn postgres_row_error(stderr: &str, line_offset: u64) -> Option<CsvRowError> {
+        let message = stderr
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("ERROR:"))?
+            .trim()
+            .to_string();
+        let line = stderr
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("CONTEXT:"))
+            .and_then(|context| context.split(", line ").nth(1))
+            .and_then(|rest| {
+                rest.split(|c: char| !c.is_ascii_digit())
+                    .next()?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .map(|line| line + line_offset);
+
+        Some(CsvRowError { line, message })
+    }
+
+    /// `SHOW WARNINGS` rows of a load, e.g. "Warning\t1366\tIncorrect integer value: 'x' for column 'id' at row 3"
+    /// followed by their count, warnings past the ones MySQL kept are reported as one error
+    /// Returns the errors and how many rows they kept out of the table
+    pub fn mysql_row_errors(output: &str, line_offset: u64) -> (Vec<CsvRowError>, u64) {
+        let mut errors = Vec::new();
+        let mut skipped = 0;
+        let mut total = 0;
+
+        for row in output.lines() {
+            if let Ok(count) = row.trim().parse::<u64>() {
+                total = count;
+                continue;
+            }
+            let mut columns = row.splitn(3, '\t');
+            let (Some(_level), Some(code), Some(message)) =
+                (columns.next(), columns.next(), columns.next())
+            else {
+                continue;
+            };
+            // LOCAL loads skip rows with duplicate keys instead of failing
+            if code == "1062" {
+                skipped += 1;
+            }
+            let line = message
+                .rsplit_once(" at row ")
+                .and_then(|(_, row)| row.trim().parse::<u64>().ok())
+                .map(|row| row + line_offset);
+            errors.push(CsvRowError {
+                line,
+                message: message.to_string(),
+            });
+        }
+
+        let unlisted = total.saturating_sub(errors.len() as u64);
+        if unlisted > 0 {
+            errors.push(CsvRowError {
+                line: None,
+                message: format!(
+                    "{} more warnings were not listed, the imported count may include rows they skipped",
+                    unlisted
+                ),
+            });
+        }
+
+        (errors, skipped)
+    }
+
+    /// Imported count and failures from mongoimport's log
+    /// e.g. "5 document(s) imported successfully. 1 document(s) failed to import."
+    pub fn mongoimport_report(stderr: &str) -> CsvImportReport {
+        let count_before = |text: &str, marker: &str| -> Option<u64> {
+            let (before, _) = text.split_once(marker)?;
+            before.split_whitespace().last()?.parse().ok()
+        };
+
+        let mut report = CsvImportReport::default();
+        for line in stderr.lines() {
+            if let Some(imported) = count_before(line, " document(s) imported successfully") {
+                report.rows_imported = imported;
+            } else if line.to_lowercase().contains("error") {
+                // Log lines start with a timestamp and a tab
+                let message = line.split_once('\t').map_or(line, |(_, message)| message);
+                report.errors.push(CsvRowError {
+                    line: None,
+                    message: message.trim().to_string(),
+                });
+            }
+        }
+        report
+    }
+
+    /// Load a local CSV file into an existing table or collection
+    /// Problems with the data come back in the report, other failures as errors
+    pub async fn import_csv(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        table: &str,
+        path: &Path,
+        mapping: &CsvImportMapping,
+    ) -> Result<CsvImportReport, AppError> {
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to import data",
+                database.name
+            )));
+        }
+
+        let schema = mapping
+            .schema
+            .clone()
+            .filter(|schema| !schema.is_empty())
+            .unwrap_or_else(|| match database.db_type.as_str() {
+                "PostgreSQL" => "public".to_string(),
+                _ => DatabaseCredentials::of(database).database,
+            });
+        if schema.is_empty() {
+            return Err(AppError::Validation(
+                "Choose the database the table belongs to".to_string(),
+            ));
+        }
+
+        let (header, skip, crlf) = Self::read_header(path, mapping)?;
+        let columns = if mapping.columns.is_empty() {
+            header.clone().unwrap_or_default()
+        } else {
+            mapping.columns.clone()
+        };
+        let line_offset = u64::from(header.is_some());
+
+        match database.db_type.as_str() {
+            "PostgreSQL" => {
+                let sql = Self::postgres_import_sql(&schema, table, &columns, mapping.delimiter);
+                let mut args = SqlService::client_args(database, None, None, &sql)?;
+                args.insert(1, "-i".to_string());
+
+                let run = self
+                    .stream_from_file(app, database, &args, path, skip)
+                    .await?;
+                if run.exit_code == Some(0) {
+                    return Ok(CsvImportReport {
+                        rows_imported: run.records,
+                        errors: Vec::new(),
+                    });
+                }
+                // COPY is all or nothing
+                match Self::postgres_row_error(&run.stderr, line_offset) {
+                    Some(error) => Ok(CsvImportReport {
+                        rows_imported: 0,
+                        errors: vec![error],
+                    }),
+                    None => Err(AppError::from_docker_output(
+                        "Failed to import CSV",
+                        &run.stderr,
+                        run.exit_code,
+                    )),
+                }
+            }
+            "MySQL" | "MariaDB" => {
+                let enabled = self
+                    .enable_local_infile(app, database, mapping.enable_local_infile)
+                    .await?;
+                let result: Result<CsvImportReport, AppError> = async {
+                    let sql =
+                        Self::mysql_import_sql(&schema, table, &columns, mapping.delimiter, crlf);
+                    let env_file = SqlService::env_file(database)?;
+                    let mut args = SqlService::client_args(
+                        database,
+                        env_file.as_ref().map(EnvFile::path),
+                        None,
+                        &sql,
+                    )?;
+                    args.insert(1, "-i".to_string());
+                    // The client refuses LOCAL loads unless asked to allow them
+                    let client = args
+                        .iter()
+                        .position(|arg| arg == &database.name)
+                        .unwrap_or_default()
+                        + 1;
+                    args.insert(client + 1, "--local-infile=1".to_string());
+
+                    let run = self
+                        .stream_from_file(app, database, &args, path, skip)
+                        .await?;
+                    if run.exit_code != Some(0) {
+                        return Err(AppError::from_docker_output(
+                            "Failed to import CSV",
+                            &run.stderr,
+                            run.exit_code,
+                        ));
+                    }
+                    let (errors, skipped) = Self::mysql_row_errors(&run.stdout, line_offset);
+                    Ok(CsvImportReport {
+                        rows_imported: run.records.saturating_sub(skipped),
+                        errors,
+                    })
+                }
+                .await;
+                if enabled {
+                    self.restore_local_infile(app, database).await;
+                }
+                result
+            }
+            "MongoDB" => {
+                let env_file = Self::mongo_env_file(database)?;
+                let args = Self::mongoimport_args(
+                    database,
+                    env_file.path(),
+                    &schema,
+                    table,
+                    &columns,
+                    mapping.delimiter,
+                )?;
+
+                let run = self
+                    .stream_from_file(app, database, &args, path, skip)
+                    .await?;
+                let report = Self::mongoimport_report(&run.stderr);
+                if run.exit_code != Some(0) && report.errors.is_empty() {
+                    return Err(AppError::from_docker_output(
+                        "Failed to import CSV",
+                        &run.stderr,
+                        run.exit_code,
+                    ));
+                }
+                Ok(report)
+            }
+            other => Err(AppError::Validation(format!(
+                "CSV files cannot be imported into {}",
+                other
+            ))),
+        }
+    }
+
+    /// Header fields when the mapping says there is one, the bytes they take up, and whether
+    /// the file ends its lines with CRLF
+    fn read_header(
+        path: &Path,
+        mapping: &CsvImportMapping,
+    ) -> Result<(Option<Vec<String>>, usize, bool), AppError> {
+        let read_error =
+            |e: std::io::Error| AppError::Io(format!("Failed to read {}: {}", path.display(), e));
+        let mut start = Vec::new();
+        File::open(path)
+            .map_err(read_error)?
+            .take(HEADER_SNIFF_BYTES)
+            .read_to_end(&mut start)
+            .map_err(read_error)?;
+
+        let first_len = match first_record_len(&start) {
+            Some(len) => len,
+            // A file shorter than the sniffed bytes is one record
+            None if (start.len() as u64) < HEADER_SNIFF_BYTES => start.len(),
+            None => {
+                return Err(AppError::Validation(format!(
+                    "The first record of {} is longer than {} KB, check the delimiter and quotes",
+                    path.display(),
+                    HEADER_SNIFF_BYTES / 1024
+                )))
+            }
+        };
+        let crlf = start[..first_len].ends_with(b"\r\n");
+        if !mapping.header {
+            return Ok((None, 0, crlf));
+        }
+
+        let fields = parse_csv_record(
+            &String::from_utf8_lossy(&start[..first_len]),
+            mapping.delimiter,
+        )
+        .into_iter()
+        // A UTF-8 byte order mark would become part of the first column name
+        .map(|field| field.trim_start_matches('\u{feff}').trim().to_string())
+        .collect();
+        Ok((Some(fields), first_len, crlf))
+    }
+
+    /// MySQL 8 turns LOCAL loads off on the server by default, `confirmed` allows turning them on
+    /// Returns whether they were turned on, to turn them off again after the import
+    async fn enable_local_infile(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        confirmed: bool,
+    ) -> Result<bool, AppError> {
+        let sql_service = SqlService::new();
+        let enabled = sql_service
+            .query_lines(app, database, "SELECT @@GLOBAL.local_infile")
+            .await?
+            .first()
+            .is_some_and(|value| value.trim() == "1");
+        if enabled {
+            return Ok(false);
+        }
+        if !confirmed {
+            return Err(AppError::SettingChange {
+                name: database.name.clone(),
+                setting: "local_infile".to_string(),
+                reason: "the server refuses loading files sent by the client".to_string(),
+            });
+        }
+
+        sql_service
+            .query_lines(app, database, "SET GLOBAL local_infile = 1")
+            .await?;
+        Ok(true)
+    }
+
+    /// Turn local_infile off again after an import turned it on
+    /// The import already ran, so a failure is reported instead of returned
+    async fn restore_local_infile(&self, app: &AppHandle, database: &DatabaseContainer) {
+        let restored = SqlService::new()
+            .query_lines(app, database, "SET GLOBAL local_infile = 0")
+            .await;
+        if let Err(error) = restored {
+            EventService::new().background_error(
+                app,
+                "csv-import",
+                format!(
+                    "local_infile stays on for {}, it could not be restored: {}",
+                    database.name, error
+                ),
+            );
+        }
+    }
+
+    /// Run docker with the file at `path` on its stdin, from byte `skip` on
+    async fn stream_from_file(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        args: &[String],
+        path: &Path,
+        skip: usize,
+    ) -> Result<StdinRun, AppError> {
+        let read_error =
+            |e: std::io::Error| AppError::Io(format!("Failed to read {}: {}", path.display(), e));
+        let mut file = File::open(path).map_err(read_error)?;
+        file.seek(SeekFrom::Start(skip as u64))
+            .map_err(read_error)?;
+
+        let (mut events, mut child) = DockerService::for_database(app, database)?
+            .command(app)
+            .await
+            .args(args)
+            .spawn()
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to import CSV", &e.to_string(), None)
+            })?;
+
+        // Writing blocks while the process is busy, its output is drained meanwhile
+        let writer = tauri::async_runtime::spawn_blocking(move || {
+            let mut counter = CsvRecordCounter::default();
+            let mut buffer = vec![0; 64 * 1024];
+            loop {
+                let read = file.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                counter.push(&buffer[..read]);
+                // A process that stopped reading failed, its exit code tells why
+                if child.write(&buffer[..read]).is_err() {
+                    break;
+                }
+            }
+            // Dropping the child closes its stdin
+            drop(child);
+            Ok::<u64, std::io::Error>(counter.finish())
+        });
+
+        let mut run = StdinRun::default();
+        while let Some(event) = events.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => {
+                    run.stdout.push_str(&String::from_utf8_lossy(&line));
+                    run.stdout.push('\n');
+                }
+                CommandEvent::Stderr(line) => {
+                    run.stderr.push_str(&String::from_utf8_lossy(&line));
+                    run.stderr.push('\n');
+                }
+                CommandEvent::Terminated(payload) => run.exit_code = payload.code,
+                _ => {}
+            }
+        }
+
+        run.records = writer
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .map_err(read_error)?;
+        Ok(run)
+    }
+}
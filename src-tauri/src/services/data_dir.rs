@@ -0,0 +1,91 @@
+use crate::services::{DockerService, MetricsHistoryService};
+use crate::types::*;
+use tauri::AppHandle;
+
+// Where the helper container mounts the volume of a stopped database
+const VOLUME_MOUNT: &str = "/data";
+
+// Prints one line per entry of the directory in $1 for `DataDirEntry::parse_line`
+// Runs with the BusyBox tools of Alpine images as well as the GNU ones of Debian images
+const LIST_SCRIPT: &str = "cd -- \"$1\" || exit 1
+for entry in .[!.]* ..?* *; do
+  [ -e \"$entry\" ] || [ -L \"$entry\" ] || continue
+  kib=$(du -sk -- \"$entry\" 2>/dev/null | cut -f1)
+  printf '%s\\t%s\\n' \"${kib:-0}\" \"$(stat -c '%F\t%s\t%Y\t%n' -- \"$entry\")\"
+done";
+
+/// Lists the data directory of a database without changing anything in it
+pub struct DataDirService;
+
+impl DataDirService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Entries of the directory at `path` below the data directory
+    /// Running databases are read with `docker exec`, stopped ones from their volume
+    pub async fn list(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        path: &str,
+    ) -> Result<DataDirListing, AppError> {
+        let relative = DataDirListing::normalize_path(path)?;
+        let root = MetricsHistoryService::data_paths(database)
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AppError::Validation(format!(
+                    "The data directory of {} is unknown",
+                    database.db_type
+                ))
+            })?;
+
+        let running = database.is_running();
+        let args: Vec<String> = if running {
+            [
+                "exec",
+                &database.name,
+                "sh",
+                "-c",
+                LIST_SCRIPT,
+                "sh",
+                &DataDirListing::join(&root, &relative),
+            ]
+            .map(str::to_string)
+            .to_vec()
+        } else {
+            let volume = database.data_volumes().into_iter().next().ok_or_else(|| {
+                AppError::Validation(format!(
+                    "Start {} to browse its data directory, it has no volume",
+                    database.name
+                ))
+            })?;
+            [
+                "run",
+                "--rm",
+                "--network",
+                "none",
+                "-v",
+                &format!("{}:{}:ro", volume, VOLUME_MOUNT),
+                "alpine:latest",
+                "sh",
+                "-c",
+                LIST_SCRIPT,
+                "sh",
+                &DataDirListing::join(VOLUME_MOUNT, &relative),
+            ]
+            .map(str::to_string)
+            .to_vec()
+        };
+
+        let output = DockerService::for_database(app, database)?
+            .stdout(app, "Failed to list the data directory", &args)
+            .await?;
+        let entries = output
+            .lines()
+            .filter_map(DataDirEntry::parse_line)
+            .collect();
+        Ok(DataDirListing::new(&root, &relative, entries, !running))
+    }
+}
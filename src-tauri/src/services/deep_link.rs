@@ -0,0 +1,48 @@
+use crate::commands::{build_creation_window, show_main_window};
+use crate::services::EventService;
+use crate::types::*;
+use tauri::{AppHandle, Manager, Url};
+
+pub struct DeepLinkService;
+
+impl DeepLinkService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Handle links opened while running or passed at launch
+    pub fn handle(&self, app: &AppHandle, urls: Vec<Url>) {
+        for url in urls {
+            match DeepLinkAction::parse(url.as_str()) {
+                Ok(DeepLinkAction::Create(prefill)) => {
+                    if let Err(e) = self.open_creation(app, &prefill) {
+                        EventService::new().background_error(
+                            app,
+                            "deep-link",
+                            format!("Failed to open deep link {}: {}", url, e),
+                        );
+                    }
+                }
+                Err(e) => EventService::new().background_error(
+                    app,
+                    "deep-link",
+                    format!("Ignoring deep link {}: {}", url, e),
+                ),
+            }
+        }
+    }
+
+    /// The creation window replaces any open one so the link's values are applied
+    pub fn open_creation(&self, app: &AppHandle, prefill: &CreatePrefill) -> Result<(), AppError> {
+        show_main_window(app);
+
+        if let Some(window) = app.get_webview_window("container-creation") {
+            let _ = window.destroy();
+        }
+
+        build_creation_window(
+            app,
+            format!("create-container.html?{}", prefill.window_query()),
+        )
+    }
+}
@@ -1,22 +1,201 @@
+use crate::services::{ConfigService, EventService, TlsService, PASSWORD_MASK};
 use crate::types::*;
 use serde_json::json;
-use std::sync::OnceLock;
-use tauri::AppHandle;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::sync::{LazyLock, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::process::{Command, CommandEvent, Output};
 use tauri_plugin_shell::ShellExt;
 
+// `docker ps` columns read by `parse_ps_output`
+pub const PS_FORMAT: &str = "{{.ID}},{{.Names}},{{.Status}}";
+
 // Cache for the enriched PATH to avoid repeated shell invocations
 static ENRICHED_PATH: OnceLock<String> = OnceLock::new();
+// Detected socket of the local runtime when the CLI would not find it by itself
+// Passed to each local docker call, the process environment is left alone
+static DETECTED_DOCKER_HOST: RwLock<Option<String>> = RwLock::new(None);
+
+// How long `docker ps` and status results are reused
+const QUERY_CACHE_TTL: Duration = Duration::from_millis(1500);
+
+static PS_CACHE: LazyLock<Mutex<QueryCache<HashMap<String, DockerContainerState>>>> =
+    LazyLock::new(|| Mutex::new(QueryCache::new(QUERY_CACHE_TTL)));
+static STATUS_CACHE: LazyLock<Mutex<QueryCache<serde_json::Value>>> =
+    LazyLock::new(|| Mutex::new(QueryCache::new(QUERY_CACHE_TTL)));
+// Remote hosts the last sync could not reach
+static UNREACHABLE_HOSTS: LazyLock<Mutex<HashSet<String>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+// How long `start_docker` waits for a launched daemon to answer
+const DAEMON_START_TIMEOUT: Duration = Duration::from_secs(120);
+const DAEMON_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// Held while a query runs, concurrent callers wait and then hit the cache
+static PS_QUERY: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+static STATUS_QUERY: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Docker CLI client, for the local daemon or a remote host
+#[derive(Clone)]
+pub struct DockerService {
+    // None for the daemon the CLI talks to by default
+    host: Option<DockerHost>,
+}
+
+// Clears the query caches when dropped
+struct InvalidateCache;
 
-pub struct DockerService;
+impl Drop for InvalidateCache {
+    fn drop(&mut self) {
+        DockerService::invalidate_cache();
+    }
+}
 
 impl DockerService {
     pub fn new() -> Self {
-        Self
+        Self { host: None }
+    }
+
+    /// Client for a remote Docker host
+    pub fn on_host(host: &DockerHost) -> Self {
+        Self {
+            host: Some(host.clone()),
+        }
+    }
+
+    /// Client for the host with id `host_id`, the local daemon when None
+    pub fn for_host_id(app: &AppHandle, host_id: Option<&str>) -> Result<Self, AppError> {
+        let Some(host_id) = host_id else {
+            return Ok(Self::new());
+        };
+
+        let settings = app.state::<SettingsStore>();
+        let settings = settings.lock().unwrap();
+        settings
+            .docker_hosts
+            .iter()
+            .find(|host| host.id == host_id)
+            .map(Self::on_host)
+            .ok_or_else(|| AppError::NotFound(format!("Docker host '{}'", host_id)))
+    }
+
+    /// Address clients connect to for the databases of this daemon, None for this machine
+    pub fn connection_host(&self) -> Option<String> {
+        self.host.as_ref().and_then(DockerHost::address)
+    }
+
+    /// Client for the host a database runs on
+    pub fn for_database(app: &AppHandle, database: &DatabaseContainer) -> Result<Self, AppError> {
+        Self::for_host_id(app, database.host.as_deref())
+    }
+
+    /// `DOCKER_HOST` of the remote host, or of the detected local socket
+    /// Nothing when the CLI finds the local daemon by itself
+    pub fn host_env(&self) -> Option<(&'static str, String)> {
+        match &self.host {
+            Some(host) => Some(("DOCKER_HOST", host.url.clone())),
+            None => Self::detected_docker_host().map(|url| ("DOCKER_HOST", url)),
+        }
+    }
+
+    /// `-H <url>` naming the daemon on a docker command line run outside the app,
+    /// which does not inherit the `DOCKER_HOST` given to the app's own calls
+    pub fn host_args(&self) -> Vec<String> {
+        self.host_env()
+            .map(|(_, url)| vec!["-H".to_string(), url])
+            .unwrap_or_default()
+    }
+
+    fn detected_docker_host() -> Option<String> {
+        DETECTED_DOCKER_HOST.read().unwrap().clone()
+    }
+
+    /// `DOCKER_HOST` local calls use, the user's own or the detected one
+    fn local_docker_host() -> Option<String> {
+        std::env::var("DOCKER_HOST")
+            .ok()
+            .or_else(Self::detected_docker_host)
+    }
+
+    /// Name the remote host in errors about an unreachable daemon
+    pub fn host_error(&self, error: AppError) -> AppError {
+        match &self.host {
+            Some(host) => error.on_host(&host.name),
+            None => error,
+        }
+    }
+
+    /// `docker` command for this client's daemon, with the enriched PATH and its `DOCKER_HOST`
+    /// Every docker call goes through it so it reaches the host the database runs on
+    pub async fn command(&self, app: &AppHandle) -> Command {
+        let enriched_path = self.get_enriched_path(app).await;
+        app.shell()
+            .command("docker")
+            .env("PATH", enriched_path)
+            .envs(self.host_env())
+    }
+
+    /// Run docker with `args`, its output whatever the exit code
+    pub async fn output<I, S>(
+        &self,
+        app: &AppHandle,
+        context: &str,
+        args: I,
+    ) -> Result<Output, AppError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.command(app)
+            .await
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| AppError::from_docker_output(context, &e.to_string(), None))
+    }
+
+    /// Output of docker with `args`, a failed call becomes an error described by `context`
+    pub async fn checked_output<I, S>(
+        &self,
+        app: &AppHandle,
+        context: &str,
+        args: I,
+    ) -> Result<Output, AppError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let output = self.output(app, context, args).await?;
+        if !output.status.success() {
+            return Err(self.host_error(AppError::from_docker_output(
+                context,
+                &String::from_utf8_lossy(&output.stderr),
+                output.status.code(),
+            )));
+        }
+        Ok(output)
+    }
+
+    /// Stdout of docker with `args`, a failed call becomes an error described by `context`
+    pub async fn stdout<I, S>(
+        &self,
+        app: &AppHandle,
+        context: &str,
+        args: I,
+    ) -> Result<String, AppError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let output = self.checked_output(app, context, args).await?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
     /// Get the enriched PATH by reading it from the user's shell
     /// This solves the issue where macOS apps don't inherit the full PATH
-    async fn get_enriched_path(&self, app: &AppHandle) -> String {
+    pub async fn get_enriched_path(&self, app: &AppHandle) -> String {
         // Return cached PATH if available
         if let Some(path) = ENRICHED_PATH.get() {
             return path.clone();
@@ -68,6 +247,15 @@ impl DockerService {
         &self,
         container_name: &str,
         docker_args: &DockerRunArgs,
+    ) -> Vec<String> {
+        Self::run_args(container_name, docker_args, Vec::new())
+    }
+
+    /// `docker run` arguments, `options` go with the other options before the image
+    fn run_args(
+        container_name: &str,
+        docker_args: &DockerRunArgs,
+        options: Vec<String>,
     ) -> Vec<String> {
         let mut args = vec![
             "run".to_string(),
@@ -75,11 +263,17 @@ impl DockerService {
             "--name".to_string(),
             container_name.to_string(),
         ];
+        args.extend(options);
+
+        if let Some(shm_size) = &docker_args.shm_size {
+            args.push("--shm-size".to_string());
+            args.push(shm_size.clone());
+        }
 
         // Add port mappings
         for port in &docker_args.ports {
             args.push("-p".to_string());
-            args.push(format!("{}:{}", port.host, port.container));
+            args.push(port.publish_arg());
         }
 
         // Add volume mounts
@@ -88,8 +282,10 @@ impl DockerService {
             args.push(format!("{}:{}", volume.name, volume.path));
         }
 
-        // Add environment variables
-        for (key, value) in &docker_args.env_vars {
+        // Add environment variables, sorted so the command is reproducible
+        let mut env_vars: Vec<_> = docker_args.env_vars.iter().collect();
+        env_vars.sort();
+        for (key, value) in env_vars {
             args.push("-e".to_string());
             args.push(format!("{}={}", key, value));
         }
@@ -105,7 +301,92 @@ impl DockerService {
         args
     }
 
-    pub async fn check_docker_status(&self, app: &AppHandle) -> Result<serde_json::Value, String> {
+    /// `docker run` arguments of a database, labelled so it is recognized without the store
+    pub fn build_database_command(
+        &self,
+        container_name: &str,
+        docker_args: &DockerRunArgs,
+        labels: &ManagementLabels,
+    ) -> Vec<String> {
+        Self::run_args(container_name, docker_args, labels.to_args())
+    }
+
+    /// `docker run` command that recreates a database from its stored configuration
+    /// With `redact_password` the password is replaced by a placeholder
+    pub fn docker_run_command(
+        &self,
+        database: &DatabaseContainer,
+        redact_password: bool,
+    ) -> Result<String, AppError> {
+        let mut docker_args = database.stored_docker_args.clone().ok_or_else(|| {
+            AppError::Validation(format!(
+                "'{}' was created before its Docker arguments were stored, edit and save it first",
+                database.name
+            ))
+        })?;
+
+        if database.stored_enable_tls {
+            TlsService::new().apply_tls_to_docker_args(
+                &database.name,
+                &database.db_type,
+                &mut docker_args,
+            )?;
+        }
+
+        if redact_password {
+            ConfigService::mask_secrets(&mut docker_args, PASSWORD_MASK);
+        }
+
+        let args: Vec<String> = std::iter::once("docker".to_string())
+            .chain(self.build_docker_command_from_args(&database.name, &docker_args))
+            .collect();
+
+        Ok(Self::shell_join(&args))
+    }
+
+    /// Join arguments into a command line for POSIX shells, quoting where needed
+    pub fn shell_join(args: &[String]) -> String {
+        args.iter()
+            .map(|arg| {
+                let safe = !arg.is_empty()
+                    && arg
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "-_./:=,@%+".contains(c));
+                if safe {
+                    arg.clone()
+                } else {
+                    format!("'{}'", arg.replace('\'', "'\\''"))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Docker version and counts, reused for a moment across callers
+    pub async fn check_docker_status(
+        &self,
+        app: &AppHandle,
+    ) -> Result<serde_json::Value, AppError> {
+        let host = self.docker_host();
+        let _query = STATUS_QUERY.lock().await;
+        if let Some(status) = STATUS_CACHE.lock().unwrap().get(&host, Instant::now()) {
+            return Ok(status);
+        }
+
+        let status = self.query_docker_status(app).await?;
+        STATUS_CACHE
+            .lock()
+            .unwrap()
+            .insert(&host, status.clone(), Instant::now());
+        Ok(status)
+    }
+
+    async fn query_docker_status(&self, app: &AppHandle) -> Result<serde_json::Value, AppError> {
+        // A runtime started after the app may have brought up a socket that was missing
+        if self.host.is_none() && Self::local_docker_host().is_none() {
+            Self::configure_docker_host();
+        }
+        let environment = Self::docker_environment();
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
 
@@ -114,6 +395,7 @@ impl DockerService {
             .command("docker")
             .args(&["version", "--format", "json"])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -126,6 +408,7 @@ impl DockerService {
                         .command("docker")
                         .args(&["info", "--format", "json"])
                         .env("PATH", &enriched_path)
+                        .envs(self.host_env())
                         .output()
                         .await;
 
@@ -144,7 +427,8 @@ impl DockerService {
                                         "stopped": info_json.get("ContainersStopped")
                                     },
                                     "images": info_json.get("Images"),
-                                    "host": info_json.get("ServerVersion")
+                                    "host": info_json.get("ServerVersion"),
+                                    "environment": environment
                                 }));
                             }
                         }
@@ -160,7 +444,8 @@ impl DockerService {
                             "stopped": 0
                         },
                         "images": 0,
-                        "host": "docker"
+                        "host": "docker",
+                        "environment": environment
                     }));
                 }
             }
@@ -169,36 +454,293 @@ impl DockerService {
         // Docker is not running or not installed
         Ok(json!({
             "status": "stopped",
-            "error": "Docker daemon is not running or Docker is not installed"
+            "error": "Docker daemon is not running or Docker is not installed",
+            "guidance": environment.guidance(),
+            "environment": environment
         }))
     }
 
-    pub async fn sync_containers_with_docker(
+    /// List all Docker containers keyed by name with their ID and running state
+    pub async fn list_containers(
         &self,
         app: &AppHandle,
-        container_map: &mut std::collections::HashMap<String, DatabaseContainer>,
-    ) -> Result<(), String> {
+    ) -> Result<std::collections::HashMap<String, (String, bool)>, AppError> {
+        Ok(self
+            .list_container_states(app)
+            .await?
+            .into_iter()
+            .map(|(name, state)| (name, (state.id, state.running)))
+            .collect())
+    }
+
+    /// List all Docker containers keyed by name with their state, exit code and health
+    pub async fn list_container_states(
+        &self,
+        app: &AppHandle,
+    ) -> Result<std::collections::HashMap<String, DockerContainerState>, AppError> {
+        let host = self.docker_host();
+        let _query = PS_QUERY.lock().await;
+        if let Some(states) = PS_CACHE.lock().unwrap().get(&host, Instant::now()) {
+            return Ok(states);
+        }
+
+        let states = self.query_container_states(app).await?;
+        PS_CACHE
+            .lock()
+            .unwrap()
+            .insert(&host, states.clone(), Instant::now());
+        Ok(states)
+    }
+
+    /// Full state of the named containers from a single `docker inspect`
+    /// Containers that no longer exist are left out of the result
+    pub async fn inspect_containers(
+        &self,
+        app: &AppHandle,
+        names: &[String],
+    ) -> Result<std::collections::HashMap<String, DockerContainerState>, AppError> {
+        if names.is_empty() {
+            return Ok(std::collections::HashMap::new());
+        }
+
+        let key = format!("{}|inspect|{}", self.docker_host(), names.join(","));
+        let _query = PS_QUERY.lock().await;
+        if let Some(states) = PS_CACHE.lock().unwrap().get(&key, Instant::now()) {
+            return Ok(states);
+        }
+
+        let enriched_path = self.get_enriched_path(app).await;
+        let mut args = vec!["inspect", "--type", "container"];
+        args.extend(names.iter().map(String::as_str));
+
+        let output = app
+            .shell()
+            .command("docker")
+            .args(&args)
+            .env("PATH", &enriched_path)
+            .envs(self.host_env())
+            .output()
+            .await
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to inspect containers", &e.to_string(), None)
+            })?;
+
+        // Exits non-zero when any name is missing, the others are still printed
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() && !stdout.trim_start().starts_with('[') {
+            let error = String::from_utf8_lossy(&output.stderr);
+            if !error.contains("No such container") {
+                return Err(AppError::from_docker_output(
+                    "Failed to inspect containers",
+                    &error,
+                    output.status.code(),
+                ));
+            }
+        }
+
+        let states = DockerContainerState::parse_inspect_output(&stdout);
+        PS_CACHE
+            .lock()
+            .unwrap()
+            .insert(&key, states.clone(), Instant::now());
+        Ok(states)
+    }
+
+    /// Docker host the CLI talks to, cached results are kept apart per host
+    fn docker_host(&self) -> String {
+        match &self.host {
+            Some(host) => host.url.clone(),
+            None => Self::local_docker_host().unwrap_or_default(),
+        }
+    }
+
+    /// Runtime and socket the Docker CLI talks to
+    pub fn docker_environment() -> DockerEnvironment {
+        let existing: Vec<_> = dirs::home_dir()
+            .map(|home| runtime_sockets(&home))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, path)| path.exists())
+            .collect();
+
+        DockerEnvironment::detect(
+            Self::local_docker_host().as_deref(),
+            Self::docker_context().as_deref(),
+            &existing,
+        )
+    }
+
+    /// Context selected by `DOCKER_CONTEXT` or `docker context use`
+    fn docker_context() -> Option<String> {
+        if let Ok(context) = std::env::var("DOCKER_CONTEXT") {
+            return Some(context);
+        }
+
+        let config = dirs::home_dir()?.join(".docker").join("config.json");
+        let config: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(config).ok()?).ok()?;
+        config
+            .get("currentContext")
+            .and_then(|context| context.as_str())
+            .map(str::to_string)
+    }
+
+    /// Point local docker calls at the detected socket when it is not the default one
+    /// Colima, OrbStack and Rancher Desktop without a context would otherwise look unreachable
+    pub fn configure_docker_host() {
+        if let Some(host) = Self::docker_environment().docker_host_override() {
+            *DETECTED_DOCKER_HOST.write().unwrap() = Some(host);
+            Self::invalidate_cache();
+        }
+    }
+
+    /// Launch the detected runtime and wait until its daemon answers
+    /// Progress is reported through `docker-start-progress`
+    pub async fn start_daemon(&self, app: &AppHandle) -> Result<serde_json::Value, AppError> {
+        let status = self.check_docker_status(app).await?;
+        if status["status"] == "running" {
+            return Ok(status);
+        }
+
+        let os = std::env::consts::OS;
+        let environment = Self::docker_environment();
+        let runtime = environment
+            .runtime
+            .or_else(|| environment.installed.first().copied())
+            .unwrap_or_else(|| DockerRuntime::platform_default(os));
+        let events = EventService::new();
+        let progress = |stage: &str, attempt: u32, message: Option<String>| {
+            events.docker_start_progress(
+                app,
+                &DockerStartProgressPayload {
+                    stage: stage.to_string(),
+                    runtime,
+                    attempt,
+                    message,
+                },
+            );
+        };
+
+        progress("launching", 0, None);
+        let enriched_path = self.get_enriched_path(app).await;
+        let commands = runtime.launch_commands(os);
+        let mut launch_error = commands
+            .is_empty()
+            .then(|| format!("{} cannot be started automatically", runtime.display_name()));
+        for command in commands {
+            let output = app
+                .shell()
+                .command(&command[0])
+                .args(&command[1..])
+                .env("PATH", &enriched_path)
+                .envs(self.host_env())
+                .output()
+                .await;
+
+            match output {
+                Ok(output) if output.status.success() => {
+                    launch_error = None;
+                    break;
+                }
+                Ok(output) => {
+                    launch_error = Some(String::from_utf8_lossy(&output.stderr).trim().to_string())
+                }
+                Err(e) => launch_error = Some(e.to_string()),
+            }
+        }
+
+        if let Some(error) = launch_error {
+            progress("failed", 0, Some(error.clone()));
+            return Err(AppError::Docker {
+                message: format!(
+                    "Failed to start {}. {}",
+                    runtime.display_name(),
+                    runtime.start_hint()
+                ),
+                details: Some(error),
+            });
+        }
+
+        let started = Instant::now();
+        let mut attempt = 0;
+        while started.elapsed() < DAEMON_START_TIMEOUT {
+            attempt += 1;
+            tokio::time::sleep(DAEMON_POLL_INTERVAL).await;
+
+            // The socket may only appear once the runtime is up
+            if Self::local_docker_host().is_none() {
+                Self::configure_docker_host();
+            }
+            Self::invalidate_cache();
+
+            let status = self.check_docker_status(app).await?;
+            if status["status"] == "running" {
+                progress("ready", attempt, None);
+                EventService::new().docker_status_checked(app, &status);
+                return Ok(status);
+            }
+            progress("waiting", attempt, None);
+        }
+
+        let message = format!(
+            "{} did not become ready within {} seconds",
+            runtime.display_name(),
+            DAEMON_START_TIMEOUT.as_secs()
+        );
+        progress("failed", attempt, Some(message.clone()));
+        Err(AppError::docker(message))
+    }
+
+    /// Forget cached query results after a call that changes containers
+    pub fn invalidate_cache() {
+        PS_CACHE.lock().unwrap().clear();
+        STATUS_CACHE.lock().unwrap().clear();
+    }
+
+    async fn query_container_states(
+        &self,
+        app: &AppHandle,
+    ) -> Result<std::collections::HashMap<String, DockerContainerState>, AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
 
         // Get all containers from Docker
         let output = shell
             .command("docker")
-            .args(&["ps", "-a", "--format", "{{.ID}},{{.Names}},{{.Status}}"])
+            .args(&["ps", "-a", "--format", PS_FORMAT])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await
-            .map_err(|e| format!("Failed to get Docker containers: {}", e))?;
+            .map_err(|e| {
+                AppError::from_docker_output(
+                    "Failed to get Docker containers",
+                    &e.to_string(),
+                    None,
+                )
+            })?;
 
         if !output.status.success() {
-            return Err("Failed to get Docker containers".to_string());
+            return Err(AppError::from_docker_output(
+                "Failed to get Docker containers",
+                &String::from_utf8_lossy(&output.stderr),
+                output.status.code(),
+            ));
         }
 
-        let docker_containers_str = String::from_utf8_lossy(&output.stdout);
+        Ok(Self::parse_ps_output(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Parse `docker ps` output in `PS_FORMAT` into container states keyed by name
+    pub fn parse_ps_output(
+        output: &str,
+    ) -> std::collections::HashMap<String, DockerContainerState> {
         let mut docker_containers = std::collections::HashMap::new();
 
         // Parse Docker containers output
-        for line in docker_containers_str.lines() {
+        for line in output.lines() {
             if line.trim().is_empty() {
                 continue;
             }
@@ -209,68 +751,170 @@ impl DockerService {
                 let name = parts[1].trim();
                 let status = parts[2].trim();
 
-                // Determine if container is running
-                let is_running = status.starts_with("Up");
-                docker_containers.insert(name.to_string(), (container_id.to_string(), is_running));
+                docker_containers.insert(
+                    name.to_string(),
+                    DockerContainerState::from_ps_status(container_id, status),
+                );
             }
         }
 
+        docker_containers
+    }
+
+    /// Update status and container ID of every database from the Docker host it runs on
+    /// Returns the Docker state of each container, keyed by name
+    /// Databases of a remote host that cannot be reached keep their last known status
+    pub async fn sync_containers_with_docker(
+        &self,
+        app: &AppHandle,
+        container_map: &mut std::collections::HashMap<String, DatabaseContainer>,
+    ) -> Result<std::collections::HashMap<String, DockerContainerState>, AppError> {
+        let mut host_ids: Vec<Option<String>> =
+            container_map.values().map(|db| db.host.clone()).collect();
+        host_ids.sort();
+        host_ids.dedup();
+
+        let mut docker_containers = HashMap::new();
+        let mut unreachable = HashSet::new();
+        for host_id in host_ids {
+            let names: Vec<String> = container_map
+                .values()
+                .filter(|db| db.host == host_id)
+                .map(|db| db.name.clone())
+                .collect();
+            let states = match Self::for_host_id(app, host_id.as_deref()) {
+                Ok(docker) => docker
+                    .inspect_containers(app, &names)
+                    .await
+                    .map_err(|error| docker.host_error(error)),
+                Err(error) => Err(error),
+            };
+            let states = match (states, &host_id) {
+                (Ok(states), _) => states,
+                // One host being down must not hide the databases of the others
+                (Err(error), Some(host_id)) => {
+                    // Reported when the host goes down, not again on every sync after
+                    if !UNREACHABLE_HOSTS.lock().unwrap().contains(host_id) {
+                        EventService::new().background_error(
+                            app,
+                            "docker-host",
+                            format!("Failed to sync Docker host {}: {}", host_id, error),
+                        );
+                    }
+                    unreachable.insert(host_id.clone());
+                    continue;
+                }
+                (Err(error), None) => return Err(error),
+            };
+
+            Self::apply_container_states(
+                container_map.values_mut().filter(|db| db.host == host_id),
+                &states,
+            );
+            docker_containers.extend(states);
+        }
+        *UNREACHABLE_HOSTS.lock().unwrap() = unreachable;
+
+        Ok(docker_containers)
+    }
+
+    /// Ids of the remote hosts the last sync could not reach
+    pub fn unreachable_hosts() -> HashSet<String> {
+        UNREACHABLE_HOSTS.lock().unwrap().clone()
+    }
+
+    fn apply_container_states<'a>(
+        databases: impl Iterator<Item = &'a mut DatabaseContainer>,
+        docker_containers: &HashMap<String, DockerContainerState>,
+    ) {
         // Update our database records
-        for (_, database) in container_map.iter_mut() {
-            if let Some((docker_id, is_running)) = docker_containers.get(&database.name) {
+        for database in databases {
+            if let Some(state) = docker_containers.get(&database.name) {
                 // Update container ID if it changed
-                database.container_id = Some(docker_id.clone());
+                database.container_id = Some(state.id.clone());
                 // Update status based on Docker reality
-                database.status = if *is_running {
-                    "running".to_string()
-                } else {
-                    "stopped".to_string()
-                };
+                database.apply_docker_state(state);
             } else {
                 // Container doesn't exist in Docker anymore
                 database.status = "stopped".to_string();
+                database.exit_code = None;
                 database.container_id = None;
             }
         }
-
-        Ok(())
     }
 
-    pub async fn start_container(&self, app: &AppHandle, container_id: &str) -> Result<(), String> {
+    pub async fn start_container(
+        &self,
+        app: &AppHandle,
+        container_id: &str,
+    ) -> Result<(), AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
+        // Cleared on every return path, once Docker has applied the change
+        let _invalidate = InvalidateCache;
 
         let output = shell
             .command("docker")
             .args(&["start", container_id])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await
-            .map_err(|e| format!("Failed to start container: {}", e))?;
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to start container", &e.to_string(), None)
+            })?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to start container: {}", error));
+            return Err(AppError::from_docker_output(
+                "Failed to start container",
+                &error,
+                output.status.code(),
+            ));
         }
 
         Ok(())
     }
 
-    pub async fn stop_container(&self, app: &AppHandle, container_id: &str) -> Result<(), String> {
+    pub async fn stop_container(
+        &self,
+        app: &AppHandle,
+        container_id: &str,
+    ) -> Result<(), AppError> {
+        self.stop_container_with_timeout(app, container_id, None)
+            .await
+    }
+
+    /// Stop a container, waiting `timeout` seconds instead of Docker's default before killing it
+    pub async fn stop_container_with_timeout(
+        &self,
+        app: &AppHandle,
+        container_id: &str,
+        timeout: Option<u32>,
+    ) -> Result<(), AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
+        // Cleared on every return path, once Docker has applied the change
+        let _invalidate = InvalidateCache;
 
         let output = shell
             .command("docker")
-            .args(&["stop", container_id])
+            .args(docker_stop_args(container_id, timeout))
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await
-            .map_err(|e| format!("Failed to stop container: {}", e))?;
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to stop container", &e.to_string(), None)
+            })?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to stop container: {}", error));
+            return Err(AppError::from_docker_output(
+                "Failed to stop container",
+                &error,
+                output.status.code(),
+            ));
         }
 
         Ok(())
@@ -280,15 +924,29 @@ impl DockerService {
         &self,
         app: &AppHandle,
         container_id: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), AppError> {
+        self.remove_container_with_timeout(app, container_id, None)
+            .await
+    }
+
+    /// Remove a container, stopping it with the database's stop timeout first
+    pub async fn remove_container_with_timeout(
+        &self,
+        app: &AppHandle,
+        container_id: &str,
+        timeout: Option<u32>,
+    ) -> Result<(), AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
+        // Cleared on every return path, once Docker has applied the change
+        let _invalidate = InvalidateCache;
 
         // Try to stop container (ignore errors)
         let _ = shell
             .command("docker")
-            .args(&["stop", container_id])
+            .args(docker_stop_args(container_id, timeout))
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -297,6 +955,7 @@ impl DockerService {
             .command("docker")
             .args(&["rm", container_id])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -306,7 +965,11 @@ impl DockerService {
                 let error = String::from_utf8_lossy(&output.stderr);
                 // Only return error if it's not "No such container"
                 if !error.contains("No such container") {
-                    return Err(format!("Failed to remove container: {}", error));
+                    return Err(AppError::from_docker_output(
+                        "Failed to remove container",
+                        &error,
+                        output.status.code(),
+                    ));
                 }
             }
         }
@@ -314,11 +977,44 @@ impl DockerService {
         Ok(())
     }
 
+    /// Give a container another name, keeping its id, volumes and state
+    /// Returns false when the container no longer exists
+    pub async fn rename_container(
+        &self,
+        app: &AppHandle,
+        container_id: &str,
+        new_name: &str,
+    ) -> Result<bool, AppError> {
+        // Cleared on every return path, once Docker has applied the change
+        let _invalidate = InvalidateCache;
+
+        let output = self
+            .output(
+                app,
+                "Failed to rename container",
+                ["rename", container_id, new_name],
+            )
+            .await?;
+        if output.status.success() {
+            return Ok(true);
+        }
+
+        let error = String::from_utf8_lossy(&output.stderr);
+        if error.contains("No such container") {
+            return Ok(false);
+        }
+        Err(self.host_error(AppError::from_docker_output(
+            "Failed to rename container",
+            &error,
+            output.status.code(),
+        )))
+    }
+
     pub async fn create_volume_if_needed(
         &self,
         app: &AppHandle,
         volume_name: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
 
@@ -327,6 +1023,7 @@ impl DockerService {
             .command("docker")
             .args(&["volume", "inspect", volume_name])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -336,13 +1033,233 @@ impl DockerService {
                 .command("docker")
                 .args(&["volume", "create", volume_name])
                 .env("PATH", &enriched_path)
+                .envs(self.host_env())
                 .output()
                 .await
-                .map_err(|e| format!("Failed to create volume: {}", e))?;
+                .map_err(|e| {
+                    AppError::from_docker_output("Failed to create volume", &e.to_string(), None)
+                })?;
 
             if !output.status.success() {
                 let error = String::from_utf8_lossy(&output.stderr);
-                return Err(format!("Failed to create volume: {}", error));
+                return Err(AppError::from_docker_output(
+                    "Failed to create volume",
+                    &error,
+                    output.status.code(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Names of all volumes Docker has
+    pub async fn list_volumes(
+        &self,
+        app: &AppHandle,
+    ) -> Result<std::collections::HashSet<String>, AppError> {
+        let enriched_path = self.get_enriched_path(app).await;
+        let output = app
+            .shell()
+            .command("docker")
+            .args(["volume", "ls", "--format", "{{.Name}}"])
+            .env("PATH", &enriched_path)
+            .envs(self.host_env())
+            .output()
+            .await
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to list volumes", &e.to_string(), None)
+            })?;
+
+        if !output.status.success() {
+            return Err(AppError::from_docker_output(
+                "Failed to list volumes",
+                &String::from_utf8_lossy(&output.stderr),
+                output.status.code(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect())
+    }
+
+    /// Local platform of an image and the daemon's platform, nothing is pulled
+    /// Either is None when Docker cannot tell, e.g. the image is not pulled yet
+    pub async fn resolve_image(&self, app: &AppHandle, image: &str) -> ImageResolution {
+        let local_platform = self
+            .query_platform(
+                app,
+                &[
+                    "image",
+                    "inspect",
+                    "--format",
+                    "{{.Os}}/{{.Architecture}}",
+                    image,
+                ],
+            )
+            .await;
+        let host_platform = self
+            .query_platform(
+                app,
+                &["version", "--format", "{{.Server.Os}}/{{.Server.Arch}}"],
+            )
+            .await;
+
+        ImageResolution::new(image, local_platform, host_platform)
+    }
+
+    /// Memory and cores of the daemon's machine, which tuning presets are sized from
+    pub async fn host_resources(&self, app: &AppHandle) -> Result<HostResources, AppError> {
+        let context = "Failed to read the Docker host resources";
+        let enriched_path = self.get_enriched_path(app).await;
+        let output = app
+            .shell()
+            .command("docker")
+            .args(["info", "--format", "{{.MemTotal}} {{.NCPU}}"])
+            .env("PATH", &enriched_path)
+            .envs(self.host_env())
+            .output()
+            .await
+            .map_err(|e| AppError::from_docker_output(context, &e.to_string(), None))?;
+
+        if !output.status.success() {
+            return Err(AppError::from_docker_output(
+                context,
+                &String::from_utf8_lossy(&output.stderr),
+                output.status.code(),
+            ));
+        }
+        HostResources::parse(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    async fn query_platform(&self, app: &AppHandle, args: &[&str]) -> Option<String> {
+        let enriched_path = self.get_enriched_path(app).await;
+        let output = app
+            .shell()
+            .command("docker")
+            .args(args)
+            .env("PATH", &enriched_path)
+            .envs(self.host_env())
+            .output()
+            .await
+            .ok()?;
+
+        let platform = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !platform.is_empty()).then_some(platform)
+    }
+
+    /// Create a user-defined bridge network if it doesn't exist yet
+    pub async fn create_network_if_needed(
+        &self,
+        app: &AppHandle,
+        network_name: &str,
+    ) -> Result<(), AppError> {
+        let shell = app.shell();
+        let enriched_path = self.get_enriched_path(app).await;
+
+        // Check if network exists
+        let network_check = shell
+            .command("docker")
+            .args(&["network", "inspect", network_name])
+            .env("PATH", &enriched_path)
+            .envs(self.host_env())
+            .output()
+            .await;
+
+        if network_check.is_err() || !network_check.unwrap().status.success() {
+            let output = shell
+                .command("docker")
+                .args(&["network", "create", network_name])
+                .env("PATH", &enriched_path)
+                .envs(self.host_env())
+                .output()
+                .await
+                .map_err(|e| {
+                    AppError::from_docker_output("Failed to create network", &e.to_string(), None)
+                })?;
+
+            if !output.status.success() {
+                let error = String::from_utf8_lossy(&output.stderr);
+                return Err(AppError::from_docker_output(
+                    "Failed to create network",
+                    &error,
+                    output.status.code(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Attach a container to a network, ignoring it if already attached
+    pub async fn connect_container_to_network(
+        &self,
+        app: &AppHandle,
+        network_name: &str,
+        container: &str,
+    ) -> Result<(), AppError> {
+        let shell = app.shell();
+        let enriched_path = self.get_enriched_path(app).await;
+
+        let output = shell
+            .command("docker")
+            .args(&["network", "connect", network_name, container])
+            .env("PATH", &enriched_path)
+            .envs(self.host_env())
+            .output()
+            .await
+            .map_err(|e| {
+                AppError::from_docker_output(
+                    "Failed to connect container to network",
+                    &e.to_string(),
+                    None,
+                )
+            })?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            if !error.contains("already exists") {
+                return Err(AppError::from_docker_output(
+                    "Failed to connect container to network",
+                    &error,
+                    output.status.code(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove_network_if_exists(
+        &self,
+        app: &AppHandle,
+        network_name: &str,
+    ) -> Result<(), AppError> {
+        let shell = app.shell();
+        let enriched_path = self.get_enriched_path(app).await;
+
+        let output = shell
+            .command("docker")
+            .args(&["network", "rm", network_name])
+            .env("PATH", &enriched_path)
+            .envs(self.host_env())
+            .output()
+            .await;
+
+        if let Ok(output) = output {
+            if !output.status.success() {
+                let error = String::from_utf8_lossy(&output.stderr);
+                // Only return error if it's not a missing network
+                if !error.contains("not found") && !error.contains("No such network") {
+                    return Err(AppError::from_docker_output(
+                        "Failed to remove network",
+                        &error,
+                        output.status.code(),
+                    ));
+                }
             }
         }
 
@@ -353,21 +1270,30 @@ impl DockerService {
         &self,
         app: &AppHandle,
         docker_args: &[String],
-    ) -> Result<String, String> {
+    ) -> Result<String, AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
+        // Cleared on every return path, once Docker has applied the change
+        let _invalidate = InvalidateCache;
 
         let output = shell
             .command("docker")
             .args(docker_args)
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await
-            .map_err(|e| format!("Failed to execute docker command: {}", e))?;
+            .map_err(|e| {
+                AppError::from_docker_output(
+                    "Failed to execute docker command",
+                    &e.to_string(),
+                    None,
+                )
+            })?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
-            return Err(error.to_string());
+            return Err(AppError::from_docker_stderr(&error, output.status.code()));
         }
 
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -377,7 +1303,7 @@ impl DockerService {
         &self,
         app: &AppHandle,
         volume_name: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
 
@@ -386,6 +1312,7 @@ impl DockerService {
             .command("docker")
             .args(&["volume", "inspect", volume_name])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -395,6 +1322,7 @@ impl DockerService {
                 .command("docker")
                 .args(&["volume", "rm", volume_name])
                 .env("PATH", &enriched_path)
+                .envs(self.host_env())
                 .output()
                 .await;
 
@@ -403,7 +1331,11 @@ impl DockerService {
                     let error = String::from_utf8_lossy(&output.stderr);
                     // Only return error if it's not "No such volume"
                     if !error.contains("No such volume") {
-                        return Err(format!("Failed to remove volume: {}", error));
+                        return Err(AppError::from_docker_output(
+                            "Failed to remove volume",
+                            &error,
+                            output.status.code(),
+                        ));
                     }
                 }
             }
@@ -412,13 +1344,49 @@ impl DockerService {
         Ok(())
     }
 
+    /// Copy the contents of a volume into another one
+    /// With `replace` the target is emptied first so it ends up identical to the source
+    pub async fn copy_volume(
+        &self,
+        app: &AppHandle,
+        source_volume: &str,
+        target_volume: &str,
+        replace: bool,
+    ) -> Result<(), AppError> {
+        self.create_volume_if_needed(app, target_volume).await?;
+
+        let clear = if replace {
+            "find /target -mindepth 1 -delete; "
+        } else {
+            ""
+        };
+        let args: Vec<String> = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "-v".to_string(),
+            format!("{}:/source:ro", source_volume),
+            "-v".to_string(),
+            format!("{}:/target", target_volume),
+            "alpine:latest".to_string(),
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("set -e; {}cp -a /source/. /target/", clear),
+        ];
+
+        self.run_container(app, &args)
+            .await
+            .map_err(|error| error.with_context("Failed to copy volume data"))?;
+
+        Ok(())
+    }
+
     pub async fn migrate_volume_data(
         &self,
         app: &AppHandle,
         old_volume: &str,
         new_volume: &str,
         _data_path: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
 
@@ -427,6 +1395,7 @@ impl DockerService {
             .command("docker")
             .args(&["volume", "inspect", old_volume])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -458,13 +1427,24 @@ impl DockerService {
                 "cp -a /old_data/. /new_data/ 2>/dev/null || true",
             ])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await
-            .map_err(|e| format!("Failed to create migration container: {}", e))?;
+            .map_err(|e| {
+                AppError::from_docker_output(
+                    "Failed to create migration container",
+                    &e.to_string(),
+                    None,
+                )
+            })?;
 
         if !create_output.status.success() {
             let error = String::from_utf8_lossy(&create_output.stderr);
-            return Err(format!("Failed to create migration container: {}", error));
+            return Err(AppError::from_docker_output(
+                "Failed to create migration container",
+                &error,
+                create_output.status.code(),
+            ));
         }
 
         // Start the container to perform the copy
@@ -472,6 +1452,7 @@ impl DockerService {
             .command("docker")
             .args(&["start", "-a", &temp_container_name])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -480,6 +1461,7 @@ impl DockerService {
             .command("docker")
             .args(&["rm", &temp_container_name])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -487,10 +1469,16 @@ impl DockerService {
         if let Ok(output) = start_output {
             if !output.status.success() {
                 let error = String::from_utf8_lossy(&output.stderr);
-                return Err(format!("Failed to migrate volume data: {}", error));
+                return Err(AppError::from_docker_output(
+                    "Failed to migrate volume data",
+                    &error,
+                    output.status.code(),
+                ));
             }
         } else {
-            return Err("Failed to execute data migration".to_string());
+            return Err(AppError::docker(
+                "Failed to execute data migration".to_string(),
+            ));
         }
 
         Ok(())
@@ -500,15 +1488,18 @@ impl DockerService {
         &self,
         app: &AppHandle,
         container_name: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
+        // Cleared on every return path, once Docker has applied the change
+        let _invalidate = InvalidateCache;
 
         // Try to stop container (ignore errors)
         let _ = shell
             .command("docker")
             .args(&["stop", container_name])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -517,6 +1508,7 @@ impl DockerService {
             .command("docker")
             .args(&["rm", container_name])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await;
 
@@ -526,7 +1518,11 @@ impl DockerService {
                 let error = String::from_utf8_lossy(&output.stderr);
                 // Only return error if it's not "No such container"
                 if !error.contains("No such container") {
-                    return Err(format!("Failed to remove container: {}", error));
+                    return Err(AppError::from_docker_output(
+                        "Failed to remove container",
+                        &error,
+                        output.status.code(),
+                    ));
                 }
             }
         }
@@ -539,25 +1535,43 @@ impl DockerService {
         app: &AppHandle,
         container_id: &str,
         tail_lines: Option<i32>,
-    ) -> Result<String, String> {
+        since: Option<&str>,
+        until: Option<&str>,
+    ) -> Result<String, AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
 
         // Default to 500 lines if not specified
         let tail = tail_lines.unwrap_or(500).to_string();
 
-        // Execute: docker logs --tail N --timestamps CONTAINER_ID
+        // Execute: docker logs --tail N --timestamps [--since T] [--until T] CONTAINER_ID
+        let mut args = vec!["logs", "--tail", &tail, "--timestamps"];
+        if let Some(since) = since {
+            args.extend(["--since", since]);
+        }
+        if let Some(until) = until {
+            args.extend(["--until", until]);
+        }
+        args.push(container_id);
+
         let output = shell
             .command("docker")
-            .args(&["logs", "--tail", &tail, "--timestamps", container_id])
+            .args(&args)
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await
-            .map_err(|e| format!("Failed to get container logs: {}", e))?;
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to get container logs", &e.to_string(), None)
+            })?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to get container logs: {}", error));
+            return Err(AppError::from_docker_output(
+                "Failed to get container logs",
+                &error,
+                output.status.code(),
+            ));
         }
 
         // Return logs as UTF-8 string
@@ -565,13 +1579,68 @@ impl DockerService {
         Ok(logs)
     }
 
+    /// One page of logs, streamed and cut off once the page is full
+    /// Unlike `--tail` this never buffers more than a page of a huge log
+    pub async fn get_container_logs_page(
+        &self,
+        app: &AppHandle,
+        container_id: &str,
+        query: &LogQuery,
+    ) -> Result<LogPage, AppError> {
+        let enriched_path = self.get_enriched_path(app).await;
+        let mut pager = LogPager::new(query)?;
+
+        let mut args = vec!["logs".to_string(), "--timestamps".to_string()];
+        if let Some(since) = pager.since(query) {
+            args.extend(["--since".to_string(), since.to_string()]);
+        }
+        if let Some(until) = &query.until {
+            args.extend(["--until".to_string(), until.clone()]);
+        }
+        args.push(container_id.to_string());
+
+        let (mut events, _child) = app
+            .shell()
+            .command("docker")
+            .args(&args)
+            .env("PATH", &enriched_path)
+            .envs(self.host_env())
+            .spawn()
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to get container logs", &e.to_string(), None)
+            })?;
+
+        // Read to the end, an early stderr line may still arrive after stdout filled the page
+        let mut exit_code = None;
+        while let Some(event) = events.recv().await {
+            match event {
+                // The container's stderr arrives on stderr, both are part of its log
+                CommandEvent::Stdout(line) | CommandEvent::Stderr(line) => {
+                    pager.push(&String::from_utf8_lossy(&line));
+                }
+                CommandEvent::Terminated(payload) => exit_code = payload.code,
+                _ => {}
+            }
+        }
+
+        if exit_code != Some(0) {
+            return Err(AppError::from_docker_output(
+                "Failed to get container logs",
+                &pager.errors(),
+                exit_code,
+            ));
+        }
+
+        Ok(pager.finish(query.cursor.as_deref()))
+    }
+
     pub async fn execute_container_command(
         &self,
         app: &AppHandle,
         container_id: &str,
         command: &str,
         columns: u16,
-    ) -> Result<serde_json::Value, String> {
+    ) -> Result<serde_json::Value, AppError> {
         let shell = app.shell();
         let enriched_path = self.get_enriched_path(app).await;
 
@@ -596,9 +1665,16 @@ impl DockerService {
                 command,
             ])
             .env("PATH", &enriched_path)
+            .envs(self.host_env())
             .output()
             .await
-            .map_err(|e| format!("Failed to execute command in container: {}", e))?;
+            .map_err(|e| {
+                AppError::from_docker_output(
+                    "Failed to execute command in container",
+                    &e.to_string(),
+                    None,
+                )
+            })?;
 
         // Get exit code (0 = success, non-zero = error)
         let exit_code = output.status.code().unwrap_or(-1);
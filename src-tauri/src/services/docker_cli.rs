@@ -0,0 +1,76 @@
+use crate::services::{DockerService, PS_FORMAT};
+use crate::types::*;
+use std::collections::HashMap;
+use std::process::Command;
+
+/// Blocking Docker client for the `ddm` CLI, which runs without the Tauri shell plugin
+pub struct DockerCli;
+
+impl DockerCli {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn run(&self, args: &[&str]) -> Result<String, AppError> {
+        let output = Command::new("docker")
+            .args(args)
+            .output()
+            .map_err(|_| AppError::DockerUnavailable)?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(AppError::from_docker_stderr(&error, output.status.code()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub fn list_container_states(&self) -> Result<HashMap<String, DockerContainerState>, AppError> {
+        let output = self.run(&["ps", "-a", "--format", PS_FORMAT])?;
+        Ok(DockerService::parse_ps_output(&output))
+    }
+
+    pub fn start_container(&self, container_id: &str) -> Result<(), AppError> {
+        self.run(&["start", container_id]).map(|_| ())
+    }
+
+    pub fn stop_container(&self, container_id: &str, timeout: Option<u32>) -> Result<(), AppError> {
+        let args = docker_stop_args(container_id, timeout);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.run(&args).map(|_| ())
+    }
+
+    pub fn create_volume_if_needed(&self, volume_name: &str) -> Result<(), AppError> {
+        if self.run(&["volume", "inspect", volume_name]).is_err() {
+            self.run(&["volume", "create", volume_name])?;
+        }
+        Ok(())
+    }
+
+    /// Run a container from a request built by a provider, returns the container ID
+    /// `workspace` is recorded in the container's labels
+    pub fn run_container(
+        &self,
+        request: &DockerRunRequest,
+        workspace: &str,
+    ) -> Result<String, AppError> {
+        for volume in &request.docker_args.volumes {
+            self.create_volume_if_needed(&volume.name)?;
+        }
+
+        let labels = ManagementLabels {
+            database_id: request.metadata.id.clone(),
+            db_type: request.metadata.db_type.clone(),
+            workspace: workspace.to_string(),
+        };
+        let args = DockerService::new().build_database_command(
+            &request.name,
+            &request.docker_args,
+            &labels,
+        );
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        self.run(&args)
+            .map_err(|error| error.for_run_request(&request.name, request.metadata.port))
+    }
+}
@@ -0,0 +1,78 @@
+use crate::services::{ConfigService, DockerService};
+use crate::types::*;
+use tauri::AppHandle;
+
+// Describes a failed docker call of this service
+const CONTEXT: &str = "Failed to read the container environment";
+
+/// Compares the environment of live containers with the configuration stored in the app
+pub struct EnvDriftService;
+
+impl EnvDriftService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Environment of the container and how it differs from the stored run arguments
+    /// Variables the image defines are only drift when the container overrides them
+    pub async fn inspect(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<EnvDriftReport, AppError> {
+        let docker_args = database.stored_docker_args.as_ref().ok_or_else(|| {
+            AppError::Validation(format!(
+                "'{}' was created before its Docker arguments were stored, edit and save it first",
+                database.name
+            ))
+        })?;
+
+        let docker_service = DockerService::for_database(app, database)?;
+        // `<image id> <env as JSON>` in one call
+        let output = docker_service
+            .stdout(
+                app,
+                CONTEXT,
+                &[
+                    "inspect",
+                    "--type",
+                    "container",
+                    "--format",
+                    "{{.Image}} {{json .Config.Env}}",
+                    &database.name,
+                ],
+            )
+            .await?;
+        let (image_id, live) = output.trim().split_once(' ').unwrap_or_default();
+        let live = Self::parse_env_list(live)?;
+
+        let image = docker_service
+            .stdout(
+                app,
+                CONTEXT,
+                &[
+                    "image",
+                    "inspect",
+                    "--format",
+                    "{{json .Config.Env}}",
+                    image_id,
+                ],
+            )
+            .await?;
+        let image = Self::parse_env_list(&image)?;
+
+        Ok(EnvDriftReport::compare(
+            &docker_args.env_vars,
+            &live,
+            &image,
+            ConfigService::is_secret_env_var,
+        ))
+    }
+
+    // `null` when the container or image has no environment
+    fn parse_env_list(json: &str) -> Result<Vec<String>, AppError> {
+        serde_json::from_str::<Option<Vec<String>>>(json.trim())
+            .map(Option::unwrap_or_default)
+            .map_err(|e| AppError::docker(format!("Unexpected docker inspect output: {}", e)))
+    }
+}
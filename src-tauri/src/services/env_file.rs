@@ -0,0 +1,121 @@
+use crate::types::AppError;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Variable holding the password read by `mongo_login`
+pub const MONGO_PASSWORD_VAR: &str = "DDM_MONGO_PASSWORD";
+/// Variable holding the `--config` file read by `mongo_tool`
+pub const MONGO_TOOLS_CONFIG_VAR: &str = "DDM_MONGO_TOOLS_CONFIG";
+
+/// Variables handed to `docker exec --env-file`, the file is removed when dropped
+/// Keeps credentials off the command line, which other users of the machine can read
+pub struct EnvFile {
+    path: PathBuf,
+}
+
+impl EnvFile {
+    /// Write `vars` to a file only the current user can read
+    pub fn create(vars: &[(&str, &str)]) -> Result<Self, AppError> {
+        // Docker reads a value up to the end of its line, there is no quoting
+        if let Some((key, _)) = vars.iter().find(|(_, value)| value.contains(['\n', '\r'])) {
+            return Err(AppError::Validation(format!(
+                "{} cannot be passed to the container, it spans several lines",
+                key
+            )));
+        }
+
+        let path =
+            std::env::temp_dir().join(format!("docker-db-manager-{}.env", uuid::Uuid::new_v4()));
+        let contents: String = vars
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect();
+        write_private(&path, contents.as_bytes())
+            .map_err(|e| AppError::Io(format!("Failed to write environment file: {}", e)))?;
+
+        Ok(Self { path })
+    }
+
+    /// File of `credentials`, None when there is nothing to pass
+    pub fn for_credentials(
+        credentials: &[(&'static str, String)],
+    ) -> Result<Option<Self>, AppError> {
+        if credentials.is_empty() {
+            return Ok(None);
+        }
+        let vars: Vec<(&str, &str)> = credentials
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect();
+        Self::create(&vars).map(Some)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// `--env-file <path>` options of `docker exec` and `docker run`
+    pub fn docker_args(&self) -> [String; 2] {
+        [
+            "--env-file".to_string(),
+            self.path.to_string_lossy().to_string(),
+        ]
+    }
+}
+
+impl Drop for EnvFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// mongosh statement logging `username` in with the password of `MONGO_PASSWORD_VAR`
+/// Unlike `-p`, the password never shows in the process list of the host
+pub fn mongo_login(username: &str) -> String {
+    format!(
+        "db.getSiblingDB('admin').auth({}, process.env.{});",
+        serde_json::Value::from(username),
+        MONGO_PASSWORD_VAR
+    )
+}
+
+/// Value of `MONGO_TOOLS_CONFIG_VAR`, a JSON string is a YAML scalar that fits on one line
+pub fn mongo_tools_config(password: &str) -> String {
+    format!("password: {}", serde_json::Value::from(password))
+}
+
+/// Command running a MongoDB database tool such as mongoexport with `args`
+/// The tool reads its password from a `--config` file written from `MONGO_TOOLS_CONFIG_VAR`
+pub fn mongo_tool(args: &[&str]) -> Vec<String> {
+    config_file_command(MONGO_TOOLS_CONFIG_VAR, "--config=", args)
+}
+
+/// Shell command running `args` followed by `option` naming a file with the value of `var`
+/// The file is a here-document on descriptor 3, so stdin stays free for data and the value
+/// never becomes a process argument
+pub fn config_file_command(var: &str, option: &str, args: &[&str]) -> Vec<String> {
+    let mut command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        format!("exec \"$@\" {}/dev/fd/3 3<<EOF\n${}\nEOF", option, var),
+        "sh".to_string(),
+    ];
+    command.extend(args.iter().map(|arg| arg.to_string()));
+    command
+}
+
+/// Create or replace a file readable and writable by its owner only
+pub fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // An existing file keeps its mode on open
+        if path.exists() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(path)?.write_all(contents)
+}
@@ -0,0 +1,206 @@
+use crate::types::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+pub const CONTAINER_CREATED_EVENT: &str = "container-created";
+pub const CONTAINER_UPDATED_EVENT: &str = "container-updated";
+pub const CONTAINER_REMOVED_EVENT: &str = "container-removed";
+pub const CONTAINER_STATUS_CHANGED_EVENT: &str = "container-status-changed";
+pub const DOCKER_STATUS_CHANGED_EVENT: &str = "docker-status-changed";
+pub const WORKSPACE_CHANGED_EVENT: &str = "workspace-changed";
+pub const AUTOSTART_PROGRESS_EVENT: &str = "autostart-progress";
+pub const CONTAINER_METRICS_EVENT: &str = "container-metrics";
+pub const DOCKER_START_PROGRESS_EVENT: &str = "docker-start-progress";
+pub const ORPHANS_DETECTED_EVENT: &str = "orphans-detected";
+pub const TABLE_EXPORT_PROGRESS_EVENT: &str = "table-export-progress";
+pub const ALERT_FIRED_EVENT: &str = "alert-fired";
+pub const ALERT_RESOLVED_EVENT: &str = "alert-resolved";
+pub const TUNNEL_STATUS_CHANGED_EVENT: &str = "tunnel-status-changed";
+pub const RECOVERY_COMPLETED_EVENT: &str = "recovery-completed";
+pub const FILE_COPY_PROGRESS_EVENT: &str = "file-copy-progress";
+pub const INTEGRITY_CHECK_COMPLETED_EVENT: &str = "integrity-check-completed";
+pub const CONTAINER_WATCHDOG_EVENT: &str = "container-watchdog";
+pub const SHARE_STATUS_CHANGED_EVENT: &str = "share-status-changed";
+pub const QUERY_EXPORT_PROGRESS_EVENT: &str = "query-export-progress";
+pub const SQL_JOB_COMPLETED_EVENT: &str = "sql-job-completed";
+pub const BACKGROUND_ERROR_EVENT: &str = "background-error";
+
+// Last Docker daemon status seen, to only emit on transitions
+static LAST_DOCKER_STATUS: Mutex<Option<String>> = Mutex::new(None);
+
+/// Broadcasts state changes to every open window
+pub struct EventService;
+
+impl EventService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn container_created(&self, app: &AppHandle, container: &DatabaseContainer) {
+        let _ = app.emit(
+            CONTAINER_CREATED_EVENT,
+            ContainerEventPayload {
+                container: container.clone(),
+            },
+        );
+    }
+
+    pub fn container_updated(&self, app: &AppHandle, container: &DatabaseContainer) {
+        let _ = app.emit(
+            CONTAINER_UPDATED_EVENT,
+            ContainerEventPayload {
+                container: container.clone(),
+            },
+        );
+    }
+
+    pub fn container_removed(&self, app: &AppHandle, id: &str) {
+        let _ = app.emit(
+            CONTAINER_REMOVED_EVENT,
+            ContainerRemovedPayload { id: id.to_string() },
+        );
+    }
+
+    pub fn container_status_changed(&self, app: &AppHandle, container: &DatabaseContainer) {
+        let _ = app.emit(
+            CONTAINER_STATUS_CHANGED_EVENT,
+            ContainerStatusPayload {
+                id: container.id.clone(),
+                name: container.name.clone(),
+                status: container.status.clone(),
+            },
+        );
+    }
+
+    pub fn workspace_changed(&self, app: &AppHandle, workspace: &Workspace) {
+        let _ = app.emit(WORKSPACE_CHANGED_EVENT, workspace.clone());
+    }
+
+    pub fn autostart_progress(&self, app: &AppHandle, progress: &AutostartProgressPayload) {
+        let _ = app.emit(AUTOSTART_PROGRESS_EVENT, progress.clone());
+    }
+
+    pub fn docker_start_progress(&self, app: &AppHandle, progress: &DockerStartProgressPayload) {
+        let _ = app.emit(DOCKER_START_PROGRESS_EVENT, progress.clone());
+    }
+
+    pub fn orphans_detected(&self, app: &AppHandle, orphans: Vec<OrphanContainer>) {
+        let _ = app.emit(ORPHANS_DETECTED_EVENT, OrphansDetectedPayload { orphans });
+    }
+
+    pub fn table_export_progress(&self, app: &AppHandle, progress: &TableExportProgressPayload) {
+        let _ = app.emit(TABLE_EXPORT_PROGRESS_EVENT, progress.clone());
+    }
+
+    pub fn query_export_progress(&self, app: &AppHandle, progress: &QueryExportProgressPayload) {
+        let _ = app.emit(QUERY_EXPORT_PROGRESS_EVENT, progress.clone());
+    }
+
+    pub fn file_copy_progress(&self, app: &AppHandle, progress: &FileCopyProgressPayload) {
+        let _ = app.emit(FILE_COPY_PROGRESS_EVENT, progress.clone());
+    }
+
+    pub fn alert_fired(&self, app: &AppHandle, alert: &AlertEvent) {
+        let _ = app.emit(ALERT_FIRED_EVENT, alert.clone());
+    }
+
+    pub fn alert_resolved(&self, app: &AppHandle, alert: &AlertEvent) {
+        let _ = app.emit(ALERT_RESOLVED_EVENT, alert.clone());
+    }
+
+    pub fn tunnel_status_changed(&self, app: &AppHandle, status: &TunnelStatus) {
+        let _ = app.emit(TUNNEL_STATUS_CHANGED_EVENT, status.clone());
+    }
+
+    pub fn integrity_check_completed(&self, app: &AppHandle, run: &IntegrityCheckRun) {
+        let _ = app.emit(INTEGRITY_CHECK_COMPLETED_EVENT, run.clone());
+    }
+
+    pub fn sql_job_completed(&self, app: &AppHandle, job: &SqlJob) {
+        let _ = app.emit(SQL_JOB_COMPLETED_EVENT, job.clone());
+    }
+
+    /// Report a failure of work nobody awaits, such as a timer or a shutdown step
+    pub fn background_error(&self, app: &AppHandle, source: &str, message: impl Into<String>) {
+        let _ = app.emit(
+            BACKGROUND_ERROR_EVENT,
+            BackgroundErrorPayload {
+                source: source.to_string(),
+                message: message.into(),
+            },
+        );
+    }
+
+    pub fn watchdog_step(&self, app: &AppHandle, payload: &WatchdogEventPayload) {
+        let _ = app.emit(CONTAINER_WATCHDOG_EVENT, payload.clone());
+    }
+
+    pub fn share_status_changed(&self, app: &AppHandle, status: &ShareStatus) {
+        let _ = app.emit(SHARE_STATUS_CHANGED_EVENT, status.clone());
+    }
+
+    pub fn recovery_completed(&self, app: &AppHandle, recovered: Vec<RecoveredCreate>) {
+        let _ = app.emit(
+            RECOVERY_COMPLETED_EVENT,
+            RecoveryCompletedPayload { recovered },
+        );
+    }
+
+    pub fn container_metrics(&self, app: &AppHandle, metrics: Vec<ContainerMetrics>) {
+        let _ = app.emit(
+            CONTAINER_METRICS_EVENT,
+            ContainerMetricsPayload {
+                metrics,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+    }
+
+    /// Emit a status event for every container whose status differs between snapshots
+    pub fn emit_status_changes(
+        &self,
+        app: &AppHandle,
+        before: &HashMap<String, DatabaseContainer>,
+        after: &HashMap<String, DatabaseContainer>,
+    ) {
+        for container in Self::status_changes(before, after) {
+            self.container_status_changed(app, container);
+        }
+    }
+
+    /// Containers of `after` that are new or whose status differs from `before`
+    pub fn status_changes<'a>(
+        before: &HashMap<String, DatabaseContainer>,
+        after: &'a HashMap<String, DatabaseContainer>,
+    ) -> Vec<&'a DatabaseContainer> {
+        after
+            .values()
+            .filter(|container| {
+                before
+                    .get(&container.id)
+                    .is_none_or(|previous| previous.status != container.status)
+            })
+            .collect()
+    }
+
+    /// Emit `docker-status-changed` only when the daemon status differs from the last one
+    pub fn docker_status_checked(&self, app: &AppHandle, status: &serde_json::Value) {
+        let current = status
+            .get("status")
+            .and_then(|s| s.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let changed = {
+            let mut last = LAST_DOCKER_STATUS.lock().unwrap();
+            let changed = last.as_deref() != Some(current.as_str());
+            *last = Some(current);
+            changed
+        };
+
+        if changed {
+            let _ = app.emit(DOCKER_STATUS_CHANGED_EVENT, status.clone());
+        }
+    }
+}
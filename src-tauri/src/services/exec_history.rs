@@ -0,0 +1,71 @@
+use crate::services::StorageService;
+use crate::types::*;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+// Commands finish concurrently, the history is read and written back as a whole
+static HISTORY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Keeps the commands run through the terminal of each container across sessions
+pub struct ExecHistoryService;
+
+impl ExecHistoryService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Record a command of `database`, its credentials are scrubbed before it is saved
+    pub async fn record(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        command: &str,
+        exit_code: i32,
+    ) -> Result<(), AppError> {
+        let credentials = DatabaseCredentials::of(database);
+        let _lock = HISTORY_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut history = storage_service.load_exec_history_from_store(app).await?;
+        history.record(
+            &database.id,
+            command,
+            &[credentials.password.as_str()],
+            exit_code,
+            chrono::Utc::now().to_rfc3339(),
+        );
+        storage_service
+            .save_exec_history_to_store(app, &history)
+            .await
+    }
+
+    /// Commands of a database, newest first, only the ones containing `query` when set
+    pub async fn search(
+        &self,
+        app: &AppHandle,
+        database_id: &str,
+        query: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ExecHistoryEntry>, AppError> {
+        let history = StorageService::new()
+            .load_exec_history_from_store(app)
+            .await?;
+        Ok(history.search(database_id, query, limit))
+    }
+
+    /// Forget the commands of a database, and of databases that no longer exist
+    pub async fn clear(
+        &self,
+        app: &AppHandle,
+        database_id: &str,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Result<(), AppError> {
+        let _lock = HISTORY_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut history = storage_service.load_exec_history_from_store(app).await?;
+        history.clear(database_id);
+        history.retain_databases(databases);
+        storage_service
+            .save_exec_history_to_store(app, &history)
+            .await
+    }
+}
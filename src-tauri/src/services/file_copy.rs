@@ -0,0 +1,265 @@
+use crate::services::{DockerService, EventService, MetricsHistoryService};
+use crate::types::*;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Where the size of a copy's destination is read from
+#[derive(Clone)]
+enum Destination {
+    Host(PathBuf),
+    Container { name: String, path: String },
+}
+
+/// One `docker cp` invocation and how to follow it
+struct CopyJob {
+    direction: CopyDirection,
+    // Shown in progress events, a host path or a path in the container
+    source: String,
+    // `docker cp` arguments
+    from: String,
+    to: String,
+    // None when its size cannot be read during the copy
+    destination: Option<Destination>,
+    total: Option<u64>,
+}
+
+/// Copies files between the host and database containers with `docker cp`
+/// `file-copy-progress` reports how much of the destination has been written
+pub struct FileCopyService;
+
+impl FileCopyService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Copy a host file or directory to `container_path`, stopped containers included
+    pub async fn copy_to_container(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        local_path: &Path,
+        container_path: &str,
+    ) -> Result<FileCopyResult, AppError> {
+        let container_path = container_copy_path(container_path)?;
+        if !local_path.exists() {
+            return Err(AppError::NotFound(local_path.display().to_string()));
+        }
+
+        let total = Self::local_size(local_path);
+        let source = local_path.display().to_string();
+        // Stopped containers cannot be measured while the copy runs
+        let destination = (database.is_running()).then(|| Destination::Container {
+            name: database.name.clone(),
+            path: container_path.clone(),
+        });
+        self.copy(
+            app,
+            database,
+            CopyJob {
+                direction: CopyDirection::ToContainer,
+                source: source.clone(),
+                from: source.clone(),
+                to: format!("{}:{}", database.name, container_path),
+                destination,
+                total: Some(total),
+            },
+        )
+        .await?;
+
+        Ok(FileCopyResult {
+            direction: CopyDirection::ToContainer,
+            source,
+            destination: container_path,
+            bytes_copied: total,
+        })
+    }
+
+    /// Copy a file or directory of the container to `local_path`
+    /// An existing directory at `local_path` receives the copy inside it, like `docker cp`
+    pub async fn copy_from_container(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        container_path: &str,
+        local_path: &Path,
+    ) -> Result<FileCopyResult, AppError> {
+        let container_path = container_copy_path(container_path)?;
+        if let Some(parent) = local_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            if !parent.is_dir() {
+                return Err(AppError::NotFound(parent.display().to_string()));
+            }
+        }
+
+        // The size of the source can only be read from a running container
+        let total = if database.is_running() {
+            let docker_service = DockerService::for_database(app, database)?;
+            self.container_size(app, &docker_service, &database.name, &container_path)
+                .await
+        } else {
+            None
+        };
+        let destination = local_path.display().to_string();
+        let bytes_copied = self
+            .copy(
+                app,
+                database,
+                CopyJob {
+                    direction: CopyDirection::FromContainer,
+                    source: container_path.clone(),
+                    from: format!("{}:{}", database.name, container_path),
+                    to: destination.clone(),
+                    destination: Some(Destination::Host(local_path.to_path_buf())),
+                    total,
+                },
+            )
+            .await?;
+
+        Ok(FileCopyResult {
+            direction: CopyDirection::FromContainer,
+            source: container_path,
+            destination,
+            bytes_copied,
+        })
+    }
+
+    /// Bytes of a file or of every file below a directory, symbolic links are not followed
+    pub fn local_size(path: &Path) -> u64 {
+        let Ok(metadata) = std::fs::symlink_metadata(path) else {
+            return 0;
+        };
+        if !metadata.is_dir() {
+            return metadata.len();
+        }
+
+        std::fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| Self::local_size(&entry.path()))
+                    .sum()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Run `docker cp` while the growth of `destination` is reported every
+    /// `FILE_COPY_PROGRESS_INTERVAL_MS`, returns the bytes copied
+    async fn copy(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        job: CopyJob,
+    ) -> Result<u64, AppError> {
+        let CopyJob {
+            direction,
+            source,
+            from,
+            to,
+            destination,
+            total,
+        } = job;
+        let payload = |bytes_copied: u64, done: bool| FileCopyProgressPayload {
+            container_id: database.id.clone(),
+            direction,
+            source: source.clone(),
+            bytes_copied,
+            total_bytes: total,
+            done,
+        };
+        let docker_service = DockerService::for_database(app, database)?;
+        EventService::new().file_copy_progress(app, &payload(0, false));
+
+        let baseline = match &destination {
+            Some(destination) => {
+                self.destination_size(app, &docker_service, destination)
+                    .await
+            }
+            None => 0,
+        };
+        let finished = Arc::new(AtomicBool::new(false));
+        if let Some(destination) = destination.clone() {
+            let app = app.clone();
+            let finished = finished.clone();
+            let docker_service = docker_service.clone();
+            let payload = payload(0, false);
+            tauri::async_runtime::spawn(async move {
+                let service = FileCopyService::new();
+                loop {
+                    tokio::time::sleep(Duration::from_millis(FILE_COPY_PROGRESS_INTERVAL_MS)).await;
+                    if finished.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let current = service
+                        .destination_size(&app, &docker_service, &destination)
+                        .await;
+                    // A late measurement must not follow the final event
+                    if finished.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    EventService::new().file_copy_progress(
+                        &app,
+                        &FileCopyProgressPayload {
+                            bytes_copied: copy_progress(baseline, current, total),
+                            ..payload.clone()
+                        },
+                    );
+                }
+            });
+        }
+
+        let result = docker_service
+            .stdout(app, "Failed to copy files", ["cp", &from, &to])
+            .await;
+        finished.store(true, Ordering::SeqCst);
+        result?;
+
+        let bytes_copied = match (total, &destination) {
+            (Some(total), _) => total,
+            (None, Some(destination)) => copy_progress(
+                baseline,
+                self.destination_size(app, &docker_service, destination)
+                    .await,
+                None,
+            ),
+            (None, None) => 0,
+        };
+        EventService::new().file_copy_progress(app, &payload(bytes_copied, true));
+        Ok(bytes_copied)
+    }
+
+    async fn destination_size(
+        &self,
+        app: &AppHandle,
+        docker_service: &DockerService,
+        destination: &Destination,
+    ) -> u64 {
+        match destination {
+            Destination::Host(path) => Self::local_size(path),
+            Destination::Container { name, path } => self
+                .container_size(app, docker_service, name, path)
+                .await
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Disk usage of a path in a running container, None when it cannot be read
+    async fn container_size(
+        &self,
+        app: &AppHandle,
+        docker_service: &DockerService,
+        name: &str,
+        path: &str,
+    ) -> Option<u64> {
+        let output = docker_service
+            .stdout(
+                app,
+                "Failed to measure files",
+                ["exec", name, "du", "-sk", path],
+            )
+            .await
+            .ok()?;
+        MetricsHistoryService::parse_du_output(&output)
+    }
+}
@@ -0,0 +1,188 @@
+use crate::services::{mongo_login, DockerService, EnvFile, EventService, MONGO_PASSWORD_VAR};
+use crate::types::*;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+
+// How long the engine gets to shut itself down when the database has no stop timeout
+const DEFAULT_SHUTDOWN_WAIT_SECS: u32 = 60;
+
+// Ids of the databases being stopped, a sync meanwhile sees an exit the app caused
+static STOPPING: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Marks a database as stopping until dropped
+struct StoppingGuard(String);
+
+impl StoppingGuard {
+    fn new(database_id: &str) -> Self {
+        STOPPING.lock().unwrap().insert(database_id.to_string());
+        Self(database_id.to_string())
+    }
+}
+
+impl Drop for StoppingGuard {
+    fn drop(&mut self) {
+        STOPPING.lock().unwrap().remove(&self.0);
+    }
+}
+
+/// Stops databases through the engine's own shutdown before `docker stop`
+/// A clean shutdown skips crash recovery at the next start, `docker stop` is the fallback
+pub struct GracefulStopService;
+
+impl GracefulStopService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether the app is stopping the database, its exit is not a crash
+    pub fn is_stopping(database_id: &str) -> bool {
+        STOPPING.lock().unwrap().contains(database_id)
+    }
+
+    /// Variables the shutdown reads its password from, passed with `--env-file`
+    pub fn exec_credentials(database: &DatabaseContainer) -> Vec<(&'static str, String)> {
+        let password = DatabaseCredentials::of(database).password;
+        let key = match database.db_type.as_str() {
+            "MySQL" | "MariaDB" => "MYSQL_PWD",
+            "Redis" if database.stored_enable_auth && !password.is_empty() => "REDISCLI_AUTH",
+            "MongoDB" if database.stored_enable_auth => MONGO_PASSWORD_VAR,
+            _ => return Vec::new(),
+        };
+        vec![(key, password)]
+    }
+
+    /// `docker exec` arguments of the engine's clean shutdown, None for engines without one
+    /// Credentials come from `env_file`, written from `exec_credentials`
+    pub fn exec_args(database: &DatabaseContainer, env_file: Option<&Path>) -> Option<Vec<String>> {
+        let credentials = DatabaseCredentials::of(database);
+        let (options, shutdown): (Vec<String>, Vec<String>) = match database.db_type.as_str() {
+            // Fast mode rolls back open transactions and writes a checkpoint
+            "PostgreSQL" => (
+                vec!["-u".to_string(), "postgres".to_string()],
+                ["pg_ctl", "stop", "-m", "fast", "-w"]
+                    .map(str::to_owned)
+                    .to_vec(),
+            ),
+            "MySQL" | "MariaDB" => (
+                Vec::new(),
+                [
+                    if database.db_type == "MariaDB" {
+                        "mariadb-admin"
+                    } else {
+                        "mysqladmin"
+                    },
+                    "-u",
+                    "root",
+                    "shutdown",
+                ]
+                .map(str::to_owned)
+                .to_vec(),
+            ),
+            "Redis" => (
+                Vec::new(),
+                ["redis-cli", "SHUTDOWN", "SAVE"]
+                    .map(str::to_owned)
+                    .to_vec(),
+            ),
+            "MongoDB" => {
+                let mut script = String::new();
+                if database.stored_enable_auth {
+                    script.push_str(&mongo_login(&credentials.username));
+                }
+                script.push_str("db.getSiblingDB('admin').shutdownServer()");
+                (
+                    Vec::new(),
+                    vec![
+                        "mongosh".to_string(),
+                        "--quiet".to_string(),
+                        "--eval".to_string(),
+                        script,
+                    ],
+                )
+            }
+            _ => return None,
+        };
+
+        let mut args = vec!["exec".to_string()];
+        if let Some(env_file) = env_file {
+            args.extend([
+                "--env-file".to_string(),
+                env_file.to_string_lossy().to_string(),
+            ]);
+        }
+        args.extend(options);
+        args.push(database.name.clone());
+        args.extend(shutdown);
+        Some(args)
+    }
+
+    /// Shut a running database down cleanly, then `docker stop` what is left
+    /// Only databases with persistent data are shut down by the engine, there is nothing to flush otherwise
+    pub async fn stop(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<(), AppError> {
+        let real_id = database
+            .container_id
+            .as_ref()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+        let docker_service = DockerService::for_database(app, database)?;
+        let _stopping = StoppingGuard::new(&database.id);
+
+        if database.stored_persist_data && database.is_running() {
+            if let Err(message) = self.shut_down(app, &docker_service, database).await {
+                // `docker stop` still runs, the engine may recover at the next start
+                EventService::new().background_error(app, "graceful-stop", message);
+            }
+        }
+
+        // Returns at once when the engine already exited with the container
+        docker_service
+            .stop_container_with_timeout(app, real_id, database.stop_timeout)
+            .await
+            .map_err(|error| docker_service.host_error(error))
+    }
+
+    /// Run the engine's shutdown, the error says why the database may not have stopped cleanly
+    async fn shut_down(
+        &self,
+        app: &AppHandle,
+        docker_service: &DockerService,
+        database: &DatabaseContainer,
+    ) -> Result<(), String> {
+        let credentials = Self::exec_credentials(database);
+        // Kept until the shutdown returns, Docker reads it when the exec starts
+        let env_file = if credentials.is_empty() {
+            None
+        } else {
+            let vars: Vec<(&str, &str)> = credentials
+                .iter()
+                .map(|(key, value)| (*key, value.as_str()))
+                .collect();
+            Some(EnvFile::create(&vars).map_err(|error| {
+                format!("Clean shutdown of {} failed: {}", database.name, error)
+            })?)
+        };
+        let Some(args) = Self::exec_args(database, env_file.as_ref().map(EnvFile::path)) else {
+            return Ok(());
+        };
+
+        let wait = database.stop_timeout.unwrap_or(DEFAULT_SHUTDOWN_WAIT_SECS);
+        let shutdown = docker_service.stdout(app, "Failed to shut down the database", &args);
+        match tokio::time::timeout(Duration::from_secs(wait.into()), shutdown).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(error)) => Err(format!(
+                "Clean shutdown of {} failed: {}",
+                database.name, error
+            )),
+            Err(_) => Err(format!(
+                "Clean shutdown of {} took longer than {}s",
+                database.name, wait
+            )),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+
+pub struct GroupService;
+
+impl GroupService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Members of a group in start order
+    /// Members missing from the explicit order follow it, sorted by name
+    pub fn ordered_members(
+        group: &ContainerGroup,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Vec<DatabaseContainer> {
+        let is_member = |db: &DatabaseContainer| db.project.as_deref() == Some(&group.name);
+
+        let mut members: Vec<DatabaseContainer> = group
+            .order
+            .iter()
+            .filter_map(|id| databases.get(id))
+            .filter(|db| is_member(db))
+            .cloned()
+            .collect();
+
+        let mut unordered: Vec<DatabaseContainer> = databases
+            .values()
+            .filter(|db| is_member(db) && !group.order.contains(&db.id))
+            .cloned()
+            .collect();
+        unordered.sort_by(|a, b| a.name.cmp(&b.name));
+
+        members.extend(unordered);
+        members
+    }
+
+    /// Members in start order, each after the members it depends on
+    /// The explicit order decides between members that do not depend on each other
+    pub fn start_order(
+        group: &ContainerGroup,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Result<Vec<DatabaseContainer>, AppError> {
+        let mut pending = Self::ordered_members(group, databases);
+        let members: HashSet<String> = pending.iter().map(|db| db.id.clone()).collect();
+        let mut started: HashSet<String> = HashSet::new();
+        let mut ordered = Vec::with_capacity(pending.len());
+
+        while !pending.is_empty() {
+            // Dependencies on containers that left the group are ignored
+            let next = pending.iter().position(|db| {
+                Self::dependencies_of(group, &db.id)
+                    .iter()
+                    .all(|id| !members.contains(id) || started.contains(id))
+            });
+            let Some(index) = next else {
+                let names: Vec<&str> = pending.iter().map(|db| db.name.as_str()).collect();
+                return Err(AppError::Validation(format!(
+                    "The dependencies of {} form a cycle",
+                    names.join(", ")
+                )));
+            };
+            let member = pending.remove(index);
+            started.insert(member.id.clone());
+            ordered.push(member);
+        }
+
+        Ok(ordered)
+    }
+
+    /// Members `container_id` waits for
+    pub fn dependencies_of<'a>(group: &'a ContainerGroup, container_id: &str) -> &'a [String] {
+        group
+            .dependencies
+            .get(container_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Whether another member waits for `container_id`
+    pub fn has_dependents(group: &ContainerGroup, container_id: &str) -> bool {
+        group
+            .dependencies
+            .values()
+            .any(|ids| ids.iter().any(|id| id == container_id))
+    }
+
+    /// Replace the dependencies of a member, all of them members of the same group
+    /// Refused when they would form a cycle, the group is left unchanged then
+    pub fn set_dependencies(
+        group: &mut ContainerGroup,
+        container_id: &str,
+        depends_on: Vec<String>,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Result<(), AppError> {
+        let is_member = |id: &str| {
+            databases
+                .get(id)
+                .is_some_and(|db| db.project.as_deref() == Some(&group.name))
+        };
+        if !is_member(container_id) {
+            return Err(AppError::NotFound("Container".to_string()));
+        }
+
+        let mut dependencies: Vec<String> = Vec::new();
+        for id in depends_on {
+            if id == container_id {
+                return Err(AppError::Validation(
+                    "A container cannot depend on itself".to_string(),
+                ));
+            }
+            if !is_member(&id) {
+                return Err(AppError::Validation(format!(
+                    "Dependencies must be members of {}",
+                    group.name
+                )));
+            }
+            if !dependencies.contains(&id) {
+                dependencies.push(id);
+            }
+        }
+
+        let mut updated = group.clone();
+        if dependencies.is_empty() {
+            updated.dependencies.remove(container_id);
+        } else {
+            updated
+                .dependencies
+                .insert(container_id.to_string(), dependencies);
+        }
+        Self::start_order(&updated, databases)?;
+
+        *group = updated;
+        Ok(())
+    }
+
+    /// Forget a container that leaves the group, with its dependencies either way
+    pub fn remove_member(group: &mut ContainerGroup, container_id: &str) {
+        group.order.retain(|id| id != container_id);
+        group.dependencies.remove(container_id);
+        for ids in group.dependencies.values_mut() {
+            ids.retain(|id| id != container_id);
+        }
+        group.dependencies.retain(|_, ids| !ids.is_empty());
+    }
+
+    pub fn validate_name(name: &str) -> Result<String, AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::Validation(
+                "Group name cannot be empty".to_string(),
+            ));
+        }
+        Ok(name.to_string())
+    }
+}
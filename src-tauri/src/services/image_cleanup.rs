@@ -0,0 +1,157 @@
+use crate::services::{DockerService, StorageService};
+use crate::types::*;
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager};
+
+// Describes a failed docker call of this service
+const CONTEXT: &str = "Failed to clean up images";
+
+/// Tracks the images of removed databases and removes them once nothing uses them
+pub struct ImageCleanupService;
+
+impl ImageCleanupService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Remember the images of removed databases as cleanup candidates
+    /// Only images of the local daemon are tracked, a remote host keeps its own
+    pub async fn release(
+        &self,
+        app: &AppHandle,
+        removed: &[DatabaseContainer],
+    ) -> Result<(), AppError> {
+        let images: Vec<String> = removed
+            .iter()
+            .filter(|database| database.host.is_none())
+            .filter_map(|database| database.stored_docker_args.as_ref())
+            .map(|docker_args| docker_args.image.clone())
+            .collect();
+        if images.is_empty() {
+            return Ok(());
+        }
+
+        let storage_service = StorageService::new();
+        let mut released = storage_service.load_released_images_from_store(app).await?;
+        release_images(&mut released, &images, &chrono::Utc::now().to_rfc3339());
+        storage_service
+            .save_released_images_to_store(app, &released)
+            .await
+    }
+
+    /// Images of removed databases no managed container uses and no Docker container runs from
+    /// Without `dry_run` they are removed, tags shared with other images only lose the tag
+    pub async fn cleanup(
+        &self,
+        app: &AppHandle,
+        dry_run: bool,
+    ) -> Result<ImageCleanupReport, AppError> {
+        let storage_service = StorageService::new();
+        let released = storage_service.load_released_images_from_store(app).await?;
+
+        let managed: Vec<String> = {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            db_map
+                .values()
+                .filter_map(|database| database.stored_docker_args.as_ref())
+                .map(|docker_args| docker_args.image.clone())
+                .collect()
+        };
+        let docker_service = DockerService::new();
+        let candidates = cleanup_candidates(&released, &managed);
+        let used_ids = self.container_image_ids(app, &docker_service).await?;
+
+        // Images deleted outside the app are forgotten
+        let mut gone = Vec::new();
+        let mut images = Vec::new();
+        for candidate in candidates {
+            let Ok(output) = docker_service
+                .stdout(
+                    app,
+                    CONTEXT,
+                    &[
+                        "image",
+                        "inspect",
+                        "--format",
+                        "{{.Id}} {{.Size}}",
+                        &candidate.image,
+                    ],
+                )
+                .await
+            else {
+                gone.push(image_key(&candidate.image));
+                continue;
+            };
+            let mut fields = output.split_whitespace();
+            let image_id = fields.next().unwrap_or_default().to_string();
+            if used_ids.contains(&image_id) {
+                continue;
+            }
+            images.push(UnusedImage {
+                image: candidate.image,
+                image_id,
+                size_bytes: fields
+                    .next()
+                    .and_then(|size| size.parse().ok())
+                    .unwrap_or(0),
+                released_at: candidate.released_at,
+            });
+        }
+
+        let mut errors = Vec::new();
+        if !dry_run {
+            let mut removed = Vec::new();
+            for image in images {
+                match docker_service
+                    .stdout(app, CONTEXT, &["rmi", &image.image])
+                    .await
+                {
+                    Ok(_) => {
+                        gone.push(image_key(&image.image));
+                        removed.push(image);
+                    }
+                    Err(error) => errors.push(format!("{}: {}", image.image, error)),
+                }
+            }
+            images = removed;
+        }
+
+        if !gone.is_empty() {
+            let remaining: Vec<ReleasedImage> = released
+                .into_iter()
+                .filter(|entry| !gone.contains(&image_key(&entry.image)))
+                .collect();
+            storage_service
+                .save_released_images_to_store(app, &remaining)
+                .await?;
+        }
+
+        Ok(ImageCleanupReport {
+            dry_run,
+            total_bytes: images.iter().map(|image| image.size_bytes).sum(),
+            images,
+            errors,
+        })
+    }
+
+    /// Image ids of every Docker container, managed or not, running or stopped
+    async fn container_image_ids(
+        &self,
+        app: &AppHandle,
+        docker_service: &DockerService,
+    ) -> Result<HashSet<String>, AppError> {
+        let ids = docker_service
+            .stdout(app, CONTEXT, &["ps", "-aq", "--no-trunc"])
+            .await?;
+        let ids: Vec<&str> = ids.split_whitespace().collect();
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let mut args = vec!["inspect", "--type", "container", "--format", "{{.Image}}"];
+        args.extend(ids);
+        let output = docker_service.stdout(app, CONTEXT, &args).await?;
+        Ok(output.lines().map(|line| line.trim().to_string()).collect())
+    }
+}
@@ -0,0 +1,227 @@
+use crate::services::{DockerService, EventService, StorageService};
+use crate::types::*;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+// Delay before the first background check, the launch already queries Docker a lot
+const FIRST_CHECK_DELAY_SECS: u64 = 60;
+// How often a disabled check looks at the settings again
+const DISABLED_RECHECK_SECS: u64 = 3600;
+// Describes a failed docker call of this service
+const CONTEXT: &str = "Failed to check image";
+
+/// Compares the images of managed databases with their registry
+pub struct ImageUpdateService;
+
+impl ImageUpdateService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check every database, or only `database_ids`, and store the resulting flags
+    /// A failing registry is reported per database and leaves its flag as it was
+    pub async fn check(
+        &self,
+        app: &AppHandle,
+        database_ids: Option<&[String]>,
+    ) -> Result<Vec<ImageUpdateCheck>, AppError> {
+        let targets: Vec<DatabaseContainer> = {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            db_map
+                .values()
+                .filter(|db| database_ids.is_none_or(|ids| ids.contains(&db.id)))
+                .cloned()
+                .collect()
+        };
+
+        let mut checks = Vec::new();
+        for database in &targets {
+            checks.push(self.check_database(app, database).await);
+        }
+
+        // Events go out once the lock is released, listeners may read the store
+        let changed: Vec<DatabaseContainer> = {
+            let databases = app.state::<DatabaseStore>();
+            let mut db_map = databases.write().await;
+            let mut changed = Vec::new();
+            for check in checks.iter().filter(|check| check.error.is_none()) {
+                if let Some(db) = db_map.get_mut(&check.database_id) {
+                    if db.updates_available != check.updates_available {
+                        db.updates_available = check.updates_available;
+                        changed.push(db.clone());
+                    }
+                }
+            }
+            changed
+        };
+
+        let event_service = EventService::new();
+        for database in &changed {
+            event_service.container_updated(app, database);
+        }
+        StorageService::new()
+            .upsert_databases_in_store(app, &changed)
+            .await?;
+
+        Ok(checks)
+    }
+
+    async fn check_database(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> ImageUpdateCheck {
+        let Some(image) = database
+            .stored_docker_args
+            .as_ref()
+            .map(|args| args.image.clone())
+        else {
+            return ImageUpdateCheck::failed(
+                &database.id,
+                "",
+                "The image of this database is unknown".to_string(),
+            );
+        };
+        let (repository, tag) = split_image_reference(&image);
+
+        let remote_digest = match self.remote_digest(app, &image).await {
+            Ok(digest) => digest,
+            Err(error) => return ImageUpdateCheck::failed(&database.id, &image, error.to_string()),
+        };
+        let local_digest = self.local_digest(app, database, &image, repository).await;
+
+        let mut newer_version = None;
+        for candidate in newer_major_tags(tag).unwrap_or_default() {
+            let reference = format!("{}:{}", repository, candidate);
+            match self.remote_digest(app, &reference).await {
+                Ok(Some(_)) => newer_version = Some(candidate),
+                _ => break,
+            }
+        }
+
+        ImageUpdateCheck::new(
+            &database.id,
+            &image,
+            local_digest,
+            remote_digest,
+            newer_version,
+        )
+    }
+
+    /// Digest the container was created from, falls back to the local image of the tag
+    async fn local_digest(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        image: &str,
+        repository: &str,
+    ) -> Option<String> {
+        let docker_service = DockerService::for_database(app, database).ok()?;
+        let image_id = docker_service
+            .stdout(
+                app,
+                CONTEXT,
+                &[
+                    "container",
+                    "inspect",
+                    "--format",
+                    "{{.Image}}",
+                    &database.name,
+                ],
+            )
+            .await
+            .ok()
+            .map(|output| output.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| image.to_string());
+
+        let output = docker_service
+            .stdout(
+                app,
+                CONTEXT,
+                &[
+                    "image",
+                    "inspect",
+                    "--format",
+                    "{{json .RepoDigests}}",
+                    &image_id,
+                ],
+            )
+            .await
+            .ok()?;
+        let repo_digests: Vec<String> = serde_json::from_str(output.trim()).ok()?;
+
+        repo_digest(&repo_digests, repository)
+    }
+
+    /// Digest `image` points to in its registry, `None` when the tag does not exist
+    /// Registries are queried from the local daemon whatever the database's host
+    async fn remote_digest(
+        &self,
+        app: &AppHandle,
+        image: &str,
+    ) -> Result<Option<String>, AppError> {
+        let output = DockerService::new()
+            .stdout(
+                app,
+                CONTEXT,
+                &[
+                    "buildx",
+                    "imagetools",
+                    "inspect",
+                    "--format",
+                    "{{json .Manifest}}",
+                    image,
+                ],
+            )
+            .await;
+
+        match output {
+            Ok(output) => {
+                let manifest: serde_json::Value =
+                    serde_json::from_str(output.trim()).map_err(|e| AppError::Docker {
+                        message: format!("Unexpected manifest of {}", image),
+                        details: Some(e.to_string()),
+                    })?;
+                Ok(manifest
+                    .get("digest")
+                    .and_then(|digest| digest.as_str())
+                    .map(str::to_owned))
+            }
+            Err(AppError::ImageNotFound { .. }) => Ok(None),
+            // imagetools reports a missing tag as "<reference>: not found"
+            Err(AppError::Docker { message, .. })
+                if message.trim_end().ends_with(": not found") =>
+            {
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Spawn the periodic check, the interval is re-read from settings after every run
+    pub fn start_worker(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let image_update_service = ImageUpdateService::new();
+            tokio::time::sleep(Duration::from_secs(FIRST_CHECK_DELAY_SECS)).await;
+
+            loop {
+                let interval_hours = {
+                    let settings = app.state::<SettingsStore>();
+                    let settings = settings.lock().unwrap();
+                    settings.image_update_check_hours
+                };
+
+                if interval_hours == 0 {
+                    tokio::time::sleep(Duration::from_secs(DISABLED_RECHECK_SECS)).await;
+                    continue;
+                }
+
+                // Offline or a locked store, retried on the next run
+                let _ = image_update_service.check(&app, None).await;
+                tokio::time::sleep(Duration::from_secs(interval_hours * 3600)).await;
+            }
+        });
+    }
+}
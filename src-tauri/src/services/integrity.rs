@@ -0,0 +1,224 @@
+use crate::services::{
+    mongo_login, AlertService, DockerService, EnvFile, EventService, StorageService,
+    MONGO_PASSWORD_VAR,
+};
+use crate::types::*;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use tauri::AppHandle;
+
+// Databases with a check in progress, a second check would only compete for I/O
+static RUNNING: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+// Checks finish concurrently, the history is read and written back as a whole
+static HISTORY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+// Prints one `{ns, valid, errors}` line per collection of every database
+const MONGO_VALIDATE_SCRIPT: &str =
+    "db.adminCommand({listDatabases: 1}).databases.forEach(function (d) {
+  if (d.name === 'local' || d.name === 'config') return;
+  var target = db.getSiblingDB(d.name);
+  target.getCollectionInfos({type: 'collection'}).forEach(function (c) {
+    var r = target.runCommand({validate: c.name});
+    print(JSON.stringify({
+      ns: d.name + '.' + c.name,
+      valid: r.ok === 1 && r.valid,
+      errors: r.errors || (r.errmsg ? [r.errmsg] : [])
+    }));
+  });
+})";
+
+// A fresh Redis has not saved a snapshot yet
+const REDIS_CHECK_SCRIPT: &str = "if [ -f /data/dump.rdb ]; then redis-check-rdb /data/dump.rdb; \
+else echo 'No RDB file to check yet'; fi";
+
+/// Runs the engine's own consistency checker inside running containers and keeps the results
+pub struct IntegrityService;
+
+impl IntegrityService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Variables the checker reads its password from, passed with `--env-file`
+    pub fn exec_credentials(database: &DatabaseContainer) -> Vec<(&'static str, String)> {
+        let password = DatabaseCredentials::of(database).password;
+        let key = match database.db_type.as_str() {
+            "PostgreSQL" => "PGPASSWORD",
+            "MySQL" | "MariaDB" => "MYSQL_PWD",
+            "MongoDB" if database.stored_enable_auth => MONGO_PASSWORD_VAR,
+            _ => return Vec::new(),
+        };
+        vec![(key, password)]
+    }
+
+    /// `docker exec` arguments of the checker, run under `nice` so queries keep priority
+    /// `install_missing` lets pg_amcheck create the amcheck extension in databases without it
+    /// Credentials come from `env_file`, written from `exec_credentials`
+    pub fn exec_args(
+        database: &DatabaseContainer,
+        install_missing: bool,
+        env_file: Option<&Path>,
+    ) -> Option<Vec<String>> {
+        let credentials = DatabaseCredentials::of(database);
+        let checker: Vec<String> = match database.db_type.as_str() {
+            "PostgreSQL" => {
+                let mut checker = vec!["pg_amcheck", "-U", &credentials.username, "--all"];
+                if install_missing {
+                    checker.push("--install-missing");
+                }
+                checker.push("--maintenance-db=postgres");
+                checker.into_iter().map(str::to_owned).collect()
+            }
+            "MySQL" | "MariaDB" => [
+                integrity_tool(&database.db_type)?,
+                "-u",
+                "root",
+                "--all-databases",
+                "--check",
+            ]
+            .map(str::to_owned)
+            .to_vec(),
+            "Redis" => ["sh", "-c", REDIS_CHECK_SCRIPT].map(str::to_owned).to_vec(),
+            "MongoDB" => {
+                let mut script = String::new();
+                if database.stored_enable_auth {
+                    script.push_str(&mongo_login(&credentials.username));
+                }
+                script.push_str(MONGO_VALIDATE_SCRIPT);
+                vec![
+                    "mongosh".to_string(),
+                    "--quiet".to_string(),
+                    "--eval".to_string(),
+                    script,
+                ]
+            }
+            _ => return None,
+        };
+
+        let mut args = vec!["exec".to_string()];
+        if let Some(env_file) = env_file {
+            args.extend([
+                "--env-file".to_string(),
+                env_file.to_string_lossy().to_string(),
+            ]);
+        }
+        args.push(database.name.clone());
+        args.extend(["nice", "-n", "19"].map(str::to_string));
+        args.extend(checker);
+        Some(args)
+    }
+
+    /// Check a running database, record the run and alert when it found corruption
+    /// A checker that could not run is recorded as an error run
+    pub async fn check(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        install_missing: bool,
+        schedule_id: Option<String>,
+    ) -> Result<IntegrityCheckRun, AppError> {
+        let tool = integrity_tool(&database.db_type).ok_or_else(|| {
+            AppError::Validation(format!("{} has no integrity checker", database.db_type))
+        })?;
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to check its integrity",
+                database.name
+            )));
+        }
+        if !RUNNING.lock().unwrap().insert(database.id.clone()) {
+            return Err(AppError::Validation(format!(
+                "An integrity check of {} is already running",
+                database.name
+            )));
+        }
+
+        let started_at = chrono::Utc::now().to_rfc3339();
+        let result = self.exec(app, database, install_missing).await;
+        RUNNING.lock().unwrap().remove(&database.id);
+
+        let (status, problems, output) = match result {
+            Ok((exit_code, output)) => {
+                let (status, problems) = evaluate_integrity(&database.db_type, exit_code, &output);
+                (status, problems, output)
+            }
+            Err(error) => (IntegrityStatus::Error, Vec::new(), error.to_string()),
+        };
+        let run = IntegrityCheckRun {
+            id: uuid::Uuid::new_v4().to_string(),
+            database_id: database.id.clone(),
+            database_name: database.name.clone(),
+            tool: tool.to_string(),
+            status,
+            problems,
+            output: integrity_output_tail(&output),
+            started_at,
+            finished_at: chrono::Utc::now().to_rfc3339(),
+            schedule_id,
+        };
+
+        {
+            let _lock = HISTORY_LOCK.lock().await;
+            let storage_service = StorageService::new();
+            let mut history = storage_service
+                .load_integrity_checks_from_store(app)
+                .await?;
+            history.push(run.clone());
+            let excess = history.len().saturating_sub(MAX_INTEGRITY_HISTORY);
+            history.drain(..excess);
+            storage_service
+                .save_integrity_checks_to_store(app, &history)
+                .await?;
+        }
+
+        EventService::new().integrity_check_completed(app, &run);
+        if run.status == IntegrityStatus::Failed {
+            AlertService::new().integrity_failed(app, database).await?;
+        }
+        Ok(run)
+    }
+
+    /// Runs newest first, of one database or of all of them
+    pub async fn history(
+        &self,
+        app: &AppHandle,
+        database_id: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<IntegrityCheckRun>, AppError> {
+        let history = StorageService::new()
+            .load_integrity_checks_from_store(app)
+            .await?;
+        Ok(history
+            .into_iter()
+            .rev()
+            .filter(|run| database_id.is_none_or(|id| run.database_id == id))
+            .take(limit.unwrap_or(MAX_INTEGRITY_HISTORY))
+            .collect())
+    }
+
+    // Exit code and combined output, a failing checker is a result rather than an error
+    async fn exec(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        install_missing: bool,
+    ) -> Result<(Option<i32>, String), AppError> {
+        let credentials = Self::exec_credentials(database);
+        let vars: Vec<(&str, &str)> = credentials
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect();
+        // Kept until the checker returns
+        let env_file = EnvFile::create(&vars)?;
+        let args =
+            Self::exec_args(database, install_missing, Some(env_file.path())).unwrap_or_default();
+        let output = DockerService::for_database(app, database)?
+            .output(app, "Failed to run the integrity check", &args)
+            .await?;
+
+        let mut combined = String::from_utf8_lossy(&output.stdout).to_string();
+        combined.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok((output.status.code(), combined))
+    }
+}
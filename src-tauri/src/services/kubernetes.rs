@@ -0,0 +1,268 @@
+use crate::services::{ConfigService, TlsService, CERTS_MOUNT_PATH};
+use crate::types::*;
+
+// Requested size of each volume claim, adjust to the data the database will hold
+const DEFAULT_VOLUME_SIZE: &str = "10Gi";
+// Kubernetes object names are DNS labels
+const MAX_RESOURCE_NAME_LENGTH: usize = 63;
+// Redis receives its password as a server argument, the manifest reads it from the secret
+const COMMAND_PASSWORD_KEY: &str = "REDIS_PASSWORD";
+// Where the PostgreSQL images keep their data, a claim mounted there holds lost+found
+const POSTGRES_DATA_PATH: &str = "/var/lib/postgresql/data";
+
+/// Kubernetes manifests running a managed database the way its container runs locally
+pub struct KubernetesService;
+
+impl KubernetesService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Secret, volume claims, Service and StatefulSet of a database as one YAML file
+    /// TLS certificates and LAN publishing stay local, the cluster handles both itself
+    pub fn manifests(&self, database: &DatabaseContainer) -> Result<String, AppError> {
+        let mut docker_args = database.stored_docker_args.clone().ok_or_else(|| {
+            AppError::Validation(format!(
+                "{} has no run configuration to export, recreate it first",
+                database.name
+            ))
+        })?;
+        Self::strip_tls(&database.db_type, &database.name, &mut docker_args);
+        // initdb refuses a data directory that is not empty, a subdirectory of the claim is
+        if database.db_type == "PostgreSQL"
+            && docker_args
+                .volumes
+                .iter()
+                .any(|volume| volume.path == POSTGRES_DATA_PATH)
+        {
+            docker_args
+                .env_vars
+                .entry("PGDATA".to_string())
+                .or_insert_with(|| format!("{}/pgdata", POSTGRES_DATA_PATH));
+        }
+
+        let name = Self::resource_name(&database.name);
+        let secret_name = format!("{}-credentials", name);
+        let labels = [format!("app.kubernetes.io/name: {}", name)];
+
+        let mut env_vars: Vec<_> = docker_args.env_vars.iter().collect();
+        env_vars.sort();
+        let (secret_vars, plain_vars): (Vec<_>, Vec<_>) = env_vars
+            .into_iter()
+            .partition(|(key, _)| ConfigService::is_secret_env_var(key));
+
+        let mut secrets: Vec<(String, String)> = secret_vars
+            .into_iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let mut args = Vec::new();
+        let mut secret_next = false;
+        for arg in &docker_args.command {
+            if secret_next {
+                secrets.push((COMMAND_PASSWORD_KEY.to_string(), arg.clone()));
+                args.push(format!("$({})", COMMAND_PASSWORD_KEY));
+            } else {
+                args.push(arg.clone());
+            }
+            secret_next = arg == "--requirepass";
+        }
+
+        let mut documents = Vec::new();
+
+        if !secrets.is_empty() {
+            let mut lines = Self::object_header("v1", "Secret", &secret_name, &labels);
+            lines.push("type: Opaque".to_string());
+            lines.push("stringData:".to_string());
+            for (key, value) in &secrets {
+                lines.push(format!("  {}: {}", key, Self::quoted(value)));
+            }
+            documents.push(lines);
+        }
+
+        // Pod volume name and claim of each Docker volume
+        let volumes: Vec<(String, String, &str)> = docker_args
+            .volumes
+            .iter()
+            .map(|volume| {
+                let short = volume
+                    .name
+                    .strip_prefix(&format!("{}-", database.name))
+                    .unwrap_or(&volume.name);
+                (
+                    Self::resource_name(short),
+                    Self::resource_name(&volume.name),
+                    volume.path.as_str(),
+                )
+            })
+            .collect();
+
+        for (_, claim, _) in &volumes {
+            let mut lines = Self::object_header("v1", "PersistentVolumeClaim", claim, &labels);
+            lines.extend([
+                "spec:".to_string(),
+                "  accessModes:".to_string(),
+                "    - ReadWriteOnce".to_string(),
+                "  resources:".to_string(),
+                "    requests:".to_string(),
+                format!("      storage: {}", DEFAULT_VOLUME_SIZE),
+            ]);
+            documents.push(lines);
+        }
+
+        let mut container_ports: Vec<i32> = docker_args
+            .ports
+            .iter()
+            .map(|port| port.container)
+            .collect();
+        container_ports.sort();
+        container_ports.dedup();
+
+        let mut lines = Self::object_header("v1", "Service", &name, &labels);
+        lines.push("spec:".to_string());
+        lines.push("  selector:".to_string());
+        lines.extend(labels.iter().map(|label| format!("    {}", label)));
+        lines.push("  ports:".to_string());
+        for port in &container_ports {
+            lines.extend([
+                format!("    - name: port-{}", port),
+                format!("      port: {}", port),
+                format!("      targetPort: {}", port),
+            ]);
+        }
+        documents.push(lines);
+
+        let mut lines = Self::object_header("apps/v1", "StatefulSet", &name, &labels);
+        lines.extend([
+            "spec:".to_string(),
+            format!("  serviceName: {}", name),
+            "  replicas: 1".to_string(),
+            "  selector:".to_string(),
+            "    matchLabels:".to_string(),
+        ]);
+        lines.extend(labels.iter().map(|label| format!("      {}", label)));
+        lines.extend([
+            "  template:".to_string(),
+            "    metadata:".to_string(),
+            "      labels:".to_string(),
+        ]);
+        lines.extend(labels.iter().map(|label| format!("        {}", label)));
+        lines.extend([
+            "    spec:".to_string(),
+            "      containers:".to_string(),
+            "        - name: database".to_string(),
+            format!("          image: {}", Self::quoted(&docker_args.image)),
+        ]);
+        if !args.is_empty() {
+            lines.push("          args:".to_string());
+            lines.extend(
+                args.iter()
+                    .map(|arg| format!("            - {}", Self::quoted(arg))),
+            );
+        }
+        if !container_ports.is_empty() {
+            lines.push("          ports:".to_string());
+            lines.extend(
+                container_ports
+                    .iter()
+                    .map(|port| format!("            - containerPort: {}", port)),
+            );
+        }
+        if !plain_vars.is_empty() || !secrets.is_empty() {
+            lines.push("          env:".to_string());
+            for (key, value) in &plain_vars {
+                lines.push(format!("            - name: {}", key));
+                lines.push(format!("              value: {}", Self::quoted(value)));
+            }
+            for (key, _) in &secrets {
+                lines.extend([
+                    format!("            - name: {}", key),
+                    "              valueFrom:".to_string(),
+                    "                secretKeyRef:".to_string(),
+                    format!("                  name: {}", secret_name),
+                    format!("                  key: {}", key),
+                ]);
+            }
+        }
+        if !volumes.is_empty() {
+            lines.push("          volumeMounts:".to_string());
+            for (volume, _, path) in &volumes {
+                lines.push(format!("            - name: {}", volume));
+                lines.push(format!("              mountPath: {}", Self::quoted(path)));
+            }
+            lines.push("      volumes:".to_string());
+            for (volume, claim, _) in &volumes {
+                lines.extend([
+                    format!("        - name: {}", volume),
+                    "          persistentVolumeClaim:".to_string(),
+                    format!("            claimName: {}", claim),
+                ]);
+            }
+        }
+        documents.push(lines);
+
+        let header = format!(
+            "# {} ({} {})\n# kubectl apply -f {}.yaml\n",
+            database.name, database.db_type, database.version, name
+        );
+        let body = documents
+            .into_iter()
+            .map(|lines| lines.join("\n") + "\n")
+            .collect::<Vec<_>>()
+            .join("---\n");
+        Ok(header + &body)
+    }
+
+    /// Drop the certificates volume and the server arguments reading from it
+    fn strip_tls(db_type: &str, container_name: &str, docker_args: &mut DockerRunArgs) {
+        let certs_volume = TlsService::certs_volume_name(container_name);
+        docker_args
+            .volumes
+            .retain(|volume| volume.name != certs_volume && volume.path != CERTS_MOUNT_PATH);
+
+        let Ok(tls_args) = TlsService::build_tls_command_args(db_type) else {
+            return;
+        };
+        if let Some(start) = docker_args
+            .command
+            .windows(tls_args.len())
+            .position(|window| window == tls_args.as_slice())
+        {
+            docker_args.command.drain(start..start + tls_args.len());
+        }
+    }
+
+    /// Closest valid object name: lowercase letters, digits and dashes
+    pub fn resource_name(name: &str) -> String {
+        let mut resource = String::new();
+        for c in name.to_lowercase().chars() {
+            let c = if c.is_ascii_alphanumeric() { c } else { '-' };
+            if c == '-' && (resource.is_empty() || resource.ends_with('-')) {
+                continue;
+            }
+            resource.push(c);
+        }
+
+        let resource: String = resource.chars().take(MAX_RESOURCE_NAME_LENGTH).collect();
+        match resource.trim_end_matches('-') {
+            "" => "database".to_string(),
+            trimmed => trimmed.to_string(),
+        }
+    }
+
+    fn object_header(api_version: &str, kind: &str, name: &str, labels: &[String]) -> Vec<String> {
+        let mut lines = vec![
+            format!("apiVersion: {}", api_version),
+            format!("kind: {}", kind),
+            "metadata:".to_string(),
+            format!("  name: {}", name),
+            "  labels:".to_string(),
+        ];
+        lines.extend(labels.iter().map(|label| format!("    {}", label)));
+        lines
+    }
+
+    /// Double-quoted YAML scalar, JSON escaping is valid YAML
+    fn quoted(value: &str) -> String {
+        serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+    }
+}
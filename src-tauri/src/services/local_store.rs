@@ -0,0 +1,87 @@
+use crate::services::{StorageService, SETTINGS_FILE};
+use crate::types::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Bundle identifier, the app data directory is named after it
+pub const APP_IDENTIFIER: &str = "com.abians.docker-db-manager";
+
+/// The app's store files read without a running Tauri app, used by the `ddm` CLI
+pub struct LocalStore {
+    data_dir: PathBuf,
+}
+
+impl LocalStore {
+    pub fn new(data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+        }
+    }
+
+    /// Same directory Tauri resolves as the app data directory
+    pub fn default_location() -> Result<Self, AppError> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| AppError::Storage("Failed to resolve app data directory".to_string()))?;
+
+        Ok(Self::new(data_dir.join(APP_IDENTIFIER)))
+    }
+
+    fn path(&self, file: &Path) -> PathBuf {
+        self.data_dir.join(file)
+    }
+
+    /// Id of the workspace active in the app
+    pub fn active_workspace(&self) -> Result<String, AppError> {
+        let workspaces: WorkspaceConfig = match StorageService::read_value_at(
+            &self.path(Path::new(SETTINGS_FILE)),
+            "workspaces",
+        )? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize workspaces: {}", e))
+            })?,
+            None => WorkspaceConfig::default(),
+        };
+
+        Ok(workspaces.active)
+    }
+
+    /// Store file of the workspace active in the app
+    fn databases_file(&self) -> Result<PathBuf, AppError> {
+        Ok(self.path(&StorageService::workspace_databases_file(
+            &self.active_workspace()?,
+        )))
+    }
+
+    /// Unlock a passphrase-encrypted store for this process
+    /// The key comes from the settings file, which names the workspace store to read next
+    pub fn unlock(&self, passphrase: &str) -> Result<(), AppError> {
+        StorageService::unlock_path(&self.path(Path::new(SETTINGS_FILE)), passphrase)
+    }
+
+    pub fn load_databases(&self) -> Result<HashMap<String, DatabaseContainer>, AppError> {
+        match StorageService::read_value_at(&self.databases_file()?, "databases")? {
+            Some(value) => Ok(StorageService::databases_from_value(value)?.0),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    /// Saved queries and commands of the workspace active in the app
+    pub fn load_snippets(&self) -> Result<SnippetLibrary, AppError> {
+        match StorageService::read_value_at(&self.databases_file()?, "snippets")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize snippets: {}", e))),
+            None => Ok(SnippetLibrary::default()),
+        }
+    }
+
+    pub fn save_databases(
+        &self,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Result<(), AppError> {
+        StorageService::write_value_at(
+            &self.databases_file()?,
+            "databases",
+            StorageService::databases_to_value(databases)?,
+        )
+    }
+}
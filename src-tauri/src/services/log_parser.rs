@@ -0,0 +1,208 @@
+use crate::types::*;
+use serde_json::Value;
+
+// PostgreSQL writes the context of an error on lines of their own after it
+const POSTGRES_DETAIL_LEVELS: [&str; 6] = [
+    "DETAIL",
+    "HINT",
+    "STATEMENT",
+    "CONTEXT",
+    "QUERY",
+    "LOCATION",
+];
+
+/// Splits container logs into entries in the format of each engine
+pub struct LogParserService;
+
+impl LogParserService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Entries of lines read with `docker logs --timestamps`, oldest first
+    /// Indented lines and PostgreSQL details continue the entry before them
+    pub fn parse_lines(&self, db_type: &str, lines: &[String]) -> Vec<LogEntry> {
+        let mut entries: Vec<LogEntry> = Vec::new();
+
+        for line in lines {
+            let (timestamp, message) = match log_line_timestamp(line) {
+                Some(timestamp) => (
+                    Some(timestamp.to_string()),
+                    line.trim_start()[timestamp.len()..]
+                        .strip_prefix(' ')
+                        .unwrap_or_default(),
+                ),
+                None => (None, line.as_str()),
+            };
+            if message.trim().is_empty() {
+                continue;
+            }
+
+            let continues = message.starts_with([' ', '\t'])
+                || (db_type == "PostgreSQL" && Self::postgres_detail(message));
+            if let Some(previous) = entries.last_mut().filter(|_| continues) {
+                previous.message.push('\n');
+                previous.message.push_str(message.trim_end());
+                continue;
+            }
+
+            entries.push(Self::parse_message(db_type, timestamp, message));
+        }
+
+        entries
+    }
+
+    /// One line of the engine's log, lines outside its format are kept whole as info
+    pub fn parse_message(db_type: &str, timestamp: Option<String>, message: &str) -> LogEntry {
+        let parsed = match db_type {
+            "PostgreSQL" => Self::parse_postgres(message),
+            "MySQL" | "MariaDB" => Self::parse_mysql(message),
+            "Redis" => Self::parse_redis(message),
+            "MongoDB" => Self::parse_mongo(message),
+            _ => None,
+        };
+        let (severity, source, text) =
+            parsed.unwrap_or((LogSeverity::Info, None, message.trim_end().to_string()));
+
+        LogEntry {
+            timestamp,
+            severity,
+            source,
+            message: text,
+        }
+    }
+
+    /// Detail lines carry the same prefix as the entry they belong to
+    fn postgres_detail(message: &str) -> bool {
+        message
+            .split_once(":  ")
+            .and_then(|(prefix, _)| prefix.rsplit(' ').next())
+            .is_some_and(|level| POSTGRES_DETAIL_LEVELS.contains(&level))
+    }
+
+    /// `<prefix> LEVEL:  message` of the stderr log, or a csvlog record
+    fn parse_postgres(message: &str) -> Option<(LogSeverity, Option<String>, String)> {
+        if let Some(parsed) = Self::parse_postgres_csv(message) {
+            return Some(parsed);
+        }
+
+        let (prefix, rest) = message.split_once(":  ")?;
+        let level = prefix.rsplit(' ').next()?;
+        let severity = Self::postgres_severity(level)?;
+        Some((severity, None, rest.trim_end().to_string()))
+    }
+
+    /// csvlog puts the level in the 12th column and the message in the 14th
+    fn parse_postgres_csv(message: &str) -> Option<(LogSeverity, Option<String>, String)> {
+        let fields = Self::split_csv(message);
+        if fields.len() < 14 {
+            return None;
+        }
+        let severity = Self::postgres_severity(&fields[11])?;
+        Some((severity, None, fields[13].clone()))
+    }
+
+    fn postgres_severity(level: &str) -> Option<LogSeverity> {
+        let severity = match level {
+            "DEBUG1" | "DEBUG2" | "DEBUG3" | "DEBUG4" | "DEBUG5" => LogSeverity::Debug,
+            "LOG" | "INFO" => LogSeverity::Info,
+            "NOTICE" => LogSeverity::Notice,
+            "WARNING" => LogSeverity::Warning,
+            "ERROR" => LogSeverity::Error,
+            "FATAL" | "PANIC" => LogSeverity::Fatal,
+            _ => return None,
+        };
+        Some(severity)
+    }
+
+    /// `<time> <thread> [Level] [code] [Subsystem] message`, MariaDB leaves out the last two
+    fn parse_mysql(message: &str) -> Option<(LogSeverity, Option<String>, String)> {
+        let open = message.find('[')?;
+        let (level, rest) = message[open + 1..].split_once(']')?;
+        let severity = match level.to_lowercase().as_str() {
+            "note" => LogSeverity::Info,
+            "system" => LogSeverity::Notice,
+            "warning" | "warn" => LogSeverity::Warning,
+            "error" => LogSeverity::Error,
+            _ => return None,
+        };
+
+        let mut rest = rest.trim_start();
+        let mut source = None;
+        // The MY-nnnnnn error code, then the subsystem or `[Entrypoint]:`
+        for _ in 0..2 {
+            let Some(tag) = rest.strip_prefix('[') else {
+                break;
+            };
+            let Some((name, after)) = tag.split_once(']') else {
+                break;
+            };
+            if !name.starts_with("MY-") {
+                source = Some(name.to_string());
+            }
+            rest = after.trim_start_matches(':').trim_start();
+        }
+
+        Some((severity, source, rest.trim_end().to_string()))
+    }
+
+    /// `pid:role day month year time level message`, the level is one of `.-*#`
+    fn parse_redis(message: &str) -> Option<(LogSeverity, Option<String>, String)> {
+        let parts: Vec<&str> = message.splitn(7, ' ').collect();
+        if parts.len() < 7 || !parts[0].contains(':') {
+            return None;
+        }
+        let severity = match parts[5] {
+            "." | "-" => LogSeverity::Debug,
+            "*" => LogSeverity::Info,
+            "#" => LogSeverity::Warning,
+            _ => return None,
+        };
+        Some((severity, None, parts[6].trim_end().to_string()))
+    }
+
+    /// Structured JSON line of mongod 4.4 and later
+    fn parse_mongo(message: &str) -> Option<(LogSeverity, Option<String>, String)> {
+        let entry: Value = serde_json::from_str(message.trim()).ok()?;
+        let severity = match entry["s"].as_str()? {
+            "F" => LogSeverity::Fatal,
+            "E" => LogSeverity::Error,
+            "W" => LogSeverity::Warning,
+            "I" => LogSeverity::Info,
+            level if level.starts_with('D') => LogSeverity::Debug,
+            _ => return None,
+        };
+        let mut text = entry["msg"].as_str()?.to_string();
+        // Failures put the reason in the attributes
+        if let Some(error) = entry["attr"].get("error") {
+            let error = error
+                .as_str()
+                .map_or_else(|| error.to_string(), str::to_owned);
+            text = format!("{}: {}", text, error);
+        }
+
+        Some((severity, entry["c"].as_str().map(str::to_owned), text))
+    }
+
+    /// Fields of a CSV record, doubled quotes inside a quoted field are one quote
+    fn split_csv(line: &str) -> Vec<String> {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(std::mem::take(&mut field)),
+                _ => field.push(c),
+            }
+        }
+        fields.push(field);
+        fields
+    }
+}
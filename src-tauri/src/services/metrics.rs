@@ -0,0 +1,131 @@
+use crate::services::{DockerService, EventService};
+use crate::types::*;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Label prefix of metrics windows, the stream runs while one is open
+pub const METRICS_WINDOW_PREFIX: &str = "metrics";
+
+// Generation of the sampling loop that runs, an older loop stops at its next sample
+static STREAM_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub struct MetricsService;
+
+impl MetricsService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// One `docker stats` sample of every running managed container, one call per Docker host
+    /// A failing host is skipped, its error is returned when no host could be sampled
+    pub async fn sample(&self, app: &AppHandle) -> Result<Vec<ContainerMetrics>, AppError> {
+        let mut running: BTreeMap<Option<String>, Vec<(String, String)>> = BTreeMap::new();
+        {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            for db in db_map.values().filter(|db| db.is_running()) {
+                running
+                    .entry(db.host.clone())
+                    .or_default()
+                    .push((db.name.clone(), db.id.clone()));
+            }
+        }
+
+        let mut metrics = Vec::new();
+        let mut failure = None;
+        for (host, containers) in running {
+            match self.sample_host(app, host.as_deref(), &containers).await {
+                Ok(samples) => metrics.extend(samples),
+                Err(error) => failure = Some(error),
+            }
+        }
+
+        match failure {
+            Some(error) if metrics.is_empty() => Err(error),
+            _ => Ok(metrics),
+        }
+    }
+
+    async fn sample_host(
+        &self,
+        app: &AppHandle,
+        host: Option<&str>,
+        containers: &[(String, String)],
+    ) -> Result<Vec<ContainerMetrics>, AppError> {
+        let mut args = vec![
+            "stats".to_string(),
+            "--no-stream".to_string(),
+            "--format".to_string(),
+            "{{json .}}".to_string(),
+        ];
+        args.extend(containers.iter().map(|(name, _)| name.clone()));
+
+        // A container stopping between the status check and the sample fails the whole call,
+        // the next tick picks up the new status
+        let output = DockerService::for_host_id(app, host)?
+            .stdout(app, "Failed to read container stats", &args)
+            .await?;
+
+        Ok(Self::parse_stats_output(&output, containers))
+    }
+
+    /// Parse `docker stats` JSON lines, keeping containers known by name
+    pub fn parse_stats_output(output: &str, names: &[(String, String)]) -> Vec<ContainerMetrics> {
+        let mut metrics: Vec<ContainerMetrics> = output
+            .lines()
+            .filter_map(|line| serde_json::from_str::<DockerStatsLine>(line).ok())
+            .filter_map(|line| {
+                names
+                    .iter()
+                    .find(|(name, _)| *name == line.name)
+                    .map(|(_, id)| line.to_metrics(id))
+            })
+            .collect();
+
+        metrics.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        metrics
+    }
+
+    /// Start sampling, replacing the loop already running, it stops once no metrics window is open
+    /// A loop that is about to stop can never leave a newly opened window without samples
+    pub fn start_stream(&self, app: AppHandle) {
+        let generation = STREAM_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+        tauri::async_runtime::spawn(async move {
+            let metrics_service = MetricsService::new();
+            // A failure repeats on every sample, it is reported once until sampling recovers
+            let mut reported = None;
+
+            while STREAM_GENERATION.load(Ordering::SeqCst) == generation
+                && Self::has_metrics_window(&app)
+            {
+                match metrics_service.sample(&app).await {
+                    Ok(metrics) => {
+                        reported = None;
+                        EventService::new().container_metrics(&app, metrics);
+                    }
+                    Err(error) => {
+                        let message = error.to_string();
+                        if reported.as_ref() != Some(&message) {
+                            EventService::new().background_error(
+                                &app,
+                                "metrics",
+                                format!("Metrics sample failed: {}", message),
+                            );
+                            reported = Some(message);
+                        }
+                    }
+                }
+                tokio::time::sleep(Duration::from_secs(METRICS_INTERVAL_SECS)).await;
+            }
+        });
+    }
+
+    fn has_metrics_window(app: &AppHandle) -> bool {
+        app.webview_windows()
+            .keys()
+            .any(|label| label.starts_with(METRICS_WINDOW_PREFIX))
+    }
+}
@@ -0,0 +1,412 @@
+use crate::services::{
+    mongo_login, AlertService, DockerService, EnvFile, EventService, MetricsService, SqlService,
+    StorageService, MONGO_PASSWORD_VAR,
+};
+use crate::types::*;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+// Settings are re-read this often while recording is turned off, alert rules are still
+// checked at this pace
+const DISABLED_RECHECK_SECS: u64 = 60;
+// Describes a failed docker call of this service
+const CONTEXT: &str = "Failed to sample metrics";
+// Samples are held in memory and written to the store this often, and when the app quits
+const FLUSH_INTERVAL_SECS: u64 = 900;
+// `du` walks the whole data directory, its result is reused for this long
+const DISK_SAMPLE_INTERVAL_SECS: u64 = 900;
+
+// Samples not written to the store yet
+static PENDING: LazyLock<Mutex<MetricsHistory>> =
+    LazyLock::new(|| Mutex::new(MetricsHistory::default()));
+static LAST_FLUSH: Mutex<Option<Instant>> = Mutex::new(None);
+// Last data size of each database and when it was measured
+static DISK_SIZES: LazyLock<Mutex<HashMap<String, (Instant, Option<u64>)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+// Failure reported for each source, repeats of it are not reported again until it clears
+static REPORTED: LazyLock<Mutex<HashMap<&'static str, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+// Flushes of the worker and of the app quitting may overlap
+static FLUSH_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Records `docker stats` samples, client connections and data size of running databases
+/// into a compacted history, so trends can be charted over hours and days
+pub struct MetricsHistoryService;
+
+impl MetricsHistoryService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Directories the engine keeps its data in, the volume targets when there are any
+    pub fn data_paths(database: &DatabaseContainer) -> Vec<String> {
+        if let Some(args) = database
+            .stored_docker_args
+            .as_ref()
+            .filter(|args| !args.volumes.is_empty())
+        {
+            return args
+                .volumes
+                .iter()
+                .map(|volume| volume.path.clone())
+                .collect();
+        }
+
+        let path = match database.db_type.as_str() {
+            "PostgreSQL" => "/var/lib/postgresql/data",
+            "MySQL" | "MariaDB" => "/var/lib/mysql",
+            "MongoDB" => "/data/db",
+            "Redis" => "/data",
+            "SQLServer" => "/var/opt/mssql",
+            _ => return Vec::new(),
+        };
+        vec![path.to_string()]
+    }
+
+    /// Variables the connection probe reads its password from, passed with `--env-file`
+    pub fn exec_credentials(database: &DatabaseContainer) -> Vec<(&'static str, String)> {
+        let password = DatabaseCredentials::of(database).password;
+        match database.db_type.as_str() {
+            "Redis" if !password.is_empty() => vec![("REDISCLI_AUTH", password)],
+            "MongoDB" if database.stored_enable_auth => vec![(MONGO_PASSWORD_VAR, password)],
+            _ => SqlService::exec_credentials(database),
+        }
+    }
+
+    /// `docker exec` arguments printing the number of client connections
+    /// Credentials come from `env_file`, written from `exec_credentials`
+    pub fn connections_args(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+    ) -> Option<Vec<String>> {
+        match database.db_type.as_str() {
+            "PostgreSQL" => SqlService::client_args(
+                database,
+                env_file,
+                None,
+                "SELECT count(*) FROM pg_stat_activity WHERE backend_type = 'client backend'",
+            )
+            .ok(),
+            "MySQL" | "MariaDB" => SqlService::client_args(
+                database,
+                env_file,
+                None,
+                "SHOW GLOBAL STATUS LIKE 'Threads_connected'",
+            )
+            .ok(),
+            "Redis" => Some(Self::exec(
+                database,
+                env_file,
+                ["redis-cli", "INFO", "clients"].map(str::to_owned).to_vec(),
+            )),
+            "MongoDB" => {
+                let mut script = String::new();
+                if database.stored_enable_auth {
+                    script.push_str(&mongo_login(&DatabaseCredentials::of(database).username));
+                }
+                script.push_str("db.serverStatus().connections.current");
+                Some(Self::exec(
+                    database,
+                    env_file,
+                    vec![
+                        "mongosh".to_string(),
+                        "--quiet".to_string(),
+                        "--eval".to_string(),
+                        script,
+                    ],
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// `docker exec` arguments running `command` in the database's container
+    fn exec(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+        command: Vec<String>,
+    ) -> Vec<String> {
+        let mut args = vec!["exec".to_string()];
+        if let Some(env_file) = env_file {
+            args.extend([
+                "--env-file".to_string(),
+                env_file.to_string_lossy().to_string(),
+            ]);
+        }
+        args.push(database.name.clone());
+        args.extend(command);
+        args
+    }
+
+    /// Connections in the output of `connections_args`, the probe's own one left out
+    pub fn parse_connections(output: &str) -> Option<u64> {
+        let value = match output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("connected_clients:"))
+        {
+            Some(clients) => clients,
+            // A bare number, or MySQL's `Threads_connected<TAB>3`
+            None => output
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())?
+                .split_whitespace()
+                .last()?,
+        };
+
+        value
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|connections| connections.saturating_sub(1))
+    }
+
+    /// Total of `du -sk` output in bytes
+    pub fn parse_du_output(output: &str) -> Option<u64> {
+        let sizes: Vec<u64> = output
+            .lines()
+            .filter_map(|line| line.split_whitespace().next()?.parse().ok())
+            .collect();
+        (!sizes.is_empty()).then(|| sizes.iter().sum::<u64>() * 1024)
+    }
+
+    /// Spawn the recorder, the interval and retention are re-read from settings every time
+    /// Alert rules are checked on every sample, even with recording turned off
+    pub fn start_worker(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let history_service = MetricsHistoryService::new();
+
+            loop {
+                let interval_secs = {
+                    let settings = app.state::<SettingsStore>();
+                    let settings = settings.lock().unwrap();
+                    settings.metrics_history_interval_secs
+                };
+
+                if interval_secs == 0 {
+                    if AlertService::new().has_metric_rules(&app).await {
+                        let result = history_service.record(&app, false).await;
+                        Self::report(&app, "metrics-alerts", result);
+                    }
+                    tokio::time::sleep(Duration::from_secs(DISABLED_RECHECK_SECS)).await;
+                    continue;
+                }
+
+                let result = history_service.record(&app, true).await;
+                Self::report(&app, "metrics-history", result);
+                tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+            }
+        });
+    }
+
+    // Failures come back on every sample, each is reported once until a sample succeeds
+    fn report(app: &AppHandle, source: &'static str, result: Result<(), AppError>) {
+        let mut reported = REPORTED.lock().unwrap();
+        match result {
+            Ok(()) => {
+                reported.remove(source);
+            }
+            Err(error) => {
+                let message = error.to_string();
+                if reported.get(source) != Some(&message) {
+                    EventService::new().background_error(app, source, message.clone());
+                    reported.insert(source, message);
+                }
+            }
+        }
+    }
+
+    /// Sample every running database once, check the alert rules against the samples and add
+    /// them to the history when `persist` is set
+    /// The history is written to the store every `FLUSH_INTERVAL_SECS`
+    pub async fn record(&self, app: &AppHandle, persist: bool) -> Result<(), AppError> {
+        let samples = MetricsService::new().sample(app).await?;
+
+        let mut points = Vec::new();
+        let mut readings = Vec::new();
+        for sample in samples {
+            let database = {
+                let databases = app.state::<DatabaseStore>();
+                let db_map = databases.read().await;
+                db_map.get(&sample.id).cloned()
+            };
+            let Some(database) = database else {
+                continue;
+            };
+            let Ok(docker_service) = DockerService::for_database(app, &database) else {
+                continue;
+            };
+
+            // Kept until the probe returns, a probe without it fails like a busy database
+            let env_file = EnvFile::for_credentials(&Self::exec_credentials(&database))
+                .ok()
+                .flatten();
+            // A database still starting or busy only misses these two values
+            let connections =
+                match Self::connections_args(&database, env_file.as_ref().map(EnvFile::path)) {
+                    Some(args) => docker_service
+                        .stdout(app, CONTEXT, &args)
+                        .await
+                        .ok()
+                        .as_deref()
+                        .and_then(Self::parse_connections),
+                    None => None,
+                };
+            let disk_bytes = self.disk_bytes(app, &docker_service, &database).await;
+
+            readings.push((
+                database,
+                AlertReading {
+                    cpu_percent: sample.cpu_percent,
+                    memory_percent: sample.memory_percent,
+                    connections,
+                    disk_bytes,
+                },
+            ));
+            points.push((
+                sample.id.clone(),
+                MetricsPoint {
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    cpu_percent: sample.cpu_percent,
+                    memory_used_bytes: sample.memory_used_bytes,
+                    connections,
+                    disk_bytes,
+                    samples: 1,
+                },
+            ));
+        }
+
+        let alerts = AlertService::new().evaluate_metrics(app, &readings).await;
+        Self::report(app, "metrics-alerts", alerts);
+        if !persist {
+            return Ok(());
+        }
+
+        {
+            let mut pending = PENDING.lock().unwrap();
+            for (id, point) in points {
+                pending.record(&id, point);
+            }
+        }
+        let due = {
+            let mut last_flush = LAST_FLUSH.lock().unwrap();
+            let last = *last_flush.get_or_insert_with(Instant::now);
+            last.elapsed() >= Duration::from_secs(FLUSH_INTERVAL_SECS)
+        };
+        if due {
+            self.flush(app).await?;
+        }
+        Ok(())
+    }
+
+    /// Size of the data directories, measured again once the last size is old enough
+    async fn disk_bytes(
+        &self,
+        app: &AppHandle,
+        docker_service: &DockerService,
+        database: &DatabaseContainer,
+    ) -> Option<u64> {
+        let cached = DISK_SIZES.lock().unwrap().get(&database.id).copied();
+        if let Some((measured_at, size)) = cached {
+            if measured_at.elapsed() < Duration::from_secs(DISK_SAMPLE_INTERVAL_SECS) {
+                return size;
+            }
+        }
+
+        let data_paths = Self::data_paths(database);
+        if data_paths.is_empty() {
+            return None;
+        }
+        let mut args = vec![
+            "exec".to_string(),
+            database.name.clone(),
+            "du".to_string(),
+            "-sk".to_string(),
+        ];
+        args.extend(data_paths);
+        let size = docker_service
+            .stdout(app, CONTEXT, &args)
+            .await
+            .ok()
+            .as_deref()
+            .and_then(Self::parse_du_output);
+        DISK_SIZES
+            .lock()
+            .unwrap()
+            .insert(database.id.clone(), (Instant::now(), size));
+        size
+    }
+
+    /// Write the samples held in memory to the store and compact it
+    /// Samples stay in memory when the store cannot be written, for the next flush
+    pub async fn flush(&self, app: &AppHandle) -> Result<(), AppError> {
+        let _lock = FLUSH_LOCK.lock().await;
+        *LAST_FLUSH.lock().unwrap() = Some(Instant::now());
+        let pending = std::mem::take(&mut *PENDING.lock().unwrap());
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let retention_days = {
+            let settings = app.state::<SettingsStore>();
+            let settings = settings.lock().unwrap();
+            settings.metrics_history_retention_days
+        };
+
+        let storage_service = StorageService::new();
+        let saved = async {
+            let mut history = storage_service.load_metrics_history_from_store(app).await?;
+            history.merge(pending.clone());
+            history.compact(chrono::Utc::now(), retention_days);
+            storage_service
+                .save_metrics_history_to_store(app, &history)
+                .await
+        }
+        .await;
+
+        if saved.is_err() {
+            // Samples taken meanwhile are newer
+            let mut current = PENDING.lock().unwrap();
+            let newer = std::mem::replace(&mut *current, pending);
+            current.merge(newer);
+        }
+        saved
+    }
+
+    /// Recorded points of a database, averaged per `bucket_secs` when set
+    pub async fn query(
+        &self,
+        app: &AppHandle,
+        database_id: &str,
+        since: Option<&str>,
+        until: Option<&str>,
+        bucket_secs: Option<i64>,
+    ) -> Result<Vec<MetricsPoint>, AppError> {
+        let parse = |value: Option<&str>, name: &str| {
+            value
+                .map(|value| {
+                    chrono::DateTime::parse_from_rfc3339(value)
+                        .map(|time| time.with_timezone(&chrono::Utc))
+                        .map_err(|_| {
+                            AppError::Validation(format!("{} must be an RFC 3339 time", name))
+                        })
+                })
+                .transpose()
+        };
+        let since = parse(since, "since")?;
+        let until = parse(until, "until")?;
+        if bucket_secs.is_some_and(|bucket_secs| bucket_secs <= 0) {
+            return Err(AppError::Validation(
+                "Bucket size must be positive".to_string(),
+            ));
+        }
+
+        let mut history = StorageService::new()
+            .load_metrics_history_from_store(app)
+            .await?;
+        history.merge(PENDING.lock().unwrap().clone());
+        Ok(history.query(database_id, since, until, bucket_secs))
+    }
+}
@@ -1,5 +1,109 @@
+pub mod alert;
+pub mod autostart;
+pub mod benchmark;
+pub mod bundle;
+pub mod client;
+pub mod companion;
+pub mod config;
+pub mod connection;
+pub mod credentials;
+pub mod csv;
+pub mod data_dir;
+pub mod deep_link;
 pub mod docker;
+pub mod docker_cli;
+pub mod env_drift;
+pub mod env_file;
+pub mod events;
+pub mod exec_history;
+pub mod file_copy;
+pub mod graceful_stop;
+pub mod group;
+pub mod image_cleanup;
+pub mod image_update;
+pub mod integrity;
+pub mod kubernetes;
+pub mod local_store;
+pub mod log_parser;
+pub mod metrics;
+pub mod metrics_history;
+pub mod notification;
+pub mod orphan;
+pub mod readiness;
+pub mod recovery;
+pub mod replication;
+pub mod scheduler;
+pub mod secrets;
+pub mod share;
+pub mod shutdown;
+pub mod slow_log;
+pub mod snapshot;
+pub mod snippet;
+pub mod sql;
+pub mod sql_job;
 pub mod storage;
+pub mod storage_breakdown;
+pub mod sync;
+pub mod terminal;
+pub mod tls;
+pub mod trash;
+pub mod tray;
+pub mod tunnel;
+pub mod uptime;
+pub mod watchdog;
+pub mod window;
 
+pub use alert::*;
+pub use autostart::*;
+pub use benchmark::*;
+pub use bundle::*;
+pub use client::*;
+pub use companion::*;
+pub use config::*;
+pub use connection::*;
+pub use credentials::*;
+pub use csv::*;
+pub use data_dir::*;
+pub use deep_link::*;
 pub use docker::*;
+pub use docker_cli::*;
+pub use env_drift::*;
+pub use env_file::*;
+pub use events::*;
+pub use exec_history::*;
+pub use file_copy::*;
+pub use graceful_stop::*;
+pub use group::*;
+pub use image_cleanup::*;
+pub use image_update::*;
+pub use integrity::*;
+pub use kubernetes::*;
+pub use local_store::*;
+pub use log_parser::*;
+pub use metrics::*;
+pub use metrics_history::*;
+pub use notification::*;
+pub use orphan::*;
+pub use readiness::*;
+pub use recovery::*;
+pub use replication::*;
+pub use scheduler::*;
+pub use secrets::*;
+pub use share::*;
+pub use shutdown::*;
+pub use slow_log::*;
+pub use snapshot::*;
+pub use snippet::*;
+pub use sql::*;
+pub use sql_job::*;
 pub use storage::*;
+pub use storage_breakdown::*;
+pub use sync::*;
+pub use terminal::*;
+pub use tls::*;
+pub use trash::*;
+pub use tray::*;
+pub use tunnel::*;
+pub use uptime::*;
+pub use watchdog::*;
+pub use window::*;
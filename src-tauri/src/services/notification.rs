@@ -0,0 +1,173 @@
+use crate::services::EventService;
+use crate::types::*;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+// Restarts within this window count towards a restart loop
+const RESTART_WINDOW_MINUTES: i64 = 10;
+const RESTART_LOOP_COUNT: usize = 3;
+
+static MONITOR: LazyLock<Mutex<HealthMonitor>> =
+    LazyLock::new(|| Mutex::new(HealthMonitor::default()));
+
+/// Turns successive Docker states of a container into alerts
+#[derive(Debug, Default)]
+pub struct HealthMonitor {
+    last: HashMap<String, DockerContainerState>,
+    restarts: HashMap<String, Vec<DateTime<Utc>>>,
+}
+
+impl HealthMonitor {
+    /// Record the latest state of a database, `was_running` is its status before this sync
+    pub fn observe(
+        &mut self,
+        database_id: &str,
+        state: &DockerContainerState,
+        was_running: bool,
+        now: DateTime<Utc>,
+    ) -> Option<ContainerAlert> {
+        let previous = self.last.insert(database_id.to_string(), state.clone());
+        let previous_health = previous.as_ref().and_then(|p| p.health.as_deref());
+        let was_restarting = previous.as_ref().is_some_and(|p| p.restarting);
+
+        // A restart between two syncs only shows in the restart count
+        let missed_restart = !state.restarting
+            && !was_restarting
+            && previous
+                .as_ref()
+                .is_some_and(|p| state.restart_count > p.restart_count);
+
+        if (state.restarting && !was_restarting) || missed_restart {
+            let restarts = self.restarts.entry(database_id.to_string()).or_default();
+            restarts.retain(|at| now - *at < Duration::minutes(RESTART_WINDOW_MINUTES));
+            restarts.push(now);
+
+            // Only once per loop, the count keeps growing while it lasts
+            if restarts.len() == RESTART_LOOP_COUNT {
+                return Some(ContainerAlert::RestartLoop);
+            }
+            return None;
+        }
+
+        if was_running && !state.running && !state.restarting {
+            return match state.exit_code {
+                Some(exit_code) if exit_code != 0 => Some(ContainerAlert::Died { exit_code }),
+                _ => None,
+            };
+        }
+
+        if state.health.as_deref() == Some("unhealthy") && previous_health != Some("unhealthy") {
+            return Some(ContainerAlert::Unhealthy);
+        }
+
+        None
+    }
+}
+
+/// Sends desktop notifications about managed containers
+pub struct NotificationService;
+
+impl NotificationService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Compare a sync result with the previous statuses and notify about new problems
+    pub fn check_health(
+        &self,
+        app: &AppHandle,
+        previous: &HashMap<String, DatabaseContainer>,
+        states: &HashMap<String, DockerContainerState>,
+    ) {
+        let (enabled, muted) = {
+            let settings = app.state::<SettingsStore>();
+            let settings = settings.lock().unwrap();
+            (
+                settings.notifications_enabled,
+                settings.muted_containers.clone(),
+            )
+        };
+
+        let now = Utc::now();
+        let alerts: Vec<(String, ContainerAlert)> = {
+            let mut monitor = MONITOR.lock().unwrap();
+            previous
+                .values()
+                .filter_map(|db| {
+                    let state = states.get(&db.name)?;
+                    let alert = monitor.observe(&db.id, state, db.is_running(), now)?;
+                    Some((db.id.clone(), alert))
+                })
+                .collect()
+        };
+
+        // The monitor keeps observing while muted so unmuting does not replay old alerts
+        if !enabled {
+            return;
+        }
+
+        for (database_id, alert) in alerts {
+            if muted.contains(&database_id) {
+                continue;
+            }
+            if let Some(db) = previous.get(&database_id) {
+                let _ = app
+                    .notification()
+                    .builder()
+                    .title(alert.title(&db.name))
+                    .body(alert.body())
+                    .show();
+            }
+        }
+    }
+
+    /// Tell about a failed run of a SQL job, unless notifications are off or the container muted
+    pub fn sql_job_failed(
+        &self,
+        app: &AppHandle,
+        job: &SqlJob,
+        database: &DatabaseContainer,
+        error: &str,
+    ) {
+        let (enabled, muted) = {
+            let settings = app.state::<SettingsStore>();
+            let settings = settings.lock().unwrap();
+            (
+                settings.notifications_enabled,
+                settings.muted_containers.contains(&database.id),
+            )
+        };
+        if !enabled || muted {
+            return;
+        }
+
+        let _ = app
+            .notification()
+            .builder()
+            .title(format!("{}: job '{}' failed", database.name, job.name))
+            .body(error.lines().next().unwrap_or(error))
+            .show();
+    }
+
+    /// Tell about a failure while the app starts, before the window listens for events
+    /// The event still reaches a window that is already up
+    pub fn startup_failed(&self, app: &AppHandle, source: &str, message: String) {
+        let enabled = app
+            .state::<SettingsStore>()
+            .lock()
+            .unwrap()
+            .notifications_enabled;
+        if enabled {
+            let _ = app
+                .notification()
+                .builder()
+                .title("Docker DB Manager")
+                .body(&message)
+                .show();
+        }
+        EventService::new().background_error(app, source, message);
+    }
+}
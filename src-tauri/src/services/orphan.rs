@@ -0,0 +1,185 @@
+use crate::services::{DockerService, EventService, StorageService};
+use crate::types::*;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+// Names of the containers without a store entry at the last sync, orphans are only looked
+// for again when that set changes
+static UNKNOWN_NAMES: Mutex<Option<BTreeSet<String>>> = Mutex::new(None);
+
+// Describes a failed docker call of this service
+const CONTEXT: &str = "Failed to look for orphans";
+
+/// Finds labelled database containers missing from the store and resolves them
+/// Only the local daemon is searched, a remote host may run containers of other machines
+pub struct OrphanService;
+
+impl OrphanService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Labelled containers no store entry of the active workspace covers
+    pub async fn find(&self, app: &AppHandle) -> Result<Vec<OrphanContainer>, AppError> {
+        let output = DockerService::new()
+            .stdout(
+                app,
+                CONTEXT,
+                &[
+                    "ps",
+                    "-a",
+                    "--filter",
+                    &format!("label={}", DATABASE_ID_LABEL),
+                    "--format",
+                    &OrphanContainer::ps_format(),
+                ],
+            )
+            .await?;
+
+        let known_names: Vec<String> = {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            db_map
+                .values()
+                .filter(|db| db.host.is_none())
+                .map(|db| db.name.clone())
+                .collect()
+        };
+        let (active_workspace, known_workspaces) = {
+            let workspaces = app.state::<WorkspaceStore>();
+            let workspaces = workspaces.lock().unwrap();
+            let known: Vec<String> = workspaces.workspaces.iter().map(|w| w.id.clone()).collect();
+            (workspaces.active.clone(), known)
+        };
+
+        Ok(output
+            .lines()
+            .filter_map(OrphanContainer::from_ps_line)
+            .filter(|orphan| orphan.is_orphan(&known_names, &active_workspace, &known_workspaces))
+            .collect())
+    }
+
+    /// Called on every sync, announces orphans when containers unknown to the store changed
+    /// `docker_names` are all container names Docker reports
+    pub async fn detect(&self, app: &AppHandle, docker_names: &[String]) -> Result<(), AppError> {
+        let unknown: BTreeSet<String> = {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            docker_names
+                .iter()
+                .filter(|name| {
+                    !db_map
+                        .values()
+                        .any(|db| db.host.is_none() && &db.name == *name)
+                })
+                .cloned()
+                .collect()
+        };
+
+        {
+            let mut last = UNKNOWN_NAMES.lock().unwrap();
+            if last.as_ref() == Some(&unknown) {
+                return Ok(());
+            }
+            *last = Some(unknown);
+        }
+
+        let orphans = self.find(app).await.inspect_err(|_| {
+            // Looked for again on the next sync
+            *UNKNOWN_NAMES.lock().unwrap() = None;
+        })?;
+        EventService::new().orphans_detected(app, orphans);
+
+        Ok(())
+    }
+
+    /// Adopt or remove each named orphan, one result per action
+    pub async fn reconcile(&self, app: &AppHandle, actions: Vec<OrphanAction>) -> Vec<BulkResult> {
+        let orphans = match self.find(app).await {
+            Ok(orphans) => orphans,
+            Err(error) => {
+                return actions
+                    .into_iter()
+                    .map(|action| BulkResult::from_result::<(), _>(action.name, Err(&error)))
+                    .collect();
+            }
+        };
+
+        let mut results = Vec::new();
+        for action in actions {
+            let result = match orphans.iter().find(|orphan| orphan.name == action.name) {
+                Some(orphan) => match action.resolution {
+                    OrphanResolution::Adopt => self.adopt(app, orphan).await.map(|_| ()),
+                    OrphanResolution::Remove => {
+                        DockerService::new()
+                            .remove_container(app, &orphan.container_id)
+                            .await
+                    }
+                },
+                None => Err(AppError::NotFound(format!(
+                    "Orphan container '{}'",
+                    action.name
+                ))),
+            };
+            results.push(BulkResult::from_result(action.name, result));
+        }
+
+        // The next sync reports whatever is left
+        *UNKNOWN_NAMES.lock().unwrap() = None;
+        results
+    }
+
+    /// Add an orphan to the store, rebuilt from its container and image
+    async fn adopt(
+        &self,
+        app: &AppHandle,
+        orphan: &OrphanContainer,
+    ) -> Result<DatabaseContainer, AppError> {
+        let container = self
+            .inspect(app, &["container", "inspect", &orphan.container_id])
+            .await?;
+        let image_id = container["Image"].as_str().unwrap_or_default().to_string();
+        // A removed image only costs the filtering of its defaults
+        let image = self
+            .inspect(app, &["image", "inspect", &image_id])
+            .await
+            .unwrap_or_default();
+
+        let databases = app.state::<DatabaseStore>();
+        let database = {
+            let mut db_map = databases.write().await;
+            // The label keeps the old id, a store that reused it gets a new one
+            let database_id = if db_map.contains_key(&orphan.labels.database_id) {
+                uuid::Uuid::new_v4().to_string()
+            } else {
+                orphan.labels.database_id.clone()
+            };
+            let database = adopted_database(orphan, &container, &image, database_id);
+            db_map.insert(database.id.clone(), database.clone());
+            database
+        };
+
+        if let Err(error) = StorageService::new()
+            .upsert_databases_in_store(app, std::slice::from_ref(&database))
+            .await
+        {
+            databases.write().await.remove(&database.id);
+            return Err(error);
+        }
+        EventService::new().container_created(app, &database);
+
+        Ok(database)
+    }
+
+    async fn inspect(&self, app: &AppHandle, args: &[&str]) -> Result<serde_json::Value, AppError> {
+        let output = DockerService::new().stdout(app, CONTEXT, args).await?;
+        let mut entries: Vec<serde_json::Value> = serde_json::from_str(output.trim())
+            .map_err(|e| AppError::docker(format!("Unexpected docker inspect output: {}", e)))?;
+
+        if entries.is_empty() {
+            return Err(AppError::NotFound(args[args.len() - 1].to_string()));
+        }
+        Ok(entries.swap_remove(0))
+    }
+}
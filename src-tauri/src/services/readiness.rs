@@ -0,0 +1,175 @@
+use crate::services::{DockerService, EnvFile};
+use crate::types::*;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+// Delay between two readiness probes
+const PROBE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Waits for a freshly started database to accept connections
+pub struct ReadinessService;
+
+impl ReadinessService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// How long an engine may take to initialize, SQL Server is the slowest to come up
+    pub fn timeout(db_type: &str) -> Duration {
+        match db_type {
+            "SQLServer" => Duration::from_secs(180),
+            _ => Duration::from_secs(90),
+        }
+    }
+
+    /// Variables the probe reads its password from, passed with `--env-file`
+    pub fn exec_credentials(database: &DatabaseContainer) -> Vec<(&'static str, String)> {
+        let password = database.stored_password.clone().unwrap_or_default();
+        let key = match database.db_type.as_str() {
+            "MySQL" | "MariaDB" => "MYSQL_PWD",
+            "Redis" if !password.is_empty() => "REDISCLI_AUTH",
+            "SQLServer" => "SQLCMDPASSWORD",
+            _ => return Vec::new(),
+        };
+        vec![(key, password)]
+    }
+
+    /// `docker` arguments of the engine's readiness probe, `None` when the engine has none
+    /// Probes go through TCP, init scripts run against a server that only listens on the socket
+    /// Credentials come from `env_file`, written from `exec_credentials`
+    pub fn probe_args(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+    ) -> Option<Vec<String>> {
+        let user = database.stored_username.clone();
+
+        let probe: Vec<&str> = match database.db_type.as_str() {
+            "PostgreSQL" => vec![
+                "pg_isready",
+                "-h",
+                "127.0.0.1",
+                "-U",
+                user.as_deref().unwrap_or("postgres"),
+            ],
+            "MySQL" => vec![
+                "mysqladmin",
+                "ping",
+                "-h",
+                "127.0.0.1",
+                "-u",
+                "root",
+                "--silent",
+            ],
+            "MariaDB" => vec![
+                "mariadb-admin",
+                "ping",
+                "-h",
+                "127.0.0.1",
+                "-u",
+                "root",
+                "--silent",
+            ],
+            "Redis" => vec!["redis-cli", "-h", "127.0.0.1", "ping"],
+            // ping does not need authentication
+            "MongoDB" => vec!["mongosh", "--quiet", "--eval", "db.adminCommand('ping').ok"],
+            "SQLServer" => vec![
+                "/opt/mssql-tools18/bin/sqlcmd",
+                "-S",
+                "localhost",
+                "-U",
+                "sa",
+                "-C",
+                "-Q",
+                "SELECT 1",
+            ],
+            _ => return None,
+        };
+
+        Some(Self::exec_args(database, env_file, &probe))
+    }
+
+    fn exec_args(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+        probe: &[&str],
+    ) -> Vec<String> {
+        let mut args = vec!["exec".to_string()];
+        if let Some(env_file) = env_file {
+            args.extend([
+                "--env-file".to_string(),
+                env_file.to_string_lossy().to_string(),
+            ]);
+        }
+        args.push(database.name.clone());
+        args.extend(probe.iter().map(|arg| arg.to_string()));
+        args
+    }
+
+    /// Whether a probe's output means the server accepts connections
+    pub fn probe_succeeded(db_type: &str, exit_code: Option<i32>, stdout: &str) -> bool {
+        if exit_code != Some(0) {
+            return false;
+        }
+
+        match db_type {
+            "Redis" => stdout.trim() == "PONG",
+            "MongoDB" => stdout.trim() == "1",
+            _ => true,
+        }
+    }
+
+    /// Probe until the database accepts connections, the container exits or the timeout passes
+    pub async fn wait_until_ready(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<(), AppError> {
+        // Kept for every probe, Docker reads it when each exec starts
+        let env_file = EnvFile::for_credentials(&Self::exec_credentials(database))?;
+        let Some(args) = Self::probe_args(database, env_file.as_ref().map(EnvFile::path)) else {
+            return Ok(());
+        };
+
+        let docker_service = DockerService::for_database(app, database)?;
+        let timeout = Self::timeout(&database.db_type);
+        let started = Instant::now();
+        let mut last_error = String::new();
+
+        while started.elapsed() < timeout {
+            let output = docker_service
+                .output(app, "Failed to probe database", &args)
+                .await?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if Self::probe_succeeded(&database.db_type, output.status.code(), &stdout) {
+                return Ok(());
+            }
+            last_error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+            // A container that exits while initializing will never become ready
+            let running = docker_service
+                .inspect_containers(app, std::slice::from_ref(&database.name))
+                .await?
+                .get(&database.name)
+                .is_some_and(|state| state.running);
+            if !running {
+                return Err(AppError::Docker {
+                    message: format!("{} exited while starting", database.name),
+                    details: (!last_error.is_empty()).then_some(last_error),
+                });
+            }
+
+            tokio::time::sleep(PROBE_INTERVAL).await;
+        }
+
+        Err(AppError::Docker {
+            message: format!(
+                "{} did not accept connections within {} seconds",
+                database.name,
+                timeout.as_secs()
+            ),
+            details: (!last_error.is_empty()).then_some(last_error),
+        })
+    }
+}
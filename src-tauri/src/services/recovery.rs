@@ -0,0 +1,141 @@
+use crate::services::{DockerService, EventService, StorageService};
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Manager};
+
+// Creations run concurrently, the pending list is read and written back as a whole
+static PENDING_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Records creations in progress and undoes the ones a crash left half done
+pub struct RecoveryService;
+
+impl RecoveryService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Record a creation before any of its resources exist
+    pub async fn begin_create(
+        &self,
+        app: &AppHandle,
+        pending: PendingCreate,
+    ) -> Result<(), AppError> {
+        let _lock = PENDING_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut all = storage_service.load_pending_creates_from_store(app).await?;
+        all.retain(|existing| existing.database_id != pending.database_id);
+        all.push(pending);
+        storage_service
+            .save_pending_creates_to_store(app, &all)
+            .await
+    }
+
+    /// Forget a creation once its entry is saved or its resources were cleaned up
+    pub async fn finish_create(&self, app: &AppHandle, database_id: &str) -> Result<(), AppError> {
+        let _lock = PENDING_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut all = storage_service.load_pending_creates_from_store(app).await?;
+        let before = all.len();
+        all.retain(|existing| existing.database_id != database_id);
+        if all.len() == before {
+            return Ok(());
+        }
+        storage_service
+            .save_pending_creates_to_store(app, &all)
+            .await
+    }
+
+    /// Undo the creations the last session left pending, called at launch
+    /// `recovery-completed` reports what was removed, leftovers that fail are kept for next time
+    pub async fn recover(&self, app: &AppHandle) -> Result<Vec<RecoveredCreate>, AppError> {
+        let pending = StorageService::new()
+            .load_pending_creates_from_store(app)
+            .await?;
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Containers and volumes of each host, listed once
+        let mut listed: HashMap<Option<String>, (HashSet<String>, HashSet<String>)> =
+            HashMap::new();
+        let mut recovered = Vec::new();
+        for create in pending {
+            if !listed.contains_key(&create.host) {
+                let docker_service = DockerService::for_host_id(app, create.host.as_deref())?;
+                let container_names: HashSet<String> = docker_service
+                    .list_container_states(app)
+                    .await?
+                    .into_keys()
+                    .collect();
+                let volumes = docker_service.list_volumes(app).await?;
+                listed.insert(create.host.clone(), (container_names, volumes));
+            }
+            let (container_names, volumes) = &listed[&create.host];
+            let plan = {
+                let databases = app.state::<DatabaseStore>();
+                let db_map = databases.read().await;
+                create.recovery_plan(&db_map, container_names, volumes)
+            };
+            if plan.is_empty() {
+                self.finish_create(app, &create.database_id).await?;
+                continue;
+            }
+
+            let mut report = RecoveredCreate {
+                database_id: create.database_id.clone(),
+                name: create.name.clone(),
+                removed_container: false,
+                removed_entry: false,
+                removed_volumes: Vec::new(),
+                error: None,
+            };
+            match self.undo(app, &create, &plan, &mut report).await {
+                Ok(()) => self.finish_create(app, &create.database_id).await?,
+                Err(error) => report.error = Some(error.to_string()),
+            }
+            recovered.push(report);
+        }
+
+        if !recovered.is_empty() {
+            DockerService::invalidate_cache();
+            EventService::new().recovery_completed(app, recovered.clone());
+        }
+        Ok(recovered)
+    }
+
+    /// Remove the leftovers of one creation, recording each step in `report`
+    async fn undo(
+        &self,
+        app: &AppHandle,
+        create: &PendingCreate,
+        plan: &RecoveryPlan,
+        report: &mut RecoveredCreate,
+    ) -> Result<(), AppError> {
+        let docker_service = DockerService::for_host_id(app, create.host.as_deref())?;
+
+        if plan.remove_container {
+            docker_service
+                .force_remove_container_by_name(app, &create.name)
+                .await?;
+            report.removed_container = true;
+        }
+
+        if plan.remove_entry {
+            let databases = app.state::<DatabaseStore>();
+            StorageService::new()
+                .delete_databases_from_store(app, std::slice::from_ref(&create.database_id))
+                .await?;
+            databases.write().await.remove(&create.database_id);
+            EventService::new().container_removed(app, &create.database_id);
+            report.removed_entry = true;
+        }
+
+        // Volumes go last, Docker refuses to remove one a container still uses
+        for volume in &plan.remove_volumes {
+            docker_service.remove_volume_if_exists(app, volume).await?;
+            report.removed_volumes.push(volume.clone());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use crate::services::{DockerService, GracefulStopService, ReadinessService, SqlService};
+use crate::types::*;
+use tauri::AppHandle;
+
+const STATUS_SQL: &str = "SELECT json_build_object(\
+'walLevel', current_setting('wal_level'), \
+'maxReplicationSlots', current_setting('max_replication_slots')::int, \
+'maxWalSenders', current_setting('max_wal_senders')::int, \
+'pendingRestart', EXISTS (SELECT 1 FROM pg_settings WHERE pending_restart \
+AND name IN ('wal_level', 'max_replication_slots', 'max_wal_senders')))";
+
+const PUBLICATIONS_SQL: &str = "SELECT json_build_object(\
+'name', p.pubname, \
+'allTables', p.puballtables, \
+'tables', CASE WHEN p.puballtables THEN '[]'::json ELSE COALESCE(\
+(SELECT json_agg(t.schemaname || '.' || t.tablename ORDER BY t.schemaname, t.tablename) \
+FROM pg_publication_tables t WHERE t.pubname = p.pubname), '[]'::json) END) \
+FROM pg_publication p ORDER BY p.pubname";
+
+const SLOTS_SQL: &str = "SELECT json_build_object(\
+'name', slot_name, \
+'plugin', plugin, \
+'slotType', slot_type, \
+'database', database, \
+'active', active, \
+'restartLsn', restart_lsn::text, \
+'confirmedFlushLsn', confirmed_flush_lsn::text, \
+'retainedWalBytes', pg_wal_lsn_diff(pg_current_wal_lsn(), restart_lsn)::bigint) \
+FROM pg_replication_slots ORDER BY slot_name";
+
+/// Logical replication of PostgreSQL databases, for trying CDC tools such as Debezium locally
+pub struct ReplicationService;
+
+impl ReplicationService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn ensure_postgres(database: &DatabaseContainer) -> Result<(), AppError> {
+        if database.db_type != "PostgreSQL" {
+            return Err(AppError::Validation(
+                "Logical replication is only available for PostgreSQL".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn query<T: serde::de::DeserializeOwned>(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        sql: &str,
+    ) -> Result<Vec<T>, AppError> {
+        Self::ensure_postgres(database)?;
+        SqlService::new()
+            .query_lines(app, database, sql)
+            .await?
+            .iter()
+            .map(|line| {
+                serde_json::from_str(line).map_err(|e| {
+                    AppError::Internal(format!("Unexpected output from PostgreSQL: {}", e))
+                })
+            })
+            .collect()
+    }
+
+    pub async fn status(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<LogicalReplicationStatus, AppError> {
+        self.query(app, database, STATUS_SQL)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AppError::Internal("PostgreSQL returned no replication settings".to_string())
+            })
+    }
+
+    /// Write the replication settings with `ALTER SYSTEM` and restart the server to apply them
+    /// They live in the data directory, so they last as long as the data does
+    pub async fn configure(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        request: &LogicalReplicationRequest,
+    ) -> Result<LogicalReplicationStatus, AppError> {
+        Self::ensure_postgres(database)?;
+        let statements = request.alter_system_sql()?;
+
+        // PostgreSQL refuses to start with a logical slot below wal_level=logical
+        if !request.enabled {
+            let slots = self.slots(app, database).await?;
+            if let Some(slot) = slots.iter().find(|slot| slot.slot_type == "logical") {
+                return Err(AppError::Validation(format!(
+                    "Drop the logical replication slot '{}' before turning logical replication off",
+                    slot.name
+                )));
+            }
+        }
+
+        let sql_service = SqlService::new();
+        // ALTER SYSTEM cannot run inside the transaction of a multi-statement command
+        for statement in &statements {
+            sql_service.query_lines(app, database, statement).await?;
+        }
+
+        let container_id = database
+            .container_id
+            .as_ref()
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+        GracefulStopService::new().stop(app, database).await?;
+        DockerService::for_database(app, database)?
+            .start_container(app, container_id)
+            .await?;
+        ReadinessService::new()
+            .wait_until_ready(app, database)
+            .await?;
+
+        self.status(app, database).await
+    }
+
+    pub async fn publications(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<Vec<Publication>, AppError> {
+        self.query(app, database, PUBLICATIONS_SQL).await
+    }
+
+    /// Publish `tables`, every table of the database when empty
+    pub async fn create_publication(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        name: &str,
+        tables: &[String],
+    ) -> Result<Vec<Publication>, AppError> {
+        Self::ensure_postgres(database)?;
+        let sql = create_publication_sql(name, tables)?;
+        SqlService::new().query_lines(app, database, &sql).await?;
+        self.publications(app, database).await
+    }
+
+    pub async fn drop_publication(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        name: &str,
+    ) -> Result<Vec<Publication>, AppError> {
+        Self::ensure_postgres(database)?;
+        validate_replication_name(name, "Publication")?;
+        let sql = format!(
+            "DROP PUBLICATION {}",
+            SqlDialect::Postgres.quote_identifier(name)
+        );
+        SqlService::new().query_lines(app, database, &sql).await?;
+        self.publications(app, database).await
+    }
+
+    pub async fn slots(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<Vec<ReplicationSlot>, AppError> {
+        self.query(app, database, SLOTS_SQL).await
+    }
+
+    /// Create a logical slot with `plugin`, pgoutput when none is given
+    pub async fn create_slot(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        name: &str,
+        plugin: Option<&str>,
+    ) -> Result<Vec<ReplicationSlot>, AppError> {
+        Self::ensure_postgres(database)?;
+        validate_replication_name(name, "Replication slot")?;
+        let plugin = plugin
+            .map(str::trim)
+            .filter(|plugin| !plugin.is_empty())
+            .unwrap_or("pgoutput");
+
+        let status = self.status(app, database).await?;
+        if !status.enabled() {
+            return Err(AppError::Validation(
+                "Turn on logical replication before creating a slot".to_string(),
+            ));
+        }
+
+        let dialect = SqlDialect::Postgres;
+        let sql = format!(
+            "SELECT pg_create_logical_replication_slot({}, {})",
+            dialect.quote_literal(name),
+            dialect.quote_literal(plugin)
+        );
+        SqlService::new().query_lines(app, database, &sql).await?;
+        self.slots(app, database).await
+    }
+
+    /// Drop a slot, PostgreSQL refuses while a consumer is connected to it
+    pub async fn drop_slot(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        name: &str,
+    ) -> Result<Vec<ReplicationSlot>, AppError> {
+        Self::ensure_postgres(database)?;
+        validate_replication_name(name, "Replication slot")?;
+        let sql = format!(
+            "SELECT pg_drop_replication_slot({})",
+            SqlDialect::Postgres.quote_literal(name)
+        );
+        SqlService::new().query_lines(app, database, &sql).await?;
+        self.slots(app, database).await
+    }
+}
@@ -0,0 +1,297 @@
+use crate::services::{
+    DockerService, EventService, GracefulStopService, IntegrityService, SqlJobService,
+    StorageService,
+};
+use crate::types::*;
+use chrono::{DateTime, Duration, Local, TimeZone};
+use croner::Cron;
+use tauri::{AppHandle, Manager};
+
+// Seconds between schedule checks
+const SCHEDULER_TICK_SECS: u64 = 30;
+// An occurrence missed by more than this, e.g. while the app was closed, is skipped
+const MISSED_SCHEDULE_GRACE_MINUTES: i64 = 30;
+// Occurrences older than this are never looked at
+const MAX_CATCH_UP_DAYS: i64 = 7;
+
+// Edits and recorded runs may overlap, the schedule list is read and written back as a whole
+static SCHEDULES_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Runs container schedules and SQL jobs in the background
+pub struct SchedulerService;
+
+impl SchedulerService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn parse(cron: &str) -> Result<Cron, AppError> {
+        Cron::new(cron.trim())
+            .parse()
+            .map_err(|e| AppError::Validation(format!("Invalid schedule '{}': {}", cron, e)))
+    }
+
+    /// Most recent occurrence after `since` and up to `now`
+    pub fn latest_occurrence<Tz: TimeZone>(
+        cron: &Cron,
+        since: &DateTime<Tz>,
+        now: &DateTime<Tz>,
+    ) -> Option<DateTime<Tz>> {
+        let mut latest = None;
+        let mut cursor = since.clone();
+
+        while let Ok(next) = cron.find_next_occurrence(&cursor, false) {
+            if next > *now {
+                break;
+            }
+            cursor = next.clone();
+            latest = Some(next);
+        }
+
+        latest
+    }
+
+    /// Whether an occurrence is recent enough to still run
+    pub fn within_grace<Tz: TimeZone>(occurrence: &DateTime<Tz>, now: &DateTime<Tz>) -> bool {
+        now.clone() - occurrence.clone() <= Duration::minutes(MISSED_SCHEDULE_GRACE_MINUTES)
+    }
+
+    /// Latest occurrence to handle since the last one handled, or since `created_at`,
+    /// and whether it is recent enough to run rather than be skipped as missed
+    pub fn due_occurrence(
+        cron: &Cron,
+        created_at: &str,
+        last_run_at: Option<&str>,
+        now: &DateTime<Local>,
+    ) -> Option<(DateTime<Local>, bool)> {
+        let oldest = *now - Duration::days(MAX_CATCH_UP_DAYS);
+        let since = DateTime::parse_from_rfc3339(last_run_at.unwrap_or(created_at))
+            .map(|since| since.with_timezone(&Local))
+            .unwrap_or(*now)
+            .max(oldest);
+
+        let occurrence = Self::latest_occurrence(cron, &since, now)?;
+        let run = Self::within_grace(&occurrence, now);
+        Some((occurrence, run))
+    }
+
+    /// Spawn the background scheduler
+    pub fn start_worker(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let scheduler = SchedulerService::new();
+
+            loop {
+                // Errors, like a locked store, are retried on the next tick
+                let _ = scheduler.tick(&app).await;
+                tokio::time::sleep(std::time::Duration::from_secs(SCHEDULER_TICK_SECS)).await;
+            }
+        });
+    }
+
+    /// Load, change and save the schedules of the active workspace as one step
+    pub async fn update_schedules<T>(
+        &self,
+        app: &AppHandle,
+        change: impl FnOnce(&mut Vec<ContainerSchedule>) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let _lock = SCHEDULES_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut schedules = storage_service.load_schedules_from_store(app).await?;
+        let result = change(&mut schedules)?;
+        storage_service
+            .save_schedules_to_store(app, &schedules)
+            .await?;
+        Ok(result)
+    }
+
+    /// Run the due schedules and SQL jobs
+    async fn tick(&self, app: &AppHandle) -> Result<(), AppError> {
+        let now = Local::now();
+        let schedules = self.run_due_schedules(app, &now).await;
+        // SQL jobs run whatever happened to the schedules
+        let jobs = SqlJobService::new().run_due(app, &now).await;
+        schedules.and(jobs)
+    }
+
+    /// Run every due schedule once and record the handled occurrences
+    /// A failing schedule keeps its error and the others still run
+    async fn run_due_schedules(
+        &self,
+        app: &AppHandle,
+        now: &DateTime<Local>,
+    ) -> Result<(), AppError> {
+        let due: Vec<ContainerSchedule> = {
+            let _lock = SCHEDULES_LOCK.lock().await;
+            let storage_service = StorageService::new();
+            let mut schedules = storage_service.load_schedules_from_store(app).await?;
+            let mut due = Vec::new();
+            let mut handled = false;
+
+            for schedule in schedules.iter_mut().filter(|s| s.enabled) {
+                let Ok(cron) = Self::parse(&schedule.cron) else {
+                    continue;
+                };
+
+                let Some((occurrence, run)) = Self::due_occurrence(
+                    &cron,
+                    &schedule.created_at,
+                    schedule.last_run_at.as_deref(),
+                    now,
+                ) else {
+                    continue;
+                };
+
+                if run {
+                    due.push(schedule.clone());
+                }
+                schedule.last_run_at = Some(occurrence.to_rfc3339());
+                handled = true;
+            }
+
+            if handled {
+                storage_service
+                    .save_schedules_to_store(app, &schedules)
+                    .await?;
+            }
+            due
+        };
+
+        let mut outcomes = Vec::new();
+        for schedule in &due {
+            let error = self.run(app, schedule).await.err().map(|error| {
+                EventService::new().background_error(
+                    app,
+                    "scheduler",
+                    format!("Schedule '{}' failed: {}", schedule.name, error),
+                );
+                error.to_string()
+            });
+            outcomes.push((schedule.id.clone(), error));
+        }
+        if !outcomes.is_empty() {
+            // Schedules deleted while they ran are left out
+            self.update_schedules(app, |schedules| {
+                for (id, error) in outcomes {
+                    if let Some(schedule) = schedules.iter_mut().find(|s| s.id == id) {
+                        schedule.last_error = error;
+                    }
+                }
+                Ok(())
+            })
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Apply a schedule's action to its containers
+    /// Every target is tried, the ones that failed are reported together
+    async fn run(&self, app: &AppHandle, schedule: &ContainerSchedule) -> Result<(), AppError> {
+        match schedule.action {
+            ScheduledAction::Start => self.set_status(app, schedule, true).await,
+            ScheduledAction::Stop => self.set_status(app, schedule, false).await,
+            ScheduledAction::IntegrityCheck => {
+                self.check_integrity(app, schedule).await;
+                Ok(())
+            }
+        }
+    }
+
+    async fn set_status(
+        &self,
+        app: &AppHandle,
+        schedule: &ContainerSchedule,
+        start: bool,
+    ) -> Result<(), AppError> {
+        let event_service = EventService::new();
+        let databases = app.state::<DatabaseStore>();
+
+        let target_status = if start { "running" } else { "stopped" };
+
+        let targets: Vec<DatabaseContainer> = {
+            let db_map = databases.read().await;
+            db_map
+                .values()
+                .filter(|db| schedule.targets(&db.id) && db.is_running() != start)
+                .cloned()
+                .collect()
+        };
+
+        if targets.is_empty() {
+            return Ok(());
+        }
+
+        let mut changed = Vec::new();
+        let mut failures = Vec::new();
+        for database in &targets {
+            let Some(real_id) = &database.container_id else {
+                continue;
+            };
+
+            let result = if start {
+                match DockerService::for_database(app, database) {
+                    Ok(docker_service) => docker_service.start_container(app, real_id).await,
+                    Err(error) => Err(error),
+                }
+            } else {
+                GracefulStopService::new().stop(app, database).await
+            };
+            if let Err(error) = result {
+                failures.push(format!("{}: {}", database.name, error));
+                continue;
+            }
+
+            let mut db_map = databases.write().await;
+            if let Some(db) = db_map.get_mut(&database.id) {
+                db.status = target_status.to_string();
+                let now = Some(chrono::Utc::now().to_rfc3339());
+                if start {
+                    db.last_started_at = now;
+                } else {
+                    db.last_stopped_at = now;
+                }
+                event_service.container_status_changed(app, db);
+                changed.push(db.id.clone());
+            }
+        }
+
+        StorageService::new()
+            .upsert_databases_by_id(app, &changed)
+            .await?;
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Docker {
+                message: failures.join("; "),
+                details: None,
+            })
+        }
+    }
+
+    /// Check the running containers one after another in the background, stopped ones are skipped
+    async fn check_integrity(&self, app: &AppHandle, schedule: &ContainerSchedule) {
+        let targets: Vec<DatabaseContainer> = {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            db_map
+                .values()
+                .filter(|db| schedule.targets(&db.id) && db.is_running())
+                .filter(|db| integrity_tool(&db.db_type).is_some())
+                .cloned()
+                .collect()
+        };
+
+        let app = app.clone();
+        let schedule_id = schedule.id.clone();
+        tauri::async_runtime::spawn(async move {
+            let integrity_service = IntegrityService::new();
+            for database in &targets {
+                // Results, errors included, end up in the check history
+                let _ = integrity_service
+                    .check(&app, database, false, Some(schedule_id.clone()))
+                    .await;
+            }
+        });
+    }
+}
@@ -0,0 +1,174 @@
+use crate::services::ConfigService;
+use crate::types::*;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+const SERVICE_NAME: &str = "com.abians.docker-db-manager";
+/// Stored in the run arguments in place of the password, which lives in the keychain
+pub const PASSWORD_PLACEHOLDER: &str = "<keychain:password>";
+
+// Values known to be in the keychain, so unchanged secrets are not rewritten on every save
+static KNOWN_SECRETS: LazyLock<Mutex<HashMap<String, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Stores credentials in the platform keychain
+/// (Keychain on macOS, Credential Manager on Windows, Secret Service on Linux)
+pub struct SecretsService;
+
+impl SecretsService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Keychain key of the password of a managed database
+    pub fn password_key(database_id: &str) -> String {
+        format!("database/{}/password", database_id)
+    }
+
+    fn entry(key: &str) -> Result<keyring::Entry, AppError> {
+        keyring::Entry::new(SERVICE_NAME, key)
+            .map_err(|e| AppError::Secrets(format!("Failed to access keychain: {}", e)))
+    }
+
+    pub fn get_secret(&self, key: &str) -> Result<Option<String>, AppError> {
+        if let Some(value) = KNOWN_SECRETS.lock().unwrap().get(key) {
+            return Ok(Some(value.clone()));
+        }
+
+        match Self::entry(key)?.get_password() {
+            Ok(value) => {
+                KNOWN_SECRETS
+                    .lock()
+                    .unwrap()
+                    .insert(key.to_string(), value.clone());
+                Ok(Some(value))
+            }
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(AppError::Secrets(format!(
+                "Failed to read secret from keychain: {}",
+                e
+            ))),
+        }
+    }
+
+    pub fn set_secret(&self, key: &str, value: &str) -> Result<(), AppError> {
+        if KNOWN_SECRETS.lock().unwrap().get(key).map(String::as_str) == Some(value) {
+            return Ok(());
+        }
+
+        Self::entry(key)?
+            .set_password(value)
+            .map_err(|e| AppError::Secrets(format!("Failed to write secret to keychain: {}", e)))?;
+
+        KNOWN_SECRETS
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+
+        Ok(())
+    }
+
+    pub fn delete_secret(&self, key: &str) -> Result<(), AppError> {
+        KNOWN_SECRETS.lock().unwrap().remove(key);
+
+        match Self::entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(AppError::Secrets(format!(
+                "Failed to delete secret from keychain: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Move the password of a database into the keychain, leaving only its key
+    /// Fails when no keychain is available rather than storing the password in plaintext
+    pub fn externalize_password(
+        &self,
+        mut database: DatabaseContainer,
+    ) -> Result<DatabaseContainer, AppError> {
+        if let Some(password) = database.stored_password.take() {
+            let key = Self::password_key(&database.id);
+            self.set_secret(&key, &password).map_err(|e| {
+                AppError::Secrets(format!(
+                    "The password of '{}' was not saved, it is never stored in plaintext: {}",
+                    database.name, e
+                ))
+            })?;
+            Self::scrub_password(&mut database, &password);
+            database.stored_password_ref = Some(key);
+        }
+
+        Ok(database)
+    }
+
+    /// Resolve the password of a database loaded from the store
+    /// The password is put back into the run arguments it was scrubbed from
+    pub fn resolve_password(&self, database: &mut DatabaseContainer) -> Result<(), AppError> {
+        if database.stored_password.is_none() {
+            if let Some(key) = &database.stored_password_ref {
+                database.stored_password = self.get_secret(key)?;
+            }
+        }
+
+        if let Some(password) = database.stored_password.clone() {
+            Self::inject_password(database, &password);
+        }
+
+        Ok(())
+    }
+
+    /// Credential values of the run arguments, the environment and Redis' `--requirepass`
+    fn credential_values(database: &mut DatabaseContainer) -> Vec<&mut String> {
+        let Some(docker_args) = &mut database.stored_docker_args else {
+            return Vec::new();
+        };
+
+        let mut values: Vec<&mut String> = docker_args
+            .env_vars
+            .iter_mut()
+            .filter(|(key, _)| ConfigService::is_secret_env_var(key))
+            .map(|(_, value)| value)
+            .collect();
+
+        let mut requirepass = false;
+        for arg in docker_args.command.iter_mut() {
+            let is_flag = arg == "--requirepass";
+            if requirepass {
+                values.push(arg);
+            }
+            requirepass = is_flag;
+        }
+
+        values
+    }
+
+    /// Replace the password in the run arguments with the placeholder
+    pub fn scrub_password(database: &mut DatabaseContainer, password: &str) {
+        if password.is_empty() {
+            return;
+        }
+        for value in Self::credential_values(database) {
+            if value == password {
+                *value = PASSWORD_PLACEHOLDER.to_string();
+            }
+        }
+    }
+
+    /// Put the password back where the run arguments hold the placeholder
+    pub fn inject_password(database: &mut DatabaseContainer, password: &str) {
+        for value in Self::credential_values(database) {
+            if value == PASSWORD_PLACEHOLDER {
+                *value = password.to_string();
+            }
+        }
+    }
+
+    /// Whether the run arguments of an entry hold the password in plaintext
+    pub fn holds_password(database: &DatabaseContainer, password: &str) -> bool {
+        let mut database = database.clone();
+        !password.is_empty()
+            && Self::credential_values(&mut database)
+                .iter()
+                .any(|value| value.as_str() == password)
+    }
+}
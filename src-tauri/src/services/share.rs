@@ -0,0 +1,216 @@
+use crate::services::EventService;
+use crate::types::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+struct RunningShare {
+    status: ShareStatus,
+    // Task set watching the tool, a share started again gets a new one
+    generation: u64,
+    child: Option<CommandChild>,
+}
+
+// Databases shared since launch, closed shares are removed
+static SHARES: LazyLock<Mutex<HashMap<String, RunningShare>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Exposes a database port on a public address through a tunneling tool for a limited time
+pub struct ShareService;
+
+impl ShareService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Status of the share of a database, stopped when it is not shared
+    pub fn status(&self, database_id: &str, tool: ShareTool) -> ShareStatus {
+        SHARES
+            .lock()
+            .unwrap()
+            .get(database_id)
+            .map(|running| running.status.clone())
+            .unwrap_or_else(|| ShareStatus::stopped(database_id, tool))
+    }
+
+    /// Run the tunneling tool for `minutes`, a database already shared is left as is
+    pub fn start(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        settings: &ShareSettings,
+        minutes: u32,
+    ) -> Result<ShareStatus, AppError> {
+        if let Some(running) = SHARES.lock().unwrap().get(&database.id) {
+            return Ok(running.status.clone());
+        }
+
+        let port = u16::try_from(database.port).map_err(|_| {
+            AppError::Validation(format!("Port {} cannot be shared", database.port))
+        })?;
+        let program = settings.program();
+        let (events, child) = app
+            .shell()
+            .command(&program)
+            .args(settings.args(port))
+            .spawn()
+            .map_err(|e| {
+                AppError::Validation(format!(
+                    "Failed to run {}: {}. Install it or set its path in the settings",
+                    program, e
+                ))
+            })?;
+
+        let now = chrono::Utc::now();
+        let generation = NEXT_GENERATION.fetch_add(1, Ordering::SeqCst);
+        let status = ShareStatus {
+            state: ShareState::Starting,
+            started_at: Some(now.to_rfc3339()),
+            expires_at: Some((now + chrono::Duration::minutes(minutes.into())).to_rfc3339()),
+            ..ShareStatus::stopped(&database.id, settings.tool)
+        };
+        SHARES.lock().unwrap().insert(
+            database.id.clone(),
+            RunningShare {
+                status: status.clone(),
+                generation,
+                child: Some(child),
+            },
+        );
+        EventService::new().share_status_changed(app, &status);
+
+        let watch_app = app.clone();
+        let database_id = database.id.clone();
+        let watch_settings = settings.clone();
+        tauri::async_runtime::spawn(async move {
+            ShareService::new()
+                .watch(
+                    &watch_app,
+                    &database_id,
+                    &watch_settings,
+                    generation,
+                    events,
+                )
+                .await;
+        });
+
+        // Closed when it expires, unless it was stopped or started again before
+        let expiry_app = app.clone();
+        let database_id = database.id.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(u64::from(minutes) * 60)).await;
+            ShareService::new().close(&expiry_app, &database_id, generation, None);
+        });
+
+        Ok(status)
+    }
+
+    /// Stop sharing a database
+    pub fn stop(&self, app: &AppHandle, database_id: &str, tool: ShareTool) -> ShareStatus {
+        let running = SHARES.lock().unwrap().remove(database_id);
+        if let Some(child) = running.and_then(|running| running.child) {
+            let _ = child.kill();
+        }
+
+        let status = ShareStatus::stopped(database_id, tool);
+        EventService::new().share_status_changed(app, &status);
+        status
+    }
+
+    /// Stop every share, used when the app quits
+    pub fn stop_all(&self) {
+        let running: Vec<RunningShare> = SHARES
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, running)| running)
+            .collect();
+        for child in running.into_iter().filter_map(|running| running.child) {
+            let _ = child.kill();
+        }
+    }
+
+    /// Read the tool's output for the public address until it exits
+    async fn watch(
+        &self,
+        app: &AppHandle,
+        database_id: &str,
+        settings: &ShareSettings,
+        generation: u64,
+        mut events: tauri::async_runtime::Receiver<CommandEvent>,
+    ) {
+        let mut last_line = None;
+        while let Some(event) = events.recv().await {
+            let line = match event {
+                CommandEvent::Stdout(line) | CommandEvent::Stderr(line) => {
+                    String::from_utf8_lossy(&line).trim().to_string()
+                }
+                CommandEvent::Terminated(payload) => {
+                    let error = last_line.unwrap_or_else(|| {
+                        format!(
+                            "{} exited with code {}",
+                            settings.program(),
+                            payload.code.unwrap_or(-1)
+                        )
+                    });
+                    self.close(app, database_id, generation, Some(error));
+                    return;
+                }
+                _ => continue,
+            };
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(address) = settings.public_address(&line) {
+                let updated = {
+                    let mut shares = SHARES.lock().unwrap();
+                    shares
+                        .get_mut(database_id)
+                        .filter(|running| running.generation == generation)
+                        .map(|running| {
+                            running.status.state = ShareState::Active;
+                            running.status.public_address = Some(address);
+                            running.status.clone()
+                        })
+                };
+                match updated {
+                    Some(status) => EventService::new().share_status_changed(app, &status),
+                    None => return,
+                }
+            }
+            last_line = Some(line);
+        }
+    }
+
+    /// Close the share still owned by `generation`, reporting why it ended
+    fn close(&self, app: &AppHandle, database_id: &str, generation: u64, error: Option<String>) {
+        let running = {
+            let mut shares = SHARES.lock().unwrap();
+            if !shares
+                .get(database_id)
+                .is_some_and(|running| running.generation == generation)
+            {
+                return;
+            }
+            shares.remove(database_id)
+        };
+        let Some(running) = running else {
+            return;
+        };
+        if let Some(child) = running.child {
+            let _ = child.kill();
+        }
+
+        let status = ShareStatus {
+            last_error: error,
+            ..ShareStatus::stopped(database_id, running.status.tool)
+        };
+        EventService::new().share_status_changed(app, &status);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::services::{GracefulStopService, StorageService};
+use crate::types::*;
+use futures_util::future;
+use futures_util::stream::{self, StreamExt};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager};
+
+// Set once containers are being stopped, the second exit request goes through
+static STOPPING: AtomicBool = AtomicBool::new(false);
+
+/// Stops managed containers when the app quits
+pub struct ShutdownService;
+
+impl ShutdownService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Running containers that have not opted out of stop on exit
+    pub fn containers_to_stop(databases: &[DatabaseContainer]) -> Vec<DatabaseContainer> {
+        databases
+            .iter()
+            .filter(|db| db.is_running() && !db.keep_running_on_exit && db.container_id.is_some())
+            .cloned()
+            .collect()
+    }
+
+    /// Handle an exit request
+    /// Returns true when the exit must be held back while containers stop,
+    /// the app exits on its own once they are down
+    pub fn on_exit_requested(&self, app: &AppHandle) -> bool {
+        let stop_on_exit = {
+            let settings = app.state::<SettingsStore>();
+            let settings = settings.lock().unwrap();
+            settings.stop_on_exit
+        };
+
+        if !stop_on_exit || STOPPING.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        let pending = {
+            let databases = app.state::<DatabaseStore>();
+            // Exit requests arrive on the event loop thread, outside the async runtime
+            let db_map = databases.blocking_read();
+            Self::containers_to_stop(&db_map.values().cloned().collect::<Vec<_>>())
+        };
+
+        if pending.is_empty() {
+            return false;
+        }
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            ShutdownService::new().stop_all(&app, &pending).await;
+            app.exit(0);
+        });
+
+        true
+    }
+
+    /// Stop every container, failures are ignored so the app can still quit
+    async fn stop_all(&self, app: &AppHandle, containers: &[DatabaseContainer]) {
+        let databases = app.state::<DatabaseStore>();
+
+        // A few at a time, each stop can wait for the database to flush to disk
+        let stopped: Vec<String> = stream::iter(containers.to_vec())
+            .map(|container| {
+                let app = app.clone();
+                async move {
+                    GracefulStopService::new()
+                        .stop(&app, &container)
+                        .await
+                        .ok()?;
+                    Some(container.id)
+                }
+            })
+            .buffer_unordered(BULK_CONCURRENCY)
+            .filter_map(future::ready)
+            .collect()
+            .await;
+
+        {
+            let mut db_map = databases.write().await;
+            for id in &stopped {
+                if let Some(db) = db_map.get_mut(id) {
+                    db.mark_stopped();
+                }
+            }
+        }
+
+        let _ = StorageService::new()
+            .upsert_databases_by_id(app, &stopped)
+            .await;
+    }
+}
@@ -0,0 +1,406 @@
+use crate::services::{DockerService, EnvFile, SqlService};
+use crate::types::*;
+use serde_json::Value;
+use std::path::Path;
+use tauri::AppHandle;
+
+// Log lines of a PostgreSQL container searched for slow statements
+const POSTGRES_LOG_SCAN_LINES: &str = "20000";
+
+// Describes a failed docker call of this service
+const CONTEXT: &str = "Failed to read the slow query log";
+
+// SLOWLOG GET replies with nested arrays, which redis-cli prints flattened
+const REDIS_SLOWLOG_SCRIPT: &str = "return cjson.encode(redis.call('SLOWLOG', 'GET', ARGV[1]))";
+
+/// Turns on, reads and clears the slow query log of PostgreSQL, MySQL, MariaDB and Redis
+/// Settings changed with SET GLOBAL and CONFIG SET last until the server restarts,
+/// PostgreSQL keeps its threshold in postgresql.auto.conf
+pub struct SlowLogService;
+
+impl SlowLogService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn unsupported(database: &DatabaseContainer) -> AppError {
+        AppError::Validation(format!("{} has no slow query log", database.db_type))
+    }
+
+    /// `SET GLOBAL` statements logging statements slower than `threshold_ms` to mysql.slow_log
+    pub fn mysql_enable_sql(threshold_ms: u64) -> String {
+        format!(
+            "SET GLOBAL log_output = 'TABLE'; \
+SET GLOBAL long_query_time = {}; \
+SET GLOBAL slow_query_log = 'ON'",
+            threshold_ms as f64 / 1000.0
+        )
+    }
+
+    /// Newest `limit` entries of mysql.slow_log as JSON lines, one `SlowQuery` each
+    pub fn mysql_select_sql(since: Option<&str>, limit: usize) -> String {
+        let since = since
+            .and_then(|since| chrono::DateTime::parse_from_rfc3339(since).ok())
+            .map(|since| {
+                format!(
+                    " WHERE start_time > '{}'",
+                    since
+                        .with_timezone(&chrono::Utc)
+                        .format("%Y-%m-%d %H:%M:%S%.6f")
+                )
+            })
+            .unwrap_or_default();
+
+        // Timestamps are read in UTC whatever the server's time zone
+        format!(
+            "SET time_zone = '+00:00'; \
+SELECT JSON_OBJECT(\
+'timestamp', DATE_FORMAT(start_time, '%Y-%m-%dT%H:%i:%s.%fZ'), \
+'durationMs', (HOUR(query_time) * 3600 + MINUTE(query_time) * 60 + SECOND(query_time)) * 1000 \
++ MICROSECOND(query_time) / 1000, \
+'query', CONVERT(sql_text USING utf8mb4), \
+'database', NULLIF(db, ''), \
+'client', user_host, \
+'rowsExamined', rows_examined) \
+FROM mysql.slow_log{} ORDER BY start_time DESC LIMIT {}",
+            since, limit
+        )
+    }
+
+    /// Variables redis-cli reads its password from, passed with `--env-file`
+    pub fn redis_credentials(database: &DatabaseContainer) -> Vec<(&'static str, String)> {
+        let password = database.stored_password.clone().unwrap_or_default();
+        if password.is_empty() {
+            return Vec::new();
+        }
+        vec![("REDISCLI_AUTH", password)]
+    }
+
+    /// `docker exec` arguments running a redis-cli command in the container
+    /// The password comes from `env_file`, written from `redis_credentials`
+    pub fn redis_args(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+        command: &[&str],
+    ) -> Vec<String> {
+        let mut args = vec!["exec".to_string()];
+        if let Some(env_file) = env_file {
+            args.extend([
+                "--env-file".to_string(),
+                env_file.to_string_lossy().to_string(),
+            ]);
+        }
+        args.extend([database.name.clone(), "redis-cli".to_string()]);
+        args.extend(command.iter().map(|arg| arg.to_string()));
+        args
+    }
+
+    /// Slow statements of PostgreSQL log output read with `docker logs --timestamps`
+    /// Continuation lines of a multi-line statement start with a tab
+    pub fn parse_postgres_log(logs: &str) -> Vec<SlowQuery> {
+        let mut entries: Vec<SlowQuery> = Vec::new();
+        let mut continuing = false;
+
+        for line in logs.lines() {
+            let (timestamp, message) = match line.split_once(' ') {
+                Some((timestamp, message)) => {
+                    match chrono::DateTime::parse_from_rfc3339(timestamp) {
+                        Ok(timestamp) => (
+                            Some(timestamp.with_timezone(&chrono::Utc).to_rfc3339()),
+                            message,
+                        ),
+                        Err(_) => (None, line),
+                    }
+                }
+                None => (None, line),
+            };
+
+            if let Some((_, logged)) = message.split_once("LOG:  duration: ") {
+                continuing = false;
+                let Some((duration, statement)) = logged.split_once(" ms  ") else {
+                    continue;
+                };
+                // Simple queries log `statement:`, prepared ones `execute <name>:`
+                let query = statement.strip_prefix("statement: ").or_else(|| {
+                    statement
+                        .strip_prefix("execute ")
+                        .and_then(|execute| execute.split_once(": "))
+                        .map(|(_, query)| query)
+                });
+                let (Ok(duration_ms), Some(query)) = (duration.parse::<f64>(), query) else {
+                    continue;
+                };
+
+                entries.push(SlowQuery {
+                    timestamp: timestamp.unwrap_or_default(),
+                    duration_ms,
+                    query: query.to_string(),
+                    database: None,
+                    client: None,
+                    rows_examined: None,
+                });
+                continuing = true;
+            } else if let Some(rest) = message.strip_prefix('\t').filter(|_| continuing) {
+                if let Some(entry) = entries.last_mut() {
+                    entry.query.push('\n');
+                    entry.query.push_str(rest);
+                }
+            } else {
+                continuing = false;
+            }
+        }
+
+        entries
+    }
+
+    /// Entries of the JSON encoded `SLOWLOG GET` reply
+    /// Each is `[id, unix time, microseconds, [arguments], client address, client name]`
+    pub fn parse_redis_slowlog(output: &str) -> Result<Vec<SlowQuery>, AppError> {
+        let reply: Value = serde_json::from_str(output.trim())
+            .map_err(|e| AppError::Internal(format!("Unexpected output from redis-cli: {}", e)))?;
+        // cjson encodes an empty array as an object
+        let Some(entries) = reply.as_array() else {
+            return Ok(Vec::new());
+        };
+
+        Ok(entries
+            .iter()
+            .filter_map(|entry| {
+                let timestamp = chrono::DateTime::from_timestamp(entry[1].as_i64()?, 0)?;
+                let arguments: Vec<&str> = entry[3]
+                    .as_array()?
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect();
+
+                Some(SlowQuery {
+                    timestamp: timestamp.to_rfc3339(),
+                    duration_ms: entry[2].as_f64()? / 1000.0,
+                    query: arguments.join(" "),
+                    database: None,
+                    client: entry[4]
+                        .as_str()
+                        .filter(|client| !client.is_empty())
+                        .map(str::to_owned),
+                    rows_examined: None,
+                })
+            })
+            .collect())
+    }
+
+    /// Log statements slower than `threshold_ms`, 0 logs every statement
+    pub async fn enable(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        threshold_ms: u64,
+    ) -> Result<(), AppError> {
+        if threshold_ms > MAX_SLOW_LOG_THRESHOLD_MS {
+            return Err(AppError::Validation(
+                "Threshold must be at most one hour".to_string(),
+            ));
+        }
+
+        let sql_service = SqlService::new();
+        match database.db_type.as_str() {
+            "PostgreSQL" => {
+                // ALTER SYSTEM cannot share a transaction with the reload
+                sql_service
+                    .query_lines(
+                        app,
+                        database,
+                        &format!(
+                            "ALTER SYSTEM SET log_min_duration_statement = {}",
+                            threshold_ms
+                        ),
+                    )
+                    .await?;
+                sql_service
+                    .query_lines(app, database, "SELECT pg_reload_conf()")
+                    .await?;
+            }
+            "MySQL" | "MariaDB" => {
+                sql_service
+                    .query_lines(app, database, &Self::mysql_enable_sql(threshold_ms))
+                    .await?;
+            }
+            "Redis" => {
+                let micros = (threshold_ms * 1000).to_string();
+                self.redis(
+                    app,
+                    database,
+                    &["CONFIG", "SET", "slowlog-log-slower-than", &micros],
+                )
+                .await?;
+            }
+            _ => return Err(Self::unsupported(database)),
+        }
+        Ok(())
+    }
+
+    /// Stop logging slow statements, entries already logged are kept
+    pub async fn disable(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<(), AppError> {
+        let sql_service = SqlService::new();
+        match database.db_type.as_str() {
+            "PostgreSQL" => {
+                sql_service
+                    .query_lines(
+                        app,
+                        database,
+                        "ALTER SYSTEM RESET log_min_duration_statement",
+                    )
+                    .await?;
+                sql_service
+                    .query_lines(app, database, "SELECT pg_reload_conf()")
+                    .await?;
+            }
+            "MySQL" | "MariaDB" => {
+                sql_service
+                    .query_lines(app, database, "SET GLOBAL slow_query_log = 'OFF'")
+                    .await?;
+            }
+            "Redis" => {
+                self.redis(
+                    app,
+                    database,
+                    &["CONFIG", "SET", "slowlog-log-slower-than", "-1"],
+                )
+                .await?;
+            }
+            _ => return Err(Self::unsupported(database)),
+        }
+        Ok(())
+    }
+
+    /// Newest slow statements first, at most `limit`
+    pub async fn fetch(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        limit: usize,
+    ) -> Result<Vec<SlowQuery>, AppError> {
+        let limit = limit.clamp(1, MAX_SLOW_LOG_LIMIT);
+        let since = database.slow_log_reset_at.as_deref();
+
+        match database.db_type.as_str() {
+            "PostgreSQL" => {
+                let mut args = vec![
+                    "logs".to_string(),
+                    "--timestamps".to_string(),
+                    "--tail".to_string(),
+                    POSTGRES_LOG_SCAN_LINES.to_string(),
+                ];
+                if let Some(since) = since {
+                    args.extend(["--since".to_string(), since.to_string()]);
+                }
+                args.push(database.name.clone());
+                let logs = self.postgres_logs(app, database, &args).await?;
+
+                let mut entries = Self::parse_postgres_log(&logs);
+                entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+                entries.truncate(limit);
+                Ok(entries)
+            }
+            "MySQL" | "MariaDB" => SqlService::new()
+                .query_lines(app, database, &Self::mysql_select_sql(since, limit))
+                .await?
+                .iter()
+                .map(|line| {
+                    serde_json::from_str(line).map_err(|e| {
+                        AppError::Internal(format!(
+                            "Unexpected output from the database client: {}",
+                            e
+                        ))
+                    })
+                })
+                .collect(),
+            "Redis" => {
+                let output = self
+                    .redis(
+                        app,
+                        database,
+                        &["EVAL", REDIS_SLOWLOG_SCRIPT, "0", &limit.to_string()],
+                    )
+                    .await?;
+                Self::parse_redis_slowlog(&output)
+            }
+            _ => Err(Self::unsupported(database)),
+        }
+    }
+
+    /// Clear the entries MySQL, MariaDB and Redis keep, PostgreSQL's are hidden by the caller
+    pub async fn reset(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<(), AppError> {
+        match database.db_type.as_str() {
+            "PostgreSQL" => {}
+            "MySQL" | "MariaDB" => {
+                SqlService::new()
+                    .query_lines(app, database, "TRUNCATE TABLE mysql.slow_log")
+                    .await?;
+            }
+            "Redis" => {
+                self.redis(app, database, &["SLOWLOG", "RESET"]).await?;
+            }
+            _ => return Err(Self::unsupported(database)),
+        }
+        Ok(())
+    }
+
+    async fn redis(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        command: &[&str],
+    ) -> Result<String, AppError> {
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to query it",
+                database.name
+            )));
+        }
+
+        // Kept until redis-cli returns
+        let env_file = EnvFile::for_credentials(&Self::redis_credentials(database))?;
+        let args = Self::redis_args(database, env_file.as_ref().map(EnvFile::path), command);
+        let output = DockerService::for_database(app, database)?
+            .checked_output(app, CONTEXT, args)
+            .await?;
+        // redis-cli prints error replies on stdout
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if ["ERR", "NOAUTH", "NOPERM", "WRONGPASS"]
+            .iter()
+            .any(|prefix| stdout.starts_with(prefix))
+        {
+            return Err(AppError::from_docker_output(
+                "Failed to run Redis command",
+                &stdout,
+                output.status.code(),
+            ));
+        }
+        Ok(stdout)
+    }
+
+    // PostgreSQL logs to stderr, which `docker logs` passes through on stderr
+    async fn postgres_logs(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        args: &[String],
+    ) -> Result<String, AppError> {
+        let output = DockerService::for_database(app, database)?
+            .checked_output(app, CONTEXT, args)
+            .await?;
+        Ok(format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
@@ -0,0 +1,165 @@
+use crate::services::{DockerService, GracefulStopService, StorageService};
+use crate::types::*;
+use std::future::Future;
+use tauri::AppHandle;
+
+pub struct SnapshotService;
+
+impl SnapshotService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Name of the data volume of a container
+    pub fn data_volume_name(container_name: &str) -> String {
+        format!("{}-data", container_name)
+    }
+
+    /// Name of the volume holding a snapshot
+    pub fn snapshot_volume_name(container_name: &str, snapshot_id: &str) -> String {
+        let short_id: String = snapshot_id.chars().take(8).collect();
+        format!("{}-snap-{}", container_name, short_id)
+    }
+
+    fn ensure_persistent(database: &DatabaseContainer) -> Result<(), AppError> {
+        if !database.stored_persist_data {
+            return Err(AppError::Validation(
+                "Snapshots require persistent data to be enabled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Run an operation on the data volume with the container stopped,
+    /// restarting it afterwards when it was running
+    async fn with_container_stopped<F, Fut>(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        operation: F,
+    ) -> Result<(), AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<(), AppError>>,
+    {
+        let docker_service = DockerService::for_database(app, database)?;
+        let container_id = database.container_id.clone().unwrap_or_default();
+
+        Self::run_stopped(
+            database.is_running() && database.container_id.is_some(),
+            GracefulStopService::new().stop(app, database),
+            operation(),
+            docker_service.start_container(app, &container_id),
+        )
+        .await
+    }
+
+    /// Run `operation` between `stop` and `start`, which only run when the container was running
+    /// The container is restarted even when the operation fails, whose error is the one returned
+    pub async fn run_stopped(
+        was_running: bool,
+        stop: impl Future<Output = Result<(), AppError>>,
+        operation: impl Future<Output = Result<(), AppError>>,
+        start: impl Future<Output = Result<(), AppError>>,
+    ) -> Result<(), AppError> {
+        if was_running {
+            stop.await?;
+        }
+
+        let result = operation.await;
+
+        let restarted = if was_running { start.await } else { Ok(()) };
+
+        result.and(restarted)
+    }
+
+    /// Copy the data volume into a new snapshot volume
+    pub async fn create(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        label: Option<String>,
+    ) -> Result<Snapshot, AppError> {
+        Self::ensure_persistent(database)?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let snapshot = Snapshot {
+            volume: Self::snapshot_volume_name(&database.name, &id),
+            id,
+            database_id: database.id.clone(),
+            label: label
+                .map(|label| label.trim().to_string())
+                .filter(|label| !label.is_empty()),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            host: database.host.clone(),
+        };
+
+        let docker_service = DockerService::for_database(app, database)?;
+        let data_volume = Self::data_volume_name(&database.name);
+
+        let result = self
+            .with_container_stopped(app, database, || {
+                docker_service.copy_volume(app, &data_volume, &snapshot.volume, false)
+            })
+            .await;
+
+        if let Err(error) = result {
+            let _ = docker_service
+                .remove_volume_if_exists(app, &snapshot.volume)
+                .await;
+            return Err(error);
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Replace the data volume with the contents of a snapshot
+    pub async fn restore(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        snapshot: &Snapshot,
+    ) -> Result<(), AppError> {
+        Self::ensure_persistent(database)?;
+
+        let docker_service = DockerService::for_database(app, database)?;
+        let data_volume = Self::data_volume_name(&database.name);
+
+        self.with_container_stopped(app, database, || {
+            docker_service.copy_volume(app, &snapshot.volume, &data_volume, true)
+        })
+        .await
+    }
+
+    pub async fn delete(&self, app: &AppHandle, snapshot: &Snapshot) -> Result<(), AppError> {
+        DockerService::for_host_id(app, snapshot.host.as_deref())?
+            .remove_volume_if_exists(app, &snapshot.volume)
+            .await
+    }
+
+    /// Delete every snapshot of a database, volumes and store entries
+    pub async fn remove_all_for_database(
+        &self,
+        app: &AppHandle,
+        database_id: &str,
+    ) -> Result<(), AppError> {
+        let storage_service = StorageService::new();
+
+        let snapshots = storage_service.load_snapshots_from_store(app).await?;
+        let (linked, remaining): (Vec<Snapshot>, Vec<Snapshot>) = snapshots
+            .into_iter()
+            .partition(|s| s.database_id == database_id);
+
+        if linked.is_empty() {
+            return Ok(());
+        }
+
+        for snapshot in &linked {
+            self.delete(app, snapshot).await?;
+        }
+
+        storage_service
+            .save_snapshots_to_store(app, &remaining)
+            .await
+    }
+}
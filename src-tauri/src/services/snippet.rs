@@ -0,0 +1,75 @@
+use crate::services::StorageService;
+use crate::types::*;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+// Saves may overlap, the library is read and written back as a whole
+static SNIPPETS_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Keeps the saved SQL queries and shell commands reused in the console and the terminal
+pub struct SnippetService;
+
+impl SnippetService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn list(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<Vec<Snippet>, AppError> {
+        let library = StorageService::new().load_snippets_from_store(app).await?;
+        Ok(library.for_database(database))
+    }
+
+    /// Add or update a snippet, snippets of removed containers are dropped on the way
+    pub async fn save(
+        &self,
+        app: &AppHandle,
+        snippet: Snippet,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Result<Snippet, AppError> {
+        if let Some(id) = &snippet.database_id {
+            if !databases.contains_key(id) {
+                return Err(AppError::NotFound("Container".to_string()));
+            }
+        }
+
+        let _lock = SNIPPETS_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut library = storage_service.load_snippets_from_store(app).await?;
+        library.retain_databases(databases);
+        let saved = library.save(snippet, chrono::Utc::now().to_rfc3339())?;
+        storage_service
+            .save_snippets_to_store(app, &library)
+            .await?;
+        Ok(saved)
+    }
+
+    pub async fn delete(&self, app: &AppHandle, snippet_id: &str) -> Result<(), AppError> {
+        let _lock = SNIPPETS_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut library = storage_service.load_snippets_from_store(app).await?;
+        if !library.remove(snippet_id) {
+            return Err(AppError::NotFound("Snippet".to_string()));
+        }
+        storage_service.save_snippets_to_store(app, &library).await
+    }
+
+    /// Text of a snippet for `database`, with its variables filled in
+    pub async fn render(
+        &self,
+        app: &AppHandle,
+        snippet_id: &str,
+        database: &DatabaseContainer,
+        values: &HashMap<String, String>,
+    ) -> Result<String, AppError> {
+        let library = StorageService::new().load_snippets_from_store(app).await?;
+        let snippet = library
+            .get(snippet_id)
+            .filter(|snippet| snippet.applies_to(database))
+            .ok_or_else(|| AppError::NotFound("Snippet".to_string()))?;
+        snippet.render(database, values)
+    }
+}
@@ -0,0 +1,831 @@
+use crate::services::{DockerService, EnvFile, EventService};
+use crate::types::*;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+use tauri::AppHandle;
+use tauri_plugin_shell::process::CommandEvent;
+
+// Rows written between two query export progress events
+const EXPORT_PROGRESS_ROWS: u64 = 10_000;
+
+// Databases whose SQL console may change data until the app quits
+static UNLOCKED_WRITES: LazyLock<Mutex<HashSet<String>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+const POSTGRES_TABLES_SQL: &str = "SELECT json_build_object(\
+'schema', n.nspname, \
+'name', c.relname, \
+'kind', CASE WHEN c.relkind IN ('v', 'm') THEN 'view' ELSE 'table' END, \
+'rowCount', CASE WHEN c.relkind IN ('r', 'p') AND c.reltuples >= 0 THEN c.reltuples::bigint END) \
+FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+WHERE c.relkind IN ('r', 'p', 'v', 'm') \
+AND n.nspname NOT IN ('pg_catalog', 'information_schema') AND n.nspname NOT LIKE 'pg_toast%' \
+ORDER BY n.nspname, c.relname";
+
+const MYSQL_TABLES_SQL: &str = "SELECT JSON_OBJECT(\
+'schema', TABLE_SCHEMA, \
+'name', TABLE_NAME, \
+'kind', IF(TABLE_TYPE = 'VIEW', 'view', 'table'), \
+'rowCount', TABLE_ROWS) \
+FROM information_schema.TABLES \
+WHERE TABLE_SCHEMA NOT IN ('mysql', 'information_schema', 'performance_schema', 'sys') \
+ORDER BY TABLE_SCHEMA, TABLE_NAME";
+
+// Whether column `c` of information_schema.columns belongs to its table's primary key
+const POSTGRES_PRIMARY_KEY_SQL: &str =
+    "EXISTS (SELECT 1 FROM information_schema.table_constraints tc \
+JOIN information_schema.key_column_usage k \
+ON k.constraint_name = tc.constraint_name AND k.table_schema = tc.table_schema \
+WHERE tc.constraint_type = 'PRIMARY KEY' AND tc.table_schema = c.table_schema \
+AND tc.table_name = c.table_name AND k.column_name = c.column_name)";
+
+/// Runs SQL in PostgreSQL, MySQL and MariaDB containers through the engine's own client
+/// Queries print one JSON value per line, which keeps types and survives newlines in values
+pub struct SqlService;
+
+impl SqlService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Variables the client reads its password from, passed with `--env-file`
+    /// Local socket connections to PostgreSQL are trusted by the image, no password needed
+    pub fn exec_credentials(database: &DatabaseContainer) -> Vec<(&'static str, String)> {
+        match SqlDialect::of(&database.db_type) {
+            Ok(SqlDialect::MySql) => {
+                vec![("MYSQL_PWD", DatabaseCredentials::of(database).password)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Env file of `exec_credentials`, to keep until the client returns
+    pub fn env_file(database: &DatabaseContainer) -> Result<Option<EnvFile>, AppError> {
+        EnvFile::for_credentials(&Self::exec_credentials(database))
+    }
+
+    /// `docker exec` with the options passing `env_file`, before the container name
+    fn exec_prefix(env_file: Option<&Path>) -> Vec<String> {
+        let mut args = vec!["exec".to_string()];
+        if let Some(env_file) = env_file {
+            args.extend([
+                "--env-file".to_string(),
+                env_file.to_string_lossy().to_string(),
+            ]);
+        }
+        args
+    }
+
+    /// `docker exec` arguments running `sql` with the engine's client, without headers or padding
+    /// `target` is the PostgreSQL database to connect to instead of the one created at init,
+    /// MySQL statements name their schema themselves
+    /// The password comes from `env_file`, written from `exec_credentials`
+    pub fn client_args(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+        target: Option<&str>,
+        sql: &str,
+    ) -> Result<Vec<String>, AppError> {
+        let credentials = DatabaseCredentials::of(database);
+        let target = target.unwrap_or(&credentials.database);
+        let client: Vec<&str> = match SqlDialect::of(&database.db_type)? {
+            SqlDialect::Postgres => vec![
+                "psql",
+                "-X",
+                "-q",
+                "-A",
+                "-t",
+                "-v",
+                "ON_ERROR_STOP=1",
+                "-U",
+                &credentials.username,
+                "-d",
+                target,
+                "-c",
+                sql,
+            ],
+            SqlDialect::MySql => vec![
+                if database.db_type == "MariaDB" {
+                    "mariadb"
+                } else {
+                    "mysql"
+                },
+                "-u",
+                "root",
+                "--batch",
+                "--raw",
+                "--skip-column-names",
+                "-e",
+                sql,
+            ],
+        };
+
+        let mut args = Self::exec_prefix(env_file);
+        args.push(database.name.clone());
+        args.extend(client.iter().map(|arg| arg.to_string()));
+        Ok(args)
+    }
+
+    /// `docker exec` arguments of the SQL console, tab-separated output with column names
+    /// `read_only` makes the server refuse writes the statement parser missed
+    /// The password comes from `env_file`, written from `exec_credentials`
+    pub fn console_args(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+        sql: &str,
+        read_only: bool,
+    ) -> Result<Vec<String>, AppError> {
+        let credentials = DatabaseCredentials::of(database);
+        let mut args = Self::exec_prefix(env_file);
+
+        match SqlDialect::of(&database.db_type)? {
+            SqlDialect::Postgres => {
+                if read_only {
+                    args.extend([
+                        "-e".to_string(),
+                        "PGOPTIONS=-c default_transaction_read_only=on".to_string(),
+                    ]);
+                }
+                args.push(database.name.clone());
+                args.extend(
+                    [
+                        "psql",
+                        "-X",
+                        "-q",
+                        "-A",
+                        "-F",
+                        "\t",
+                        "-P",
+                        "footer=off",
+                        "-v",
+                        "ON_ERROR_STOP=1",
+                        "-U",
+                        &credentials.username,
+                        "-d",
+                        &credentials.database,
+                        "-c",
+                        sql,
+                    ]
+                    .map(str::to_owned),
+                );
+            }
+            SqlDialect::MySql => {
+                args.extend([
+                    database.name.clone(),
+                    if database.db_type == "MariaDB" {
+                        "mariadb".to_string()
+                    } else {
+                        "mysql".to_string()
+                    },
+                    "-u".to_string(),
+                    "root".to_string(),
+                    "--batch".to_string(),
+                    "--raw".to_string(),
+                ]);
+                if !credentials.database.is_empty() {
+                    args.extend(["-D".to_string(), credentials.database.clone()]);
+                }
+                args.extend([
+                    "-e".to_string(),
+                    if read_only {
+                        format!("SET SESSION TRANSACTION READ ONLY; {}", sql)
+                    } else {
+                        sql.to_string()
+                    },
+                ]);
+            }
+        }
+
+        Ok(args)
+    }
+
+    /// `docker exec` arguments streaming the rows of one query as tab-separated lines
+    /// PostgreSQL sends them through `COPY ... TO STDOUT` without column names, MySQL's batch
+    /// output starts with them and `--quick` keeps the client from buffering the result
+    /// MySQL prints the string 'NULL' like a NULL, see `unescape_field`
+    pub fn export_args(
+        database: &DatabaseContainer,
+        env_file: Option<&Path>,
+        statement: &str,
+        read_only: bool,
+    ) -> Result<Vec<String>, AppError> {
+        match SqlDialect::of(&database.db_type)? {
+            SqlDialect::Postgres => Self::console_args(
+                database,
+                env_file,
+                &format!("COPY ({}) TO STDOUT", statement),
+                read_only,
+            ),
+            SqlDialect::MySql => Ok(
+                Self::console_args(database, env_file, statement, read_only)?
+                    .into_iter()
+                    .map(|arg| {
+                        if arg == "--raw" {
+                            "--quick".to_string()
+                        } else {
+                            arg
+                        }
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Catalog query listing one JSON column description per line
+    pub fn columns_sql(dialect: SqlDialect, schema: &str, table: &str) -> String {
+        let schema = dialect.quote_literal(schema);
+        let table = dialect.quote_literal(table);
+
+        match dialect {
+            SqlDialect::Postgres => format!(
+                "SELECT json_build_object(\
+'name', c.column_name, \
+'dataType', c.data_type, \
+'nullable', c.is_nullable = 'YES', \
+'primaryKey', {}) \
+FROM information_schema.columns c \
+WHERE c.table_schema = {} AND c.table_name = {} ORDER BY c.ordinal_position",
+                POSTGRES_PRIMARY_KEY_SQL, schema, table
+            ),
+            SqlDialect::MySql => format!(
+                "SELECT JSON_OBJECT(\
+'name', COLUMN_NAME, \
+'dataType', DATA_TYPE, \
+'nullable', IS_NULLABLE = 'YES', \
+'primaryKey', COLUMN_KEY = 'PRI') \
+FROM information_schema.COLUMNS \
+WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {} ORDER BY ORDINAL_POSITION",
+                schema, table
+            ),
+        }
+    }
+
+    /// Catalog queries of a whole schema: columns, foreign key columns and index columns
+    /// `target` is the MySQL schema to read, all user schemas when empty
+    pub fn schema_sql(dialect: SqlDialect, target: &str) -> [String; 3] {
+        match dialect {
+            SqlDialect::Postgres => [
+                format!(
+                    "SELECT json_build_object(\
+'schema', c.table_schema, \
+'table', c.table_name, \
+'name', c.column_name, \
+'dataType', c.data_type, \
+'nullable', c.is_nullable = 'YES', \
+'primaryKey', {}) \
+FROM information_schema.columns c JOIN information_schema.tables t \
+ON t.table_schema = c.table_schema AND t.table_name = c.table_name \
+WHERE t.table_type = 'BASE TABLE' AND c.table_schema NOT IN ('pg_catalog', 'information_schema') \
+ORDER BY c.table_schema, c.table_name, c.ordinal_position",
+                    POSTGRES_PRIMARY_KEY_SQL
+                ),
+                "SELECT json_build_object(\
+'schema', n.nspname, \
+'table', c.relname, \
+'name', con.conname, \
+'column', a.attname, \
+'referencedSchema', rn.nspname, \
+'referencedTable', rc.relname, \
+'referencedColumn', ra.attname) \
+FROM pg_constraint con \
+JOIN pg_class c ON c.oid = con.conrelid JOIN pg_namespace n ON n.oid = c.relnamespace \
+JOIN pg_class rc ON rc.oid = con.confrelid JOIN pg_namespace rn ON rn.oid = rc.relnamespace \
+CROSS JOIN LATERAL unnest(con.conkey, con.confkey) WITH ORDINALITY AS k(attnum, refattnum, position) \
+JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum \
+JOIN pg_attribute ra ON ra.attrelid = con.confrelid AND ra.attnum = k.refattnum \
+WHERE con.contype = 'f' AND n.nspname NOT IN ('pg_catalog', 'information_schema') \
+ORDER BY n.nspname, c.relname, con.conname, k.position"
+                    .to_string(),
+                "SELECT json_build_object(\
+'schema', n.nspname, \
+'table', c.relname, \
+'name', i.relname, \
+'column', a.attname, \
+'unique', ix.indisunique) \
+FROM pg_index ix \
+JOIN pg_class i ON i.oid = ix.indexrelid \
+JOIN pg_class c ON c.oid = ix.indrelid JOIN pg_namespace n ON n.oid = c.relnamespace \
+CROSS JOIN LATERAL unnest(ix.indkey::int2[]) WITH ORDINALITY AS k(attnum, position) \
+LEFT JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = k.attnum \
+WHERE NOT ix.indisprimary AND n.nspname NOT IN ('pg_catalog', 'information_schema') \
+AND n.nspname NOT LIKE 'pg_toast%' \
+ORDER BY n.nspname, c.relname, i.relname, k.position"
+                    .to_string(),
+            ],
+            SqlDialect::MySql => {
+                let schemas = |column: &str| {
+                    if target.is_empty() {
+                        format!(
+                            "{} NOT IN ('mysql', 'information_schema', 'performance_schema', 'sys')",
+                            column
+                        )
+                    } else {
+                        format!("{} = {}", column, dialect.quote_literal(target))
+                    }
+                };
+
+                [
+                    format!(
+                        "SELECT JSON_OBJECT(\
+'schema', c.TABLE_SCHEMA, \
+'table', c.TABLE_NAME, \
+'name', c.COLUMN_NAME, \
+'dataType', c.DATA_TYPE, \
+'nullable', c.IS_NULLABLE = 'YES', \
+'primaryKey', c.COLUMN_KEY = 'PRI') \
+FROM information_schema.COLUMNS c JOIN information_schema.TABLES t \
+ON t.TABLE_SCHEMA = c.TABLE_SCHEMA AND t.TABLE_NAME = c.TABLE_NAME \
+WHERE t.TABLE_TYPE = 'BASE TABLE' AND {} \
+ORDER BY c.TABLE_SCHEMA, c.TABLE_NAME, c.ORDINAL_POSITION",
+                        schemas("c.TABLE_SCHEMA")
+                    ),
+                    format!(
+                        "SELECT JSON_OBJECT(\
+'schema', TABLE_SCHEMA, \
+'table', TABLE_NAME, \
+'name', CONSTRAINT_NAME, \
+'column', COLUMN_NAME, \
+'referencedSchema', REFERENCED_TABLE_SCHEMA, \
+'referencedTable', REFERENCED_TABLE_NAME, \
+'referencedColumn', REFERENCED_COLUMN_NAME) \
+FROM information_schema.KEY_COLUMN_USAGE \
+WHERE REFERENCED_TABLE_NAME IS NOT NULL AND {} \
+ORDER BY TABLE_SCHEMA, TABLE_NAME, CONSTRAINT_NAME, ORDINAL_POSITION",
+                        schemas("TABLE_SCHEMA")
+                    ),
+                    format!(
+                        "SELECT JSON_OBJECT(\
+'schema', TABLE_SCHEMA, \
+'table', TABLE_NAME, \
+'name', INDEX_NAME, \
+'column', COLUMN_NAME, \
+'unique', NON_UNIQUE = 0) \
+FROM information_schema.STATISTICS \
+WHERE INDEX_NAME <> 'PRIMARY' AND {} \
+ORDER BY TABLE_SCHEMA, TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX",
+                        schemas("TABLE_SCHEMA")
+                    ),
+                ]
+            }
+        }
+    }
+
+    /// Column description from one line of `columns_sql`
+    pub fn parse_column(line: &str) -> Result<ColumnInfo, AppError> {
+        let value: Value = serde_json::from_str(line).map_err(Self::unexpected_output)?;
+        Ok(ColumnInfo::from_json(&value))
+    }
+
+    /// Rows printed by `TableRowsQuery::select_sql`, values ordered like `columns`
+    pub fn parse_rows(
+        lines: &[String],
+        columns: &[ColumnInfo],
+    ) -> Result<Vec<Vec<Value>>, AppError> {
+        lines
+            .iter()
+            .map(|line| {
+                let row: Value = serde_json::from_str(line).map_err(Self::unexpected_output)?;
+                Ok(columns
+                    .iter()
+                    .map(|column| row.get(&column.name).cloned().unwrap_or(Value::Null))
+                    .collect())
+            })
+            .collect()
+    }
+
+    fn unexpected_output(error: serde_json::Error) -> AppError {
+        AppError::Internal(format!(
+            "Unexpected output from the database client: {}",
+            error
+        ))
+    }
+
+    /// Run `sql` in the database's container, returns the non-empty output lines
+    pub async fn query_lines(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        sql: &str,
+    ) -> Result<Vec<String>, AppError> {
+        self.query_lines_in(app, database, None, sql).await
+    }
+
+    /// Run the post-create statements of a new database in order, stops at the first failure
+    pub async fn run_post_create(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        statements: &[String],
+    ) -> Result<(), AppError> {
+        for statement in statements {
+            self.query_lines(app, database, statement).await?;
+        }
+        Ok(())
+    }
+
+    /// `query_lines` against another database than the one created at init
+    pub async fn query_lines_in(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        target: Option<&str>,
+        sql: &str,
+    ) -> Result<Vec<String>, AppError> {
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to query it",
+                database.name
+            )));
+        }
+
+        let env_file = Self::env_file(database)?;
+        let args = Self::client_args(database, env_file.as_ref().map(EnvFile::path), target, sql)?;
+        Ok(self
+            .run_client(app, database, &args)
+            .await?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    /// Run the engine's client with `args` in the database's container, returns its output
+    async fn run_client(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        args: &[String],
+    ) -> Result<String, AppError> {
+        DockerService::for_database(app, database)?
+            .stdout(app, "Failed to run query", args)
+            .await
+    }
+
+    /// Whether the SQL console of a database refuses writes, from the setting and the session
+    pub fn guard_status(&self, database_id: &str, enforced: bool) -> SqlGuardStatus {
+        SqlGuardStatus {
+            enforced,
+            unlocked: UNLOCKED_WRITES.lock().unwrap().contains(database_id),
+        }
+    }
+
+    /// Allow or refuse writes from the SQL console of a database until the app quits
+    pub fn set_writes_unlocked(&self, database_id: &str, unlocked: bool) {
+        let mut unlocked_writes = UNLOCKED_WRITES.lock().unwrap();
+        if unlocked {
+            unlocked_writes.insert(database_id.to_string());
+        } else {
+            unlocked_writes.remove(database_id);
+        }
+    }
+
+    /// Run a script typed in the SQL console
+    /// While the guard blocks writes, statements that change data are refused before anything
+    /// runs and the rest runs in a read-only transaction
+    pub async fn run_console(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        sql: &str,
+        guard: SqlGuardStatus,
+    ) -> Result<SqlConsoleResult, AppError> {
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to query it",
+                database.name
+            )));
+        }
+        let dialect = SqlDialect::of(&database.db_type)?;
+        let statements = split_statements(sql, dialect).len();
+        if statements == 0 {
+            return Err(AppError::Validation("There is no SQL to run".to_string()));
+        }
+
+        let read_only = guard.blocks_writes();
+        if read_only {
+            if let Some(statement) = first_write_statement(sql, dialect) {
+                return Err(AppError::WriteBlocked { statement });
+            }
+        }
+
+        let started = Instant::now();
+        let env_file = Self::env_file(database)?;
+        let args = Self::console_args(
+            database,
+            env_file.as_ref().map(EnvFile::path),
+            sql,
+            read_only,
+        )?;
+        let output = self.run_client(app, database, &args).await?;
+        let (columns, rows) = SqlConsoleResult::parse_output(&output);
+
+        Ok(SqlConsoleResult {
+            columns,
+            rows,
+            statements,
+            read_only,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Write the result set of one query to a CSV or JSON file at `path` as rows arrive,
+    /// so millions of rows never sit in memory or cross IPC
+    /// The guard applies as in the console, a failed export leaves no partial file
+    pub async fn export_query(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        sql: &str,
+        guard: SqlGuardStatus,
+        format: QueryExportFormat,
+        path: &Path,
+    ) -> Result<QueryExportResult, AppError> {
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to export its data",
+                database.name
+            )));
+        }
+        let dialect = SqlDialect::of(&database.db_type)?;
+        let statements = split_statements(sql, dialect);
+        let [statement] = statements.as_slice() else {
+            return Err(AppError::Validation(
+                "Export one query at a time".to_string(),
+            ));
+        };
+
+        let read_only = guard.blocks_writes();
+        if read_only {
+            if let Some(statement) = first_write_statement(statement, dialect) {
+                return Err(AppError::WriteBlocked { statement });
+            }
+        }
+
+        // Kept until the export returns, Docker reads it when each exec starts
+        let env_file = Self::env_file(database)?;
+        let env_path = env_file.as_ref().map(EnvFile::path);
+
+        // COPY leaves out the column names, an empty run of the query gives them
+        let columns = match dialect {
+            SqlDialect::Postgres => {
+                let sql = format!("SELECT * FROM ({}) AS export LIMIT 0", statement);
+                let args = Self::console_args(database, env_path, &sql, read_only)?;
+                let output = self.run_client(app, database, &args).await?;
+                Some(SqlConsoleResult::parse_output(&output).0)
+            }
+            SqlDialect::MySql => None,
+        };
+
+        let args = Self::export_args(database, env_path, statement, read_only)?;
+        let result = self
+            .write_export(app, database, &args, dialect, columns, format, path)
+            .await;
+        if result.is_err() {
+            let _ = std::fs::remove_file(path);
+        }
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn write_export(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        args: &[String],
+        dialect: SqlDialect,
+        columns: Option<Vec<String>>,
+        format: QueryExportFormat,
+        path: &Path,
+    ) -> Result<QueryExportResult, AppError> {
+        let write_error =
+            |e: std::io::Error| AppError::Io(format!("Failed to write {}: {}", path.display(), e));
+        let mut file = BufWriter::new(File::create(path).map_err(write_error)?);
+        let mut bytes_written = 0;
+
+        let progress = |rows_written: u64, bytes_written: u64, done: bool| {
+            EventService::new().query_export_progress(
+                app,
+                &QueryExportProgressPayload {
+                    container_id: database.id.clone(),
+                    rows_written,
+                    bytes_written,
+                    done,
+                },
+            )
+        };
+
+        // MySQL's first line holds the column names
+        let mut writer = columns.map(|columns| QueryExportWriter::new(format, columns));
+        if let Some(writer) = &writer {
+            let start = writer.start();
+            file.write_all(start.as_bytes()).map_err(write_error)?;
+            bytes_written += start.len() as u64;
+        }
+
+        let (mut events, child) = DockerService::for_database(app, database)?
+            .command(app)
+            .await
+            .args(args)
+            .set_raw_out(true)
+            .spawn()
+            .map_err(|e| {
+                AppError::from_docker_output("Failed to export query", &e.to_string(), None)
+            })?;
+
+        // Bytes after the last line break of the output so far
+        let mut pending: Vec<u8> = Vec::new();
+        let mut stderr = String::new();
+        let mut exit_code = None;
+        let mut reported = 0;
+        let mut finished = false;
+        while !finished {
+            let lines = match events.recv().await {
+                Some(CommandEvent::Stdout(chunk)) => {
+                    pending.extend_from_slice(&chunk);
+                    match pending.iter().rposition(|byte| *byte == b'\n') {
+                        Some(end) => pending.drain(..=end).collect(),
+                        None => continue,
+                    }
+                }
+                Some(CommandEvent::Stderr(chunk)) => {
+                    stderr.push_str(&String::from_utf8_lossy(&chunk));
+                    continue;
+                }
+                Some(CommandEvent::Terminated(payload)) => {
+                    exit_code = payload.code;
+                    continue;
+                }
+                Some(_) => continue,
+                None => {
+                    finished = true;
+                    std::mem::take(&mut pending)
+                }
+            };
+
+            for line in String::from_utf8_lossy(&lines).lines() {
+                let text = match &mut writer {
+                    Some(writer) => {
+                        let fields: Vec<Option<String>> = line
+                            .split('\t')
+                            .map(|field| unescape_field(field, dialect))
+                            .collect();
+                        writer.row(&fields)
+                    }
+                    None => {
+                        let columns = line
+                            .split('\t')
+                            .map(|field| {
+                                unescape_field(field, dialect).unwrap_or_else(|| field.to_string())
+                            })
+                            .collect();
+                        writer
+                            .insert(QueryExportWriter::new(format, columns))
+                            .start()
+                    }
+                };
+                if let Err(e) = file.write_all(text.as_bytes()) {
+                    let _ = child.kill();
+                    return Err(write_error(e));
+                }
+                bytes_written += text.len() as u64;
+            }
+
+            let rows = writer.as_ref().map_or(0, QueryExportWriter::rows);
+            if rows - reported >= EXPORT_PROGRESS_ROWS {
+                reported = rows;
+                progress(rows, bytes_written, false);
+            }
+        }
+
+        if exit_code != Some(0) {
+            return Err(AppError::from_docker_output(
+                "Failed to export query",
+                &stderr,
+                exit_code,
+            ));
+        }
+
+        // A result without rows prints nothing on MySQL
+        let writer = writer.unwrap_or_else(|| QueryExportWriter::new(format, Vec::new()));
+        let end = writer.finish();
+        file.write_all(end.as_bytes()).map_err(write_error)?;
+        bytes_written += end.len() as u64;
+        file.flush().map_err(write_error)?;
+        file.get_ref().sync_all().map_err(write_error)?;
+
+        progress(writer.rows(), bytes_written, true);
+        Ok(QueryExportResult {
+            path: path.display().to_string(),
+            rows: writer.rows(),
+            bytes_written,
+        })
+    }
+
+    /// Tables and views outside the system schemas, with estimated row counts
+    pub async fn list_tables(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<Vec<TableInfo>, AppError> {
+        let sql = match SqlDialect::of(&database.db_type)? {
+            SqlDialect::Postgres => POSTGRES_TABLES_SQL,
+            SqlDialect::MySql => MYSQL_TABLES_SQL,
+        };
+
+        self.query_lines(app, database, sql)
+            .await?
+            .iter()
+            .map(|line| serde_json::from_str(line).map_err(Self::unexpected_output))
+            .collect()
+    }
+
+    pub async fn table_columns(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ColumnInfo>, AppError> {
+        let dialect = SqlDialect::of(&database.db_type)?;
+        let columns: Vec<ColumnInfo> = self
+            .query_lines(app, database, &Self::columns_sql(dialect, schema, table))
+            .await?
+            .iter()
+            .map(|line| Self::parse_column(line))
+            .collect::<Result<_, _>>()?;
+
+        if columns.is_empty() {
+            return Err(AppError::NotFound(format!("Table {}.{}", schema, table)));
+        }
+        Ok(columns)
+    }
+
+    /// Tables, keys and indexes of `target`, or of the database created at init
+    pub async fn get_schema(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        target: Option<&str>,
+    ) -> Result<DatabaseSchema, AppError> {
+        let dialect = SqlDialect::of(&database.db_type)?;
+        let target = target
+            .map(str::to_owned)
+            .unwrap_or_else(|| DatabaseCredentials::of(database).database);
+
+        let mut results = Vec::new();
+        for sql in Self::schema_sql(dialect, &target) {
+            let rows: Vec<Value> = self
+                .query_lines_in(app, database, Some(&target), &sql)
+                .await?
+                .iter()
+                .map(|line| serde_json::from_str(line).map_err(Self::unexpected_output))
+                .collect::<Result<_, _>>()?;
+            results.push(rows);
+        }
+
+        Ok(DatabaseSchema::from_catalog(
+            &target,
+            &results[0],
+            &results[1],
+            &results[2],
+        ))
+    }
+
+    /// One page of a table with its column metadata and the number of matching rows
+    pub async fn fetch_rows(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        query: &TableRowsQuery,
+    ) -> Result<TableRowsPage, AppError> {
+        let dialect = SqlDialect::of(&database.db_type)?;
+        let columns = self
+            .table_columns(app, database, &query.schema, &query.table)
+            .await?;
+
+        let lines = self
+            .query_lines(app, database, &query.select_sql(dialect, &columns)?)
+            .await?;
+        let rows = Self::parse_rows(&lines, &columns)?;
+
+        let total = self
+            .query_lines(app, database, &query.count_sql(dialect, &columns)?)
+            .await?
+            .first()
+            .and_then(|count| count.trim().parse().ok())
+            .unwrap_or_default();
+
+        Ok(TableRowsPage {
+            columns,
+            rows,
+            total,
+            offset: query.offset,
+            limit: query.limit,
+        })
+    }
+}
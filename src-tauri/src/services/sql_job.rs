@@ -0,0 +1,216 @@
+use crate::services::{
+    EventService, NotificationService, SchedulerService, SqlService, StorageService,
+};
+use crate::types::*;
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Manager};
+
+// Saves and recorded runs may overlap, the job list is read and written back as a whole
+static SQL_JOBS_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+// Jobs with a run in progress, a slow run is never started twice
+static RUNNING: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Runs recurring SQL scripts of databases through the scheduler and keeps their history
+pub struct SqlJobService;
+
+impl SqlJobService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Jobs of one database or of all of them, by name
+    pub async fn list(
+        &self,
+        app: &AppHandle,
+        database_id: Option<&str>,
+    ) -> Result<Vec<SqlJob>, AppError> {
+        let mut jobs: Vec<SqlJob> = StorageService::new()
+            .load_sql_jobs_from_store(app)
+            .await?
+            .into_iter()
+            .filter(|job| database_id.is_none_or(|id| job.database_id == id))
+            .collect();
+        jobs.sort_by_key(|job| job.name.to_lowercase());
+        Ok(jobs)
+    }
+
+    /// Add or update a job, jobs of removed containers are dropped on the way
+    pub async fn save(
+        &self,
+        app: &AppHandle,
+        job: SqlJob,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Result<SqlJob, AppError> {
+        let database = databases
+            .get(&job.database_id)
+            .ok_or_else(|| AppError::NotFound("Container".to_string()))?;
+        SqlDialect::of(&database.db_type)?;
+        SchedulerService::parse(&job.cron)?;
+
+        let _lock = SQL_JOBS_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut jobs = storage_service.load_sql_jobs_from_store(app).await?;
+        jobs.retain(|other| databases.contains_key(&other.database_id));
+        let saved = upsert_sql_job(&mut jobs, job, Local::now().to_rfc3339())?;
+        storage_service.save_sql_jobs_to_store(app, &jobs).await?;
+        Ok(saved)
+    }
+
+    pub async fn delete(&self, app: &AppHandle, job_id: &str) -> Result<(), AppError> {
+        let _lock = SQL_JOBS_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut jobs = storage_service.load_sql_jobs_from_store(app).await?;
+        let before = jobs.len();
+        jobs.retain(|job| job.id != job_id);
+        if jobs.len() == before {
+            return Err(AppError::NotFound("SQL job".to_string()));
+        }
+        storage_service.save_sql_jobs_to_store(app, &jobs).await
+    }
+
+    /// Drop the jobs of removed databases
+    pub async fn remove_for_databases(
+        &self,
+        app: &AppHandle,
+        database_ids: &[String],
+    ) -> Result<(), AppError> {
+        let _lock = SQL_JOBS_LOCK.lock().await;
+        let storage_service = StorageService::new();
+        let mut jobs = storage_service.load_sql_jobs_from_store(app).await?;
+        let before = jobs.len();
+        jobs.retain(|job| !database_ids.contains(&job.database_id));
+        if jobs.len() == before {
+            return Ok(());
+        }
+        storage_service.save_sql_jobs_to_store(app, &jobs).await
+    }
+
+    /// Run a job right away, outside its schedule
+    pub async fn run_now(&self, app: &AppHandle, job_id: &str) -> Result<SqlJob, AppError> {
+        let job = StorageService::new()
+            .load_sql_jobs_from_store(app)
+            .await?
+            .into_iter()
+            .find(|job| job.id == job_id)
+            .ok_or_else(|| AppError::NotFound("SQL job".to_string()))?;
+        if !RUNNING.lock().unwrap().insert(job.id.clone()) {
+            return Err(AppError::Validation(format!(
+                "'{}' is already running",
+                job.name
+            )));
+        }
+
+        self.run(app, &job, true).await
+    }
+
+    /// Start the runs of every due job in the background and record the handled occurrences
+    /// Called from the scheduler's tick
+    pub async fn run_due(&self, app: &AppHandle, now: &DateTime<Local>) -> Result<(), AppError> {
+        let due: Vec<SqlJob> = {
+            let _lock = SQL_JOBS_LOCK.lock().await;
+            let storage_service = StorageService::new();
+            let mut jobs = storage_service.load_sql_jobs_from_store(app).await?;
+            let mut due = Vec::new();
+            let mut handled = false;
+
+            for job in jobs.iter_mut().filter(|job| job.enabled) {
+                let Ok(cron) = SchedulerService::parse(&job.cron) else {
+                    continue;
+                };
+                let Some((occurrence, run)) = SchedulerService::due_occurrence(
+                    &cron,
+                    &job.created_at,
+                    job.last_run_at.as_deref(),
+                    now,
+                ) else {
+                    continue;
+                };
+
+                // An occurrence reached while the previous run goes on is skipped
+                if run && RUNNING.lock().unwrap().insert(job.id.clone()) {
+                    due.push(job.clone());
+                }
+                job.last_run_at = Some(occurrence.to_rfc3339());
+                handled = true;
+            }
+
+            if handled {
+                storage_service.save_sql_jobs_to_store(app, &jobs).await?;
+            }
+            due
+        };
+
+        for job in due {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                // Failures end up in the job's history
+                let _ = SqlJobService::new().run(&app, &job, false).await;
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Run a job claimed in `RUNNING`, record the run and notify when it failed
+    async fn run(&self, app: &AppHandle, job: &SqlJob, manual: bool) -> Result<SqlJob, AppError> {
+        let database = {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            db_map.get(&job.database_id).cloned()
+        };
+        // Read-only mode makes jobs run in read-only transactions, writes then fail
+        let read_only = {
+            let settings = app.state::<SettingsStore>();
+            let settings = settings.lock().unwrap();
+            settings.read_only
+        };
+        let guard = SqlGuardStatus {
+            enforced: read_only,
+            unlocked: false,
+        };
+
+        let started_at = chrono::Utc::now().to_rfc3339();
+        let result = match &database {
+            Some(database) => {
+                SqlService::new()
+                    .run_console(app, database, &job.sql, guard)
+                    .await
+            }
+            None => Err(AppError::NotFound("Container".to_string())),
+        };
+        RUNNING.lock().unwrap().remove(&job.id);
+
+        let run = SqlJobRun {
+            started_at,
+            finished_at: chrono::Utc::now().to_rfc3339(),
+            success: result.is_ok(),
+            statements: result.as_ref().map_or(0, |result| result.statements),
+            error: result.err().map(|error| error.to_string()),
+            manual,
+        };
+
+        let updated = {
+            let _lock = SQL_JOBS_LOCK.lock().await;
+            let storage_service = StorageService::new();
+            let mut jobs = storage_service.load_sql_jobs_from_store(app).await?;
+            // Deleted while it ran
+            let Some(stored) = jobs.iter_mut().find(|stored| stored.id == job.id) else {
+                return Err(AppError::NotFound("SQL job".to_string()));
+            };
+            stored.record(run.clone());
+            let updated = stored.clone();
+            storage_service.save_sql_jobs_to_store(app, &jobs).await?;
+            updated
+        };
+
+        EventService::new().sql_job_completed(app, &updated);
+        if let (Some(error), Some(database)) = (&run.error, &database) {
+            if updated.notify_on_failure {
+                NotificationService::new().sql_job_failed(app, &updated, database, error);
+            }
+        }
+        Ok(updated)
+    }
+}
@@ -1,8 +1,89 @@
+use crate::services::SecretsService;
 use crate::types::*;
-use serde_json::json;
-use std::collections::HashMap;
-use tauri::AppHandle;
-use tauri_plugin_store::StoreExt;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+const DATABASES_FILE: &str = "databases.json";
+pub const SETTINGS_FILE: &str = "settings.json";
+// Sampled metrics are rewritten every few minutes, apart from the databases so the store stays small
+const METRICS_HISTORY_FILE: &str = "metrics_history.json";
+const WORKSPACES_DIR: &str = "workspaces";
+
+// Serializes read-modify-write cycles, several keys share the same file
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+// Key of the encrypted payload in an encrypted store file
+const ENCRYPTED_KEY: &str = "encrypted";
+// Keychain entry holding the generated store key
+const STORE_KEY_SECRET: &str = "store/encryption-key";
+const KDF_PASSPHRASE: &str = "passphrase";
+const KDF_KEYCHAIN: &str = "keychain";
+
+// Key used to encrypt the store files, `None` while encryption is off or the store is locked
+static STORE_KEY: Mutex<Option<StoreKey>> = Mutex::new(None);
+
+/// Key used to encrypt the persisted store
+#[derive(Clone)]
+pub struct StoreKey {
+    kdf: String,
+    salt: Option<Vec<u8>>,
+    key: [u8; 32],
+}
+
+impl StoreKey {
+    /// Derive a key from a user passphrase with Argon2id
+    pub fn from_passphrase(passphrase: &str, salt: &[u8]) -> Result<Self, AppError> {
+        let mut key = [0u8; 32];
+        argon2::Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| AppError::Internal(format!("Failed to derive store key: {}", e)))?;
+
+        Ok(Self {
+            kdf: KDF_PASSPHRASE.to_string(),
+            salt: Some(salt.to_vec()),
+            key,
+        })
+    }
+
+    /// Random key meant to be kept in the keychain
+    pub fn generate() -> Self {
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+
+        Self {
+            kdf: KDF_KEYCHAIN.to_string(),
+            salt: None,
+            key,
+        }
+    }
+
+    pub fn random_salt() -> Vec<u8> {
+        let mut salt = vec![0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        salt
+    }
+}
+
+/// Payload of an encrypted store file
+#[derive(Serialize, Deserialize)]
+struct EncryptedStore {
+    version: u32,
+    kdf: String,
+    salt: Option<String>,
+    nonce: String,
+    ciphertext: String,
+}
 
 pub struct StorageService;
 
@@ -11,48 +92,1118 @@ impl StorageService {
         Self
     }
 
-    pub async fn save_databases_to_store(
+    /// Copy of the last good store, refreshed before every save
+    pub fn backup_path(path: &Path) -> PathBuf {
+        Self::sibling_path(path, "bak")
+    }
+
+    /// Where an unreadable store is moved so it is not overwritten
+    pub fn corrupt_path(path: &Path) -> PathBuf {
+        Self::sibling_path(path, "corrupt")
+    }
+
+    fn temp_path(path: &Path) -> PathBuf {
+        Self::sibling_path(path, "tmp")
+    }
+
+    fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(format!(".{}", extension));
+        path.with_file_name(file_name)
+    }
+
+    /// Parse a store file, `None` when it does not exist
+    fn parse_store_file(
+        path: &Path,
+    ) -> Result<Option<Result<Map<String, Value>, String>>, AppError> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(Some(
+                serde_json::from_slice::<Map<String, Value>>(&bytes).map_err(|e| e.to_string()),
+            )),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::Storage(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Read a store file, falling back to its backup when it is corrupted or truncated
+    /// The damaged file is kept aside and the backup put back in its place, an empty store is
+    /// returned if the backup is unusable too
+    pub fn read_store_file(path: &Path) -> Result<Map<String, Value>, AppError> {
+        match Self::parse_store_file(path)? {
+            None => Ok(Map::new()),
+            Some(Ok(store)) => Ok(store),
+            Some(Err(_)) => {
+                let backup_path = Self::backup_path(path);
+                let recovered = match Self::parse_store_file(&backup_path)? {
+                    Some(Ok(backup)) => Some(backup),
+                    _ => None,
+                };
+
+                fs::rename(path, Self::corrupt_path(path)).map_err(|e| {
+                    AppError::Storage(format!(
+                        "Failed to move corrupted store {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+
+                match recovered {
+                    Some(backup) => {
+                        Self::restore_backup(path, &backup_path)?;
+                        Ok(backup)
+                    }
+                    None => Ok(Map::new()),
+                }
+            }
+        }
+    }
+
+    /// Put a copy of the backup in place of the store through a flushed temp file, so the
+    /// next read finds a good store and the next save keeps the backup
+    fn restore_backup(path: &Path, backup_path: &Path) -> Result<(), AppError> {
+        let storage_error = |action: &str, e: std::io::Error| {
+            AppError::Storage(format!("Failed to {} {}: {}", action, path.display(), e))
+        };
+
+        let temp_path = Self::temp_path(path);
+        fs::copy(backup_path, &temp_path).map_err(|e| storage_error("restore", e))?;
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&temp_path)
+            .and_then(|file| file.sync_all())
+            .map_err(|e| storage_error("flush", e))?;
+        fs::rename(&temp_path, path).map_err(|e| storage_error("restore", e))
+    }
+
+    /// Write a store file atomically: write a temp file, flush it to disk, then rename it over
+    /// the store. The previous store is kept as a backup when it is still readable
+    pub fn write_store_file(path: &Path, store: &Map<String, Value>) -> Result<(), AppError> {
+        let storage_error = |action: &str, e: std::io::Error| {
+            AppError::Storage(format!("Failed to {} {}: {}", action, path.display(), e))
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| storage_error("create directory for", e))?;
+        }
+
+        let bytes = serde_json::to_vec_pretty(store)
+            .map_err(|e| AppError::Storage(format!("Failed to serialize store: {}", e)))?;
+
+        let temp_path = Self::temp_path(path);
+        {
+            let mut file = File::create(&temp_path).map_err(|e| storage_error("write", e))?;
+            file.write_all(&bytes)
+                .map_err(|e| storage_error("write", e))?;
+            file.sync_all().map_err(|e| storage_error("flush", e))?;
+        }
+
+        // Never replace a good backup with a damaged store
+        if let Some(Ok(_)) = Self::parse_store_file(path)? {
+            fs::copy(path, Self::backup_path(path)).map_err(|e| storage_error("back up", e))?;
+        }
+
+        fs::rename(&temp_path, path).map_err(|e| storage_error("replace", e))?;
+
+        // Persist the rename itself, not supported on every platform
+        if let Some(parent) = path.parent() {
+            if let Ok(dir) = File::open(parent) {
+                let _ = dir.sync_all();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encrypt a store with AES-256-GCM
+    pub fn encrypt_store(
+        store: &Map<String, Value>,
+        key: &StoreKey,
+    ) -> Result<Map<String, Value>, AppError> {
+        let plaintext = serde_json::to_vec(store)?;
+        let cipher = Aes256Gcm::new(&key.key.into());
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_ref())
+            .map_err(|e| AppError::Internal(format!("Failed to encrypt store: {}", e)))?;
+
+        let envelope = EncryptedStore {
+            version: 1,
+            kdf: key.kdf.clone(),
+            salt: key.salt.as_ref().map(|salt| BASE64.encode(salt)),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+
+        let mut encrypted = Map::new();
+        encrypted.insert(ENCRYPTED_KEY.to_string(), serde_json::to_value(envelope)?);
+        Ok(encrypted)
+    }
+
+    /// Decrypt a store, plaintext stores are returned unchanged
+    pub fn decrypt_store(
+        store: Map<String, Value>,
+        key: Option<&StoreKey>,
+    ) -> Result<Map<String, Value>, AppError> {
+        let Some(envelope) = Self::encrypted_envelope(&store)? else {
+            return Ok(store);
+        };
+        let key = key.ok_or(AppError::StoreLocked)?;
+
+        let decode = |value: &str| {
+            BASE64
+                .decode(value)
+                .map_err(|e| AppError::Storage(format!("Invalid encrypted store: {}", e)))
+        };
+        let nonce = decode(&envelope.nonce)?;
+        let ciphertext = decode(&envelope.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err(AppError::Storage(
+                "Invalid encrypted store nonce".to_string(),
+            ));
+        }
+
+        let plaintext = Aes256Gcm::new(&key.key.into())
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| {
+                AppError::Validation("Incorrect passphrase or damaged store".to_string())
+            })?;
+
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| AppError::Storage(format!("Failed to deserialize store: {}", e)))
+    }
+
+    fn encrypted_envelope(store: &Map<String, Value>) -> Result<Option<EncryptedStore>, AppError> {
+        match store.get(ENCRYPTED_KEY) {
+            Some(value) => serde_json::from_value(value.clone())
+                .map(Some)
+                .map_err(|e| AppError::Storage(format!("Invalid encrypted store: {}", e))),
+            None => Ok(None),
+        }
+    }
+
+    /// Key for an encrypted store, loading it from the keychain when it lives there
+    fn resolve_store_key(envelope: &EncryptedStore) -> Result<Option<StoreKey>, AppError> {
+        if let Some(key) = STORE_KEY.lock().unwrap().clone() {
+            return Ok(Some(key));
+        }
+
+        if envelope.kdf != KDF_KEYCHAIN {
+            return Ok(None);
+        }
+
+        let Some(encoded) = SecretsService::new().get_secret(STORE_KEY_SECRET)? else {
+            return Ok(None);
+        };
+        let bytes = BASE64
+            .decode(encoded)
+            .map_err(|e| AppError::Secrets(format!("Invalid store key: {}", e)))?;
+        let key: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| AppError::Secrets("Invalid store key length".to_string()))?;
+
+        let key = StoreKey {
+            kdf: KDF_KEYCHAIN.to_string(),
+            salt: None,
+            key,
+        };
+        *STORE_KEY.lock().unwrap() = Some(key.clone());
+
+        Ok(Some(key))
+    }
+
+    /// Read and decrypt a store file
+    fn open_store(path: &Path) -> Result<Map<String, Value>, AppError> {
+        let store = Self::read_store_file(path)?;
+        let key = match Self::encrypted_envelope(&store)? {
+            Some(envelope) => Self::resolve_store_key(&envelope)?,
+            None => None,
+        };
+
+        Self::decrypt_store(store, key.as_ref())
+    }
+
+    /// Encrypt a store when encryption is enabled, then write it
+    fn seal_store(path: &Path, store: &Map<String, Value>) -> Result<(), AppError> {
+        let key = STORE_KEY.lock().unwrap().clone();
+        match key {
+            Some(key) => Self::write_store_file(path, &Self::encrypt_store(store, &key)?),
+            None => Self::write_store_file(path, store),
+        }
+    }
+
+    /// Rewrite every store file with the current key
+    /// Written twice so the backup does not keep the previous format
+    fn rewrite_stores(stores: &[(PathBuf, Map<String, Value>)]) -> Result<(), AppError> {
+        for (path, store) in stores {
+            Self::seal_store(path, store)?;
+            Self::seal_store(path, store)?;
+        }
+        Ok(())
+    }
+
+    fn read_all_stores(app: &AppHandle) -> Result<Vec<(PathBuf, Map<String, Value>)>, AppError> {
+        let mut files = vec![
+            PathBuf::from(SETTINGS_FILE),
+            PathBuf::from(METRICS_HISTORY_FILE),
+        ];
+        {
+            let workspaces = app.state::<WorkspaceStore>();
+            let workspaces = workspaces.lock().unwrap();
+            files.extend(
+                workspaces
+                    .workspaces
+                    .iter()
+                    .map(|workspace| Self::workspace_databases_file(&workspace.id)),
+            );
+        }
+
+        files
+            .iter()
+            .map(|file| {
+                let path = Self::store_path(app, file)?;
+                let store = Self::open_store(&path)?;
+                Ok((path, store))
+            })
+            .collect()
+    }
+
+    /// Encrypt the store with a passphrase, or with a key kept in the keychain
+    pub async fn enable_encryption(
         &self,
         app: &AppHandle,
-        databases: &HashMap<String, DatabaseContainer>,
-    ) -> Result<(), String> {
-        let path = std::path::PathBuf::from("databases.json");
+        passphrase: Option<String>,
+    ) -> Result<(), AppError> {
+        let _guard = STORE_LOCK.lock().unwrap();
+        let stores = Self::read_all_stores(app)?;
 
-        let store = app
-            .store(path)
-            .map_err(|e| format!("Failed to access store: {}", e))?;
+        let key = match passphrase {
+            Some(passphrase) => StoreKey::from_passphrase(&passphrase, &StoreKey::random_salt())?,
+            None => {
+                let key = StoreKey::generate();
+                SecretsService::new().set_secret(STORE_KEY_SECRET, &BASE64.encode(key.key))?;
+                key
+            }
+        };
 
-        let databases_vec: Vec<DatabaseContainer> = databases.values().cloned().collect();
+        *STORE_KEY.lock().unwrap() = Some(key);
+        Self::rewrite_stores(&stores)
+    }
 
-        store.set("databases".to_string(), json!(databases_vec));
-        store
-            .save()
-            .map_err(|e| format!("Failed to save store: {}", e))?;
+    /// Decrypt the store and write it back in plaintext
+    pub async fn disable_encryption(&self, app: &AppHandle) -> Result<(), AppError> {
+        let _guard = STORE_LOCK.lock().unwrap();
+        let stores = Self::read_all_stores(app)?;
 
+        *STORE_KEY.lock().unwrap() = None;
+        Self::rewrite_stores(&stores)?;
+
+        let _ = SecretsService::new().delete_secret(STORE_KEY_SECRET);
         Ok(())
     }
 
-    pub async fn load_databases_from_store(
+    /// Unlock a passphrase-encrypted store for this session
+    pub async fn unlock(&self, app: &AppHandle, passphrase: &str) -> Result<(), AppError> {
+        Self::unlock_path(
+            &Self::store_path(app, &Self::databases_file(app))?,
+            passphrase,
+        )
+    }
+
+    /// Unlock with the passphrase of the store file at `path`
+    pub fn unlock_path(path: &Path, passphrase: &str) -> Result<(), AppError> {
+        let _guard = STORE_LOCK.lock().unwrap();
+
+        let store = Self::read_store_file(path)?;
+        let Some(envelope) = Self::encrypted_envelope(&store)? else {
+            return Ok(());
+        };
+        let salt = envelope
+            .salt
+            .as_deref()
+            .map(|salt| BASE64.decode(salt))
+            .transpose()
+            .map_err(|e| AppError::Storage(format!("Invalid encrypted store: {}", e)))?
+            .ok_or_else(|| {
+                AppError::Validation("The store is not passphrase protected".to_string())
+            })?;
+
+        let key = StoreKey::from_passphrase(passphrase, &salt)?;
+        Self::decrypt_store(store, Some(&key))?;
+
+        *STORE_KEY.lock().unwrap() = Some(key);
+        Ok(())
+    }
+
+    pub async fn encryption_status(
         &self,
         app: &AppHandle,
-    ) -> Result<HashMap<String, DatabaseContainer>, String> {
-        let path = std::path::PathBuf::from("databases.json");
+    ) -> Result<StoreEncryptionStatus, AppError> {
+        let path = Self::store_path(app, &Self::databases_file(app))?;
+        let _guard = STORE_LOCK.lock().unwrap();
+
+        let store = Self::read_store_file(&path)?;
+        let status = match Self::encrypted_envelope(&store)? {
+            Some(envelope) => StoreEncryptionStatus {
+                enabled: true,
+                locked: Self::resolve_store_key(&envelope)?.is_none(),
+                mode: Some(envelope.kdf),
+            },
+            None => StoreEncryptionStatus::default(),
+        };
+
+        Ok(status)
+    }
+
+    /// Store file of a workspace, relative to the app data directory
+    pub fn workspace_databases_file(workspace_id: &str) -> PathBuf {
+        if workspace_id == DEFAULT_WORKSPACE_ID {
+            PathBuf::from(DATABASES_FILE)
+        } else {
+            Path::new(WORKSPACES_DIR)
+                .join(workspace_id)
+                .join(DATABASES_FILE)
+        }
+    }
+
+    /// Store file of the active workspace
+    fn databases_file(app: &AppHandle) -> PathBuf {
+        let workspaces = app.state::<WorkspaceStore>();
+        let active = workspaces.lock().unwrap().active.clone();
+        Self::workspace_databases_file(&active)
+    }
+
+    fn store_path(app: &AppHandle, file: &Path) -> Result<PathBuf, AppError> {
+        let data_dir = app.path().app_data_dir().map_err(|e| {
+            AppError::Storage(format!("Failed to resolve app data directory: {}", e))
+        })?;
+
+        Ok(data_dir.join(file))
+    }
+
+    fn get_value(app: &AppHandle, file: &Path, key: &str) -> Result<Option<Value>, AppError> {
+        Self::read_value_at(&Self::store_path(app, file)?, key)
+    }
+
+    fn set_value(app: &AppHandle, file: &Path, key: &str, value: Value) -> Result<(), AppError> {
+        Self::write_value_at(&Self::store_path(app, file)?, key, value)
+    }
+
+    /// Read-modify-write one key of a store file under a single lock
+    fn update_value(
+        app: &AppHandle,
+        file: &Path,
+        key: &str,
+        update: impl FnOnce(Option<Value>) -> Value,
+    ) -> Result<(), AppError> {
+        let path = Self::store_path(app, file)?;
+        let _guard = STORE_LOCK.lock().unwrap();
+
+        let mut store = Self::open_store(&path)?;
+        let value = update(store.remove(key));
+        store.insert(key.to_string(), value);
+        Self::seal_store(&path, &store)
+    }
 
-        let store = app
-            .store(path)
-            .map_err(|e| format!("Failed to access store: {}", e))?;
+    /// Read one key of the store file at `path`, decrypting it if needed
+    pub fn read_value_at(path: &Path, key: &str) -> Result<Option<Value>, AppError> {
+        let _guard = STORE_LOCK.lock().unwrap();
 
+        let mut store = Self::open_store(path)?;
+        Ok(store.remove(key))
+    }
+
+    /// Replace one key of the store file at `path`, keeping the others
+    pub fn write_value_at(path: &Path, key: &str, value: Value) -> Result<(), AppError> {
+        let _guard = STORE_LOCK.lock().unwrap();
+
+        let mut store = Self::open_store(path)?;
+        store.insert(key.to_string(), value);
+        Self::seal_store(path, &store)
+    }
+
+    /// Stored form of the databases, passwords go to the keychain and the file only keeps their keys
+    pub fn databases_to_value(
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Result<Value, AppError> {
+        let secrets_service = SecretsService::new();
+
+        let databases_vec = databases
+            .values()
+            .cloned()
+            .map(|db| secrets_service.externalize_password(db))
+            .collect::<Result<Vec<DatabaseContainer>, AppError>>()?;
+
+        Ok(json!(databases_vec))
+    }
+
+    /// Databases from their stored form with passwords resolved
+    /// The flag tells whether entries from earlier versions need saving again
+    pub fn databases_from_value(
+        value: Value,
+    ) -> Result<(HashMap<String, DatabaseContainer>, bool), AppError> {
+        let secrets_service = SecretsService::new();
         let mut database_map = HashMap::new();
+        let mut needs_migration = false;
 
-        if let Some(value) = store.get("databases") {
-            let databases_vec: Vec<DatabaseContainer> = serde_json::from_value(value.clone())
-                .map_err(|e| format!("Failed to deserialize databases: {}", e))?;
+        let databases_vec: Vec<DatabaseContainer> = serde_json::from_value(value)
+            .map_err(|e| AppError::Storage(format!("Failed to deserialize databases: {}", e)))?;
 
-            for db in databases_vec {
-                database_map.insert(db.id.clone(), db);
+        for mut db in databases_vec {
+            needs_migration |= db.stored_password.is_some();
+            needs_migration |= db.migrate_created_at();
+            needs_migration |= db.migrate_status();
+            let stored = db.clone();
+            secrets_service.resolve_password(&mut db)?;
+            // Entries from earlier versions kept the password in their run arguments
+            if let Some(password) = &db.stored_password {
+                needs_migration |= SecretsService::holds_password(&stored, password);
             }
+            database_map.insert(db.id.clone(), db);
+        }
+
+        Ok((database_map, needs_migration))
+    }
+
+    /// Rewrite every database entry, for changes that replace the whole set
+    pub async fn save_databases_to_store(
+        &self,
+        app: &AppHandle,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "databases",
+            Self::databases_to_value(databases)?,
+        )
+    }
+
+    /// Replace or add the stored entries of the given databases and drop the removed ones
+    /// Other entries are kept as stored, so only changed passwords reach the keychain
+    pub fn merge_database_entries(
+        stored: Option<Value>,
+        upserts: Vec<Value>,
+        removed: &[String],
+    ) -> Value {
+        let entry_id = |entry: &Value| entry.get("id").and_then(Value::as_str).map(str::to_owned);
+        let removed: HashSet<&str> = removed.iter().map(String::as_str).collect();
+
+        let mut entries = match stored {
+            Some(Value::Array(entries)) => entries,
+            _ => Vec::new(),
+        };
+        entries.retain(|entry| entry_id(entry).is_none_or(|id| !removed.contains(id.as_str())));
+
+        // Position of each entry by id
+        let mut positions: HashMap<String, usize> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| Some((entry_id(entry)?, index)))
+            .collect();
+        for upsert in upserts {
+            match entry_id(&upsert) {
+                Some(id) => match positions.get(&id) {
+                    Some(&index) => entries[index] = upsert,
+                    None => {
+                        positions.insert(id, entries.len());
+                        entries.push(upsert);
+                    }
+                },
+                None => entries.push(upsert),
+            }
+        }
+
+        Value::Array(entries)
+    }
+
+    /// Persist only the given databases instead of rewriting every entry
+    pub async fn upsert_databases_in_store(
+        &self,
+        app: &AppHandle,
+        databases: &[DatabaseContainer],
+    ) -> Result<(), AppError> {
+        let secrets_service = SecretsService::new();
+        let upserts = databases
+            .iter()
+            .cloned()
+            .map(|db| Ok(json!(secrets_service.externalize_password(db)?)))
+            .collect::<Result<Vec<Value>, AppError>>()?;
+
+        Self::update_value(app, &Self::databases_file(app), "databases", |stored| {
+            Self::merge_database_entries(stored, upserts, &[])
+        })
+    }
+
+    /// Persist the in-memory state of the given databases
+    pub async fn upsert_databases_by_id(
+        &self,
+        app: &AppHandle,
+        database_ids: &[String],
+    ) -> Result<(), AppError> {
+        let changed: Vec<DatabaseContainer> = {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            database_ids
+                .iter()
+                .filter_map(|id| db_map.get(id).cloned())
+                .collect()
+        };
+
+        if changed.is_empty() {
+            return Ok(());
+        }
+        self.upsert_databases_in_store(app, &changed).await
+    }
+
+    /// Drop the stored entries of removed databases
+    pub async fn delete_databases_from_store(
+        &self,
+        app: &AppHandle,
+        database_ids: &[String],
+    ) -> Result<(), AppError> {
+        Self::update_value(app, &Self::databases_file(app), "databases", |stored| {
+            Self::merge_database_entries(stored, Vec::new(), database_ids)
+        })
+    }
+
+    pub async fn load_databases_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<HashMap<String, DatabaseContainer>, AppError> {
+        let Some(value) = Self::get_value(app, &Self::databases_file(app), "databases")? else {
+            return Ok(HashMap::new());
+        };
+        let (database_map, needs_migration) = Self::databases_from_value(value)?;
+
+        // Migrate plaintext passwords and date-only creation times saved by earlier versions
+        if needs_migration {
+            self.save_databases_to_store(app, &database_map).await?;
         }
 
         Ok(database_map)
     }
+
+    pub async fn save_companions_to_store(
+        &self,
+        app: &AppHandle,
+        companions: &HashMap<String, CompanionContainer>,
+    ) -> Result<(), AppError> {
+        let companions_vec: Vec<CompanionContainer> = companions.values().cloned().collect();
+
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "companions",
+            json!(companions_vec),
+        )
+    }
+
+    pub async fn load_companions_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<HashMap<String, CompanionContainer>, AppError> {
+        let mut companion_map = HashMap::new();
+
+        if let Some(value) = Self::get_value(app, &Self::databases_file(app), "companions")? {
+            let companions_vec: Vec<CompanionContainer> =
+                serde_json::from_value(value).map_err(|e| {
+                    AppError::Storage(format!("Failed to deserialize companions: {}", e))
+                })?;
+
+            for companion in companions_vec {
+                companion_map.insert(companion.id.clone(), companion);
+            }
+        }
+
+        Ok(companion_map)
+    }
+
+    pub async fn save_settings_to_store(
+        &self,
+        app: &AppHandle,
+        settings: &AppSettings,
+    ) -> Result<(), AppError> {
+        Self::set_value(app, Path::new(SETTINGS_FILE), "settings", json!(settings))
+    }
+
+    pub async fn load_settings_from_store(&self, app: &AppHandle) -> Result<AppSettings, AppError> {
+        match Self::get_value(app, Path::new(SETTINGS_FILE), "settings")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize settings: {}", e))),
+            None => Ok(AppSettings::default()),
+        }
+    }
+
+    /// Saved window geometries, keyed by `window_geometry_key`
+    /// Not async: windows are placed from synchronous window and run event handlers
+    pub fn load_window_geometries(
+        app: &AppHandle,
+    ) -> Result<HashMap<String, WindowGeometry>, AppError> {
+        match Self::get_value(app, Path::new(SETTINGS_FILE), "windows")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize window geometry: {}", e))
+            }),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    pub fn save_window_geometries(
+        app: &AppHandle,
+        geometries: &HashMap<String, WindowGeometry>,
+    ) -> Result<(), AppError> {
+        Self::set_value(app, Path::new(SETTINGS_FILE), "windows", json!(geometries))
+    }
+
+    pub async fn save_workspaces_to_store(
+        &self,
+        app: &AppHandle,
+        workspaces: &WorkspaceConfig,
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            Path::new(SETTINGS_FILE),
+            "workspaces",
+            json!(workspaces),
+        )
+    }
+
+    pub async fn load_workspaces_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<WorkspaceConfig, AppError> {
+        match Self::get_value(app, Path::new(SETTINGS_FILE), "workspaces")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize workspaces: {}", e))),
+            None => Ok(WorkspaceConfig::default()),
+        }
+    }
+
+    /// Save the schedules of the active workspace
+    pub async fn save_schedules_to_store(
+        &self,
+        app: &AppHandle,
+        schedules: &[ContainerSchedule],
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "schedules",
+            json!(schedules),
+        )
+    }
+
+    /// Schedules of the active workspace
+    /// Schedules used to be app-wide, the default workspace takes over the ones saved back then
+    pub async fn load_schedules_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<ContainerSchedule>, AppError> {
+        let file = Self::databases_file(app);
+        let value = match Self::get_value(app, &file, "schedules")? {
+            Some(value) => Some(value),
+            None if file.as_path() == Path::new(DATABASES_FILE) => {
+                Self::get_value(app, Path::new(SETTINGS_FILE), "schedules")?
+            }
+            None => None,
+        };
+        match value {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize schedules: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Databases stored in a workspace other than the active one
+    pub async fn load_workspace_databases(
+        &self,
+        app: &AppHandle,
+        workspace_id: &str,
+    ) -> Result<Vec<DatabaseContainer>, AppError> {
+        let file = Self::workspace_databases_file(workspace_id);
+
+        match Self::get_value(app, &file, "databases")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize databases: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Delete the store file of a workspace, with its backups
+    pub async fn remove_workspace_store(
+        &self,
+        app: &AppHandle,
+        workspace_id: &str,
+    ) -> Result<(), AppError> {
+        let dir = Self::store_path(app, Path::new(WORKSPACES_DIR))?.join(workspace_id);
+        let _guard = STORE_LOCK.lock().unwrap();
+
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| {
+                AppError::Storage(format!("Failed to remove {}: {}", dir.display(), e))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn save_groups_to_store(
+        &self,
+        app: &AppHandle,
+        groups: &[ContainerGroup],
+    ) -> Result<(), AppError> {
+        Self::set_value(app, &Self::databases_file(app), "groups", json!(groups))
+    }
+
+    pub async fn load_groups_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<ContainerGroup>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "groups")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize groups: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_snapshots_to_store(
+        &self,
+        app: &AppHandle,
+        snapshots: &[Snapshot],
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "snapshots",
+            json!(snapshots),
+        )
+    }
+
+    pub async fn load_snapshots_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<Snapshot>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "snapshots")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize snapshots: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_benchmarks_to_store(
+        &self,
+        app: &AppHandle,
+        benchmarks: &[BenchmarkRun],
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "benchmarks",
+            json!(benchmarks),
+        )
+    }
+
+    pub async fn load_benchmarks_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<BenchmarkRun>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "benchmarks")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize benchmarks: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_tunnels_to_store(
+        &self,
+        app: &AppHandle,
+        tunnels: &[TunnelDefinition],
+    ) -> Result<(), AppError> {
+        Self::set_value(app, &Self::databases_file(app), "tunnels", json!(tunnels))
+    }
+
+    pub async fn load_tunnels_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<TunnelDefinition>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "tunnels")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize tunnels: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_pending_creates_to_store(
+        &self,
+        app: &AppHandle,
+        pending: &[PendingCreate],
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "pending_creates",
+            json!(pending),
+        )
+    }
+
+    pub async fn load_pending_creates_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<PendingCreate>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "pending_creates")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize pending creates: {}", e))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_alert_rules_to_store(
+        &self,
+        app: &AppHandle,
+        rules: &[AlertRule],
+    ) -> Result<(), AppError> {
+        Self::set_value(app, &Self::databases_file(app), "alert_rules", json!(rules))
+    }
+
+    pub async fn load_alert_rules_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<AlertRule>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "alert_rules")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize alert rules: {}", e))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_alert_history_to_store(
+        &self,
+        app: &AppHandle,
+        history: &[AlertEvent],
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "alert_history",
+            json!(history),
+        )
+    }
+
+    pub async fn load_alert_history_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<AlertEvent>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "alert_history")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize alert history: {}", e))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_integrity_checks_to_store(
+        &self,
+        app: &AppHandle,
+        checks: &[IntegrityCheckRun],
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "integrity_checks",
+            json!(checks),
+        )
+    }
+
+    pub async fn load_integrity_checks_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<IntegrityCheckRun>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "integrity_checks")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize integrity checks: {}", e))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_released_images_to_store(
+        &self,
+        app: &AppHandle,
+        images: &[ReleasedImage],
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "released_images",
+            json!(images),
+        )
+    }
+
+    pub async fn load_released_images_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<ReleasedImage>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "released_images")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize released images: {}", e))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_uptime_history_to_store(
+        &self,
+        app: &AppHandle,
+        history: &UptimeHistory,
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "uptime_history",
+            json!(history),
+        )
+    }
+
+    pub async fn load_uptime_history_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<UptimeHistory, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "uptime_history")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize uptime history: {}", e))
+            }),
+            None => Ok(UptimeHistory::default()),
+        }
+    }
+
+    pub async fn save_exec_history_to_store(
+        &self,
+        app: &AppHandle,
+        history: &ExecHistory,
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            &Self::databases_file(app),
+            "exec_history",
+            json!(history),
+        )
+    }
+
+    pub async fn load_exec_history_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<ExecHistory, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "exec_history")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize exec history: {}", e))
+            }),
+            None => Ok(ExecHistory::default()),
+        }
+    }
+
+    pub async fn save_snippets_to_store(
+        &self,
+        app: &AppHandle,
+        library: &SnippetLibrary,
+    ) -> Result<(), AppError> {
+        Self::set_value(app, &Self::databases_file(app), "snippets", json!(library))
+    }
+
+    pub async fn load_snippets_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<SnippetLibrary, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "snippets")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize snippets: {}", e))),
+            None => Ok(SnippetLibrary::default()),
+        }
+    }
+
+    pub async fn save_sql_jobs_to_store(
+        &self,
+        app: &AppHandle,
+        jobs: &[SqlJob],
+    ) -> Result<(), AppError> {
+        Self::set_value(app, &Self::databases_file(app), "sql_jobs", json!(jobs))
+    }
+
+    pub async fn load_sql_jobs_from_store(&self, app: &AppHandle) -> Result<Vec<SqlJob>, AppError> {
+        match Self::get_value(app, &Self::databases_file(app), "sql_jobs")? {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| AppError::Storage(format!("Failed to deserialize SQL jobs: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_metrics_history_to_store(
+        &self,
+        app: &AppHandle,
+        history: &MetricsHistory,
+    ) -> Result<(), AppError> {
+        Self::set_value(
+            app,
+            Path::new(METRICS_HISTORY_FILE),
+            "history",
+            json!(history),
+        )
+    }
+
+    pub async fn load_metrics_history_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<MetricsHistory, AppError> {
+        match Self::get_value(app, Path::new(METRICS_HISTORY_FILE), "history")? {
+            Some(value) => serde_json::from_value(value).map_err(|e| {
+                AppError::Storage(format!("Failed to deserialize metrics history: {}", e))
+            }),
+            None => Ok(MetricsHistory::default()),
+        }
+    }
+
+    pub async fn save_trash_to_store(
+        &self,
+        app: &AppHandle,
+        trash: &[TrashedDatabase],
+    ) -> Result<(), AppError> {
+        let secrets_service = SecretsService::new();
+
+        // Trashed passwords stay in the keychain until the entry is purged
+        let trash_vec = trash
+            .iter()
+            .cloned()
+            .map(|mut entry| {
+                entry.database = secrets_service.externalize_password(entry.database)?;
+                Ok(entry)
+            })
+            .collect::<Result<Vec<TrashedDatabase>, AppError>>()?;
+
+        Self::set_value(app, &Self::databases_file(app), "trash", json!(trash_vec))
+    }
+
+    pub async fn load_trash_from_store(
+        &self,
+        app: &AppHandle,
+    ) -> Result<Vec<TrashedDatabase>, AppError> {
+        let secrets_service = SecretsService::new();
+
+        let mut trash: Vec<TrashedDatabase> =
+            match Self::get_value(app, &Self::databases_file(app), "trash")? {
+                Some(value) => serde_json::from_value(value).map_err(|e| {
+                    AppError::Storage(format!("Failed to deserialize trash: {}", e))
+                })?,
+                None => Vec::new(),
+            };
+
+        for entry in trash.iter_mut() {
+            secrets_service.resolve_password(&mut entry.database)?;
+        }
+
+        Ok(trash)
+    }
 }
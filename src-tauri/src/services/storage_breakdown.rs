@@ -0,0 +1,307 @@
+use crate::services::{mongo_login, DockerService, EnvFile, SqlService, MONGO_PASSWORD_VAR};
+use crate::types::*;
+use serde_json::Value;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+static BREAKDOWN_CACHE: LazyLock<Mutex<QueryCache<StorageBreakdown>>> = LazyLock::new(|| {
+    Mutex::new(QueryCache::new(Duration::from_secs(
+        STORAGE_BREAKDOWN_TTL_SECS,
+    )))
+});
+
+const POSTGRES_DATABASES_SQL: &str = "SELECT json_build_object(\
+'name', datname, \
+'sizeBytes', pg_database_size(datname)) \
+FROM pg_database WHERE datallowconn AND NOT datistemplate ORDER BY datname";
+
+const POSTGRES_TABLES_SQL: &str = "SELECT json_build_object(\
+'schema', n.nspname, \
+'name', c.relname, \
+'sizeBytes', pg_total_relation_size(c.oid), \
+'rowCount', CASE WHEN c.reltuples >= 0 THEN c.reltuples::bigint END) \
+FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+WHERE c.relkind IN ('r', 'm') \
+AND n.nspname NOT IN ('pg_catalog', 'information_schema') AND n.nspname NOT LIKE 'pg_toast%'";
+
+// Schemas without tables are kept by the outer join
+const MYSQL_TABLES_SQL: &str = "SELECT JSON_OBJECT(\
+'database', s.SCHEMA_NAME, \
+'name', t.TABLE_NAME, \
+'sizeBytes', IFNULL(t.DATA_LENGTH, 0) + IFNULL(t.INDEX_LENGTH, 0), \
+'rowCount', t.TABLE_ROWS) \
+FROM information_schema.SCHEMATA s \
+LEFT JOIN information_schema.TABLES t \
+ON t.TABLE_SCHEMA = s.SCHEMA_NAME AND t.TABLE_TYPE = 'BASE TABLE' \
+WHERE s.SCHEMA_NAME NOT IN ('mysql', 'information_schema', 'performance_schema', 'sys')";
+
+// Prints one `DatabaseSize` per line, system databases left out
+const MONGO_SIZES_SCRIPT: &str = "db.adminCommand({ listDatabases: 1 }).databases\
+.filter((d) => !['admin', 'config', 'local'].includes(d.name))\
+.forEach((d) => {\
+  const database = db.getSiblingDB(d.name);\
+  const stats = database.runCommand({ dbStats: 1 });\
+  const tables = database.getCollectionInfos({ type: 'collection' })\
+    .filter((c) => !c.name.startsWith('system.'))\
+    .map((c) => {\
+      const s = database.getCollection(c.name).aggregate([{ $collStats: { storageStats: {} } }]).next().storageStats;\
+      return { name: c.name, sizeBytes: Number(s.storageSize) + Number(s.totalIndexSize), rowCount: Number(s.count) };\
+    });\
+  print(JSON.stringify({ name: d.name, sizeBytes: Number(stats.storageSize) + Number(stats.indexSize), tables }));\
+})";
+
+// Measures keys grouped by the prefix before their first colon in every non-empty database
+// Returns `{ usedMemory, databases: [{ name, sizeBytes, partial, tables }] }`
+const REDIS_SIZES_SCRIPT: &str = "\
+local limit = tonumber(ARGV[1])
+local databases = {}
+for index in string.gmatch(redis.call('INFO', 'keyspace'), 'db(%d+):') do
+  redis.call('SELECT', index)
+  local prefixes = {}
+  local total = 0
+  local scanned = 0
+  local cursor = '0'
+  repeat
+    local reply = redis.call('SCAN', cursor, 'COUNT', 1000)
+    cursor = reply[1]
+    for _, key in ipairs(reply[2]) do
+      local bytes = redis.call('MEMORY', 'USAGE', key) or 0
+      local prefix = string.match(key, '^([^:]+):') or ''
+      local entry = prefixes[prefix] or { name = prefix, sizeBytes = 0, rowCount = 0 }
+      entry.sizeBytes = entry.sizeBytes + bytes
+      entry.rowCount = entry.rowCount + 1
+      prefixes[prefix] = entry
+      total = total + bytes
+      scanned = scanned + 1
+    end
+  until cursor == '0' or scanned >= limit
+  local tables = {}
+  for _, entry in pairs(prefixes) do
+    table.insert(tables, entry)
+  end
+  table.insert(databases, { name = 'db' .. index, sizeBytes = total, partial = cursor ~= '0', tables = tables })
+end
+local memory = redis.call('INFO', 'memory')
+return cjson.encode({ usedMemory = tonumber(string.match(memory, 'used_memory:(%d+)')), databases = databases })";
+
+/// Per-database and per-table sizes from the engine's catalogs, cached per container
+pub struct StorageBreakdownService;
+
+impl StorageBreakdownService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn unexpected_output(error: serde_json::Error) -> AppError {
+        AppError::Internal(format!(
+            "Unexpected output from the database client: {}",
+            error
+        ))
+    }
+
+    /// Databases of `MYSQL_TABLES_SQL` rows, one row per table
+    pub fn parse_mysql_rows(lines: &[String]) -> Result<Vec<DatabaseSize>, AppError> {
+        let mut databases: Vec<DatabaseSize> = Vec::new();
+        for line in lines {
+            let row: Value = serde_json::from_str(line).map_err(Self::unexpected_output)?;
+            let name = row["database"].as_str().unwrap_or_default();
+            if databases
+                .last()
+                .is_none_or(|database| database.name != name)
+            {
+                databases.push(DatabaseSize {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+            }
+            let database = databases.last_mut().unwrap();
+
+            // Schemas without tables have a single row with no table name
+            let Some(table) = row["name"].as_str() else {
+                continue;
+            };
+            let size_bytes = row["sizeBytes"].as_u64().unwrap_or_default();
+            database.size_bytes += size_bytes;
+            database.tables.push(TableSize {
+                schema: None,
+                name: table.to_string(),
+                size_bytes,
+                row_count: row["rowCount"].as_u64(),
+            });
+        }
+        Ok(databases)
+    }
+
+    /// Server memory use and databases of the `REDIS_SIZES_SCRIPT` reply
+    pub fn parse_redis_reply(output: &str) -> Result<(Option<u64>, Vec<DatabaseSize>), AppError> {
+        let reply: Value = serde_json::from_str(output.trim())
+            .map_err(|e| AppError::Internal(format!("Unexpected output from redis-cli: {}", e)))?;
+        // cjson encodes empty arrays as objects
+        let as_list = |value: &Value| value.as_array().cloned().unwrap_or_default();
+
+        let databases = as_list(&reply["databases"])
+            .iter()
+            .map(|database| DatabaseSize {
+                name: database["name"].as_str().unwrap_or_default().to_string(),
+                size_bytes: database["sizeBytes"].as_u64().unwrap_or_default(),
+                partial: database["partial"].as_bool().unwrap_or_default(),
+                tables: as_list(&database["tables"])
+                    .iter()
+                    .map(|prefix| TableSize {
+                        schema: None,
+                        name: match prefix["name"].as_str().unwrap_or_default() {
+                            "" => "(no prefix)".to_string(),
+                            name => format!("{}:*", name),
+                        },
+                        size_bytes: prefix["sizeBytes"].as_u64().unwrap_or_default(),
+                        row_count: prefix["rowCount"].as_u64(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok((reply["usedMemory"].as_u64(), databases))
+    }
+
+    /// Sizes of the container's databases, read again once the cached ones are older than
+    /// `STORAGE_BREAKDOWN_TTL_SECS` or when `refresh` is set
+    pub async fn get(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+        refresh: bool,
+    ) -> Result<StorageBreakdown, AppError> {
+        if !refresh {
+            if let Some(breakdown) = BREAKDOWN_CACHE
+                .lock()
+                .unwrap()
+                .get(&database.id, Instant::now())
+            {
+                return Ok(breakdown);
+            }
+        }
+
+        let breakdown = self.collect(app, database).await?;
+        BREAKDOWN_CACHE
+            .lock()
+            .unwrap()
+            .insert(&database.id, breakdown.clone(), Instant::now());
+        Ok(breakdown)
+    }
+
+    async fn collect(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<StorageBreakdown, AppError> {
+        if !database.is_running() {
+            return Err(AppError::Validation(format!(
+                "Start {} to measure it",
+                database.name
+            )));
+        }
+
+        let sql_service = SqlService::new();
+        match database.db_type.as_str() {
+            "PostgreSQL" => {
+                let mut databases: Vec<DatabaseSize> = sql_service
+                    .query_lines(app, database, POSTGRES_DATABASES_SQL)
+                    .await?
+                    .iter()
+                    .map(|line| serde_json::from_str(line).map_err(Self::unexpected_output))
+                    .collect::<Result<_, _>>()?;
+                // Each database has its own catalog
+                for entry in &mut databases {
+                    entry.tables = sql_service
+                        .query_lines_in(app, database, Some(&entry.name), POSTGRES_TABLES_SQL)
+                        .await?
+                        .iter()
+                        .map(|line| serde_json::from_str(line).map_err(Self::unexpected_output))
+                        .collect::<Result<_, _>>()?;
+                }
+                Ok(StorageBreakdown::new(databases, None))
+            }
+            "MySQL" | "MariaDB" => {
+                // MySQL 8 caches table statistics for a day by default
+                let sql = if database.db_type == "MySQL" {
+                    format!(
+                        "SET SESSION information_schema_stats_expiry = 0; {} ORDER BY s.SCHEMA_NAME",
+                        MYSQL_TABLES_SQL
+                    )
+                } else {
+                    format!("{} ORDER BY s.SCHEMA_NAME", MYSQL_TABLES_SQL)
+                };
+                let lines = sql_service.query_lines(app, database, &sql).await?;
+                Ok(StorageBreakdown::new(Self::parse_mysql_rows(&lines)?, None))
+            }
+            "MongoDB" => {
+                let credentials = DatabaseCredentials::of(database);
+                let mut script = String::new();
+                if database.stored_enable_auth {
+                    script.push_str(&mongo_login(&credentials.username));
+                }
+                script.push_str(MONGO_SIZES_SCRIPT);
+                // Kept until mongosh returns, the password never reaches the command line
+                let env_file = EnvFile::create(&[(MONGO_PASSWORD_VAR, &credentials.password)])?;
+                let output = DockerService::for_database(app, database)?
+                    .stdout(
+                        app,
+                        "Failed to read database sizes",
+                        &[
+                            "exec",
+                            "--env-file",
+                            &env_file.path().to_string_lossy(),
+                            &database.name,
+                            "mongosh",
+                            "--quiet",
+                            "--eval",
+                            &script,
+                        ],
+                    )
+                    .await?;
+                let databases = output
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| serde_json::from_str(line).map_err(Self::unexpected_output))
+                    .collect::<Result<_, _>>()?;
+                Ok(StorageBreakdown::new(databases, None))
+            }
+            "Redis" => {
+                let password = database.stored_password.clone().unwrap_or_default();
+                // Kept until redis-cli returns, the password never reaches the command line
+                let env_file = if password.is_empty() {
+                    None
+                } else {
+                    Some(EnvFile::create(&[("REDISCLI_AUTH", &password)])?)
+                };
+                let env_path = env_file
+                    .as_ref()
+                    .map(|env_file| env_file.path().to_string_lossy().to_string());
+                let limit = REDIS_SIZE_SCAN_LIMIT.to_string();
+                let mut args = vec!["exec"];
+                if let Some(env_path) = &env_path {
+                    args.extend(["--env-file", env_path]);
+                }
+                args.extend([
+                    &database.name,
+                    "redis-cli",
+                    "EVAL",
+                    REDIS_SIZES_SCRIPT,
+                    "0",
+                    &limit,
+                ]);
+
+                let output = DockerService::for_database(app, database)?
+                    .stdout(app, "Failed to read database sizes", &args)
+                    .await?;
+                let (used_memory, databases) = Self::parse_redis_reply(&output)?;
+                Ok(StorageBreakdown::new(databases, used_memory))
+            }
+            _ => Err(AppError::Validation(format!(
+                "Storage breakdown is not available for {}",
+                database.db_type
+            ))),
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::services::{
+    AlertService, AutostartService, DockerService, EventService, NotificationService,
+    OrphanService, StorageService, TrashService, TrayService, UptimeService, WatchdogService,
+};
+use crate::types::*;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+// Failure reported for each source, repeats of it are not reported again until it clears
+static REPORTED: LazyLock<Mutex<HashMap<&'static str, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Keeps the in-memory state reconciled with Docker in the background
+pub struct SyncService;
+
+impl SyncService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Load persisted workspaces, databases and settings into app state
+    pub async fn initialize(&self, app: &AppHandle) -> Result<(), AppError> {
+        let storage_service = StorageService::new();
+
+        // A passphrase-protected store stays empty until it is unlocked
+        let workspaces = match storage_service.load_workspaces_from_store(app).await {
+            Err(AppError::StoreLocked) => return Ok(()),
+            result => result?,
+        };
+
+        // The workspace decides which store file the databases are read from
+        {
+            let workspace_state = app.state::<WorkspaceStore>();
+            let mut current = workspace_state.lock().unwrap();
+            *current = workspaces;
+        }
+
+        let mut loaded_databases = storage_service.load_databases_from_store(app).await?;
+        // A readiness probe cut short by a quit would otherwise keep it starting forever
+        for database in loaded_databases.values_mut() {
+            if database.status == "starting" {
+                database.status = "stopped".to_string();
+            }
+        }
+        let settings = storage_service.load_settings_from_store(app).await?;
+
+        {
+            let databases = app.state::<DatabaseStore>();
+            let mut db_map = databases.write().await;
+            *db_map = loaded_databases;
+        }
+        {
+            let settings_state = app.state::<SettingsStore>();
+            let mut current = settings_state.lock().unwrap();
+            *current = settings;
+        }
+
+        Ok(())
+    }
+
+    /// Reconcile every managed container with Docker, persist and emit changes
+    pub async fn sync_once(&self, app: &AppHandle) -> Result<(), AppError> {
+        let docker_service = DockerService::new();
+        let storage_service = StorageService::new();
+        let databases = app.state::<DatabaseStore>();
+
+        let previous_map = {
+            let db_map = databases.read().await;
+            db_map.clone()
+        };
+        let mut synced_map = previous_map.clone();
+        let docker_states = docker_service
+            .sync_containers_with_docker(app, &mut synced_map)
+            .await?;
+
+        NotificationService::new().check_health(app, &previous_map, &docker_states);
+        let checked = AlertService::new()
+            .check_restarts(app, &previous_map, &docker_states)
+            .await;
+        Self::report(app, "alerts", "Failed to check alert rules", checked);
+        let recorded = UptimeService::new()
+            .record(app, &previous_map, &docker_states)
+            .await;
+        Self::report(app, "uptime", "Failed to record uptime history", recorded);
+        // Persistent databases whose volume was pruned outside the app, skipped when unknown
+        let volumes = docker_service.list_volumes(app).await.ok();
+
+        // Merge only Docker-derived fields, commands may have changed the map meanwhile
+        let current_map = {
+            let mut db_map = databases.write().await;
+            for (id, synced) in &synced_map {
+                if let Some(db) = db_map.get_mut(id) {
+                    Self::merge_synced(db, synced);
+                    // Only local volumes are listed
+                    if let (Some(volumes), None) = (&volumes, &db.host) {
+                        db.storage_state = db.storage_state_in(volumes);
+                    }
+                }
+            }
+            db_map.clone()
+        };
+
+        WatchdogService::new()
+            .check(app, &previous_map, &current_map)
+            .await;
+
+        let changed = Self::changed_databases(&previous_map, &current_map);
+
+        // Only entries Docker changed are written back
+        if !changed.is_empty() {
+            storage_service
+                .upsert_databases_in_store(app, &changed)
+                .await?;
+            let event_service = EventService::new();
+            event_service.emit_status_changes(app, &previous_map, &current_map);
+            for db in &changed {
+                if previous_map
+                    .get(&db.id)
+                    .is_some_and(|previous| previous.storage_state != db.storage_state)
+                {
+                    event_service.container_updated(app, db);
+                }
+            }
+        }
+
+        // Also picks up containers created or removed since the last tick
+        let _ = TrayService::new().refresh(app).await;
+
+        // Labelled containers missing from the store, e.g. after the store was lost
+        if let Ok(states) = docker_service.list_container_states(app).await {
+            let names: Vec<String> = states.into_keys().collect();
+            let _ = OrphanService::new().detect(app, &names).await;
+        }
+
+        Ok(())
+    }
+
+    /// Copy the Docker-derived fields of a synced entry onto the current one
+    pub fn merge_synced(db: &mut DatabaseContainer, synced: &DatabaseContainer) {
+        // Started outside the app
+        if synced.is_running() && !db.is_running() {
+            db.last_started_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        // Stopped, crashed or removed outside the app
+        if matches!(synced.status.as_str(), "stopped" | "exited") && db.is_running() {
+            db.last_stopped_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        // The readiness probe of a new container decides when it is running
+        if !(db.status == "starting" && synced.status == "running") {
+            db.status = synced.status.clone();
+        }
+        db.exit_code = synced.exit_code;
+        db.container_id = synced.container_id.clone();
+    }
+
+    /// Entries whose Docker-derived fields differ from the previous tick, new ones included
+    pub fn changed_databases(
+        previous: &HashMap<String, DatabaseContainer>,
+        current: &HashMap<String, DatabaseContainer>,
+    ) -> Vec<DatabaseContainer> {
+        current
+            .iter()
+            .filter(|(id, db)| {
+                previous.get(*id).is_none_or(|previous| {
+                    previous.status != db.status
+                        || previous.container_id != db.container_id
+                        || previous.storage_state != db.storage_state
+                })
+            })
+            .map(|(_, db)| db.clone())
+            .collect()
+    }
+
+    /// Time between two ticks, at least a second whatever the settings say
+    // Failures come back on every sync, each is reported once until a sync succeeds
+    fn report(app: &AppHandle, source: &'static str, context: &str, result: Result<(), AppError>) {
+        let mut reported = REPORTED.lock().unwrap();
+        match result {
+            Ok(()) => {
+                reported.remove(source);
+            }
+            Err(error) => {
+                let message = format!("{}: {}", context, error);
+                if reported.get(source) != Some(&message) {
+                    EventService::new().background_error(app, source, message.clone());
+                    reported.insert(source, message);
+                }
+            }
+        }
+    }
+
+    pub fn interval(settings: &AppSettings) -> Duration {
+        Duration::from_secs(settings.sync_interval_secs.max(1))
+    }
+
+    /// Spawn the background worker
+    /// The interval is re-read from settings on every tick so changes apply immediately
+    pub fn start_worker(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let sync_service = SyncService::new();
+            let docker_service = DockerService::new();
+            let event_service = EventService::new();
+
+            // Drop trash entries that expired while the app was closed
+            let _ = TrashService::new().purge(&app, false).await;
+
+            // Statuses must be fresh before deciding what to start
+            let mut autostart_pending = true;
+
+            loop {
+                let status = docker_service.check_docker_status(&app).await;
+                let docker_running = match &status {
+                    Ok(status) => {
+                        event_service.docker_status_checked(&app, status);
+                        status.get("status").and_then(|s| s.as_str()) == Some("running")
+                    }
+                    Err(_) => false,
+                };
+
+                // Errors are retried on the next tick
+                if docker_running && sync_service.sync_once(&app).await.is_ok() && autostart_pending
+                {
+                    autostart_pending = false;
+                    let _ = AutostartService::new().apply(&app).await;
+                }
+
+                let interval = {
+                    let settings = app.state::<SettingsStore>();
+                    let settings = settings.lock().unwrap();
+                    Self::interval(&settings)
+                };
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+}
@@ -0,0 +1,241 @@
+use crate::services::DockerService;
+use crate::types::*;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+// How long the script a Windows terminal runs is kept, it is read when the window opens
+#[cfg(target_os = "windows")]
+const SCRIPT_KEEP_SECS: u64 = 60;
+
+/// Opens the platform's terminal app running a command
+pub struct TerminalService;
+
+impl TerminalService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Open a terminal window running `args`, joined for the platform's shell
+    pub async fn open(&self, app: &AppHandle, args: &[String]) -> Result<(), AppError> {
+        let (program, launcher_args) = self.launcher(app, args).await?;
+
+        app.shell()
+            .command(&program)
+            .args(&launcher_args)
+            .spawn()
+            .map_err(|e| AppError::Internal(format!("Failed to open a terminal: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// iTerm when installed, otherwise Terminal.app
+    #[cfg(target_os = "macos")]
+    async fn launcher(
+        &self,
+        _app: &AppHandle,
+        args: &[String],
+    ) -> Result<(String, Vec<String>), AppError> {
+        let command = Self::applescript_escape(&DockerService::shell_join(args));
+        let has_iterm = std::iter::once(std::path::PathBuf::from("/Applications/iTerm.app"))
+            .chain(dirs::home_dir().map(|home| home.join("Applications/iTerm.app")))
+            .any(|app| app.exists());
+
+        let script = if has_iterm {
+            vec![
+                "tell application \"iTerm\"".to_string(),
+                "activate".to_string(),
+                "set newWindow to (create window with default profile)".to_string(),
+                format!(
+                    "tell current session of newWindow to write text \"{}\"",
+                    command
+                ),
+                "end tell".to_string(),
+            ]
+        } else {
+            vec![
+                format!("tell application \"Terminal\" to do script \"{}\"", command),
+                "tell application \"Terminal\" to activate".to_string(),
+            ]
+        };
+
+        Ok((
+            "osascript".to_string(),
+            script
+                .into_iter()
+                .flat_map(|line| ["-e".to_string(), line])
+                .collect(),
+        ))
+    }
+
+    /// Windows Terminal when installed, otherwise a console window
+    /// The command is written to a batch file, a command line would be quoted again for cmd
+    #[cfg(target_os = "windows")]
+    async fn launcher(
+        &self,
+        app: &AppHandle,
+        args: &[String],
+    ) -> Result<(String, Vec<String>), AppError> {
+        let path = DockerService::new().get_enriched_path(app).await;
+
+        let script_path = std::env::temp_dir().join(format!(
+            "docker-db-manager-terminal-{}.cmd",
+            uuid::Uuid::new_v4()
+        ));
+        let script = format!("@{}\r\n", Self::batch_line(args)?);
+        crate::services::write_private(&script_path, script.as_bytes())
+            .map_err(|e| AppError::Io(format!("Failed to write terminal script: {}", e)))?;
+        let removed_path = script_path.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(SCRIPT_KEEP_SECS)).await;
+            let _ = std::fs::remove_file(removed_path);
+        });
+        let command = script_path.to_string_lossy().to_string();
+
+        if Self::find_in_path("wt.exe", &path) {
+            return Ok((
+                "wt.exe".to_string(),
+                vec![
+                    "new-tab".to_string(),
+                    "cmd".to_string(),
+                    "/K".to_string(),
+                    command,
+                ],
+            ));
+        }
+
+        Ok((
+            "cmd".to_string(),
+            vec![
+                "/C".to_string(),
+                "start".to_string(),
+                "Docker DB Manager".to_string(),
+                "cmd".to_string(),
+                "/K".to_string(),
+                command,
+            ],
+        ))
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    async fn launcher(
+        &self,
+        app: &AppHandle,
+        args: &[String],
+    ) -> Result<(String, Vec<String>), AppError> {
+        let path = DockerService::new().get_enriched_path(app).await;
+        // Keep the window open once the command exits
+        let script = format!(
+            "{}; exec \"${{SHELL:-sh}}\"",
+            DockerService::shell_join(args)
+        );
+
+        for terminal in ["x-terminal-emulator", "gnome-terminal", "konsole", "xterm"] {
+            if Self::find_in_path(terminal, &path) {
+                let separator = if terminal == "gnome-terminal" {
+                    "--"
+                } else {
+                    "-e"
+                };
+                return Ok((
+                    terminal.to_string(),
+                    vec![
+                        separator.to_string(),
+                        "sh".to_string(),
+                        "-c".to_string(),
+                        script,
+                    ],
+                ));
+            }
+        }
+
+        Err(AppError::Validation(
+            "No supported terminal app was found".to_string(),
+        ))
+    }
+
+    /// Line of a batch file running `args`
+    /// Each argument is quoted the way Windows programs split their command line, then every
+    /// character cmd treats specially is escaped so it reaches the program as is
+    pub fn batch_line(args: &[String]) -> Result<String, AppError> {
+        if args.iter().any(|arg| arg.contains(['\n', '\r'])) {
+            return Err(AppError::Validation(
+                "A command spanning several lines cannot run in a terminal".to_string(),
+            ));
+        }
+
+        Ok(args
+            .iter()
+            .map(|arg| Self::batch_escape(&Self::argv_quote(arg)))
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    /// Argument quoted for `CommandLineToArgvW`, backslashes only double before a quote
+    fn argv_quote(arg: &str) -> String {
+        if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+            return arg.to_string();
+        }
+
+        let mut quoted = String::from("\"");
+        let mut backslashes = 0;
+        for c in arg.chars() {
+            match c {
+                '\\' => backslashes += 1,
+                '"' => {
+                    quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                    quoted.push('"');
+                    backslashes = 0;
+                }
+                c => {
+                    quoted.push_str(&"\\".repeat(backslashes));
+                    quoted.push(c);
+                    backslashes = 0;
+                }
+            }
+        }
+        quoted.push_str(&"\\".repeat(backslashes * 2));
+        quoted.push('"');
+        quoted
+    }
+
+    /// Escape cmd's special characters, quotes included so none of them starts a quoted part
+    fn batch_escape(value: &str) -> String {
+        let mut escaped = String::new();
+        for c in value.chars() {
+            match c {
+                // Batch files read `%%` as a literal percent sign
+                '%' => escaped.push_str("%%"),
+                '(' | ')' | '!' | '^' | '"' | '<' | '>' | '&' | '|' => {
+                    escaped.push('^');
+                    escaped.push(c);
+                }
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    /// Whether an executable with this name is in one of the PATH directories
+    /// Metadata is enough, Windows app execution aliases are not regular files
+    pub fn find_in_path(program: &str, path: &str) -> bool {
+        std::env::split_paths(path).any(|dir| std::fs::symlink_metadata(dir.join(program)).is_ok())
+    }
+
+    /// Interactive shell in a container, bash when the image has it
+    pub fn container_shell_command(container_id: &str) -> Vec<String> {
+        vec![
+            "docker".to_string(),
+            "exec".to_string(),
+            "-it".to_string(),
+            container_id.to_string(),
+            "sh".to_string(),
+            "-c".to_string(),
+            "command -v bash >/dev/null && exec bash || exec sh".to_string(),
+        ]
+    }
+
+    /// Escape a command for a double-quoted AppleScript string
+    pub fn applescript_escape(command: &str) -> String {
+        command.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+}
@@ -0,0 +1,217 @@
+use crate::services::DockerService;
+use crate::types::*;
+use tauri::{AppHandle, Manager};
+
+/// Mount point of the certificates volume inside database containers
+pub const CERTS_MOUNT_PATH: &str = "/certs";
+
+// Image used to run openssl without requiring it on the host
+// Pinned, so certificates are generated by the same openssl release everywhere
+const OPENSSL_IMAGE: &str = "alpine/openssl:3.3.2";
+
+pub struct TlsService;
+
+impl TlsService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Name of the Docker volume holding the certificates of a container
+    pub fn certs_volume_name(container_name: &str) -> String {
+        format!("{}-certs", container_name)
+    }
+
+    /// Server arguments that enable TLS for the given database type
+    /// Returns an error for engines without TLS support in this app
+    pub fn build_tls_command_args(db_type: &str) -> Result<Vec<String>, AppError> {
+        let ca = format!("{}/ca.crt", CERTS_MOUNT_PATH);
+        let cert = format!("{}/server.crt", CERTS_MOUNT_PATH);
+        let key = format!("{}/server.key", CERTS_MOUNT_PATH);
+        let pem = format!("{}/server.pem", CERTS_MOUNT_PATH);
+
+        let args = match db_type {
+            "PostgreSQL" => vec![
+                "-c".to_string(),
+                "ssl=on".to_string(),
+                "-c".to_string(),
+                format!("ssl_cert_file={}", cert),
+                "-c".to_string(),
+                format!("ssl_key_file={}", key),
+                "-c".to_string(),
+                format!("ssl_ca_file={}", ca),
+            ],
+            "MySQL" | "MariaDB" => vec![
+                format!("--ssl-ca={}", ca),
+                format!("--ssl-cert={}", cert),
+                format!("--ssl-key={}", key),
+            ],
+            "MongoDB" => vec![
+                "--tlsMode".to_string(),
+                "preferTLS".to_string(),
+                "--tlsCertificateKeyFile".to_string(),
+                pem,
+                "--tlsCAFile".to_string(),
+                ca,
+                "--tlsAllowConnectionsWithoutCertificates".to_string(),
+            ],
+            // Redis serves TLS on its regular port and disables the plaintext one
+            "Redis" => vec![
+                "--tls-port".to_string(),
+                "6379".to_string(),
+                "--port".to_string(),
+                "0".to_string(),
+                "--tls-cert-file".to_string(),
+                cert,
+                "--tls-key-file".to_string(),
+                key,
+                "--tls-ca-cert-file".to_string(),
+                ca,
+                "--tls-auth-clients".to_string(),
+                "no".to_string(),
+            ],
+            _ => {
+                return Err(AppError::Validation(format!(
+                    "TLS is not supported for {}",
+                    db_type
+                )))
+            }
+        };
+
+        Ok(args)
+    }
+
+    /// Mount the certificates volume and append the TLS server arguments
+    pub fn apply_tls_to_docker_args(
+        &self,
+        container_name: &str,
+        db_type: &str,
+        docker_args: &mut DockerRunArgs,
+    ) -> Result<(), AppError> {
+        let tls_args = Self::build_tls_command_args(db_type)?;
+
+        docker_args.volumes.push(VolumeMount {
+            name: Self::certs_volume_name(container_name),
+            path: CERTS_MOUNT_PATH.to_string(),
+        });
+        docker_args.command.extend(tls_args);
+
+        Ok(())
+    }
+
+    /// User id the database server runs as, so it can read its private key
+    /// Official images use 999, except the Alpine PostgreSQL images
+    fn server_uid(db_type: &str, image: &str) -> u32 {
+        if db_type == "PostgreSQL" && image.contains("alpine") {
+            70
+        } else {
+            999
+        }
+    }
+
+    /// Generate a local CA and a server certificate into the certificates volume
+    /// Returns the host path of the CA certificate
+    pub async fn generate_certificates(
+        &self,
+        app: &AppHandle,
+        container_name: &str,
+        db_type: &str,
+        image: &str,
+    ) -> Result<String, AppError> {
+        let docker_service = DockerService::new();
+        let volume_name = Self::certs_volume_name(container_name);
+
+        docker_service
+            .create_volume_if_needed(app, &volume_name)
+            .await?;
+
+        let uid = Self::server_uid(db_type, image);
+        let script = format!(
+            "set -e; cd {dir}; \
+             openssl req -x509 -new -nodes -newkey rsa:2048 -sha256 -days 3650 \
+             -keyout ca.key -out ca.crt -subj '/CN=Docker DB Manager Local CA' 2>/dev/null; \
+             openssl req -new -nodes -newkey rsa:2048 \
+             -keyout server.key -out server.csr -subj '/CN=localhost' 2>/dev/null; \
+             printf 'subjectAltName=DNS:localhost,DNS:{name},IP:127.0.0.1\\n' > san.ext; \
+             openssl x509 -req -sha256 -days 825 -in server.csr -CA ca.crt -CAkey ca.key \
+             -CAcreateserial -out server.crt -extfile san.ext 2>/dev/null; \
+             cat server.key server.crt > server.pem; \
+             rm -f server.csr san.ext ca.srl; \
+             chown {uid}:{uid} server.key server.pem server.crt ca.crt; \
+             chmod 600 server.key server.pem ca.key; \
+             cat ca.crt",
+            dir = CERTS_MOUNT_PATH,
+            name = container_name,
+            uid = uid,
+        );
+
+        let volume_mount = format!("{}:{}", volume_name, CERTS_MOUNT_PATH);
+        let args: Vec<String> = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "-v".to_string(),
+            volume_mount,
+            "--entrypoint".to_string(),
+            "sh".to_string(),
+            OPENSSL_IMAGE.to_string(),
+            "-c".to_string(),
+            script,
+        ];
+
+        let ca_cert = docker_service
+            .run_container(app, &args)
+            .await
+            .map_err(|error| error.with_context("Failed to generate TLS certificates"))?;
+
+        // Keep a copy of the CA certificate on the host for client configuration
+        let certs_dir = Self::host_certs_dir(app, container_name)?;
+        let ca_path = certs_dir.join("ca.crt");
+        let written = std::fs::create_dir_all(&certs_dir)
+            .map_err(|e| AppError::Io(format!("Failed to create certificates directory: {}", e)))
+            .and_then(|_| {
+                std::fs::write(&ca_path, format!("{}\n", ca_cert))
+                    .map_err(|e| AppError::Io(format!("Failed to write CA certificate: {}", e)))
+            });
+
+        // A partial copy would be handed to clients as the CA
+        if let Err(error) = written {
+            let _ = std::fs::remove_dir_all(&certs_dir);
+            return Err(error);
+        }
+
+        Ok(ca_path.to_string_lossy().to_string())
+    }
+
+    /// Remove the certificates volume and the host copy of the CA certificate
+    pub async fn remove_certificates(
+        &self,
+        app: &AppHandle,
+        container_name: &str,
+    ) -> Result<(), AppError> {
+        let docker_service = DockerService::new();
+
+        docker_service
+            .remove_volume_if_exists(app, &Self::certs_volume_name(container_name))
+            .await?;
+
+        let certs_dir = Self::host_certs_dir(app, container_name)?;
+        if certs_dir.exists() {
+            std::fs::remove_dir_all(&certs_dir).map_err(|e| {
+                AppError::Io(format!("Failed to remove certificates directory: {}", e))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn host_certs_dir(
+        app: &AppHandle,
+        container_name: &str,
+    ) -> Result<std::path::PathBuf, AppError> {
+        let data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::Io(format!("Failed to resolve app data directory: {}", e)))?;
+
+        Ok(data_dir.join("certs").join(container_name))
+    }
+}
@@ -0,0 +1,66 @@
+use crate::services::{SecretsService, SnapshotService, StorageService};
+use crate::types::*;
+use chrono::{DateTime, Duration, Utc};
+use tauri::{AppHandle, Manager};
+
+pub struct TrashService;
+
+impl TrashService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether an entry is past the retention window
+    /// Entries with an unreadable date are treated as expired
+    pub fn is_expired(entry: &TrashedDatabase, retention_days: u32, now: DateTime<Utc>) -> bool {
+        match DateTime::parse_from_rfc3339(&entry.deleted_at) {
+            Ok(deleted_at) => {
+                deleted_at.with_timezone(&Utc) + Duration::days(retention_days as i64) <= now
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// Permanently delete what a trashed container left behind: snapshots and password
+    pub async fn destroy(&self, app: &AppHandle, entry: &TrashedDatabase) -> Result<(), AppError> {
+        SnapshotService::new()
+            .remove_all_for_database(app, &entry.database.id)
+            .await?;
+
+        // It may have been kept in plaintext
+        let _ =
+            SecretsService::new().delete_secret(&SecretsService::password_key(&entry.database.id));
+
+        Ok(())
+    }
+
+    /// Permanently delete entries past the retention window, or all of them with `all`
+    /// Returns the number of deleted entries
+    pub async fn purge(&self, app: &AppHandle, all: bool) -> Result<usize, AppError> {
+        let storage_service = StorageService::new();
+
+        let retention_days = {
+            let settings = app.state::<SettingsStore>();
+            let settings = settings.lock().unwrap();
+            settings.trash_retention_days
+        };
+        let now = Utc::now();
+
+        let trash = storage_service.load_trash_from_store(app).await?;
+        let (expired, kept): (Vec<TrashedDatabase>, Vec<TrashedDatabase>) = trash
+            .into_iter()
+            .partition(|entry| all || Self::is_expired(entry, retention_days, now));
+
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        for entry in &expired {
+            self.destroy(app, entry).await?;
+        }
+
+        storage_service.save_trash_to_store(app, &kept).await?;
+
+        Ok(expired.len())
+    }
+}
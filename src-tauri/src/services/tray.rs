@@ -0,0 +1,109 @@
+use crate::types::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Manager, Wry};
+
+pub const TRAY_ID: &str = "main-tray";
+
+// Entries of the menu currently shown, the menu is only rebuilt when they change
+static SHOWN_ENTRIES: Mutex<Option<Vec<TrayEntry>>> = Mutex::new(None);
+
+/// Keeps the tray menu in line with the managed containers
+pub struct TrayService;
+
+impl TrayService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Menu entries sorted by name
+    pub fn entries(databases: &HashMap<String, DatabaseContainer>) -> Vec<TrayEntry> {
+        let mut entries: Vec<TrayEntry> = databases
+            .values()
+            .map(|db| TrayEntry {
+                id: db.id.clone(),
+                name: db.name.clone(),
+                running: db.is_running(),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        entries
+    }
+
+    pub fn build_menu(app: &AppHandle, entries: &[TrayEntry]) -> tauri::Result<Menu<Wry>> {
+        let menu = Menu::new(app)?;
+
+        menu.append(&MenuItem::with_id(
+            app,
+            TrayAction::ShowWindow.menu_id(),
+            "Open Docker DB Manager",
+            true,
+            None::<&str>,
+        )?)?;
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+
+        if entries.is_empty() {
+            menu.append(&MenuItem::new(app, "No databases", false, None::<&str>)?)?;
+        }
+
+        for entry in entries {
+            let (status, action, label) = if entry.running {
+                ("Running", TrayAction::Stop(entry.id.clone()), "Stop")
+            } else {
+                ("Stopped", TrayAction::Start(entry.id.clone()), "Start")
+            };
+
+            let submenu = Submenu::new(app, format!("{} — {}", entry.name, status), true)?;
+            submenu.append(&MenuItem::with_id(
+                app,
+                action.menu_id(),
+                label,
+                true,
+                None::<&str>,
+            )?)?;
+            menu.append(&submenu)?;
+        }
+
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+        menu.append(&MenuItem::with_id(
+            app,
+            TrayAction::StopAll.menu_id(),
+            "Stop All",
+            entries.iter().any(|entry| entry.running),
+            None::<&str>,
+        )?)?;
+        menu.append(&MenuItem::with_id(
+            app,
+            TrayAction::Quit.menu_id(),
+            "Quit",
+            true,
+            None::<&str>,
+        )?)?;
+
+        Ok(menu)
+    }
+
+    /// Rebuild the tray menu if the containers or their status changed
+    pub async fn refresh(&self, app: &AppHandle) -> tauri::Result<()> {
+        let Some(tray) = app.tray_by_id(TRAY_ID) else {
+            return Ok(());
+        };
+
+        let entries = {
+            let databases = app.state::<DatabaseStore>();
+            let db_map = databases.read().await;
+            Self::entries(&db_map)
+        };
+
+        {
+            let mut shown = SHOWN_ENTRIES.lock().unwrap();
+            if shown.as_ref() == Some(&entries) {
+                return Ok(());
+            }
+            *shown = Some(entries.clone());
+        }
+
+        tray.set_menu(Some(Self::build_menu(app, &entries)?))
+    }
+}
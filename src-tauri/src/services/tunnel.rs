@@ -0,0 +1,272 @@
+use crate::services::{EventService, StorageService};
+use crate::types::*;
+use std::collections::HashMap;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+struct RunningTunnel {
+    status: TunnelStatus,
+    // Supervisor that owns the tunnel, a restarted tunnel gets a new one
+    generation: u64,
+    // Current ssh process, killed when the tunnel stops
+    child: Option<CommandChild>,
+}
+
+// Workspace and tunnel id
+type TunnelKey = (String, String);
+
+// Tunnels opened since launch, stopped ones are removed
+// Definitions are stored per workspace, a tunnel keeps running when another one is opened
+static TUNNELS: LazyLock<Mutex<HashMap<TunnelKey, RunningTunnel>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Opens SSH tunnels to remote databases and reconnects them when ssh exits
+pub struct TunnelService;
+
+impl TunnelService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn key(app: &AppHandle, tunnel: &TunnelDefinition) -> TunnelKey {
+        let workspace = app.state::<WorkspaceStore>().lock().unwrap().active.clone();
+        (workspace, tunnel.id.clone())
+    }
+
+    /// Status of a tunnel of the active workspace, stopped when it is not open
+    pub fn status(&self, app: &AppHandle, tunnel: &TunnelDefinition) -> TunnelStatus {
+        TUNNELS
+            .lock()
+            .unwrap()
+            .get(&Self::key(app, tunnel))
+            .map(|running| running.status.clone())
+            .unwrap_or_else(|| TunnelStatus::new(tunnel, TunnelState::Stopped))
+    }
+
+    /// Open a tunnel and keep it open until stopped, an open tunnel is left as is
+    pub fn start(&self, app: &AppHandle, tunnel: &TunnelDefinition) -> TunnelStatus {
+        let generation = NEXT_GENERATION.fetch_add(1, Ordering::SeqCst);
+        let key = Self::key(app, tunnel);
+        let status = {
+            let mut tunnels = TUNNELS.lock().unwrap();
+            if let Some(running) = tunnels.get(&key) {
+                return running.status.clone();
+            }
+            let status = TunnelStatus::new(tunnel, TunnelState::Connecting);
+            tunnels.insert(
+                key.clone(),
+                RunningTunnel {
+                    status: status.clone(),
+                    generation,
+                    child: None,
+                },
+            );
+            status
+        };
+        EventService::new().tunnel_status_changed(app, &status);
+
+        let app = app.clone();
+        let tunnel = tunnel.clone();
+        tauri::async_runtime::spawn(async move {
+            TunnelService::new()
+                .supervise(&app, &tunnel, &key, generation)
+                .await;
+        });
+
+        status
+    }
+
+    /// Close a tunnel and stop reconnecting it
+    pub fn stop(&self, app: &AppHandle, tunnel: &TunnelDefinition) -> TunnelStatus {
+        let running = TUNNELS.lock().unwrap().remove(&Self::key(app, tunnel));
+        if let Some(child) = running.and_then(|running| running.child) {
+            let _ = child.kill();
+        }
+
+        let status = TunnelStatus::new(tunnel, TunnelState::Stopped);
+        EventService::new().tunnel_status_changed(app, &status);
+        status
+    }
+
+    /// Close every tunnel, used when the app quits
+    pub fn stop_all(&self) {
+        let running: Vec<RunningTunnel> = TUNNELS
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, running)| running)
+            .collect();
+        for child in running.into_iter().filter_map(|running| running.child) {
+            let _ = child.kill();
+        }
+    }
+
+    /// Open the tunnels marked to start with the app
+    pub async fn start_auto(&self, app: &AppHandle) -> Result<(), AppError> {
+        let tunnels = StorageService::new().load_tunnels_from_store(app).await?;
+        for tunnel in tunnels.iter().filter(|tunnel| tunnel.auto_start) {
+            self.start(app, tunnel);
+        }
+        Ok(())
+    }
+
+    /// Run ssh until the tunnel is stopped, waiting longer after each failed attempt
+    async fn supervise(
+        &self,
+        app: &AppHandle,
+        tunnel: &TunnelDefinition,
+        key: &TunnelKey,
+        generation: u64,
+    ) {
+        let mut attempts = 0;
+
+        loop {
+            let (connected, error) = self.run_once(app, tunnel, key, generation).await;
+            if connected {
+                attempts = 0;
+            }
+            attempts += 1;
+
+            let updated = Self::update(key, generation, |status| {
+                status.state = TunnelState::Reconnecting;
+                status.attempts = attempts;
+                status.last_error = error;
+                status.connected_at = None;
+            });
+            let Some(status) = updated else {
+                return;
+            };
+            EventService::new().tunnel_status_changed(app, &status);
+
+            tokio::time::sleep(tunnel_retry_delay(attempts)).await;
+
+            let updated = Self::update(key, generation, |status| {
+                status.state = TunnelState::Connecting;
+            });
+            let Some(status) = updated else {
+                return;
+            };
+            EventService::new().tunnel_status_changed(app, &status);
+        }
+    }
+
+    /// Run one ssh process until it exits
+    /// Returns whether it got connected and the last error it printed
+    async fn run_once(
+        &self,
+        app: &AppHandle,
+        tunnel: &TunnelDefinition,
+        key: &TunnelKey,
+        generation: u64,
+    ) -> (bool, Option<String>) {
+        // ssh would exit on the forward failure, a probe would reach the other listener meanwhile
+        let local = SocketAddr::from(([127, 0, 0, 1], tunnel.local_port));
+        if TcpListener::bind(local).is_err() {
+            return (
+                false,
+                Some(format!(
+                    "Local port {} is already in use",
+                    tunnel.local_port
+                )),
+            );
+        }
+
+        let (mut events, child) = match app.shell().command("ssh").args(tunnel.ssh_args()).spawn() {
+            Ok(spawned) => spawned,
+            Err(e) => return (false, Some(format!("Failed to run ssh: {}", e))),
+        };
+
+        {
+            let mut tunnels = TUNNELS.lock().unwrap();
+            match tunnels
+                .get_mut(key)
+                .filter(|running| running.generation == generation)
+            {
+                Some(running) => running.child = Some(child),
+                None => {
+                    let _ = child.kill();
+                    return (false, None);
+                }
+            }
+        }
+
+        let mut connected = false;
+        let mut last_error = None;
+        loop {
+            let event = if connected {
+                events.recv().await
+            } else {
+                let probe = Duration::from_millis(TUNNEL_PROBE_INTERVAL_MS);
+                match tokio::time::timeout(probe, events.recv()).await {
+                    Ok(event) => event,
+                    Err(_) => {
+                        if !local_port_open(local).await {
+                            continue;
+                        }
+                        connected = true;
+                        let updated = Self::update(key, generation, |status| {
+                            status.state = TunnelState::Connected;
+                            status.attempts = 0;
+                            status.last_error = None;
+                            status.connected_at = Some(chrono::Utc::now().to_rfc3339());
+                        });
+                        if let Some(status) = updated {
+                            EventService::new().tunnel_status_changed(app, &status);
+                        }
+                        continue;
+                    }
+                }
+            };
+
+            match event {
+                Some(CommandEvent::Stderr(line)) => {
+                    let line = String::from_utf8_lossy(&line).trim().to_string();
+                    if !line.is_empty() {
+                        last_error = Some(line);
+                    }
+                }
+                Some(CommandEvent::Terminated(payload)) => {
+                    last_error = last_error.or_else(|| {
+                        Some(format!(
+                            "ssh exited with code {}",
+                            payload.code.unwrap_or(-1)
+                        ))
+                    });
+                    break;
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+
+        (connected, last_error)
+    }
+
+    /// Change the status of a tunnel still owned by `generation`, None once it was stopped
+    fn update(
+        key: &TunnelKey,
+        generation: u64,
+        change: impl FnOnce(&mut TunnelStatus),
+    ) -> Option<TunnelStatus> {
+        let mut tunnels = TUNNELS.lock().unwrap();
+        let running = tunnels
+            .get_mut(key)
+            .filter(|running| running.generation == generation)?;
+        change(&mut running.status);
+        Some(running.status.clone())
+    }
+}
+
+/// Whether the local end of the forward accepts connections
+async fn local_port_open(address: SocketAddr) -> bool {
+    let timeout = Duration::from_millis(TUNNEL_PROBE_INTERVAL_MS);
+    tauri::async_runtime::spawn_blocking(move || TcpStream::connect_timeout(&address, timeout))
+        .await
+        .is_ok_and(|connected| connected.is_ok())
+}
@@ -0,0 +1,78 @@
+use crate::services::{DockerService, StorageService};
+use crate::types::*;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Manager};
+
+// States recorded by the last sync per workspace and database id, the store is only read
+// when one of them changes
+static LAST_SEEN: LazyLock<Mutex<HashMap<(String, String), UptimeSnapshot>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Keeps the starts, restarts and exits of managed containers across syncs
+pub struct UptimeService;
+
+impl UptimeService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Record the starts and exits since the last sync
+    pub async fn record(
+        &self,
+        app: &AppHandle,
+        databases: &HashMap<String, DatabaseContainer>,
+        states: &HashMap<String, DockerContainerState>,
+    ) -> Result<(), AppError> {
+        // Databases of two workspaces may share an id, each has its own history
+        let workspace = app.state::<WorkspaceStore>().lock().unwrap().active.clone();
+        let key = |db: &DatabaseContainer| (workspace.clone(), db.id.clone());
+        let changed: Vec<(&DatabaseContainer, &DockerContainerState)> = {
+            let last_seen = LAST_SEEN.lock().unwrap();
+            databases
+                .values()
+                .filter_map(|db| Some((db, states.get(&db.name)?)))
+                .filter(|(db, state)| last_seen.get(&key(db)) != Some(&UptimeSnapshot::of(state)))
+                .collect()
+        };
+        if changed.is_empty() {
+            return Ok(());
+        }
+
+        let storage_service = StorageService::new();
+        let mut history = storage_service.load_uptime_history_from_store(app).await?;
+        for (db, state) in &changed {
+            history.observe(&db.id, state);
+        }
+        history.retain_databases(databases);
+        storage_service
+            .save_uptime_history_to_store(app, &history)
+            .await?;
+
+        let mut last_seen = LAST_SEEN.lock().unwrap();
+        for (db, state) in changed {
+            last_seen.insert(key(db), UptimeSnapshot::of(state));
+        }
+        Ok(())
+    }
+
+    /// Uptime from the live Docker state and the recorded starts and exits
+    pub async fn report(
+        &self,
+        app: &AppHandle,
+        database: &DatabaseContainer,
+    ) -> Result<UptimeReport, AppError> {
+        let states = DockerService::for_database(app, database)?
+            .inspect_containers(app, std::slice::from_ref(&database.name))
+            .await?;
+        let history = StorageService::new()
+            .load_uptime_history_from_store(app)
+            .await?;
+
+        Ok(UptimeReport::new(
+            states.get(&database.name),
+            history.containers.get(&database.id),
+            chrono::Utc::now(),
+        ))
+    }
+}
@@ -0,0 +1,220 @@
+use crate::services::{DockerService, EventService, GracefulStopService};
+use crate::types::*;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+// Running this long after a restart counts as recovered, the attempts start over
+const STABLE_AFTER_SECS: i64 = 300;
+
+static WATCHDOG: LazyLock<Mutex<Watchdog>> = LazyLock::new(|| Mutex::new(Watchdog::default()));
+
+#[derive(Debug, Default)]
+struct WatchdogEntry {
+    attempts: u32,
+    // Set while a restart waits for its delay
+    due_at: Option<DateTime<Utc>>,
+    last_restart_at: Option<DateTime<Utc>>,
+    // Every attempt was used, nothing is tried until the container runs again
+    gave_up: bool,
+}
+
+/// Turns successive statuses of watched databases into restarts with exponential backoff
+#[derive(Debug, Default)]
+pub struct Watchdog {
+    entries: HashMap<String, WatchdogEntry>,
+}
+
+impl Watchdog {
+    /// Record the status of a database after a sync, `was_running` is its status before it
+    /// Exits the app caused, e.g. a stop killed after its timeout, were already recorded as stopped
+    pub fn observe(
+        &mut self,
+        database_id: &str,
+        policy: &WatchdogPolicy,
+        status: &str,
+        was_running: bool,
+        now: DateTime<Utc>,
+    ) -> Option<WatchdogAction> {
+        match status {
+            // Only crashes, a clean exit is reported as "stopped"
+            "exited" => {
+                if !was_running && !self.entries.contains_key(database_id) {
+                    return None;
+                }
+                let entry = self.entries.entry(database_id.to_string()).or_default();
+                if entry.gave_up {
+                    return None;
+                }
+
+                match entry.due_at {
+                    None if entry.attempts >= policy.max_attempts => {
+                        entry.gave_up = true;
+                        Some(WatchdogAction::GaveUp {
+                            attempts: entry.attempts,
+                        })
+                    }
+                    None => {
+                        let attempt = entry.attempts + 1;
+                        let delay_secs = policy.delay_secs(attempt);
+                        entry.due_at = Some(now + Duration::seconds(delay_secs as i64));
+                        Some(WatchdogAction::Scheduled {
+                            attempt,
+                            delay_secs,
+                        })
+                    }
+                    Some(due_at) if now >= due_at => {
+                        entry.attempts += 1;
+                        entry.due_at = None;
+                        entry.last_restart_at = Some(now);
+                        Some(WatchdogAction::Restart {
+                            attempt: entry.attempts,
+                        })
+                    }
+                    Some(_) => None,
+                }
+            }
+            // Docker's own restart policy is at work
+            "restarting" => None,
+            status if is_running_status(status) => {
+                let recovered = self.entries.get(database_id).is_some_and(|entry| {
+                    entry.gave_up
+                        || entry.due_at.is_some()
+                        || entry
+                            .last_restart_at
+                            .is_none_or(|at| now - at >= Duration::seconds(STABLE_AFTER_SECS))
+                });
+                if recovered {
+                    self.entries.remove(database_id);
+                }
+                None
+            }
+            // Stopped or removed on purpose
+            _ => {
+                self.entries.remove(database_id);
+                None
+            }
+        }
+    }
+
+    /// Drop the state of databases that are no longer watched
+    pub fn retain(&mut self, watched: &[String]) {
+        self.entries.retain(|id, _| watched.contains(id));
+    }
+}
+
+/// Restarts watched databases that exited unexpectedly, reporting each attempt
+pub struct WatchdogService;
+
+impl WatchdogService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Act on the statuses of the latest sync, `previous` holds the ones before it
+    pub async fn check(
+        &self,
+        app: &AppHandle,
+        previous: &HashMap<String, DatabaseContainer>,
+        databases: &HashMap<String, DatabaseContainer>,
+    ) {
+        let now = Utc::now();
+        let actions: Vec<(DatabaseContainer, WatchdogAction)> = {
+            let mut watchdog = WATCHDOG.lock().unwrap();
+            let watched: Vec<String> = databases
+                .values()
+                .filter(|db| db.watchdog.is_some())
+                .map(|db| db.id.clone())
+                .collect();
+            watchdog.retain(&watched);
+
+            databases
+                .values()
+                .filter_map(|db| {
+                    let policy = db.watchdog.as_ref()?;
+                    let was_running = previous
+                        .get(&db.id)
+                        .is_some_and(|p| p.is_running() || p.status == "restarting");
+                    // An engine killed after its stop timeout exits with 137 like a crash
+                    let status = if GracefulStopService::is_stopping(&db.id) {
+                        "stopped"
+                    } else {
+                        db.status.as_str()
+                    };
+                    let action = watchdog.observe(&db.id, policy, status, was_running, now)?;
+                    Some((db.clone(), action))
+                })
+                .collect()
+        };
+
+        for (database, action) in actions {
+            let policy = database.watchdog.clone().unwrap_or_default();
+            let mut payload = WatchdogEventPayload {
+                id: database.id.clone(),
+                name: database.name.clone(),
+                stage: String::new(),
+                attempt: 0,
+                max_attempts: policy.max_attempts,
+                delay_secs: None,
+                error: None,
+            };
+
+            match action {
+                WatchdogAction::Scheduled {
+                    attempt,
+                    delay_secs,
+                } => {
+                    payload.stage = "scheduled".to_string();
+                    payload.attempt = attempt;
+                    payload.delay_secs = Some(delay_secs);
+                }
+                WatchdogAction::Restart { attempt } => {
+                    payload.attempt = attempt;
+                    // The next sync records the start, or schedules the next attempt
+                    let real_id = database.container_id.clone().unwrap_or_default();
+                    let started = match DockerService::for_database(app, &database) {
+                        Ok(docker_service) => docker_service
+                            .start_container(app, &real_id)
+                            .await
+                            .map_err(|error| docker_service.host_error(error)),
+                        Err(error) => Err(error),
+                    };
+                    match started {
+                        Ok(()) => payload.stage = "restarted".to_string(),
+                        Err(error) => {
+                            payload.stage = "restartFailed".to_string();
+                            payload.error = Some(error.to_string());
+                        }
+                    }
+                }
+                WatchdogAction::GaveUp { attempts } => {
+                    payload.stage = "gaveUp".to_string();
+                    payload.attempt = attempts;
+                }
+            }
+
+            EventService::new().watchdog_step(app, &payload);
+            self.notify(app, &payload);
+        }
+    }
+
+    fn notify(&self, app: &AppHandle, payload: &WatchdogEventPayload) {
+        let (enabled, muted) = {
+            let settings = app.state::<SettingsStore>();
+            let settings = settings.lock().unwrap();
+            (
+                settings.notifications_enabled,
+                settings.muted_containers.contains(&payload.id),
+            )
+        };
+        if !enabled || muted {
+            return;
+        }
+
+        if let Some((title, body)) = payload.notification() {
+            let _ = app.notification().builder().title(title).body(body).show();
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::services::{EventService, StorageService};
+use crate::types::*;
+use tauri::{AppHandle, Manager, Monitor, WebviewWindowBuilder, Window};
+
+/// Remembers the size and position of windows between launches
+pub struct WindowService;
+
+impl WindowService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Size and place a window being built, from its saved geometry or the defaults
+    pub fn place<'a, M: Manager<tauri::Wry>>(
+        &self,
+        app: &AppHandle,
+        builder: WebviewWindowBuilder<'a, tauri::Wry, M>,
+        label: &str,
+        default_size: (f64, f64),
+    ) -> WebviewWindowBuilder<'a, tauri::Wry, M> {
+        match self.saved_geometry(app, label) {
+            Some((geometry, true)) => builder
+                .inner_size(geometry.width, geometry.height)
+                .position(geometry.x, geometry.y)
+                .maximized(geometry.maximized),
+            Some((geometry, false)) => builder
+                .inner_size(geometry.width, geometry.height)
+                .center()
+                .maximized(geometry.maximized),
+            None => builder.inner_size(default_size.0, default_size.1).center(),
+        }
+    }
+
+    /// Apply the saved geometry to a window created from the config, before it is shown
+    pub fn restore(&self, window: &Window) {
+        let Some((geometry, keep_position)) =
+            self.saved_geometry(window.app_handle(), window.label())
+        else {
+            return;
+        };
+
+        let _ = window.set_size(tauri::LogicalSize::new(geometry.width, geometry.height));
+        if keep_position {
+            let _ = window.set_position(tauri::LogicalPosition::new(geometry.x, geometry.y));
+        } else {
+            let _ = window.center();
+        }
+        if geometry.maximized {
+            let _ = window.maximize();
+        }
+    }
+
+    /// Save the current geometry of a window
+    pub fn save(&self, window: &Window) -> Result<(), AppError> {
+        let Some(key) = window_geometry_key(window.label()) else {
+            return Ok(());
+        };
+        // A minimized window reports a meaningless position
+        if window.is_minimized().unwrap_or(false) {
+            return Ok(());
+        }
+
+        let app = window.app_handle();
+        let mut geometries = StorageService::load_window_geometries(app)?;
+        let maximized = window.is_maximized().unwrap_or(false);
+
+        let geometry = match geometries.get(key) {
+            // Keep the restored size so unmaximizing later goes back to it
+            Some(previous) if maximized => WindowGeometry {
+                maximized,
+                ..*previous
+            },
+            _ => {
+                let scale = window.scale_factor().map_err(Self::window_error)?;
+                let position = window
+                    .outer_position()
+                    .map_err(Self::window_error)?
+                    .to_logical::<f64>(scale);
+                let size = window
+                    .inner_size()
+                    .map_err(Self::window_error)?
+                    .to_logical::<f64>(scale);
+
+                WindowGeometry {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                    maximized,
+                }
+            }
+        };
+
+        geometries.insert(key.to_string(), geometry);
+        StorageService::save_window_geometries(app, &geometries)
+    }
+
+    /// Save every open window, quitting from the tray closes them without a close request
+    pub fn save_all(&self, app: &AppHandle) {
+        for window in app.webview_windows().values() {
+            if let Err(e) = self.save(&window.as_ref().window()) {
+                EventService::new().background_error(
+                    app,
+                    "window",
+                    format!("Failed to save window geometry: {}", e),
+                );
+            }
+        }
+    }
+
+    fn saved_geometry(&self, app: &AppHandle, label: &str) -> Option<(WindowGeometry, bool)> {
+        let key = window_geometry_key(label)?;
+        let geometries = StorageService::load_window_geometries(app).ok()?;
+        let monitors = app
+            .available_monitors()
+            .unwrap_or_default()
+            .iter()
+            .map(Self::monitor_area)
+            .collect::<Vec<_>>();
+
+        geometries
+            .get(key)
+            .map(|geometry| geometry.restore_on(&Self::primary_first(app, monitors)))
+    }
+
+    /// The primary monitor is where windows fall back to
+    fn primary_first(app: &AppHandle, mut monitors: Vec<MonitorArea>) -> Vec<MonitorArea> {
+        if let Ok(Some(primary)) = app.primary_monitor() {
+            let primary = Self::monitor_area(&primary);
+            if let Some(index) = monitors.iter().position(|monitor| *monitor == primary) {
+                monitors.swap(0, index);
+            }
+        }
+        monitors
+    }
+
+    fn monitor_area(monitor: &Monitor) -> MonitorArea {
+        let area = monitor.work_area();
+        let position = area.position.to_logical::<f64>(monitor.scale_factor());
+        let size = area.size.to_logical::<f64>(monitor.scale_factor());
+
+        MonitorArea {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    fn window_error(e: tauri::Error) -> AppError {
+        AppError::Internal(format!("Failed to read window geometry: {}", e))
+    }
+}
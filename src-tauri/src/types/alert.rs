@@ -0,0 +1,171 @@
+use crate::types::AppError;
+use serde::{Deserialize, Serialize};
+
+// Fired alerts kept, oldest are dropped first
+pub const MAX_ALERT_HISTORY: usize = 200;
+// Longest time a condition can be required to hold, one day
+pub const MAX_ALERT_DURATION_SECS: u64 = 86_400;
+
+/// What an alert rule watches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AlertCondition {
+    // Memory use above a percentage of the container's limit
+    MemoryPercent { percent: f64 },
+    CpuPercent { percent: f64 },
+    // Data directory larger than a number of bytes
+    DiskBytes { bytes: u64 },
+    // Client connections above a percentage of the container's max connections
+    ConnectionsPercent { percent: f64 },
+    // Docker restarted the container through its restart policy
+    Restarted,
+    // An integrity check reported corruption
+    IntegrityFailed,
+}
+
+/// Values of one metrics sample an alert condition is checked against
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlertReading {
+    pub cpu_percent: f64,
+    pub memory_percent: f64,
+    pub connections: Option<u64>,
+    pub disk_bytes: Option<u64>,
+}
+
+impl AlertCondition {
+    /// Measured value and whether it breaches the condition, None when the sample lacks it
+    pub fn check(&self, reading: &AlertReading, max_connections: i32) -> Option<(f64, bool)> {
+        match self {
+            AlertCondition::MemoryPercent { percent } => {
+                Some((reading.memory_percent, reading.memory_percent > *percent))
+            }
+            AlertCondition::CpuPercent { percent } => {
+                Some((reading.cpu_percent, reading.cpu_percent > *percent))
+            }
+            AlertCondition::DiskBytes { bytes } => {
+                let disk_bytes = reading.disk_bytes?;
+                Some((disk_bytes as f64, disk_bytes > *bytes))
+            }
+            AlertCondition::ConnectionsPercent { percent } => {
+                if max_connections <= 0 {
+                    return None;
+                }
+                let used = reading.connections? as f64 * 100.0 / f64::from(max_connections);
+                Some((used, used > *percent))
+            }
+            AlertCondition::Restarted | AlertCondition::IntegrityFailed => None,
+        }
+    }
+
+    /// Whether the condition fires on an event instead of being checked on metrics samples
+    pub fn is_event(&self) -> bool {
+        matches!(
+            self,
+            AlertCondition::Restarted | AlertCondition::IntegrityFailed
+        )
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            AlertCondition::MemoryPercent { percent } => format!("Memory above {}%", percent),
+            AlertCondition::CpuPercent { percent } => format!("CPU above {}%", percent),
+            AlertCondition::DiskBytes { bytes } => {
+                format!("Data above {:.1} GB", *bytes as f64 / 1e9)
+            }
+            AlertCondition::ConnectionsPercent { percent } => {
+                format!("Connections above {}% of the maximum", percent)
+            }
+            AlertCondition::Restarted => "Container restarted".to_string(),
+            AlertCondition::IntegrityFailed => "Integrity check found corruption".to_string(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Condition on one container, checked on every metrics sample
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRule {
+    // Empty when creating a rule
+    #[serde(default)]
+    pub id: String,
+    pub database_id: String,
+    pub condition: AlertCondition,
+    // Seconds the condition must hold before the alert fires, 0 fires on the first sample
+    #[serde(default)]
+    pub duration_secs: u64,
+    // Desktop notification, subject to the global notification settings
+    #[serde(default = "default_true")]
+    pub notify: bool,
+    // Receives a JSON POST when the alert fires and when it resolves
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+impl AlertRule {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let percent = match &self.condition {
+            AlertCondition::MemoryPercent { percent }
+            | AlertCondition::CpuPercent { percent }
+            | AlertCondition::ConnectionsPercent { percent } => Some(*percent),
+            AlertCondition::DiskBytes { bytes } if *bytes == 0 => {
+                return Err(AppError::Validation(
+                    "Disk threshold must be above zero".to_string(),
+                ))
+            }
+            _ => None,
+        };
+        // CPU can exceed 100% on several cores
+        if percent.is_some_and(|percent| !(percent > 0.0 && percent <= 1000.0)) {
+            return Err(AppError::Validation(
+                "Threshold must be a percentage above zero".to_string(),
+            ));
+        }
+        if self.duration_secs > MAX_ALERT_DURATION_SECS {
+            return Err(AppError::Validation(
+                "An alert can wait at most one day before firing".to_string(),
+            ));
+        }
+        if let Some(url) = &self.webhook_url {
+            // Passed to curl as a single argument
+            if !(url.starts_with("http://") || url.starts_with("https://"))
+                || url.chars().any(char::is_whitespace)
+            {
+                return Err(AppError::Validation(
+                    "Webhook URL must be an http or https URL".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Alert that fired, resolved once its condition stops holding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertEvent {
+    pub id: String,
+    pub rule_id: String,
+    pub database_id: String,
+    pub database_name: String,
+    pub message: String,
+    // Measured value when it fired
+    pub value: Option<f64>,
+    pub fired_at: String,
+    // Restart alerts never resolve
+    pub resolved_at: Option<String>,
+}
+
+/// Body of a webhook call
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertWebhookPayload {
+    // "fired" or "resolved"
+    pub state: String,
+    pub alert: AlertEvent,
+}
@@ -0,0 +1,108 @@
+use crate::types::DatabaseContainer;
+use serde::Serialize;
+
+/// Keychain key of the HTTP API token
+pub const API_TOKEN_KEY: &str = "api/token";
+
+/// Endpoint of the local HTTP API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiRoute {
+    // GET /databases
+    ListDatabases,
+    // POST /databases, the body is a provider-built run request
+    CreateDatabase,
+    // POST /databases/{id}/start
+    StartDatabase(String),
+    // POST /databases/{id}/stop
+    StopDatabase(String),
+    // POST /databases/{id}/backup, the body may carry a snapshot label
+    BackupDatabase(String),
+}
+
+impl ApiRoute {
+    pub fn parse(method: &str, path: &str) -> Option<Self> {
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (method, segments.as_slice()) {
+            ("GET", ["databases"]) => Some(ApiRoute::ListDatabases),
+            ("POST", ["databases"]) => Some(ApiRoute::CreateDatabase),
+            ("POST", ["databases", id, action]) if !id.is_empty() => match *action {
+                "start" => Some(ApiRoute::StartDatabase(id.to_string())),
+                "stop" => Some(ApiRoute::StopDatabase(id.to_string())),
+                "backup" => Some(ApiRoute::BackupDatabase(id.to_string())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Database as the HTTP API returns it, without its password or run arguments
+/// Field names are those of the stored record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiDatabase {
+    pub id: String,
+    pub name: String,
+    pub db_type: String,
+    pub version: String,
+    pub status: String,
+    pub exit_code: Option<i32>,
+    pub port: i32,
+    pub created_at: String,
+    pub container_id: Option<String>,
+    pub stored_username: Option<String>,
+    pub stored_database_name: Option<String>,
+    pub stored_persist_data: bool,
+    pub stored_enable_auth: bool,
+    pub stored_enable_tls: bool,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+    pub host: Option<String>,
+}
+
+impl From<&DatabaseContainer> for ApiDatabase {
+    fn from(database: &DatabaseContainer) -> Self {
+        Self {
+            id: database.id.clone(),
+            name: database.name.clone(),
+            db_type: database.db_type.clone(),
+            version: database.version.clone(),
+            status: database.status.clone(),
+            exit_code: database.exit_code,
+            port: database.port,
+            created_at: database.created_at.clone(),
+            container_id: database.container_id.clone(),
+            stored_username: database.stored_username.clone(),
+            stored_database_name: database.stored_database_name.clone(),
+            stored_persist_data: database.stored_persist_data,
+            stored_enable_auth: database.stored_enable_auth,
+            stored_enable_tls: database.stored_enable_tls,
+            project: database.project.clone(),
+            tags: database.tags.clone(),
+            host: database.host.clone(),
+        }
+    }
+}
+
+/// Body accepted by the backup endpoint
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct ApiBackupRequest {
+    pub label: Option<String>,
+}
+
+/// Check an `Authorization: Bearer <token>` header, in constant time
+pub fn api_token_matches(header: Option<&str>, token: &str) -> bool {
+    let Some(provided) = header.and_then(|h| h.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    if token.is_empty() || provided.len() != token.len() {
+        return false;
+    }
+
+    provided
+        .bytes()
+        .zip(token.bytes())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
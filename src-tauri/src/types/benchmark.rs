@@ -0,0 +1,279 @@
+use crate::types::AppError;
+use serde::{Deserialize, Serialize};
+
+// Runs kept per container, older ones are dropped
+pub const MAX_BENCHMARK_HISTORY: usize = 20;
+// Output lines kept with a run for reference
+pub const BENCHMARK_OUTPUT_LINES: usize = 40;
+
+/// Load generator run in a helper container next to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BenchmarkTool {
+    // PostgreSQL, from the database's own image
+    Pgbench,
+    // MySQL and MariaDB
+    Sysbench,
+    // Redis
+    Memtier,
+    // MongoDB, through the go-ycsb port
+    Ycsb,
+}
+
+impl BenchmarkTool {
+    /// Tool used for an engine when none is chosen
+    pub fn for_db_type(db_type: &str) -> Option<Self> {
+        match db_type {
+            "PostgreSQL" => Some(BenchmarkTool::Pgbench),
+            "MySQL" | "MariaDB" => Some(BenchmarkTool::Sysbench),
+            "Redis" => Some(BenchmarkTool::Memtier),
+            "MongoDB" => Some(BenchmarkTool::Ycsb),
+            _ => None,
+        }
+    }
+
+    pub fn supports(&self, db_type: &str) -> bool {
+        BenchmarkTool::for_db_type(db_type) == Some(*self)
+    }
+}
+
+fn default_clients() -> u32 {
+    8
+}
+
+fn default_threads() -> u32 {
+    2
+}
+
+fn default_duration_secs() -> u32 {
+    30
+}
+
+fn default_scale() -> u32 {
+    10
+}
+
+/// What to run, every field has a default suited to a laptop
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkParams {
+    // The engine's usual tool when not set
+    #[serde(default)]
+    pub tool: Option<BenchmarkTool>,
+    // Concurrent connections
+    #[serde(default = "default_clients")]
+    pub clients: u32,
+    // Worker threads of the tool, at most `clients`
+    #[serde(default = "default_threads")]
+    pub threads: u32,
+    #[serde(default = "default_duration_secs")]
+    pub duration_secs: u32,
+    // Data set size: pgbench scale factor, or thousands of rows or records for the others
+    #[serde(default = "default_scale")]
+    pub scale: u32,
+    // sysbench test (e.g. "oltp_read_only") or YCSB workload letter ("a", "b" or "c")
+    #[serde(default)]
+    pub workload: Option<String>,
+}
+
+impl Default for BenchmarkParams {
+    fn default() -> Self {
+        Self {
+            tool: None,
+            clients: default_clients(),
+            threads: default_threads(),
+            duration_secs: default_duration_secs(),
+            scale: default_scale(),
+            workload: None,
+        }
+    }
+}
+
+impl BenchmarkParams {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !(1..=1000).contains(&self.clients) {
+            return Err(AppError::Validation(
+                "Clients must be between 1 and 1000".to_string(),
+            ));
+        }
+        if self.threads == 0 || self.threads > self.clients {
+            return Err(AppError::Validation(
+                "Threads must be between 1 and the number of clients".to_string(),
+            ));
+        }
+        if !(1..=3600).contains(&self.duration_secs) {
+            return Err(AppError::Validation(
+                "Duration must be between 1 second and 1 hour".to_string(),
+            ));
+        }
+        if !(1..=10_000).contains(&self.scale) {
+            return Err(AppError::Validation(
+                "Scale must be between 1 and 10000".to_string(),
+            ));
+        }
+        if let Some(workload) = &self.workload {
+            // Passed to the tool as a single argument
+            if !workload
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return Err(AppError::Validation(format!(
+                    "Unknown workload '{}'",
+                    workload
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Figures parsed from a tool's report, each tool fills what it reports
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkMetrics {
+    // Transactions per second for pgbench and sysbench, operations per second otherwise
+    pub throughput: Option<f64>,
+    pub latency_avg_ms: Option<f64>,
+    pub latency_p95_ms: Option<f64>,
+    pub latency_p99_ms: Option<f64>,
+}
+
+impl BenchmarkMetrics {
+    /// Metrics of the report `output` of `tool`
+    pub fn parse(tool: BenchmarkTool, output: &str) -> Self {
+        match tool {
+            BenchmarkTool::Pgbench => Self::parse_pgbench(output),
+            BenchmarkTool::Sysbench => Self::parse_sysbench(output),
+            BenchmarkTool::Memtier => Self::parse_memtier(output),
+            BenchmarkTool::Ycsb => Self::parse_ycsb(output),
+        }
+    }
+
+    // First number of `text`, e.g. "2890.12 (without initial connection time)"
+    fn leading_number(text: &str) -> Option<f64> {
+        text.split_whitespace().next()?.parse().ok()
+    }
+
+    /// "latency average = 3.456 ms" and "tps = 2890.123456 (without initial connection time)"
+    fn parse_pgbench(output: &str) -> Self {
+        let mut metrics = Self::default();
+        for line in output.lines() {
+            let line = line.trim();
+            if let Some(tps) = line.strip_prefix("tps = ") {
+                metrics.throughput = Self::leading_number(tps);
+            } else if let Some(latency) = line.strip_prefix("latency average = ") {
+                metrics.latency_avg_ms = Self::leading_number(latency);
+            }
+        }
+        metrics
+    }
+
+    /// "transactions:  12345  (411.42 per sec.)" and the "Latency (ms):" block
+    fn parse_sysbench(output: &str) -> Self {
+        let mut metrics = Self::default();
+        for line in output.lines() {
+            let Some((key, value)) = line.trim().split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key {
+                "transactions" => {
+                    metrics.throughput = value
+                        .split_once('(')
+                        .and_then(|(_, rate)| Self::leading_number(rate));
+                }
+                "avg" => metrics.latency_avg_ms = Self::leading_number(value),
+                "95th percentile" => metrics.latency_p95_ms = Self::leading_number(value),
+                _ => {}
+            }
+        }
+        metrics
+    }
+
+    /// "Totals" row of the table whose header starts with "Type", columns are named by
+    /// the header since memtier versions differ in which latencies they print
+    fn parse_memtier(output: &str) -> Self {
+        let mut metrics = Self::default();
+        let mut header: Vec<String> = Vec::new();
+
+        for line in output.lines() {
+            let line = line.trim();
+            if line.starts_with("Type ") {
+                // Column names contain single spaces, columns are set apart by several
+                header = line
+                    .split("  ")
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_owned)
+                    .collect();
+            } else if line.starts_with("Totals ") {
+                let values: Vec<&str> = line.split_whitespace().collect();
+                let column = |name: &str| -> Option<f64> {
+                    let index = header.iter().position(|column| column == name)?;
+                    values.get(index)?.parse().ok()
+                };
+                metrics.throughput = column("Ops/sec");
+                metrics.latency_avg_ms = column("Avg. Latency").or_else(|| column("Latency"));
+                metrics.latency_p99_ms = column("p99 Latency");
+            }
+        }
+        metrics
+    }
+
+    /// "TOTAL  - Takes(s): 30.0, Count: 123456, OPS: 4115.2, Avg(us): 1940, ..., 99th(us): 5000"
+    fn parse_ycsb(output: &str) -> Self {
+        let mut metrics = Self::default();
+        let Some(total) = output
+            .lines()
+            .rev()
+            .find_map(|line| line.trim().strip_prefix("TOTAL"))
+        else {
+            return metrics;
+        };
+
+        let micros_to_ms = |value: f64| value / 1000.0;
+        for field in total.trim_start_matches([' ', '-']).split(", ") {
+            let Some((key, value)) = field.split_once(':') else {
+                continue;
+            };
+            let value: Option<f64> = value.trim().parse().ok();
+            match key.trim() {
+                "OPS" => metrics.throughput = value,
+                "Avg(us)" => metrics.latency_avg_ms = value.map(micros_to_ms),
+                "95th(us)" => metrics.latency_p95_ms = value.map(micros_to_ms),
+                "99th(us)" => metrics.latency_p99_ms = value.map(micros_to_ms),
+                _ => {}
+            }
+        }
+        metrics
+    }
+}
+
+/// Finished benchmark kept in the history of its container
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkRun {
+    pub id: String,
+    pub database_id: String,
+    pub tool: BenchmarkTool,
+    pub params: BenchmarkParams,
+    pub started_at: String,
+    pub finished_at: String,
+    pub metrics: BenchmarkMetrics,
+    // Last lines of the tool's report
+    pub output: String,
+}
+
+/// Last `BENCHMARK_OUTPUT_LINES` lines of a report
+pub fn benchmark_output_tail(output: &str) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    lines[lines.len().saturating_sub(BENCHMARK_OUTPUT_LINES)..].join("\n")
+}
+
+/// Docker invocations of one benchmark, run in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchmarkPlan {
+    pub steps: Vec<Vec<String>>,
+    // Step whose output is the report, failures of later cleanup steps are ignored
+    pub measured: usize,
+}
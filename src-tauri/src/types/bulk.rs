@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// Containers handled at the same time by bulk operations, autostart and shutdown
+pub const BULK_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BulkAction {
+    Start,
+    Stop,
+    Remove,
+}
+
+/// Outcome of a bulk operation for one container
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkResult {
+    pub container_id: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl BulkResult {
+    pub fn from_result<T, E: std::fmt::Display>(
+        container_id: String,
+        result: Result<T, E>,
+    ) -> Self {
+        match result {
+            Ok(_) => Self {
+                container_id,
+                success: true,
+                error: None,
+            },
+            Err(error) => Self {
+                container_id,
+                success: false,
+                error: Some(error.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::types::{
+    normalize_container_name, validate_container_name, AppError, DatabaseContainer,
+    DockerRunRequest, FIRST_UNPRIVILEGED_PORT,
+};
+use serde::{Deserialize, Serialize};
+
+/// Format version written to database bundles
+pub const DATABASE_BUNDLE_VERSION: u32 = 1;
+// Entries of a bundle archive, an uncompressed tar
+pub const BUNDLE_MANIFEST_FILE: &str = "manifest.json";
+pub const BUNDLE_CONFIG_FILE: &str = "database.json";
+pub const BUNDLE_DATA_FILE: &str = "data.tar.gz";
+
+/// File inside a bundle with its checksum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleFile {
+    pub name: String,
+    // Hex SHA-256 of the file
+    pub sha256: String,
+    pub size_bytes: u64,
+}
+
+impl BundleFile {
+    /// One `<sha256> <size> <name>` line of the bundle script
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let sha256 = fields.next()?;
+        let size_bytes = fields.next()?.parse().ok()?;
+        let name = fields.next()?;
+        if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        Some(Self {
+            name: name.to_string(),
+            sha256: sha256.to_lowercase(),
+            size_bytes,
+        })
+    }
+}
+
+/// Describes a bundle, read first when it is imported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    pub version: u32,
+    // Version of the app that wrote the bundle
+    pub app_version: String,
+    pub exported_at: String,
+    pub db_type: String,
+    pub engine_version: String,
+    pub image: String,
+    // Name of the exported container
+    pub name: String,
+    pub files: Vec<BundleFile>,
+}
+
+impl BundleManifest {
+    pub fn new(app_version: &str, database: &DatabaseContainer, files: Vec<BundleFile>) -> Self {
+        Self {
+            version: DATABASE_BUNDLE_VERSION,
+            app_version: app_version.to_string(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            db_type: database.db_type.clone(),
+            engine_version: database.version.clone(),
+            image: database
+                .stored_docker_args
+                .as_ref()
+                .map(|docker_args| docker_args.image.clone())
+                .unwrap_or_default(),
+            name: database.name.clone(),
+            files,
+        }
+    }
+
+    pub fn file(&self, name: &str) -> Option<&BundleFile> {
+        self.files.iter().find(|file| file.name == name)
+    }
+
+    /// Check the manifest against the files unpacked from the bundle
+    pub fn verify(&self, unpacked: &[BundleFile]) -> Result<(), AppError> {
+        if self.version == 0 || self.version > DATABASE_BUNDLE_VERSION {
+            return Err(AppError::Validation(format!(
+                "Bundle format {} is not supported, update the app to import it",
+                self.version
+            )));
+        }
+
+        for name in [BUNDLE_CONFIG_FILE, BUNDLE_DATA_FILE] {
+            let expected = self.file(name).ok_or_else(|| {
+                AppError::Validation(format!("The bundle manifest does not list {}", name))
+            })?;
+            let actual = unpacked
+                .iter()
+                .find(|file| file.name == name)
+                .ok_or_else(|| AppError::Validation(format!("The bundle has no {}", name)))?;
+            if actual.sha256 != expected.sha256 || actual.size_bytes != expected.size_bytes {
+                return Err(AppError::Validation(format!(
+                    "{} does not match the bundle manifest, the bundle is damaged",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Bundle written by `export_bundle`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleExport {
+    pub path: String,
+    pub manifest: BundleManifest,
+}
+
+/// Host port moved because it was taken on this machine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortRemap {
+    pub from: i32,
+    pub to: i32,
+}
+
+/// Bundle recreated by `import_bundle`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleImport {
+    pub database: DatabaseContainer,
+    pub manifest: BundleManifest,
+    // Name of the exported container when it was taken here
+    pub renamed_from: Option<String>,
+    pub remapped_ports: Vec<PortRemap>,
+}
+
+/// Move a bundled request off the names and host ports already in use
+/// Volumes named after the container follow the new name
+pub fn relocate_bundle_request(
+    request: &mut DockerRunRequest,
+    taken_names: &[String],
+    port_free: impl Fn(i32) -> bool,
+) -> (Option<String>, Vec<PortRemap>) {
+    let mut renamed_from = None;
+    if validate_container_name(&request.name, taken_names).is_err() {
+        let name = normalize_container_name(&request.name, taken_names);
+        let old_prefix = format!("{}-", request.name);
+        for volume in &mut request.docker_args.volumes {
+            if let Some(rest) = volume.name.strip_prefix(&old_prefix) {
+                volume.name = format!("{}-{}", name, rest);
+            }
+        }
+        renamed_from = Some(std::mem::replace(&mut request.name, name));
+    }
+
+    let mut host_ports = vec![request.metadata.port];
+    host_ports.extend(
+        request
+            .docker_args
+            .ports
+            .iter()
+            .map(|mapping| mapping.host)
+            .filter(|port| *port != request.metadata.port),
+    );
+
+    let mut remapped: Vec<PortRemap> = Vec::new();
+    for port in &host_ports {
+        if port_free(*port) {
+            continue;
+        }
+        let next = (*port + 1..=65535)
+            .chain(FIRST_UNPRIVILEGED_PORT..*port)
+            .find(|candidate| {
+                !host_ports.contains(candidate)
+                    && !remapped.iter().any(|remap| remap.to == *candidate)
+                    && port_free(*candidate)
+            });
+        if let Some(to) = next {
+            remapped.push(PortRemap { from: *port, to });
+        }
+    }
+
+    for remap in &remapped {
+        if request.metadata.port == remap.from {
+            request.metadata.port = remap.to;
+        }
+        for mapping in &mut request.docker_args.ports {
+            if mapping.host == remap.from {
+                mapping.host = remap.to;
+            }
+        }
+    }
+
+    (renamed_from, remapped)
+}
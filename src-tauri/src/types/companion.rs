@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Admin tool container deployed next to a managed database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompanionContainer {
+    pub id: String,
+    // Tool identifier: "pgadmin", "phpmyadmin", "mongo-express", or "redisinsight"
+    pub kind: String,
+    pub name: String,
+    pub database_id: String,
+    pub container_id: Option<String>,
+    pub status: String,
+    pub port: i32,
+    pub url: String,
+    pub created_at: String,
+}
+
+pub type CompanionStore = std::sync::Mutex<std::collections::HashMap<String, CompanionContainer>>;
@@ -0,0 +1,36 @@
+use crate::types::{AppSettings, DatabaseContainer};
+use serde::{Deserialize, Serialize};
+
+/// Format version written to exported bundles
+pub const CONFIG_BUNDLE_VERSION: u32 = 1;
+
+/// Portable snapshot of managed containers and settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub includes_secrets: bool,
+    pub settings: AppSettings,
+    pub databases: Vec<DatabaseContainer>,
+}
+
+/// Container from a bundle that could not be recreated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedImport {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Outcome of importing a bundle
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub imported: Vec<DatabaseContainer>,
+    pub skipped: Vec<SkippedImport>,
+    // Containers that were imported but could not be put back in their exported state
+    #[serde(default)]
+    pub warnings: Vec<SkippedImport>,
+    // Whether the bundle's settings replaced the current ones
+    #[serde(default)]
+    pub settings_applied: bool,
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Variable naming used by a generated .env block
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EnvPreset {
+    // DATABASE_URL plus DB_HOST, DB_PORT, DB_USER, DB_PASSWORD and DB_NAME
+    #[default]
+    Generic,
+    Rails,
+    Django,
+    Laravel,
+    Prisma,
+}
+
+/// External client a database can be opened in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DatabaseClient {
+    TablePlus,
+    DBeaver,
+    PgAdmin,
+    Beekeeper,
+    // The engine's own shell (psql, mysql, mongosh...) run in the container from a terminal
+    Cli,
+}
+
+impl DatabaseClient {
+    pub const ALL: [DatabaseClient; 5] = [
+        DatabaseClient::TablePlus,
+        DatabaseClient::DBeaver,
+        DatabaseClient::PgAdmin,
+        DatabaseClient::Beekeeper,
+        DatabaseClient::Cli,
+    ];
+
+    pub fn supports(&self, db_type: &str) -> bool {
+        match self {
+            DatabaseClient::TablePlus => db_type != "Elasticsearch",
+            DatabaseClient::DBeaver | DatabaseClient::Beekeeper => {
+                matches!(db_type, "PostgreSQL" | "MySQL" | "MariaDB" | "SQLServer")
+            }
+            DatabaseClient::PgAdmin => db_type == "PostgreSQL",
+            DatabaseClient::Cli => db_type != "Elasticsearch",
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::types::AppError;
+
+// Longest name that still fits a DNS label, companions reach the database by its name
+pub const MAX_CONTAINER_NAME_LENGTH: usize = 63;
+
+// Used when nothing of the requested name survives normalization
+const FALLBACK_CONTAINER_NAME: &str = "database";
+
+/// Why `name` breaks Docker's naming rules (`[a-zA-Z0-9][a-zA-Z0-9_.-]+`), `None` when valid
+pub fn container_name_violation(name: &str) -> Option<String> {
+    let Some(first) = name.chars().next() else {
+        return Some("cannot be empty".to_string());
+    };
+
+    if name.len() > MAX_CONTAINER_NAME_LENGTH {
+        return Some(format!(
+            "must be at most {} characters long",
+            MAX_CONTAINER_NAME_LENGTH
+        ));
+    }
+    if !first.is_ascii_alphanumeric() {
+        return Some("must start with a letter or a digit".to_string());
+    }
+    if let Some(invalid) = name.chars().find(|c| !is_name_char(*c)) {
+        return Some(format!(
+            "contains '{}', only letters, digits, '_', '.' and '-' are allowed",
+            invalid
+        ));
+    }
+    if name.len() < 2 {
+        return Some("must be at least 2 characters long".to_string());
+    }
+
+    None
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+/// Closest valid name to `name` that is not in `taken`
+pub fn normalize_container_name(name: &str, taken: &[String]) -> String {
+    let mut normalized = String::new();
+    for c in name.trim().chars() {
+        let c = if is_name_char(c) { c } else { '-' };
+        // Runs of replaced characters collapse into a single dash
+        if c == '-' && (normalized.is_empty() || normalized.ends_with('-')) {
+            continue;
+        }
+        normalized.push(c);
+    }
+
+    let base: String = normalized
+        .trim_start_matches(|c: char| !c.is_ascii_alphanumeric())
+        .chars()
+        .take(MAX_CONTAINER_NAME_LENGTH)
+        .collect();
+    let base = base.trim_end_matches('-');
+    let base = match base.len() {
+        0 => FALLBACK_CONTAINER_NAME.to_string(),
+        1 => format!("{}-db", base),
+        _ => base.to_string(),
+    };
+
+    if !taken.contains(&base) {
+        return base;
+    }
+
+    (2..)
+        .map(|n| {
+            let suffix = format!("-{}", n);
+            let stem: String = base
+                .chars()
+                .take(MAX_CONTAINER_NAME_LENGTH - suffix.len())
+                .collect();
+            format!("{}{}", stem, suffix)
+        })
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap()
+}
+
+/// Check a requested name before anything is created, `taken` are the other managed names
+pub fn validate_container_name(name: &str, taken: &[String]) -> Result<(), AppError> {
+    let reason = if taken.iter().any(|other| other == name) {
+        Some("is already used by another database".to_string())
+    } else {
+        container_name_violation(name)
+    };
+
+    match reason {
+        Some(reason) => Err(AppError::InvalidName {
+            name: name.to_string(),
+            reason,
+            suggestion: normalize_container_name(name, taken),
+        }),
+        None => Ok(()),
+    }
+}
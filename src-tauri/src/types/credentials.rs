@@ -0,0 +1,85 @@
+use crate::types::{ContainerMetadata, DatabaseContainer};
+
+/// Login of a database with the engine defaults filled in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseCredentials {
+    pub username: String,
+    pub password: String,
+    // Empty for engines without a database created at init
+    pub database: String,
+}
+
+impl DatabaseCredentials {
+    /// User the official image creates when none is given
+    pub fn default_username(db_type: &str) -> &'static str {
+        match db_type {
+            "PostgreSQL" => "postgres",
+            "MySQL" | "MariaDB" => "root",
+            "MongoDB" => "admin",
+            "SQLServer" => "sa",
+            _ => "",
+        }
+    }
+
+    /// Whether the engine reads its credentials on every start instead of at init
+    /// Redis gets its password as a server argument, so a new container is enough
+    pub fn applies_on_start(db_type: &str) -> bool {
+        db_type == "Redis"
+    }
+
+    fn resolve(
+        db_type: &str,
+        username: Option<&str>,
+        password: String,
+        database: Option<&str>,
+    ) -> Self {
+        let username = username
+            .filter(|username| !username.is_empty())
+            .unwrap_or(Self::default_username(db_type))
+            .to_string();
+        // PostgreSQL names the initial database after the user
+        let database = match database.filter(|database| !database.is_empty()) {
+            Some(database) => database.to_string(),
+            None if db_type == "PostgreSQL" => username.clone(),
+            None => String::new(),
+        };
+
+        Self {
+            username,
+            password,
+            database,
+        }
+    }
+
+    /// Credentials the running database currently accepts
+    pub fn of(database: &DatabaseContainer) -> Self {
+        Self::resolve(
+            &database.db_type,
+            database.stored_username.as_deref(),
+            database.stored_password.clone().unwrap_or_default(),
+            database.stored_database_name.as_deref(),
+        )
+    }
+
+    /// Credentials an update asks for, an empty password keeps the current one
+    /// An empty database name keeps the current database, a new user does not rename it
+    pub fn requested(metadata: &ContainerMetadata, current: &Self) -> Self {
+        let password = if metadata.password.is_empty() {
+            current.password.clone()
+        } else {
+            metadata.password.clone()
+        };
+        let database = metadata
+            .database_name
+            .as_deref()
+            .filter(|database| !database.is_empty())
+            .unwrap_or(&current.database);
+
+        Self::resolve(
+            &metadata.db_type,
+            metadata.username.as_deref(),
+            password,
+            Some(database),
+        )
+    }
+}
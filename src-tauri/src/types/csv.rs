@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+
+fn default_header() -> bool {
+    true
+}
+
+/// How `export_table` writes a table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableExportOptions {
+    // SQL schema, or MongoDB database; the database created with the container by default
+    #[serde(default)]
+    pub schema: Option<String>,
+    // Columns or document fields to write, all columns when empty
+    // MongoDB reads the fields of the first document when none are given
+    #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default = "default_header")]
+    pub header: bool,
+}
+
+impl Default for TableExportOptions {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            columns: Vec::new(),
+            header: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableExportResult {
+    pub path: String,
+    pub bytes_written: u64,
+}
+
+/// One CSV field, quoted when it holds a delimiter, a quote or a line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One CSV record terminated by a newline
+pub fn csv_record(fields: &[String]) -> String {
+    let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+    format!("{}\n", fields.join(","))
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+/// How `import_csv` maps a file onto a table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportMapping {
+    // SQL schema, or MongoDB database; the database created with the container by default
+    #[serde(default)]
+    pub schema: Option<String>,
+    // Target column of each CSV column in file order, the header names when empty
+    #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    // Whether the first record names the columns instead of holding data
+    #[serde(default = "default_header")]
+    pub header: bool,
+    // Confirms turning on MySQL's local_infile for the import, it is turned off again after
+    #[serde(default)]
+    pub enable_local_infile: bool,
+}
+
+impl Default for CsvImportMapping {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            columns: Vec::new(),
+            delimiter: default_delimiter(),
+            header: true,
+            enable_local_infile: false,
+        }
+    }
+}
+
+/// Problem the engine reported with the data, `line` is the file line when known
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvRowError {
+    pub line: Option<u64>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportReport {
+    pub rows_imported: u64,
+    pub errors: Vec<CsvRowError>,
+}
+
+/// Byte length of the first record including its line break, `None` when `bytes` ends first
+/// Line breaks inside quoted fields belong to the record
+pub fn first_record_len(bytes: &[u8]) -> Option<usize> {
+    let mut in_quotes = false;
+    for (index, byte) in bytes.iter().enumerate() {
+        match byte {
+            b'"' => in_quotes = !in_quotes,
+            b'\n' if !in_quotes => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Fields of one CSV record, without its line break
+pub fn parse_csv_record(record: &str, delimiter: char) -> Vec<String> {
+    let record = record.trim_end_matches(['\r', '\n']);
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = record.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Counts CSV records across chunks of a stream
+#[derive(Debug, Default)]
+pub struct CsvRecordCounter {
+    in_quotes: bool,
+    // Bytes seen since the last record ended
+    pending: bool,
+    records: u64,
+}
+
+impl CsvRecordCounter {
+    pub fn push(&mut self, chunk: &[u8]) {
+        for byte in chunk {
+            match byte {
+                b'"' => {
+                    self.in_quotes = !self.in_quotes;
+                    self.pending = true;
+                }
+                b'\n' if !self.in_quotes => {
+                    if self.pending {
+                        self.records += 1;
+                    }
+                    self.pending = false;
+                }
+                // Blank CRLF lines are no records
+                b'\r' => {}
+                _ => self.pending = true,
+            }
+        }
+    }
+
+    /// Records seen, a last record without a line break included
+    pub fn finish(&self) -> u64 {
+        self.records + u64::from(self.pending)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::types::AppError;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DataDirEntryKind {
+    File,
+    Directory,
+    Symlink,
+    Other,
+}
+
+/// File or directory inside a database's data directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataDirEntry {
+    pub name: String,
+    pub kind: DataDirEntryKind,
+    // Disk usage of everything below a directory, the length of a file
+    pub size_bytes: u64,
+    // RFC 3339
+    pub modified_at: Option<String>,
+}
+
+impl DataDirEntry {
+    /// One line of the listing script: `<du KiB>\t<stat %F>\t<stat %s>\t<stat %Y>\t<name>`
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+        let disk_kib: u64 = fields.next()?.trim().parse().unwrap_or_default();
+        let file_type = fields.next()?;
+        let length: u64 = fields.next()?.parse().unwrap_or_default();
+        let modified = fields.next()?.parse::<i64>().ok();
+        let name = fields.next()?.trim_start_matches("./").to_string();
+        if name.is_empty() {
+            return None;
+        }
+
+        let kind = match file_type {
+            "regular file" | "regular empty file" => DataDirEntryKind::File,
+            "directory" => DataDirEntryKind::Directory,
+            "symbolic link" => DataDirEntryKind::Symlink,
+            _ => DataDirEntryKind::Other,
+        };
+
+        Some(Self {
+            name,
+            kind,
+            size_bytes: match kind {
+                DataDirEntryKind::Directory => disk_kib * 1024,
+                _ => length,
+            },
+            modified_at: modified
+                .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+                .map(|modified| modified.to_rfc3339()),
+        })
+    }
+}
+
+/// Contents of one directory below the data directory, largest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataDirListing {
+    // Data directory inside the database container
+    pub root: String,
+    // Listed directory relative to `root`, empty for the root itself
+    pub path: String,
+    pub entries: Vec<DataDirEntry>,
+    pub total_bytes: u64,
+    // Read through a helper container from the volume, the database is stopped
+    pub from_volume: bool,
+}
+
+impl DataDirListing {
+    pub fn new(root: &str, path: &str, mut entries: Vec<DataDirEntry>, from_volume: bool) -> Self {
+        entries.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.name.cmp(&b.name)));
+
+        Self {
+            root: root.to_string(),
+            path: path.to_string(),
+            total_bytes: entries.iter().map(|entry| entry.size_bytes).sum(),
+            entries,
+            from_volume,
+        }
+    }
+
+    /// `path` relative to the data directory without `.` or empty components
+    /// Paths leaving the data directory are rejected
+    pub fn normalize_path(path: &str) -> Result<String, AppError> {
+        let mut components = Vec::new();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    return Err(AppError::Validation(
+                        "Only the data directory can be browsed".to_string(),
+                    ))
+                }
+                component => components.push(component),
+            }
+        }
+        Ok(components.join("/"))
+    }
+
+    /// `relative` inside the directory at `root`
+    pub fn join(root: &str, relative: &str) -> String {
+        if relative.is_empty() {
+            root.to_string()
+        } else {
+            format!("{}/{}", root.trim_end_matches('/'), relative)
+        }
+    }
+}
@@ -1,22 +1,285 @@
+use crate::types::{
+    DockerContainerState, DockerRunArgs, DockerRunRequest, Framework, LocaleSettings,
+    RegionSettings, TuningSettings, WatchdogPolicy, LOCAL_HOST_ID,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Every status a database can have
+pub const DATABASE_STATUSES: [&str; 6] = [
+    "running",
+    "starting",
+    "unhealthy",
+    "restarting",
+    "exited",
+    "stopped",
+];
+
+/// Whether a database with this status has its engine process up
+pub fn is_running_status(status: &str) -> bool {
+    matches!(status, "running" | "starting" | "unhealthy")
+}
+
+/// Whether the data of a persistent database still has somewhere to live
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageState {
+    #[default]
+    Ok,
+    // Persistence is enabled but the data volume was removed outside the app
+    VolumeMissing,
+}
+
+/// How `repair_storage` fixes a database whose volume is missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageRepair {
+    // Create an empty volume under the expected name
+    RecreateVolume,
+    // Keep the database without persistence
+    DisablePersistence,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DatabaseContainer {
     pub id: String,
     pub name: String,
     pub db_type: String,
     pub version: String,
+    // "running", "starting", "unhealthy", "restarting", "exited" or "stopped"
     pub status: String,
+    // Exit code of the last run, set while the status is "exited"
+    #[serde(default)]
+    pub exit_code: Option<i32>,
     pub port: i32,
+    // RFC 3339, entries saved by earlier versions only had the date
     pub created_at: String,
     pub max_connections: i32,
     pub container_id: Option<String>,
     // Store these to recreate container when needed
+    // The password lives in the system keychain, the store only keeps its key
     pub stored_password: Option<String>,
+    #[serde(default)]
+    pub stored_password_ref: Option<String>,
     pub stored_username: Option<String>,
     pub stored_database_name: Option<String>,
     pub stored_persist_data: bool,
     pub stored_enable_auth: bool,
+    #[serde(default)]
+    pub stored_enable_tls: bool,
+    // Ports published on all interfaces instead of 127.0.0.1
+    #[serde(default)]
+    pub stored_expose_to_lan: bool,
+    // Host path of the CA certificate clients need to verify the server
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    // Provider-built run arguments, used to recreate the container from an export
+    #[serde(default)]
+    pub stored_docker_args: Option<DockerRunArgs>,
+    // Group the container belongs to
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub last_started_at: Option<String>,
+    #[serde(default)]
+    pub last_stopped_at: Option<String>,
+    // The registry has a newer digest of the image or a newer major version
+    #[serde(default)]
+    pub updates_available: bool,
+    #[serde(default)]
+    pub storage_state: StorageState,
+    // Started when the app launches
+    #[serde(default)]
+    pub auto_start: bool,
+    // Left running when the app quits with stop on exit enabled
+    #[serde(default)]
+    pub keep_running_on_exit: bool,
+    // Slow queries logged before this are hidden, PostgreSQL writes them to the container
+    // log, which cannot be cleared
+    #[serde(default)]
+    pub slow_log_reset_at: Option<String>,
+    // Framework preset the database was created for, its snippet is offered by default
+    #[serde(default)]
+    pub framework: Option<Framework>,
+    // Encoding and collation chosen at creation, kept over recreations
+    #[serde(default)]
+    pub locale: Option<LocaleSettings>,
+    // Server arguments appended to the provider's command, editable after creation
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    // SQL run once the database first accepts connections, e.g. enabling pgvector
+    #[serde(default)]
+    pub stored_post_create_sql: Vec<String>,
+    // Seconds a stop waits for the engine to shut down before killing it, Docker's default when unset
+    #[serde(default)]
+    pub stop_timeout: Option<u32>,
+    // Timezone and system locale of the container
+    #[serde(default)]
+    pub region: Option<RegionSettings>,
+    // Preset the memory settings were sized with, re-applied on recreation
+    #[serde(default)]
+    pub tuning: Option<TuningSettings>,
+    // Restarts the container when it exits unexpectedly, off when unset
+    #[serde(default)]
+    pub watchdog: Option<WatchdogPolicy>,
+    // Id of the Docker host it runs on, None for the local daemon
+    #[serde(default)]
+    pub host: Option<String>,
+    // What went wrong setting the container up after it was created, e.g. a failed readiness probe
+    #[serde(default)]
+    pub setup_warnings: Vec<String>,
+}
+
+impl DatabaseContainer {
+    /// Record of a container just started from a run request
+    pub fn from_run_request(request: &DockerRunRequest, container_id: String) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: request.metadata.id.clone(),
+            name: request.name.clone(),
+            db_type: request.metadata.db_type.clone(),
+            version: request.metadata.version.clone(),
+            status: "running".to_string(),
+            port: request.metadata.port,
+            created_at: now.to_rfc3339(),
+            max_connections: request.metadata.max_connections.unwrap_or(100),
+            container_id: Some(container_id),
+            stored_password: Some(request.metadata.password.clone()),
+            stored_username: request.metadata.username.clone(),
+            stored_database_name: request.metadata.database_name.clone(),
+            stored_persist_data: request.metadata.persist_data,
+            stored_enable_auth: request.metadata.enable_auth,
+            stored_enable_tls: request.metadata.enable_tls,
+            stored_expose_to_lan: request.metadata.expose_to_lan,
+            stored_docker_args: Some(request.docker_args.clone()),
+            last_started_at: Some(now.to_rfc3339()),
+            framework: request.metadata.framework,
+            locale: request.metadata.locale.clone(),
+            extra_args: request.metadata.extra_args.clone(),
+            stored_post_create_sql: request.metadata.post_create_sql.clone(),
+            region: request.metadata.region.clone(),
+            tuning: request.metadata.tuning,
+            host: request.metadata.host.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Record that the container just stopped
+    pub fn mark_stopped(&mut self) {
+        if self.status != "stopped" {
+            self.last_stopped_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        self.status = "stopped".to_string();
+        self.exit_code = None;
+    }
+
+    /// PostgreSQL run from a pgvector image, ready to store embeddings
+    pub fn has_pgvector(&self) -> bool {
+        self.db_type == "PostgreSQL"
+            && self
+                .stored_docker_args
+                .as_ref()
+                .is_some_and(|args| args.image.starts_with("pgvector/"))
+    }
+
+    /// Named volumes the database keeps its data in, empty without persistence
+    pub fn data_volumes(&self) -> Vec<String> {
+        if !self.stored_persist_data {
+            return Vec::new();
+        }
+
+        match &self.stored_docker_args {
+            Some(args) if !args.volumes.is_empty() => args
+                .volumes
+                .iter()
+                .map(|volume| volume.name.clone())
+                .collect(),
+            // Entries from before the arguments were stored use the provider naming
+            _ => vec![format!("{}-data", self.name)],
+        }
+    }
+
+    /// Storage state given the names of the volumes Docker has
+    pub fn storage_state_in(&self, volumes: &HashSet<String>) -> StorageState {
+        if self
+            .data_volumes()
+            .iter()
+            .all(|volume| volumes.contains(volume))
+        {
+            StorageState::Ok
+        } else {
+            StorageState::VolumeMissing
+        }
+    }
+
+    /// Id of the Docker host it runs on, `LOCAL_HOST_ID` for the local daemon
+    pub fn host_id(&self) -> &str {
+        self.host.as_deref().unwrap_or(LOCAL_HOST_ID)
+    }
+
+    /// Whether the engine process is up, healthy or not
+    pub fn is_running(&self) -> bool {
+        is_running_status(&self.status)
+    }
+
+    /// Take the status Docker reports for the container
+    pub fn apply_docker_state(&mut self, state: &DockerContainerState) {
+        self.status = state.status().to_string();
+        self.exit_code = if self.status == "exited" {
+            state.exit_code
+        } else {
+            None
+        };
+    }
+
+    /// Map a status earlier versions or an interrupted write left behind to "stopped"
+    /// Returns whether the entry changed and needs saving
+    pub fn migrate_status(&mut self) -> bool {
+        if DATABASE_STATUSES.contains(&self.status.as_str()) {
+            return false;
+        }
+
+        self.status = "stopped".to_string();
+        self.exit_code = None;
+        true
+    }
+
+    /// Turn a date-only `created_at` from earlier versions into a timestamp at midnight UTC
+    /// Returns whether the entry changed and needs saving
+    pub fn migrate_created_at(&mut self) -> bool {
+        if chrono::DateTime::parse_from_rfc3339(&self.created_at).is_ok() {
+            return false;
+        }
+        let Ok(date) = chrono::NaiveDate::parse_from_str(&self.created_at, "%Y-%m-%d") else {
+            return false;
+        };
+
+        self.created_at = date.and_time(chrono::NaiveTime::MIN).and_utc().to_rfc3339();
+        true
+    }
+
+    /// Trim tags, drop empty ones and duplicates (case-insensitive), keeping order
+    pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::new();
+        for tag in tags {
+            let tag = tag.trim().to_string();
+            if !tag.is_empty() && !normalized.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                normalized.push(tag);
+            }
+        }
+        normalized
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim()))
+    }
 }
 
-pub type DatabaseStore = std::sync::Mutex<std::collections::HashMap<String, DatabaseContainer>>;
+/// Managed databases by id
+/// An async lock, commands await it instead of blocking a runtime thread
+pub type DatabaseStore = tokio::sync::RwLock<std::collections::HashMap<String, DatabaseContainer>>;
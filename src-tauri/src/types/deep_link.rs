@@ -0,0 +1,158 @@
+use crate::types::{AppError, Framework};
+use tauri::Url;
+
+/// URL scheme registered with the OS, e.g. `dbmanager://create?type=postgres&port=5433`
+/// or `dbmanager://create?preset=django`
+pub const DEEP_LINK_SCHEME: &str = "dbmanager";
+
+/// Database creation requested by a `dbmanager://create` link
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreatePrefill {
+    // Provider id, e.g. "PostgreSQL"
+    pub db_type: String,
+    pub version: Option<String>,
+    pub port: Option<i32>,
+    pub name: Option<String>,
+    pub database_name: Option<String>,
+    pub username: Option<String>,
+    // Framework preset filling in the values the link leaves out
+    pub framework: Option<Framework>,
+    // Skip to the review step, creating still needs the user's confirmation
+    pub direct: bool,
+}
+
+/// Action behind a deep link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLinkAction {
+    Create(CreatePrefill),
+}
+
+impl DeepLinkAction {
+    pub fn parse(link: &str) -> Result<Self, AppError> {
+        let url = Url::parse(link)
+            .map_err(|e| AppError::Validation(format!("Invalid link '{}': {}", link, e)))?;
+
+        if url.scheme() != DEEP_LINK_SCHEME {
+            return Err(AppError::Validation(format!(
+                "Unsupported link scheme '{}'",
+                url.scheme()
+            )));
+        }
+
+        match url.host_str() {
+            Some("create") => CreatePrefill::from_url(&url).map(DeepLinkAction::Create),
+            other => Err(AppError::Validation(format!(
+                "Unknown link action '{}'",
+                other.unwrap_or_default()
+            ))),
+        }
+    }
+}
+
+impl CreatePrefill {
+    fn from_url(url: &Url) -> Result<Self, AppError> {
+        let mut prefill = CreatePrefill::default();
+
+        for (key, value) in url.query_pairs() {
+            let value = value.trim();
+            match key.as_ref() {
+                "type" => {
+                    prefill.db_type = Self::provider_id(value).ok_or_else(|| {
+                        AppError::Validation(format!("Unsupported database type '{}'", value))
+                    })?;
+                }
+                "version" if !value.is_empty() => prefill.version = Some(value.to_string()),
+                "port" => {
+                    let port = value
+                        .parse::<i32>()
+                        .ok()
+                        .filter(|port| (1..=65535).contains(port))
+                        .ok_or_else(|| AppError::Validation(format!("Invalid port '{}'", value)))?;
+                    prefill.port = Some(port);
+                }
+                "name" if !value.is_empty() => prefill.name = Some(value.to_string()),
+                "database" if !value.is_empty() => prefill.database_name = Some(value.to_string()),
+                "user" if !value.is_empty() => prefill.username = Some(value.to_string()),
+                "preset" => {
+                    prefill.framework = Some(Framework::parse(value).ok_or_else(|| {
+                        AppError::Validation(format!("Unknown framework preset '{}'", value))
+                    })?);
+                }
+                "direct" => prefill.direct = matches!(value, "1" | "true"),
+                _ => {}
+            }
+        }
+
+        // Values the link sets win over the preset's
+        if let Some(preset) = prefill.framework.map(|framework| framework.preset()) {
+            if prefill.db_type.is_empty() {
+                prefill.db_type = preset.db_type.to_string();
+            }
+            if prefill.db_type == preset.db_type {
+                prefill
+                    .version
+                    .get_or_insert_with(|| preset.version.to_string());
+            }
+            prefill
+                .database_name
+                .get_or_insert_with(|| preset.database_name.to_string());
+            prefill
+                .username
+                .get_or_insert_with(|| preset.username.to_string());
+        }
+
+        if prefill.db_type.is_empty() {
+            return Err(AppError::Validation(
+                "Create links need a database type or a preset".to_string(),
+            ));
+        }
+
+        Ok(prefill)
+    }
+
+    /// Provider id for the type names people write in links
+    pub fn provider_id(db_type: &str) -> Option<String> {
+        let id = match db_type.to_lowercase().as_str() {
+            "postgres" | "postgresql" | "pg" => "PostgreSQL",
+            "mysql" => "MySQL",
+            "mariadb" => "MariaDB",
+            "mongo" | "mongodb" => "MongoDB",
+            "redis" => "Redis",
+            "sqlserver" | "mssql" => "SQLServer",
+            "elasticsearch" | "elastic" => "Elasticsearch",
+            _ => return None,
+        };
+        Some(id.to_string())
+    }
+
+    /// Query string read by the creation window
+    pub fn window_query(&self) -> String {
+        let mut url = Url::parse("dbmanager://create").expect("static url");
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("type", &self.db_type);
+            if let Some(version) = &self.version {
+                query.append_pair("version", version);
+            }
+            if let Some(port) = self.port {
+                query.append_pair("port", &port.to_string());
+            }
+            if let Some(name) = &self.name {
+                query.append_pair("name", name);
+            }
+            if let Some(database_name) = &self.database_name {
+                query.append_pair("database", database_name);
+            }
+            if let Some(username) = &self.username {
+                query.append_pair("user", username);
+            }
+            if let Some(framework) = self.framework {
+                query.append_pair("preset", framework.id());
+            }
+            if self.direct {
+                query.append_pair("direct", "1");
+            }
+        }
+        url.query().unwrap_or_default().to_string()
+    }
+}
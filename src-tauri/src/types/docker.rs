@@ -1,11 +1,62 @@
+use crate::types::{Framework, LocaleSettings, RegionSettings, TuningSettings};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// Interface published ports listen on, unreachable from other machines
+pub const LOCAL_HOST_ADDRESS: &str = "127.0.0.1";
+// Every interface, used for containers exposed to the local network
+pub const LAN_HOST_ADDRESS: &str = "0.0.0.0";
+
+fn default_host_address() -> String {
+    LOCAL_HOST_ADDRESS.to_string()
+}
 
 /// Port mapping for Docker containers
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PortMapping {
     pub host: i32,
     pub container: i32,
+    // Host interface the port is published on, mappings saved by earlier versions are local
+    #[serde(rename = "hostAddress", default = "default_host_address")]
+    pub host_address: String,
+}
+
+impl PortMapping {
+    /// Mapping published on the loopback interface only
+    pub fn local(host: i32, container: i32) -> Self {
+        Self {
+            host,
+            container,
+            host_address: default_host_address(),
+        }
+    }
+
+    /// Value of `docker run -p`, IPv6 addresses in brackets
+    pub fn publish_arg(&self) -> String {
+        if self.host_address.contains(':') {
+            format!("[{}]:{}:{}", self.host_address, self.host, self.container)
+        } else {
+            format!("{}:{}:{}", self.host_address, self.host, self.container)
+        }
+    }
+
+    /// Address of a `docker inspect` port binding, Docker leaves it empty for all interfaces
+    pub fn inspected_address(binding: &serde_json::Value) -> String {
+        binding["HostIp"]
+            .as_str()
+            .filter(|address| !address.is_empty())
+            .unwrap_or(LAN_HOST_ADDRESS)
+            .to_string()
+    }
+
+    /// Whether other machines can reach the port
+    pub fn is_exposed(&self) -> bool {
+        !self
+            .host_address
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|address| address.is_loopback())
+    }
 }
 
 /// Volume mount configuration
@@ -24,10 +75,53 @@ pub struct DockerRunArgs {
     pub ports: Vec<PortMapping>,
     pub volumes: Vec<VolumeMount>,
     pub command: Vec<String>,
+    // `--shm-size` of the container, Docker's 64 MB when unset
+    #[serde(rename = "shmSize", default, skip_serializing_if = "Option::is_none")]
+    pub shm_size: Option<String>,
+}
+
+impl DockerRunArgs {
+    /// Publish every port on all interfaces when `expose_to_lan` is set, on loopback otherwise
+    pub fn bind_ports(&mut self, expose_to_lan: bool) {
+        let address = if expose_to_lan {
+            LAN_HOST_ADDRESS
+        } else {
+            LOCAL_HOST_ADDRESS
+        };
+        for port in &mut self.ports {
+            port.host_address = address.to_string();
+        }
+    }
+
+    /// Move `extra_args` to the end of the command, stored arguments already holding them keep one copy
+    pub fn apply_extra_args(&mut self, extra_args: &[String]) {
+        if extra_args.is_empty() {
+            return;
+        }
+        if let Some(start) = self
+            .command
+            .windows(extra_args.len())
+            .rposition(|window| window == extra_args)
+        {
+            self.command.drain(start..start + extra_args.len());
+        }
+        self.command.extend(extra_args.iter().cloned());
+    }
+}
+
+/// Arguments of `docker stop`, `timeout` replaces Docker's 10 seconds before the engine is killed
+pub fn docker_stop_args(container_id: &str, timeout: Option<u32>) -> Vec<String> {
+    let mut args = vec!["stop".to_string()];
+    if let Some(seconds) = timeout {
+        args.push("-t".to_string());
+        args.push(seconds.to_string());
+    }
+    args.push(container_id.to_string());
+    args
 }
 
 /// Container metadata (for storage and tracking)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ContainerMetadata {
     pub id: String,
     #[serde(rename = "dbType")]
@@ -44,6 +138,51 @@ pub struct ContainerMetadata {
     pub enable_auth: bool,
     #[serde(rename = "maxConnections")]
     pub max_connections: Option<i32>,
+    #[serde(rename = "enableTls", default)]
+    pub enable_tls: bool,
+    // Publish the ports on all interfaces instead of 127.0.0.1
+    #[serde(rename = "exposeToLan", default)]
+    pub expose_to_lan: bool,
+    // Statements run once the new database accepts connections, e.g. enabling an extension
+    #[serde(rename = "postCreateSql", default)]
+    pub post_create_sql: Vec<String>,
+    // Framework preset the database is created for
+    #[serde(default)]
+    pub framework: Option<Framework>,
+    // Encoding and collation of SQL engines, applied to the provider arguments
+    #[serde(default)]
+    pub locale: Option<LocaleSettings>,
+    // Server arguments the user appends to the provider's command, e.g. `-c shared_buffers=256MB`
+    #[serde(rename = "extraArgs", default)]
+    pub extra_args: Vec<String>,
+    // Timezone and system locale, the app default fills what is unset at creation
+    #[serde(default)]
+    pub region: Option<RegionSettings>,
+    // Memory settings sized from the host, applied to the provider arguments
+    #[serde(default)]
+    pub tuning: Option<TuningSettings>,
+    // Id of the Docker host to create it on, the local daemon when unset
+    #[serde(default)]
+    pub host: Option<String>,
+}
+
+impl ContainerMetadata {
+    /// Caution shown when a database is reachable from the local network
+    pub fn lan_exposure_warning(&self) -> Option<String> {
+        lan_exposure_warning(self.expose_to_lan, self.enable_auth)
+    }
+}
+
+/// Caution for a database published on all interfaces, None when it only listens locally
+pub fn lan_exposure_warning(exposed: bool, enable_auth: bool) -> Option<String> {
+    if !exposed {
+        return None;
+    }
+    Some(if enable_auth {
+        "Other machines on your network can reach this database, use a strong password".to_string()
+    } else {
+        "Anyone on your network can connect to this database without a password".to_string()
+    })
 }
 
 /// Complete Docker run request from frontend
@@ -54,3 +193,176 @@ pub struct DockerRunRequest {
     pub docker_args: DockerRunArgs,
     pub metadata: ContainerMetadata,
 }
+
+/// State of a container as reported by `docker ps` or `docker inspect`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DockerContainerState {
+    pub id: String,
+    pub running: bool,
+    pub restarting: bool,
+    // Set once the container has exited, kept while the restart policy brings it back
+    pub exit_code: Option<i32>,
+    // "healthy", "unhealthy" or "starting", only for images with a healthcheck
+    pub health: Option<String>,
+    // Published ports and restarts by the restart policy, only known from `docker inspect`
+    pub ports: Vec<PortMapping>,
+    pub restart_count: u32,
+    // Last start and exit, also only known from `docker inspect`
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    // The kernel killed the container for exceeding its memory limit
+    pub oom_killed: bool,
+}
+
+impl DockerContainerState {
+    /// Parse the `{{.Status}}` column, e.g. "Up 2 minutes (healthy)" or "Exited (137) 5 seconds ago"
+    pub fn from_ps_status(id: &str, status: &str) -> Self {
+        let exit_code = status
+            .strip_prefix("Exited (")
+            .and_then(|rest| rest.split(')').next())
+            .and_then(|code| code.trim().parse().ok());
+
+        let health = if status.contains("(healthy)") {
+            Some("healthy".to_string())
+        } else if status.contains("(unhealthy)") {
+            Some("unhealthy".to_string())
+        } else if status.contains("(health: starting)") {
+            Some("starting".to_string())
+        } else {
+            None
+        };
+
+        Self {
+            id: id.to_string(),
+            running: status.starts_with("Up"),
+            restarting: status.starts_with("Restarting"),
+            exit_code,
+            health,
+            ..Default::default()
+        }
+    }
+
+    /// Status of a database in this state
+    /// A healthcheck refines "running", a non-zero exit code tells a crash from a stop
+    pub fn status(&self) -> &'static str {
+        if self.restarting {
+            return "restarting";
+        }
+        if self.running {
+            return match self.health.as_deref() {
+                Some("starting") => "starting",
+                Some("unhealthy") => "unhealthy",
+                _ => "running",
+            };
+        }
+        match self.exit_code {
+            Some(code) if code != 0 => "exited",
+            _ => "stopped",
+        }
+    }
+
+    /// Name and state of one entry of `docker inspect` output
+    pub fn from_inspect(container: &serde_json::Value) -> Option<(String, Self)> {
+        let name = container["Name"]
+            .as_str()?
+            .trim_start_matches('/')
+            .to_string();
+        let state = &container["State"];
+
+        let exit_code = match state["Status"].as_str() {
+            Some("exited") | Some("dead") | Some("restarting") => {
+                state["ExitCode"].as_i64().map(|code| code as i32)
+            }
+            _ => None,
+        };
+        // Docker reports the zero time for a container that never started or exited
+        let time = |value: &serde_json::Value| {
+            value
+                .as_str()
+                .filter(|time| !time.starts_with("0001-"))
+                .map(str::to_string)
+        };
+
+        // {"5432/tcp": [{"HostIp": "0.0.0.0", "HostPort": "5432"}], "9000/tcp": null}
+        let mut ports: Vec<PortMapping> = container["NetworkSettings"]["Ports"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(port, bindings)| {
+                let container_port = port.split('/').next()?.parse().ok()?;
+                let binding = bindings
+                    .as_array()?
+                    .iter()
+                    .find(|binding| binding["HostPort"].as_str().is_some())?;
+                Some(PortMapping {
+                    host: binding["HostPort"].as_str()?.parse().ok()?,
+                    container: container_port,
+                    host_address: PortMapping::inspected_address(binding),
+                })
+            })
+            .collect();
+        ports.sort_by_key(|port| port.container);
+
+        Some((
+            name,
+            Self {
+                id: container["Id"].as_str()?.to_string(),
+                running: state["Running"].as_bool().unwrap_or(false),
+                restarting: state["Restarting"].as_bool().unwrap_or(false),
+                exit_code,
+                health: state["Health"]["Status"]
+                    .as_str()
+                    .filter(|status| *status != "none")
+                    .map(str::to_string),
+                ports,
+                restart_count: container["RestartCount"].as_u64().unwrap_or(0) as u32,
+                started_at: time(&state["StartedAt"]),
+                finished_at: time(&state["FinishedAt"]),
+                oom_killed: state["OOMKilled"].as_bool().unwrap_or(false),
+            },
+        ))
+    }
+
+    /// Parse `docker inspect` output into container states keyed by name
+    pub fn parse_inspect_output(output: &str) -> HashMap<String, Self> {
+        serde_json::from_str::<Vec<serde_json::Value>>(output.trim())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(Self::from_inspect)
+            .collect()
+    }
+}
+
+/// Results of read-only Docker queries kept for a short time, keyed by Docker host
+/// Rapid successive calls (window opens, list refreshes, status badges) share one process
+#[derive(Debug)]
+pub struct QueryCache<V> {
+    ttl: Duration,
+    entries: HashMap<String, (Instant, V)>,
+}
+
+impl<V: Clone> QueryCache<V> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Value for `host` when it was stored less than the TTL before `now`
+    pub fn get(&self, host: &str, now: Instant) -> Option<V> {
+        self.entries
+            .get(host)
+            .filter(|(stored_at, _)| now.saturating_duration_since(*stored_at) < self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn insert(&mut self, host: &str, value: V, now: Instant) {
+        self.entries.insert(host.to_string(), (now, value));
+    }
+
+    /// Drop every entry, called after anything that changes container state
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -0,0 +1,221 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+// Socket of a native daemon, used by the CLI when neither DOCKER_HOST nor a context is set
+pub const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// Docker-compatible runtime serving the daemon socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DockerRuntime {
+    DockerDesktop,
+    Colima,
+    OrbStack,
+    RancherDesktop,
+    DockerEngine,
+}
+
+impl DockerRuntime {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DockerRuntime::DockerDesktop => "Docker Desktop",
+            DockerRuntime::Colima => "Colima",
+            DockerRuntime::OrbStack => "OrbStack",
+            DockerRuntime::RancherDesktop => "Rancher Desktop",
+            DockerRuntime::DockerEngine => "Docker Engine",
+        }
+    }
+
+    /// What the user has to do to bring the daemon up
+    pub fn start_hint(&self) -> &'static str {
+        match self {
+            DockerRuntime::DockerDesktop => "Open Docker Desktop and wait for the engine to start.",
+            DockerRuntime::Colima => "Run `colima start` in a terminal.",
+            DockerRuntime::OrbStack => "Open OrbStack.",
+            DockerRuntime::RancherDesktop => {
+                "Open Rancher Desktop with the dockerd (moby) container engine."
+            }
+            DockerRuntime::DockerEngine => "Start the daemon with `sudo systemctl start docker`.",
+        }
+    }
+
+    /// Runtime assumed when nothing is installed at a known location
+    pub fn platform_default(os: &str) -> Self {
+        match os {
+            "linux" => DockerRuntime::DockerEngine,
+            _ => DockerRuntime::DockerDesktop,
+        }
+    }
+
+    /// Commands that bring the daemon up on `os`, tried in order until one succeeds
+    pub fn launch_commands(&self, os: &str) -> Vec<Vec<String>> {
+        let commands: Vec<Vec<&str>> = match (self, os) {
+            (DockerRuntime::Colima, _) => vec![vec!["colima", "start"]],
+            (DockerRuntime::DockerDesktop, "macos") => vec![vec!["open", "-a", "Docker"]],
+            (DockerRuntime::OrbStack, "macos") => vec![vec!["open", "-a", "OrbStack"]],
+            (DockerRuntime::RancherDesktop, "macos") => {
+                vec![vec!["open", "-a", "Rancher Desktop"]]
+            }
+            (DockerRuntime::DockerDesktop, "windows") => vec![vec![
+                "cmd",
+                "/C",
+                "start",
+                "",
+                "C:\\Program Files\\Docker\\Docker\\Docker Desktop.exe",
+            ]],
+            (DockerRuntime::RancherDesktop, "windows") => vec![vec![
+                "cmd",
+                "/C",
+                "start",
+                "",
+                "C:\\Program Files\\Rancher Desktop\\Rancher Desktop.exe",
+            ]],
+            (DockerRuntime::DockerDesktop, "linux") => {
+                vec![vec!["systemctl", "--user", "start", "docker-desktop"]]
+            }
+            (DockerRuntime::RancherDesktop, "linux") => vec![vec!["rancher-desktop"]],
+            // systemctl asks polkit for authorization, pkexec when no agent answers it
+            (DockerRuntime::DockerEngine, "linux") => vec![
+                vec!["systemctl", "start", "docker"],
+                vec!["pkexec", "systemctl", "start", "docker"],
+            ],
+            _ => Vec::new(),
+        };
+
+        commands
+            .into_iter()
+            .map(|command| command.into_iter().map(str::to_string).collect())
+            .collect()
+    }
+
+    /// Runtime behind a `docker context` name
+    pub fn from_context(context: &str) -> Option<Self> {
+        match context {
+            "desktop-linux" | "desktop-windows" => Some(DockerRuntime::DockerDesktop),
+            "orbstack" => Some(DockerRuntime::OrbStack),
+            "rancher-desktop" => Some(DockerRuntime::RancherDesktop),
+            name if name == "colima" || name.starts_with("colima-") => Some(DockerRuntime::Colima),
+            _ => None,
+        }
+    }
+
+    /// Runtime serving a socket path or `DOCKER_HOST` URL
+    pub fn from_socket(socket: &str) -> Option<Self> {
+        if socket.contains("/.colima/") {
+            Some(DockerRuntime::Colima)
+        } else if socket.contains("/.orbstack/") {
+            Some(DockerRuntime::OrbStack)
+        } else if socket.contains("/.rd/") {
+            Some(DockerRuntime::RancherDesktop)
+        } else if socket.contains("/.docker/run/")
+            || socket.contains("/.docker/desktop/")
+            || socket.contains("dockerDesktop")
+        {
+            Some(DockerRuntime::DockerDesktop)
+        } else if socket.ends_with(DEFAULT_DOCKER_SOCKET) || socket.ends_with("/run/docker.sock") {
+            Some(DockerRuntime::DockerEngine)
+        } else {
+            None
+        }
+    }
+}
+
+/// Sockets the known runtimes listen on, in order of preference
+pub fn runtime_sockets(home: &Path) -> Vec<(DockerRuntime, PathBuf)> {
+    vec![
+        (
+            DockerRuntime::DockerEngine,
+            PathBuf::from(DEFAULT_DOCKER_SOCKET),
+        ),
+        (
+            DockerRuntime::DockerDesktop,
+            home.join(".docker/run/docker.sock"),
+        ),
+        (
+            DockerRuntime::DockerDesktop,
+            home.join(".docker/desktop/docker.sock"),
+        ),
+        (
+            DockerRuntime::OrbStack,
+            home.join(".orbstack/run/docker.sock"),
+        ),
+        (
+            DockerRuntime::Colima,
+            home.join(".colima/default/docker.sock"),
+        ),
+        (DockerRuntime::Colima, home.join(".colima/docker.sock")),
+        (DockerRuntime::RancherDesktop, home.join(".rd/docker.sock")),
+    ]
+}
+
+/// Where the Docker CLI connects and which runtime serves it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerEnvironment {
+    pub runtime: Option<DockerRuntime>,
+    // `DOCKER_HOST` style URL, `None` when the CLI resolves it from the context
+    pub socket: Option<String>,
+    pub context: Option<String>,
+    // Runtimes with a socket on disk, they may be installed but stopped
+    pub installed: Vec<DockerRuntime>,
+}
+
+impl DockerEnvironment {
+    /// `docker_host` and `context` are the ones the CLI would use, `existing` the sockets found on disk
+    pub fn detect(
+        docker_host: Option<&str>,
+        context: Option<&str>,
+        existing: &[(DockerRuntime, PathBuf)],
+    ) -> Self {
+        let context = context
+            .filter(|context| !context.is_empty() && *context != "default")
+            .map(str::to_string);
+        let mut installed: Vec<DockerRuntime> = Vec::new();
+        for (runtime, _) in existing {
+            if !installed.contains(runtime) {
+                installed.push(*runtime);
+            }
+        }
+
+        let (runtime, socket) = if let Some(host) = docker_host.filter(|host| !host.is_empty()) {
+            (DockerRuntime::from_socket(host), Some(host.to_string()))
+        } else if let Some(context) = &context {
+            (DockerRuntime::from_context(context), None)
+        } else {
+            existing
+                .first()
+                .map(|(runtime, path)| (Some(*runtime), Some(format!("unix://{}", path.display()))))
+                .unwrap_or((None, None))
+        };
+
+        Self {
+            runtime,
+            socket,
+            context,
+            installed,
+        }
+    }
+
+    /// `DOCKER_HOST` the CLI needs when the detected socket is not the one it looks at by default
+    pub fn docker_host_override(&self) -> Option<String> {
+        if self.context.is_some() {
+            return None;
+        }
+
+        self.socket
+            .clone()
+            .filter(|socket| *socket != format!("unix://{}", DEFAULT_DOCKER_SOCKET))
+    }
+
+    /// Actionable message for when the daemon does not answer
+    pub fn guidance(&self) -> String {
+        match self.runtime.or_else(|| self.installed.first().copied()) {
+            Some(runtime) => format!(
+                "{} is not running. {}",
+                runtime.display_name(),
+                runtime.start_hint()
+            ),
+            None => "No Docker runtime was found. Install Docker Desktop, Colima, OrbStack or Rancher Desktop.".to_string(),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EnvDriftKind {
+    // Stored in the app, absent from the container
+    Missing,
+    // Set in both with different values
+    Changed,
+    // Set in the container, neither stored in the app nor defined by the image
+    Unexpected,
+}
+
+/// Environment variable of the live container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveEnvVar {
+    pub key: String,
+    // None for credentials
+    pub value: Option<String>,
+    // Defined by the image with this value, not by the app
+    pub from_image: bool,
+}
+
+/// Difference between the stored configuration and the live container for one variable
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvVarDrift {
+    pub key: String,
+    pub kind: EnvDriftKind,
+    // Values are left out for credentials
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub secret: bool,
+}
+
+/// Live environment of a container compared with what the app would run it with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvDriftReport {
+    pub variables: Vec<LiveEnvVar>,
+    pub drift: Vec<EnvVarDrift>,
+    pub in_sync: bool,
+}
+
+impl EnvDriftReport {
+    /// Compare the stored variables with the container's and its image's `KEY=VALUE` lists
+    /// `is_secret` tells which variables hold credentials, their values are never reported
+    pub fn compare(
+        expected: &HashMap<String, String>,
+        live: &[String],
+        image: &[String],
+        is_secret: impl Fn(&str) -> bool,
+    ) -> Self {
+        let live = parse_env(live);
+        let image = parse_env(image);
+        let shown = |key: &str, value: Option<&String>| value.filter(|_| !is_secret(key)).cloned();
+
+        let mut variables: Vec<LiveEnvVar> = live
+            .iter()
+            .map(|(key, value)| LiveEnvVar {
+                key: key.clone(),
+                value: shown(key, Some(value)),
+                from_image: !expected.contains_key(key) && image.get(key) == Some(value),
+            })
+            .collect();
+        variables.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let mut drift: Vec<EnvVarDrift> = expected
+            .iter()
+            .filter_map(|(key, value)| {
+                let kind = match live.get(key) {
+                    None => EnvDriftKind::Missing,
+                    Some(actual) if actual != value => EnvDriftKind::Changed,
+                    Some(_) => return None,
+                };
+                Some((key, kind))
+            })
+            .chain(
+                live.iter()
+                    .filter(|(key, value)| {
+                        !expected.contains_key(*key) && image.get(*key) != Some(value)
+                    })
+                    .map(|(key, _)| (key, EnvDriftKind::Unexpected)),
+            )
+            .map(|(key, kind)| EnvVarDrift {
+                key: key.clone(),
+                kind,
+                expected: shown(key, expected.get(key)),
+                actual: shown(key, live.get(key)),
+                secret: is_secret(key),
+            })
+            .collect();
+        drift.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Self {
+            variables,
+            in_sync: drift.is_empty(),
+            drift,
+        }
+    }
+}
+
+/// `KEY=VALUE` entries as `docker inspect` lists them, a later duplicate wins like in Docker
+fn parse_env(entries: &[String]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (key, value) = entry.split_once('=').unwrap_or((entry, ""));
+            (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
@@ -1,9 +1,388 @@
-use serde::{Deserialize, Serialize};
+use crate::types::{describe_violations, FieldViolation};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CreateContainerError {
-    pub error_type: String,
-    pub message: String,
-    pub port: Option<i32>,
-    pub details: Option<String>,
+/// Error returned by every command
+/// Serialized as `{ code, message, details, hint, port, suggestion, violations }` so the frontend
+/// can branch on `code` instead of parsing message strings
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AppError {
+    #[error("Port {port} is already in use")]
+    PortInUse { port: i32 },
+    #[error("A container with the name '{name}' already exists")]
+    NameInUse { name: String },
+    #[error("{0} not found")]
+    NotFound(String),
+    #[error("{0}")]
+    Validation(String),
+    #[error("{}", describe_violations(.violations))]
+    InvalidRequest { violations: Vec<FieldViolation> },
+    #[error("Invalid container name '{name}': it {reason}")]
+    InvalidName {
+        name: String,
+        reason: String,
+        suggestion: String,
+    },
+    #[error("Docker daemon is not running or Docker is not installed")]
+    DockerUnavailable,
+    #[error("The Docker host '{host}' cannot be reached")]
+    HostUnreachable { host: String },
+    #[error("Permission denied while talking to the Docker daemon")]
+    DockerPermissionDenied,
+    #[error("Image not found")]
+    ImageNotFound { details: Option<String> },
+    #[error("Docker ran out of disk space")]
+    DiskFull,
+    #[error("{message}")]
+    Docker {
+        message: String,
+        details: Option<String>,
+    },
+    #[error("{0}")]
+    Storage(String),
+    #[error("{0}")]
+    Io(String),
+    #[error("{0}")]
+    Secrets(String),
+    #[error("The data store is locked")]
+    StoreLocked,
+    #[error("Read-only mode is on")]
+    ReadOnly,
+    #[error("The SQL console is read-only, '{statement}' changes data")]
+    WriteBlocked { statement: String },
+    #[error("Updating '{name}' deletes its data: {reason}")]
+    DataLoss { name: String, reason: String },
+    #[error("'{name}' needs {setting} turned on: {reason}")]
+    SettingChange {
+        name: String,
+        setting: String,
+        reason: String,
+    },
+    #[error("{0}")]
+    Internal(String),
 }
+
+impl AppError {
+    /// Docker error without extra details
+    pub fn docker(message: impl Into<String>) -> Self {
+        AppError::Docker {
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Stable machine-readable code
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::PortInUse { .. } => "PORT_IN_USE",
+            AppError::NameInUse { .. } => "NAME_IN_USE",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Validation(_) => "VALIDATION_ERROR",
+            AppError::InvalidName { .. } => "INVALID_NAME",
+            AppError::InvalidRequest { .. } => "INVALID_REQUEST",
+            AppError::DockerUnavailable => "DOCKER_UNAVAILABLE",
+            AppError::HostUnreachable { .. } => "HOST_UNREACHABLE",
+            AppError::DockerPermissionDenied => "DOCKER_PERMISSION_DENIED",
+            AppError::ImageNotFound { .. } => "IMAGE_NOT_FOUND",
+            AppError::DiskFull => "DISK_FULL",
+            AppError::Docker { .. } => "DOCKER_ERROR",
+            AppError::Storage(_) => "STORAGE_ERROR",
+            AppError::Io(_) => "IO_ERROR",
+            AppError::Secrets(_) => "SECRETS_ERROR",
+            AppError::StoreLocked => "STORE_LOCKED",
+            AppError::ReadOnly => "READ_ONLY",
+            AppError::WriteBlocked { .. } => "WRITE_BLOCKED",
+            AppError::DataLoss { .. } => "DATA_LOSS",
+            AppError::SettingChange { .. } => "SETTING_CHANGE",
+            AppError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// Status code returned by the local HTTP API
+    pub fn http_status(&self) -> u16 {
+        match self {
+            AppError::PortInUse { .. } | AppError::NameInUse { .. } => 409,
+            AppError::NotFound(_) | AppError::ImageNotFound { .. } => 404,
+            AppError::Validation(_)
+            | AppError::InvalidName { .. }
+            | AppError::InvalidRequest { .. } => 400,
+            AppError::ReadOnly | AppError::WriteBlocked { .. } => 403,
+            AppError::DataLoss { .. } | AppError::SettingChange { .. } => 409,
+            AppError::DockerUnavailable
+            | AppError::HostUnreachable { .. }
+            | AppError::StoreLocked => 503,
+            _ => 500,
+        }
+    }
+
+    /// Suggested next step for the user, when there is one
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            AppError::PortInUse { .. } => {
+                Some("You can change the port in the configuration and try again.")
+            }
+            AppError::NameInUse { .. } => Some("Change the container name and try again."),
+            AppError::NotFound(_) => Some("Refresh the list, it may have been removed."),
+            AppError::InvalidName { .. } => Some("Use the suggested name or pick another one."),
+            AppError::InvalidRequest { .. } => Some("Fix the highlighted fields and try again."),
+            AppError::DockerUnavailable => Some("Start Docker and try again."),
+            AppError::HostUnreachable { .. } => {
+                Some("Check that the host is online and its Docker daemon is running.")
+            }
+            AppError::DockerPermissionDenied => {
+                Some("Add your user to the docker group or start Docker Desktop, then try again.")
+            }
+            AppError::ImageNotFound { .. } => {
+                Some("Check the image name and version, or log in to the registry.")
+            }
+            AppError::DiskFull => {
+                Some("Free up disk space, e.g. remove unused images and volumes.")
+            }
+            AppError::Storage(_) => Some("Check that the app data directory is writable."),
+            AppError::Secrets(_) => Some("Check that the system keychain is unlocked."),
+            AppError::StoreLocked => Some("Unlock the store with your passphrase."),
+            AppError::ReadOnly => Some("Turn off read-only mode in the settings to make changes."),
+            AppError::WriteBlocked { .. } => Some(
+                "Unlock writes for this database to run it, they stay allowed until the app quits.",
+            ),
+            AppError::DataLoss { .. } => {
+                Some("Confirm the update to go ahead, or turn on data persistence first.")
+            }
+            AppError::SettingChange { .. } => {
+                Some("Confirm to turn it on for this operation, the previous value is restored afterwards.")
+            }
+            _ => None,
+        }
+    }
+
+    /// Map the stderr of a failed `docker run` to a specific error
+    /// Port and name conflicts are reported with the values of the request
+    pub fn from_docker_run_error(error: &str, container_name: &str, port: i32) -> Self {
+        match DockerErrorKind::classify(error, None) {
+            DockerErrorKind::PortConflict => AppError::PortInUse { port },
+            DockerErrorKind::NameConflict => AppError::NameInUse {
+                name: container_name.to_string(),
+            },
+            DockerErrorKind::Other => AppError::Docker {
+                message: "Docker command failed".to_string(),
+                details: Some(error.trim().to_string()),
+            },
+            _ => Self::from_docker_output("Docker command failed", error, None),
+        }
+    }
+
+    /// Map a failed Docker CLI call whose caller knows the request, conflicts and unknown
+    /// errors keep the raw stderr so `for_run_request` can map them afterwards
+    pub fn from_docker_stderr(stderr: &str, exit_code: Option<i32>) -> Self {
+        match DockerErrorKind::classify(stderr, exit_code) {
+            DockerErrorKind::PortConflict
+            | DockerErrorKind::NameConflict
+            | DockerErrorKind::Other => AppError::docker(stderr.trim().to_string()),
+            _ => Self::from_docker_output("", stderr, exit_code),
+        }
+    }
+
+    /// Name the remote host in errors about a daemon that could not be reached
+    pub fn on_host(self, host: &str) -> Self {
+        match self {
+            AppError::DockerUnavailable => AppError::HostUnreachable {
+                host: host.to_string(),
+            },
+            other => other,
+        }
+    }
+
+    /// Say what was being done in an unclassified Docker error, typed errors are kept as they are
+    pub fn with_context(self, context: &str) -> Self {
+        match self {
+            AppError::Docker { message, details } => AppError::Docker {
+                message: format!("{}: {}", context, message),
+                details,
+            },
+            other => other,
+        }
+    }
+
+    /// Map port/name conflicts of a failed `docker run` to the values of the request
+    pub fn for_run_request(self, container_name: &str, port: i32) -> Self {
+        match self {
+            AppError::Docker { message, .. } => {
+                Self::from_docker_run_error(&message, container_name, port)
+            }
+            other => other,
+        }
+    }
+
+    /// Map a failed Docker CLI call to a specific error
+    /// `context` prefixes the stderr of errors that do not fall in a known category
+    pub fn from_docker_output(context: &str, stderr: &str, exit_code: Option<i32>) -> Self {
+        let stderr = stderr.trim();
+
+        match DockerErrorKind::classify(stderr, exit_code) {
+            DockerErrorKind::DaemonUnreachable => AppError::DockerUnavailable,
+            DockerErrorKind::PermissionDenied => AppError::DockerPermissionDenied,
+            DockerErrorKind::DiskFull => AppError::DiskFull,
+            DockerErrorKind::ImageNotFound => AppError::ImageNotFound {
+                details: Some(stderr.to_string()),
+            },
+            DockerErrorKind::PortConflict => match DockerErrorKind::conflicting_port(stderr) {
+                Some(port) => AppError::PortInUse { port },
+                None => AppError::docker(format!("{}: {}", context, stderr)),
+            },
+            DockerErrorKind::NameConflict => match DockerErrorKind::conflicting_name(stderr) {
+                Some(name) => AppError::NameInUse { name },
+                None => AppError::docker(format!("{}: {}", context, stderr)),
+            },
+            DockerErrorKind::Other => AppError::docker(format!("{}: {}", context, stderr)),
+        }
+    }
+}
+
+/// Category of a failed Docker CLI call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DockerErrorKind {
+    DaemonUnreachable,
+    PermissionDenied,
+    DiskFull,
+    PortConflict,
+    NameConflict,
+    ImageNotFound,
+    Other,
+}
+
+impl DockerErrorKind {
+    /// Classify from the stderr and exit code, `None` when the CLI could not be run at all
+    pub fn classify(stderr: &str, exit_code: Option<i32>) -> Self {
+        let error = stderr.to_lowercase();
+        let matches = |patterns: &[&str]| patterns.iter().any(|p| error.contains(p));
+
+        // 127: shell could not find the binary, os error 2: spawning it failed
+        if exit_code == Some(127)
+            || matches(&[
+                "cannot connect to the docker daemon",
+                "is the docker daemon running",
+                "error during connect",
+                "docker daemon is not running",
+                "program not found",
+            ])
+            || (exit_code.is_none() && error.contains("(os error 2)"))
+        {
+            return DockerErrorKind::DaemonUnreachable;
+        }
+
+        if exit_code == Some(126)
+            || matches(&[
+                "permission denied while trying to connect",
+                "got permission denied",
+                "access is denied",
+            ])
+        {
+            return DockerErrorKind::PermissionDenied;
+        }
+
+        if matches(&["no space left on device", "disk quota exceeded"]) {
+            return DockerErrorKind::DiskFull;
+        }
+
+        if matches(&[
+            "port is already allocated",
+            "address already in use",
+            "bind for",
+        ]) {
+            return DockerErrorKind::PortConflict;
+        }
+
+        if matches(&["the container name", "name is already in use"]) {
+            return DockerErrorKind::NameConflict;
+        }
+
+        if matches(&[
+            "pull access denied",
+            "repository does not exist",
+            "no such image",
+            "not found: manifest",
+        ]) {
+            return DockerErrorKind::ImageNotFound;
+        }
+
+        DockerErrorKind::Other
+    }
+
+    /// Host port of "Bind for 0.0.0.0:5432 failed" or "listen tcp 0.0.0.0:5432: bind: ..."
+    pub fn conflicting_port(stderr: &str) -> Option<i32> {
+        let address = if let Some(rest) = stderr.split("Bind for ").nth(1) {
+            rest.split(" failed").next()?
+        } else {
+            stderr.split(": bind:").next()?.rsplit(' ').next()?
+        };
+
+        address.rsplit(':').next()?.trim().parse().ok()
+    }
+
+    /// Name of `The container name "/my-db" is already in use`
+    pub fn conflicting_name(stderr: &str) -> Option<String> {
+        let rest = stderr.split("container name \"").nth(1)?;
+        let name = rest.split('"').next()?.trim_start_matches('/');
+        (!name.is_empty()).then(|| name.to_string())
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 7)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field(
+            "details",
+            &match self {
+                AppError::Docker { details, .. } | AppError::ImageNotFound { details } => {
+                    details.clone()
+                }
+                _ => None,
+            },
+        )?;
+        state.serialize_field("hint", &self.hint())?;
+        state.serialize_field(
+            "port",
+            &match self {
+                AppError::PortInUse { port } => Some(*port),
+                _ => None,
+            },
+        )?;
+        state.serialize_field(
+            "suggestion",
+            &match self {
+                AppError::InvalidName { suggestion, .. } => Some(suggestion),
+                _ => None,
+            },
+        )?;
+        state.serialize_field(
+            "violations",
+            &match self {
+                AppError::InvalidRequest { violations } => Some(violations),
+                _ => None,
+            },
+        )?;
+        state.end()
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        AppError::Io(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        AppError::Internal(error.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(error: tauri::Error) -> Self {
+        AppError::Internal(error.to_string())
+    }
+}
+
+pub type AppResult<T> = Result<T, AppError>;
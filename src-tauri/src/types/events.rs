@@ -0,0 +1,98 @@
+use crate::types::{
+    CopyDirection, DatabaseContainer, DockerRuntime, OrphanContainer, RecoveredCreate,
+};
+use serde::Serialize;
+
+/// Payload of `container-created` and `container-updated`
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerEventPayload {
+    pub container: DatabaseContainer,
+}
+
+/// Payload of `container-removed`
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerRemovedPayload {
+    pub id: String,
+}
+
+/// Payload of `container-status-changed`
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerStatusPayload {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+}
+
+/// Payload of `autostart-progress`, sent once per container started on launch
+#[derive(Debug, Clone, Serialize)]
+pub struct AutostartProgressPayload {
+    pub id: String,
+    pub name: String,
+    pub current: usize,
+    pub total: usize,
+    pub error: Option<String>,
+}
+
+/// Payload of `docker-start-progress`, sent while `start_docker` brings the daemon up
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerStartProgressPayload {
+    // "launching", "waiting", "ready" or "failed"
+    pub stage: String,
+    pub runtime: DockerRuntime,
+    pub attempt: u32,
+    pub message: Option<String>,
+}
+
+/// Payload of `orphans-detected`, sent when the containers missing from the store change
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphansDetectedPayload {
+    pub orphans: Vec<OrphanContainer>,
+}
+
+/// Payload of `recovery-completed`, sent at launch when interrupted creations were undone
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoveryCompletedPayload {
+    pub recovered: Vec<RecoveredCreate>,
+}
+
+/// Payload of `table-export-progress`, sent while a table is written to a file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableExportProgressPayload {
+    pub container_id: String,
+    pub table: String,
+    pub bytes_written: u64,
+    pub done: bool,
+}
+
+/// Payload of `query-export-progress`, sent while a result set is written to a file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryExportProgressPayload {
+    pub container_id: String,
+    pub rows_written: u64,
+    pub bytes_written: u64,
+    pub done: bool,
+}
+
+/// Payload of `file-copy-progress`, sent while files are copied into or out of a container
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileCopyProgressPayload {
+    pub container_id: String,
+    pub direction: CopyDirection,
+    pub source: String,
+    pub bytes_copied: u64,
+    // Unknown when the source is in a stopped container
+    pub total_bytes: Option<u64>,
+    pub done: bool,
+}
+
+/// Payload of `background-error`, sent when work running outside a command fails
+#[derive(Debug, Clone, Serialize)]
+pub struct BackgroundErrorPayload {
+    // What was running, such as "graceful-stop" or "scheduler"
+    pub source: String,
+    pub message: String,
+}
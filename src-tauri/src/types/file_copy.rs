@@ -0,0 +1,48 @@
+use crate::types::AppError;
+use serde::Serialize;
+
+// How often the size of the destination is measured while `docker cp` runs
+pub const FILE_COPY_PROGRESS_INTERVAL_MS: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CopyDirection {
+    ToContainer,
+    FromContainer,
+}
+
+/// Finished `docker cp` between the host and a database container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileCopyResult {
+    pub direction: CopyDirection,
+    pub source: String,
+    pub destination: String,
+    pub bytes_copied: u64,
+}
+
+/// Path inside a container given to `docker cp`, relative paths would depend on the image
+pub fn container_copy_path(path: &str) -> Result<String, AppError> {
+    let path = path.trim();
+    if !path.starts_with('/') {
+        return Err(AppError::Validation(format!(
+            "Use an absolute path inside the container, e.g. /tmp/{}",
+            path.trim_start_matches("./")
+        )));
+    }
+    if path.contains('\0') {
+        return Err(AppError::Validation(
+            "The container path contains a NUL character".to_string(),
+        ));
+    }
+    Ok(path.to_string())
+}
+
+/// Bytes copied so far given the destination size before and now, at most `total`
+pub fn copy_progress(baseline: u64, current: u64, total: Option<u64>) -> u64 {
+    let copied = current.saturating_sub(baseline);
+    match total {
+        Some(total) => copied.min(total),
+        None => copied,
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Named set of containers started and stopped together
+/// Membership is the `project` of each container, `order` is the start order between
+/// members that do not depend on each other
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerGroup {
+    pub name: String,
+    // Database ids, started first to last and stopped in reverse
+    #[serde(default)]
+    pub order: Vec<String>,
+    pub created_at: String,
+    // Network the members share when the group was created as a stack
+    #[serde(default)]
+    pub network: Option<String>,
+    // Members each member waits for, by database id, e.g. a cache on its database
+    #[serde(default)]
+    pub dependencies: HashMap<String, Vec<String>>,
+}
@@ -0,0 +1,110 @@
+use crate::types::DatabaseContainer;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// Host of databases without one, the daemon the Docker CLI talks to by default
+pub const LOCAL_HOST_ID: &str = "local";
+pub const LOCAL_HOST_NAME: &str = "This machine";
+
+// `DOCKER_HOST` schemes the Docker CLI connects through
+const HOST_URL_SCHEMES: [&str; 4] = ["ssh://", "tcp://", "unix://", "npipe://"];
+
+/// Docker daemon on another machine, its databases are listed with the local ones
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerHost {
+    pub id: String,
+    pub name: String,
+    // `DOCKER_HOST` URL, e.g. "ssh://deploy@build-server" or "tcp://10.0.0.5:2376"
+    pub url: String,
+}
+
+impl DockerHost {
+    /// Problems with the values, checked before the host is saved
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if self.id.trim().is_empty() || self.id == LOCAL_HOST_ID {
+            violations.push(format!(
+                "'{}' cannot be used as the id of a Docker host",
+                self.id
+            ));
+        }
+        if self.name.trim().is_empty() {
+            violations.push("The Docker host needs a name".to_string());
+        }
+
+        let valid_url = HOST_URL_SCHEMES
+            .iter()
+            .find_map(|scheme| self.url.strip_prefix(scheme))
+            .is_some_and(|address| !address.is_empty() && !address.contains(char::is_whitespace));
+        if !valid_url {
+            violations.push(format!(
+                "'{}' is not a Docker host URL like ssh://user@server or tcp://server:2376",
+                self.url
+            ));
+        }
+
+        violations
+    }
+
+    /// Machine clients reach published ports on, None for a daemon behind a local socket
+    pub fn address(&self) -> Option<String> {
+        let address = ["ssh://", "tcp://"]
+            .iter()
+            .find_map(|scheme| self.url.strip_prefix(scheme))?;
+        let address = address.split('/').next().unwrap_or_default();
+        let address = address.rsplit_once('@').map_or(address, |(_, host)| host);
+        // An IPv6 address keeps its brackets, URLs need them
+        let host = match address.rfind(']') {
+            Some(end) => &address[..=end],
+            None => address.split(':').next().unwrap_or_default(),
+        };
+        (!host.is_empty()).then(|| host.to_string())
+    }
+}
+
+/// Host entry of the dashboard, the local daemon first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerHostStatus {
+    pub id: String,
+    pub name: String,
+    // None for the local daemon
+    pub url: Option<String>,
+    // False when the last sync could not reach it
+    pub reachable: bool,
+    pub database_count: usize,
+}
+
+impl DockerHostStatus {
+    /// Every host with the databases it runs, `unreachable` holds the ids the last sync missed
+    pub fn list(
+        hosts: &[DockerHost],
+        databases: &HashMap<String, DatabaseContainer>,
+        unreachable: &HashSet<String>,
+    ) -> Vec<Self> {
+        let count = |host_id: &str| {
+            databases
+                .values()
+                .filter(|db| db.host_id() == host_id)
+                .count()
+        };
+
+        std::iter::once(Self {
+            id: LOCAL_HOST_ID.to_string(),
+            name: LOCAL_HOST_NAME.to_string(),
+            url: None,
+            reachable: true,
+            database_count: count(LOCAL_HOST_ID),
+        })
+        .chain(hosts.iter().map(|host| Self {
+            id: host.id.clone(),
+            name: host.name.clone(),
+            url: Some(host.url.clone()),
+            reachable: !unreachable.contains(&host.id),
+            database_count: count(&host.id),
+        }))
+        .collect()
+    }
+}
@@ -0,0 +1,68 @@
+use crate::types::split_image_reference;
+use serde::{Deserialize, Serialize};
+
+/// Image of a removed database, a cleanup candidate while no managed container uses it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleasedImage {
+    pub image: String,
+    pub released_at: String,
+}
+
+/// Local image a cleanup removes, or removed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedImage {
+    pub image: String,
+    pub image_id: String,
+    pub size_bytes: u64,
+    pub released_at: String,
+}
+
+/// Outcome of `cleanup_images`, a dry run only lists what would be removed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageCleanupReport {
+    pub dry_run: bool,
+    pub images: Vec<UnusedImage>,
+    pub total_bytes: u64,
+    // Images Docker refused to remove, with its reason
+    pub errors: Vec<String>,
+}
+
+/// Comparable form of an image reference, `postgres` and `docker.io/library/postgres:latest` match
+pub fn image_key(image: &str) -> String {
+    let (repository, tag) = split_image_reference(image.trim());
+    let repository = repository
+        .trim_start_matches("docker.io/")
+        .trim_start_matches("library/");
+    format!("{}:{}", repository, tag)
+}
+
+/// Add the images of removed databases to `released`, each image is listed once
+pub fn release_images(released: &mut Vec<ReleasedImage>, images: &[String], released_at: &str) {
+    for image in images {
+        let key = image_key(image);
+        match released
+            .iter_mut()
+            .find(|entry| image_key(&entry.image) == key)
+        {
+            // Released again, the newest removal counts
+            Some(entry) => entry.released_at = released_at.to_string(),
+            None => released.push(ReleasedImage {
+                image: image.clone(),
+                released_at: released_at.to_string(),
+            }),
+        }
+    }
+}
+
+/// Released images none of the `in_use` images match
+pub fn cleanup_candidates(released: &[ReleasedImage], in_use: &[String]) -> Vec<ReleasedImage> {
+    let in_use: Vec<String> = in_use.iter().map(|image| image_key(image)).collect();
+    released
+        .iter()
+        .filter(|entry| !in_use.contains(&image_key(&entry.image)))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,102 @@
+use serde::Serialize;
+
+// Newer major tags probed past the current one, each probe is a registry round trip
+pub const MAX_NEWER_MAJOR_PROBES: u32 = 3;
+
+/// Result of comparing a database's image with its registry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageUpdateCheck {
+    pub database_id: String,
+    pub image: String,
+    // Digest the container runs and the one the tag points to in the registry
+    pub local_digest: Option<String>,
+    pub remote_digest: Option<String>,
+    // Highest newer major version tag found, e.g. "17" for a database on "16"
+    pub newer_version: Option<String>,
+    pub updates_available: bool,
+    // Registry or Docker failure, the other fields are left empty
+    pub error: Option<String>,
+}
+
+impl ImageUpdateCheck {
+    /// Check result from the digests and the newer tag found
+    pub fn new(
+        database_id: &str,
+        image: &str,
+        local_digest: Option<String>,
+        remote_digest: Option<String>,
+        newer_version: Option<String>,
+    ) -> Self {
+        // An unknown local digest, e.g. a locally built image, is never reported as outdated
+        let digest_changed = matches!(
+            (&local_digest, &remote_digest),
+            (Some(local), Some(remote)) if local != remote
+        );
+
+        Self {
+            database_id: database_id.to_string(),
+            image: image.to_string(),
+            updates_available: digest_changed || newer_version.is_some(),
+            local_digest,
+            remote_digest,
+            newer_version,
+            error: None,
+        }
+    }
+
+    pub fn failed(database_id: &str, image: &str, error: String) -> Self {
+        Self {
+            database_id: database_id.to_string(),
+            image: image.to_string(),
+            error: Some(error),
+            ..Default::default()
+        }
+    }
+}
+
+/// Split an image reference into repository and tag, a registry port is not a tag
+pub fn split_image_reference(image: &str) -> (&str, &str) {
+    let image = image.split('@').next().unwrap_or(image);
+    let name_start = image.rfind('/').map_or(0, |slash| slash + 1);
+
+    match image[name_start..].rfind(':') {
+        Some(colon) => (
+            &image[..name_start + colon],
+            &image[name_start + colon + 1..],
+        ),
+        None => (image, "latest"),
+    }
+}
+
+/// Digest of `repository` among the `RepoDigests` of a local image
+pub fn repo_digest(repo_digests: &[String], repository: &str) -> Option<String> {
+    // Docker Hub images are listed without the implicit "docker.io/library/" prefix
+    let short = repository
+        .trim_start_matches("docker.io/")
+        .trim_start_matches("library/");
+
+    repo_digests.iter().find_map(|entry| {
+        let (name, digest) = entry.split_once('@')?;
+        (name == repository || name == short).then(|| digest.to_string())
+    })
+}
+
+/// Major version tags after `tag` with the same variant, e.g. "17-alpine" after "16.2-alpine"
+/// `None` when the tag does not start with a number
+pub fn newer_major_tags(tag: &str) -> Option<Vec<String>> {
+    let (version, variant) = match tag.split_once('-') {
+        Some((version, variant)) => (version, Some(variant)),
+        None => (tag, None),
+    };
+    let major: u32 = version.split('.').next()?.parse().ok()?;
+
+    Some(
+        (1..=MAX_NEWER_MAJOR_PROBES)
+            .map(|step| match variant {
+                Some(variant) => format!("{}-{}", major + step, variant),
+                None => (major + step).to_string(),
+            })
+            .collect(),
+    )
+}
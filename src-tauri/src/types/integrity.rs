@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+// Check runs kept, oldest are dropped first
+pub const MAX_INTEGRITY_HISTORY: usize = 200;
+// Tail of the checker's output kept with a run
+pub const INTEGRITY_OUTPUT_LIMIT: usize = 4096;
+// Problems listed per run, the output keeps the rest
+pub const MAX_INTEGRITY_PROBLEMS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntegrityStatus {
+    // The checker found nothing wrong
+    Passed,
+    // The checker reported corruption
+    Failed,
+    // The checker could not run to the end, e.g. it is missing from the image
+    Error,
+}
+
+/// One integrity check of a database, manual or from a schedule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityCheckRun {
+    pub id: String,
+    pub database_id: String,
+    pub database_name: String,
+    // Checker that ran, e.g. "pg_amcheck"
+    pub tool: String,
+    pub status: IntegrityStatus,
+    // Lines of the output describing what is wrong
+    #[serde(default)]
+    pub problems: Vec<String>,
+    // Last `INTEGRITY_OUTPUT_LIMIT` bytes of the output
+    #[serde(default)]
+    pub output: String,
+    pub started_at: String,
+    pub finished_at: String,
+    // Schedule that started it, None for manual runs
+    #[serde(default)]
+    pub schedule_id: Option<String>,
+}
+
+/// Checker of each engine, run inside the database container
+pub fn integrity_tool(db_type: &str) -> Option<&'static str> {
+    match db_type {
+        "PostgreSQL" => Some("pg_amcheck"),
+        "MySQL" => Some("mysqlcheck"),
+        "MariaDB" => Some("mariadb-check"),
+        "Redis" => Some("redis-check-rdb"),
+        "MongoDB" => Some("validate"),
+        _ => None,
+    }
+}
+
+/// Outcome of a checker given its exit code and combined output
+pub fn evaluate_integrity(
+    db_type: &str,
+    exit_code: Option<i32>,
+    output: &str,
+) -> (IntegrityStatus, Vec<String>) {
+    let lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+
+    let problems: Vec<String> = match db_type {
+        // `heap table "db.public.t", block 0, offset 1:` followed by the details
+        "PostgreSQL" => lines
+            .filter(|line| line.starts_with("heap table ") || line.starts_with("btree index "))
+            .map(str::to_owned)
+            .collect(),
+        // `error    : Table is marked as crashed` below the table name
+        "MySQL" | "MariaDB" => {
+            let mut problems = Vec::new();
+            let mut table = "";
+            for line in lines {
+                if line.to_lowercase().starts_with("error") {
+                    problems.push(format!("{}: {}", table, error_text(line)));
+                } else if !line.contains(':') || line.ends_with("OK") {
+                    table = line.split_whitespace().next().unwrap_or_default();
+                }
+            }
+            problems
+        }
+        // `--- RDB ERROR DETECTED ---` follows the reason, e.g. `[additional info] ...`
+        "Redis" if output.contains("RDB ERROR DETECTED") => lines
+            .filter(|line| line.contains("[additional info]") || line.contains("ERROR"))
+            .map(str::to_owned)
+            .collect(),
+        // `{"ns":"app.users","valid":false,"errors":["..."]}` per collection
+        "MongoDB" => lines
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|result| result["valid"] == false)
+            .map(|result| {
+                let errors: Vec<String> = result["errors"]
+                    .as_array()
+                    .map(|errors| {
+                        errors
+                            .iter()
+                            .filter_map(|error| error.as_str().map(str::to_owned))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                format!(
+                    "{}: {}",
+                    result["ns"].as_str().unwrap_or_default(),
+                    if errors.is_empty() {
+                        "invalid".to_string()
+                    } else {
+                        errors.join("; ")
+                    }
+                )
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let status = if !problems.is_empty() {
+        IntegrityStatus::Failed
+    } else if exit_code == Some(0) {
+        IntegrityStatus::Passed
+    } else {
+        IntegrityStatus::Error
+    };
+    (
+        status,
+        problems.into_iter().take(MAX_INTEGRITY_PROBLEMS).collect(),
+    )
+}
+
+/// Text after the `error :` label of a mysqlcheck line
+fn error_text(line: &str) -> &str {
+    line.split_once(':')
+        .map(|(_, text)| text.trim())
+        .unwrap_or(line)
+}
+
+/// Last `INTEGRITY_OUTPUT_LIMIT` bytes of `output`, cut at a character boundary
+pub fn integrity_output_tail(output: &str) -> String {
+    let mut start = output.len().saturating_sub(INTEGRITY_OUTPUT_LIMIT);
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    output[start..].to_string()
+}
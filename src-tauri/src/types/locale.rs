@@ -0,0 +1,384 @@
+use crate::types::DockerRunArgs;
+use serde::{Deserialize, Serialize};
+
+// Encodings a PostgreSQL server can store, client-only encodings like SJIS are left out
+const POSTGRES_ENCODINGS: &[&str] = &[
+    "EUC_CN",
+    "EUC_JP",
+    "EUC_JIS_2004",
+    "EUC_KR",
+    "EUC_TW",
+    "ISO_8859_5",
+    "ISO_8859_6",
+    "ISO_8859_7",
+    "ISO_8859_8",
+    "KOI8R",
+    "KOI8U",
+    "LATIN1",
+    "LATIN2",
+    "LATIN3",
+    "LATIN4",
+    "LATIN5",
+    "LATIN6",
+    "LATIN7",
+    "LATIN8",
+    "LATIN9",
+    "LATIN10",
+    "MULE_INTERNAL",
+    "SQL_ASCII",
+    "UTF8",
+    "WIN866",
+    "WIN874",
+    "WIN1250",
+    "WIN1251",
+    "WIN1252",
+    "WIN1253",
+    "WIN1254",
+    "WIN1255",
+    "WIN1256",
+    "WIN1257",
+    "WIN1258",
+];
+
+// Locales the official images generate, Alpine images only have the first four
+const POSTGRES_IMAGE_LOCALES: [&str; 6] = [
+    "c",
+    "posix",
+    "c.utf8",
+    "c.utf-8",
+    "en_us.utf8",
+    "en_us.utf-8",
+];
+
+// Character sets MySQL and MariaDB ship, utf8 is the older name of utf8mb3
+const MYSQL_CHARACTER_SETS: &[&str] = &[
+    "armscii8", "ascii", "big5", "binary", "cp1250", "cp1251", "cp1256", "cp1257", "cp850",
+    "cp852", "cp866", "cp932", "dec8", "eucjpms", "euckr", "gb18030", "gb2312", "gbk", "geostd8",
+    "greek", "hebrew", "hp8", "keybcs2", "koi8r", "koi8u", "latin1", "latin2", "latin5", "latin7",
+    "macce", "macroman", "sjis", "swe7", "tis620", "ucs2", "ujis", "utf16", "utf16le", "utf32",
+    "utf8", "utf8mb3", "utf8mb4",
+];
+
+/// Encoding and collation a SQL database is created with
+/// Set once at creation, initdb and existing schemas keep them afterwards
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleSettings {
+    // PostgreSQL encoding, e.g. "UTF8", or MySQL character set, e.g. "utf8mb4"
+    #[serde(default)]
+    pub encoding: Option<String>,
+    // PostgreSQL locale, e.g. "de_DE.utf8" or "de-DE"
+    #[serde(default)]
+    pub locale: Option<String>,
+    // MySQL collation, e.g. "utf8mb4_0900_ai_ci"
+    #[serde(default)]
+    pub collation: Option<String>,
+}
+
+impl LocaleSettings {
+    pub fn is_empty(&self) -> bool {
+        self.encoding().is_none() && self.locale().is_none() && self.collation().is_none()
+    }
+
+    pub fn encoding(&self) -> Option<&str> {
+        non_empty(&self.encoding)
+    }
+
+    pub fn locale(&self) -> Option<&str> {
+        non_empty(&self.locale)
+    }
+
+    pub fn collation(&self) -> Option<&str> {
+        non_empty(&self.collation)
+    }
+
+    /// Why the image of `db_type` at `version` cannot create a database with these settings
+    pub fn violations(&self, db_type: &str, version: &str) -> Vec<String> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        match db_type {
+            "PostgreSQL" => self.postgres_violations(version),
+            "MySQL" | "MariaDB" => self.mysql_violations(db_type, version),
+            _ => vec![format!(
+                "{} does not take locale settings, only PostgreSQL, MySQL and MariaDB do",
+                db_type
+            )],
+        }
+    }
+
+    /// Add the settings to the provider arguments, replacing any the provider set
+    pub fn apply(&self, db_type: &str, version: &str, docker_args: &mut DockerRunArgs) {
+        if self.is_empty() {
+            return;
+        }
+
+        match db_type {
+            "PostgreSQL" => {
+                let mut flags: Vec<String> = docker_args
+                    .env_vars
+                    .get("POSTGRES_INITDB_ARGS")
+                    .map(|args| {
+                        args.split_whitespace()
+                            .filter(|flag| {
+                                ![
+                                    "--encoding=",
+                                    "--locale=",
+                                    "--locale-provider=",
+                                    "--icu-locale=",
+                                ]
+                                .iter()
+                                .any(|prefix| flag.starts_with(prefix))
+                            })
+                            .map(str::to_owned)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                if let Some(encoding) = self.encoding() {
+                    flags.push(format!("--encoding={}", encoding));
+                }
+                if let Some(locale) = self.locale() {
+                    if postgres_image_locale(locale, version) {
+                        flags.push(format!("--locale={}", locale));
+                    } else {
+                        flags.push("--locale-provider=icu".to_string());
+                        flags.push(format!("--icu-locale={}", icu_language_tag(locale)));
+                    }
+                }
+
+                docker_args
+                    .env_vars
+                    .insert("POSTGRES_INITDB_ARGS".to_string(), flags.join(" "));
+            }
+            "MySQL" | "MariaDB" => {
+                docker_args.command.retain(|arg| {
+                    !arg.starts_with("--character-set-server=")
+                        && !arg.starts_with("--collation-server=")
+                });
+                if let Some(character_set) = self.mysql_character_set() {
+                    docker_args
+                        .command
+                        .push(format!("--character-set-server={}", character_set));
+                }
+                if let Some(collation) = self.collation() {
+                    docker_args
+                        .command
+                        .push(format!("--collation-server={}", collation.to_lowercase()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn postgres_violations(&self, version: &str) -> Vec<String> {
+        let mut violations = Vec::new();
+        let encoding = self.encoding().map(postgres_encoding_key);
+
+        if let Some(raw) = self.encoding() {
+            if !POSTGRES_ENCODINGS
+                .iter()
+                .any(|known| postgres_encoding_key(known) == postgres_encoding_key(raw))
+            {
+                violations.push(format!("PostgreSQL has no server encoding named '{}'", raw));
+            }
+        }
+        if self.collation().is_some() {
+            violations.push(
+                "PostgreSQL takes a locale instead of a collation, e.g. de_DE.utf8".to_string(),
+            );
+        }
+
+        let Some(locale) = self.locale() else {
+            return violations;
+        };
+        if postgres_image_locale(locale, version) {
+            // UTF-8 locales only sort text stored as UTF8
+            let utf8_locale =
+                locale.to_lowercase().ends_with("utf8") || locale.to_lowercase().ends_with("utf-8");
+            if utf8_locale && encoding.as_deref().is_some_and(|key| key != "UTF8") {
+                violations.push(format!("Locale {} needs the UTF8 encoding", locale));
+            }
+            return violations;
+        }
+
+        // Any other locale comes from ICU, which initdb can use from PostgreSQL 15
+        if !is_locale_name(locale) {
+            violations.push(format!(
+                "'{}' is not a locale name, use a name like de_DE.utf8 or de-DE",
+                locale
+            ));
+        } else if version_number(version).is_some_and(|number| number < (15, 0)) {
+            violations.push(format!(
+                "The PostgreSQL {} image only includes the C{} locales, {} needs PostgreSQL 15 or later",
+                version,
+                if is_alpine(version) { "" } else { " and en_US.utf8" },
+                locale
+            ));
+        } else if encoding.as_deref() == Some("SQL_ASCII") {
+            violations.push(format!(
+                "Locale {} cannot be used with the SQL_ASCII encoding",
+                locale
+            ));
+        }
+
+        violations
+    }
+
+    fn mysql_violations(&self, db_type: &str, version: &str) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if self.locale().is_some() {
+            violations.push(format!(
+                "{} takes a character set and a collation instead of a locale",
+                db_type
+            ));
+        }
+
+        let character_set = self.mysql_character_set();
+        if let Some(character_set) = &character_set {
+            if !MYSQL_CHARACTER_SETS.contains(&character_set.as_str()) {
+                violations.push(format!(
+                    "{} has no character set named '{}'",
+                    db_type, character_set
+                ));
+            }
+        }
+
+        let Some(collation) = self.collation().map(str::to_lowercase) else {
+            return violations;
+        };
+        // A collation belongs to exactly one character set, named by its prefix
+        if let Some(character_set) = self.encoding().map(str::to_lowercase) {
+            let prefix = collation_character_set(&collation);
+            let same =
+                prefix == character_set || (is_utf8mb3(prefix) && is_utf8mb3(&character_set));
+            if !same {
+                violations.push(format!(
+                    "Collation {} belongs to {}, not to {}",
+                    collation, prefix, character_set
+                ));
+            }
+        }
+
+        let number = version_number(version);
+        if collation.contains("_0900_") {
+            // MariaDB maps the MySQL 8 collations onto its own from 11.4
+            let supported = match db_type {
+                "MySQL" => number.is_none_or(|number| number >= (8, 0)),
+                _ => number.is_none_or(|number| number >= (11, 4)),
+            };
+            if !supported {
+                violations.push(format!(
+                    "Collation {} needs {} or later",
+                    collation,
+                    if db_type == "MySQL" {
+                        "MySQL 8.0"
+                    } else {
+                        "MariaDB 11.4"
+                    }
+                ));
+            }
+        }
+        if collation.contains("_uca1400_")
+            && (db_type != "MariaDB" || number.is_some_and(|number| number < (10, 10)))
+        {
+            violations.push(format!(
+                "Collation {} needs MariaDB 10.10 or later",
+                collation
+            ));
+        }
+
+        violations
+    }
+
+    /// Character set the server starts with, taken from the collation when only that is set
+    fn mysql_character_set(&self) -> Option<String> {
+        self.encoding().map(str::to_lowercase).or_else(|| {
+            self.collation()
+                .map(|collation| collation_character_set(&collation.to_lowercase()).to_string())
+        })
+    }
+}
+
+pub(crate) fn non_empty(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// PostgreSQL accepts encoding names in any case and with or without separators
+fn postgres_encoding_key(encoding: &str) -> String {
+    let key: String = encoding
+        .to_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    match key.as_str() {
+        "UTF8" | "UNICODE" => "UTF8".to_string(),
+        "SQLASCII" => "SQL_ASCII".to_string(),
+        _ => key,
+    }
+}
+
+/// Whether the image generates the locale, everything else has to come from ICU
+pub(crate) fn postgres_image_locale(locale: &str, version: &str) -> bool {
+    let locale = locale.to_lowercase();
+    let available = if is_alpine(version) {
+        &POSTGRES_IMAGE_LOCALES[..4]
+    } else {
+        &POSTGRES_IMAGE_LOCALES[..]
+    };
+    available.contains(&locale.as_str())
+}
+
+/// `language[_-]REGION[.codeset][@modifier]`
+fn is_locale_name(locale: &str) -> bool {
+    let name = locale.split(['.', '@']).next().unwrap_or_default();
+    let mut parts = name.split(['_', '-']);
+    let language_ok = parts.next().is_some_and(|language| {
+        (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_alphabetic())
+    });
+    language_ok
+        && parts.all(|part| {
+            (2..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// ICU wants a BCP 47 tag, e.g. `de-DE` for `de_DE.utf8`
+fn icu_language_tag(locale: &str) -> String {
+    locale
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-")
+}
+
+fn collation_character_set(collation: &str) -> &str {
+    if collation == "binary" {
+        return "binary";
+    }
+    collation.split('_').next().unwrap_or_default()
+}
+
+fn is_utf8mb3(character_set: &str) -> bool {
+    character_set == "utf8" || character_set == "utf8mb3"
+}
+
+fn is_alpine(version: &str) -> bool {
+    version.contains("alpine")
+}
+
+/// Major and minor number of an image tag, None for tags like `latest` that follow the newest
+fn version_number(version: &str) -> Option<(u32, u32)> {
+    let number = version.split('-').next()?;
+    let mut parts = number.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts
+        .next()
+        .and_then(|minor| minor.parse().ok())
+        .unwrap_or(0);
+    Some((major, minor))
+}
@@ -0,0 +1,270 @@
+use crate::types::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Lines returned by a log page when the caller does not ask for a size
+pub const DEFAULT_LOG_PAGE_SIZE: usize = 500;
+/// Upper bound of a log page, keeps a single response small
+pub const MAX_LOG_PAGE_SIZE: usize = 5000;
+
+/// Range of container logs to read, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQuery {
+    // RFC 3339 timestamp or relative duration understood by `docker logs`, e.g. "10m"
+    pub since: Option<String>,
+    pub until: Option<String>,
+    // `nextCursor` of the previous page, takes over from `since`
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// One page of container logs, every line starts with its timestamp
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogPage {
+    pub lines: Vec<String>,
+    // Pass back to continue after the last line, also to poll for new lines
+    pub next_cursor: Option<String>,
+    // More lines were available when the page filled up
+    pub has_more: bool,
+}
+
+/// Severity of a log entry, in the terms of syslog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LogSeverity {
+    Debug,
+    Info,
+    // Normal but significant, e.g. PostgreSQL NOTICE or MySQL [System]
+    Notice,
+    Warning,
+    Error,
+    // The server or the session stopped, e.g. PostgreSQL FATAL and PANIC
+    Fatal,
+}
+
+/// How the logs of a container are shown, kept so its logs window reopens the same way
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogViewPreferences {
+    // Lines loaded when the window opens
+    pub tail_lines: usize,
+    pub wrap: bool,
+    // Keep scrolling to new lines
+    pub follow: bool,
+    // Entries below this severity are hidden, all are shown when unset
+    #[serde(default)]
+    pub min_severity: Option<LogSeverity>,
+}
+
+impl Default for LogViewPreferences {
+    fn default() -> Self {
+        Self {
+            tail_lines: DEFAULT_LOG_PAGE_SIZE,
+            wrap: true,
+            follow: true,
+            min_severity: None,
+        }
+    }
+}
+
+impl LogViewPreferences {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !(1..=MAX_LOG_PAGE_SIZE).contains(&self.tail_lines) {
+            return Err(AppError::Validation(format!(
+                "The log tail must be between 1 and {} lines",
+                MAX_LOG_PAGE_SIZE
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Log line split into the fields its engine writes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    // RFC 3339 from `docker logs --timestamps`
+    pub timestamp: Option<String>,
+    pub severity: LogSeverity,
+    // Subsystem that logged it, e.g. the MongoDB component or the MySQL subsystem
+    pub source: Option<String>,
+    // Continuation lines are appended after a newline
+    pub message: String,
+}
+
+/// Page of logs parsed into entries, with the number of entries of each severity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedLogPage {
+    pub entries: Vec<LogEntry>,
+    pub counts: BTreeMap<LogSeverity, usize>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl ParsedLogPage {
+    pub fn new(page: LogPage, entries: Vec<LogEntry>) -> Self {
+        let mut counts = BTreeMap::new();
+        for entry in &entries {
+            *counts.entry(entry.severity).or_insert(0) += 1;
+        }
+
+        Self {
+            entries,
+            counts,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+        }
+    }
+}
+
+/// Position after the last returned line
+/// Several lines can share a timestamp, `skip` counts the ones already returned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogCursor {
+    pub timestamp: String,
+    pub skip: usize,
+}
+
+impl LogCursor {
+    pub fn parse(cursor: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::Validation(format!("Invalid log cursor '{}'", cursor));
+
+        let (timestamp, skip) = cursor.rsplit_once('#').ok_or_else(invalid)?;
+        if log_line_timestamp(timestamp).is_none() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            timestamp: timestamp.to_string(),
+            skip: skip.parse().map_err(|_| invalid())?,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}#{}", self.timestamp, self.skip)
+    }
+}
+
+/// Timestamp `docker logs --timestamps` puts in front of a line
+pub fn log_line_timestamp(line: &str) -> Option<&str> {
+    let timestamp = line.split_whitespace().next()?;
+    chrono::DateTime::parse_from_rfc3339(timestamp).ok()?;
+    Some(timestamp)
+}
+
+/// Collects the lines of one page while `docker logs` streams them
+/// The container's stdout and stderr arrive on separate pipes, so lines are ordered by their
+/// timestamp before the page is cut
+#[derive(Debug)]
+pub struct LogPager {
+    cursor: Option<LogCursor>,
+    limit: usize,
+    lines: Vec<(chrono::DateTime<chrono::FixedOffset>, String)>,
+    // Lines without a timestamp come from the Docker CLI, not the container
+    errors: Vec<String>,
+}
+
+impl LogPager {
+    pub fn new(query: &LogQuery) -> Result<Self, AppError> {
+        Ok(Self {
+            cursor: query.cursor.as_deref().map(LogCursor::parse).transpose()?,
+            limit: query
+                .limit
+                .unwrap_or(DEFAULT_LOG_PAGE_SIZE)
+                .clamp(1, MAX_LOG_PAGE_SIZE),
+            lines: Vec::new(),
+            errors: Vec::new(),
+        })
+    }
+
+    /// Value for `docker logs --since`, the cursor wins over the query
+    pub fn since<'a>(&'a self, query: &'a LogQuery) -> Option<&'a str> {
+        match &self.cursor {
+            Some(cursor) => Some(&cursor.timestamp),
+            None => query.since.as_deref(),
+        }
+    }
+
+    /// Add a streamed line, in the order it arrived on either pipe
+    pub fn push(&mut self, line: &str) {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let Some(time) = log_line_timestamp(line)
+            .and_then(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).ok())
+        else {
+            if !line.trim().is_empty() {
+                self.errors.push(line.to_string());
+            }
+            return;
+        };
+
+        self.lines.push((time, line.to_string()));
+
+        // Only the earliest lines can end up on the page, later ones are dropped as they pile up
+        let kept = self.limit + 1 + self.cursor.as_ref().map_or(0, |cursor| cursor.skip);
+        if self.lines.len() > kept * 2 {
+            self.sort_lines();
+            self.lines.truncate(kept);
+        }
+    }
+
+    /// Oldest first, lines of one pipe keep their order
+    fn sort_lines(&mut self) {
+        self.lines.sort_by_key(|(time, _)| *time);
+    }
+
+    /// Output of the Docker CLI itself, e.g. an unknown container
+    pub fn errors(&self) -> String {
+        self.errors.join("\n")
+    }
+
+    pub fn finish(mut self, previous_cursor: Option<&str>) -> LogPage {
+        self.sort_lines();
+
+        // `--since` is inclusive, lines of the cursor's timestamp were partly returned already
+        let mut lines: Vec<String> = self.lines.into_iter().map(|(_, line)| line).collect();
+        if let Some(cursor) = &self.cursor {
+            let repeated = lines
+                .iter()
+                .take(cursor.skip)
+                .take_while(|line| log_line_timestamp(line) == Some(cursor.timestamp.as_str()))
+                .count();
+            lines.drain(..repeated);
+        }
+
+        let has_more = lines.len() > self.limit;
+        lines.truncate(self.limit);
+
+        let next_cursor = match lines.last().and_then(|line| log_line_timestamp(line)) {
+            Some(last) => {
+                let mut skip = lines
+                    .iter()
+                    .rev()
+                    .take_while(|line| log_line_timestamp(line) == Some(last))
+                    .count();
+                // Lines of the same timestamp skipped from the previous page still count
+                if let Some(previous) = previous_cursor.and_then(|c| LogCursor::parse(c).ok()) {
+                    if previous.timestamp == last && skip == lines.len() {
+                        skip += previous.skip;
+                    }
+                }
+                Some(
+                    LogCursor {
+                        timestamp: last.to_string(),
+                        skip,
+                    }
+                    .encode(),
+                )
+            }
+            None => previous_cursor.map(str::to_string),
+        };
+
+        LogPage {
+            lines,
+            next_cursor,
+            has_more,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// MCP revisions the server speaks, newest first
+/// Both use the streamable HTTP transport and the tool calls the server answers
+pub const MCP_PROTOCOL_VERSIONS: [&str; 2] = ["2025-06-18", "2025-03-26"];
+
+/// Revision answered to `initialize`, the client's when supported, the newest otherwise
+/// A client that cannot use the answered revision disconnects
+pub fn negotiate_protocol_version(requested: Option<&str>) -> &'static str {
+    MCP_PROTOCOL_VERSIONS
+        .into_iter()
+        .find(|version| Some(*version) == requested)
+        .unwrap_or(MCP_PROTOCOL_VERSIONS[0])
+}
+
+/// JSON-RPC error codes used by the MCP server
+pub const JSONRPC_METHOD_NOT_FOUND: i32 = -32601;
+pub const JSONRPC_INVALID_PARAMS: i32 = -32602;
+
+/// JSON-RPC message received from an MCP client
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonRpcRequest {
+    // Absent on notifications, which get no response
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl JsonRpcRequest {
+    pub fn result(&self, result: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": self.id, "result": result })
+    }
+
+    pub fn error(&self, code: i32, message: impl Into<String>) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": self.id,
+            "error": { "code": code, "message": message.into() },
+        })
+    }
+}
+
+/// Tool exposed to AI assistants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpTool {
+    ListDatabases,
+    CreateDatabase,
+    GetConnectionString,
+    GetLogs,
+}
+
+impl McpTool {
+    pub const ALL: [McpTool; 4] = [
+        McpTool::ListDatabases,
+        McpTool::CreateDatabase,
+        McpTool::GetConnectionString,
+        McpTool::GetLogs,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            McpTool::ListDatabases => "list_databases",
+            McpTool::CreateDatabase => "create_database",
+            McpTool::GetConnectionString => "get_connection_string",
+            McpTool::GetLogs => "get_logs",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tool| tool.name() == name)
+    }
+
+    /// Tools that reveal credentials or database output ask the user first
+    pub fn needs_approval(&self) -> bool {
+        matches!(self, McpTool::GetConnectionString | McpTool::GetLogs)
+    }
+
+    /// Entry of the `tools/list` response
+    pub fn definition(&self) -> Value {
+        let (description, input_schema) = match self {
+            McpTool::ListDatabases => (
+                "List the local databases managed by Docker DB Manager with their type, version, port and status",
+                json!({ "type": "object", "properties": {} }),
+            ),
+            McpTool::CreateDatabase => (
+                "Open the database creation window prefilled with these values, the user reviews and confirms it",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "type": { "type": "string", "description": "postgres, mysql, mariadb, mongodb, redis, sqlserver or elasticsearch" },
+                        "name": { "type": "string" },
+                        "version": { "type": "string" },
+                        "port": { "type": "integer" },
+                    },
+                    "required": ["type"],
+                }),
+            ),
+            McpTool::GetConnectionString => (
+                "Connection string of a database, including its password, after the user approves",
+                json!({
+                    "type": "object",
+                    "properties": { "database": { "type": "string", "description": "Database name or id" } },
+                    "required": ["database"],
+                }),
+            ),
+            McpTool::GetLogs => (
+                "Latest log lines of a database container, after the user approves",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "database": { "type": "string", "description": "Database name or id" },
+                        "lines": { "type": "integer", "description": "Number of lines, 100 by default" },
+                    },
+                    "required": ["database"],
+                }),
+            ),
+        };
+
+        json!({
+            "name": self.name(),
+            "description": description,
+            "inputSchema": input_schema,
+        })
+    }
+}
+
+/// Result of a `tools/call`, errors are reported to the model rather than as protocol errors
+pub fn mcp_tool_result(text: impl Into<String>, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text.into() }],
+        "isError": is_error,
+    })
+}
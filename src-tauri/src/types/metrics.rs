@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// Seconds between two `docker stats` samples while a metrics window is open
+pub const METRICS_INTERVAL_SECS: u64 = 2;
+
+/// Resource usage of one container, as sampled by `docker stats`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerMetrics {
+    // Database id in the app
+    pub id: String,
+    pub name: String,
+    pub cpu_percent: f64,
+    pub memory_used_bytes: u64,
+    pub memory_limit_bytes: u64,
+    pub memory_percent: f64,
+    pub net_rx_bytes: u64,
+    pub net_tx_bytes: u64,
+    pub block_read_bytes: u64,
+    pub block_write_bytes: u64,
+    pub pids: u32,
+}
+
+/// One line of `docker stats --format "{{json .}}"`
+#[derive(Debug, Clone, Deserialize)]
+pub struct DockerStatsLine {
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "CPUPerc")]
+    pub cpu_perc: String,
+    #[serde(rename = "MemUsage")]
+    pub mem_usage: String,
+    #[serde(rename = "MemPerc")]
+    pub mem_perc: String,
+    #[serde(rename = "NetIO")]
+    pub net_io: String,
+    #[serde(rename = "BlockIO")]
+    pub block_io: String,
+    #[serde(rename = "PIDs", default)]
+    pub pids: String,
+}
+
+impl DockerStatsLine {
+    /// Metrics with sizes in bytes, unparseable fields read as zero
+    pub fn to_metrics(&self, id: &str) -> ContainerMetrics {
+        let (memory_used_bytes, memory_limit_bytes) = parse_size_pair(&self.mem_usage);
+        let (net_rx_bytes, net_tx_bytes) = parse_size_pair(&self.net_io);
+        let (block_read_bytes, block_write_bytes) = parse_size_pair(&self.block_io);
+
+        ContainerMetrics {
+            id: id.to_string(),
+            name: self.name.clone(),
+            cpu_percent: parse_percent(&self.cpu_perc),
+            memory_used_bytes,
+            memory_limit_bytes,
+            memory_percent: parse_percent(&self.mem_perc),
+            net_rx_bytes,
+            net_tx_bytes,
+            block_read_bytes,
+            block_write_bytes,
+            pids: self.pids.trim().parse().unwrap_or(0),
+        }
+    }
+}
+
+/// Payload of `container-metrics`, one sample of every watched container
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerMetricsPayload {
+    pub metrics: Vec<ContainerMetrics>,
+    pub timestamp: String,
+}
+
+fn parse_percent(value: &str) -> f64 {
+    value.trim().trim_end_matches('%').parse().unwrap_or(0.0)
+}
+
+/// Parse "8.5MiB / 7.6GiB" style pairs
+fn parse_size_pair(value: &str) -> (u64, u64) {
+    let mut parts = value.split('/').map(parse_size);
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+/// Parse a Docker size, decimal ("kB", "MB") or binary ("KiB", "MiB") units
+pub fn parse_size(value: &str) -> u64 {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let Ok(number) = number.parse::<f64>() else {
+        return 0;
+    };
+
+    let multiplier: f64 = match unit.trim() {
+        "" | "B" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return 0,
+    };
+
+    (number * multiplier).round() as u64
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+// Samples are kept as taken for a day, older ones are merged into hourly points
+pub const RAW_METRICS_RETENTION_HOURS: i64 = 24;
+pub const COMPACTED_METRICS_BUCKET_SECS: i64 = 3600;
+
+fn default_samples() -> u32 {
+    1
+}
+
+/// Resource usage of a container at one point in time, or averaged over a bucket
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsPoint {
+    // RFC 3339, the start of the bucket for averaged points
+    pub timestamp: String,
+    pub cpu_percent: f64,
+    pub memory_used_bytes: u64,
+    // Client connections, None when the engine could not be asked
+    #[serde(default)]
+    pub connections: Option<u64>,
+    // Size of the data directory
+    #[serde(default)]
+    pub disk_bytes: Option<u64>,
+    // Samples averaged into this point
+    #[serde(default = "default_samples")]
+    pub samples: u32,
+}
+
+impl MetricsPoint {
+    fn time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|time| time.with_timezone(&Utc))
+    }
+
+    /// Mean of `points` weighted by their sample counts, at `timestamp`
+    pub fn average(points: &[MetricsPoint], timestamp: DateTime<Utc>) -> Self {
+        let samples: u32 = points.iter().map(|point| point.samples.max(1)).sum();
+        let weighted = |value: fn(&MetricsPoint) -> Option<f64>| -> Option<f64> {
+            let (total, weight) = points
+                .iter()
+                .filter_map(|point| Some((value(point)?, f64::from(point.samples.max(1)))))
+                .fold((0.0, 0.0), |(total, weight), (value, samples)| {
+                    (total + value * samples, weight + samples)
+                });
+            (weight > 0.0).then(|| total / weight)
+        };
+
+        Self {
+            timestamp: timestamp.to_rfc3339(),
+            cpu_percent: weighted(|point| Some(point.cpu_percent)).unwrap_or_default(),
+            memory_used_bytes: weighted(|point| Some(point.memory_used_bytes as f64))
+                .unwrap_or_default()
+                .round() as u64,
+            connections: weighted(|point| point.connections.map(|value| value as f64))
+                .map(|value| value.round() as u64),
+            disk_bytes: weighted(|point| point.disk_bytes.map(|value| value as f64))
+                .map(|value| value.round() as u64),
+            samples,
+        }
+    }
+}
+
+/// `points` averaged per `bucket_secs`, oldest first, points with a bad timestamp are dropped
+pub fn downsample(points: &[MetricsPoint], bucket_secs: i64) -> Vec<MetricsPoint> {
+    let bucket_secs = bucket_secs.max(1);
+    let mut buckets: BTreeMap<i64, Vec<MetricsPoint>> = BTreeMap::new();
+    for point in points {
+        if let Some(time) = point.time() {
+            let start = time.timestamp().div_euclid(bucket_secs) * bucket_secs;
+            buckets.entry(start).or_default().push(point.clone());
+        }
+    }
+
+    buckets
+        .into_iter()
+        .filter_map(|(start, points)| {
+            Some(MetricsPoint::average(
+                &points,
+                DateTime::from_timestamp(start, 0)?,
+            ))
+        })
+        .collect()
+}
+
+/// Sampled metrics of every container, kept in their own store file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetricsHistory {
+    // Points per database id, oldest first
+    pub containers: HashMap<String, Vec<MetricsPoint>>,
+}
+
+impl MetricsHistory {
+    pub fn record(&mut self, id: &str, point: MetricsPoint) {
+        self.containers
+            .entry(id.to_string())
+            .or_default()
+            .push(point);
+    }
+
+    /// Add the points of `other`, which are newer than the ones recorded here
+    pub fn merge(&mut self, other: MetricsHistory) {
+        for (id, points) in other.containers {
+            self.containers.entry(id).or_default().extend(points);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty()
+    }
+
+    /// Merge points older than `RAW_METRICS_RETENTION_HOURS` into hourly points and drop the
+    /// ones older than `retention_days`, running it again changes nothing
+    pub fn compact(&mut self, now: DateTime<Utc>, retention_days: u32) {
+        let raw_cutoff = now - chrono::Duration::hours(RAW_METRICS_RETENTION_HOURS);
+        let cutoff = now - chrono::Duration::days(i64::from(retention_days));
+
+        for points in self.containers.values_mut() {
+            let (old, recent): (Vec<MetricsPoint>, Vec<MetricsPoint>) = points
+                .drain(..)
+                .filter(|point| point.time().is_some_and(|time| time >= cutoff))
+                .partition(|point| point.time().is_some_and(|time| time < raw_cutoff));
+
+            *points = downsample(&old, COMPACTED_METRICS_BUCKET_SECS);
+            points.extend(recent);
+            points.sort_by(|a, b| a.time().cmp(&b.time()));
+        }
+
+        self.containers.retain(|_, points| !points.is_empty());
+    }
+
+    /// Points of a container between `since` and `until`, averaged per `bucket_secs` when set
+    pub fn query(
+        &self,
+        id: &str,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+        bucket_secs: Option<i64>,
+    ) -> Vec<MetricsPoint> {
+        let points: Vec<MetricsPoint> = self
+            .containers
+            .get(id)
+            .map(|points| {
+                points
+                    .iter()
+                    .filter(|point| {
+                        point.time().is_some_and(|time| {
+                            since.is_none_or(|since| time >= since)
+                                && until.is_none_or(|until| time <= until)
+                        })
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        match bucket_secs {
+            Some(bucket_secs) => downsample(&points, bucket_secs),
+            None => points,
+        }
+    }
+}
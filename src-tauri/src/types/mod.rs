@@ -1,7 +1,127 @@
+pub mod alert;
+pub mod api;
+pub mod benchmark;
+pub mod bulk;
+pub mod bundle;
+pub mod companion;
+pub mod config;
+pub mod connection;
+pub mod container_name;
+pub mod credentials;
+pub mod csv;
+pub mod data_dir;
 pub mod database;
+pub mod deep_link;
 pub mod docker;
+pub mod docker_runtime;
+pub mod env_drift;
 pub mod errors;
+pub mod events;
+pub mod exec_history;
+pub mod file_copy;
+pub mod group;
+pub mod host;
+pub mod image_cleanup;
+pub mod image_update;
+pub mod integrity;
+pub mod locale;
+pub mod logs;
+pub mod mcp;
+pub mod metrics;
+pub mod metrics_history;
+pub mod notification;
+pub mod orphan;
+pub mod preset;
+pub mod preview;
+pub mod query;
+pub mod query_export;
+pub mod recovery;
+pub mod region;
+pub mod replication;
+pub mod schedule;
+pub mod schema;
+pub mod settings;
+pub mod share;
+pub mod slow_log;
+pub mod snapshot;
+pub mod snippet;
+pub mod sql;
+pub mod sql_guard;
+pub mod sql_job;
+pub mod stack;
+pub mod startup;
+pub mod storage_breakdown;
+pub mod trash;
+pub mod tray;
+pub mod tuning;
+pub mod tunnel;
+pub mod update;
+pub mod uptime;
+pub mod validation;
+pub mod watchdog;
+pub mod window;
+pub mod workspace;
 
+pub use alert::*;
+pub use api::*;
+pub use benchmark::*;
+pub use bulk::*;
+pub use bundle::*;
+pub use companion::*;
+pub use config::*;
+pub use connection::*;
+pub use container_name::*;
+pub use credentials::*;
+pub use csv::*;
+pub use data_dir::*;
 pub use database::*;
+pub use deep_link::*;
 pub use docker::*;
+pub use docker_runtime::*;
+pub use env_drift::*;
 pub use errors::*;
+pub use events::*;
+pub use exec_history::*;
+pub use file_copy::*;
+pub use group::*;
+pub use host::*;
+pub use image_cleanup::*;
+pub use image_update::*;
+pub use integrity::*;
+pub use locale::*;
+pub use logs::*;
+pub use mcp::*;
+pub use metrics::*;
+pub use metrics_history::*;
+pub use notification::*;
+pub use orphan::*;
+pub use preset::*;
+pub use preview::*;
+pub use query::*;
+pub use query_export::*;
+pub use recovery::*;
+pub use region::*;
+pub use replication::*;
+pub use schedule::*;
+pub use schema::*;
+pub use settings::*;
+pub use share::*;
+pub use slow_log::*;
+pub use snapshot::*;
+pub use snippet::*;
+pub use sql::*;
+pub use sql_guard::*;
+pub use sql_job::*;
+pub use stack::*;
+pub use startup::*;
+pub use storage_breakdown::*;
+pub use trash::*;
+pub use tray::*;
+pub use tuning::*;
+pub use tunnel::*;
+pub use update::*;
+pub use uptime::*;
+pub use validation::*;
+pub use watchdog::*;
+pub use window::*;
+pub use workspace::*;
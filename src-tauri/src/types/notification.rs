@@ -0,0 +1,33 @@
+/// Health problem worth a desktop notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerAlert {
+    // Stopped on its own with a non-zero exit code
+    Died { exit_code: i32 },
+    Unhealthy,
+    RestartLoop,
+}
+
+impl ContainerAlert {
+    pub fn title(&self, name: &str) -> String {
+        match self {
+            ContainerAlert::Died { .. } => format!("{} stopped unexpectedly", name),
+            ContainerAlert::Unhealthy => format!("{} is unhealthy", name),
+            ContainerAlert::RestartLoop => format!("{} keeps restarting", name),
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            ContainerAlert::Died { exit_code } => {
+                format!(
+                    "The container exited with code {}. Check its logs.",
+                    exit_code
+                )
+            }
+            ContainerAlert::Unhealthy => "Its healthcheck is failing.".to_string(),
+            ContainerAlert::RestartLoop => {
+                "Docker restarted it several times in the last minutes.".to_string()
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::types::{
+    split_image_reference, DatabaseContainer, DockerRunArgs, PortMapping, VolumeMount,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+// Labels put on every database container, a container carrying the id label is ours
+pub const DATABASE_ID_LABEL: &str = "com.abians.docker-db-manager.id";
+pub const DB_TYPE_LABEL: &str = "com.abians.docker-db-manager.db-type";
+pub const WORKSPACE_LABEL: &str = "com.abians.docker-db-manager.workspace";
+
+/// What the labels of a database container record about it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagementLabels {
+    pub database_id: String,
+    pub db_type: String,
+    pub workspace: String,
+}
+
+impl ManagementLabels {
+    /// `--label` arguments of `docker run`
+    pub fn to_args(&self) -> Vec<String> {
+        [
+            (DATABASE_ID_LABEL, &self.database_id),
+            (DB_TYPE_LABEL, &self.db_type),
+            (WORKSPACE_LABEL, &self.workspace),
+        ]
+        .into_iter()
+        .flat_map(|(key, value)| ["--label".to_string(), format!("{}={}", key, value)])
+        .collect()
+    }
+
+    /// Go template columns printing each label as a JSON string after a tab, for `docker ps`
+    /// The `{{.Labels}}` column joins labels with commas, which values may contain
+    pub fn ps_format_columns() -> String {
+        [DATABASE_ID_LABEL, DB_TYPE_LABEL, WORKSPACE_LABEL]
+            .into_iter()
+            .map(|key| format!("\t{{{{json (.Label \"{}\")}}}}", key))
+            .collect()
+    }
+
+    /// Values printed by `ps_format_columns`, in their order
+    /// `None` when the container is not one of ours
+    pub fn from_columns(columns: &[&str]) -> Option<Self> {
+        let value = |index: usize| {
+            columns
+                .get(index)
+                .and_then(|column| serde_json::from_str::<String>(column).ok())
+                .unwrap_or_default()
+        };
+
+        let database_id = value(0);
+        if database_id.is_empty() {
+            return None;
+        }
+        Some(Self {
+            database_id,
+            db_type: value(1),
+            workspace: value(2),
+        })
+    }
+}
+
+/// Container labelled as a managed database that no store entry knows about
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanContainer {
+    pub container_id: String,
+    pub name: String,
+    pub image: String,
+    pub running: bool,
+    pub labels: ManagementLabels,
+}
+
+impl OrphanContainer {
+    /// `docker ps --format` value printing a container as JSON followed by our labels
+    pub fn ps_format() -> String {
+        format!("{{{{json .}}}}{}", ManagementLabels::ps_format_columns())
+    }
+
+    /// One line of `ps_format` output, `None` for containers without our labels
+    pub fn from_ps_line(line: &str) -> Option<Self> {
+        let columns: Vec<&str> = line.trim().split('\t').collect();
+        let entry: Value = serde_json::from_str(columns.first()?).ok()?;
+
+        Some(Self {
+            container_id: entry["ID"].as_str()?.to_string(),
+            name: entry["Names"].as_str()?.to_string(),
+            image: entry["Image"].as_str().unwrap_or_default().to_string(),
+            running: entry["State"].as_str() == Some("running"),
+            labels: ManagementLabels::from_columns(&columns[1..])?,
+        })
+    }
+
+    /// Whether no store entry covers this container
+    /// Containers of another known workspace belong to that workspace's store and are skipped
+    pub fn is_orphan(
+        &self,
+        known_names: &[String],
+        active_workspace: &str,
+        known_workspaces: &[String],
+    ) -> bool {
+        let other_workspace = self.labels.workspace != active_workspace
+            && known_workspaces.contains(&self.labels.workspace);
+
+        !known_names.contains(&self.name) && !other_workspace
+    }
+}
+
+/// What to do with an orphan container
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrphanResolution {
+    // Add it to the store as it is
+    Adopt,
+    // Remove the container, its volumes are kept
+    Remove,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanAction {
+    // Container name
+    pub name: String,
+    pub resolution: OrphanResolution,
+}
+
+/// Store entry rebuilt from `docker inspect` of an orphan and of its image
+/// Values the image sets itself, like its environment and default command, are left out
+pub fn adopted_database(
+    orphan: &OrphanContainer,
+    container: &Value,
+    image: &Value,
+    database_id: String,
+) -> DatabaseContainer {
+    let strings = |value: &Value| -> Vec<String> {
+        value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item.as_str().map(str::to_owned))
+            .collect()
+    };
+
+    let image_env = strings(&image["Config"]["Env"]);
+    let env_vars: HashMap<String, String> = strings(&container["Config"]["Env"])
+        .into_iter()
+        .filter(|entry| !image_env.contains(entry))
+        .filter_map(|entry| {
+            let (key, value) = entry.split_once('=')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect();
+
+    let cmd = strings(&container["Config"]["Cmd"]);
+    let command = if cmd == strings(&image["Config"]["Cmd"]) {
+        Vec::new()
+    } else {
+        cmd
+    };
+
+    // {"5432/tcp": [{"HostIp": "", "HostPort": "5432"}]}, also known for stopped containers
+    let mut ports: Vec<PortMapping> = container["HostConfig"]["PortBindings"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(port, bindings)| {
+            Some(PortMapping {
+                host: bindings[0]["HostPort"].as_str()?.parse().ok()?,
+                container: port.split('/').next()?.parse().ok()?,
+                host_address: PortMapping::inspected_address(&bindings[0]),
+            })
+        })
+        .collect();
+    ports.sort_by_key(|port| port.container);
+
+    let volumes: Vec<VolumeMount> = container["Mounts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|mount| mount["Type"].as_str() == Some("volume"))
+        .filter_map(|mount| {
+            Some(VolumeMount {
+                name: mount["Name"].as_str()?.to_string(),
+                path: mount["Destination"].as_str()?.to_string(),
+            })
+        })
+        .collect();
+
+    let env = |keys: &[&str]| keys.iter().find_map(|key| env_vars.get(*key).cloned());
+    let db_type = orphan.labels.db_type.as_str();
+    let (username, password, database_name) = match db_type {
+        "PostgreSQL" => (
+            env(&["POSTGRES_USER"]),
+            env(&["POSTGRES_PASSWORD"]),
+            env(&["POSTGRES_DB"]),
+        ),
+        "MySQL" => (
+            None,
+            env(&["MYSQL_ROOT_PASSWORD"]),
+            env(&["MYSQL_DATABASE"]),
+        ),
+        "MariaDB" => (
+            env(&["MARIADB_USER"]),
+            env(&["MARIADB_ROOT_PASSWORD", "MYSQL_ROOT_PASSWORD"]),
+            env(&["MARIADB_DATABASE", "MYSQL_DATABASE"]),
+        ),
+        "MongoDB" => (
+            env(&["MONGO_INITDB_ROOT_USERNAME"]),
+            env(&["MONGO_INITDB_ROOT_PASSWORD"]),
+            env(&["MONGO_INITDB_DATABASE"]),
+        ),
+        "Redis" => (
+            None,
+            command
+                .iter()
+                .position(|arg| arg == "--requirepass")
+                .and_then(|index| command.get(index + 1).cloned()),
+            None,
+        ),
+        "SQLServer" => (None, env(&["MSSQL_SA_PASSWORD", "SA_PASSWORD"]), None),
+        "Elasticsearch" => (None, env(&["ELASTIC_PASSWORD"]), None),
+        _ => (None, None, None),
+    };
+
+    // `docker ps` shows an image id once the tag moved on, the run reference is kept in Config
+    let image_name = container["Config"]["Image"]
+        .as_str()
+        .unwrap_or(&orphan.image)
+        .to_string();
+    let version = split_image_reference(&image_name).1.to_string();
+    let created_at = container["Created"]
+        .as_str()
+        .and_then(|created| chrono::DateTime::parse_from_rfc3339(created).ok())
+        .map(|created| created.with_timezone(&chrono::Utc).to_rfc3339())
+        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+
+    DatabaseContainer {
+        id: database_id,
+        name: orphan.name.clone(),
+        db_type: db_type.to_string(),
+        version,
+        status: if orphan.running { "running" } else { "stopped" }.to_string(),
+        port: ports.first().map(|port| port.host).unwrap_or_default(),
+        created_at,
+        max_connections: 100,
+        container_id: Some(orphan.container_id.clone()),
+        stored_enable_auth: password.as_deref().is_some_and(|p| !p.is_empty()),
+        stored_password: password,
+        stored_username: username,
+        stored_database_name: database_name,
+        stored_persist_data: !volumes.is_empty(),
+        stored_expose_to_lan: ports.iter().any(PortMapping::is_exposed),
+        stored_docker_args: Some(DockerRunArgs {
+            image: image_name,
+            env_vars,
+            ports,
+            volumes,
+            command,
+            shm_size: None,
+        }),
+        ..Default::default()
+    }
+}
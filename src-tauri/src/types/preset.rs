@@ -0,0 +1,127 @@
+use crate::types::EnvPreset;
+use serde::{Deserialize, Serialize};
+
+/// Web framework a database can be preset for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Framework {
+    Django,
+    Rails,
+    Laravel,
+    Spring,
+    // Next.js with Prisma
+    Prisma,
+}
+
+/// Engine and naming conventions a framework expects out of the box
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameworkPreset {
+    pub framework: Framework,
+    pub name: &'static str,
+    // Provider id, e.g. "PostgreSQL"
+    pub db_type: &'static str,
+    pub version: &'static str,
+    pub database_name: &'static str,
+    pub username: &'static str,
+    // Where the generated snippet goes in the project
+    pub config_file: &'static str,
+}
+
+impl Framework {
+    pub const ALL: [Framework; 5] = [
+        Framework::Django,
+        Framework::Rails,
+        Framework::Laravel,
+        Framework::Spring,
+        Framework::Prisma,
+    ];
+
+    /// Framework for the names people write in links and on the command line
+    pub fn parse(value: &str) -> Option<Self> {
+        let framework = match value.to_lowercase().as_str() {
+            "django" => Framework::Django,
+            "rails" | "ruby-on-rails" => Framework::Rails,
+            "laravel" => Framework::Laravel,
+            "spring" | "spring-boot" => Framework::Spring,
+            "prisma" | "nextjs" | "next" => Framework::Prisma,
+            _ => return None,
+        };
+        Some(framework)
+    }
+
+    /// Identifier used in links and on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            Framework::Django => "django",
+            Framework::Rails => "rails",
+            Framework::Laravel => "laravel",
+            Framework::Spring => "spring",
+            Framework::Prisma => "prisma",
+        }
+    }
+
+    /// .env naming the framework reads its connection from
+    pub fn env_preset(&self) -> EnvPreset {
+        match self {
+            Framework::Django => EnvPreset::Django,
+            Framework::Rails => EnvPreset::Rails,
+            Framework::Laravel => EnvPreset::Laravel,
+            Framework::Spring => EnvPreset::Generic,
+            Framework::Prisma => EnvPreset::Prisma,
+        }
+    }
+
+    pub fn preset(&self) -> FrameworkPreset {
+        let (name, db_type, version, database_name, username, config_file) = match self {
+            Framework::Django => (
+                "Django",
+                "PostgreSQL",
+                "17",
+                "django",
+                "django",
+                "settings.py",
+            ),
+            Framework::Rails => (
+                "Ruby on Rails",
+                "PostgreSQL",
+                "17",
+                "app_development",
+                "rails",
+                "config/database.yml",
+            ),
+            Framework::Laravel => ("Laravel", "MySQL", "8.4", "laravel", "laravel", ".env"),
+            Framework::Spring => (
+                "Spring Boot",
+                "PostgreSQL",
+                "17",
+                "spring",
+                "spring",
+                "src/main/resources/application.properties",
+            ),
+            Framework::Prisma => (
+                "Next.js (Prisma)",
+                "PostgreSQL",
+                "17",
+                "app",
+                "prisma",
+                ".env and prisma/schema.prisma",
+            ),
+        };
+
+        FrameworkPreset {
+            framework: *self,
+            name,
+            db_type,
+            version,
+            database_name,
+            username,
+            config_file,
+        }
+    }
+}
+
+/// Every framework preset, in catalog order
+pub fn framework_presets() -> Vec<FrameworkPreset> {
+    Framework::ALL.iter().map(Framework::preset).collect()
+}
@@ -0,0 +1,64 @@
+use crate::types::PortMapping;
+use serde::Serialize;
+
+// Network containers join when `docker run` gets no --network
+pub const DEFAULT_NETWORK: &str = "bridge";
+
+/// Where the image of a new container comes from and what it runs as
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageResolution {
+    pub reference: String,
+    // `os/arch` of the local copy, None when it still has to be pulled
+    pub local_platform: Option<String>,
+    // `os/arch` of the Docker daemon, None when it could not be asked
+    pub host_platform: Option<String>,
+    pub pull_required: bool,
+    // The local copy is built for another architecture and runs under emulation
+    pub emulated: bool,
+}
+
+impl ImageResolution {
+    pub fn new(
+        reference: &str,
+        local_platform: Option<String>,
+        host_platform: Option<String>,
+    ) -> Self {
+        let emulated = matches!(
+            (&local_platform, &host_platform),
+            (Some(local), Some(host)) if local != host
+        );
+
+        Self {
+            reference: reference.to_string(),
+            pull_required: local_platform.is_none(),
+            local_platform,
+            host_platform,
+            emulated,
+        }
+    }
+}
+
+/// Volume a new container mounts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumePreview {
+    pub name: String,
+    pub path: String,
+    // An existing volume is reused with the data it holds
+    pub exists: bool,
+}
+
+/// What creating a container would run and create, nothing is executed to build it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationPreview {
+    // Arguments after `docker` as creation passes them, with the password masked
+    pub args: Vec<String>,
+    // Copyable command line of the same arguments
+    pub command: String,
+    pub image: ImageResolution,
+    pub volumes: Vec<VolumePreview>,
+    pub network: String,
+    pub ports: Vec<PortMapping>,
+}
@@ -0,0 +1,122 @@
+use crate::types::DatabaseContainer;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Criteria for listing managed databases, every field is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseFilter {
+    pub db_type: Option<String>,
+    pub status: Option<String>,
+    pub tag: Option<String>,
+    // Case-insensitive substring of the container name
+    pub name: Option<String>,
+    pub port: Option<i32>,
+    pub favorite: Option<bool>,
+}
+
+impl DatabaseFilter {
+    pub fn matches(&self, database: &DatabaseContainer) -> bool {
+        self.db_type
+            .as_ref()
+            .is_none_or(|db_type| database.db_type.eq_ignore_ascii_case(db_type))
+            && self
+                .status
+                .as_ref()
+                .is_none_or(|status| database.status.eq_ignore_ascii_case(status))
+            && self.tag.as_ref().is_none_or(|tag| database.has_tag(tag))
+            && self.name.as_ref().is_none_or(|name| {
+                database
+                    .name
+                    .to_lowercase()
+                    .contains(&name.trim().to_lowercase())
+            })
+            && self.port.is_none_or(|port| database.port == port)
+            && self
+                .favorite
+                .is_none_or(|favorite| database.favorite == favorite)
+    }
+}
+
+/// Field used to order database listings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DatabaseSortKey {
+    Name,
+    CreatedAt,
+    LastStarted,
+    DbType,
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DatabaseSort {
+    pub key: DatabaseSortKey,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+impl Default for DatabaseSort {
+    fn default() -> Self {
+        Self {
+            key: DatabaseSortKey::Name,
+            descending: false,
+        }
+    }
+}
+
+impl DatabaseSort {
+    /// Order two databases, ties are broken by name then id so pages are stable
+    pub fn compare(&self, a: &DatabaseContainer, b: &DatabaseContainer) -> Ordering {
+        let ordering = match self.key {
+            DatabaseSortKey::Name => Ordering::Equal,
+            DatabaseSortKey::CreatedAt => a.created_at.cmp(&b.created_at),
+            // Never started containers sort first
+            DatabaseSortKey::LastStarted => a.last_started_at.cmp(&b.last_started_at),
+            DatabaseSortKey::DbType => a.db_type.cmp(&b.db_type),
+            DatabaseSortKey::Status => a.status.cmp(&b.status),
+        }
+        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        .then_with(|| a.id.cmp(&b.id));
+
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// One page of a database listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabasePage {
+    pub items: Vec<DatabaseContainer>,
+    // Number of databases matching the filter, across all pages
+    pub total: usize,
+    pub offset: usize,
+}
+
+impl DatabasePage {
+    /// Sort the matching databases and cut the requested page
+    pub fn from_matches(
+        mut matches: Vec<DatabaseContainer>,
+        sort: DatabaseSort,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Self {
+        matches.sort_by(|a, b| sort.compare(a, b));
+
+        let total = matches.len();
+        let items = matches
+            .into_iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+
+        Self {
+            items,
+            total,
+            offset,
+        }
+    }
+}
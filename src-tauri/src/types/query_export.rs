@@ -0,0 +1,129 @@
+use crate::types::{csv_record, SqlDialect};
+use serde::{Deserialize, Serialize};
+
+/// File format of an exported result set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryExportFormat {
+    // Header line, then one record per row, NULL as an empty field
+    #[default]
+    Csv,
+    // Array of objects keyed by column, values as text or null
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryExportResult {
+    pub path: String,
+    pub rows: u64,
+    pub bytes_written: u64,
+}
+
+/// Value of one field of the client's tab-separated output, None for NULL
+/// PostgreSQL's COPY writes NULL as `\N`, MySQL's batch mode as `NULL`
+/// Both escape backslashes, tabs and line breaks so every row stays on one line
+/// MySQL writes the string 'NULL' the same way as a NULL, so it is exported as NULL too, its
+/// XML output tells them apart but is not line-based
+pub fn unescape_field(field: &str, dialect: SqlDialect) -> Option<String> {
+    match (dialect, field) {
+        (SqlDialect::Postgres, "\\N") | (SqlDialect::MySql, "NULL") => return None,
+        _ => {}
+    }
+
+    let mut value = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some('0') => value.push('\0'),
+            Some('b') => value.push('\u{8}'),
+            Some('f') => value.push('\u{c}'),
+            Some('v') => value.push('\u{b}'),
+            Some(other) => value.push(other),
+            None => value.push('\\'),
+        }
+    }
+    Some(value)
+}
+
+/// Turns rows of a result set into the text of an export file, one piece at a time
+/// so rows are written as they arrive
+#[derive(Debug, Clone)]
+pub struct QueryExportWriter {
+    format: QueryExportFormat,
+    columns: Vec<String>,
+    rows: u64,
+}
+
+impl QueryExportWriter {
+    pub fn new(format: QueryExportFormat, columns: Vec<String>) -> Self {
+        Self {
+            format,
+            columns,
+            rows: 0,
+        }
+    }
+
+    pub fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// Text before the first row
+    pub fn start(&self) -> String {
+        match self.format {
+            // MySQL prints no column names for an empty result
+            QueryExportFormat::Csv if self.columns.is_empty() => String::new(),
+            QueryExportFormat::Csv => csv_record(&self.columns),
+            QueryExportFormat::Json => "[".to_string(),
+        }
+    }
+
+    /// Text of one row, fields in column order
+    pub fn row(&mut self, fields: &[Option<String>]) -> String {
+        self.rows += 1;
+        match self.format {
+            QueryExportFormat::Csv => csv_record(
+                &fields
+                    .iter()
+                    .map(|field| field.clone().unwrap_or_default())
+                    .collect::<Vec<_>>(),
+            ),
+            QueryExportFormat::Json => {
+                // Built by hand so the keys keep the column order
+                let members: Vec<String> = self
+                    .columns
+                    .iter()
+                    .zip(fields.iter().chain(std::iter::repeat(&None)))
+                    .map(|(column, field)| {
+                        format!(
+                            "{}:{}",
+                            serde_json::Value::from(column.as_str()),
+                            field
+                                .as_deref()
+                                .map(serde_json::Value::from)
+                                .unwrap_or(serde_json::Value::Null)
+                        )
+                    })
+                    .collect();
+                let separator = if self.rows == 1 { "\n" } else { ",\n" };
+                format!("{}{{{}}}", separator, members.join(","))
+            }
+        }
+    }
+
+    /// Text after the last row
+    pub fn finish(&self) -> String {
+        match self.format {
+            QueryExportFormat::Csv => String::new(),
+            QueryExportFormat::Json if self.rows == 0 => "]\n".to_string(),
+            QueryExportFormat::Json => "\n]\n".to_string(),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::types::DatabaseContainer;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Creation of a database in progress, kept in the store until its entry is saved
+/// One still there at launch was cut short by a crash or a quit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingCreate {
+    pub database_id: String,
+    // Container name
+    pub name: String,
+    // Volumes the creation made, volumes that already existed are never removed
+    pub volumes: Vec<String>,
+    pub started_at: String,
+    // Id of the remote Docker host the creation ran on, the local daemon when unset
+    #[serde(default)]
+    pub host: Option<String>,
+}
+
+/// What is removed to undo an interrupted creation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecoveryPlan {
+    pub remove_container: bool,
+    pub remove_entry: bool,
+    pub remove_volumes: Vec<String>,
+}
+
+impl RecoveryPlan {
+    pub fn is_empty(&self) -> bool {
+        !self.remove_container && !self.remove_entry && self.remove_volumes.is_empty()
+    }
+}
+
+impl PendingCreate {
+    /// Leftovers of the creation given what the store and Docker have now
+    /// A creation whose entry and container both exist finished, only the record is left
+    /// Resources another database owns, e.g. after a later creation reused the name, are kept
+    pub fn recovery_plan(
+        &self,
+        databases: &HashMap<String, DatabaseContainer>,
+        container_names: &HashSet<String>,
+        volumes: &HashSet<String>,
+    ) -> RecoveryPlan {
+        let has_entry = databases.contains_key(&self.database_id);
+        let has_container = container_names.contains(&self.name);
+        if has_entry && has_container {
+            return RecoveryPlan::default();
+        }
+
+        let others = || databases.values().filter(|db| db.id != self.database_id);
+        let owned_volumes: HashSet<String> = others().flat_map(|db| db.data_volumes()).collect();
+
+        RecoveryPlan {
+            remove_container: has_container && !others().any(|db| db.name == self.name),
+            remove_entry: has_entry,
+            remove_volumes: self
+                .volumes
+                .iter()
+                .filter(|volume| volumes.contains(*volume) && !owned_volumes.contains(*volume))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Interrupted creation undone at launch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredCreate {
+    pub database_id: String,
+    pub name: String,
+    pub removed_container: bool,
+    pub removed_entry: bool,
+    pub removed_volumes: Vec<String>,
+    // Set when a leftover could not be removed, it is tried again on the next launch
+    pub error: Option<String>,
+}
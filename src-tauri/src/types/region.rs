@@ -0,0 +1,131 @@
+use crate::types::locale::{non_empty, postgres_image_locale};
+use crate::types::DockerRunArgs;
+use serde::{Deserialize, Serialize};
+
+// PostgreSQL settings initdb fixes in postgresql.conf, the TZ variable alone does not change them
+const POSTGRES_TIMEZONE_SETTINGS: [&str; 2] = ["timezone", "log_timezone"];
+
+/// Timezone and system locale a container runs with
+/// Applied through environment variables, and the server settings of engines that ignore them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionSettings {
+    // IANA timezone, e.g. "Europe/Madrid"
+    #[serde(default)]
+    pub timezone: Option<String>,
+    // LANG of the container, e.g. "en_US.UTF-8"
+    #[serde(default)]
+    pub lang: Option<String>,
+}
+
+impl RegionSettings {
+    pub fn is_empty(&self) -> bool {
+        self.timezone().is_none() && self.lang().is_none()
+    }
+
+    pub fn timezone(&self) -> Option<&str> {
+        non_empty(&self.timezone)
+    }
+
+    pub fn lang(&self) -> Option<&str> {
+        non_empty(&self.lang)
+    }
+
+    /// Fill the fields left unset from `defaults`, the app-wide settings
+    pub fn or_defaults(&self, defaults: &RegionSettings) -> RegionSettings {
+        RegionSettings {
+            timezone: self.timezone().or(defaults.timezone()).map(str::to_string),
+            lang: self.lang().or(defaults.lang()).map(str::to_string),
+        }
+    }
+
+    /// Problems with the values, checked before they reach the container
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if let Some(timezone) = self.timezone() {
+            let valid = timezone
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "/_+-".contains(c))
+                && !timezone.starts_with('/')
+                && !timezone.ends_with('/')
+                && !timezone.contains("//");
+            if !valid {
+                violations.push(format!(
+                    "'{}' is not a timezone name like Europe/Madrid or UTC",
+                    timezone
+                ));
+            }
+        }
+
+        if let Some(lang) = self.lang() {
+            if !lang
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "_.@-".contains(c))
+            {
+                violations.push(format!("'{}' is not a locale name like en_US.UTF-8", lang));
+            }
+        }
+
+        violations
+    }
+
+    /// Problems with the values for a database of `db_type` and `version`
+    /// initdb starts from LANG, PostgreSQL images only generate a few locales
+    pub fn violations_for(&self, db_type: &str, version: &str) -> Vec<String> {
+        let mut violations = self.violations();
+        if let Some(lang) = self.lang() {
+            if db_type == "PostgreSQL" && !postgres_image_locale(lang, version) {
+                violations.push(format!(
+                    "The PostgreSQL {} image does not generate the {} locale, use C.UTF-8",
+                    version, lang
+                ));
+            }
+        }
+        violations
+    }
+
+    /// Set the environment and server settings of the container, replacing earlier values
+    pub fn apply(&self, db_type: &str, docker_args: &mut DockerRunArgs) {
+        for (key, value) in [("TZ", self.timezone()), ("LANG", self.lang())] {
+            match value {
+                Some(value) => {
+                    docker_args
+                        .env_vars
+                        .insert(key.to_string(), value.to_string());
+                }
+                None => {
+                    docker_args.env_vars.remove(key);
+                }
+            }
+        }
+
+        if db_type == "PostgreSQL" {
+            remove_postgres_settings(&mut docker_args.command);
+            if let Some(timezone) = self.timezone() {
+                for setting in POSTGRES_TIMEZONE_SETTINGS {
+                    docker_args.command.push("-c".to_string());
+                    docker_args
+                        .command
+                        .push(format!("{}={}", setting, timezone));
+                }
+            }
+        }
+    }
+}
+
+/// Drop `-c timezone=...` pairs an earlier apply added
+fn remove_postgres_settings(command: &mut Vec<String>) {
+    let mut index = 0;
+    while index + 1 < command.len() {
+        let is_timezone = command[index] == "-c"
+            && POSTGRES_TIMEZONE_SETTINGS
+                .iter()
+                .any(|setting| command[index + 1].starts_with(&format!("{}=", setting)));
+        if is_timezone {
+            command.drain(index..index + 2);
+        } else {
+            index += 1;
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::types::{AppError, SqlDialect};
+use serde::{Deserialize, Serialize};
+
+// Slots and senders configured when logical replication is turned on without values
+pub const DEFAULT_MAX_REPLICATION_SLOTS: u32 = 10;
+pub const DEFAULT_MAX_WAL_SENDERS: u32 = 10;
+// Upper bound accepted for both, far above what a local CDC setup needs
+pub const MAX_REPLICATION_CONNECTIONS: u32 = 100;
+// PostgreSQL's NAMEDATALEN - 1
+pub const MAX_REPLICATION_NAME_LEN: usize = 63;
+
+/// Change to the server configuration of a PostgreSQL database
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicalReplicationRequest {
+    // wal_level=logical when on, the server's defaults when off
+    pub enabled: bool,
+    #[serde(default)]
+    pub max_replication_slots: Option<u32>,
+    #[serde(default)]
+    pub max_wal_senders: Option<u32>,
+}
+
+impl LogicalReplicationRequest {
+    /// `ALTER SYSTEM` statements applying the request, each has to run on its own
+    pub fn alter_system_sql(&self) -> Result<Vec<String>, AppError> {
+        if !self.enabled {
+            return Ok(vec![
+                "ALTER SYSTEM RESET wal_level".to_string(),
+                "ALTER SYSTEM RESET max_replication_slots".to_string(),
+                "ALTER SYSTEM RESET max_wal_senders".to_string(),
+            ]);
+        }
+
+        let slots = self
+            .max_replication_slots
+            .unwrap_or(DEFAULT_MAX_REPLICATION_SLOTS);
+        let senders = self.max_wal_senders.unwrap_or(DEFAULT_MAX_WAL_SENDERS);
+        for (name, value) in [
+            ("max_replication_slots", slots),
+            ("max_wal_senders", senders),
+        ] {
+            if !(1..=MAX_REPLICATION_CONNECTIONS).contains(&value) {
+                return Err(AppError::Validation(format!(
+                    "{} must be between 1 and {}",
+                    name, MAX_REPLICATION_CONNECTIONS
+                )));
+            }
+        }
+
+        Ok(vec![
+            "ALTER SYSTEM SET wal_level = 'logical'".to_string(),
+            format!("ALTER SYSTEM SET max_replication_slots = {}", slots),
+            format!("ALTER SYSTEM SET max_wal_senders = {}", senders),
+        ])
+    }
+}
+
+/// Replication settings the server runs with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicalReplicationStatus {
+    pub wal_level: String,
+    pub max_replication_slots: u32,
+    pub max_wal_senders: u32,
+    // A changed setting waits for a restart
+    pub pending_restart: bool,
+}
+
+impl LogicalReplicationStatus {
+    pub fn enabled(&self) -> bool {
+        self.wal_level == "logical"
+    }
+}
+
+/// Publication of a PostgreSQL database, what a CDC consumer subscribes to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Publication {
+    pub name: String,
+    pub all_tables: bool,
+    // "schema.table" of each published table
+    #[serde(default)]
+    pub tables: Vec<String>,
+}
+
+/// Replication slot of a PostgreSQL server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicationSlot {
+    pub name: String,
+    // Output plugin of a logical slot, e.g. "pgoutput"
+    pub plugin: Option<String>,
+    // "logical" or "physical"
+    pub slot_type: String,
+    pub database: Option<String>,
+    // A consumer is connected
+    pub active: bool,
+    pub restart_lsn: Option<String>,
+    pub confirmed_flush_lsn: Option<String>,
+    // WAL the server keeps for the slot, it grows while nothing consumes it
+    pub retained_wal_bytes: Option<i64>,
+}
+
+/// Reject names PostgreSQL would fold or truncate
+/// Slot names only take lower-case letters, digits and underscores; publications follow the same
+/// rule here so both can be typed into connector configs as is
+pub fn validate_replication_name(name: &str, what: &str) -> Result<(), AppError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_REPLICATION_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(AppError::Validation(format!(
+            "{} names are 1 to {} lower-case letters, digits or underscores",
+            what, MAX_REPLICATION_NAME_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// `CREATE PUBLICATION` for `tables` given as "schema.table" or "table", all tables when empty
+pub fn create_publication_sql(name: &str, tables: &[String]) -> Result<String, AppError> {
+    validate_replication_name(name, "Publication")?;
+    let dialect = SqlDialect::Postgres;
+
+    let target = if tables.is_empty() {
+        "ALL TABLES".to_string()
+    } else {
+        let tables: Vec<String> = tables
+            .iter()
+            .map(|table| {
+                let table = table.trim();
+                if table.is_empty() {
+                    return Err(AppError::Validation(
+                        "Table names cannot be empty".to_string(),
+                    ));
+                }
+                Ok(match table.split_once('.') {
+                    Some((schema, table)) => format!(
+                        "{}.{}",
+                        dialect.quote_identifier(schema),
+                        dialect.quote_identifier(table)
+                    ),
+                    None => dialect.quote_identifier(table),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        format!("TABLE {}", tables.join(", "))
+    };
+
+    Ok(format!(
+        "CREATE PUBLICATION {} FOR {}",
+        dialect.quote_identifier(name),
+        target
+    ))
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScheduledAction {
+    Start,
+    Stop,
+    // Run the integrity check of each running container at low priority
+    IntegrityCheck,
+}
+
+/// Start, stop or check containers on a cron schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerSchedule {
+    pub id: String,
+    pub name: String,
+    // Database ids, empty targets every managed container
+    #[serde(default)]
+    pub container_ids: Vec<String>,
+    pub action: ScheduledAction,
+    // Five-field cron expression evaluated in local time, e.g. "0 9 * * 1-5"
+    pub cron: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub created_at: String,
+    // Last occurrence handled, whether it ran or was skipped as missed
+    #[serde(default)]
+    pub last_run_at: Option<String>,
+    // Why the last run failed, cleared by a run that succeeds
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl ContainerSchedule {
+    pub fn targets(&self, database_id: &str) -> bool {
+        self.container_ids.is_empty() || self.container_ids.iter().any(|id| id == database_id)
+    }
+}
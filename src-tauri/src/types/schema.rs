@@ -0,0 +1,335 @@
+use crate::types::{json_flag, ColumnInfo};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Tables of one database with their keys and indexes, as an ERD needs them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseSchema {
+    // Empty when a MySQL or MariaDB server was read as a whole
+    pub database: String,
+    pub tables: Vec<SchemaTable>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaTable {
+    pub schema: String,
+    pub name: String,
+    pub columns: Vec<ColumnInfo>,
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<ForeignKey>,
+    // Secondary indexes, the primary key is not repeated here
+    pub indexes: Vec<IndexInfo>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignKey {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub referenced_schema: String,
+    pub referenced_table: String,
+    // Same order as `columns`
+    pub referenced_columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexInfo {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+/// Text formats a schema can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SchemaFormat {
+    // Mermaid `erDiagram`, renders in Markdown on GitHub and GitLab
+    Mermaid,
+    // dbdiagram.io markup
+    Dbml,
+}
+
+fn json_str(value: &Value, key: &str) -> String {
+    value[key].as_str().unwrap_or_default().to_string()
+}
+
+impl DatabaseSchema {
+    /// Assemble the schema from catalog rows, one JSON object per column, per foreign key
+    /// column and per index column, each sorted by table and position
+    pub fn from_catalog(
+        database: &str,
+        columns: &[Value],
+        foreign_keys: &[Value],
+        indexes: &[Value],
+    ) -> Self {
+        let mut schema = DatabaseSchema {
+            database: database.to_string(),
+            tables: Vec::new(),
+        };
+
+        for row in columns {
+            let column = ColumnInfo::from_json(row);
+            let table = schema.table_entry(&json_str(row, "schema"), &json_str(row, "table"));
+            if column.primary_key {
+                table.primary_key.push(column.name.clone());
+            }
+            table.columns.push(column);
+        }
+
+        for row in foreign_keys {
+            let Some(table) =
+                schema.existing_table(&json_str(row, "schema"), &json_str(row, "table"))
+            else {
+                continue;
+            };
+            let name = json_str(row, "name");
+            if table.foreign_keys.last().is_none_or(|key| key.name != name) {
+                table.foreign_keys.push(ForeignKey {
+                    name,
+                    referenced_schema: json_str(row, "referencedSchema"),
+                    referenced_table: json_str(row, "referencedTable"),
+                    ..Default::default()
+                });
+            }
+            let key = table.foreign_keys.last_mut().unwrap();
+            key.columns.push(json_str(row, "column"));
+            key.referenced_columns
+                .push(json_str(row, "referencedColumn"));
+        }
+
+        for row in indexes {
+            // Expression indexes have no column name
+            let Some(column) = row["column"].as_str() else {
+                continue;
+            };
+            let Some(table) =
+                schema.existing_table(&json_str(row, "schema"), &json_str(row, "table"))
+            else {
+                continue;
+            };
+            let name = json_str(row, "name");
+            if table.indexes.last().is_none_or(|index| index.name != name) {
+                table.indexes.push(IndexInfo {
+                    name,
+                    columns: Vec::new(),
+                    unique: json_flag(&row["unique"]),
+                });
+            }
+            table
+                .indexes
+                .last_mut()
+                .unwrap()
+                .columns
+                .push(column.to_string());
+        }
+
+        schema
+    }
+
+    fn table_entry(&mut self, schema: &str, name: &str) -> &mut SchemaTable {
+        let position = match self
+            .tables
+            .iter()
+            .position(|table| table.schema == schema && table.name == name)
+        {
+            Some(position) => position,
+            None => {
+                self.tables.push(SchemaTable {
+                    schema: schema.to_string(),
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.tables.len() - 1
+            }
+        };
+        &mut self.tables[position]
+    }
+
+    fn existing_table(&mut self, schema: &str, name: &str) -> Option<&mut SchemaTable> {
+        self.tables
+            .iter_mut()
+            .find(|table| table.schema == schema && table.name == name)
+    }
+
+    pub fn render(&self, format: SchemaFormat) -> String {
+        match format {
+            SchemaFormat::Mermaid => self.to_mermaid(),
+            SchemaFormat::Dbml => self.to_dbml(),
+        }
+    }
+
+    // Schema names are left out when every table shares one
+    fn single_schema(&self) -> bool {
+        self.tables.iter().all(|table| {
+            self.tables
+                .first()
+                .is_some_and(|first| first.schema == table.schema)
+        })
+    }
+
+    fn display_name(&self, schema: &str, table: &str) -> String {
+        if self.single_schema() {
+            table.to_string()
+        } else {
+            format!("{}.{}", schema, table)
+        }
+    }
+
+    /// Mermaid `erDiagram` with columns and one relationship per foreign key
+    pub fn to_mermaid(&self) -> String {
+        // Mermaid names and types are single words
+        let word = |text: &str| -> String {
+            text.chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect()
+        };
+
+        let mut lines = vec!["erDiagram".to_string()];
+        for table in &self.tables {
+            lines.push(format!(
+                "    {} {{",
+                word(&self.display_name(&table.schema, &table.name))
+            ));
+            for column in &table.columns {
+                let foreign = table
+                    .foreign_keys
+                    .iter()
+                    .any(|key| key.columns.contains(&column.name));
+                let keys: Vec<&str> = [(column.primary_key, "PK"), (foreign, "FK")]
+                    .into_iter()
+                    .filter_map(|(set, key)| set.then_some(key))
+                    .collect();
+                let mut line =
+                    format!("        {} {}", word(&column.data_type), word(&column.name));
+                if !keys.is_empty() {
+                    line.push(' ');
+                    line.push_str(&keys.join(", "));
+                }
+                lines.push(line);
+            }
+            lines.push("    }".to_string());
+        }
+
+        for table in &self.tables {
+            for key in &table.foreign_keys {
+                // Optional when any referencing column accepts NULL
+                let optional = table
+                    .columns
+                    .iter()
+                    .any(|column| column.nullable && key.columns.contains(&column.name));
+                lines.push(format!(
+                    "    {} }}o--{} {} : \"{}\"",
+                    word(&self.display_name(&table.schema, &table.name)),
+                    if optional { "o|" } else { "||" },
+                    word(&self.display_name(&key.referenced_schema, &key.referenced_table)),
+                    key.name.replace('"', "'")
+                ));
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// DBML with columns, indexes and references
+    pub fn to_dbml(&self) -> String {
+        let name = |text: &str| -> String {
+            if !text.is_empty() && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                text.to_string()
+            } else {
+                format!("\"{}\"", text.replace('"', "\\\""))
+            }
+        };
+        let table_name = |schema: &str, table: &str| -> String {
+            if self.single_schema() {
+                name(table)
+            } else {
+                format!("{}.{}", name(schema), name(table))
+            }
+        };
+        let column_list = |columns: &[String]| -> String {
+            if columns.len() == 1 {
+                name(&columns[0])
+            } else {
+                format!(
+                    "({})",
+                    columns
+                        .iter()
+                        .map(|c| name(c))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+        };
+
+        let mut blocks = Vec::new();
+        for table in &self.tables {
+            let mut lines = vec![format!(
+                "Table {} {{",
+                table_name(&table.schema, &table.name)
+            )];
+            for column in &table.columns {
+                let mut settings = Vec::new();
+                if column.primary_key && table.primary_key.len() == 1 {
+                    settings.push("pk");
+                }
+                if !column.nullable {
+                    settings.push("not null");
+                }
+                let settings = if settings.is_empty() {
+                    String::new()
+                } else {
+                    format!(" [{}]", settings.join(", "))
+                };
+                lines.push(format!(
+                    "  {} {}{}",
+                    name(&column.name),
+                    name(&column.data_type),
+                    settings
+                ));
+            }
+
+            let composite_key = (table.primary_key.len() > 1)
+                .then(|| format!("    {} [pk]", column_list(&table.primary_key)));
+            if composite_key.is_some() || !table.indexes.is_empty() {
+                lines.push(String::new());
+                lines.push("  indexes {".to_string());
+                lines.extend(composite_key);
+                for index in &table.indexes {
+                    lines.push(format!(
+                        "    {} [{}name: '{}']",
+                        column_list(&index.columns),
+                        if index.unique { "unique, " } else { "" },
+                        index.name.replace('\'', "\\'")
+                    ));
+                }
+                lines.push("  }".to_string());
+            }
+            lines.push("}".to_string());
+            blocks.push(lines.join("\n"));
+        }
+
+        for table in &self.tables {
+            for key in &table.foreign_keys {
+                blocks.push(format!(
+                    "Ref {}: {}.{} > {}.{}",
+                    name(&key.name),
+                    table_name(&table.schema, &table.name),
+                    column_list(&key.columns),
+                    table_name(&key.referenced_schema, &key.referenced_table),
+                    column_list(&key.referenced_columns)
+                ));
+            }
+        }
+
+        blocks.join("\n\n") + "\n"
+    }
+}
@@ -0,0 +1,161 @@
+use crate::types::{AppError, DockerHost, LogViewPreferences, RegionSettings, ShareSettings};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+fn default_sync_interval_secs() -> u64 {
+    5
+}
+
+fn default_trash_retention_days() -> u32 {
+    7
+}
+
+fn default_notifications_enabled() -> bool {
+    true
+}
+
+fn default_api_port() -> u16 {
+    17321
+}
+
+fn default_mcp_port() -> u16 {
+    17322
+}
+
+fn default_image_update_check_hours() -> u64 {
+    24
+}
+
+fn default_metrics_history_interval_secs() -> u64 {
+    60
+}
+
+fn default_metrics_history_retention_days() -> u32 {
+    7
+}
+
+fn default_sql_read_only_guard() -> bool {
+    true
+}
+
+/// User-configurable application settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    // Seconds between background reconciliations with Docker
+    #[serde(default = "default_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+    // Days removed containers stay restorable, 0 deletes them right away
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    // Stop running containers when the app quits
+    #[serde(default)]
+    pub stop_on_exit: bool,
+    // Desktop notifications for crashes, failing healthchecks and restart loops
+    #[serde(default = "default_notifications_enabled")]
+    pub notifications_enabled: bool,
+    // Database ids that never notify
+    #[serde(default)]
+    pub muted_containers: Vec<String>,
+    // Token-protected HTTP API on localhost for scripts and editors
+    #[serde(default)]
+    pub api_enabled: bool,
+    #[serde(default = "default_api_port")]
+    pub api_port: u16,
+    // MCP server for AI assistants, authenticated with the API token
+    #[serde(default)]
+    pub mcp_enabled: bool,
+    #[serde(default = "default_mcp_port")]
+    pub mcp_port: u16,
+    // Hours between checks of the registry for newer images, 0 only checks on demand
+    #[serde(default = "default_image_update_check_hours")]
+    pub image_update_check_hours: u64,
+    // Seconds between samples recorded in the metrics history, 0 stops recording
+    #[serde(default = "default_metrics_history_interval_secs")]
+    pub metrics_history_interval_secs: u64,
+    // Days of metrics history kept, older points are dropped
+    #[serde(default = "default_metrics_history_retention_days")]
+    pub metrics_history_retention_days: u32,
+    // Blocks creating, changing, removing and exec'ing into containers, viewing still works
+    #[serde(default)]
+    pub read_only: bool,
+    // Timezone and locale of new containers that do not set their own
+    #[serde(default)]
+    pub default_region: RegionSettings,
+    // Log view of each database id, containers without an entry use the defaults
+    #[serde(default)]
+    pub log_preferences: HashMap<String, LogViewPreferences>,
+    // Remote Docker daemons whose databases are managed along with the local ones
+    #[serde(default)]
+    pub docker_hosts: Vec<DockerHost>,
+    // Tunneling tool that shares a database on a public address
+    #[serde(default)]
+    pub share: ShareSettings,
+    // The SQL console refuses statements that change data until writes are unlocked for the session
+    #[serde(default = "default_sql_read_only_guard")]
+    pub sql_read_only_guard: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            sync_interval_secs: default_sync_interval_secs(),
+            trash_retention_days: default_trash_retention_days(),
+            stop_on_exit: false,
+            notifications_enabled: default_notifications_enabled(),
+            muted_containers: Vec::new(),
+            api_enabled: false,
+            api_port: default_api_port(),
+            mcp_enabled: false,
+            mcp_port: default_mcp_port(),
+            image_update_check_hours: default_image_update_check_hours(),
+            metrics_history_interval_secs: default_metrics_history_interval_secs(),
+            metrics_history_retention_days: default_metrics_history_retention_days(),
+            read_only: false,
+            default_region: RegionSettings::default(),
+            log_preferences: HashMap::new(),
+            docker_hosts: Vec::new(),
+            share: ShareSettings::default(),
+            sql_read_only_guard: default_sql_read_only_guard(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Fails while read-only mode is on
+    pub fn ensure_writable(&self) -> Result<(), AppError> {
+        if self.read_only {
+            return Err(AppError::ReadOnly);
+        }
+        Ok(())
+    }
+
+    /// Log view of a database, the defaults until it is changed
+    pub fn log_preferences_of(&self, database_id: &str) -> LogViewPreferences {
+        self.log_preferences
+            .get(database_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Keep the log view of a database, preferences equal to the defaults are not stored
+    pub fn set_log_preferences(&mut self, database_id: &str, preferences: LogViewPreferences) {
+        if preferences == LogViewPreferences::default() {
+            self.log_preferences.remove(database_id);
+        } else {
+            self.log_preferences
+                .insert(database_id.to_string(), preferences);
+        }
+    }
+}
+
+pub type SettingsStore = std::sync::Mutex<AppSettings>;
+
+/// Encryption state of the persisted store
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoreEncryptionStatus {
+    pub enabled: bool,
+    // "passphrase" or "keychain"
+    pub mode: Option<String>,
+    // A passphrase is required before the store can be read
+    pub locked: bool,
+}
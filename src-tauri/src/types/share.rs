@@ -0,0 +1,185 @@
+use crate::types::AppError;
+use serde::{Deserialize, Serialize};
+
+// How long a share stays open when no duration is given, and the longest allowed
+pub const DEFAULT_SHARE_MINUTES: u32 = 60;
+pub const MAX_SHARE_MINUTES: u32 = 24 * 60;
+
+fn default_bore_server() -> String {
+    "bore.pub".to_string()
+}
+
+/// Tunneling tool that gives a local port a public address
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShareTool {
+    // Quick tunnel on trycloudflare.com, the collaborator connects with `cloudflared access tcp`
+    Cloudflared,
+    // TCP endpoint on ngrok's servers, needs an authtoken configured in ngrok
+    Ngrok,
+    // Open source TCP tunnel, bore.pub or a self-hosted server
+    #[default]
+    Bore,
+}
+
+impl ShareTool {
+    /// Executable run when the settings do not give a path
+    pub fn binary(&self) -> &'static str {
+        match self {
+            ShareTool::Cloudflared => "cloudflared",
+            ShareTool::Ngrok => "ngrok",
+            ShareTool::Bore => "bore",
+        }
+    }
+}
+
+/// Tunneling tool used to share databases, picked in the settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareSettings {
+    #[serde(default)]
+    pub tool: ShareTool,
+    // Full path of the executable, found on the PATH when unset
+    #[serde(default)]
+    pub binary_path: Option<String>,
+    // Server bore forwards through
+    #[serde(default = "default_bore_server")]
+    pub bore_server: String,
+}
+
+impl Default for ShareSettings {
+    fn default() -> Self {
+        Self {
+            tool: ShareTool::default(),
+            binary_path: None,
+            bore_server: default_bore_server(),
+        }
+    }
+}
+
+impl ShareSettings {
+    /// Problems with the values, checked before they are saved
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if let Some(path) = &self.binary_path {
+            if path.trim().is_empty() || path.starts_with('-') {
+                violations.push(format!("'{}' is not a valid path to the tunnel tool", path));
+            }
+        }
+        let server = self.bore_server.trim();
+        if server.is_empty()
+            || server.starts_with('-')
+            || server.chars().any(|c| c.is_whitespace() || c.is_control())
+        {
+            violations.push(format!("'{}' is not a valid bore server", self.bore_server));
+        }
+
+        violations
+    }
+
+    /// Executable to run, the configured path or the tool's usual name
+    pub fn program(&self) -> String {
+        self.binary_path
+            .clone()
+            .filter(|path| !path.trim().is_empty())
+            .unwrap_or_else(|| self.tool.binary().to_string())
+    }
+
+    /// Arguments exposing `port` on 127.0.0.1, the tool keeps running until killed
+    pub fn args(&self, port: u16) -> Vec<String> {
+        match self.tool {
+            ShareTool::Cloudflared => vec![
+                "tunnel".to_string(),
+                "--no-autoupdate".to_string(),
+                "--url".to_string(),
+                format!("tcp://127.0.0.1:{}", port),
+            ],
+            ShareTool::Ngrok => vec![
+                "tcp".to_string(),
+                port.to_string(),
+                "--log".to_string(),
+                "stdout".to_string(),
+                "--log-format".to_string(),
+                "logfmt".to_string(),
+            ],
+            ShareTool::Bore => vec![
+                "local".to_string(),
+                port.to_string(),
+                "--to".to_string(),
+                self.bore_server.trim().to_string(),
+            ],
+        }
+    }
+
+    /// Public address in a line the tool printed, None for the other lines
+    pub fn public_address(&self, line: &str) -> Option<String> {
+        match self.tool {
+            ShareTool::Cloudflared => line
+                .split_whitespace()
+                .find(|word| word.starts_with("https://") && word.contains(".trycloudflare.com"))
+                .map(|word| word.trim_end_matches(['|', ',']).to_string()),
+            ShareTool::Ngrok => line
+                .split_whitespace()
+                .find_map(|pair| pair.strip_prefix("url="))
+                .filter(|url| url.starts_with("tcp://"))
+                .map(str::to_string),
+            ShareTool::Bore => line
+                .split_once("listening at ")
+                .map(|(_, address)| address.trim().to_string())
+                .filter(|address| !address.is_empty()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShareState {
+    // The tool runs but has not printed the public address yet
+    Starting,
+    Active,
+    // Stopped by the user, expired, or the tool exited
+    Stopped,
+}
+
+/// Live state of a database shared through a public tunnel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareStatus {
+    pub database_id: String,
+    pub tool: ShareTool,
+    pub state: ShareState,
+    // Address the collaborator connects to, e.g. "bore.pub:41234"
+    pub public_address: Option<String>,
+    pub started_at: Option<String>,
+    // The tunnel is closed at this time
+    pub expires_at: Option<String>,
+    // Last line the tool printed before exiting
+    pub last_error: Option<String>,
+}
+
+impl ShareStatus {
+    pub fn stopped(database_id: &str, tool: ShareTool) -> Self {
+        Self {
+            database_id: database_id.to_string(),
+            tool,
+            state: ShareState::Stopped,
+            public_address: None,
+            started_at: None,
+            expires_at: None,
+            last_error: None,
+        }
+    }
+}
+
+/// Minutes a share stays open, the default when unset
+pub fn share_duration_minutes(minutes: Option<u32>) -> Result<u32, AppError> {
+    let minutes = minutes.unwrap_or(DEFAULT_SHARE_MINUTES);
+    if !(1..=MAX_SHARE_MINUTES).contains(&minutes) {
+        return Err(AppError::Validation(format!(
+            "A database can be shared for 1 to {} minutes",
+            MAX_SHARE_MINUTES
+        )));
+    }
+    Ok(minutes)
+}
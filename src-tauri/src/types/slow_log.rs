@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+// Entries returned when no limit is given
+pub const DEFAULT_SLOW_LOG_LIMIT: usize = 100;
+pub const MAX_SLOW_LOG_LIMIT: usize = 1000;
+// Longest threshold accepted, one hour
+pub const MAX_SLOW_LOG_THRESHOLD_MS: u64 = 3_600_000;
+
+/// Statement the engine logged for running longer than the threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowQuery {
+    // RFC 3339, when the statement started or, for PostgreSQL, finished
+    pub timestamp: String,
+    pub duration_ms: f64,
+    // Redis commands are joined with spaces
+    pub query: String,
+    // Schema the statement ran in, MySQL and MariaDB only
+    #[serde(default)]
+    pub database: Option<String>,
+    // `user[user] @ host [ip]` for MySQL and MariaDB, client address for Redis
+    #[serde(default)]
+    pub client: Option<String>,
+    #[serde(default)]
+    pub rows_examined: Option<u64>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Copy of a container's data volume taken at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub database_id: String,
+    pub label: Option<String>,
+    // Docker volume holding the copied data
+    pub volume: String,
+    pub created_at: String,
+    // Id of the remote Docker host holding the volume, the local daemon when unset
+    #[serde(default)]
+    pub host: Option<String>,
+}
@@ -0,0 +1,234 @@
+use crate::types::{AppError, DatabaseContainer, DatabaseCredentials};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const MAX_SNIPPET_NAME_LEN: usize = 100;
+
+/// Where a snippet is run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SnippetKind {
+    // Pasted in the SQL console
+    Sql,
+    // Run in the container's terminal
+    Command,
+}
+
+/// Named SQL query or shell command with `{{variable}}` placeholders
+/// Scoped to one container or to every database of a type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    // Empty when saving a new snippet
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub kind: SnippetKind,
+    pub body: String,
+    // Set for a snippet of one container
+    #[serde(default)]
+    pub database_id: Option<String>,
+    // Set for a snippet shared by every database of the type, e.g. "PostgreSQL"
+    #[serde(default)]
+    pub db_type: Option<String>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+impl Snippet {
+    /// Problems with the values, checked before the snippet is saved
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_SNIPPET_NAME_LEN {
+            violations.push(format!(
+                "Snippet names are 1 to {} characters long",
+                MAX_SNIPPET_NAME_LEN
+            ));
+        }
+        if self.body.trim().is_empty() {
+            violations.push("The snippet is empty".to_string());
+        }
+        if self.database_id.is_some() == self.db_type.is_some() {
+            violations
+                .push("A snippet belongs to either a container or a database type".to_string());
+        }
+
+        violations
+    }
+
+    /// Whether the snippet can be used with `database`
+    pub fn applies_to(&self, database: &DatabaseContainer) -> bool {
+        self.database_id.as_deref() == Some(database.id.as_str())
+            || self.db_type.as_deref() == Some(database.db_type.as_str())
+    }
+
+    /// Names of the `{{variable}}` placeholders, in order of first use
+    pub fn variables(&self) -> Vec<String> {
+        let mut variables: Vec<String> = Vec::new();
+        for (_, name) in placeholders(&self.body) {
+            if !variables.contains(&name) {
+                variables.push(name);
+            }
+        }
+        variables
+    }
+
+    /// Body with its placeholders replaced
+    /// `{{container}}`, `{{database}}` and `{{username}}` default to the values of `database`
+    pub fn render(
+        &self,
+        database: &DatabaseContainer,
+        values: &HashMap<String, String>,
+    ) -> Result<String, AppError> {
+        let credentials = DatabaseCredentials::of(database);
+        let builtin = |name: &str| match name {
+            "container" => Some(database.name.clone()),
+            "database" => Some(credentials.database.clone()),
+            "username" => Some(credentials.username.clone()),
+            _ => None,
+        };
+
+        let mut missing = Vec::new();
+        let mut rendered = String::new();
+        let mut rest = self.body.as_str();
+        for (placeholder, name) in placeholders(&self.body) {
+            let Some(start) = rest.find(&placeholder) else {
+                continue;
+            };
+            rendered.push_str(&rest[..start]);
+            match values.get(&name).cloned().or_else(|| builtin(&name)) {
+                Some(value) => rendered.push_str(&value),
+                None => {
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                }
+            }
+            rest = &rest[start + placeholder.len()..];
+        }
+        rendered.push_str(rest);
+
+        if !missing.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Fill in {} to use '{}'",
+                missing.join(", "),
+                self.name
+            )));
+        }
+        Ok(rendered)
+    }
+}
+
+/// `{{name}}` placeholders of a body as (placeholder text, trimmed name), in order
+/// Names are letters, digits and underscores, anything else between braces is left as is
+fn placeholders(body: &str) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            found.push((rest[start..start + end + 4].to_string(), name.to_string()));
+            rest = &after[end + 2..];
+        } else {
+            rest = after;
+        }
+    }
+    found
+}
+
+/// Saved snippets of every container and database type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnippetLibrary {
+    pub snippets: Vec<Snippet>,
+}
+
+impl SnippetLibrary {
+    /// Snippets usable with `database`, its own first, then by name
+    pub fn for_database(&self, database: &DatabaseContainer) -> Vec<Snippet> {
+        let mut snippets: Vec<Snippet> = self
+            .snippets
+            .iter()
+            .filter(|snippet| snippet.applies_to(database))
+            .cloned()
+            .collect();
+        snippets.sort_by(|a, b| {
+            b.database_id
+                .is_some()
+                .cmp(&a.database_id.is_some())
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        snippets
+    }
+
+    /// Add or replace a snippet, a new one gets an id
+    /// Names are unique per scope and kind
+    pub fn save(&mut self, mut snippet: Snippet, now: String) -> Result<Snippet, AppError> {
+        if let Some(violation) = snippet.violations().into_iter().next() {
+            return Err(AppError::Validation(violation));
+        }
+        snippet.name = snippet.name.trim().to_string();
+
+        let duplicate = self.snippets.iter().any(|other| {
+            other.id != snippet.id
+                && other.kind == snippet.kind
+                && other.database_id == snippet.database_id
+                && other.db_type == snippet.db_type
+                && other.name.eq_ignore_ascii_case(&snippet.name)
+        });
+        if duplicate {
+            return Err(AppError::Validation(format!(
+                "A snippet named '{}' already exists",
+                snippet.name
+            )));
+        }
+
+        if snippet.id.is_empty() {
+            snippet.id = uuid::Uuid::new_v4().to_string();
+            snippet.created_at = now.clone();
+            snippet.updated_at = now;
+            self.snippets.push(snippet.clone());
+        } else {
+            let existing = self
+                .snippets
+                .iter_mut()
+                .find(|other| other.id == snippet.id)
+                .ok_or_else(|| AppError::NotFound("Snippet".to_string()))?;
+            snippet.created_at = existing.created_at.clone();
+            snippet.updated_at = now;
+            *existing = snippet.clone();
+        }
+
+        Ok(snippet)
+    }
+
+    /// Forget a snippet, returns whether it existed
+    pub fn remove(&mut self, snippet_id: &str) -> bool {
+        let before = self.snippets.len();
+        self.snippets.retain(|snippet| snippet.id != snippet_id);
+        self.snippets.len() != before
+    }
+
+    pub fn get(&self, snippet_id: &str) -> Option<&Snippet> {
+        self.snippets
+            .iter()
+            .find(|snippet| snippet.id == snippet_id)
+    }
+
+    /// Drop the snippets of containers that were removed, type-wide ones are kept
+    pub fn retain_databases(&mut self, databases: &HashMap<String, DatabaseContainer>) {
+        self.snippets.retain(|snippet| {
+            snippet
+                .database_id
+                .as_ref()
+                .is_none_or(|id| databases.contains_key(id))
+        });
+    }
+}
@@ -0,0 +1,278 @@
+use crate::types::AppError;
+use serde::{Deserialize, Serialize};
+
+// Most rows a single page may ask for
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// SQL flavour of an engine, decides quoting and catalog queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    // MySQL and MariaDB
+    MySql,
+}
+
+impl SqlDialect {
+    pub fn of(db_type: &str) -> Result<Self, AppError> {
+        match db_type {
+            "PostgreSQL" => Ok(SqlDialect::Postgres),
+            "MySQL" | "MariaDB" => Ok(SqlDialect::MySql),
+            _ => Err(AppError::Validation(format!(
+                "{} is not a SQL database, only PostgreSQL, MySQL and MariaDB can be browsed",
+                db_type
+            ))),
+        }
+    }
+
+    pub fn quote_identifier(&self, name: &str) -> String {
+        match self {
+            SqlDialect::Postgres => format!("\"{}\"", name.replace('"', "\"\"")),
+            SqlDialect::MySql => format!("`{}`", name.replace('`', "``")),
+        }
+    }
+
+    pub fn quote_literal(&self, value: &str) -> String {
+        match self {
+            SqlDialect::Postgres => format!("'{}'", value.replace('\'', "''")),
+            // Backslashes are escapes in MySQL strings unless NO_BACKSLASH_ESCAPES is set
+            SqlDialect::MySql => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+        }
+    }
+
+    /// Expression turning a row of `t` into one JSON object, so each row is one output line
+    /// JSON escapes newlines and keeps numbers, booleans and NULL apart from strings
+    pub fn row_json(&self, columns: &[ColumnInfo]) -> String {
+        match self {
+            SqlDialect::Postgres => "row_to_json(t)".to_string(),
+            SqlDialect::MySql => format!(
+                "JSON_OBJECT({})",
+                columns
+                    .iter()
+                    .map(|column| format!(
+                        "{}, t.{}",
+                        self.quote_literal(&column.name),
+                        self.quote_identifier(&column.name)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// `schema.table` with both parts quoted
+    pub fn qualified_name(&self, schema: &str, table: &str) -> String {
+        format!(
+            "{}.{}",
+            self.quote_identifier(schema),
+            self.quote_identifier(table)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TableKind {
+    Table,
+    View,
+}
+
+/// Table or view of a SQL database
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableInfo {
+    pub schema: String,
+    pub name: String,
+    pub kind: TableKind,
+    // Estimate from the engine's statistics, `None` for views and tables never analyzed
+    pub row_count: Option<i64>,
+}
+
+/// Column of a table as the engine declares it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnInfo {
+    pub name: String,
+    // Engine type name, e.g. "integer", "character varying" or "varchar"
+    pub data_type: String,
+    pub nullable: bool,
+    pub primary_key: bool,
+}
+
+impl ColumnInfo {
+    /// Column of a catalog query row with `name`, `dataType`, `nullable` and `primaryKey`
+    pub fn from_json(row: &serde_json::Value) -> Self {
+        Self {
+            name: row["name"].as_str().unwrap_or_default().to_string(),
+            data_type: row["dataType"].as_str().unwrap_or_default().to_string(),
+            nullable: json_flag(&row["nullable"]),
+            primary_key: json_flag(&row["primaryKey"]),
+        }
+    }
+}
+
+/// Boolean printed by a catalog query, MySQL prints comparisons as 0 and 1
+pub fn json_flag(value: &serde_json::Value) -> bool {
+    value.as_bool().unwrap_or(value.as_i64() == Some(1))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterOperator {
+    Equals,
+    NotEquals,
+    GreaterThan,
+    LessThan,
+    // Case-insensitive substring match on the text form of the value
+    Contains,
+    IsNull,
+    IsNotNull,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableFilter {
+    pub column: String,
+    pub operator: FilterOperator,
+    // Ignored by IsNull and IsNotNull
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableSort {
+    pub column: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// Page of rows a table browser asks for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRowsQuery {
+    pub schema: String,
+    pub table: String,
+    #[serde(default)]
+    pub offset: u64,
+    pub limit: u32,
+    #[serde(default)]
+    pub sort: Option<TableSort>,
+    // Combined with AND
+    #[serde(default)]
+    pub filters: Vec<TableFilter>,
+}
+
+impl TableRowsQuery {
+    /// WHERE clause of the filters, empty without filters
+    /// Columns must be among `columns`, so no caller-provided name reaches the SQL unchecked
+    pub fn where_clause(
+        &self,
+        dialect: SqlDialect,
+        columns: &[ColumnInfo],
+    ) -> Result<String, AppError> {
+        let mut conditions = Vec::new();
+
+        for filter in &self.filters {
+            let column = dialect.quote_identifier(Self::known_column(columns, &filter.column)?);
+            let value = dialect.quote_literal(&filter.value);
+
+            conditions.push(match filter.operator {
+                FilterOperator::Equals => format!("{} = {}", column, value),
+                FilterOperator::NotEquals => format!("{} <> {}", column, value),
+                FilterOperator::GreaterThan => format!("{} > {}", column, value),
+                FilterOperator::LessThan => format!("{} < {}", column, value),
+                FilterOperator::Contains => {
+                    let pattern = dialect.quote_literal(&format!(
+                        "%{}%",
+                        filter
+                            .value
+                            .replace('\\', "\\\\")
+                            .replace('%', "\\%")
+                            .replace('_', "\\_")
+                    ));
+                    match dialect {
+                        SqlDialect::Postgres => {
+                            format!("CAST({} AS text) ILIKE {}", column, pattern)
+                        }
+                        // Case-insensitive with the default collations
+                        SqlDialect::MySql => format!("CAST({} AS CHAR) LIKE {}", column, pattern),
+                    }
+                }
+                FilterOperator::IsNull => format!("{} IS NULL", column),
+                FilterOperator::IsNotNull => format!("{} IS NOT NULL", column),
+            });
+        }
+
+        if conditions.is_empty() {
+            Ok(String::new())
+        } else {
+            Ok(format!(" WHERE {}", conditions.join(" AND ")))
+        }
+    }
+
+    /// SELECT of the requested page as one JSON object per row, sorted and filtered
+    pub fn select_sql(
+        &self,
+        dialect: SqlDialect,
+        columns: &[ColumnInfo],
+    ) -> Result<String, AppError> {
+        if self.limit == 0 || self.limit > MAX_PAGE_SIZE {
+            return Err(AppError::Validation(format!(
+                "Pages hold between 1 and {} rows",
+                MAX_PAGE_SIZE
+            )));
+        }
+
+        let order = match &self.sort {
+            Some(sort) => format!(
+                " ORDER BY {} {}",
+                dialect.quote_identifier(Self::known_column(columns, &sort.column)?),
+                if sort.descending { "DESC" } else { "ASC" }
+            ),
+            None => String::new(),
+        };
+
+        Ok(format!(
+            "SELECT {} FROM {} AS t{}{} LIMIT {} OFFSET {}",
+            dialect.row_json(columns),
+            dialect.qualified_name(&self.schema, &self.table),
+            self.where_clause(dialect, columns)?,
+            order,
+            self.limit,
+            self.offset
+        ))
+    }
+
+    /// COUNT of the rows matching the filters
+    pub fn count_sql(
+        &self,
+        dialect: SqlDialect,
+        columns: &[ColumnInfo],
+    ) -> Result<String, AppError> {
+        Ok(format!(
+            "SELECT count(*) FROM {}{}",
+            dialect.qualified_name(&self.schema, &self.table),
+            self.where_clause(dialect, columns)?
+        ))
+    }
+
+    fn known_column<'a>(columns: &'a [ColumnInfo], name: &str) -> Result<&'a str, AppError> {
+        columns
+            .iter()
+            .find(|column| column.name == name)
+            .map(|column| column.name.as_str())
+            .ok_or_else(|| AppError::Validation(format!("Unknown column '{}'", name)))
+    }
+}
+
+/// Rows of one page, each row holds its values in the order of `columns`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRowsPage {
+    pub columns: Vec<ColumnInfo>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    // Rows matching the filters across all pages
+    pub total: u64,
+    pub offset: u64,
+    pub limit: u32,
+}
@@ -0,0 +1,230 @@
+use crate::types::SqlDialect;
+use serde::{Deserialize, Serialize};
+
+// First keywords of statements that only read
+const READ_KEYWORDS: [&str; 8] = [
+    "SELECT", "WITH", "SHOW", "EXPLAIN", "DESCRIBE", "DESC", "VALUES", "TABLE",
+];
+// Keywords that make a read statement write, e.g. a data-modifying CTE or SELECT ... INTO
+// `REPLACE(` is the string function and is left alone
+const WRITE_KEYWORDS: [&str; 16] = [
+    "INSERT", "UPDATE", "DELETE", "MERGE", "REPLACE", "UPSERT", "INTO", "CREATE", "DROP", "ALTER",
+    "TRUNCATE", "GRANT", "REVOKE", "COPY", "CALL", "LOCK",
+];
+// Longest statement quoted in a guard error
+const MAX_QUOTED_STATEMENT: usize = 80;
+
+/// Whether the SQL console of a database refuses statements that change data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlGuardStatus {
+    // The guard setting is on
+    pub enforced: bool,
+    // Writes were allowed for this database until the app quits
+    pub unlocked: bool,
+}
+
+impl SqlGuardStatus {
+    /// Whether statements run in a read-only transaction
+    pub fn blocks_writes(&self) -> bool {
+        self.enforced && !self.unlocked
+    }
+}
+
+/// Output of a script run in the SQL console, the rows of its last statement returning any
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlConsoleResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub statements: usize,
+    // Ran in a read-only transaction
+    pub read_only: bool,
+    pub elapsed_ms: u64,
+}
+
+impl SqlConsoleResult {
+    /// Tab-separated client output, its first line holds the column names
+    pub fn parse_output(output: &str) -> (Vec<String>, Vec<Vec<String>>) {
+        let mut lines = output.lines().filter(|line| !line.trim().is_empty());
+        let Some(header) = lines.next() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let columns = header.split('\t').map(str::to_owned).collect();
+        let rows = lines
+            .map(|line| line.split('\t').map(str::to_owned).collect())
+            .collect();
+        (columns, rows)
+    }
+}
+
+/// Part of a statement seen by the guard
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    // Upper-cased keyword or unquoted identifier
+    Word(String),
+    OpenParen,
+    Semicolon,
+    // Anything else outside strings and comments
+    Other,
+}
+
+/// Keywords, parentheses and semicolons of a script, skipping strings, quoted identifiers
+/// and comments
+fn tokenize(sql: &str, dialect: SqlDialect) -> Vec<(Token, usize)> {
+    let chars: Vec<(usize, char)> = sql.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let (offset, c) = chars[index];
+        let next = chars.get(index + 1).map(|(_, c)| *c);
+
+        match c {
+            c if c.is_whitespace() => index += 1,
+            '-' if next == Some('-') => {
+                while index < chars.len() && chars[index].1 != '\n' {
+                    index += 1;
+                }
+            }
+            '#' if dialect == SqlDialect::MySql => {
+                while index < chars.len() && chars[index].1 != '\n' {
+                    index += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                index += 2;
+                while index < chars.len()
+                    && !(chars[index].1 == '*'
+                        && chars.get(index + 1).map(|(_, c)| *c) == Some('/'))
+                {
+                    index += 1;
+                }
+                index += 2;
+            }
+            '\'' | '"' | '`' => {
+                // MySQL strings take backslash escapes, a doubled quote is an escape everywhere
+                let escapes = dialect == SqlDialect::MySql && c != '`';
+                index += 1;
+                while index < chars.len() {
+                    let current = chars[index].1;
+                    if escapes && current == '\\' {
+                        index += 2;
+                    } else if current == c {
+                        if chars.get(index + 1).map(|(_, c)| *c) == Some(c) {
+                            index += 2;
+                        } else {
+                            break;
+                        }
+                    } else {
+                        index += 1;
+                    }
+                }
+                index += 1;
+                tokens.push((Token::Other, offset));
+            }
+            '$' if dialect == SqlDialect::Postgres => {
+                // Dollar-quoted body, `$tag$ ... $tag$`
+                let rest = &sql[offset + 1..];
+                let tag_len = rest
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                    .unwrap_or(rest.len());
+                if rest[tag_len..].starts_with('$') {
+                    let tag = &sql[offset..offset + tag_len + 2];
+                    let body_start = offset + tag.len();
+                    let end = sql[body_start..]
+                        .find(tag)
+                        .map(|position| body_start + position + tag.len())
+                        .unwrap_or(sql.len());
+                    while index < chars.len() && chars[index].0 < end {
+                        index += 1;
+                    }
+                } else {
+                    index += 1;
+                }
+                tokens.push((Token::Other, offset));
+            }
+            '(' => {
+                tokens.push((Token::OpenParen, offset));
+                index += 1;
+            }
+            ';' => {
+                tokens.push((Token::Semicolon, offset));
+                index += 1;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = index;
+                while index < chars.len()
+                    && (chars[index].1.is_alphanumeric() || matches!(chars[index].1, '_' | '$'))
+                {
+                    index += 1;
+                }
+                let word: String = chars[start..index].iter().map(|(_, c)| *c).collect();
+                tokens.push((Token::Word(word.to_uppercase()), offset));
+            }
+            _ => {
+                tokens.push((Token::Other, offset));
+                index += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Statements of a script with comments kept, empty ones are dropped
+pub fn split_statements(sql: &str, dialect: SqlDialect) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    for (token, offset) in tokenize(sql, dialect) {
+        if token == Token::Semicolon {
+            statements.push(sql[start..offset].to_string());
+            start = offset + 1;
+        }
+    }
+    statements.push(sql[start..].to_string());
+
+    statements
+        .into_iter()
+        .filter(|statement| !tokenize(statement, dialect).is_empty())
+        .map(|statement| statement.trim().to_string())
+        .collect()
+}
+
+/// Whether a single statement only reads
+/// Conservative: an unquoted column named like a write keyword is refused too
+pub fn is_read_only_statement(statement: &str, dialect: SqlDialect) -> bool {
+    let tokens = tokenize(statement, dialect);
+    let Some((Token::Word(first), _)) = tokens.iter().find(|(token, _)| *token != Token::OpenParen)
+    else {
+        return false;
+    };
+    if !READ_KEYWORDS.contains(&first.as_str()) {
+        return false;
+    }
+
+    !tokens.iter().enumerate().any(|(position, (token, _))| {
+        let Token::Word(word) = token else {
+            return false;
+        };
+        let function_call = matches!(tokens.get(position + 1), Some((Token::OpenParen, _)));
+        WRITE_KEYWORDS.contains(&word.as_str()) && !(word == "REPLACE" && function_call)
+    })
+}
+
+/// First statement of a script that would change data, shortened for an error message
+pub fn first_write_statement(sql: &str, dialect: SqlDialect) -> Option<String> {
+    split_statements(sql, dialect)
+        .into_iter()
+        .find(|statement| !is_read_only_statement(statement, dialect))
+        .map(|statement| {
+            let statement = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+            if statement.chars().count() > MAX_QUOTED_STATEMENT {
+                let shortened: String = statement.chars().take(MAX_QUOTED_STATEMENT).collect();
+                format!("{}...", shortened)
+            } else {
+                statement
+            }
+        })
+}
@@ -0,0 +1,157 @@
+use crate::types::AppError;
+use serde::{Deserialize, Serialize};
+
+// Runs kept per job, oldest are dropped first
+pub const MAX_SQL_JOB_RUNS: usize = 20;
+// Longest error kept with a run
+pub const SQL_JOB_ERROR_LIMIT: usize = 2000;
+pub const MAX_SQL_JOB_NAME_LEN: usize = 100;
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_notify_on_failure() -> bool {
+    true
+}
+
+/// One run of a SQL job, from the scheduler or started by hand
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlJobRun {
+    pub started_at: String,
+    pub finished_at: String,
+    pub success: bool,
+    // Statements of the script, 0 when it could not start
+    #[serde(default)]
+    pub statements: usize,
+    #[serde(default)]
+    pub error: Option<String>,
+    // Started from the job list rather than by the scheduler
+    #[serde(default)]
+    pub manual: bool,
+}
+
+/// SQL script run against one database on a cron schedule,
+/// e.g. refreshing a materialized view every night
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlJob {
+    // Empty when saving a new job
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub database_id: String,
+    pub sql: String,
+    // Five-field cron expression evaluated in local time, e.g. "0 3 * * *"
+    pub cron: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // Desktop notification when a run fails
+    #[serde(default = "default_notify_on_failure")]
+    pub notify_on_failure: bool,
+    #[serde(default)]
+    pub created_at: String,
+    // Last occurrence handled, whether it ran or was skipped as missed
+    #[serde(default)]
+    pub last_run_at: Option<String>,
+    // Error of the latest run, None once a run succeeds
+    #[serde(default)]
+    pub last_error: Option<String>,
+    // Latest runs, oldest first
+    #[serde(default)]
+    pub runs: Vec<SqlJobRun>,
+}
+
+impl SqlJob {
+    /// Problems with the values, checked before the job is saved
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_SQL_JOB_NAME_LEN {
+            violations.push(format!(
+                "Job names are 1 to {} characters long",
+                MAX_SQL_JOB_NAME_LEN
+            ));
+        }
+        if self.sql.trim().is_empty() {
+            violations.push("The job has no SQL to run".to_string());
+        }
+        if self.cron.trim().is_empty() {
+            violations.push("Choose when the job runs".to_string());
+        }
+
+        violations
+    }
+
+    /// Keep a run in the history, its error becomes the job's last error
+    pub fn record(&mut self, mut run: SqlJobRun) {
+        if let Some(error) = &mut run.error {
+            if error.chars().count() > SQL_JOB_ERROR_LIMIT {
+                *error = error.chars().take(SQL_JOB_ERROR_LIMIT).collect();
+            }
+        }
+        self.last_error = run.error.clone();
+        self.runs.push(run);
+        let excess = self.runs.len().saturating_sub(MAX_SQL_JOB_RUNS);
+        self.runs.drain(..excess);
+    }
+}
+
+/// Add a job or replace the definition of an existing one, a new job gets an id
+/// The schedule state and run history of an existing job are kept
+pub fn upsert_sql_job(
+    jobs: &mut Vec<SqlJob>,
+    mut job: SqlJob,
+    now: String,
+) -> Result<SqlJob, AppError> {
+    if let Some(violation) = job.violations().into_iter().next() {
+        return Err(AppError::Validation(violation));
+    }
+    job.name = job.name.trim().to_string();
+    job.cron = job.cron.trim().to_string();
+
+    let duplicate = jobs.iter().any(|other| {
+        other.id != job.id
+            && other.database_id == job.database_id
+            && other.name.eq_ignore_ascii_case(&job.name)
+    });
+    if duplicate {
+        return Err(AppError::Validation(format!(
+            "A job named '{}' already exists",
+            job.name
+        )));
+    }
+
+    if job.id.is_empty() {
+        job.id = uuid::Uuid::new_v4().to_string();
+        job.created_at = now;
+        job.last_run_at = None;
+        job.last_error = None;
+        job.runs = Vec::new();
+        jobs.push(job.clone());
+    } else {
+        let existing = jobs
+            .iter_mut()
+            .find(|other| other.id == job.id)
+            .ok_or_else(|| AppError::NotFound("SQL job".to_string()))?;
+        if existing.database_id != job.database_id {
+            return Err(AppError::Validation(
+                "A job cannot move to another container".to_string(),
+            ));
+        }
+        job.created_at = existing.created_at.clone();
+        job.last_error = existing.last_error.clone();
+        job.runs = std::mem::take(&mut existing.runs);
+        // A new schedule starts counting from now, occurrences of the old one are not caught up
+        job.last_run_at = if job.cron != existing.cron || (job.enabled && !existing.enabled) {
+            Some(now)
+        } else {
+            existing.last_run_at.clone()
+        };
+        *existing = job.clone();
+    }
+
+    Ok(job)
+}
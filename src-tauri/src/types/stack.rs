@@ -0,0 +1,93 @@
+use crate::types::{normalize_container_name, AppError, DockerRunRequest};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Service of a stack, with the request built by its provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackService {
+    // Short role in the stack, e.g. "postgres" or "cache"
+    pub service: String,
+    pub request: DockerRunRequest,
+}
+
+/// Linked databases created together as a group on a shared network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackRequest {
+    // Name of the group the services join
+    pub name: String,
+    // Created and started in this order
+    pub services: Vec<StackService>,
+}
+
+impl StackRequest {
+    /// Lowercase stack name usable in container and network names
+    fn slug(&self) -> String {
+        normalize_container_name(&self.name.to_lowercase(), &[])
+    }
+
+    /// Name of the network shared by the services
+    pub fn network_name(&self) -> String {
+        format!("{}-stack", self.slug())
+    }
+
+    /// Container name of a service, also its host name on the stack network
+    pub fn container_name(&self, service: &str) -> String {
+        format!("{}-{}", self.slug(), service)
+    }
+
+    /// Id of the Docker host the stack runs on, the local daemon when None
+    pub fn host(&self) -> Option<&str> {
+        self.services
+            .first()
+            .and_then(|service| service.request.metadata.host.as_deref())
+    }
+
+    /// Check the stack as a whole, each service is validated again when it is created
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.services.is_empty() {
+            return Err(AppError::Validation(
+                "A stack needs at least one service".to_string(),
+            ));
+        }
+
+        let mut services = HashSet::new();
+        let mut ports = HashSet::new();
+        for service in &self.services {
+            // The services share a network, which only exists on one host
+            if service.request.metadata.host.as_deref() != self.host() {
+                return Err(AppError::Validation(
+                    "The services of a stack run on the same Docker host".to_string(),
+                ));
+            }
+            if service.service.trim().is_empty() {
+                return Err(AppError::Validation(
+                    "Every service of a stack needs a name".to_string(),
+                ));
+            }
+            if !services.insert(service.service.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "Service '{}' appears more than once in the stack",
+                    service.service
+                )));
+            }
+            let host_ports = std::iter::once(service.request.metadata.port).chain(
+                service
+                    .request
+                    .docker_args
+                    .ports
+                    .iter()
+                    .map(|mapping| mapping.host),
+            );
+            for port in host_ports.collect::<HashSet<_>>() {
+                if !ports.insert(port) {
+                    return Err(AppError::Validation(format!(
+                        "Port {} is used by more than one service of the stack",
+                        port
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+/// Argument passed by the OS login item, starts the app without a window
+pub const HEADLESS_ARG: &str = "--headless";
+
+/// How the app was launched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupMode {
+    // Launched by the user, the main window is shown
+    Window,
+    // Launched at login, only auto-start containers are brought up
+    Headless,
+}
+
+impl StartupMode {
+    pub fn from_args<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        if args.into_iter().any(|arg| arg.as_ref() == HEADLESS_ARG) {
+            StartupMode::Headless
+        } else {
+            StartupMode::Window
+        }
+    }
+}
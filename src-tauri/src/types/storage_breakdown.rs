@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+// How long a breakdown is reused before the catalogs are read again
+pub const STORAGE_BREAKDOWN_TTL_SECS: u64 = 300;
+// Redis keys measured per logical database, MEMORY USAGE blocks the server while it runs
+pub const REDIS_SIZE_SCAN_LIMIT: u64 = 10_000;
+
+/// Size of a table, a MongoDB collection or, for Redis, the keys sharing a prefix
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableSize {
+    // PostgreSQL schema
+    #[serde(default)]
+    pub schema: Option<String>,
+    pub name: String,
+    // Data and indexes
+    pub size_bytes: u64,
+    // Estimated rows, documents or keys
+    #[serde(default)]
+    pub row_count: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseSize {
+    pub name: String,
+    pub size_bytes: u64,
+    // Largest first
+    #[serde(default)]
+    pub tables: Vec<TableSize>,
+    // Only the first `REDIS_SIZE_SCAN_LIMIT` keys were measured
+    #[serde(default)]
+    pub partial: bool,
+}
+
+/// Sizes of every database of a container, read from the engine's catalogs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageBreakdown {
+    // Sum of the databases, or the server's memory use for Redis
+    pub total_bytes: u64,
+    pub databases: Vec<DatabaseSize>,
+    pub collected_at: String,
+}
+
+impl StorageBreakdown {
+    /// Breakdown of `databases` with tables sorted largest first
+    /// `total_bytes` defaults to the sum of the databases
+    pub fn new(mut databases: Vec<DatabaseSize>, total_bytes: Option<u64>) -> Self {
+        for database in &mut databases {
+            database
+                .tables
+                .sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.name.cmp(&b.name)));
+        }
+        databases.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            total_bytes: total_bytes
+                .unwrap_or_else(|| databases.iter().map(|database| database.size_bytes).sum()),
+            databases,
+            collected_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
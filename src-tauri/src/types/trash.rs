@@ -0,0 +1,11 @@
+use crate::types::DatabaseContainer;
+use serde::{Deserialize, Serialize};
+
+/// Removed container kept restorable until the retention window ends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedDatabase {
+    pub database: DatabaseContainer,
+    pub deleted_at: String,
+    // Snapshot of the data volume taken right before removal
+    pub final_snapshot_id: Option<String>,
+}
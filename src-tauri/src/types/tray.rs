@@ -0,0 +1,42 @@
+/// Container listed in the tray menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrayEntry {
+    pub id: String,
+    pub name: String,
+    pub running: bool,
+}
+
+/// Action behind a tray menu item, encoded in the item id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayAction {
+    ShowWindow,
+    Start(String),
+    Stop(String),
+    StopAll,
+    Quit,
+}
+
+impl TrayAction {
+    pub fn menu_id(&self) -> String {
+        match self {
+            TrayAction::ShowWindow => "show".to_string(),
+            TrayAction::Start(id) => format!("start:{}", id),
+            TrayAction::Stop(id) => format!("stop:{}", id),
+            TrayAction::StopAll => "stop-all".to_string(),
+            TrayAction::Quit => "quit".to_string(),
+        }
+    }
+
+    pub fn from_menu_id(menu_id: &str) -> Option<Self> {
+        match menu_id {
+            "show" => Some(TrayAction::ShowWindow),
+            "stop-all" => Some(TrayAction::StopAll),
+            "quit" => Some(TrayAction::Quit),
+            _ => match menu_id.split_once(':') {
+                Some(("start", id)) => Some(TrayAction::Start(id.to_string())),
+                Some(("stop", id)) => Some(TrayAction::Stop(id.to_string())),
+                _ => None,
+            },
+        }
+    }
+}
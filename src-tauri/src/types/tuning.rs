@@ -0,0 +1,231 @@
+use crate::types::{AppError, DockerRunArgs};
+use serde::{Deserialize, Serialize};
+
+// Memory left to a database whatever the host, engines start poorly below it
+const MIN_BUDGET_MB: u64 = 256;
+// PostgreSQL settings a tuning writes, earlier values are replaced on every apply
+const POSTGRES_TUNED_SETTINGS: [&str; 5] = [
+    "shared_buffers",
+    "effective_cache_size",
+    "maintenance_work_mem",
+    "work_mem",
+    "max_parallel_workers_per_gather",
+];
+// Flags taking a separate value, of Redis and MongoDB
+const FLAG_TUNED_SETTINGS: [&str; 2] = ["--maxmemory", "--wiredTigerCacheSizeGB"];
+// MySQL and MariaDB options written as `--name=value`
+const MYSQL_TUNED_SETTINGS: [&str; 3] = [
+    "--innodb-buffer-pool-size",
+    "--tmp-table-size",
+    "--max-heap-table-size",
+];
+
+/// Share of the Docker host a database is tuned to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TuningPreset {
+    // A tenth of the memory, one core, for a database next to many others
+    Small,
+    // A quarter of the memory and half the cores
+    Medium,
+    // Half of the memory and every core, for a database under load tests
+    Large,
+}
+
+impl TuningPreset {
+    fn memory_share(&self) -> (u64, u64) {
+        match self {
+            TuningPreset::Small => (1, 10),
+            TuningPreset::Medium => (1, 4),
+            TuningPreset::Large => (1, 2),
+        }
+    }
+
+    fn cpus(&self, host_cpus: u32) -> u32 {
+        let cpus = match self {
+            TuningPreset::Small => 1,
+            TuningPreset::Medium => host_cpus / 2,
+            TuningPreset::Large => host_cpus,
+        };
+        cpus.max(1)
+    }
+}
+
+/// Memory and cores of the Docker host, the VM on Docker Desktop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostResources {
+    pub memory_mb: u64,
+    pub cpus: u32,
+}
+
+impl HostResources {
+    /// Output of `docker info --format '{{.MemTotal}} {{.NCPU}}'`, memory in bytes
+    pub fn parse(output: &str) -> Result<Self, AppError> {
+        let mut fields = output.split_whitespace();
+        let memory_bytes = fields.next().and_then(|value| value.parse::<u64>().ok());
+        let cpus = fields.next().and_then(|value| value.parse::<u32>().ok());
+        match (memory_bytes, cpus) {
+            (Some(memory_bytes), Some(cpus)) if memory_bytes > 0 && cpus > 0 => Ok(Self {
+                memory_mb: memory_bytes / (1024 * 1024),
+                cpus,
+            }),
+            _ => Err(AppError::Internal(format!(
+                "Unexpected resources reported by Docker: '{}'",
+                output.trim()
+            ))),
+        }
+    }
+}
+
+/// Engine setting derived from a tuning, as shown before it is applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TunedSetting {
+    pub name: String,
+    pub value: String,
+}
+
+/// Preset a database was tuned with and the host it was measured on
+/// Kept so a recreation gives the same settings, re-tuning measures the host again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TuningSettings {
+    pub preset: TuningPreset,
+    pub host: HostResources,
+}
+
+impl TuningSettings {
+    /// Memory the database is given, in MB
+    pub fn budget_mb(&self) -> u64 {
+        let (numerator, denominator) = self.preset.memory_share();
+        (self.host.memory_mb * numerator / denominator).max(MIN_BUDGET_MB)
+    }
+
+    /// Settings of the engine, empty for engines without memory settings
+    pub fn settings(&self, db_type: &str) -> Vec<TunedSetting> {
+        let budget = self.budget_mb();
+        let cpus = self.preset.cpus(self.host.cpus);
+        let setting = |name: &str, value: String| TunedSetting {
+            name: name.to_string(),
+            value,
+        };
+
+        match db_type {
+            // A quarter for shared buffers, the rest is expected to serve as OS cache
+            "PostgreSQL" => vec![
+                setting("shared_buffers", format!("{}MB", budget / 4)),
+                setting("effective_cache_size", format!("{}MB", budget * 3 / 4)),
+                setting(
+                    "maintenance_work_mem",
+                    format!("{}MB", (budget / 16).clamp(16, 2048)),
+                ),
+                setting("work_mem", format!("{}MB", (budget / 64).clamp(4, 256))),
+                setting(
+                    "max_parallel_workers_per_gather",
+                    (cpus / 2).max(1).to_string(),
+                ),
+            ],
+            "MySQL" | "MariaDB" => {
+                let temp_tables = format!("{}M", (budget / 32).clamp(16, 256));
+                vec![
+                    setting(
+                        "innodb_buffer_pool_size",
+                        format!("{}M", (budget * 3 / 4).max(128)),
+                    ),
+                    setting("tmp_table_size", temp_tables.clone()),
+                    setting("max_heap_table_size", temp_tables),
+                ]
+            }
+            // The eviction policy stays the one picked for the database
+            "Redis" => vec![setting("maxmemory", format!("{}mb", budget * 3 / 4))],
+            // The WiredTiger cache takes half, like mongod's own default of the machine
+            "MongoDB" => vec![setting(
+                "wiredTigerCacheSizeGB",
+                format!("{:.2}", (budget as f64 / 2.0 / 1024.0).max(0.25)),
+            )],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Server arguments of the settings, in the engine's command-line syntax
+    pub fn command_args(&self, db_type: &str) -> Vec<String> {
+        self.settings(db_type)
+            .into_iter()
+            .flat_map(|setting| match db_type {
+                "PostgreSQL" => vec![
+                    "-c".to_string(),
+                    format!("{}={}", setting.name, setting.value),
+                ],
+                "MySQL" | "MariaDB" => {
+                    vec![format!(
+                        "--{}={}",
+                        setting.name.replace('_', "-"),
+                        setting.value
+                    )]
+                }
+                _ => vec![format!("--{}", setting.name), setting.value],
+            })
+            .collect()
+    }
+
+    /// Shared memory of the container in MB, PostgreSQL sizes parallel query buffers from it
+    /// Docker's 64 MB fails queries once the buffers grow with the budget
+    pub fn shm_size_mb(&self, db_type: &str) -> Option<u64> {
+        (db_type == "PostgreSQL").then(|| (self.budget_mb() / 4).max(64))
+    }
+
+    /// Add the tuned arguments to the command, replacing the same settings set earlier
+    /// A preset wins over the provider's memory settings, extra arguments come after it
+    pub fn apply(&self, db_type: &str, docker_args: &mut DockerRunArgs) {
+        remove_tuned_args(&mut docker_args.command);
+        docker_args.command.extend(self.command_args(db_type));
+        docker_args.shm_size = self.shm_size_mb(db_type).map(|mb| format!("{}m", mb));
+    }
+}
+
+/// Drop the arguments setting what a tuning sets, of any engine
+fn remove_tuned_args(command: &mut Vec<String>) {
+    let mut index = 0;
+    while index < command.len() {
+        let arg = &command[index];
+        let next = command.get(index + 1);
+        let pair = (arg == "-c"
+            && next.is_some_and(|next| {
+                POSTGRES_TUNED_SETTINGS
+                    .iter()
+                    .any(|setting| next.starts_with(&format!("{}=", setting)))
+            }))
+            || (FLAG_TUNED_SETTINGS.contains(&arg.as_str()) && next.is_some());
+        let single = MYSQL_TUNED_SETTINGS
+            .iter()
+            .any(|setting| arg.starts_with(&format!("{}=", setting)));
+
+        if pair {
+            command.drain(index..index + 2);
+        } else if single {
+            command.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+}
+
+/// Settings a preset gives a database on the current host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TuningPreview {
+    pub tuning: TuningSettings,
+    pub budget_mb: u64,
+    pub settings: Vec<TunedSetting>,
+}
+
+impl TuningPreview {
+    pub fn new(db_type: &str, tuning: TuningSettings) -> Self {
+        Self {
+            tuning,
+            budget_mb: tuning.budget_mb(),
+            settings: tuning.settings(db_type),
+        }
+    }
+}
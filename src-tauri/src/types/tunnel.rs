@@ -0,0 +1,161 @@
+use crate::types::AppError;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Longest wait between two reconnect attempts
+pub const MAX_TUNNEL_RETRY_SECS: u64 = 60;
+// How often the local end of a connecting tunnel is tried, ssh opens it once the forward is up
+pub const TUNNEL_PROBE_INTERVAL_MS: u64 = 500;
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+fn default_remote_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+/// SSH local forward from this machine to a database on a remote Docker host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TunnelDefinition {
+    pub id: String,
+    // Managed database the tunnel reaches
+    pub database_id: String,
+    // `host` or `user@host`, aliases from ~/.ssh/config work too
+    pub ssh_host: String,
+    #[serde(default = "default_ssh_port")]
+    pub ssh_port: u16,
+    #[serde(default)]
+    pub identity_file: Option<String>,
+    // Port on 127.0.0.1 clients connect to
+    pub local_port: u16,
+    // Address the remote host reaches the database on
+    #[serde(default = "default_remote_host")]
+    pub remote_host: String,
+    pub remote_port: u16,
+    // Opened when the app launches
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+impl TunnelDefinition {
+    pub fn validate(&self) -> Result<(), AppError> {
+        for (label, host) in [
+            ("SSH host", &self.ssh_host),
+            ("Remote host", &self.remote_host),
+        ] {
+            if host.trim().is_empty() {
+                return Err(AppError::Validation(format!("{} is required", label)));
+            }
+            // ssh would read a leading dash as an option
+            if host.starts_with('-') || host.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                return Err(AppError::Validation(format!(
+                    "{} '{}' is not a valid host",
+                    label, host
+                )));
+            }
+        }
+
+        if [self.ssh_port, self.local_port, self.remote_port].contains(&0) {
+            return Err(AppError::Validation(
+                "Tunnel ports must be between 1 and 65535".to_string(),
+            ));
+        }
+
+        if let Some(identity_file) = &self.identity_file {
+            if identity_file.trim().is_empty() || identity_file.starts_with('-') {
+                return Err(AppError::Validation(format!(
+                    "Identity file '{}' is not a valid path",
+                    identity_file
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `ssh` arguments holding the forward open without running a remote command
+    /// Batch mode fails instead of prompting, keys must come from the agent or the identity file
+    pub fn ssh_args(&self) -> Vec<String> {
+        let remote_host = if self.remote_host.contains(':') {
+            format!("[{}]", self.remote_host)
+        } else {
+            self.remote_host.clone()
+        };
+
+        let mut args: Vec<String> = [
+            "-N",
+            "-o",
+            "ExitOnForwardFailure=yes",
+            "-o",
+            "ServerAliveInterval=15",
+            "-o",
+            "ServerAliveCountMax=3",
+            "-o",
+            "BatchMode=yes",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+        args.extend([
+            "-p".to_string(),
+            self.ssh_port.to_string(),
+            "-L".to_string(),
+            format!(
+                "127.0.0.1:{}:{}:{}",
+                self.local_port, remote_host, self.remote_port
+            ),
+        ]);
+        if let Some(identity_file) = &self.identity_file {
+            args.extend(["-i".to_string(), identity_file.clone()]);
+        }
+        args.push(self.ssh_host.clone());
+        args
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TunnelState {
+    Stopped,
+    Connecting,
+    Connected,
+    // ssh exited, waiting before the next attempt
+    Reconnecting,
+}
+
+/// Live state of a tunnel, shown next to its database
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TunnelStatus {
+    pub tunnel_id: String,
+    pub database_id: String,
+    pub state: TunnelState,
+    pub local_port: u16,
+    // Failed attempts since the tunnel was last connected
+    pub attempts: u32,
+    // Last line ssh printed before exiting, e.g. a refused key
+    pub last_error: Option<String>,
+    pub connected_at: Option<String>,
+}
+
+impl TunnelStatus {
+    pub fn new(tunnel: &TunnelDefinition, state: TunnelState) -> Self {
+        Self {
+            tunnel_id: tunnel.id.clone(),
+            database_id: tunnel.database_id.clone(),
+            state,
+            local_port: tunnel.local_port,
+            attempts: 0,
+            last_error: None,
+            connected_at: None,
+        }
+    }
+}
+
+/// Wait before reconnect attempt `attempt`, doubling from 2 seconds up to the maximum
+pub fn tunnel_retry_delay(attempt: u32) -> Duration {
+    let secs = 2u64.saturating_pow(attempt.clamp(1, 6));
+    Duration::from_secs(secs.min(MAX_TUNNEL_RETRY_SECS))
+}
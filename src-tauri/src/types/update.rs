@@ -0,0 +1,184 @@
+use crate::types::{AppError, DatabaseContainer, DatabaseCredentials, DockerRunRequest};
+use serde::Serialize;
+
+/// Setting of a database an update can change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdateField {
+    Name,
+    Port,
+    PersistData,
+    EnableTls,
+    ExposeToLan,
+    // Username, password or initial database
+    Credentials,
+    Version,
+    MaxConnections,
+    // Server arguments appended to the provider's command
+    ExtraArgs,
+    // Timezone or system locale
+    Region,
+    // Memory settings preset, or the host it was sized for
+    Tuning,
+}
+
+/// How a change reaches the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdateEffect {
+    // The container is removed and created again
+    Recreate,
+    // Applied to the running engine, the container is kept
+    Live,
+    // Saved only, used the next time the container is recreated
+    Stored,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChange {
+    pub field: UpdateField,
+    pub effect: UpdateEffect,
+}
+
+/// What applying an update request to a database does
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePlan {
+    pub changes: Vec<UpdateChange>,
+    pub needs_recreation: bool,
+    // Data is lost: the container is recreated without a volume, or its volume is removed
+    pub data_loss: bool,
+    // Credentials change in the running engine, which has to be started first
+    pub requires_running: bool,
+}
+
+impl UpdatePlan {
+    pub fn new(current: &DatabaseContainer, request: &DockerRunRequest) -> Self {
+        let metadata = &request.metadata;
+
+        let current_credentials = DatabaseCredentials::of(current);
+        let requested_credentials = DatabaseCredentials::requested(metadata, &current_credentials);
+        let credentials_changed = requested_credentials != current_credentials;
+        // Engines reading credentials at every start only need a new container
+        let credentials_on_start =
+            credentials_changed && DatabaseCredentials::applies_on_start(&current.db_type);
+
+        let recreating = [
+            (UpdateField::Name, request.name != current.name),
+            (UpdateField::Port, metadata.port != current.port),
+            (
+                UpdateField::PersistData,
+                metadata.persist_data != current.stored_persist_data,
+            ),
+            (
+                UpdateField::EnableTls,
+                metadata.enable_tls != current.stored_enable_tls,
+            ),
+            (
+                UpdateField::ExposeToLan,
+                metadata.expose_to_lan != current.stored_expose_to_lan,
+            ),
+            (
+                UpdateField::ExtraArgs,
+                metadata.extra_args != current.extra_args,
+            ),
+            (
+                UpdateField::Region,
+                metadata.region.clone().unwrap_or_default()
+                    != current.region.clone().unwrap_or_default(),
+            ),
+            (UpdateField::Tuning, metadata.tuning != current.tuning),
+        ];
+        let needs_recreation =
+            recreating.iter().any(|(_, changed)| *changed) || credentials_on_start;
+        let data_survives =
+            !needs_recreation || (current.stored_persist_data && metadata.persist_data);
+
+        let mut changes: Vec<UpdateChange> = recreating
+            .iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(field, _)| UpdateChange {
+                field: *field,
+                effect: UpdateEffect::Recreate,
+            })
+            .collect();
+
+        // Init variables are ignored once the data directory exists, the engine is told directly
+        if credentials_changed {
+            let effect = if credentials_on_start || !data_survives {
+                UpdateEffect::Recreate
+            } else {
+                UpdateEffect::Live
+            };
+            changes.push(UpdateChange {
+                field: UpdateField::Credentials,
+                effect,
+            });
+        }
+
+        // Image and server arguments only change with a new container
+        let deferred = if needs_recreation {
+            UpdateEffect::Recreate
+        } else {
+            UpdateEffect::Stored
+        };
+        if metadata.version != current.version {
+            changes.push(UpdateChange {
+                field: UpdateField::Version,
+                effect: deferred,
+            });
+        }
+        if metadata
+            .max_connections
+            .is_some_and(|max| max != current.max_connections)
+        {
+            changes.push(UpdateChange {
+                field: UpdateField::MaxConnections,
+                effect: deferred,
+            });
+        }
+
+        let requires_running = !current.is_running()
+            && changes.iter().any(|change| {
+                change.field == UpdateField::Credentials && change.effect == UpdateEffect::Live
+            });
+
+        Self {
+            changes,
+            needs_recreation,
+            data_loss: (needs_recreation && !current.stored_persist_data)
+                || (current.stored_persist_data && !metadata.persist_data),
+            requires_running,
+        }
+    }
+
+    /// How `field` changes, None when the request keeps it
+    pub fn effect(&self, field: UpdateField) -> Option<UpdateEffect> {
+        self.changes
+            .iter()
+            .find(|change| change.field == field)
+            .map(|change| change.effect)
+    }
+
+    pub fn changes(&self, field: UpdateField) -> bool {
+        self.effect(field).is_some()
+    }
+
+    /// Error that stops an update losing data until it is confirmed, None when the data is kept
+    pub fn data_loss_error(&self, current: &DatabaseContainer) -> Option<AppError> {
+        if !self.data_loss {
+            return None;
+        }
+
+        let reason = if current.stored_persist_data {
+            "turning off persistence removes its data volume"
+        } else {
+            "the container is recreated and its data is not persisted"
+        };
+        Some(AppError::DataLoss {
+            name: current.name.clone(),
+            reason: reason.to_string(),
+        })
+    }
+}
@@ -0,0 +1,236 @@
+use crate::types::{DatabaseContainer, DockerContainerState, TunnelStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Starts and exits kept per container, oldest are dropped first
+pub const MAX_LIFECYCLE_EVENTS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LifecycleEventKind {
+    Started,
+    // Started again by the restart policy
+    Restarted,
+    Exited,
+}
+
+/// Start or exit of a container seen across syncs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleEvent {
+    pub kind: LifecycleEventKind,
+    // RFC 3339 as reported by Docker
+    pub at: String,
+    // Only known for exits seen before the container started again
+    pub exit_code: Option<i32>,
+    #[serde(default)]
+    pub oom_killed: bool,
+    pub restart_count: u32,
+    // Readable cause of an exit, or the restarts a restart stands for
+    pub reason: Option<String>,
+}
+
+/// Readable cause of an exit, None when the exit code is unknown
+pub fn exit_reason(exit_code: Option<i32>, oom_killed: bool) -> Option<String> {
+    if oom_killed {
+        return Some("Killed for running out of memory".to_string());
+    }
+    let reason = match exit_code? {
+        0 => "Stopped cleanly".to_string(),
+        137 => "Killed (SIGKILL)".to_string(),
+        143 => "Terminated (SIGTERM)".to_string(),
+        139 => "Crashed with a segmentation fault".to_string(),
+        134 => "Aborted (SIGABRT)".to_string(),
+        code => format!("Exited with code {}", code),
+    };
+    Some(reason)
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Fields of a container's Docker state that change when it starts or exits
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UptimeSnapshot {
+    pub running: bool,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub restart_count: u32,
+}
+
+impl UptimeSnapshot {
+    pub fn of(state: &DockerContainerState) -> Self {
+        Self {
+            running: state.running,
+            started_at: state.started_at.clone(),
+            finished_at: state.finished_at.clone(),
+            restart_count: state.restart_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerUptime {
+    // State at the last sync, compared with the next one
+    pub last_seen: Option<UptimeSnapshot>,
+    // Oldest first
+    pub events: Vec<LifecycleEvent>,
+}
+
+/// Starts and exits of every container, persisted so they survive app restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UptimeHistory {
+    // Per database id
+    pub containers: HashMap<String, ContainerUptime>,
+}
+
+impl UptimeHistory {
+    /// Record the starts and exits between the last state of a database and `state`
+    /// The first sighting only records the state
+    pub fn observe(&mut self, id: &str, state: &DockerContainerState) {
+        let uptime = self.containers.entry(id.to_string()).or_default();
+        let snapshot = UptimeSnapshot::of(state);
+        let Some(previous) = uptime.last_seen.replace(snapshot.clone()) else {
+            return;
+        };
+
+        if let Some(finished_at) = snapshot
+            .finished_at
+            .as_ref()
+            .filter(|finished_at| previous.finished_at.as_ref() != Some(*finished_at))
+        {
+            // Docker clears the exit code and OOM flag when the container starts again
+            let stopped = !state.running || state.restarting;
+            let exit_code = state.exit_code.filter(|_| stopped);
+            let oom_killed = stopped && state.oom_killed;
+            uptime.events.push(LifecycleEvent {
+                kind: LifecycleEventKind::Exited,
+                at: finished_at.clone(),
+                exit_code,
+                oom_killed,
+                restart_count: previous.restart_count,
+                reason: exit_reason(exit_code, oom_killed),
+            });
+        }
+
+        if let Some(started_at) = snapshot
+            .started_at
+            .as_ref()
+            .filter(|started_at| previous.started_at.as_ref() != Some(*started_at))
+        {
+            let restarts = snapshot
+                .restart_count
+                .saturating_sub(previous.restart_count);
+            let kind = if restarts > 0 {
+                LifecycleEventKind::Restarted
+            } else {
+                LifecycleEventKind::Started
+            };
+            // Docker only keeps the last start, earlier restarts since the last sync are counted
+            uptime.events.push(LifecycleEvent {
+                kind,
+                at: started_at.clone(),
+                exit_code: None,
+                oom_killed: false,
+                restart_count: snapshot.restart_count,
+                reason: (restarts > 1)
+                    .then(|| format!("Restarted {} times since the previous check", restarts)),
+            });
+        }
+
+        uptime.events.sort_by_key(|event| parse_time(&event.at));
+        let excess = uptime.events.len().saturating_sub(MAX_LIFECYCLE_EVENTS);
+        uptime.events.drain(..excess);
+    }
+
+    /// Drop the history of databases that are no longer managed
+    pub fn retain_databases(&mut self, databases: &HashMap<String, DatabaseContainer>) {
+        self.containers.retain(|id, _| databases.contains_key(id));
+    }
+}
+
+/// Uptime, restarts and recent starts and exits of a container
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UptimeReport {
+    pub running: bool,
+    pub started_at: Option<String>,
+    // Seconds since the last start while running
+    pub uptime_secs: Option<i64>,
+    pub restart_count: u32,
+    pub last_exit: Option<LifecycleEvent>,
+    // Newest first
+    pub events: Vec<LifecycleEvent>,
+}
+
+impl UptimeReport {
+    /// Report from the live Docker state, None when the container does not exist, and the
+    /// recorded history
+    pub fn new(
+        state: Option<&DockerContainerState>,
+        uptime: Option<&ContainerUptime>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let events: Vec<LifecycleEvent> = uptime
+            .map(|uptime| uptime.events.iter().rev().cloned().collect())
+            .unwrap_or_default();
+        let recorded_exit = events
+            .iter()
+            .find(|event| event.kind == LifecycleEventKind::Exited)
+            .cloned();
+
+        let Some(state) = state else {
+            return Self {
+                last_exit: recorded_exit,
+                events,
+                ..Default::default()
+            };
+        };
+
+        // A stopped container still reports why it exited
+        let live_exit = state
+            .finished_at
+            .clone()
+            .filter(|_| !state.running || state.restarting)
+            .map(|finished_at| LifecycleEvent {
+                kind: LifecycleEventKind::Exited,
+                at: finished_at,
+                exit_code: state.exit_code,
+                oom_killed: state.oom_killed,
+                restart_count: state.restart_count,
+                reason: exit_reason(state.exit_code, state.oom_killed),
+            });
+
+        Self {
+            running: state.running,
+            started_at: state.started_at.clone(),
+            uptime_secs: state
+                .started_at
+                .as_deref()
+                .and_then(parse_time)
+                .filter(|_| state.running && !state.restarting)
+                .map(|started_at| (now - started_at).num_seconds().max(0)),
+            restart_count: state.restart_count,
+            last_exit: live_exit.or(recorded_exit),
+            events,
+        }
+    }
+}
+
+/// Stored record of a database with its uptime and tunnels, returned by the details command
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerDetails {
+    pub database: DatabaseContainer,
+    pub uptime: UptimeReport,
+    // Set while the ports are published to the local network
+    pub lan_warning: Option<String>,
+    // SSH tunnels reaching the database
+    pub tunnels: Vec<TunnelStatus>,
+}
@@ -0,0 +1,218 @@
+use crate::types::{AppError, DatabaseContainer, DockerRunRequest, SqlDialect};
+use serde::Serialize;
+
+// Ports below this need root to bind, rootless Docker and Podman may refuse them
+pub const FIRST_UNPRIVILEGED_PORT: i32 = 1024;
+// Longest a stop may wait for the engine to flush before it is killed
+pub const MAX_STOP_TIMEOUT_SECS: u32 = 3600;
+
+/// One problem found in a create or update request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldViolation {
+    // Request field the problem is about, e.g. "port" or "password"
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldViolation {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Messages of all violations as a single sentence list
+pub fn describe_violations(violations: &[FieldViolation]) -> String {
+    violations
+        .iter()
+        .map(|violation| violation.message.as_str())
+        .collect::<Vec<_>>()
+        .join(". ")
+}
+
+/// Check a create or update request, `others` are the managed databases it must not clash with
+/// Every violation is collected so the form can flag all of them at once
+pub fn validate_run_request(
+    request: &DockerRunRequest,
+    others: &[&DatabaseContainer],
+) -> Result<(), AppError> {
+    let mut violations = Vec::new();
+    let metadata = &request.metadata;
+
+    let mut host_ports = vec![metadata.port];
+    host_ports.extend(
+        request
+            .docker_args
+            .ports
+            .iter()
+            .map(|mapping| mapping.host)
+            .filter(|port| *port != metadata.port),
+    );
+
+    for (index, port) in host_ports.iter().enumerate() {
+        let field = if index == 0 { "port" } else { "ports" };
+
+        if !(1..=65535).contains(port) {
+            violations.push(FieldViolation::new(
+                field,
+                format!("Port {} is outside the range 1-65535", port),
+            ));
+            continue;
+        }
+        if host_ports[..index].contains(port) {
+            violations.push(FieldViolation::new(
+                field,
+                format!("Port {} is mapped more than once", port),
+            ));
+        }
+        if let Some(other) = others
+            .iter()
+            .find(|other| database_host_ports(other).contains(port))
+        {
+            violations.push(FieldViolation::new(
+                field,
+                format!("Port {} is already used by {}", port, other.name),
+            ));
+        }
+    }
+
+    if metadata.enable_auth && metadata.password.trim().is_empty() {
+        violations.push(FieldViolation::new(
+            "password",
+            "A password is required when authentication is enabled",
+        ));
+    }
+
+    if let Some(message) = version_violation(&metadata.version) {
+        violations.push(FieldViolation::new("version", message));
+    }
+
+    if !metadata.post_create_sql.is_empty() && SqlDialect::of(&metadata.db_type).is_err() {
+        violations.push(FieldViolation::new(
+            "postCreateSql",
+            format!("{} does not run post-create SQL", metadata.db_type),
+        ));
+    }
+
+    if !metadata.extra_args.is_empty() && !takes_extra_args(&metadata.db_type) {
+        violations.push(FieldViolation::new(
+            "extraArgs",
+            format!("{} does not take extra server arguments", metadata.db_type),
+        ));
+    }
+    if metadata.extra_args.iter().any(|arg| arg.trim().is_empty()) {
+        violations.push(FieldViolation::new(
+            "extraArgs",
+            "Extra server arguments cannot be empty",
+        ));
+    }
+
+    if let Some(tuning) = &metadata.tuning {
+        if tuning.settings(&metadata.db_type).is_empty() {
+            violations.push(FieldViolation::new(
+                "tuning",
+                format!("{} has no tuning presets", metadata.db_type),
+            ));
+        }
+    }
+
+    if let Some(region) = &metadata.region {
+        for message in region.violations_for(&metadata.db_type, &metadata.version) {
+            violations.push(FieldViolation::new("region", message));
+        }
+    }
+
+    if let Some(locale) = &metadata.locale {
+        for message in locale.violations(&metadata.db_type, &metadata.version) {
+            violations.push(FieldViolation::new("locale", message));
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::InvalidRequest { violations })
+    }
+}
+
+/// Problems that do not stop a request but are worth telling, e.g. a privileged port
+/// Rootful Docker binds them fine, rootless Docker and Podman fail to start the container
+pub fn run_request_warnings(request: &DockerRunRequest) -> Vec<String> {
+    let mut host_ports = vec![request.metadata.port];
+    for mapping in &request.docker_args.ports {
+        if !host_ports.contains(&mapping.host) {
+            host_ports.push(mapping.host);
+        }
+    }
+
+    host_ports
+        .into_iter()
+        .filter(|port| (1..FIRST_UNPRIVILEGED_PORT).contains(port))
+        .map(|port| {
+            format!(
+                "Port {} is privileged, rootless Docker and Podman need a port from {} up",
+                port, FIRST_UNPRIVILEGED_PORT
+            )
+        })
+        .collect()
+}
+
+/// Reject a stop timeout Docker would not take or that would hang a stop for too long
+pub fn validate_stop_timeout(stop_timeout: Option<u32>) -> Result<(), AppError> {
+    match stop_timeout {
+        Some(seconds) if !(1..=MAX_STOP_TIMEOUT_SECS).contains(&seconds) => {
+            Err(AppError::Validation(format!(
+                "The stop timeout must be between 1 and {} seconds",
+                MAX_STOP_TIMEOUT_SECS
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether the engine's image passes arguments that start with `-` to the server
+pub fn takes_extra_args(db_type: &str) -> bool {
+    matches!(
+        db_type,
+        "PostgreSQL" | "MySQL" | "MariaDB" | "MongoDB" | "Redis"
+    )
+}
+
+/// Host ports a managed database publishes
+fn database_host_ports(database: &DatabaseContainer) -> Vec<i32> {
+    let mut ports = vec![database.port];
+    if let Some(args) = &database.stored_docker_args {
+        ports.extend(args.ports.iter().map(|mapping| mapping.host));
+    }
+    ports
+}
+
+/// Why a version is not a valid image tag (`[A-Za-z0-9_][A-Za-z0-9_.-]{0,127}`)
+fn version_violation(version: &str) -> Option<String> {
+    let Some(first) = version.chars().next() else {
+        return Some("A version is required".to_string());
+    };
+
+    if version.len() > 128 {
+        return Some("The version is longer than 128 characters".to_string());
+    }
+    if !(first.is_ascii_alphanumeric() || first == '_') {
+        return Some(format!(
+            "Version '{}' must start with a letter, a digit or '_'",
+            version
+        ));
+    }
+    if version
+        .chars()
+        .any(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')))
+    {
+        return Some(format!(
+            "Version '{}' may only contain letters, digits, '_', '.' and '-'",
+            version
+        ));
+    }
+
+    None
+}
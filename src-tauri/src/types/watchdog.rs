@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+pub const MAX_WATCHDOG_ATTEMPTS: u32 = 20;
+pub const MAX_WATCHDOG_DELAY_SECS: u32 = 3600;
+
+/// How the app restarts a container that exited unexpectedly
+/// Separate from Docker's restart policy so every attempt can be reported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchdogPolicy {
+    // Restarts tried before the watchdog gives up until the container is started again
+    pub max_attempts: u32,
+    // Wait before the first restart, doubled after each one that did not last
+    pub initial_delay_secs: u32,
+    pub max_delay_secs: u32,
+}
+
+impl Default for WatchdogPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay_secs: 5,
+            max_delay_secs: 300,
+        }
+    }
+}
+
+impl WatchdogPolicy {
+    /// Seconds to wait before restart number `attempt`, counted from 1
+    pub fn delay_secs(&self, attempt: u32) -> u64 {
+        let doublings = attempt.saturating_sub(1).min(31);
+        (u64::from(self.initial_delay_secs) << doublings).min(u64::from(self.max_delay_secs))
+    }
+
+    /// Problems with the values, checked before the policy is saved
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if !(1..=MAX_WATCHDOG_ATTEMPTS).contains(&self.max_attempts) {
+            violations.push(format!(
+                "The watchdog makes between 1 and {} attempts",
+                MAX_WATCHDOG_ATTEMPTS
+            ));
+        }
+        if self.initial_delay_secs == 0 || self.max_delay_secs > MAX_WATCHDOG_DELAY_SECS {
+            violations.push(format!(
+                "Watchdog delays must be between 1 and {} seconds",
+                MAX_WATCHDOG_DELAY_SECS
+            ));
+        }
+        if self.initial_delay_secs > self.max_delay_secs {
+            violations.push(
+                "The first watchdog delay cannot be longer than the maximum delay".to_string(),
+            );
+        }
+
+        violations
+    }
+}
+
+/// Step taken by the watchdog after a sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchdogAction {
+    // The container exited, restart `attempt` runs after the delay
+    Scheduled { attempt: u32, delay_secs: u64 },
+    // The delay is over, the container is started now
+    Restart { attempt: u32 },
+    // Every attempt was used, the container is left stopped
+    GaveUp { attempts: u32 },
+}
+
+/// Payload of `container-watchdog`, sent for every step the watchdog takes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchdogEventPayload {
+    pub id: String,
+    pub name: String,
+    // "scheduled", "restarted", "restartFailed" or "gaveUp"
+    pub stage: String,
+    pub attempt: u32,
+    pub max_attempts: u32,
+    // Wait before the attempt, only when scheduled
+    pub delay_secs: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl WatchdogEventPayload {
+    /// Desktop notification for the step, None for steps that are only reported in the app
+    pub fn notification(&self) -> Option<(String, String)> {
+        match self.stage.as_str() {
+            "restarted" => Some((
+                format!("{} was restarted", self.name),
+                format!(
+                    "It exited unexpectedly, restart {} of {}.",
+                    self.attempt, self.max_attempts
+                ),
+            )),
+            "restartFailed" => Some((
+                format!("{} could not be restarted", self.name),
+                self.error
+                    .clone()
+                    .unwrap_or_else(|| "Docker did not start the container.".to_string()),
+            )),
+            "gaveUp" => Some((
+                format!("{} keeps crashing", self.name),
+                format!(
+                    "The watchdog stopped after {} restarts. Start it again once it is fixed.",
+                    self.attempt
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
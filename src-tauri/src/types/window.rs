@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+// Height of the strip at the top of a window that must stay on screen to drag it back
+const TITLE_BAR_HEIGHT: f64 = 40.0;
+// Width of that strip that must be reachable
+const MIN_VISIBLE_WIDTH: f64 = 100.0;
+
+/// Saved size and position of a window, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+/// Work area of a connected monitor, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl MonitorArea {
+    /// Visible width of the title bar of a window placed at `geometry`
+    fn title_bar_overlap(&self, geometry: &WindowGeometry) -> f64 {
+        let left = geometry.x.max(self.x);
+        let right = (geometry.x + geometry.width).min(self.x + self.width);
+        let top = geometry.y.max(self.y);
+        let bottom = (geometry.y + TITLE_BAR_HEIGHT).min(self.y + self.height);
+
+        if bottom <= top {
+            return 0.0;
+        }
+        (right - left).max(0.0)
+    }
+}
+
+impl WindowGeometry {
+    /// Geometry to open the window with on the current monitors
+    /// The size is clamped to the monitor the window lands on; when its title bar is no
+    /// longer reachable (e.g. the monitor was unplugged) the position is dropped so the
+    /// window gets centered instead, the flag tells whether the position is kept
+    pub fn restore_on(&self, monitors: &[MonitorArea]) -> (WindowGeometry, bool) {
+        let Some(primary) = monitors.first() else {
+            return (*self, true);
+        };
+
+        let target = monitors
+            .iter()
+            .map(|monitor| (monitor, monitor.title_bar_overlap(self)))
+            .filter(|(_, overlap)| *overlap >= MIN_VISIBLE_WIDTH.min(self.width))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(monitor, _)| monitor);
+
+        let monitor = target.unwrap_or(primary);
+        let mut geometry = *self;
+        geometry.width = geometry.width.min(monitor.width);
+        geometry.height = geometry.height.min(monitor.height);
+
+        if target.is_some() {
+            // Pull a window hanging off an edge back onto the monitor
+            geometry.x = geometry
+                .x
+                .clamp(monitor.x, monitor.x + monitor.width - geometry.width);
+            geometry.y = geometry
+                .y
+                .clamp(monitor.y, monitor.y + monitor.height - geometry.height);
+        }
+
+        (geometry, target.is_some())
+    }
+}
+
+/// Key under which the geometry of a window is saved
+/// Windows opened per container share one entry so a new one opens like the last one
+pub fn window_geometry_key(label: &str) -> Option<&'static str> {
+    const KEYS: [&str; 5] = [
+        "main",
+        "container-creation",
+        "container-edit",
+        "metrics",
+        "logs",
+    ];
+
+    KEYS.into_iter()
+        .find(|key| label == *key || label.starts_with(&format!("{}-", key)))
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Workspace that existed before workspaces were introduced, backed by `databases.json`
+pub const DEFAULT_WORKSPACE_ID: &str = "default";
+
+/// Named set of managed containers with its own store file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+}
+
+impl Workspace {
+    pub fn default_workspace() -> Self {
+        Self {
+            id: DEFAULT_WORKSPACE_ID.to_string(),
+            name: "Default".to_string(),
+            created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Known workspaces and the one currently loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    pub active: String,
+    pub workspaces: Vec<Workspace>,
+}
+
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_WORKSPACE_ID.to_string(),
+            workspaces: vec![Workspace::default_workspace()],
+        }
+    }
+}
+
+pub type WorkspaceStore = std::sync::Mutex<WorkspaceConfig>;
@@ -42,12 +42,10 @@ async fn test_create_basic_mongodb_container() {
         docker_args: DockerRunArgs {
             image: "mongo:7".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 27018,
-                container: 27017,
-            }],
+            ports: vec![PortMapping::local(27018, 27017)],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -60,6 +58,7 @@ async fn test_create_basic_mongodb_container() {
             persist_data: false,
             enable_auth: true,
             max_connections: Some(1000),
+            ..Default::default()
         },
     };
 
@@ -72,7 +71,7 @@ async fn test_create_basic_mongodb_container() {
         "Should use correct MongoDB image"
     );
     assert!(
-        command.contains(&"27018:27017".to_string()),
+        command.contains(&"127.0.0.1:27018:27017".to_string()),
         "Should map MongoDB port correctly"
     );
     assert!(
@@ -148,15 +147,13 @@ async fn test_create_mongodb_container_with_volume() {
         docker_args: DockerRunArgs {
             image: "mongo:7".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 27019,
-                container: 27017,
-            }],
+            ports: vec![PortMapping::local(27019, 27017)],
             volumes: vec![VolumeMount {
                 name: volume_name.clone(),
                 path: "/data/db".to_string(),
             }],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -169,6 +166,7 @@ async fn test_create_mongodb_container_with_volume() {
             persist_data: true,
             enable_auth: true,
             max_connections: Some(1000),
+            ..Default::default()
         },
     };
 
@@ -241,12 +239,10 @@ async fn test_create_mongodb_container_without_auth() {
         docker_args: DockerRunArgs {
             image: "mongo:7".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 27020,
-                container: 27017,
-            }],
+            ports: vec![PortMapping::local(27020, 27017)],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -259,6 +255,7 @@ async fn test_create_mongodb_container_without_auth() {
             persist_data: false,
             enable_auth: false,
             max_connections: Some(1000),
+            ..Default::default()
         },
     };
 
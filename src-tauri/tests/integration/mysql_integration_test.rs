@@ -37,12 +37,10 @@ async fn test_create_basic_mysql_container() {
         docker_args: DockerRunArgs {
             image: "mysql:8.0".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 3307,
-                container: 3306,
-            }],
+            ports: vec![PortMapping::local(3307, 3306)],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -55,6 +53,7 @@ async fn test_create_basic_mysql_container() {
             persist_data: false,
             enable_auth: true,
             max_connections: Some(150),
+            ..Default::default()
         },
     };
 
@@ -67,7 +66,7 @@ async fn test_create_basic_mysql_container() {
         "Should use correct MySQL image"
     );
     assert!(
-        command.contains(&"3307:3306".to_string()),
+        command.contains(&"127.0.0.1:3307:3306".to_string()),
         "Should map MySQL port correctly"
     );
     assert!(
@@ -137,15 +136,13 @@ async fn test_create_mysql_container_with_volume() {
         docker_args: DockerRunArgs {
             image: "mysql:8.0".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 3308,
-                container: 3306,
-            }],
+            ports: vec![PortMapping::local(3308, 3306)],
             volumes: vec![VolumeMount {
                 name: volume_name.clone(),
                 path: "/var/lib/mysql".to_string(),
             }],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -158,6 +155,7 @@ async fn test_create_mysql_container_with_volume() {
             persist_data: true,
             enable_auth: true,
             max_connections: Some(150),
+            ..Default::default()
         },
     };
 
@@ -38,12 +38,10 @@ async fn test_create_basic_postgresql_container() {
         docker_args: DockerRunArgs {
             image: "postgres:13-alpine".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 5435,
-                container: 5432,
-            }],
+            ports: vec![PortMapping::local(5435, 5432)],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -56,6 +54,7 @@ async fn test_create_basic_postgresql_container() {
             persist_data: false,
             enable_auth: true,
             max_connections: Some(50),
+            ..Default::default()
         },
     };
 
@@ -69,7 +68,7 @@ async fn test_create_basic_postgresql_container() {
         "Should use correct PostgreSQL image"
     );
     assert!(
-        command.contains(&"5435:5432".to_string()),
+        command.contains(&"127.0.0.1:5435:5432".to_string()),
         "Should map PostgreSQL port correctly"
     );
     assert!(
@@ -151,15 +150,13 @@ async fn test_create_postgresql_container_with_volume() {
         docker_args: DockerRunArgs {
             image: "postgres:13-alpine".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 5436,
-                container: 5432,
-            }],
+            ports: vec![PortMapping::local(5436, 5432)],
             volumes: vec![VolumeMount {
                 name: volume_name.clone(),
                 path: "/var/lib/postgresql/data".to_string(),
             }],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -172,6 +169,7 @@ async fn test_create_postgresql_container_with_volume() {
             persist_data: true,
             enable_auth: true,
             max_connections: Some(100),
+            ..Default::default()
         },
     };
 
@@ -255,12 +253,10 @@ async fn test_update_postgresql_port() {
         docker_args: DockerRunArgs {
             image: "postgres:13-alpine".to_string(),
             env_vars: env_vars.clone(),
-            ports: vec![PortMapping {
-                host: old_port,
-                container: 5432,
-            }],
+            ports: vec![PortMapping::local(old_port, 5432)],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -273,6 +269,7 @@ async fn test_update_postgresql_port() {
             persist_data: false,
             enable_auth: true,
             max_connections: Some(100),
+            ..Default::default()
         },
     };
 
@@ -311,12 +308,10 @@ async fn test_update_postgresql_port() {
         docker_args: DockerRunArgs {
             image: "postgres:13-alpine".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: new_port,
-                container: 5432,
-            }],
+            ports: vec![PortMapping::local(new_port, 5432)],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -329,6 +324,7 @@ async fn test_update_postgresql_port() {
             persist_data: false,
             enable_auth: true,
             max_connections: Some(100),
+            ..Default::default()
         },
     };
 
@@ -33,12 +33,10 @@ async fn test_create_basic_redis_container() {
         docker_args: DockerRunArgs {
             image: "redis:7-alpine".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 6380,
-                container: 6379,
-            }],
+            ports: vec![PortMapping::local(6380, 6379)],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -51,6 +49,7 @@ async fn test_create_basic_redis_container() {
             persist_data: false,
             enable_auth: false,
             max_connections: Some(10000),
+            ..Default::default()
         },
     };
 
@@ -63,7 +62,7 @@ async fn test_create_basic_redis_container() {
         "Should use correct Redis image"
     );
     assert!(
-        command.contains(&"6380:6379".to_string()),
+        command.contains(&"127.0.0.1:6380:6379".to_string()),
         "Should map Redis port correctly"
     );
 
@@ -122,16 +121,14 @@ async fn test_create_redis_container_with_auth() {
         docker_args: DockerRunArgs {
             image: "redis:7-alpine".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 6381,
-                container: 6379,
-            }],
+            ports: vec![PortMapping::local(6381, 6379)],
             volumes: vec![],
             command: vec![
                 "redis-server".to_string(),
                 "--requirepass".to_string(),
                 "myredispass123".to_string(),
             ],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -144,6 +141,7 @@ async fn test_create_redis_container_with_auth() {
             persist_data: false,
             enable_auth: true,
             max_connections: Some(10000),
+            ..Default::default()
         },
     };
 
@@ -209,10 +207,7 @@ async fn test_create_redis_container_with_persistence() {
         docker_args: DockerRunArgs {
             image: "redis:7-alpine".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 6382,
-                container: 6379,
-            }],
+            ports: vec![PortMapping::local(6382, 6379)],
             volumes: vec![VolumeMount {
                 name: volume_name.clone(),
                 path: "/data".to_string(),
@@ -222,6 +217,7 @@ async fn test_create_redis_container_with_persistence() {
                 "--appendonly".to_string(),
                 "yes".to_string(),
             ],
+            shm_size: None,
         },
         metadata: ContainerMetadata {
             id: uuid::Uuid::new_v4().to_string(),
@@ -234,6 +230,7 @@ async fn test_create_redis_container_with_persistence() {
             persist_data: true,
             enable_auth: false,
             max_connections: Some(10000),
+            ..Default::default()
         },
     };
 
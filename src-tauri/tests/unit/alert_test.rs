@@ -0,0 +1,241 @@
+use chrono::{DateTime, Duration, Utc};
+use docker_db_manager_lib::services::{AlertEvaluator, AlertService, AlertTransition};
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod alert_tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-05-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn rule(condition: AlertCondition, duration_secs: u64) -> AlertRule {
+        AlertRule {
+            id: "rule-1".to_string(),
+            database_id: "db-1".to_string(),
+            condition,
+            duration_secs,
+            notify: true,
+            webhook_url: None,
+            enabled: true,
+        }
+    }
+
+    fn reading(memory_percent: f64, connections: Option<u64>) -> AlertReading {
+        AlertReading {
+            cpu_percent: 10.0,
+            memory_percent,
+            connections,
+            disk_bytes: None,
+        }
+    }
+
+    fn alert(id: &str) -> AlertEvent {
+        AlertEvent {
+            id: id.to_string(),
+            rule_id: "rule-1".to_string(),
+            database_id: "db-1".to_string(),
+            database_name: "postgres-dev".to_string(),
+            message: "Memory above 80%".to_string(),
+            value: Some(85.0),
+            fired_at: now().to_rfc3339(),
+            resolved_at: None,
+        }
+    }
+
+    #[test]
+    fn test_memory_condition_compares_percentage() {
+        let condition = AlertCondition::MemoryPercent { percent: 80.0 };
+
+        assert_eq!(
+            condition.check(&reading(85.0, None), 100),
+            Some((85.0, true))
+        );
+        assert_eq!(
+            condition.check(&reading(80.0, None), 100),
+            Some((80.0, false))
+        );
+    }
+
+    #[test]
+    fn test_connections_condition_uses_max_connections() {
+        let condition = AlertCondition::ConnectionsPercent { percent: 90.0 };
+
+        assert_eq!(
+            condition.check(&reading(0.0, Some(95)), 100),
+            Some((95.0, true))
+        );
+        assert_eq!(
+            condition.check(&reading(0.0, Some(45)), 50),
+            Some((90.0, false))
+        );
+    }
+
+    #[test]
+    fn test_conditions_without_a_value_are_skipped() {
+        let sample = reading(50.0, None);
+
+        assert_eq!(
+            AlertCondition::ConnectionsPercent { percent: 90.0 }.check(&sample, 100),
+            None
+        );
+        assert_eq!(
+            AlertCondition::ConnectionsPercent { percent: 90.0 }.check(&reading(0.0, Some(10)), 0),
+            None
+        );
+        assert_eq!(
+            AlertCondition::DiskBytes { bytes: 1 }.check(&sample, 100),
+            None
+        );
+        assert_eq!(AlertCondition::Restarted.check(&sample, 100), None);
+    }
+
+    #[test]
+    fn test_describe_condition() {
+        assert_eq!(
+            AlertCondition::DiskBytes {
+                bytes: 5_000_000_000
+            }
+            .describe(),
+            "Data above 5.0 GB"
+        );
+        assert_eq!(AlertCondition::Restarted.describe(), "Container restarted");
+    }
+
+    #[test]
+    fn test_condition_serializes_with_kind_tag() {
+        let json = serde_json::to_value(AlertCondition::MemoryPercent { percent: 80.0 }).unwrap();
+        assert_eq!(json["kind"], "memoryPercent");
+        assert_eq!(json["percent"], 80.0);
+
+        let condition: AlertCondition =
+            serde_json::from_value(serde_json::json!({ "kind": "restarted" })).unwrap();
+        assert_eq!(condition, AlertCondition::Restarted);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_thresholds() {
+        assert!(rule(AlertCondition::MemoryPercent { percent: 80.0 }, 300)
+            .validate()
+            .is_ok());
+        assert!(rule(AlertCondition::MemoryPercent { percent: 0.0 }, 0)
+            .validate()
+            .is_err());
+        assert!(rule(AlertCondition::CpuPercent { percent: f64::NAN }, 0)
+            .validate()
+            .is_err());
+        assert!(rule(AlertCondition::DiskBytes { bytes: 0 }, 0)
+            .validate()
+            .is_err());
+        assert!(rule(AlertCondition::Restarted, MAX_ALERT_DURATION_SECS + 1)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        let mut with_webhook = rule(AlertCondition::Restarted, 0);
+
+        with_webhook.webhook_url = Some("https://hooks.example.com/alerts".to_string());
+        assert!(with_webhook.validate().is_ok());
+
+        with_webhook.webhook_url = Some("file:///etc/passwd".to_string());
+        assert!(with_webhook.validate().is_err());
+
+        with_webhook.webhook_url = Some("https://example.com/a b".to_string());
+        assert!(with_webhook.validate().is_err());
+    }
+
+    #[test]
+    fn test_rule_fires_after_duration() {
+        let mut evaluator = AlertEvaluator::default();
+        let rule = rule(AlertCondition::MemoryPercent { percent: 80.0 }, 300);
+
+        assert_eq!(evaluator.observe(&rule, true, now()), None);
+        assert_eq!(
+            evaluator.observe(&rule, true, now() + Duration::seconds(200)),
+            None
+        );
+        assert_eq!(
+            evaluator.observe(&rule, true, now() + Duration::seconds(300)),
+            Some(AlertTransition::Fired)
+        );
+        // Fires only once while the condition keeps holding
+        assert_eq!(
+            evaluator.observe(&rule, true, now() + Duration::seconds(360)),
+            None
+        );
+        assert_eq!(
+            evaluator.observe(&rule, false, now() + Duration::seconds(420)),
+            Some(AlertTransition::Resolved)
+        );
+    }
+
+    #[test]
+    fn test_interrupted_breach_starts_over() {
+        let mut evaluator = AlertEvaluator::default();
+        let rule = rule(AlertCondition::MemoryPercent { percent: 80.0 }, 300);
+
+        evaluator.observe(&rule, true, now());
+        assert_eq!(
+            evaluator.observe(&rule, false, now() + Duration::seconds(200)),
+            None
+        );
+        assert_eq!(
+            evaluator.observe(&rule, true, now() + Duration::seconds(320)),
+            None
+        );
+        assert_eq!(
+            evaluator.observe(&rule, true, now() + Duration::seconds(620)),
+            Some(AlertTransition::Fired)
+        );
+    }
+
+    #[test]
+    fn test_zero_duration_fires_immediately() {
+        let mut evaluator = AlertEvaluator::default();
+        let rule = rule(AlertCondition::CpuPercent { percent: 90.0 }, 0);
+
+        assert_eq!(
+            evaluator.observe(&rule, true, now()),
+            Some(AlertTransition::Fired)
+        );
+    }
+
+    #[test]
+    fn test_forget_clears_firing_rule() {
+        let mut evaluator = AlertEvaluator::default();
+        let rule = rule(AlertCondition::CpuPercent { percent: 90.0 }, 0);
+
+        evaluator.observe(&rule, true, now());
+        evaluator.forget(&rule.id);
+
+        assert_eq!(evaluator.observe(&rule, false, now()), None);
+    }
+
+    #[test]
+    fn test_restart_detected_from_count() {
+        let mut evaluator = AlertEvaluator::default();
+
+        // The first sighting only records the count
+        assert!(!evaluator.restarted("db-1", 3));
+        assert!(!evaluator.restarted("db-1", 3));
+        assert!(evaluator.restarted("db-1", 4));
+        assert!(!evaluator.restarted("db-1", 4));
+    }
+
+    #[test]
+    fn test_trim_history_keeps_newest() {
+        let history: Vec<AlertEvent> = (0..MAX_ALERT_HISTORY + 5)
+            .map(|index| alert(&index.to_string()))
+            .collect();
+
+        let trimmed = AlertService::trim_history(history);
+
+        assert_eq!(trimmed.len(), MAX_ALERT_HISTORY);
+        assert_eq!(trimmed[0].id, "5");
+    }
+}
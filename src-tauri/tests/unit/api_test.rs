@@ -0,0 +1,79 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod api_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_routes() {
+        assert_eq!(
+            ApiRoute::parse("GET", "/databases"),
+            Some(ApiRoute::ListDatabases)
+        );
+        assert_eq!(
+            ApiRoute::parse("POST", "/databases/"),
+            Some(ApiRoute::CreateDatabase)
+        );
+        assert_eq!(
+            ApiRoute::parse("POST", "/databases/db-1/start"),
+            Some(ApiRoute::StartDatabase("db-1".to_string()))
+        );
+        assert_eq!(
+            ApiRoute::parse("POST", "/databases/db-1/stop?wait=1"),
+            Some(ApiRoute::StopDatabase("db-1".to_string()))
+        );
+        assert_eq!(
+            ApiRoute::parse("POST", "/databases/db-1/backup"),
+            Some(ApiRoute::BackupDatabase("db-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_unknown_routes() {
+        assert_eq!(ApiRoute::parse("DELETE", "/databases/db-1"), None);
+        assert_eq!(ApiRoute::parse("GET", "/databases/db-1/start"), None);
+        assert_eq!(ApiRoute::parse("POST", "/databases/db-1/restart"), None);
+        assert_eq!(ApiRoute::parse("GET", "/"), None);
+    }
+
+    #[test]
+    fn test_token_check() {
+        assert!(api_token_matches(Some("Bearer secret"), "secret"));
+        assert!(!api_token_matches(Some("Bearer secreT"), "secret"));
+        assert!(!api_token_matches(Some("secret"), "secret"));
+        assert!(!api_token_matches(Some("Bearer "), ""));
+        assert!(!api_token_matches(None, "secret"));
+    }
+
+    #[test]
+    fn test_listed_databases_leave_out_secrets() {
+        let database = DatabaseContainer {
+            id: "db-1".to_string(),
+            name: "my-postgres".to_string(),
+            stored_password: Some("secret".to_string()),
+            stored_docker_args: Some(DockerRunArgs {
+                image: "postgres:16".to_string(),
+                env_vars: [("POSTGRES_PASSWORD".to_string(), "secret".to_string())].into(),
+                ports: vec![],
+                volumes: vec![],
+                command: vec![],
+                shm_size: None,
+            }),
+            ..Default::default()
+        };
+
+        let listed = serde_json::to_string(&ApiDatabase::from(&database)).unwrap();
+
+        assert!(listed.contains("\"name\":\"my-postgres\""));
+        assert!(!listed.contains("secret"));
+    }
+
+    #[test]
+    fn test_error_status_codes() {
+        assert_eq!(AppError::NotFound("Container".into()).http_status(), 404);
+        assert_eq!(AppError::PortInUse { port: 5432 }.http_status(), 409);
+        assert_eq!(AppError::Validation("bad".into()).http_status(), 400);
+        assert_eq!(AppError::DockerUnavailable.http_status(), 503);
+        assert_eq!(AppError::Internal("oops".into()).http_status(), 500);
+    }
+}
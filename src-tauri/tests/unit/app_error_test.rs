@@ -0,0 +1,193 @@
+use docker_db_manager_lib::types::{AppError, AppSettings, DockerErrorKind};
+
+#[cfg(test)]
+mod app_error_tests {
+    use super::*;
+
+    #[test]
+    fn test_port_conflict_is_classified() {
+        let error = AppError::from_docker_run_error(
+            "Bind for 0.0.0.0:5432 failed: port is already allocated",
+            "my-db",
+            5432,
+        );
+
+        assert_eq!(error.code(), "PORT_IN_USE");
+        assert_eq!(error.to_string(), "Port 5432 is already in use");
+    }
+
+    #[test]
+    fn test_name_conflict_is_classified() {
+        let error = AppError::from_docker_run_error(
+            "Conflict. The container name \"/my-db\" is already in use",
+            "my-db",
+            5432,
+        );
+
+        assert_eq!(error.code(), "NAME_IN_USE");
+        assert!(error.hint().is_some());
+    }
+
+    #[test]
+    fn test_unknown_docker_error_keeps_details() {
+        let error = AppError::from_docker_run_error("manifest unknown", "my-db", 5432);
+
+        match error {
+            AppError::Docker { details, .. } => {
+                assert_eq!(details, Some("manifest unknown".to_string()))
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_serialization_shape() {
+        let error = AppError::PortInUse { port: 3306 };
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(json["code"], "PORT_IN_USE");
+        assert_eq!(json["message"], "Port 3306 is already in use");
+        assert_eq!(json["port"], 3306);
+        assert!(json["hint"].is_string());
+        assert!(json["details"].is_null());
+    }
+
+    #[test]
+    fn test_not_found_message() {
+        let error = AppError::NotFound("Container".to_string());
+
+        assert_eq!(error.code(), "NOT_FOUND");
+        assert_eq!(error.to_string(), "Container not found");
+    }
+
+    #[test]
+    fn test_read_only_settings_block_changes() {
+        let mut settings = AppSettings::default();
+        assert!(settings.ensure_writable().is_ok());
+
+        settings.read_only = true;
+        let error = settings.ensure_writable().unwrap_err();
+
+        assert_eq!(error.code(), "READ_ONLY");
+        assert_eq!(error.http_status(), 403);
+        assert!(error.hint().is_some());
+    }
+
+    #[test]
+    fn test_docker_errors_are_classified() {
+        let cases = [
+            (
+                "Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?",
+                Some(1),
+                DockerErrorKind::DaemonUnreachable,
+            ),
+            ("", Some(127), DockerErrorKind::DaemonUnreachable),
+            (
+                "No such file or directory (os error 2)",
+                None,
+                DockerErrorKind::DaemonUnreachable,
+            ),
+            (
+                "permission denied while trying to connect to the Docker daemon socket",
+                Some(1),
+                DockerErrorKind::PermissionDenied,
+            ),
+            (
+                "write /var/lib/docker/tmp: no space left on device",
+                Some(1),
+                DockerErrorKind::DiskFull,
+            ),
+            (
+                "listen tcp 0.0.0.0:6379: bind: address already in use",
+                Some(125),
+                DockerErrorKind::PortConflict,
+            ),
+            (
+                "manifest for postgres:99 not found: manifest unknown: manifest unknown",
+                Some(125),
+                DockerErrorKind::ImageNotFound,
+            ),
+            ("No such container: my-db", Some(1), DockerErrorKind::Other),
+        ];
+
+        for (stderr, exit_code, kind) in cases {
+            assert_eq!(
+                DockerErrorKind::classify(stderr, exit_code),
+                kind,
+                "{}",
+                stderr
+            );
+        }
+    }
+
+    #[test]
+    fn test_conflicting_port_is_parsed() {
+        assert_eq!(
+            DockerErrorKind::conflicting_port(
+                "Bind for 0.0.0.0:5432 failed: port is already allocated"
+            ),
+            Some(5432)
+        );
+        assert_eq!(
+            DockerErrorKind::conflicting_port(
+                "listen tcp 0.0.0.0:6379: bind: address already in use"
+            ),
+            Some(6379)
+        );
+    }
+
+    #[test]
+    fn test_conflicting_name_is_parsed() {
+        assert_eq!(
+            DockerErrorKind::conflicting_name(
+                "Conflict. The container name \"/my-db\" is already in use"
+            ),
+            Some("my-db".to_string())
+        );
+    }
+
+    #[test]
+    fn test_docker_output_maps_categories() {
+        let error = AppError::from_docker_output(
+            "Failed to start container",
+            "no space left on device",
+            Some(1),
+        );
+        assert_eq!(error.code(), "DISK_FULL");
+
+        let error = AppError::from_docker_output(
+            "Failed to start container",
+            "Error: No such container: my-db\n",
+            Some(1),
+        );
+        assert_eq!(
+            error.to_string(),
+            "Failed to start container: Error: No such container: my-db"
+        );
+    }
+
+    #[test]
+    fn test_run_request_keeps_classified_errors() {
+        let error = AppError::from_docker_stderr("pull access denied for nope", Some(125))
+            .for_run_request("my-db", 5432);
+        assert_eq!(error.code(), "IMAGE_NOT_FOUND");
+
+        let error = AppError::from_docker_stderr("port is already allocated", Some(125))
+            .for_run_request("my-db", 5432);
+        assert_eq!(error.code(), "PORT_IN_USE");
+    }
+
+    #[test]
+    fn test_context_keeps_typed_errors() {
+        let error = AppError::from_docker_stderr("no space left on device", Some(1))
+            .with_context("Failed to copy volume data");
+        assert_eq!(error.code(), "DISK_FULL");
+
+        let error = AppError::from_docker_stderr("sh: cp: bad file", Some(1))
+            .with_context("Failed to copy volume data");
+        assert_eq!(
+            error.to_string(),
+            "Failed to copy volume data: sh: cp: bad file"
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use docker_db_manager_lib::services::AutostartService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod autostart_service_tests {
+    use super::*;
+
+    fn database(name: &str, status: &str, auto_start: bool) -> DatabaseContainer {
+        DatabaseContainer {
+            id: format!("{}-id", name),
+            name: name.to_string(),
+            status: status.to_string(),
+            container_id: Some(format!("{}-container", name)),
+            auto_start,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_only_stopped_flagged_containers_are_pending() {
+        let databases = vec![
+            database("orders", "stopped", true),
+            database("cache", "running", true),
+            database("reports", "stopped", false),
+        ];
+
+        let pending = AutostartService::pending(&databases);
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].name, "orders");
+    }
+
+    #[test]
+    fn test_pending_skips_containers_without_docker_id() {
+        let mut missing = database("orders", "stopped", true);
+        missing.container_id = None;
+
+        assert!(AutostartService::pending(&[missing]).is_empty());
+    }
+
+    #[test]
+    fn test_pending_is_sorted_by_name() {
+        let databases = vec![
+            database("users", "stopped", true),
+            database("Accounts", "stopped", true),
+        ];
+
+        let names: Vec<String> = AutostartService::pending(&databases)
+            .into_iter()
+            .map(|db| db.name)
+            .collect();
+
+        assert_eq!(names, vec!["Accounts", "users"]);
+    }
+}
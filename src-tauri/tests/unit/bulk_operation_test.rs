@@ -0,0 +1,32 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod bulk_operation_tests {
+    use super::*;
+
+    #[test]
+    fn test_action_deserializes_from_camel_case() {
+        let action: BulkAction = serde_json::from_str("\"stop\"").unwrap();
+        assert_eq!(action, BulkAction::Stop);
+    }
+
+    #[test]
+    fn test_successful_result() {
+        let result = BulkResult::from_result("db-1".to_string(), Ok::<(), AppError>(()));
+
+        assert!(result.success);
+        assert!(result.error.is_none());
+    }
+
+    #[test]
+    fn test_failed_result_keeps_message() {
+        let result = BulkResult::from_result(
+            "db-1".to_string(),
+            Err::<(), _>(AppError::NotFound("Container".to_string())),
+        );
+
+        assert!(!result.success);
+        assert_eq!(result.container_id, "db-1");
+        assert!(result.error.unwrap().contains("Container"));
+    }
+}
@@ -0,0 +1,166 @@
+use docker_db_manager_lib::services::ConfigService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod bundle_tests {
+    use super::*;
+
+    const SHA: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    fn database() -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-1".to_string(),
+            name: "orders".to_string(),
+            db_type: "PostgreSQL".to_string(),
+            version: "16".to_string(),
+            container_id: Some("abc123".to_string()),
+            ca_cert_path: Some("/home/me/ca.crt".to_string()),
+            stored_password: Some("secret".to_string()),
+            stored_password_ref: Some("db-1-password".to_string()),
+            stored_docker_args: Some(DockerRunArgs {
+                image: "postgres:16".to_string(),
+                env_vars: Default::default(),
+                ports: Vec::new(),
+                volumes: Vec::new(),
+                command: Vec::new(),
+                shm_size: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_bundle_file_line() {
+        let file = BundleFile::parse_line(&format!("{} 1024 data.tar.gz", SHA)).unwrap();
+
+        assert_eq!(file.name, "data.tar.gz");
+        assert_eq!(file.sha256, SHA);
+        assert_eq!(file.size_bytes, 1024);
+
+        assert!(BundleFile::parse_line("sha256sum: data.tar.gz: No such file").is_none());
+        assert!(BundleFile::parse_line(&format!("{} big data.tar.gz", SHA)).is_none());
+        assert!(BundleFile::parse_line("abc 10 data.tar.gz").is_none());
+    }
+
+    #[test]
+    fn test_manifest_describes_the_database() {
+        let files = vec![BundleFile {
+            name: BUNDLE_DATA_FILE.to_string(),
+            sha256: SHA.to_string(),
+            size_bytes: 10,
+        }];
+        let manifest = BundleManifest::new("1.4.0", &database(), files);
+
+        assert_eq!(manifest.version, DATABASE_BUNDLE_VERSION);
+        assert_eq!(manifest.app_version, "1.4.0");
+        assert_eq!(manifest.db_type, "PostgreSQL");
+        assert_eq!(manifest.engine_version, "16");
+        assert_eq!(manifest.image, "postgres:16");
+        assert_eq!(manifest.file(BUNDLE_DATA_FILE).unwrap().size_bytes, 10);
+        assert!(manifest.file(BUNDLE_CONFIG_FILE).is_none());
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["engineVersion"], "16");
+        assert_eq!(json["files"][0]["sizeBytes"], 10);
+    }
+
+    #[test]
+    fn test_portable_database_drops_host_fields() {
+        let portable = ConfigService::portable_database(database(), true);
+
+        assert_eq!(portable.container_id, None);
+        assert_eq!(portable.ca_cert_path, None);
+        assert_eq!(portable.stored_password_ref, None);
+        assert_eq!(portable.stored_password.as_deref(), Some("secret"));
+
+        let redacted = ConfigService::portable_database(database(), false);
+        assert_eq!(redacted.stored_password, None);
+    }
+
+    fn files(data_sha: &str) -> Vec<BundleFile> {
+        vec![
+            BundleFile {
+                name: BUNDLE_CONFIG_FILE.to_string(),
+                sha256: SHA.to_string(),
+                size_bytes: 512,
+            },
+            BundleFile {
+                name: BUNDLE_DATA_FILE.to_string(),
+                sha256: data_sha.to_string(),
+                size_bytes: 1024,
+            },
+        ]
+    }
+
+    fn bundled_request() -> DockerRunRequest {
+        let mut database = database();
+        database.port = 5432;
+        database.stored_docker_args = Some(DockerRunArgs {
+            image: "postgres:16".to_string(),
+            env_vars: Default::default(),
+            ports: vec![PortMapping {
+                host: 5432,
+                container: 5432,
+                host_address: "127.0.0.1".to_string(),
+            }],
+            volumes: vec![VolumeMount {
+                name: "orders-data".to_string(),
+                path: "/var/lib/postgresql/data".to_string(),
+            }],
+            command: Vec::new(),
+            shm_size: None,
+        });
+        ConfigService::build_run_request(&database).unwrap()
+    }
+
+    #[test]
+    fn test_manifest_verifies_unpacked_files() {
+        let manifest = BundleManifest::new("1.4.0", &database(), files(SHA));
+        assert!(manifest.verify(&files(SHA)).is_ok());
+
+        let damaged = SHA.replace('9', "0");
+        assert!(manifest.verify(&files(&damaged)).is_err());
+        assert!(manifest.verify(&files(SHA)[..1]).is_err());
+
+        let newer = BundleManifest {
+            version: DATABASE_BUNDLE_VERSION + 1,
+            ..manifest
+        };
+        assert!(newer.verify(&files(SHA)).is_err());
+    }
+
+    #[test]
+    fn test_relocate_keeps_a_free_name_and_port() {
+        let mut request = bundled_request();
+        let (renamed_from, remapped) =
+            relocate_bundle_request(&mut request, &["billing".to_string()], |_| true);
+
+        assert_eq!(renamed_from, None);
+        assert!(remapped.is_empty());
+        assert_eq!(request.name, "orders");
+        assert_eq!(request.metadata.port, 5432);
+    }
+
+    #[test]
+    fn test_relocate_moves_off_taken_name_and_port() {
+        let mut request = bundled_request();
+        let (renamed_from, remapped) =
+            relocate_bundle_request(&mut request, &["orders".to_string()], |port| {
+                port != 5432 && port != 5433
+            });
+
+        assert_eq!(renamed_from.as_deref(), Some("orders"));
+        assert_eq!(request.name, "orders-2");
+        assert_eq!(request.docker_args.volumes[0].name, "orders-2-data");
+        assert_eq!(
+            remapped,
+            vec![PortRemap {
+                from: 5432,
+                to: 5434
+            }]
+        );
+        assert_eq!(request.metadata.port, 5434);
+        assert_eq!(request.docker_args.ports[0].host, 5434);
+        assert_eq!(request.docker_args.ports[0].container, 5432);
+    }
+}
@@ -0,0 +1,147 @@
+use docker_db_manager_lib::services::{ClientService, ConnectionService, TerminalService};
+use docker_db_manager_lib::types::*;
+use std::path::Path;
+
+#[cfg(test)]
+mod client_service_tests {
+    use super::*;
+
+    fn create_test_database(db_type: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-id".to_string(),
+            name: "my-db".to_string(),
+            db_type: db_type.to_string(),
+            port: 5433,
+            container_id: Some("abc123".to_string()),
+            stored_username: Some("app".to_string()),
+            stored_password: Some("secret".to_string()),
+            stored_database_name: Some("app_dev".to_string()),
+            stored_enable_auth: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_supported_clients() {
+        assert!(DatabaseClient::PgAdmin.supports("PostgreSQL"));
+        assert!(!DatabaseClient::PgAdmin.supports("MySQL"));
+        assert!(DatabaseClient::TablePlus.supports("Redis"));
+        assert!(!DatabaseClient::DBeaver.supports("Redis"));
+        assert!(!DatabaseClient::Cli.supports("Elasticsearch"));
+    }
+
+    #[test]
+    fn test_psql_runs_in_container() {
+        let database = create_test_database("PostgreSQL");
+        let command =
+            ClientService::cli_command(&database, Some(Path::new("/tmp/db.env"))).unwrap();
+
+        assert_eq!(
+            ClientService::cli_credentials(&database),
+            vec![("PGPASSWORD", "secret".to_string())]
+        );
+        assert_eq!(
+            command,
+            vec![
+                "docker",
+                "exec",
+                "-it",
+                "--env-file",
+                "/tmp/db.env",
+                "abc123",
+                "psql",
+                "-U",
+                "app",
+                "-d",
+                "app_dev"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redis_cli_without_password() {
+        let mut database = create_test_database("Redis");
+        database.stored_password = None;
+
+        let command = ClientService::cli_command(&database, None).unwrap();
+
+        assert!(ClientService::cli_credentials(&database).is_empty());
+        assert_eq!(
+            command,
+            vec!["docker", "exec", "-it", "abc123", "redis-cli"]
+        );
+    }
+
+    #[test]
+    fn test_mongosh_password_stays_off_the_command() {
+        let database = create_test_database("MongoDB");
+        let command =
+            ClientService::cli_command(&database, Some(Path::new("/tmp/db.env"))).unwrap();
+
+        assert!(!command.iter().any(|arg| arg.contains("secret")));
+        assert_eq!(ClientService::cli_credentials(&database)[0].1, "secret");
+    }
+
+    #[test]
+    fn test_cli_needs_a_container() {
+        let mut database = create_test_database("MySQL");
+        database.container_id = None;
+
+        assert!(ClientService::cli_command(&database, None).is_none());
+        assert!(ClientService::cli_command(&create_test_database("Elasticsearch"), None).is_none());
+    }
+
+    #[test]
+    fn test_dbeaver_connection_spec() {
+        let connection = ConnectionService::new();
+        assert_eq!(
+            ClientService::dbeaver_connection(&connection, &create_test_database("MariaDB"))
+                .unwrap(),
+            "driver=mariadb|host=localhost|port=5433|name=my-db|database=app_dev|user=app|connect=true"
+        );
+
+        let mut database = create_test_database("PostgreSQL");
+        database.stored_username = Some("a|b".to_string());
+        assert!(ClientService::dbeaver_connection(&connection, &database).is_err());
+    }
+
+    #[test]
+    fn test_container_shell_prefers_bash() {
+        let command = TerminalService::container_shell_command("abc123");
+
+        assert_eq!(command[..4], ["docker", "exec", "-it", "abc123"]);
+        assert_eq!(
+            command.last().unwrap(),
+            "command -v bash >/dev/null && exec bash || exec sh"
+        );
+    }
+
+    #[test]
+    fn test_batch_line_quotes_for_programs_and_escapes_for_cmd() {
+        let args: Vec<String> = [
+            "docker",
+            "exec",
+            "my db&calc",
+            "--eval",
+            r#"auth("a\b", "50%")"#,
+            r"C:\dir\",
+            "",
+        ]
+        .map(str::to_string)
+        .to_vec();
+
+        assert_eq!(
+            TerminalService::batch_line(&args).unwrap(),
+            r#"docker exec ^"my db^&calc^" --eval ^"auth^(\^"a\b\^", \^"50%%\^"^)^" C:\dir\ ^"^""#
+        );
+        assert!(TerminalService::batch_line(&["echo\nexit".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_applescript_escape() {
+        assert_eq!(
+            TerminalService::applescript_escape(r#"echo "a\b""#),
+            r#"echo \"a\\b\""#
+        );
+    }
+}
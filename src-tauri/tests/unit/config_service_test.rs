@@ -0,0 +1,176 @@
+use docker_db_manager_lib::services::{ConfigService, SecretsService};
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod config_service_tests {
+    use super::*;
+
+    fn create_test_database(name: &str, db_type: &str) -> DatabaseContainer {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("POSTGRES_PASSWORD".to_string(), "secret".to_string());
+        env_vars.insert("POSTGRES_USER".to_string(), "admin".to_string());
+
+        DatabaseContainer {
+            id: format!("{}-id", name),
+            name: name.to_string(),
+            db_type: db_type.to_string(),
+            version: "16".to_string(),
+            status: "running".to_string(),
+            port: 5432,
+            max_connections: 100,
+            container_id: Some("abc123".to_string()),
+            stored_password: Some("secret".to_string()),
+            stored_username: Some("admin".to_string()),
+            ca_cert_path: Some("/tmp/certs/ca.crt".to_string()),
+            stored_docker_args: Some(DockerRunArgs {
+                image: "postgres:16".to_string(),
+                env_vars,
+                ports: vec![PortMapping::local(5432, 5432)],
+                volumes: vec![],
+                command: vec![],
+                shm_size: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_secret_env_vars() {
+        assert!(ConfigService::is_secret_env_var("POSTGRES_PASSWORD"));
+        assert!(ConfigService::is_secret_env_var("MSSQL_SA_PASSWORD"));
+        assert!(!ConfigService::is_secret_env_var("POSTGRES_USER"));
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let mut database = create_test_database("redis-db", "Redis");
+        if let Some(args) = &mut database.stored_docker_args {
+            args.command = vec!["--requirepass".to_string(), "secret".to_string()];
+        }
+
+        ConfigService::redact_secrets(&mut database);
+
+        let args = database.stored_docker_args.unwrap();
+        assert!(database.stored_password.is_none());
+        assert_eq!(args.env_vars["POSTGRES_PASSWORD"], "");
+        assert_eq!(args.env_vars["POSTGRES_USER"], "admin");
+        assert_eq!(
+            args.command,
+            vec!["--requirepass".to_string(), String::new()]
+        );
+    }
+
+    #[test]
+    fn test_build_bundle_drops_host_fields() {
+        let mut databases = HashMap::new();
+        for name in ["b-db", "a-db"] {
+            let database = create_test_database(name, "PostgreSQL");
+            databases.insert(database.id.clone(), database);
+        }
+
+        let bundle = ConfigService::build_bundle(&databases, &AppSettings::default(), true);
+
+        assert_eq!(bundle.version, CONFIG_BUNDLE_VERSION);
+        assert!(bundle.includes_secrets);
+        assert_eq!(bundle.databases[0].name, "a-db");
+        assert_eq!(bundle.databases[1].name, "b-db");
+        assert!(bundle.databases[0].container_id.is_none());
+        assert!(bundle.databases[0].ca_cert_path.is_none());
+        assert_eq!(
+            bundle.databases[0].stored_password,
+            Some("secret".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_bundle_without_secrets() {
+        let mut databases = HashMap::new();
+        let database = create_test_database("pg", "PostgreSQL");
+        databases.insert(database.id.clone(), database);
+
+        let bundle = ConfigService::build_bundle(&databases, &AppSettings::default(), false);
+
+        assert!(!bundle.includes_secrets);
+        assert!(bundle.databases[0].stored_password.is_none());
+    }
+
+    #[test]
+    fn test_restore_organization() {
+        let mut exported = create_test_database("pg", "PostgreSQL");
+        exported.project = Some("shop".to_string());
+        exported.tags = vec!["api".to_string()];
+        exported.favorite = true;
+        exported.auto_start = true;
+        exported.keep_running_on_exit = true;
+        let mut database = create_test_database("pg", "PostgreSQL");
+
+        ConfigService::restore_organization(&mut database, &exported);
+
+        assert_eq!(database.project, Some("shop".to_string()));
+        assert_eq!(database.tags, ["api"]);
+        assert!(database.favorite && database.auto_start && database.keep_running_on_exit);
+    }
+
+    #[test]
+    fn test_build_run_request() {
+        let database = create_test_database("pg", "PostgreSQL");
+
+        let request = ConfigService::build_run_request(&database).unwrap();
+
+        assert_eq!(request.name, "pg");
+        assert_eq!(request.metadata.id, "pg-id");
+        assert_eq!(request.metadata.password, "secret");
+        assert_eq!(request.metadata.max_connections, Some(100));
+        assert_eq!(request.docker_args.image, "postgres:16");
+    }
+
+    #[test]
+    fn test_build_run_request_requires_args_and_credentials() {
+        let mut without_args = create_test_database("pg", "PostgreSQL");
+        without_args.stored_docker_args = None;
+        assert!(matches!(
+            ConfigService::build_run_request(&without_args),
+            Err(AppError::Validation(_))
+        ));
+
+        let mut without_password = create_test_database("pg", "PostgreSQL");
+        ConfigService::redact_secrets(&mut without_password);
+        assert!(matches!(
+            ConfigService::build_run_request(&without_password),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_run_request_rejects_unresolved_password() {
+        let mut database = create_test_database("pg", "PostgreSQL");
+        SecretsService::scrub_password(&mut database, "secret");
+        database.stored_password = None;
+
+        assert!(matches!(
+            ConfigService::build_run_request(&database),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_run_request_without_credentials() {
+        let mut database = create_test_database("cache", "Redis");
+        database.stored_password = None;
+        database.stored_username = None;
+        if let Some(docker_args) = &mut database.stored_docker_args {
+            docker_args.image = "redis:7".to_string();
+            docker_args.env_vars.clear();
+            docker_args.command = vec!["redis-server".to_string()];
+        }
+
+        let request = ConfigService::build_run_request(&database).unwrap();
+
+        assert_eq!(request.metadata.password, "");
+        assert_eq!(
+            request.docker_args.command,
+            vec!["redis-server".to_string()]
+        );
+    }
+}
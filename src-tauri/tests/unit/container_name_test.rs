@@ -0,0 +1,78 @@
+use docker_db_manager_lib::types::{
+    container_name_violation, normalize_container_name, validate_container_name, AppError,
+    MAX_CONTAINER_NAME_LENGTH,
+};
+
+#[cfg(test)]
+mod container_name_tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_names() {
+        assert_eq!(container_name_violation("my-db"), None);
+        assert_eq!(container_name_violation("db_1.test"), None);
+        assert_eq!(container_name_violation("9lives"), None);
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        assert!(container_name_violation("").is_some());
+        assert!(container_name_violation("a").is_some());
+        assert!(container_name_violation("-db").is_some());
+        assert!(container_name_violation("my db").is_some());
+        assert!(container_name_violation("my/db").is_some());
+        assert!(container_name_violation(&"a".repeat(MAX_CONTAINER_NAME_LENGTH + 1)).is_some());
+    }
+
+    #[test]
+    fn test_normalize_replaces_invalid_characters() {
+        assert_eq!(normalize_container_name("My Cool DB!", &[]), "My-Cool-DB");
+        assert_eq!(normalize_container_name("  _postgres  ", &[]), "postgres");
+        assert_eq!(normalize_container_name("a", &[]), "a-db");
+        assert_eq!(normalize_container_name("!!!", &[]), "database");
+    }
+
+    #[test]
+    fn test_normalize_avoids_taken_names() {
+        let taken = vec!["my-db".to_string(), "my-db-2".to_string()];
+
+        assert_eq!(normalize_container_name("my db", &taken), "my-db-3");
+    }
+
+    #[test]
+    fn test_normalize_keeps_length_limit() {
+        let name = "x".repeat(80);
+        let taken = vec!["x".repeat(MAX_CONTAINER_NAME_LENGTH)];
+
+        let normalized = normalize_container_name(&name, &taken);
+
+        assert_eq!(normalized.len(), MAX_CONTAINER_NAME_LENGTH);
+        assert!(normalized.ends_with("-2"));
+        assert_eq!(container_name_violation(&normalized), None);
+    }
+
+    #[test]
+    fn test_validate_reports_suggestion() {
+        match validate_container_name("my db", &[]) {
+            Err(AppError::InvalidName { suggestion, .. }) => assert_eq!(suggestion, "my-db"),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_taken_name() {
+        let taken = vec!["my-db".to_string()];
+        let error = validate_container_name("my-db", &taken).unwrap_err();
+
+        assert_eq!(error.code(), "INVALID_NAME");
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["suggestion"], "my-db-2");
+    }
+
+    #[test]
+    fn test_validate_accepts_free_name() {
+        let taken = vec!["other".to_string()];
+
+        assert!(validate_container_name("my-db", &taken).is_ok());
+    }
+}
@@ -0,0 +1,181 @@
+use docker_db_manager_lib::services::CredentialService;
+use docker_db_manager_lib::types::*;
+use std::path::Path;
+
+#[cfg(test)]
+mod credential_service_tests {
+    use super::*;
+
+    fn credentials(username: &str, password: &str, database: &str) -> DatabaseCredentials {
+        DatabaseCredentials {
+            username: username.to_string(),
+            password: password.to_string(),
+            database: database.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_defaults_are_filled_in() {
+        let database = DatabaseContainer {
+            db_type: "PostgreSQL".to_string(),
+            stored_password: Some("secret".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            DatabaseCredentials::of(&database),
+            credentials("postgres", "secret", "postgres")
+        );
+    }
+
+    #[test]
+    fn test_empty_password_keeps_current() {
+        let current = credentials("postgres", "secret", "app");
+        let metadata = ContainerMetadata {
+            db_type: "PostgreSQL".to_string(),
+            username: Some("postgres".to_string()),
+            database_name: Some("app".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(DatabaseCredentials::requested(&metadata, &current), current);
+    }
+
+    #[test]
+    fn test_new_user_keeps_database() {
+        let current = credentials("postgres", "secret", "postgres");
+        let metadata = ContainerMetadata {
+            db_type: "PostgreSQL".to_string(),
+            username: Some("admin".to_string()),
+            ..Default::default()
+        };
+
+        let requested = DatabaseCredentials::requested(&metadata, &current);
+        assert_eq!(requested, credentials("admin", "secret", "postgres"));
+
+        let commands =
+            CredentialService::change_commands("PostgreSQL", &current, &requested).unwrap();
+        assert!(commands
+            .iter()
+            .all(|command| !command.statement().unwrap().starts_with("ALTER DATABASE")));
+    }
+
+    #[test]
+    fn test_unchanged_credentials_run_nothing() {
+        let current = credentials("postgres", "secret", "app");
+
+        let commands =
+            CredentialService::change_commands("PostgreSQL", &current, &current).unwrap();
+
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn test_postgres_password_change() {
+        let commands = CredentialService::change_commands(
+            "PostgreSQL",
+            &credentials("postgres", "old", "app"),
+            &credentials("postgres", "it's new", "app"),
+        )
+        .unwrap();
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            commands[0].statement().unwrap(),
+            "ALTER ROLE \"postgres\" WITH PASSWORD 'it''s new'"
+        );
+
+        let args = commands[0].exec_args("my-db", Some(Path::new("/tmp/db.env")));
+        assert_eq!(&args[..4], ["exec", "--env-file", "/tmp/db.env", "my-db"]);
+        assert!(!args.iter().any(|arg| arg.contains("it''s new")));
+    }
+
+    #[test]
+    fn test_postgres_rename_goes_through_helper_role() {
+        let commands = CredentialService::change_commands(
+            "PostgreSQL",
+            &credentials("postgres", "secret", "postgres"),
+            &credentials("admin", "secret", "app"),
+        )
+        .unwrap();
+
+        let statements: Vec<&str> = commands
+            .iter()
+            .map(|command| command.statement().unwrap())
+            .collect();
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE ROLE \"ddm_credentials\" SUPERUSER LOGIN",
+                "ALTER ROLE \"postgres\" RENAME TO \"admin\"",
+                "DROP ROLE \"ddm_credentials\"",
+                "ALTER ROLE \"admin\" WITH PASSWORD 'secret'",
+                "ALTER DATABASE \"postgres\" RENAME TO \"app\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mysql_authenticates_with_current_password() {
+        let commands = CredentialService::change_commands(
+            "MySQL",
+            &credentials("root", "old", ""),
+            &credentials("root", "new", ""),
+        )
+        .unwrap();
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].env[0], ("MYSQL_PWD", "old".to_string()));
+        assert!(commands[0]
+            .statement()
+            .unwrap()
+            .contains("ALTER USER IF EXISTS 'root'@'%' IDENTIFIED BY 'new'"));
+        assert!(!commands[0]
+            .command
+            .iter()
+            .any(|arg| arg.contains("old") || arg.contains("new")));
+    }
+
+    #[test]
+    fn test_mysql_root_cannot_be_renamed() {
+        let result = CredentialService::change_commands(
+            "MySQL",
+            &credentials("root", "secret", ""),
+            &credentials("admin", "secret", ""),
+        );
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_mongo_rename_creates_then_drops_user() {
+        let commands = CredentialService::change_commands(
+            "MongoDB",
+            &credentials("admin", "secret", ""),
+            &credentials("root", "secret", ""),
+        )
+        .unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert!(commands[0].command.last().unwrap().contains("createUser"));
+        assert!(commands[1]
+            .command
+            .last()
+            .unwrap()
+            .contains("auth(\"root\", process.env.DDM_MONGO_PASSWORD);"));
+        assert!(commands[1]
+            .command
+            .last()
+            .unwrap()
+            .contains("dropUser(\"admin\")"));
+        assert!(commands
+            .iter()
+            .all(|command| !command.command.iter().any(|arg| arg.contains("secret"))));
+    }
+
+    #[test]
+    fn test_redis_applies_on_start() {
+        assert!(CredentialService::applies_on_start("Redis"));
+        assert!(!CredentialService::applies_on_start("PostgreSQL"));
+    }
+}
@@ -0,0 +1,238 @@
+use docker_db_manager_lib::services::CsvService;
+use docker_db_manager_lib::types::*;
+use std::path::Path;
+
+#[cfg(test)]
+mod csv_service_tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_record(&strings(&["id", "full name"])), "id,full name\n");
+    }
+
+    #[test]
+    fn test_postgres_export_copies_a_query() {
+        assert_eq!(
+            CsvService::postgres_export_sql("public", "users", &[], true),
+            "COPY (SELECT * FROM \"public\".\"users\") TO STDOUT WITH (FORMAT csv, HEADER true)"
+        );
+        assert_eq!(
+            CsvService::postgres_export_sql("public", "users", &strings(&["id", "e\"mail"]), false),
+            "COPY (SELECT \"id\", \"e\"\"mail\" FROM \"public\".\"users\") TO STDOUT WITH (FORMAT csv, HEADER false)"
+        );
+    }
+
+    #[test]
+    fn test_mysql_outfile_writes_rfc_csv() {
+        let sql = CsvService::mysql_outfile_sql(
+            "shop",
+            "orders",
+            &strings(&["id", "note"]),
+            "/var/lib/mysql-files/out.csv",
+        );
+
+        assert!(sql.starts_with(
+            "SELECT IF(`id` IS NULL, '', CONCAT('\"', REPLACE(`id`, '\"', '\"\"'), '\"')), \
+IF(`note` IS NULL, '', CONCAT('\"', REPLACE(`note`, '\"', '\"\"'), '\"')) FROM `shop`.`orders` \
+INTO OUTFILE '/var/lib/mysql-files/out.csv'"
+        ));
+        assert!(sql.contains("FIELDS TERMINATED BY ',' ESCAPED BY ''"));
+        assert!(!sql.contains("ENCLOSED BY"));
+    }
+
+    #[test]
+    fn test_outfile_path_follows_secure_file_priv() {
+        assert!(CsvService::outfile_path("/var/lib/mysql-files/")
+            .unwrap()
+            .starts_with("/var/lib/mysql-files/ddm-export-"));
+        assert!(CsvService::outfile_path("").unwrap().starts_with("/tmp/"));
+        assert!(matches!(
+            CsvService::outfile_path("NULL"),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_mongoexport_args() {
+        let database = DatabaseContainer {
+            name: "mongo".to_string(),
+            db_type: "MongoDB".to_string(),
+            stored_username: Some("admin".to_string()),
+            stored_password: Some("secret".to_string()),
+            ..Default::default()
+        };
+
+        let args = CsvService::mongoexport_args(
+            &database,
+            Path::new("/tmp/db.env"),
+            "app",
+            "events",
+            &strings(&["_id", "kind"]),
+            false,
+        );
+
+        assert_eq!(args[0..4], ["exec", "--env-file", "/tmp/db.env", "mongo"]);
+        assert!(args.contains(&"mongoexport".to_string()));
+        assert!(args.contains(&"--fields=_id,kind".to_string()));
+        assert!(args.contains(&"--noHeaderLine".to_string()));
+        assert!(!args.iter().any(|arg| arg.contains("secret")));
+
+        assert_eq!(
+            CsvService::mongo_credentials(&database)[0],
+            ("DDM_MONGO_TOOLS_CONFIG", "password: \"secret\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_export_options_default_to_a_header() {
+        let options: TableExportOptions = serde_json::from_str("{}").unwrap();
+
+        assert!(options.header);
+        assert!(options.columns.is_empty());
+    }
+
+    #[test]
+    fn test_parse_csv_record_handles_quotes() {
+        assert_eq!(
+            parse_csv_record("1,\"Doe, Jane\",\"say \"\"hi\"\"\"\r\n", ','),
+            strings(&["1", "Doe, Jane", "say \"hi\""])
+        );
+        assert_eq!(parse_csv_record("a;b;", ';'), strings(&["a", "b", ""]));
+    }
+
+    #[test]
+    fn test_records_span_quoted_line_breaks() {
+        assert_eq!(first_record_len(b"id,note\n1,x\n"), Some(8));
+        assert_eq!(first_record_len(b"\"a\nb\",c\n1,2\n"), Some(8));
+        assert_eq!(first_record_len(b"no line break"), None);
+
+        let mut counter = CsvRecordCounter::default();
+        counter.push(b"1,\"multi");
+        counter.push(b"\nline\"\r\n\r\n2,x");
+        assert_eq!(counter.finish(), 2);
+    }
+
+    #[test]
+    fn test_import_statements() {
+        assert_eq!(
+            CsvService::postgres_import_sql("public", "users", &strings(&["id", "email"]), ';'),
+            "COPY \"public\".\"users\" (\"id\", \"email\") FROM STDIN WITH (FORMAT csv, DELIMITER ';')"
+        );
+
+        let sql = CsvService::mysql_import_sql("shop", "orders", &[], ',', true);
+        assert!(sql.starts_with("SET SESSION max_error_count = 65535; LOAD DATA LOCAL INFILE '/dev/stdin' INTO TABLE `shop`.`orders`"));
+        assert!(
+            sql.ends_with("LINES TERMINATED BY '\\r\\n'; SHOW WARNINGS; SHOW COUNT(*) WARNINGS")
+        );
+    }
+
+    #[test]
+    fn test_mongoimport_needs_fields_and_a_known_delimiter() {
+        let database = DatabaseContainer {
+            name: "mongo".to_string(),
+            db_type: "MongoDB".to_string(),
+            ..Default::default()
+        };
+
+        let env_file = Path::new("/tmp/db.env");
+        let args = CsvService::mongoimport_args(
+            &database,
+            env_file,
+            "app",
+            "events",
+            &strings(&["a"]),
+            '\t',
+        )
+        .unwrap();
+        assert_eq!(
+            args[0..5],
+            ["exec", "-i", "--env-file", "/tmp/db.env", "mongo"]
+        );
+        assert!(args.contains(&"mongoimport".to_string()));
+        assert!(args.contains(&"--type=tsv".to_string()));
+
+        assert!(
+            CsvService::mongoimport_args(&database, env_file, "app", "events", &[], ',').is_err()
+        );
+        assert!(CsvService::mongoimport_args(
+            &database,
+            env_file,
+            "app",
+            "events",
+            &strings(&["a"]),
+            ';'
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_postgres_row_error_points_at_the_file_line() {
+        let stderr = "ERROR:  invalid input syntax for type integer: \"x\"\n\
+CONTEXT:  COPY users, line 3, column id: \"x\"";
+
+        assert_eq!(
+            CsvService::postgres_row_error(stderr, 1),
+            Some(CsvRowError {
+                line: Some(4),
+                message: "invalid input syntax for type integer: \"x\"".to_string(),
+            })
+        );
+        assert_eq!(
+            CsvService::postgres_row_error("psql: connection refused", 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_mysql_row_errors_count_skipped_duplicates() {
+        let output = "Warning\t1366\tIncorrect integer value: 'x' for column 'id' at row 2\n\
+Warning\t1062\tDuplicate entry '1' for key 'PRIMARY'\n";
+
+        let (errors, skipped) = CsvService::mysql_row_errors(output, 1);
+
+        assert_eq!(skipped, 1);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line, Some(3));
+        assert_eq!(errors[1].line, None);
+    }
+
+    #[test]
+    fn test_mysql_row_errors_report_unlisted_warnings() {
+        let output =
+            "Warning\t1366\tIncorrect integer value: 'x' for column 'id' at row 2\n70000\n";
+
+        let (errors, _) = CsvService::mysql_row_errors(output, 0);
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[1]
+            .message
+            .starts_with("69999 more warnings were not listed"));
+    }
+
+    #[test]
+    fn test_mongoimport_report() {
+        let stderr =
+            "2024-05-01T10:00:00.000+0000\tcontinuing through error: E11000 duplicate key error\n\
+2024-05-01T10:00:00.100+0000\t4 document(s) imported successfully. 1 document(s) failed to import.";
+
+        let report = CsvService::mongoimport_report(stderr);
+
+        assert_eq!(report.rows_imported, 4);
+        assert_eq!(
+            report.errors,
+            vec![CsvRowError {
+                line: None,
+                message: "continuing through error: E11000 duplicate key error".to_string(),
+            }]
+        );
+    }
+}
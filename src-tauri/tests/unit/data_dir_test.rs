@@ -0,0 +1,82 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod data_dir_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_directory_uses_disk_usage() {
+        let entry =
+            DataDirEntry::parse_line("1048576\tdirectory\t4096\t1715331600\tpg_wal").unwrap();
+
+        assert_eq!(entry.name, "pg_wal");
+        assert_eq!(entry.kind, DataDirEntryKind::Directory);
+        assert_eq!(entry.size_bytes, 1024 * 1024 * 1024);
+        assert_eq!(
+            entry.modified_at.as_deref(),
+            Some("2024-05-10T09:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_parse_file_uses_length() {
+        let file =
+            DataDirEntry::parse_line("8\tregular file\t5000\t1715331600\tcore.1234").unwrap();
+        let empty =
+            DataDirEntry::parse_line("0\tregular empty file\t0\t1715331600\tpostmaster.opts")
+                .unwrap();
+        let link = DataDirEntry::parse_line("0\tsymbolic link\t12\t1715331600\tlog").unwrap();
+
+        assert_eq!(file.kind, DataDirEntryKind::File);
+        assert_eq!(file.size_bytes, 5000);
+        assert_eq!(empty.kind, DataDirEntryKind::File);
+        assert_eq!(link.kind, DataDirEntryKind::Symlink);
+    }
+
+    #[test]
+    fn test_parse_keeps_tabs_in_names_and_skips_noise() {
+        let entry = DataDirEntry::parse_line("4\tregular file\t10\t0\tname\twith tab").unwrap();
+
+        assert_eq!(entry.name, "name\twith tab");
+        assert!(DataDirEntry::parse_line("du: cannot read directory").is_none());
+        assert!(DataDirEntry::parse_line("").is_none());
+    }
+
+    #[test]
+    fn test_listing_sorts_largest_first() {
+        let entry = |name: &str, size_bytes| DataDirEntry {
+            name: name.to_string(),
+            kind: DataDirEntryKind::File,
+            size_bytes,
+            modified_at: None,
+        };
+
+        let listing = DataDirListing::new(
+            "/var/lib/postgresql/data",
+            "",
+            vec![entry("b", 10), entry("a", 10), entry("c", 30)],
+            false,
+        );
+
+        let names: Vec<&str> = listing.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["c", "a", "b"]);
+        assert_eq!(listing.total_bytes, 50);
+    }
+
+    #[test]
+    fn test_normalize_path_stays_inside() {
+        assert_eq!(DataDirListing::normalize_path("").unwrap(), "");
+        assert_eq!(
+            DataDirListing::normalize_path("/base//16384/./").unwrap(),
+            "base/16384"
+        );
+        assert!(DataDirListing::normalize_path("../etc").is_err());
+        assert!(DataDirListing::normalize_path("base/../../etc").is_err());
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(DataDirListing::join("/data", ""), "/data");
+        assert_eq!(DataDirListing::join("/data/", "pg_wal"), "/data/pg_wal");
+    }
+}
@@ -0,0 +1,184 @@
+use docker_db_manager_lib::types::{
+    DatabaseContainer, DockerContainerState, DockerRunArgs, StorageState, VolumeMount,
+};
+use std::collections::{HashMap, HashSet};
+
+#[cfg(test)]
+mod database_container_tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_store_entry_gets_defaults() {
+        let json = r#"{
+            "id": "1",
+            "name": "my-db",
+            "db_type": "PostgreSQL",
+            "version": "16",
+            "status": "running",
+            "port": 5432,
+            "created_at": "2025-01-01",
+            "max_connections": 100,
+            "container_id": null,
+            "stored_password": null,
+            "stored_username": null,
+            "stored_database_name": null,
+            "stored_persist_data": true,
+            "stored_enable_auth": true
+        }"#;
+
+        let database: DatabaseContainer = serde_json::from_str(json).unwrap();
+
+        assert!(database.tags.is_empty());
+        assert!(!database.favorite);
+        assert!(database.project.is_none());
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = vec![
+            " backend ".to_string(),
+            "".to_string(),
+            "Backend".to_string(),
+            "client-x".to_string(),
+        ];
+
+        assert_eq!(
+            DatabaseContainer::normalize_tags(tags),
+            vec!["backend".to_string(), "client-x".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_has_tag_ignores_case() {
+        let database = DatabaseContainer {
+            tags: vec!["Backend".to_string()],
+            ..Default::default()
+        };
+
+        assert!(database.has_tag("backend"));
+        assert!(!database.has_tag("frontend"));
+    }
+
+    #[test]
+    fn test_migrate_date_only_created_at() {
+        let mut database = DatabaseContainer {
+            created_at: "2025-01-01".to_string(),
+            ..Default::default()
+        };
+
+        assert!(database.migrate_created_at());
+        assert_eq!(database.created_at, "2025-01-01T00:00:00+00:00");
+        // Already migrated entries are left alone
+        assert!(!database.migrate_created_at());
+    }
+
+    #[test]
+    fn test_mark_stopped_records_stop_time_once() {
+        let mut database = DatabaseContainer {
+            status: "running".to_string(),
+            ..Default::default()
+        };
+
+        database.mark_stopped();
+        let stopped_at = database.last_stopped_at.clone();
+        assert_eq!(database.status, "stopped");
+        assert!(stopped_at.is_some());
+
+        database.mark_stopped();
+        assert_eq!(database.last_stopped_at, stopped_at);
+    }
+
+    #[test]
+    fn test_status_follows_health_and_exit_code() {
+        let status = |ps: &str| DockerContainerState::from_ps_status("abc123", ps).status();
+
+        assert_eq!(status("Up 2 minutes"), "running");
+        assert_eq!(status("Up 2 minutes (healthy)"), "running");
+        assert_eq!(status("Up 5 seconds (health: starting)"), "starting");
+        assert_eq!(status("Up 2 minutes (unhealthy)"), "unhealthy");
+        assert_eq!(status("Restarting (1) 3 seconds ago"), "restarting");
+        assert_eq!(status("Exited (137) 5 seconds ago"), "exited");
+        assert_eq!(status("Exited (0) 5 seconds ago"), "stopped");
+        assert_eq!(status("Created"), "stopped");
+    }
+
+    #[test]
+    fn test_apply_docker_state_keeps_exit_code_of_crash() {
+        let mut database = DatabaseContainer::default();
+
+        database.apply_docker_state(&DockerContainerState::from_ps_status(
+            "abc123",
+            "Exited (137) 5 seconds ago",
+        ));
+        assert_eq!(database.status, "exited");
+        assert_eq!(database.exit_code, Some(137));
+        assert!(!database.is_running());
+
+        database.apply_docker_state(&DockerContainerState::from_ps_status(
+            "abc123",
+            "Up 1 second (unhealthy)",
+        ));
+        assert_eq!(database.status, "unhealthy");
+        assert_eq!(database.exit_code, None);
+        assert!(database.is_running());
+    }
+
+    #[test]
+    fn test_migrate_unknown_status() {
+        let mut database = DatabaseContainer {
+            status: "missing".to_string(),
+            ..Default::default()
+        };
+
+        assert!(database.migrate_status());
+        assert_eq!(database.status, "stopped");
+
+        database.status = "unhealthy".to_string();
+        assert!(!database.migrate_status());
+        assert_eq!(database.status, "unhealthy");
+    }
+
+    #[test]
+    fn test_data_volumes_fall_back_to_provider_naming() {
+        let mut database = DatabaseContainer {
+            name: "my-db".to_string(),
+            stored_persist_data: true,
+            ..Default::default()
+        };
+        assert_eq!(database.data_volumes(), vec!["my-db-data".to_string()]);
+
+        database.stored_docker_args = Some(DockerRunArgs {
+            image: "postgres:16".to_string(),
+            env_vars: HashMap::new(),
+            ports: vec![],
+            volumes: vec![VolumeMount {
+                name: "custom-data".to_string(),
+                path: "/var/lib/postgresql/data".to_string(),
+            }],
+            command: vec![],
+            shm_size: None,
+        });
+        assert_eq!(database.data_volumes(), vec!["custom-data".to_string()]);
+
+        database.stored_persist_data = false;
+        assert!(database.data_volumes().is_empty());
+    }
+
+    #[test]
+    fn test_storage_state_flags_missing_volume() {
+        let database = DatabaseContainer {
+            name: "my-db".to_string(),
+            stored_persist_data: true,
+            ..Default::default()
+        };
+        let mut volumes = HashSet::from(["other-data".to_string()]);
+
+        assert_eq!(
+            database.storage_state_in(&volumes),
+            StorageState::VolumeMissing
+        );
+
+        volumes.insert("my-db-data".to_string());
+        assert_eq!(database.storage_state_in(&volumes), StorageState::Ok);
+    }
+}
@@ -0,0 +1,176 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod database_filter_tests {
+    use super::*;
+
+    fn create_test_database() -> DatabaseContainer {
+        DatabaseContainer {
+            id: "1".to_string(),
+            name: "Shop-Postgres".to_string(),
+            db_type: "PostgreSQL".to_string(),
+            status: "running".to_string(),
+            port: 5432,
+            tags: vec!["backend".to_string()],
+            favorite: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        assert!(DatabaseFilter::default().matches(&create_test_database()));
+    }
+
+    #[test]
+    fn test_filter_by_each_field() {
+        let database = create_test_database();
+
+        let matching = [
+            DatabaseFilter {
+                db_type: Some("postgresql".to_string()),
+                ..Default::default()
+            },
+            DatabaseFilter {
+                status: Some("running".to_string()),
+                ..Default::default()
+            },
+            DatabaseFilter {
+                tag: Some("Backend".to_string()),
+                ..Default::default()
+            },
+            DatabaseFilter {
+                name: Some("postgres".to_string()),
+                ..Default::default()
+            },
+            DatabaseFilter {
+                port: Some(5432),
+                ..Default::default()
+            },
+            DatabaseFilter {
+                favorite: Some(true),
+                ..Default::default()
+            },
+        ];
+        for filter in &matching {
+            assert!(filter.matches(&database), "{:?}", filter);
+        }
+
+        let filter = DatabaseFilter {
+            status: Some("stopped".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&database));
+    }
+
+    #[test]
+    fn test_filters_are_combined() {
+        let filter = DatabaseFilter {
+            db_type: Some("PostgreSQL".to_string()),
+            port: Some(3306),
+            ..Default::default()
+        };
+
+        assert!(!filter.matches(&create_test_database()));
+    }
+
+    #[test]
+    fn test_filter_deserializes_from_camel_case() {
+        let filter: DatabaseFilter =
+            serde_json::from_str(r#"{ "dbType": "Redis", "favorite": false }"#).unwrap();
+
+        assert_eq!(filter.db_type, Some("Redis".to_string()));
+        assert_eq!(filter.favorite, Some(false));
+        assert!(filter.tag.is_none());
+    }
+
+    fn create_named_database(name: &str, db_type: &str, created_at: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: name.to_string(),
+            name: name.to_string(),
+            db_type: db_type.to_string(),
+            created_at: created_at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn names(page: &DatabasePage) -> Vec<&str> {
+        page.items.iter().map(|db| db.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_page_is_sorted_by_name_by_default() {
+        let page = DatabasePage::from_matches(
+            vec![
+                create_named_database("cache", "Redis", "2025-01-02"),
+                create_named_database("Api", "PostgreSQL", "2025-01-03"),
+                create_named_database("blog", "MySQL", "2025-01-01"),
+            ],
+            DatabaseSort::default(),
+            0,
+            None,
+        );
+
+        assert_eq!(names(&page), vec!["Api", "blog", "cache"]);
+        assert_eq!(page.total, 3);
+    }
+
+    #[test]
+    fn test_page_sorted_descending_by_key() {
+        let page = DatabasePage::from_matches(
+            vec![
+                create_named_database("cache", "Redis", "2025-01-02"),
+                create_named_database("api", "PostgreSQL", "2025-01-03"),
+                create_named_database("blog", "MySQL", "2025-01-01"),
+            ],
+            DatabaseSort {
+                key: DatabaseSortKey::CreatedAt,
+                descending: true,
+            },
+            0,
+            None,
+        );
+
+        assert_eq!(names(&page), vec!["api", "cache", "blog"]);
+    }
+
+    #[test]
+    fn test_never_started_sorts_first() {
+        let mut started = create_named_database("started", "Redis", "2025-01-01");
+        started.last_started_at = Some("2025-02-01T10:00:00+00:00".to_string());
+        let never = create_named_database("never", "Redis", "2025-01-01");
+
+        let page = DatabasePage::from_matches(
+            vec![started, never],
+            DatabaseSort {
+                key: DatabaseSortKey::LastStarted,
+                descending: false,
+            },
+            0,
+            None,
+        );
+
+        assert_eq!(names(&page), vec!["never", "started"]);
+    }
+
+    #[test]
+    fn test_offset_and_limit_keep_total() {
+        let databases = (0..5)
+            .map(|i| create_named_database(&format!("db-{}", i), "Redis", "2025-01-01"))
+            .collect();
+
+        let page = DatabasePage::from_matches(databases, DatabaseSort::default(), 2, Some(2));
+
+        assert_eq!(names(&page), vec!["db-2", "db-3"]);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.offset, 2);
+    }
+
+    #[test]
+    fn test_sort_deserializes_from_camel_case() {
+        let sort: DatabaseSort = serde_json::from_str(r#"{ "key": "lastStarted" }"#).unwrap();
+
+        assert!(matches!(sort.key, DatabaseSortKey::LastStarted));
+        assert!(!sort.descending);
+    }
+}
@@ -0,0 +1,92 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod deep_link_tests {
+    use super::*;
+
+    fn parse_create(link: &str) -> CreatePrefill {
+        match DeepLinkAction::parse(link).unwrap() {
+            DeepLinkAction::Create(prefill) => prefill,
+        }
+    }
+
+    #[test]
+    fn test_parse_create_link() {
+        let prefill = parse_create("dbmanager://create?type=postgres&version=16&port=5433");
+
+        assert_eq!(prefill.db_type, "PostgreSQL");
+        assert_eq!(prefill.version.as_deref(), Some("16"));
+        assert_eq!(prefill.port, Some(5433));
+        assert_eq!(prefill.name, None);
+        assert!(!prefill.direct);
+    }
+
+    #[test]
+    fn test_parse_direct_create_link() {
+        let prefill = parse_create("dbmanager://create?type=Redis&name=cache%20dev&direct=1");
+
+        assert_eq!(prefill.db_type, "Redis");
+        assert_eq!(prefill.name.as_deref(), Some("cache dev"));
+        assert!(prefill.direct);
+    }
+
+    #[test]
+    fn test_provider_aliases() {
+        assert_eq!(
+            CreatePrefill::provider_id("mongo").as_deref(),
+            Some("MongoDB")
+        );
+        assert_eq!(
+            CreatePrefill::provider_id("MSSQL").as_deref(),
+            Some("SQLServer")
+        );
+        assert_eq!(CreatePrefill::provider_id("oracle"), None);
+    }
+
+    #[test]
+    fn test_invalid_links_are_rejected() {
+        assert!(DeepLinkAction::parse("https://create?type=postgres").is_err());
+        assert!(DeepLinkAction::parse("dbmanager://delete?id=1").is_err());
+        assert!(DeepLinkAction::parse("dbmanager://create?version=16").is_err());
+        assert!(DeepLinkAction::parse("dbmanager://create?type=oracle").is_err());
+        assert!(DeepLinkAction::parse("dbmanager://create?type=redis&port=70000").is_err());
+    }
+
+    #[test]
+    fn test_window_query_round_trip() {
+        let prefill =
+            parse_create("dbmanager://create?type=mysql&port=3307&name=my%20db&direct=true");
+
+        assert_eq!(
+            prefill.window_query(),
+            "type=MySQL&port=3307&name=my+db&direct=1"
+        );
+        assert_eq!(
+            parse_create(&format!("dbmanager://create?{}", prefill.window_query())),
+            prefill
+        );
+    }
+
+    #[test]
+    fn test_preset_fills_in_missing_values() {
+        let prefill = parse_create("dbmanager://create?preset=rails&version=16");
+
+        assert_eq!(prefill.framework, Some(Framework::Rails));
+        assert_eq!(prefill.db_type, "PostgreSQL");
+        assert_eq!(prefill.version.as_deref(), Some("16"));
+        assert_eq!(prefill.database_name.as_deref(), Some("app_development"));
+        assert_eq!(prefill.username.as_deref(), Some("rails"));
+        assert!(DeepLinkAction::parse("dbmanager://create?preset=express").is_err());
+    }
+
+    #[test]
+    fn test_preset_round_trip() {
+        let prefill = parse_create("dbmanager://create?preset=laravel&user=admin");
+
+        assert_eq!(prefill.username.as_deref(), Some("admin"));
+        assert_eq!(
+            parse_create(&format!("dbmanager://create?{}", prefill.window_query())),
+            prefill
+        );
+    }
+}
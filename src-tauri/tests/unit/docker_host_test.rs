@@ -0,0 +1,120 @@
+use docker_db_manager_lib::types::*;
+use std::collections::{HashMap, HashSet};
+
+#[cfg(test)]
+mod docker_host_tests {
+    use super::*;
+
+    fn host(id: &str, url: &str) -> DockerHost {
+        DockerHost {
+            id: id.to_string(),
+            name: format!("Server {}", id),
+            url: url.to_string(),
+        }
+    }
+
+    fn database(id: &str, host: Option<&str>) -> DatabaseContainer {
+        DatabaseContainer {
+            id: id.to_string(),
+            name: id.to_string(),
+            host: host.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_host_urls_use_a_docker_host_scheme() {
+        assert!(host("a", "ssh://deploy@build-server")
+            .violations()
+            .is_empty());
+        assert!(host("a", "tcp://10.0.0.5:2376").violations().is_empty());
+
+        assert_eq!(host("a", "build-server").violations().len(), 1);
+        assert_eq!(host("a", "ssh://").violations().len(), 1);
+        assert_eq!(host("a", "ssh://build server").violations().len(), 1);
+    }
+
+    #[test]
+    fn test_host_address_is_the_machine_of_the_url() {
+        let address = |url: &str| host("a", url).address();
+
+        assert_eq!(
+            address("ssh://deploy@build-server"),
+            Some("build-server".to_string())
+        );
+        assert_eq!(
+            address("ssh://deploy@build-server:2222"),
+            Some("build-server".to_string())
+        );
+        assert_eq!(address("tcp://10.0.0.5:2376"), Some("10.0.0.5".to_string()));
+        assert_eq!(
+            address("tcp://[fd00::5]:2376"),
+            Some("[fd00::5]".to_string())
+        );
+        assert_eq!(address("unix:///var/run/docker.sock"), None);
+    }
+
+    #[test]
+    fn test_local_id_is_reserved() {
+        assert_eq!(
+            host(LOCAL_HOST_ID, "ssh://deploy@build-server")
+                .violations()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_databases_without_a_host_run_locally() {
+        let stored = r#"{
+            "id": "db-1", "name": "pg", "db_type": "PostgreSQL", "version": "16",
+            "status": "stopped", "port": 5432, "created_at": "2024-01-01T00:00:00Z",
+            "max_connections": 100, "container_id": null, "stored_password": null,
+            "stored_username": null, "stored_database_name": null,
+            "stored_persist_data": false, "stored_enable_auth": false
+        }"#;
+        let local: DatabaseContainer = serde_json::from_str(stored).unwrap();
+
+        assert_eq!(local.host, None);
+        assert_eq!(local.host_id(), LOCAL_HOST_ID);
+        assert_eq!(database("db", Some("remote")).host_id(), "remote");
+    }
+
+    #[test]
+    fn test_host_list_counts_databases_and_reachability() {
+        let hosts = vec![host("a", "ssh://deploy@a"), host("b", "tcp://b:2376")];
+        let databases: HashMap<String, DatabaseContainer> = [
+            database("one", None),
+            database("two", Some("a")),
+            database("three", Some("a")),
+        ]
+        .into_iter()
+        .map(|db| (db.id.clone(), db))
+        .collect();
+        let unreachable: HashSet<String> = ["b".to_string()].into();
+
+        let list = DockerHostStatus::list(&hosts, &databases, &unreachable);
+
+        let summary: Vec<(&str, bool, usize)> = list
+            .iter()
+            .map(|host| (host.id.as_str(), host.reachable, host.database_count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(LOCAL_HOST_ID, true, 1), ("a", true, 2), ("b", false, 0)]
+        );
+        assert_eq!(list[0].url, None);
+    }
+
+    #[test]
+    fn test_unreachable_daemon_names_the_host() {
+        let error = AppError::DockerUnavailable.on_host("Build server");
+
+        assert_eq!(error.code(), "HOST_UNREACHABLE");
+        assert_eq!(error.http_status(), 503);
+        assert!(error.to_string().contains("Build server"));
+
+        let other = AppError::NotFound("Container".to_string()).on_host("Build server");
+        assert_eq!(other.code(), "NOT_FOUND");
+    }
+}
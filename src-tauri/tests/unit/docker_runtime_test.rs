@@ -0,0 +1,151 @@
+use docker_db_manager_lib::types::{runtime_sockets, DockerEnvironment, DockerRuntime};
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod docker_runtime_tests {
+    use super::*;
+
+    fn socket(runtime: DockerRuntime, path: &str) -> (DockerRuntime, PathBuf) {
+        (runtime, PathBuf::from(path))
+    }
+
+    #[test]
+    fn test_runtime_from_socket() {
+        assert_eq!(
+            DockerRuntime::from_socket("unix:///Users/me/.colima/default/docker.sock"),
+            Some(DockerRuntime::Colima)
+        );
+        assert_eq!(
+            DockerRuntime::from_socket("unix:///Users/me/.orbstack/run/docker.sock"),
+            Some(DockerRuntime::OrbStack)
+        );
+        assert_eq!(
+            DockerRuntime::from_socket("unix:///var/run/docker.sock"),
+            Some(DockerRuntime::DockerEngine)
+        );
+        assert_eq!(DockerRuntime::from_socket("tcp://10.0.0.5:2376"), None);
+    }
+
+    #[test]
+    fn test_runtime_from_context() {
+        assert_eq!(
+            DockerRuntime::from_context("desktop-linux"),
+            Some(DockerRuntime::DockerDesktop)
+        );
+        assert_eq!(
+            DockerRuntime::from_context("colima-work"),
+            Some(DockerRuntime::Colima)
+        );
+        assert_eq!(DockerRuntime::from_context("remote"), None);
+    }
+
+    #[test]
+    fn test_docker_host_wins() {
+        let existing = [socket(DockerRuntime::DockerEngine, "/var/run/docker.sock")];
+        let environment = DockerEnvironment::detect(
+            Some("unix:///home/me/.rd/docker.sock"),
+            Some("desktop-linux"),
+            &existing,
+        );
+
+        assert_eq!(environment.runtime, Some(DockerRuntime::RancherDesktop));
+        assert_eq!(
+            environment.socket.as_deref(),
+            Some("unix:///home/me/.rd/docker.sock")
+        );
+    }
+
+    #[test]
+    fn test_context_is_left_to_the_cli() {
+        let existing = [socket(
+            DockerRuntime::Colima,
+            "/home/me/.colima/default/docker.sock",
+        )];
+        let environment = DockerEnvironment::detect(None, Some("colima"), &existing);
+
+        assert_eq!(environment.runtime, Some(DockerRuntime::Colima));
+        assert_eq!(environment.socket, None);
+        assert_eq!(environment.docker_host_override(), None);
+    }
+
+    #[test]
+    fn test_nonstandard_socket_is_overridden() {
+        let existing = [socket(
+            DockerRuntime::OrbStack,
+            "/Users/me/.orbstack/run/docker.sock",
+        )];
+        let environment = DockerEnvironment::detect(None, Some("default"), &existing);
+
+        assert_eq!(environment.context, None);
+        assert_eq!(
+            environment.docker_host_override().as_deref(),
+            Some("unix:///Users/me/.orbstack/run/docker.sock")
+        );
+    }
+
+    #[test]
+    fn test_default_socket_is_not_overridden() {
+        let existing = [
+            socket(DockerRuntime::DockerEngine, "/var/run/docker.sock"),
+            socket(DockerRuntime::Colima, "/home/me/.colima/docker.sock"),
+        ];
+        let environment = DockerEnvironment::detect(None, None, &existing);
+
+        assert_eq!(environment.runtime, Some(DockerRuntime::DockerEngine));
+        assert_eq!(
+            environment.installed,
+            vec![DockerRuntime::DockerEngine, DockerRuntime::Colima]
+        );
+        assert_eq!(environment.docker_host_override(), None);
+    }
+
+    #[test]
+    fn test_guidance() {
+        let environment = DockerEnvironment::detect(None, Some("colima"), &[]);
+        assert!(environment.guidance().contains("colima start"));
+
+        let environment = DockerEnvironment::detect(None, None, &[]);
+        assert!(environment.guidance().starts_with("No Docker runtime"));
+    }
+
+    #[test]
+    fn test_runtime_sockets_are_under_home() {
+        let sockets = runtime_sockets(Path::new("/home/me"));
+
+        assert_eq!(sockets[0].1, PathBuf::from("/var/run/docker.sock"));
+        assert!(sockets
+            .iter()
+            .skip(1)
+            .all(|(_, path)| path.starts_with("/home/me")));
+    }
+
+    #[test]
+    fn test_launch_commands_per_platform() {
+        assert_eq!(
+            DockerRuntime::DockerDesktop.launch_commands("macos"),
+            vec![vec!["open", "-a", "Docker"]]
+        );
+        assert_eq!(
+            DockerRuntime::Colima.launch_commands("linux"),
+            vec![vec!["colima", "start"]]
+        );
+
+        let engine = DockerRuntime::DockerEngine.launch_commands("linux");
+        assert_eq!(engine.len(), 2);
+        assert_eq!(engine[1][0], "pkexec");
+
+        assert!(DockerRuntime::OrbStack.launch_commands("linux").is_empty());
+    }
+
+    #[test]
+    fn test_platform_default_runtime() {
+        assert_eq!(
+            DockerRuntime::platform_default("linux"),
+            DockerRuntime::DockerEngine
+        );
+        assert_eq!(
+            DockerRuntime::platform_default("windows"),
+            DockerRuntime::DockerDesktop
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use docker_db_manager_lib::services::DockerService;
 use docker_db_manager_lib::types::docker::*;
+use docker_db_manager_lib::types::{DatabaseContainer, ImageResolution};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 #[cfg(test)]
 mod docker_service_tests {
@@ -15,15 +17,13 @@ mod docker_service_tests {
         DockerRunArgs {
             image: "postgres:16".to_string(),
             env_vars,
-            ports: vec![PortMapping {
-                host: 5432,
-                container: 5432,
-            }],
+            ports: vec![PortMapping::local(5432, 5432)],
             volumes: vec![VolumeMount {
                 name: "test-postgres-data".to_string(),
                 path: "/var/lib/postgresql/data".to_string(),
             }],
             command: vec![],
+            shm_size: None,
         }
     }
 
@@ -54,7 +54,7 @@ mod docker_service_tests {
 
         // Verify port mapping
         assert!(command.contains("-p"));
-        assert!(command.contains("5432:5432"));
+        assert!(command.contains("-p 127.0.0.1:5432:5432"));
     }
 
     #[test]
@@ -118,20 +118,33 @@ mod docker_service_tests {
         assert!(command.contains("secret"));
     }
 
+    #[test]
+    fn test_build_docker_command_with_shm_size() {
+        let service = DockerService::new();
+        let mut args = create_test_docker_args();
+        assert!(!service
+            .build_docker_command_from_args("test-db", &args)
+            .contains(&"--shm-size".to_string()));
+
+        args.shm_size = Some("512m".to_string());
+        let command_args = service.build_docker_command_from_args("test-db", &args);
+        let image = command_args
+            .iter()
+            .position(|arg| *arg == args.image)
+            .unwrap();
+        let shm = command_args
+            .iter()
+            .position(|arg| arg == "--shm-size")
+            .unwrap();
+        assert!(shm < image);
+        assert_eq!(command_args[shm + 1], "512m");
+    }
+
     #[test]
     fn test_build_docker_command_with_multiple_ports() {
         let service = DockerService::new();
         let mut args = create_test_docker_args();
-        args.ports = vec![
-            PortMapping {
-                host: 8080,
-                container: 80,
-            },
-            PortMapping {
-                host: 8443,
-                container: 443,
-            },
-        ];
+        args.ports = vec![PortMapping::local(8080, 80), PortMapping::local(8443, 443)];
 
         let command_args = service.build_docker_command_from_args("test-web", &args);
         let command = command_args.join(" ");
@@ -172,4 +185,238 @@ mod docker_service_tests {
         assert_eq!(recovered.image, "postgres:16");
         assert_eq!(recovered.ports.len(), 1);
     }
+
+    fn create_test_database() -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-id".to_string(),
+            name: "test-postgres".to_string(),
+            db_type: "PostgreSQL".to_string(),
+            port: 5432,
+            stored_password: Some("secret123".to_string()),
+            stored_docker_args: Some(create_test_docker_args()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_docker_run_command() {
+        let command = DockerService::new()
+            .docker_run_command(&create_test_database(), false)
+            .unwrap();
+
+        assert_eq!(
+            command,
+            "docker run -d --name test-postgres -p 5432:5432 \
+             -v test-postgres-data:/var/lib/postgresql/data \
+             -e POSTGRES_DB=testdb -e POSTGRES_PASSWORD=secret123 -e POSTGRES_USER=postgres \
+             postgres:16"
+        );
+    }
+
+    #[test]
+    fn test_docker_run_command_redacts_password() {
+        let command = DockerService::new()
+            .docker_run_command(&create_test_database(), true)
+            .unwrap();
+
+        assert!(command.contains("'POSTGRES_PASSWORD=<password>'"));
+        assert!(!command.contains("secret123"));
+    }
+
+    #[test]
+    fn test_docker_run_command_redacts_only_credentials() {
+        let mut database = create_test_database();
+        // A password that is also the user and database name
+        let docker_args = database.stored_docker_args.as_mut().unwrap();
+        for value in docker_args.env_vars.values_mut() {
+            *value = "postgres".to_string();
+        }
+
+        let command = DockerService::new()
+            .docker_run_command(&database, true)
+            .unwrap();
+
+        assert!(command.contains("'POSTGRES_PASSWORD=<password>'"));
+        assert!(command.contains("POSTGRES_USER=postgres"));
+        assert!(command.contains("POSTGRES_DB=postgres"));
+        assert!(command.ends_with(" postgres:16"));
+    }
+
+    #[test]
+    fn test_docker_run_command_needs_stored_args() {
+        let mut database = create_test_database();
+        database.stored_docker_args = None;
+
+        assert!(DockerService::new()
+            .docker_run_command(&database, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_shell_join_quotes_arguments() {
+        let args = vec![
+            "redis-server".to_string(),
+            "--save".to_string(),
+            "60 1".to_string(),
+            "it's".to_string(),
+        ];
+
+        assert_eq!(
+            DockerService::shell_join(&args),
+            "redis-server --save '60 1' 'it'\\''s'"
+        );
+    }
+
+    #[test]
+    fn test_query_cache_expires() {
+        let mut cache = QueryCache::new(Duration::from_millis(1500));
+        let now = Instant::now();
+
+        cache.insert("", 3, now);
+
+        assert_eq!(cache.get("", now + Duration::from_millis(1000)), Some(3));
+        assert_eq!(cache.get("", now + Duration::from_millis(1500)), None);
+    }
+
+    #[test]
+    fn test_query_cache_is_keyed_per_host() {
+        let mut cache = QueryCache::new(Duration::from_secs(2));
+        let now = Instant::now();
+
+        cache.insert("unix:///var/run/docker.sock", 1, now);
+
+        assert_eq!(cache.get("unix:///var/run/docker.sock", now), Some(1));
+        assert_eq!(cache.get("ssh://build-box", now), None);
+
+        cache.clear();
+        assert_eq!(cache.get("unix:///var/run/docker.sock", now), None);
+    }
+
+    #[test]
+    fn test_parse_inspect_output() {
+        let output = r#"[
+            {
+                "Id": "4f1c2d3e",
+                "Name": "/my-postgres",
+                "RestartCount": 2,
+                "State": {
+                    "Status": "running",
+                    "Running": true,
+                    "Restarting": false,
+                    "ExitCode": 0,
+                    "Health": { "Status": "healthy" }
+                },
+                "NetworkSettings": {
+                    "Ports": {
+                        "5432/tcp": [{ "HostIp": "0.0.0.0", "HostPort": "15432" }],
+                        "8080/tcp": null
+                    }
+                }
+            },
+            {
+                "Id": "9a8b7c6d",
+                "Name": "/my-redis",
+                "RestartCount": 0,
+                "State": { "Status": "exited", "Running": false, "ExitCode": 137 },
+                "NetworkSettings": { "Ports": {} }
+            }
+        ]"#;
+
+        let states = DockerContainerState::parse_inspect_output(output);
+
+        let postgres = &states["my-postgres"];
+        assert_eq!(postgres.id, "4f1c2d3e");
+        assert!(postgres.running);
+        assert_eq!(postgres.health.as_deref(), Some("healthy"));
+        assert_eq!(postgres.restart_count, 2);
+        assert_eq!(postgres.exit_code, None);
+        assert_eq!(
+            postgres.ports,
+            vec![PortMapping {
+                host: 15432,
+                container: 5432,
+                host_address: LAN_HOST_ADDRESS.to_string(),
+            }]
+        );
+
+        let redis = &states["my-redis"];
+        assert!(!redis.running);
+        assert_eq!(redis.exit_code, Some(137));
+        assert!(redis.ports.is_empty());
+    }
+
+    #[test]
+    fn test_parse_inspect_output_start_and_exit() {
+        let output = r#"[
+            {
+                "Id": "9a8b7c6d",
+                "Name": "/my-mysql",
+                "RestartCount": 1,
+                "State": {
+                    "Status": "exited",
+                    "Running": false,
+                    "ExitCode": 137,
+                    "OOMKilled": true,
+                    "StartedAt": "2024-05-10T09:00:00.123456789Z",
+                    "FinishedAt": "2024-05-10T23:10:00.5Z"
+                }
+            },
+            {
+                "Id": "1b2c3d4e",
+                "Name": "/my-redis",
+                "State": {
+                    "Status": "created",
+                    "StartedAt": "0001-01-01T00:00:00Z",
+                    "FinishedAt": "0001-01-01T00:00:00Z"
+                }
+            }
+        ]"#;
+
+        let states = DockerContainerState::parse_inspect_output(output);
+
+        let mysql = &states["my-mysql"];
+        assert!(mysql.oom_killed);
+        assert_eq!(mysql.exit_code, Some(137));
+        assert_eq!(
+            mysql.started_at.as_deref(),
+            Some("2024-05-10T09:00:00.123456789Z")
+        );
+        assert_eq!(mysql.finished_at.as_deref(), Some("2024-05-10T23:10:00.5Z"));
+
+        let redis = &states["my-redis"];
+        assert_eq!(redis.started_at, None);
+        assert_eq!(redis.finished_at, None);
+    }
+
+    #[test]
+    fn test_parse_inspect_output_without_containers() {
+        assert!(DockerContainerState::parse_inspect_output("[]\n").is_empty());
+        assert!(DockerContainerState::parse_inspect_output("").is_empty());
+    }
+
+    #[test]
+    fn test_image_resolution_needs_pull_when_not_local() {
+        let image = ImageResolution::new("postgres:17", None, Some("linux/arm64".to_string()));
+
+        assert!(image.pull_required);
+        assert!(!image.emulated);
+    }
+
+    #[test]
+    fn test_image_resolution_detects_emulation() {
+        let image = ImageResolution::new(
+            "mysql:5.7",
+            Some("linux/amd64".to_string()),
+            Some("linux/arm64".to_string()),
+        );
+        assert!(!image.pull_required);
+        assert!(image.emulated);
+
+        let native = ImageResolution::new(
+            "postgres:17",
+            Some("linux/arm64".to_string()),
+            Some("linux/arm64".to_string()),
+        );
+        assert!(!native.emulated);
+    }
 }
@@ -0,0 +1,123 @@
+use docker_db_manager_lib::services::ConfigService;
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod env_drift_tests {
+    use super::*;
+
+    fn env(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    fn expected() -> HashMap<String, String> {
+        HashMap::from([
+            ("POSTGRES_USER".to_string(), "postgres".to_string()),
+            ("POSTGRES_PASSWORD".to_string(), "secret".to_string()),
+            ("POSTGRES_DB".to_string(), "app".to_string()),
+        ])
+    }
+
+    const IMAGE: &[&str] = &[
+        "PATH=/usr/local/bin:/usr/bin",
+        "PG_MAJOR=16",
+        "PGDATA=/var/lib/postgresql/data",
+    ];
+
+    #[test]
+    fn test_matching_container_is_in_sync() {
+        let live = env(&[
+            "POSTGRES_USER=postgres",
+            "POSTGRES_PASSWORD=secret",
+            "POSTGRES_DB=app",
+            "PATH=/usr/local/bin:/usr/bin",
+            "PG_MAJOR=16",
+            "PGDATA=/var/lib/postgresql/data",
+        ]);
+        let report = EnvDriftReport::compare(
+            &expected(),
+            &live,
+            &env(IMAGE),
+            ConfigService::is_secret_env_var,
+        );
+
+        assert!(report.in_sync);
+        assert_eq!(report.variables.len(), 6);
+        let path = report.variables.iter().find(|v| v.key == "PATH").unwrap();
+        assert!(path.from_image);
+        let password = report
+            .variables
+            .iter()
+            .find(|v| v.key == "POSTGRES_PASSWORD")
+            .unwrap();
+        assert_eq!(password.value, None);
+        assert!(!password.from_image);
+    }
+
+    #[test]
+    fn test_drift_kinds() {
+        let live = env(&[
+            "POSTGRES_USER=postgres",
+            "POSTGRES_PASSWORD=changed",
+            "PATH=/usr/local/bin:/usr/bin",
+            "PG_MAJOR=16",
+            "PGDATA=/data/pg",
+            "TZ=Europe/Madrid",
+        ]);
+        let report = EnvDriftReport::compare(
+            &expected(),
+            &live,
+            &env(IMAGE),
+            ConfigService::is_secret_env_var,
+        );
+
+        assert!(!report.in_sync);
+        let kinds: Vec<(&str, EnvDriftKind)> = report
+            .drift
+            .iter()
+            .map(|drift| (drift.key.as_str(), drift.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                // Overrides the image's value
+                ("PGDATA", EnvDriftKind::Unexpected),
+                ("POSTGRES_DB", EnvDriftKind::Missing),
+                ("POSTGRES_PASSWORD", EnvDriftKind::Changed),
+                ("TZ", EnvDriftKind::Unexpected),
+            ]
+        );
+
+        let password = &report.drift[2];
+        assert!(password.secret);
+        assert_eq!((&password.expected, &password.actual), (&None, &None));
+
+        let database = &report.drift[1];
+        assert_eq!(database.expected.as_deref(), Some("app"));
+        assert_eq!(database.actual, None);
+    }
+
+    #[test]
+    fn test_entries_without_value() {
+        let report = EnvDriftReport::compare(
+            &HashMap::new(),
+            &env(&["EMPTY=", "FLAG", "URL=a=b"]),
+            &[],
+            ConfigService::is_secret_env_var,
+        );
+
+        let values: Vec<(&str, Option<&str>)> = report
+            .variables
+            .iter()
+            .map(|v| (v.key.as_str(), v.value.as_deref()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                ("EMPTY", Some("")),
+                ("FLAG", Some("")),
+                ("URL", Some("a=b"))
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use docker_db_manager_lib::services::EventService;
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod event_service_tests {
+    use super::*;
+
+    fn database(id: &str, status: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: id.to_string(),
+            name: format!("{}-name", id),
+            status: status.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn snapshot(databases: &[DatabaseContainer]) -> HashMap<String, DatabaseContainer> {
+        databases
+            .iter()
+            .map(|database| (database.id.clone(), database.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_status_changes_only_reports_changed_and_new_containers() {
+        let before = snapshot(&[database("a", "running"), database("b", "running")]);
+        let after = snapshot(&[
+            database("a", "running"),
+            database("b", "exited"),
+            database("c", "running"),
+        ]);
+
+        let mut changed: Vec<&str> = EventService::status_changes(&before, &after)
+            .into_iter()
+            .map(|database| database.id.as_str())
+            .collect();
+        changed.sort();
+
+        assert_eq!(changed, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_unchanged_snapshots_report_nothing() {
+        let before = snapshot(&[database("a", "stopped")]);
+
+        assert!(EventService::status_changes(&before, &before.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_status_payload_shape() {
+        let payload = ContainerStatusPayload {
+            id: "a".to_string(),
+            name: "pg".to_string(),
+            status: "running".to_string(),
+        };
+
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({ "id": "a", "name": "pg", "status": "running" })
+        );
+    }
+
+    #[test]
+    fn test_removed_payload_shape() {
+        let payload = ContainerRemovedPayload {
+            id: "a".to_string(),
+        };
+
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({ "id": "a" })
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use docker_db_manager_lib::services::FileCopyService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod file_copy_tests {
+    use super::*;
+
+    #[test]
+    fn test_container_path_must_be_absolute() {
+        assert_eq!(
+            container_copy_path(" /tmp/dump.sql ").unwrap(),
+            "/tmp/dump.sql"
+        );
+
+        match container_copy_path("./dump.sql") {
+            Err(AppError::Validation(message)) => assert!(message.ends_with("/tmp/dump.sql")),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(container_copy_path("/tmp/a\0b").is_err());
+    }
+
+    #[test]
+    fn test_copy_progress() {
+        assert_eq!(copy_progress(100, 400, Some(1000)), 300);
+        // Files written next to the destination are not counted past the total
+        assert_eq!(copy_progress(100, 5000, Some(1000)), 1000);
+        // The destination shrank, e.g. an existing file was truncated first
+        assert_eq!(copy_progress(500, 200, Some(1000)), 0);
+        assert_eq!(copy_progress(0, 2048, None), 2048);
+    }
+
+    #[test]
+    fn test_local_size_walks_directories() {
+        let root = std::env::temp_dir().join(format!("ddm-copy-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("nested")).unwrap();
+        std::fs::write(root.join("a.sql"), vec![0u8; 100]).unwrap();
+        std::fs::write(root.join("nested").join("b.sql"), vec![0u8; 50]).unwrap();
+
+        assert_eq!(FileCopyService::local_size(&root), 150);
+        assert_eq!(FileCopyService::local_size(&root.join("a.sql")), 100);
+        assert_eq!(FileCopyService::local_size(&root.join("missing")), 0);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
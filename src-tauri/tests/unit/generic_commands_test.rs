@@ -15,15 +15,13 @@ mod generic_commands_tests {
             docker_args: DockerRunArgs {
                 image: image.to_string(),
                 env_vars,
-                ports: vec![PortMapping {
-                    host: port,
-                    container: 5432,
-                }],
+                ports: vec![PortMapping::local(port, 5432)],
                 volumes: vec![VolumeMount {
                     name: format!("{}-data", name),
                     path: "/var/lib/postgresql/data".to_string(),
                 }],
                 command: vec![],
+                shm_size: None,
             },
             metadata: ContainerMetadata {
                 id: uuid::Uuid::new_v4().to_string(),
@@ -36,6 +34,7 @@ mod generic_commands_tests {
                 persist_data: true,
                 enable_auth: true,
                 max_connections: Some(100),
+                ..Default::default()
             },
         }
     }
@@ -55,15 +54,93 @@ mod generic_commands_tests {
 
     #[test]
     fn test_port_mapping() {
-        let port_mapping = PortMapping {
-            host: 5432,
-            container: 5432,
-        };
+        let port_mapping = PortMapping::local(5432, 5432);
 
         assert_eq!(port_mapping.host, 5432);
         assert_eq!(port_mapping.container, 5432);
     }
 
+    #[test]
+    fn test_port_mapping_defaults_to_loopback() {
+        let port_mapping: PortMapping =
+            serde_json::from_str(r#"{ "host": 5433, "container": 5432 }"#).unwrap();
+
+        assert_eq!(port_mapping.host_address, LOCAL_HOST_ADDRESS);
+        assert_eq!(port_mapping.publish_arg(), "127.0.0.1:5433:5432");
+        assert!(!port_mapping.is_exposed());
+    }
+
+    #[test]
+    fn test_publish_arg_brackets_ipv6() {
+        let port_mapping = PortMapping {
+            host_address: "::1".to_string(),
+            ..PortMapping::local(5433, 5432)
+        };
+
+        assert_eq!(port_mapping.publish_arg(), "[::1]:5433:5432");
+        assert!(!port_mapping.is_exposed());
+    }
+
+    #[test]
+    fn test_bind_ports_to_lan() {
+        let mut args = create_test_docker_request("test-db", "postgres:16", 5432).docker_args;
+
+        args.bind_ports(true);
+        assert!(args.ports.iter().all(PortMapping::is_exposed));
+        assert_eq!(args.ports[0].publish_arg(), "0.0.0.0:5432:5432");
+
+        args.bind_ports(false);
+        assert!(!args.ports.iter().any(PortMapping::is_exposed));
+    }
+
+    #[test]
+    fn test_extra_args_are_applied_once() {
+        let mut args = create_test_docker_request("test-db", "mysql:8.0", 3306).docker_args;
+        args.command = vec!["--max-connections=100".to_string()];
+        let extra_args = vec!["--innodb-buffer-pool-size=1G".to_string()];
+
+        args.apply_extra_args(&extra_args);
+        args.command
+            .push("--character-set-server=utf8mb4".to_string());
+        args.apply_extra_args(&extra_args);
+
+        assert_eq!(
+            args.command,
+            vec![
+                "--max-connections=100",
+                "--character-set-server=utf8mb4",
+                "--innodb-buffer-pool-size=1G",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stop_args_with_timeout() {
+        assert_eq!(docker_stop_args("abc123", None), vec!["stop", "abc123"]);
+        assert_eq!(
+            docker_stop_args("abc123", Some(120)),
+            vec!["stop", "-t", "120", "abc123"]
+        );
+    }
+
+    #[test]
+    fn test_inspected_address_of_empty_host_ip() {
+        let binding = serde_json::json!({ "HostIp": "", "HostPort": "5432" });
+        assert_eq!(PortMapping::inspected_address(&binding), LAN_HOST_ADDRESS);
+
+        let binding = serde_json::json!({ "HostIp": "127.0.0.1", "HostPort": "5432" });
+        assert_eq!(PortMapping::inspected_address(&binding), LOCAL_HOST_ADDRESS);
+    }
+
+    #[test]
+    fn test_lan_exposure_warning() {
+        assert_eq!(lan_exposure_warning(false, false), None);
+        assert!(lan_exposure_warning(true, false)
+            .unwrap()
+            .contains("without a password"));
+        assert!(lan_exposure_warning(true, true).is_some());
+    }
+
     #[test]
     fn test_volume_mount() {
         let volume = VolumeMount {
@@ -88,6 +165,7 @@ mod generic_commands_tests {
             persist_data: true,
             enable_auth: true,
             max_connections: Some(100),
+            ..Default::default()
         };
 
         assert_eq!(metadata.db_type, "PostgreSQL");
@@ -106,6 +184,7 @@ mod generic_commands_tests {
             ports: vec![],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         };
 
         assert_eq!(args.image, "postgres:16");
@@ -125,6 +204,7 @@ mod generic_commands_tests {
                 "--requirepass".to_string(),
                 "secret".to_string(),
             ],
+            shm_size: None,
         };
 
         assert_eq!(args.image, "redis:7");
@@ -139,18 +219,10 @@ mod generic_commands_tests {
             docker_args: DockerRunArgs {
                 image: "test:1.0".to_string(),
                 env_vars: HashMap::new(),
-                ports: vec![
-                    PortMapping {
-                        host: 8080,
-                        container: 80,
-                    },
-                    PortMapping {
-                        host: 8443,
-                        container: 443,
-                    },
-                ],
+                ports: vec![PortMapping::local(8080, 80), PortMapping::local(8443, 443)],
                 volumes: vec![],
                 command: vec![],
+                shm_size: None,
             },
             metadata: ContainerMetadata {
                 id: "test-id".to_string(),
@@ -163,6 +235,7 @@ mod generic_commands_tests {
                 persist_data: false,
                 enable_auth: false,
                 max_connections: None,
+                ..Default::default()
             },
         };
 
@@ -202,6 +275,7 @@ mod generic_commands_tests {
             ports: vec![],
             volumes: vec![],
             command: vec![],
+            shm_size: None,
         };
 
         assert_eq!(args.env_vars.len(), 3);
@@ -0,0 +1,99 @@
+use docker_db_manager_lib::services::GracefulStopService;
+use docker_db_manager_lib::types::*;
+use std::path::Path;
+
+#[cfg(test)]
+mod graceful_stop_tests {
+    use super::*;
+
+    fn database(db_type: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-1".to_string(),
+            name: "orders".to_string(),
+            db_type: db_type.to_string(),
+            status: "running".to_string(),
+            stored_username: Some("app".to_string()),
+            stored_password: Some("secret".to_string()),
+            stored_persist_data: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_postgres_stops_as_the_server_user() {
+        let args = GracefulStopService::exec_args(&database("PostgreSQL"), None).unwrap();
+
+        assert_eq!(
+            args,
+            vec!["exec", "-u", "postgres", "orders", "pg_ctl", "stop", "-m", "fast", "-w"]
+        );
+    }
+
+    #[test]
+    fn test_mysql_family_uses_its_admin_tool() {
+        let args =
+            GracefulStopService::exec_args(&database("MySQL"), Some(Path::new("/tmp/db.env")))
+                .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "exec",
+                "--env-file",
+                "/tmp/db.env",
+                "orders",
+                "mysqladmin",
+                "-u",
+                "root",
+                "shutdown",
+            ]
+        );
+
+        assert_eq!(
+            GracefulStopService::exec_credentials(&database("MySQL")),
+            vec![("MYSQL_PWD", "secret".to_string())]
+        );
+
+        let args = GracefulStopService::exec_args(&database("MariaDB"), None).unwrap();
+        assert!(args.contains(&"mariadb-admin".to_string()));
+    }
+
+    #[test]
+    fn test_redis_saves_before_shutting_down() {
+        let args = GracefulStopService::exec_args(&database("Redis"), None).unwrap();
+        assert_eq!(
+            args,
+            vec!["exec", "orders", "redis-cli", "SHUTDOWN", "SAVE"]
+        );
+
+        let mut secured = database("Redis");
+        secured.stored_enable_auth = true;
+        assert_eq!(
+            GracefulStopService::exec_credentials(&secured),
+            vec![("REDISCLI_AUTH", "secret".to_string())]
+        );
+        assert!(GracefulStopService::exec_credentials(&database("Redis")).is_empty());
+    }
+
+    #[test]
+    fn test_mongo_password_stays_off_the_command() {
+        let mut secured = database("MongoDB");
+        secured.stored_enable_auth = true;
+
+        let args = GracefulStopService::exec_args(&secured, None).unwrap();
+        assert!(!args.iter().any(|arg| arg.contains("secret")));
+        assert!(args
+            .last()
+            .unwrap()
+            .ends_with("db.getSiblingDB('admin').shutdownServer()"));
+        assert_eq!(
+            GracefulStopService::exec_credentials(&secured),
+            vec![("DDM_MONGO_PASSWORD", "secret".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_engines_without_clean_shutdown() {
+        assert!(GracefulStopService::exec_args(&database("SQLServer"), None).is_none());
+        assert!(GracefulStopService::exec_args(&database("Elasticsearch"), None).is_none());
+    }
+}
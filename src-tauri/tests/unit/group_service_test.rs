@@ -0,0 +1,197 @@
+use docker_db_manager_lib::services::GroupService;
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod group_service_tests {
+    use super::*;
+
+    fn create_test_database(id: &str, project: Option<&str>) -> DatabaseContainer {
+        DatabaseContainer {
+            id: id.to_string(),
+            name: format!("{}-name", id),
+            project: project.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn create_test_group(order: &[&str]) -> ContainerGroup {
+        ContainerGroup {
+            name: "shop".to_string(),
+            order: order.iter().map(|id| id.to_string()).collect(),
+            created_at: "2025-01-01".to_string(),
+            network: None,
+            dependencies: HashMap::new(),
+        }
+    }
+
+    fn database_map(databases: Vec<DatabaseContainer>) -> HashMap<String, DatabaseContainer> {
+        databases
+            .into_iter()
+            .map(|db| (db.id.clone(), db))
+            .collect()
+    }
+
+    fn ids(members: &[DatabaseContainer]) -> Vec<&str> {
+        members.iter().map(|db| db.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_members_follow_explicit_order() {
+        let databases = database_map(vec![
+            create_test_database("postgres", Some("shop")),
+            create_test_database("redis", Some("shop")),
+        ]);
+
+        let members =
+            GroupService::ordered_members(&create_test_group(&["redis", "postgres"]), &databases);
+
+        assert_eq!(ids(&members), vec!["redis", "postgres"]);
+    }
+
+    #[test]
+    fn test_unordered_members_are_appended_by_name() {
+        let databases = database_map(vec![
+            create_test_database("mongo", Some("shop")),
+            create_test_database("elastic", Some("shop")),
+            create_test_database("postgres", Some("shop")),
+        ]);
+
+        let members = GroupService::ordered_members(&create_test_group(&["postgres"]), &databases);
+
+        assert_eq!(ids(&members), vec!["postgres", "elastic", "mongo"]);
+    }
+
+    #[test]
+    fn test_non_members_and_stale_ids_are_skipped() {
+        let databases = database_map(vec![
+            create_test_database("postgres", Some("shop")),
+            create_test_database("redis", Some("blog")),
+            create_test_database("mysql", None),
+        ]);
+
+        let members = GroupService::ordered_members(
+            &create_test_group(&["removed", "redis", "postgres"]),
+            &databases,
+        );
+
+        assert_eq!(ids(&members), vec!["postgres"]);
+    }
+
+    fn depend(group: &mut ContainerGroup, id: &str, depends_on: &[&str]) {
+        group.dependencies.insert(
+            id.to_string(),
+            depends_on.iter().map(|id| id.to_string()).collect(),
+        );
+    }
+
+    fn shop_databases() -> HashMap<String, DatabaseContainer> {
+        database_map(vec![
+            create_test_database("postgres", Some("shop")),
+            create_test_database("pgbouncer", Some("shop")),
+            create_test_database("redis", Some("shop")),
+        ])
+    }
+
+    #[test]
+    fn test_start_order_puts_dependencies_first() {
+        let databases = shop_databases();
+        let mut group = create_test_group(&["redis", "pgbouncer", "postgres"]);
+        depend(&mut group, "pgbouncer", &["postgres"]);
+        depend(&mut group, "redis", &["postgres"]);
+
+        let members = GroupService::start_order(&group, &databases).unwrap();
+
+        assert_eq!(ids(&members), vec!["postgres", "redis", "pgbouncer"]);
+        assert!(GroupService::has_dependents(&group, "postgres"));
+        assert!(!GroupService::has_dependents(&group, "redis"));
+    }
+
+    #[test]
+    fn test_start_order_ignores_former_members() {
+        let databases = shop_databases();
+        let mut group = create_test_group(&["redis"]);
+        depend(&mut group, "redis", &["removed"]);
+
+        let members = GroupService::start_order(&group, &databases).unwrap();
+
+        assert_eq!(ids(&members), vec!["redis", "pgbouncer", "postgres"]);
+    }
+
+    #[test]
+    fn test_cycles_are_refused() {
+        let databases = shop_databases();
+        let mut group = create_test_group(&[]);
+        depend(&mut group, "postgres", &["redis"]);
+        depend(&mut group, "redis", &["postgres"]);
+        assert!(GroupService::start_order(&group, &databases).is_err());
+
+        let mut group = create_test_group(&[]);
+        GroupService::set_dependencies(
+            &mut group,
+            "pgbouncer",
+            vec!["postgres".to_string(), "postgres".to_string()],
+            &databases,
+        )
+        .unwrap();
+        assert_eq!(
+            GroupService::dependencies_of(&group, "pgbouncer"),
+            ["postgres"]
+        );
+
+        let result = GroupService::set_dependencies(
+            &mut group,
+            "postgres",
+            vec!["pgbouncer".to_string()],
+            &databases,
+        );
+        assert!(result.is_err());
+        assert!(GroupService::dependencies_of(&group, "postgres").is_empty());
+    }
+
+    #[test]
+    fn test_dependencies_stay_within_the_group() {
+        let mut databases = shop_databases();
+        databases.insert(
+            "mysql".to_string(),
+            create_test_database("mysql", Some("blog")),
+        );
+        let mut group = create_test_group(&[]);
+
+        let outside = GroupService::set_dependencies(
+            &mut group,
+            "redis",
+            vec!["mysql".to_string()],
+            &databases,
+        );
+        let itself = GroupService::set_dependencies(
+            &mut group,
+            "redis",
+            vec!["redis".to_string()],
+            &databases,
+        );
+
+        assert!(matches!(outside, Err(AppError::Validation(_))));
+        assert!(matches!(itself, Err(AppError::Validation(_))));
+        assert!(group.dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_removed_member_loses_its_dependencies() {
+        let mut group = create_test_group(&["postgres", "redis", "pgbouncer"]);
+        depend(&mut group, "pgbouncer", &["postgres"]);
+        depend(&mut group, "redis", &["postgres", "pgbouncer"]);
+
+        GroupService::remove_member(&mut group, "pgbouncer");
+
+        assert_eq!(group.order, vec!["postgres", "redis"]);
+        assert_eq!(group.dependencies.len(), 1);
+        assert_eq!(GroupService::dependencies_of(&group, "redis"), ["postgres"]);
+    }
+
+    #[test]
+    fn test_group_name_validation() {
+        assert_eq!(GroupService::validate_name("  shop ").unwrap(), "shop");
+        assert!(GroupService::validate_name("   ").is_err());
+    }
+}
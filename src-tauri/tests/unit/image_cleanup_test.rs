@@ -0,0 +1,60 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod image_cleanup_tests {
+    use super::*;
+
+    fn images(references: &[&str]) -> Vec<String> {
+        references.iter().map(|image| image.to_string()).collect()
+    }
+
+    #[test]
+    fn test_image_key_normalizes_docker_hub_references() {
+        assert_eq!(image_key("postgres"), "postgres:latest");
+        assert_eq!(image_key("docker.io/library/postgres:16"), "postgres:16");
+        assert_eq!(image_key("library/redis:7-alpine"), "redis:7-alpine");
+        assert_eq!(
+            image_key("mcr.microsoft.com/mssql/server:2022-latest"),
+            "mcr.microsoft.com/mssql/server:2022-latest"
+        );
+        assert_eq!(
+            image_key("localhost:5000/postgres"),
+            "localhost:5000/postgres:latest"
+        );
+    }
+
+    #[test]
+    fn test_release_lists_each_image_once() {
+        let mut released = Vec::new();
+        release_images(
+            &mut released,
+            &images(&["postgres:15", "mysql:8.0"]),
+            "2026-01-01T00:00:00Z",
+        );
+        release_images(
+            &mut released,
+            &images(&["docker.io/library/postgres:15"]),
+            "2026-02-01T00:00:00Z",
+        );
+
+        assert_eq!(released.len(), 2);
+        assert_eq!(released[0].image, "postgres:15");
+        assert_eq!(released[0].released_at, "2026-02-01T00:00:00Z");
+        assert_eq!(released[1].released_at, "2026-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_images_still_in_use_are_not_candidates() {
+        let mut released = Vec::new();
+        release_images(
+            &mut released,
+            &images(&["postgres:15", "postgres:16", "mongo"]),
+            "2026-01-01T00:00:00Z",
+        );
+
+        let candidates = cleanup_candidates(&released, &images(&["postgres:16", "mongo:latest"]));
+
+        let names: Vec<&str> = candidates.iter().map(|c| c.image.as_str()).collect();
+        assert_eq!(names, vec!["postgres:15"]);
+    }
+}
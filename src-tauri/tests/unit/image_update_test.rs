@@ -0,0 +1,71 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod image_update_tests {
+    use super::*;
+
+    #[test]
+    fn test_split_image_reference() {
+        assert_eq!(split_image_reference("postgres:16"), ("postgres", "16"));
+        assert_eq!(split_image_reference("redis"), ("redis", "latest"));
+        assert_eq!(
+            split_image_reference("localhost:5000/team/postgres:16-alpine"),
+            ("localhost:5000/team/postgres", "16-alpine")
+        );
+        assert_eq!(
+            split_image_reference("localhost:5000/postgres"),
+            ("localhost:5000/postgres", "latest")
+        );
+    }
+
+    #[test]
+    fn test_repo_digest_matches_docker_hub_short_name() {
+        let repo_digests = vec![
+            "mirror.local/postgres@sha256:aaa".to_string(),
+            "postgres@sha256:bbb".to_string(),
+        ];
+
+        assert_eq!(
+            repo_digest(&repo_digests, "docker.io/library/postgres"),
+            Some("sha256:bbb".to_string())
+        );
+        assert_eq!(repo_digest(&repo_digests, "mysql"), None);
+    }
+
+    #[test]
+    fn test_newer_major_tags_keep_variant() {
+        assert_eq!(
+            newer_major_tags("16.2-alpine"),
+            Some(vec![
+                "17-alpine".to_string(),
+                "18-alpine".to_string(),
+                "19-alpine".to_string()
+            ])
+        );
+        assert_eq!(newer_major_tags("latest"), None);
+    }
+
+    #[test]
+    fn test_unknown_local_digest_is_not_an_update() {
+        let check = ImageUpdateCheck::new("1", "postgres:16", None, Some("sha256:b".into()), None);
+        assert!(!check.updates_available);
+
+        let check = ImageUpdateCheck::new(
+            "1",
+            "postgres:16",
+            Some("sha256:a".into()),
+            Some("sha256:b".into()),
+            None,
+        );
+        assert!(check.updates_available);
+
+        let check = ImageUpdateCheck::new(
+            "1",
+            "postgres:16",
+            Some("sha256:a".into()),
+            Some("sha256:a".into()),
+            Some("17".into()),
+        );
+        assert!(check.updates_available);
+    }
+}
@@ -0,0 +1,161 @@
+use docker_db_manager_lib::services::IntegrityService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod integrity_tests {
+    use super::*;
+
+    fn database(db_type: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-1".to_string(),
+            name: "checked".to_string(),
+            db_type: db_type.to_string(),
+            status: "running".to_string(),
+            stored_password: Some("secret".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_postgres_corruption_is_reported() {
+        let output = "heap table \"app.public.users\", block 0, offset 3:\n    \
+                      xmin 4294967295 precedes relation freeze threshold 0:730\n\
+                      btree index \"app.public.users_pkey\":\n    \
+                      ERROR:  item order invariant violated\n";
+        let (status, problems) = evaluate_integrity("PostgreSQL", Some(2), output);
+
+        assert_eq!(status, IntegrityStatus::Failed);
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("heap table \"app.public.users\""));
+
+        assert_eq!(
+            evaluate_integrity("PostgreSQL", Some(0), "").0,
+            IntegrityStatus::Passed
+        );
+    }
+
+    #[test]
+    fn test_missing_checker_is_an_error() {
+        // pg_amcheck ships with PostgreSQL 14 and later
+        let (status, problems) = evaluate_integrity(
+            "PostgreSQL",
+            Some(127),
+            "OCI runtime exec failed: exec: \"pg_amcheck\": executable file not found in $PATH",
+        );
+
+        assert_eq!(status, IntegrityStatus::Error);
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_mysql_errors_name_their_table() {
+        let output = "app.orders                                         OK\n\
+                      app.users\n\
+                      warning  : 1 client is using or hasn't closed the table properly\n\
+                      error    : Table './app/users' is marked as crashed and should be repaired\n";
+        let (status, problems) = evaluate_integrity("MySQL", Some(2), output);
+
+        assert_eq!(status, IntegrityStatus::Failed);
+        assert_eq!(
+            problems,
+            vec!["app.users: Table './app/users' is marked as crashed and should be repaired"]
+        );
+
+        let (status, _) = evaluate_integrity("MariaDB", Some(0), "app.orders   OK\n");
+        assert_eq!(status, IntegrityStatus::Passed);
+    }
+
+    #[test]
+    fn test_redis_rdb_check() {
+        let ok = "[offset 0] Checking RDB file dump.rdb\n[offset 92] \\o/ RDB looks OK! \\o/\n";
+        assert_eq!(
+            evaluate_integrity("Redis", Some(0), ok).0,
+            IntegrityStatus::Passed
+        );
+        assert_eq!(
+            evaluate_integrity("Redis", Some(0), "No RDB file to check yet\n").0,
+            IntegrityStatus::Passed
+        );
+
+        let corrupt = "--- RDB ERROR DETECTED ---\n\
+                       [offset 31] Unexpected EOF reading RDB file\n\
+                       [additional info] Reading key 'user:1'\n";
+        let (status, problems) = evaluate_integrity("Redis", Some(1), corrupt);
+        assert_eq!(status, IntegrityStatus::Failed);
+        assert!(problems.contains(&"[additional info] Reading key 'user:1'".to_string()));
+    }
+
+    #[test]
+    fn test_mongo_invalid_collections() {
+        let output = "{\"ns\":\"app.users\",\"valid\":true,\"errors\":[]}\n\
+                      {\"ns\":\"app.orders\",\"valid\":false,\"errors\":[\"index 'total_1' is corrupt\"]}\n";
+        let (status, problems) = evaluate_integrity("MongoDB", Some(0), output);
+
+        assert_eq!(status, IntegrityStatus::Failed);
+        assert_eq!(problems, vec!["app.orders: index 'total_1' is corrupt"]);
+    }
+
+    #[test]
+    fn test_exec_args_run_at_low_priority() {
+        let env_file = std::path::Path::new("/tmp/checked.env");
+        let args =
+            IntegrityService::exec_args(&database("MariaDB"), false, Some(env_file)).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "exec",
+                "--env-file",
+                "/tmp/checked.env",
+                "checked",
+                "nice",
+                "-n",
+                "19",
+                "mariadb-check",
+                "-u",
+                "root",
+                "--all-databases",
+                "--check",
+            ]
+        );
+
+        let args =
+            IntegrityService::exec_args(&database("PostgreSQL"), false, Some(env_file)).unwrap();
+        assert_eq!(&args[3..7], ["checked", "nice", "-n", "19"]);
+        assert!(args.contains(&"pg_amcheck".to_string()));
+        assert!(!args.iter().any(|arg| arg.contains("secret")));
+
+        assert!(IntegrityService::exec_args(&database("SQLServer"), false, None).is_none());
+    }
+
+    #[test]
+    fn test_amcheck_is_installed_only_on_request() {
+        let args = IntegrityService::exec_args(&database("PostgreSQL"), false, None).unwrap();
+        assert!(!args.contains(&"--install-missing".to_string()));
+
+        let args = IntegrityService::exec_args(&database("PostgreSQL"), true, None).unwrap();
+        assert!(args.contains(&"--install-missing".to_string()));
+    }
+
+    #[test]
+    fn test_mongo_password_stays_off_the_command_line() {
+        let mut mongo = database("MongoDB");
+        mongo.stored_enable_auth = true;
+        let args = IntegrityService::exec_args(&mongo, false, None).unwrap();
+        assert!(!args.iter().any(|arg| arg.contains("secret")));
+        assert!(!args.contains(&"-p".to_string()));
+
+        assert_eq!(
+            IntegrityService::exec_credentials(&mongo),
+            vec![("DDM_MONGO_PASSWORD", "secret".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_output_tail_keeps_the_end() {
+        let output = format!("{}é{}", "a".repeat(INTEGRITY_OUTPUT_LIMIT), "end");
+        let tail = integrity_output_tail(&output);
+
+        assert!(tail.len() <= INTEGRITY_OUTPUT_LIMIT);
+        assert!(tail.ends_with("éend"));
+    }
+}
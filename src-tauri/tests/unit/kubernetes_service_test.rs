@@ -0,0 +1,139 @@
+use docker_db_manager_lib::services::{KubernetesService, TlsService};
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod kubernetes_service_tests {
+    use super::*;
+
+    fn create_test_database() -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-id".to_string(),
+            name: "My_Postgres".to_string(),
+            db_type: "PostgreSQL".to_string(),
+            version: "16".to_string(),
+            port: 5433,
+            stored_docker_args: Some(DockerRunArgs {
+                image: "postgres:16".to_string(),
+                env_vars: HashMap::from([
+                    ("POSTGRES_USER".to_string(), "app".to_string()),
+                    ("POSTGRES_PASSWORD".to_string(), "s3cret".to_string()),
+                ]),
+                ports: vec![PortMapping::local(5433, 5432)],
+                volumes: vec![VolumeMount {
+                    name: "My_Postgres-data".to_string(),
+                    path: "/var/lib/postgresql/data".to_string(),
+                }],
+                command: vec![],
+                shm_size: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resource_name_is_dns_label() {
+        assert_eq!(
+            KubernetesService::resource_name("My_Postgres"),
+            "my-postgres"
+        );
+        assert_eq!(
+            KubernetesService::resource_name("db.local--1"),
+            "db-local-1"
+        );
+        assert_eq!(KubernetesService::resource_name("__"), "database");
+        assert_eq!(KubernetesService::resource_name(&"a".repeat(80)).len(), 63);
+    }
+
+    #[test]
+    fn test_manifests_contain_every_object() {
+        let yaml = KubernetesService::new()
+            .manifests(&create_test_database())
+            .unwrap();
+
+        assert!(yaml.contains("kind: Secret\n"));
+        assert!(yaml.contains("kind: PersistentVolumeClaim\n"));
+        assert!(yaml.contains("kind: Service\n"));
+        assert!(yaml.contains("kind: StatefulSet\n"));
+        assert_eq!(yaml.matches("---\n").count(), 3);
+        assert!(yaml.contains("  name: my-postgres\n"));
+        assert!(yaml.contains("          image: \"postgres:16\"\n"));
+    }
+
+    #[test]
+    fn test_password_only_in_secret() {
+        let yaml = KubernetesService::new()
+            .manifests(&create_test_database())
+            .unwrap();
+
+        assert_eq!(yaml.matches("s3cret").count(), 1);
+        assert!(yaml.contains("  POSTGRES_PASSWORD: \"s3cret\"\n"));
+        assert!(yaml.contains("                  name: my-postgres-credentials\n"));
+        assert!(yaml.contains("            - name: POSTGRES_USER\n              value: \"app\"\n"));
+    }
+
+    #[test]
+    fn test_volume_uses_claim_and_container_port() {
+        let yaml = KubernetesService::new()
+            .manifests(&create_test_database())
+            .unwrap();
+
+        assert!(yaml.contains("            - name: data\n"));
+        assert!(yaml.contains("            claimName: my-postgres-data\n"));
+        assert!(yaml.contains("      storage: 10Gi\n"));
+        assert!(yaml.contains("            - containerPort: 5432\n"));
+        assert!(!yaml.contains("5433"));
+    }
+
+    #[test]
+    fn test_command_password_read_from_secret() {
+        let mut database = create_test_database();
+        let docker_args = database.stored_docker_args.as_mut().unwrap();
+        docker_args.env_vars.clear();
+        docker_args.command = vec!["--requirepass".to_string(), "hunter2".to_string()];
+
+        let yaml = KubernetesService::new().manifests(&database).unwrap();
+
+        assert!(yaml.contains("            - \"$(REDIS_PASSWORD)\"\n"));
+        assert!(yaml.contains("  REDIS_PASSWORD: \"hunter2\"\n"));
+        assert_eq!(yaml.matches("hunter2").count(), 1);
+    }
+
+    #[test]
+    fn test_postgres_data_in_claim_subdirectory() {
+        let yaml = KubernetesService::new()
+            .manifests(&create_test_database())
+            .unwrap();
+
+        assert!(yaml.contains(
+            "            - name: PGDATA\n              value: \"/var/lib/postgresql/data/pgdata\"\n"
+        ));
+    }
+
+    #[test]
+    fn test_tls_stays_local() {
+        let mut database = create_test_database();
+        let docker_args = database.stored_docker_args.as_mut().unwrap();
+        docker_args.command = TlsService::build_tls_command_args("PostgreSQL").unwrap();
+        docker_args.command.push("-c".to_string());
+        docker_args.command.push("max_connections=50".to_string());
+        docker_args.volumes.push(VolumeMount {
+            name: TlsService::certs_volume_name("My_Postgres"),
+            path: "/certs".to_string(),
+        });
+
+        let yaml = KubernetesService::new().manifests(&database).unwrap();
+
+        assert!(!yaml.contains("ssl"));
+        assert!(!yaml.contains("certs"));
+        assert!(yaml.contains("            - \"max_connections=50\"\n"));
+    }
+
+    #[test]
+    fn test_requires_run_configuration() {
+        let mut database = create_test_database();
+        database.stored_docker_args = None;
+
+        assert!(KubernetesService::new().manifests(&database).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use docker_db_manager_lib::services::{DockerService, LocalStore, StorageService, StoreKey};
+use docker_db_manager_lib::types::*;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod local_store_tests {
+    use super::*;
+
+    // Removed with its files when the test ends
+    fn temp_dir() -> TempDir {
+        tempfile::Builder::new()
+            .prefix("docker-db-manager-local-")
+            .tempdir()
+            .unwrap()
+    }
+
+    fn create_test_database(id: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: id.to_string(),
+            name: format!("db-{}", id),
+            db_type: "PostgreSQL".to_string(),
+            version: "16".to_string(),
+            status: "stopped".to_string(),
+            port: 5432,
+            ..Default::default()
+        }
+    }
+
+    fn create_test_request() -> DockerRunRequest {
+        DockerRunRequest {
+            name: "my-postgres".to_string(),
+            docker_args: DockerRunArgs {
+                image: "postgres:16".to_string(),
+                env_vars: HashMap::new(),
+                ports: vec![PortMapping::local(5433, 5432)],
+                volumes: vec![],
+                command: vec![],
+                shm_size: None,
+            },
+            metadata: ContainerMetadata {
+                id: "db-id".to_string(),
+                db_type: "PostgreSQL".to_string(),
+                version: "16".to_string(),
+                port: 5433,
+                username: Some("postgres".to_string()),
+                password: "secret".to_string(),
+                persist_data: true,
+                enable_auth: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_ps_output() {
+        let states = DockerService::parse_ps_output(
+            "abc123,my-postgres,Up 2 hours (healthy)\ndef456,my-redis,Exited (1) 3 minutes ago\n",
+        );
+
+        assert_eq!(states.len(), 2);
+        assert!(states["my-postgres"].running);
+        assert_eq!(states["my-postgres"].id, "abc123");
+        assert!(!states["my-redis"].running);
+        assert_eq!(states["my-redis"].exit_code, Some(1));
+    }
+
+    #[test]
+    fn test_database_from_run_request() {
+        let database = DatabaseContainer::from_run_request(&create_test_request(), "abc123".into());
+
+        assert_eq!(database.id, "db-id");
+        assert_eq!(database.name, "my-postgres");
+        assert_eq!(database.port, 5433);
+        assert_eq!(database.status, "running");
+        assert_eq!(database.container_id.as_deref(), Some("abc123"));
+        assert_eq!(database.stored_password.as_deref(), Some("secret"));
+        assert_eq!(database.max_connections, 100);
+        assert!(database.stored_docker_args.is_some());
+    }
+
+    #[test]
+    fn test_databases_round_trip() {
+        let dir = temp_dir();
+        let store = LocalStore::new(dir.path());
+        let mut databases = HashMap::new();
+        databases.insert("1".to_string(), create_test_database("1"));
+
+        store.save_databases(&databases).unwrap();
+        let loaded = store.load_databases().unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded["1"].name, "db-1");
+    }
+
+    #[test]
+    fn test_missing_store_loads_empty() {
+        let dir = temp_dir();
+        let store = LocalStore::new(dir.path());
+
+        assert!(store.load_databases().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reads_active_workspace() {
+        let dir = temp_dir();
+        StorageService::write_value_at(
+            &dir.path().join("settings.json"),
+            "workspaces",
+            json!({ "active": "client-x", "workspaces": [] }),
+        )
+        .unwrap();
+        let databases = vec![create_test_database("2")];
+        StorageService::write_value_at(
+            &dir.path()
+                .join(StorageService::workspace_databases_file("client-x")),
+            "databases",
+            json!(databases),
+        )
+        .unwrap();
+
+        let loaded = LocalStore::new(dir.path()).load_databases().unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert!(loaded.contains_key("2"));
+    }
+
+    #[test]
+    fn test_reads_snippets_of_active_workspace() {
+        let dir = temp_dir();
+        StorageService::write_value_at(
+            &dir.path().join(StorageService::workspace_databases_file(
+                DEFAULT_WORKSPACE_ID,
+            )),
+            "snippets",
+            json!({ "snippets": [{
+                "id": "s1",
+                "name": "Table size",
+                "kind": "sql",
+                "body": "SELECT pg_total_relation_size('{{table}}');",
+                "dbType": "PostgreSQL"
+            }] }),
+        )
+        .unwrap();
+        let store = LocalStore::new(dir.path());
+
+        let snippets = store
+            .load_snippets()
+            .unwrap()
+            .for_database(&create_test_database("1"));
+
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].variables(), vec!["table".to_string()]);
+    }
+
+    #[test]
+    fn test_unlocks_passphrase_encrypted_store() {
+        let dir = temp_dir();
+        // Every store file is sealed with the same key, as the app writes them
+        let key = StoreKey::from_passphrase("correct horse", &StoreKey::random_salt()).unwrap();
+        let write_encrypted = |file: &Path, name: &str, value: Value| {
+            let mut store = Map::new();
+            store.insert(name.to_string(), value);
+            StorageService::write_store_file(
+                &dir.path().join(file),
+                &StorageService::encrypt_store(&store, &key).unwrap(),
+            )
+            .unwrap();
+        };
+        write_encrypted(
+            Path::new("settings.json"),
+            "workspaces",
+            json!({ "active": "client-y", "workspaces": [] }),
+        );
+        write_encrypted(
+            &StorageService::workspace_databases_file("client-y"),
+            "databases",
+            json!(vec![create_test_database("3")]),
+        );
+
+        let store = LocalStore::new(dir.path());
+        assert!(store.unlock("wrong horse").is_err());
+        store.unlock("correct horse").unwrap();
+        let loaded = store.load_databases().unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert!(loaded.contains_key("3"));
+    }
+}
@@ -0,0 +1,209 @@
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod locale_tests {
+    use super::*;
+
+    fn settings(
+        encoding: Option<&str>,
+        locale: Option<&str>,
+        collation: Option<&str>,
+    ) -> LocaleSettings {
+        LocaleSettings {
+            encoding: encoding.map(str::to_string),
+            locale: locale.map(str::to_string),
+            collation: collation.map(str::to_string),
+        }
+    }
+
+    fn args(env_vars: &[(&str, &str)], command: &[&str]) -> DockerRunArgs {
+        DockerRunArgs {
+            image: "image".to_string(),
+            env_vars: env_vars
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>(),
+            ports: vec![],
+            volumes: vec![],
+            command: command.iter().map(|arg| arg.to_string()).collect(),
+            shm_size: None,
+        }
+    }
+
+    #[test]
+    fn test_blank_settings_are_empty() {
+        let locale = settings(Some(" "), Some(""), None);
+
+        assert!(locale.is_empty());
+        assert!(locale.violations("Redis", "7").is_empty());
+    }
+
+    #[test]
+    fn test_postgres_image_locale_is_passed_to_initdb() {
+        let locale = settings(Some("UTF8"), Some("en_US.utf8"), None);
+        let mut docker_args = args(
+            &[("POSTGRES_INITDB_ARGS", "--data-checksums --encoding=LATIN1")],
+            &[],
+        );
+
+        assert!(locale.violations("PostgreSQL", "16").is_empty());
+        locale.apply("PostgreSQL", "16", &mut docker_args);
+
+        assert_eq!(
+            docker_args.env_vars["POSTGRES_INITDB_ARGS"],
+            "--data-checksums --encoding=UTF8 --locale=en_US.utf8"
+        );
+    }
+
+    #[test]
+    fn test_postgres_other_locales_use_icu() {
+        let locale = settings(None, Some("de_DE.utf8"), None);
+        let mut docker_args = args(&[], &[]);
+
+        assert!(locale.violations("PostgreSQL", "17-alpine").is_empty());
+        locale.apply("PostgreSQL", "17-alpine", &mut docker_args);
+
+        assert_eq!(
+            docker_args.env_vars["POSTGRES_INITDB_ARGS"],
+            "--locale-provider=icu --icu-locale=de-DE"
+        );
+    }
+
+    #[test]
+    fn test_postgres_icu_needs_version_15() {
+        let locale = settings(None, Some("de-DE"), None);
+
+        let violations = locale.violations("PostgreSQL", "14-bookworm");
+
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("PostgreSQL 15 or later"));
+        assert!(locale.violations("PostgreSQL", "latest").is_empty());
+    }
+
+    #[test]
+    fn test_postgres_alpine_only_has_c_locales() {
+        let locale = settings(None, Some("en_US.utf8"), None);
+        let mut docker_args = args(&[], &[]);
+
+        locale.apply("PostgreSQL", "16-alpine", &mut docker_args);
+
+        assert!(docker_args.env_vars["POSTGRES_INITDB_ARGS"].contains("--icu-locale=en-US"));
+        assert_eq!(locale.violations("PostgreSQL", "14-alpine").len(), 1);
+    }
+
+    #[test]
+    fn test_postgres_rejects_bad_encodings_and_names() {
+        assert_eq!(
+            settings(Some("SJIS"), None, None).violations("PostgreSQL", "16"),
+            vec!["PostgreSQL has no server encoding named 'SJIS'".to_string()]
+        );
+        assert!(settings(Some("utf-8"), None, None)
+            .violations("PostgreSQL", "16")
+            .is_empty());
+        assert_eq!(
+            settings(Some("LATIN1"), Some("C.UTF-8"), None)
+                .violations("PostgreSQL", "16")
+                .len(),
+            1
+        );
+        assert_eq!(
+            settings(None, Some("german"), None)
+                .violations("PostgreSQL", "16")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_mysql_flags_replace_provider_flags() {
+        let locale = settings(Some("utf8mb4"), None, Some("utf8mb4_0900_ai_ci"));
+        let mut docker_args = args(
+            &[],
+            &["--character-set-server=latin1", "--sql-mode=TRADITIONAL"],
+        );
+
+        assert!(locale.violations("MySQL", "8.4").is_empty());
+        locale.apply("MySQL", "8.4", &mut docker_args);
+
+        assert_eq!(
+            docker_args.command,
+            vec![
+                "--sql-mode=TRADITIONAL",
+                "--character-set-server=utf8mb4",
+                "--collation-server=utf8mb4_0900_ai_ci",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mysql_character_set_follows_collation() {
+        let locale = settings(None, None, Some("latin1_swedish_ci"));
+        let mut docker_args = args(&[], &[]);
+
+        locale.apply("MariaDB", "11.4", &mut docker_args);
+
+        assert_eq!(docker_args.command[0], "--character-set-server=latin1");
+    }
+
+    #[test]
+    fn test_mysql_collation_must_match_character_set() {
+        let violations =
+            settings(Some("latin1"), None, Some("utf8mb4_general_ci")).violations("MySQL", "8.0");
+
+        assert_eq!(
+            violations,
+            vec!["Collation utf8mb4_general_ci belongs to utf8mb4, not to latin1".to_string()]
+        );
+        assert!(settings(Some("utf8"), None, Some("utf8mb3_general_ci"))
+            .violations("MySQL", "8.0")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_collations_need_a_recent_image() {
+        let mysql_8_collation = settings(None, None, Some("utf8mb4_0900_ai_ci"));
+        let mariadb_collation = settings(None, None, Some("utf8mb4_uca1400_ai_ci"));
+
+        assert_eq!(mysql_8_collation.violations("MySQL", "5.7").len(), 1);
+        assert_eq!(mysql_8_collation.violations("MariaDB", "10.11").len(), 1);
+        assert!(mysql_8_collation.violations("MariaDB", "11.4").is_empty());
+        assert_eq!(mariadb_collation.violations("MySQL", "8.0").len(), 1);
+        assert_eq!(mariadb_collation.violations("MariaDB", "10.6").len(), 1);
+        assert!(mariadb_collation.violations("MariaDB", "11.8").is_empty());
+    }
+
+    #[test]
+    fn test_other_engines_take_no_locale() {
+        let violations = settings(Some("utf8"), None, None).violations("MongoDB", "7");
+
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].starts_with("MongoDB does not take locale settings"));
+    }
+
+    #[test]
+    fn test_request_validation_reports_locale() {
+        let request = DockerRunRequest {
+            name: "pg".to_string(),
+            docker_args: args(&[], &[]),
+            metadata: ContainerMetadata {
+                id: "id".to_string(),
+                db_type: "PostgreSQL".to_string(),
+                version: "13".to_string(),
+                port: 5432,
+                password: "secret".to_string(),
+                enable_auth: true,
+                locale: Some(settings(None, Some("fr_FR.utf8"), None)),
+                ..Default::default()
+            },
+        };
+
+        match validate_run_request(&request, &[]) {
+            Err(AppError::InvalidRequest { violations }) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].field, "locale");
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod log_pager_tests {
+    use super::*;
+
+    fn query(cursor: Option<&str>, limit: usize) -> LogQuery {
+        LogQuery {
+            cursor: cursor.map(str::to_string),
+            limit: Some(limit),
+            ..Default::default()
+        }
+    }
+
+    fn line(second: u32, text: &str) -> String {
+        format!("2024-05-01T10:00:{:02}.000000000Z {}", second, text)
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = LogCursor::parse("2024-05-01T10:00:01.000000000Z#2").unwrap();
+
+        assert_eq!(cursor.skip, 2);
+        assert_eq!(cursor.encode(), "2024-05-01T10:00:01.000000000Z#2");
+        assert!(LogCursor::parse("yesterday#1").is_err());
+        assert!(LogCursor::parse("2024-05-01T10:00:01Z").is_err());
+    }
+
+    #[test]
+    fn test_page_stops_after_limit() {
+        let query = query(None, 2);
+        let mut pager = LogPager::new(&query).unwrap();
+
+        pager.push(&line(1, "a"));
+        pager.push(&line(2, "b"));
+        pager.push(&line(3, "c"));
+
+        let page = pager.finish(None);
+        assert_eq!(page.lines, vec![line(1, "a"), line(2, "b")]);
+        assert!(page.has_more);
+        assert_eq!(
+            page.next_cursor.as_deref(),
+            Some("2024-05-01T10:00:02.000000000Z#1")
+        );
+    }
+
+    #[test]
+    fn test_stdout_and_stderr_are_merged_by_timestamp() {
+        let mut pager = LogPager::new(&query(None, 3)).unwrap();
+
+        // The stdout pipe delivered its lines before the earlier stderr ones
+        for second in [2, 4, 6, 8, 10, 12, 14, 16] {
+            pager.push(&line(second, "out"));
+        }
+        pager.push(&line(1, "err"));
+        pager.push(&line(3, "err"));
+
+        let page = pager.finish(None);
+        assert_eq!(
+            page.lines,
+            vec![line(1, "err"), line(2, "out"), line(3, "err")]
+        );
+        assert!(page.has_more);
+    }
+
+    #[test]
+    fn test_cursor_skips_lines_already_returned() {
+        let cursor = "2024-05-01T10:00:02.000000000Z#1";
+        let query = query(Some(cursor), 10);
+        let mut pager = LogPager::new(&query).unwrap();
+
+        // `--since` repeats the lines of the cursor's timestamp
+        pager.push(&line(2, "b"));
+        pager.push(&line(2, "b2"));
+        pager.push(&line(3, "c"));
+
+        assert_eq!(pager.since(&query), Some("2024-05-01T10:00:02.000000000Z"));
+        let page = pager.finish(Some(cursor));
+        assert_eq!(page.lines, vec![line(2, "b2"), line(3, "c")]);
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_shared_timestamp_across_pages_accumulates_skip() {
+        let cursor = "2024-05-01T10:00:02.000000000Z#1";
+        let query = query(Some(cursor), 1);
+        let mut pager = LogPager::new(&query).unwrap();
+
+        pager.push(&line(2, "b"));
+        pager.push(&line(2, "b2"));
+        pager.push(&line(2, "b3"));
+
+        let page = pager.finish(Some(cursor));
+        assert_eq!(page.lines, vec![line(2, "b2")]);
+        assert_eq!(
+            page.next_cursor.as_deref(),
+            Some("2024-05-01T10:00:02.000000000Z#2")
+        );
+    }
+
+    #[test]
+    fn test_empty_page_keeps_cursor() {
+        let cursor = "2024-05-01T10:00:02.000000000Z#1";
+        let pager = LogPager::new(&query(Some(cursor), 10)).unwrap();
+
+        let page = pager.finish(Some(cursor));
+
+        assert!(page.lines.is_empty());
+        assert_eq!(page.next_cursor.as_deref(), Some(cursor));
+    }
+
+    #[test]
+    fn test_cli_errors_are_not_log_lines() {
+        let mut pager = LogPager::new(&query(None, 10)).unwrap();
+
+        pager.push("Error response from daemon: No such container: abc\n");
+
+        assert_eq!(
+            pager.errors(),
+            "Error response from daemon: No such container: abc"
+        );
+        assert!(pager.finish(None).lines.is_empty());
+    }
+
+    #[test]
+    fn test_log_preferences_default_until_changed() {
+        let mut settings = AppSettings::default();
+        assert_eq!(
+            settings.log_preferences_of("db-1"),
+            LogViewPreferences::default()
+        );
+
+        let preferences = LogViewPreferences {
+            tail_lines: 2000,
+            wrap: false,
+            follow: false,
+            min_severity: Some(LogSeverity::Warning),
+        };
+        settings.set_log_preferences("db-1", preferences.clone());
+        assert_eq!(settings.log_preferences_of("db-1"), preferences);
+        assert_eq!(
+            settings.log_preferences_of("db-2"),
+            LogViewPreferences::default()
+        );
+
+        // Back to the defaults, nothing is kept
+        settings.set_log_preferences("db-1", LogViewPreferences::default());
+        assert!(settings.log_preferences.is_empty());
+    }
+
+    #[test]
+    fn test_log_preferences_tail_is_bounded() {
+        let too_long = LogViewPreferences {
+            tail_lines: MAX_LOG_PAGE_SIZE + 1,
+            ..Default::default()
+        };
+        assert!(too_long.validate().is_err());
+        assert!(LogViewPreferences::default().validate().is_ok());
+
+        let json = serde_json::to_value(LogViewPreferences::default()).unwrap();
+        assert_eq!(json["tailLines"], DEFAULT_LOG_PAGE_SIZE);
+    }
+}
@@ -0,0 +1,177 @@
+use docker_db_manager_lib::services::LogParserService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod log_parser_tests {
+    use super::*;
+
+    const TS: &str = "2024-05-10T09:00:00.123456789Z";
+
+    fn lines(messages: &[&str]) -> Vec<String> {
+        messages
+            .iter()
+            .map(|message| format!("{} {}", TS, message))
+            .collect()
+    }
+
+    #[test]
+    fn test_postgres_stderr_levels() {
+        let entries = LogParserService::new().parse_lines(
+            "PostgreSQL",
+            &lines(&[
+                "2024-05-10 09:00:00.123 UTC [1] LOG:  database system is ready to accept connections",
+                "2024-05-10 09:00:01.000 UTC [63] FATAL:  password authentication failed for user \"app\"",
+                "2024-05-10 09:00:02.000 UTC [64] WARNING:  there is no transaction in progress",
+            ]),
+        );
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].severity, LogSeverity::Info);
+        assert_eq!(
+            entries[0].message,
+            "database system is ready to accept connections"
+        );
+        assert_eq!(entries[0].timestamp.as_deref(), Some(TS));
+        assert_eq!(entries[1].severity, LogSeverity::Fatal);
+        assert_eq!(entries[2].severity, LogSeverity::Warning);
+    }
+
+    #[test]
+    fn test_postgres_details_join_their_error() {
+        let entries = LogParserService::new().parse_lines(
+            "PostgreSQL",
+            &lines(&[
+                "2024-05-10 09:00:00.123 UTC [63] ERROR:  duplicate key value violates unique constraint \"users_pkey\"",
+                "2024-05-10 09:00:00.123 UTC [63] DETAIL:  Key (id)=(1) already exists.",
+                "2024-05-10 09:00:00.123 UTC [63] STATEMENT:  INSERT INTO users VALUES (1)",
+            ]),
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].severity, LogSeverity::Error);
+        assert!(entries[0].message.contains("\n"));
+        assert!(entries[0].message.ends_with("INSERT INTO users VALUES (1)"));
+    }
+
+    #[test]
+    fn test_postgres_csvlog() {
+        let entries = LogParserService::new().parse_lines(
+            "PostgreSQL",
+            &lines(&["2024-05-10 09:00:00.123 UTC,\"app\",\"app\",63,\"172.17.0.1:51234\",663df,1,\"SELECT\",2024-05-10 09:00:00 UTC,3/2,0,ERROR,42P01,\"relation \"\"missing\"\" does not exist\",,,,,,\"SELECT * FROM missing\",15,,\"psql\""]),
+        );
+
+        assert_eq!(entries[0].severity, LogSeverity::Error);
+        assert_eq!(entries[0].message, "relation \"missing\" does not exist");
+    }
+
+    #[test]
+    fn test_mysql_error_log() {
+        let entries = LogParserService::new().parse_lines(
+            "MySQL",
+            &lines(&[
+                "2024-05-10T09:00:00.123456Z 0 [System] [MY-010931] [Server] /usr/sbin/mysqld: ready for connections.",
+                "2024-05-10T09:00:01.000000Z 8 [Warning] [MY-013360] [Server] Plugin mysql_native_password reported: 'deprecated'",
+                "2024-05-10 09:00:00+00:00 [Note] [Entrypoint]: Switching to dedicated user 'mysql'",
+            ]),
+        );
+
+        assert_eq!(entries[0].severity, LogSeverity::Notice);
+        assert_eq!(entries[0].source.as_deref(), Some("Server"));
+        assert_eq!(
+            entries[0].message,
+            "/usr/sbin/mysqld: ready for connections."
+        );
+        assert_eq!(entries[1].severity, LogSeverity::Warning);
+        assert_eq!(entries[2].severity, LogSeverity::Info);
+        assert_eq!(entries[2].source.as_deref(), Some("Entrypoint"));
+        assert_eq!(entries[2].message, "Switching to dedicated user 'mysql'");
+    }
+
+    #[test]
+    fn test_mariadb_error_log() {
+        let entries = LogParserService::new().parse_lines(
+            "MariaDB",
+            &lines(&["2024-05-10  9:00:00 0 [ERROR] mariadbd: Can't lock aria control file"]),
+        );
+
+        assert_eq!(entries[0].severity, LogSeverity::Error);
+        assert_eq!(entries[0].source, None);
+        assert_eq!(entries[0].message, "mariadbd: Can't lock aria control file");
+    }
+
+    #[test]
+    fn test_redis_levels() {
+        let entries = LogParserService::new().parse_lines(
+            "Redis",
+            &lines(&[
+                "1:M 10 May 2024 09:00:00.123 * Ready to accept connections tcp",
+                "1:M 10 May 2024 09:00:00.124 # WARNING Memory overcommit must be enabled!",
+            ]),
+        );
+
+        assert_eq!(entries[0].severity, LogSeverity::Info);
+        assert_eq!(entries[0].message, "Ready to accept connections tcp");
+        assert_eq!(entries[1].severity, LogSeverity::Warning);
+    }
+
+    #[test]
+    fn test_mongo_json_log() {
+        let entries = LogParserService::new().parse_lines(
+            "MongoDB",
+            &lines(&[
+                r#"{"t":{"$date":"2024-05-10T09:00:00.123+00:00"},"s":"I","c":"NETWORK","id":23016,"ctx":"listener","msg":"Waiting for connections","attr":{"port":27017}}"#,
+                r#"{"t":{"$date":"2024-05-10T09:00:01.000+00:00"},"s":"E","c":"ACCESS","id":20249,"ctx":"conn1","msg":"Authentication failed","attr":{"error":"UserNotFound: Could not find user"}}"#,
+            ]),
+        );
+
+        assert_eq!(entries[0].severity, LogSeverity::Info);
+        assert_eq!(entries[0].source.as_deref(), Some("NETWORK"));
+        assert_eq!(entries[1].severity, LogSeverity::Error);
+        assert_eq!(
+            entries[1].message,
+            "Authentication failed: UserNotFound: Could not find user"
+        );
+    }
+
+    #[test]
+    fn test_unknown_lines_are_info() {
+        let entries = LogParserService::new().parse_lines(
+            "PostgreSQL",
+            &lines(&[
+                "The files belonging to this database system will be owned by user \"postgres\".",
+            ]),
+        );
+
+        assert_eq!(entries[0].severity, LogSeverity::Info);
+        assert!(entries[0].message.starts_with("The files belonging"));
+    }
+
+    #[test]
+    fn test_page_counts_severities() {
+        let page = LogPage {
+            lines: vec![],
+            next_cursor: Some("cursor".to_string()),
+            has_more: true,
+        };
+        let entry = |severity| LogEntry {
+            timestamp: None,
+            severity,
+            source: None,
+            message: String::new(),
+        };
+
+        let parsed = ParsedLogPage::new(
+            page,
+            vec![
+                entry(LogSeverity::Error),
+                entry(LogSeverity::Info),
+                entry(LogSeverity::Error),
+            ],
+        );
+
+        assert_eq!(parsed.counts[&LogSeverity::Error], 2);
+        assert_eq!(parsed.counts[&LogSeverity::Info], 1);
+        assert!(!parsed.counts.contains_key(&LogSeverity::Warning));
+        assert_eq!(parsed.next_cursor.as_deref(), Some("cursor"));
+    }
+}
@@ -0,0 +1,54 @@
+use docker_db_manager_lib::types::*;
+use serde_json::json;
+
+#[cfg(test)]
+mod mcp_tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_names_round_trip() {
+        for tool in McpTool::ALL {
+            assert_eq!(McpTool::from_name(tool.name()), Some(tool));
+            assert_eq!(tool.definition()["name"], tool.name());
+            assert_eq!(tool.definition()["inputSchema"]["type"], "object");
+        }
+        assert_eq!(McpTool::from_name("drop_database"), None);
+    }
+
+    #[test]
+    fn test_credentials_and_logs_need_approval() {
+        assert!(McpTool::GetConnectionString.needs_approval());
+        assert!(McpTool::GetLogs.needs_approval());
+        assert!(!McpTool::ListDatabases.needs_approval());
+        assert!(!McpTool::CreateDatabase.needs_approval());
+    }
+
+    #[test]
+    fn test_protocol_version_is_negotiated() {
+        assert_eq!(negotiate_protocol_version(Some("2025-03-26")), "2025-03-26");
+        assert_eq!(negotiate_protocol_version(Some("2031-01-01")), "2025-06-18");
+        assert_eq!(negotiate_protocol_version(None), "2025-06-18");
+    }
+
+    #[test]
+    fn test_jsonrpc_responses_echo_id() {
+        let request: JsonRpcRequest =
+            serde_json::from_value(json!({ "jsonrpc": "2.0", "id": 7, "method": "ping" })).unwrap();
+
+        assert_eq!(request.result(json!({}))["id"], 7);
+        assert_eq!(
+            request.error(JSONRPC_METHOD_NOT_FOUND, "nope")["error"]["code"],
+            -32601
+        );
+    }
+
+    #[test]
+    fn test_notifications_have_no_id() {
+        let request: JsonRpcRequest = serde_json::from_value(
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        )
+        .unwrap();
+
+        assert!(request.id.is_none());
+    }
+}
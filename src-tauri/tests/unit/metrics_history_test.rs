@@ -0,0 +1,168 @@
+use chrono::{DateTime, Duration, Utc};
+use docker_db_manager_lib::services::MetricsHistoryService;
+use docker_db_manager_lib::types::*;
+use std::path::Path;
+
+#[cfg(test)]
+mod metrics_history_tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-05-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn point(time: DateTime<Utc>, cpu_percent: f64, connections: Option<u64>) -> MetricsPoint {
+        MetricsPoint {
+            timestamp: time.to_rfc3339(),
+            cpu_percent,
+            memory_used_bytes: 1000,
+            connections,
+            disk_bytes: None,
+            samples: 1,
+        }
+    }
+
+    #[test]
+    fn test_average_weights_by_samples() {
+        let averaged = MetricsPoint::average(
+            &[
+                MetricsPoint {
+                    samples: 3,
+                    ..point(now(), 10.0, Some(4))
+                },
+                point(now(), 50.0, None),
+            ],
+            now(),
+        );
+
+        assert_eq!(averaged.cpu_percent, 20.0);
+        assert_eq!(averaged.samples, 4);
+        // Points without a value do not pull the mean down
+        assert_eq!(averaged.connections, Some(4));
+        assert_eq!(averaged.disk_bytes, None);
+    }
+
+    #[test]
+    fn test_compact_merges_old_points_hourly() {
+        let mut history = MetricsHistory::default();
+        let old = now() - Duration::days(2);
+        history.record("db-1", point(old, 10.0, None));
+        history.record("db-1", point(old + Duration::minutes(1), 30.0, None));
+        history.record("db-1", point(now() - Duration::minutes(5), 5.0, None));
+        history.record("db-1", point(now() - Duration::minutes(4), 6.0, None));
+        history.record("gone", point(now() - Duration::days(30), 1.0, None));
+
+        history.compact(now(), 7);
+
+        let points = &history.containers["db-1"];
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].cpu_percent, 20.0);
+        assert_eq!(points[0].samples, 2);
+        assert!(!history.containers.contains_key("gone"));
+
+        let compacted = history.containers.clone();
+        history.compact(now(), 7);
+        assert_eq!(history.containers, compacted);
+    }
+
+    #[test]
+    fn test_query_filters_and_buckets() {
+        let mut history = MetricsHistory::default();
+        for minute in 0..10 {
+            history.record(
+                "db-1",
+                point(now() - Duration::minutes(minute), minute as f64, None),
+            );
+        }
+
+        let recent = history.query("db-1", Some(now() - Duration::minutes(4)), None, None);
+        assert_eq!(recent.len(), 5);
+
+        let bucketed = history.query("db-1", None, None, Some(3600));
+        assert_eq!(bucketed.len(), 2);
+        assert_eq!(bucketed[1].timestamp, "2024-05-10T12:00:00+00:00");
+
+        assert!(history.query("other", None, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_merge_appends_newer_points() {
+        let mut stored = MetricsHistory::default();
+        stored.record("db-1", point(now() - Duration::minutes(5), 10.0, None));
+        let mut pending = MetricsHistory::default();
+        pending.record("db-1", point(now(), 20.0, None));
+        pending.record("db-2", point(now(), 30.0, None));
+
+        stored.merge(pending);
+
+        let points = stored.query("db-1", None, None, None);
+        assert_eq!(
+            points.iter().map(|p| p.cpu_percent).collect::<Vec<_>>(),
+            [10.0, 20.0]
+        );
+        assert_eq!(stored.query("db-2", None, None, None).len(), 1);
+        assert!(MetricsHistory::default().is_empty());
+    }
+
+    #[test]
+    fn test_parse_connections_leaves_out_the_probe() {
+        assert_eq!(MetricsHistoryService::parse_connections("3\n"), Some(2));
+        assert_eq!(
+            MetricsHistoryService::parse_connections("Threads_connected\t5\n"),
+            Some(4)
+        );
+        assert_eq!(
+            MetricsHistoryService::parse_connections(
+                "# Clients\r\nconnected_clients:7\r\nblocked_clients:0\r\n"
+            ),
+            Some(6)
+        );
+        assert_eq!(MetricsHistoryService::parse_connections("error"), None);
+    }
+
+    #[test]
+    fn test_parse_du_output_sums_paths() {
+        assert_eq!(
+            MetricsHistoryService::parse_du_output("100\t/var/lib/mysql\n28\t/etc/mysql/conf.d\n"),
+            Some(128 * 1024)
+        );
+        assert_eq!(MetricsHistoryService::parse_du_output(""), None);
+    }
+
+    #[test]
+    fn test_data_paths_fall_back_to_the_engine_default() {
+        let database = DatabaseContainer {
+            db_type: "PostgreSQL".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            MetricsHistoryService::data_paths(&database),
+            ["/var/lib/postgresql/data"]
+        );
+    }
+
+    #[test]
+    fn test_connections_args_keep_the_password_off_the_command_line() {
+        let env_file = Path::new("/tmp/db.env");
+        for db_type in ["Redis", "MongoDB", "MySQL"] {
+            let database = DatabaseContainer {
+                name: "app-db".to_string(),
+                db_type: db_type.to_string(),
+                stored_password: Some("secret".to_string()),
+                stored_enable_auth: true,
+                ..Default::default()
+            };
+
+            let args = MetricsHistoryService::connections_args(&database, Some(env_file)).unwrap();
+            assert_eq!(args[..4], ["exec", "--env-file", "/tmp/db.env", "app-db"]);
+            assert!(!args.iter().any(|arg| arg.contains("secret")));
+            assert_eq!(
+                MetricsHistoryService::exec_credentials(&database)[0].1,
+                "secret"
+            );
+        }
+    }
+}
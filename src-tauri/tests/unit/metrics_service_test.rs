@@ -0,0 +1,35 @@
+use docker_db_manager_lib::services::MetricsService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod metrics_service_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("0B"), 0);
+        assert_eq!(parse_size("1.5kB"), 1500);
+        assert_eq!(parse_size("8MiB"), 8 * 1024 * 1024);
+        assert_eq!(parse_size(" 2GiB "), 2 * 1024 * 1024 * 1024);
+        assert_eq!(parse_size("--"), 0);
+    }
+
+    #[test]
+    fn test_parse_stats_output() {
+        let output = r#"{"BlockIO":"4.1MB / 0B","CPUPerc":"1.25%","Container":"abc","ID":"abc","MemPerc":"0.50%","MemUsage":"40MiB / 7.6GiB","Name":"my-postgres","NetIO":"1.2kB / 648B","PIDs":"7"}
+{"BlockIO":"0B / 0B","CPUPerc":"0.30%","Container":"def","ID":"def","MemPerc":"0.10%","MemUsage":"8MiB / 7.6GiB","Name":"unmanaged","NetIO":"0B / 0B","PIDs":"4"}
+not json"#;
+        let names = vec![("my-postgres".to_string(), "db-1".to_string())];
+
+        let metrics = MetricsService::parse_stats_output(output, &names);
+
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].id, "db-1");
+        assert_eq!(metrics[0].cpu_percent, 1.25);
+        assert_eq!(metrics[0].memory_used_bytes, 40 * 1024 * 1024);
+        assert_eq!(metrics[0].net_rx_bytes, 1200);
+        assert_eq!(metrics[0].net_tx_bytes, 648);
+        assert_eq!(metrics[0].block_read_bytes, 4_100_000);
+        assert_eq!(metrics[0].pids, 7);
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{Duration, Utc};
+use docker_db_manager_lib::services::HealthMonitor;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod notification_service_tests {
+    use super::*;
+
+    fn state(status: &str) -> DockerContainerState {
+        DockerContainerState::from_ps_status("abc123", status)
+    }
+
+    #[test]
+    fn test_parse_ps_status() {
+        let healthy = state("Up 2 minutes (healthy)");
+        assert!(healthy.running);
+        assert_eq!(healthy.health.as_deref(), Some("healthy"));
+
+        let exited = state("Exited (137) 5 seconds ago");
+        assert!(!exited.running);
+        assert_eq!(exited.exit_code, Some(137));
+
+        assert!(state("Restarting (1) 3 seconds ago").restarting);
+    }
+
+    #[test]
+    fn test_crash_of_running_container_alerts() {
+        let mut monitor = HealthMonitor::default();
+
+        let alert = monitor.observe("db", &state("Exited (1) 1 second ago"), true, Utc::now());
+
+        assert_eq!(alert, Some(ContainerAlert::Died { exit_code: 1 }));
+    }
+
+    #[test]
+    fn test_clean_exit_does_not_alert() {
+        let mut monitor = HealthMonitor::default();
+
+        let alert = monitor.observe("db", &state("Exited (0) 1 second ago"), true, Utc::now());
+
+        assert_eq!(alert, None);
+    }
+
+    #[test]
+    fn test_unhealthy_alerts_once() {
+        let mut monitor = HealthMonitor::default();
+        let now = Utc::now();
+
+        assert_eq!(
+            monitor.observe("db", &state("Up 1 minute (unhealthy)"), true, now),
+            Some(ContainerAlert::Unhealthy)
+        );
+        assert_eq!(
+            monitor.observe("db", &state("Up 2 minutes (unhealthy)"), true, now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_repeated_restarts_alert() {
+        let mut monitor = HealthMonitor::default();
+        let now = Utc::now();
+        let mut alerts = Vec::new();
+
+        for minute in 0..3 {
+            let at = now + Duration::minutes(minute);
+            alerts.push(monitor.observe("db", &state("Restarting (1) 1 second ago"), true, at));
+            monitor.observe("db", &state("Up 1 second"), false, at);
+        }
+
+        assert_eq!(alerts, vec![None, None, Some(ContainerAlert::RestartLoop)]);
+    }
+
+    #[test]
+    fn test_restarts_between_syncs_count_towards_loop() {
+        let mut monitor = HealthMonitor::default();
+        let now = Utc::now();
+        let mut alerts = Vec::new();
+
+        for restart_count in 0..4 {
+            let state = DockerContainerState {
+                restart_count,
+                ..state("Up 1 second")
+            };
+            alerts.push(monitor.observe("db", &state, true, now));
+        }
+
+        assert_eq!(
+            alerts,
+            vec![None, None, None, Some(ContainerAlert::RestartLoop)]
+        );
+    }
+}
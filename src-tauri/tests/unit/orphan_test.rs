@@ -0,0 +1,174 @@
+use docker_db_manager_lib::services::DockerService;
+use docker_db_manager_lib::types::*;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod orphan_tests {
+    use super::*;
+
+    fn labels() -> ManagementLabels {
+        ManagementLabels {
+            database_id: "db-1".to_string(),
+            db_type: "PostgreSQL".to_string(),
+            workspace: "default".to_string(),
+        }
+    }
+
+    fn orphan(name: &str, workspace: &str) -> OrphanContainer {
+        OrphanContainer {
+            container_id: "abc123".to_string(),
+            name: name.to_string(),
+            image: "postgres:16".to_string(),
+            running: true,
+            labels: ManagementLabels {
+                workspace: workspace.to_string(),
+                ..labels()
+            },
+        }
+    }
+
+    #[test]
+    fn test_labels_are_added_before_the_image() {
+        let docker_args = DockerRunArgs {
+            image: "postgres:16".to_string(),
+            env_vars: HashMap::new(),
+            ports: vec![],
+            volumes: vec![],
+            command: vec![],
+            shm_size: None,
+        };
+
+        let args = DockerService::new().build_database_command("my-db", &docker_args, &labels());
+
+        assert_eq!(&args[..4], &["run", "-d", "--name", "my-db"]);
+        assert_eq!(args[4], "--label");
+        assert_eq!(args[5], format!("{}=db-1", DATABASE_ID_LABEL));
+        assert_eq!(args.last().unwrap(), "postgres:16");
+    }
+
+    #[test]
+    fn test_ps_format_prints_each_label() {
+        let format = OrphanContainer::ps_format();
+
+        assert!(format.starts_with("{{json .}}\t"));
+        assert!(format.contains(&format!("{{{{json (.Label \"{}\")}}}}", WORKSPACE_LABEL)));
+    }
+
+    #[test]
+    fn test_from_ps_line_reads_our_labels() {
+        let line = format!(
+            "{}\t\"db-1\"\t\"PostgreSQL\"\t\"a,b=c\"",
+            json!({
+                "ID": "abc123",
+                "Names": "my-db",
+                "Image": "postgres:16",
+                "State": "exited",
+                "Labels": "maintainer=someone",
+            })
+        );
+
+        let orphan = OrphanContainer::from_ps_line(&line).unwrap();
+
+        assert_eq!(orphan.name, "my-db");
+        assert!(!orphan.running);
+        // Commas stay part of the value
+        assert_eq!(orphan.labels.workspace, "a,b=c");
+        assert_eq!(orphan.labels.database_id, "db-1");
+    }
+
+    #[test]
+    fn test_from_ps_line_skips_unlabelled_containers() {
+        let line = format!(
+            "{}\t\"\"\t\"\"\t\"\"",
+            json!({
+                "ID": "abc123",
+                "Names": "other",
+                "Image": "nginx",
+                "State": "running",
+                "Labels": "maintainer=someone",
+            })
+        );
+
+        assert!(OrphanContainer::from_ps_line(&line).is_none());
+    }
+
+    #[test]
+    fn test_containers_of_other_known_workspaces_are_not_orphans() {
+        let known_workspaces = vec!["default".to_string(), "client-x".to_string()];
+
+        assert!(orphan("my-db", "default").is_orphan(&[], "default", &known_workspaces));
+        assert!(!orphan("my-db", "client-x").is_orphan(&[], "default", &known_workspaces));
+        // Created by another machine, no workspace here claims it
+        assert!(orphan("my-db", "laptop").is_orphan(&[], "default", &known_workspaces));
+        assert!(!orphan("my-db", "default").is_orphan(
+            &["my-db".to_string()],
+            "default",
+            &known_workspaces
+        ));
+    }
+
+    #[test]
+    fn test_adopted_database_leaves_out_image_defaults() {
+        let container = json!({
+            "Created": "2025-03-01T10:00:00.123456789Z",
+            "Config": {
+                "Image": "postgres:16",
+                "Env": ["POSTGRES_PASSWORD=secret", "POSTGRES_DB=app", "PGDATA=/var/lib/postgresql/data"],
+                "Cmd": ["postgres"]
+            },
+            "HostConfig": {
+                "PortBindings": {"5432/tcp": [{"HostIp": "", "HostPort": "15432"}]}
+            },
+            "Mounts": [
+                {"Type": "volume", "Name": "my-db-data", "Destination": "/var/lib/postgresql/data"},
+                {"Type": "bind", "Source": "/tmp", "Destination": "/certs"}
+            ]
+        });
+        let image = json!({
+            "Config": {"Env": ["PGDATA=/var/lib/postgresql/data"], "Cmd": ["postgres"]}
+        });
+
+        let database = adopted_database(
+            &orphan("my-db", "default"),
+            &container,
+            &image,
+            "db-1".into(),
+        );
+        let docker_args = database.stored_docker_args.unwrap();
+
+        assert_eq!(database.port, 15432);
+        assert_eq!(database.version, "16");
+        assert_eq!(database.status, "running");
+        assert_eq!(database.stored_password.as_deref(), Some("secret"));
+        assert_eq!(database.stored_database_name.as_deref(), Some("app"));
+        assert!(database.stored_persist_data);
+        assert!(database.created_at.starts_with("2025-03-01T10:00:00"));
+        assert_eq!(docker_args.env_vars.len(), 2);
+        assert!(docker_args.command.is_empty());
+        assert_eq!(docker_args.volumes.len(), 1);
+    }
+
+    #[test]
+    fn test_adopted_redis_reads_password_from_command() {
+        let container = json!({
+            "Config": {
+                "Image": "redis:7",
+                "Env": [],
+                "Cmd": ["redis-server", "--requirepass", "secret"]
+            }
+        });
+        let redis = OrphanContainer {
+            labels: ManagementLabels {
+                db_type: "Redis".to_string(),
+                ..labels()
+            },
+            ..orphan("cache", "default")
+        };
+
+        let database = adopted_database(&redis, &container, &json!({}), "db-1".into());
+
+        assert_eq!(database.stored_password.as_deref(), Some("secret"));
+        assert!(database.stored_enable_auth);
+    }
+}
@@ -0,0 +1,88 @@
+use docker_db_manager_lib::services::SqlService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod query_export_tests {
+    use super::*;
+
+    fn database(db_type: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            name: "app-db".to_string(),
+            db_type: db_type.to_string(),
+            stored_password: Some("secret".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn columns() -> Vec<String> {
+        vec!["id".to_string(), "note".to_string()]
+    }
+
+    #[test]
+    fn test_unescape_field_reads_nulls_and_escapes() {
+        assert_eq!(unescape_field("\\N", SqlDialect::Postgres), None);
+        assert_eq!(unescape_field("NULL", SqlDialect::MySql), None);
+        assert_eq!(
+            unescape_field("NULL", SqlDialect::Postgres),
+            Some("NULL".to_string())
+        );
+        assert_eq!(
+            unescape_field("a\\tb\\nc\\\\d", SqlDialect::Postgres),
+            Some("a\tb\nc\\d".to_string())
+        );
+        assert_eq!(unescape_field("", SqlDialect::MySql), Some(String::new()));
+    }
+
+    #[test]
+    fn test_csv_export_has_header_and_quoted_records() {
+        let mut writer = QueryExportWriter::new(QueryExportFormat::Csv, columns());
+        let mut output = writer.start();
+        output.push_str(&writer.row(&[Some("1".to_string()), Some("a, \"b\"".to_string())]));
+        output.push_str(&writer.row(&[Some("2".to_string()), None]));
+        output.push_str(&writer.finish());
+
+        assert_eq!(output, "id,note\n1,\"a, \"\"b\"\"\"\n2,\n");
+        assert_eq!(writer.rows(), 2);
+    }
+
+    #[test]
+    fn test_json_export_keeps_column_order_and_nulls() {
+        let mut writer = QueryExportWriter::new(QueryExportFormat::Json, columns());
+        let mut output = writer.start();
+        output.push_str(&writer.row(&[Some("1".to_string()), Some("line\nbreak".to_string())]));
+        output.push_str(&writer.row(&[Some("2".to_string()), None]));
+        output.push_str(&writer.finish());
+
+        assert_eq!(
+            output,
+            "[\n{\"id\":\"1\",\"note\":\"line\\nbreak\"},\n{\"id\":\"2\",\"note\":null}\n]\n"
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_empty_results_are_valid_files() {
+        let json = QueryExportWriter::new(QueryExportFormat::Json, columns());
+        assert_eq!(format!("{}{}", json.start(), json.finish()), "[]\n");
+
+        let csv = QueryExportWriter::new(QueryExportFormat::Csv, Vec::new());
+        assert_eq!(format!("{}{}", csv.start(), csv.finish()), "");
+    }
+
+    #[test]
+    fn test_export_args_stream_rows() {
+        let args =
+            SqlService::export_args(&database("PostgreSQL"), None, "SELECT * FROM users", true)
+                .unwrap();
+        assert_eq!(args.last().unwrap(), "COPY (SELECT * FROM users) TO STDOUT");
+        assert!(args.contains(&"PGOPTIONS=-c default_transaction_read_only=on".to_string()));
+
+        let args = SqlService::export_args(&database("MySQL"), None, "SELECT 1", false).unwrap();
+        assert!(args.contains(&"--quick".to_string()));
+        assert!(!args.contains(&"--raw".to_string()));
+        assert_eq!(args.last().unwrap(), "SELECT 1");
+
+        assert!(SqlService::export_args(&database("Redis"), None, "GET a", false).is_err());
+    }
+}
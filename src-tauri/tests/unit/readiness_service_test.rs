@@ -0,0 +1,92 @@
+use docker_db_manager_lib::services::ReadinessService;
+use docker_db_manager_lib::types::DatabaseContainer;
+use std::path::Path;
+
+#[cfg(test)]
+mod readiness_service_tests {
+    use super::*;
+
+    fn create_test_database(db_type: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-id".to_string(),
+            name: "my-db".to_string(),
+            db_type: db_type.to_string(),
+            stored_username: Some("admin".to_string()),
+            stored_password: Some("secret".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_postgres_probe_uses_tcp() {
+        let args = ReadinessService::probe_args(&create_test_database("PostgreSQL"), None).unwrap();
+
+        assert_eq!(
+            args,
+            vec![
+                "exec",
+                "my-db",
+                "pg_isready",
+                "-h",
+                "127.0.0.1",
+                "-U",
+                "admin"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mysql_probe_passes_password_through_env_file() {
+        let database = create_test_database("MySQL");
+        let args = ReadinessService::probe_args(&database, Some(Path::new("/tmp/db.env"))).unwrap();
+
+        assert_eq!(&args[..4], ["exec", "--env-file", "/tmp/db.env", "my-db"]);
+        assert!(args.contains(&"mysqladmin".to_string()));
+        assert!(!args.iter().any(|arg| arg.contains("secret")));
+        assert_eq!(
+            ReadinessService::exec_credentials(&database),
+            vec![("MYSQL_PWD", "secret".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_redis_without_password_has_no_auth() {
+        let mut database = create_test_database("Redis");
+        database.stored_password = None;
+
+        let args = ReadinessService::probe_args(&database, None).unwrap();
+        assert!(ReadinessService::exec_credentials(&database).is_empty());
+
+        assert_eq!(
+            args,
+            vec!["exec", "my-db", "redis-cli", "-h", "127.0.0.1", "ping"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_engine_has_no_probe() {
+        assert!(
+            ReadinessService::probe_args(&create_test_database("Elasticsearch"), None).is_none()
+        );
+    }
+
+    #[test]
+    fn test_probe_output_is_checked() {
+        assert!(ReadinessService::probe_succeeded(
+            "Redis",
+            Some(0),
+            "PONG\n"
+        ));
+        assert!(!ReadinessService::probe_succeeded(
+            "Redis",
+            Some(0),
+            "LOADING Redis is loading the dataset in memory"
+        ));
+        assert!(ReadinessService::probe_succeeded("MongoDB", Some(0), "1\n"));
+        assert!(!ReadinessService::probe_succeeded(
+            "PostgreSQL",
+            Some(2),
+            ""
+        ));
+    }
+}
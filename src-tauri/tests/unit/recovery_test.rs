@@ -0,0 +1,89 @@
+use docker_db_manager_lib::types::*;
+use std::collections::{HashMap, HashSet};
+
+#[cfg(test)]
+mod recovery_tests {
+    use super::*;
+
+    fn create_pending() -> PendingCreate {
+        PendingCreate {
+            database_id: "db-1".to_string(),
+            name: "my-db".to_string(),
+            volumes: vec!["my-db-data".to_string()],
+            started_at: "2026-10-16T10:00:00Z".to_string(),
+            host: None,
+        }
+    }
+
+    fn create_entry(id: &str, name: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: id.to_string(),
+            name: name.to_string(),
+            stored_persist_data: true,
+            ..Default::default()
+        }
+    }
+
+    fn names(values: &[&str]) -> HashSet<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_finished_creation_needs_nothing() {
+        let databases = HashMap::from([("db-1".to_string(), create_entry("db-1", "my-db"))]);
+
+        let plan =
+            create_pending().recovery_plan(&databases, &names(&["my-db"]), &names(&["my-db-data"]));
+
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_container_without_entry_is_removed() {
+        let plan = create_pending().recovery_plan(
+            &HashMap::new(),
+            &names(&["my-db"]),
+            &names(&["my-db-data"]),
+        );
+
+        assert_eq!(
+            plan,
+            RecoveryPlan {
+                remove_container: true,
+                remove_entry: false,
+                remove_volumes: vec!["my-db-data".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_volume_without_container_is_removed() {
+        let plan =
+            create_pending().recovery_plan(&HashMap::new(), &names(&[]), &names(&["my-db-data"]));
+
+        assert!(!plan.remove_container);
+        assert_eq!(plan.remove_volumes, vec!["my-db-data".to_string()]);
+    }
+
+    #[test]
+    fn test_entry_without_container_is_removed() {
+        let databases = HashMap::from([("db-1".to_string(), create_entry("db-1", "my-db"))]);
+
+        let plan = create_pending().recovery_plan(&databases, &names(&[]), &names(&[]));
+
+        assert!(plan.remove_entry);
+        assert!(!plan.remove_container);
+        assert!(plan.remove_volumes.is_empty());
+    }
+
+    #[test]
+    fn test_resources_of_another_database_are_kept() {
+        // A later creation reused the name after this one failed
+        let databases = HashMap::from([("db-2".to_string(), create_entry("db-2", "my-db"))]);
+
+        let plan =
+            create_pending().recovery_plan(&databases, &names(&["my-db"]), &names(&["my-db-data"]));
+
+        assert!(plan.is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod region_tests {
+    use super::*;
+
+    fn docker_args(command: &[&str]) -> DockerRunArgs {
+        DockerRunArgs {
+            image: "postgres:16".to_string(),
+            env_vars: HashMap::new(),
+            ports: vec![],
+            volumes: vec![],
+            command: command.iter().map(|arg| arg.to_string()).collect(),
+            shm_size: None,
+        }
+    }
+
+    fn region(timezone: Option<&str>, lang: Option<&str>) -> RegionSettings {
+        RegionSettings {
+            timezone: timezone.map(str::to_string),
+            lang: lang.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_apply_sets_environment() {
+        let mut args = docker_args(&[]);
+        region(Some("Europe/Madrid"), Some("es_ES.UTF-8")).apply("MySQL", &mut args);
+
+        assert_eq!(args.env_vars["TZ"], "Europe/Madrid");
+        assert_eq!(args.env_vars["LANG"], "es_ES.UTF-8");
+        assert!(args.command.is_empty());
+
+        region(None, Some("es_ES.UTF-8")).apply("MySQL", &mut args);
+        assert!(!args.env_vars.contains_key("TZ"));
+    }
+
+    #[test]
+    fn test_postgres_settings_are_replaced() {
+        let mut args = docker_args(&["-c", "max_connections=50"]);
+        region(Some("UTC"), None).apply("PostgreSQL", &mut args);
+        region(Some("America/New_York"), None).apply("PostgreSQL", &mut args);
+
+        assert_eq!(
+            args.command,
+            vec![
+                "-c",
+                "max_connections=50",
+                "-c",
+                "timezone=America/New_York",
+                "-c",
+                "log_timezone=America/New_York",
+            ]
+        );
+
+        region(None, None).apply("PostgreSQL", &mut args);
+        assert_eq!(args.command, vec!["-c", "max_connections=50"]);
+    }
+
+    #[test]
+    fn test_defaults_fill_unset_fields() {
+        let defaults = region(Some("Europe/Berlin"), Some("de_DE.UTF-8"));
+
+        let filled = region(Some("UTC"), Some(" ")).or_defaults(&defaults);
+
+        assert_eq!(filled, region(Some("UTC"), Some("de_DE.UTF-8")));
+        assert!(RegionSettings::default()
+            .or_defaults(&RegionSettings::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_invalid_names_are_reported() {
+        assert!(region(Some("Etc/GMT+3"), Some("C.UTF-8"))
+            .violations()
+            .is_empty());
+        assert_eq!(
+            region(Some("Europe/Madrid; rm"), None).violations().len(),
+            1
+        );
+        assert_eq!(region(Some("/etc/localtime"), None).violations().len(), 1);
+        assert_eq!(region(None, Some("en US")).violations().len(), 1);
+    }
+
+    #[test]
+    fn test_postgres_lang_must_exist_in_image() {
+        let german = region(None, Some("de_DE.UTF-8"));
+        assert_eq!(german.violations_for("PostgreSQL", "16").len(), 1);
+        assert!(german.violations_for("MySQL", "8.4").is_empty());
+
+        let english = region(None, Some("en_US.UTF-8"));
+        assert!(english.violations_for("PostgreSQL", "16").is_empty());
+        assert_eq!(english.violations_for("PostgreSQL", "16-alpine").len(), 1);
+        assert!(region(None, Some("C.UTF-8"))
+            .violations_for("PostgreSQL", "16-alpine")
+            .is_empty());
+    }
+}
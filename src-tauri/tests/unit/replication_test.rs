@@ -0,0 +1,96 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod replication_tests {
+    use super::*;
+
+    #[test]
+    fn test_enabling_sets_logical_wal_with_defaults() {
+        let request = LogicalReplicationRequest {
+            enabled: true,
+            max_replication_slots: None,
+            max_wal_senders: Some(4),
+        };
+
+        assert_eq!(
+            request.alter_system_sql().unwrap(),
+            vec![
+                "ALTER SYSTEM SET wal_level = 'logical'".to_string(),
+                format!(
+                    "ALTER SYSTEM SET max_replication_slots = {}",
+                    DEFAULT_MAX_REPLICATION_SLOTS
+                ),
+                "ALTER SYSTEM SET max_wal_senders = 4".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disabling_resets_the_settings() {
+        let statements = LogicalReplicationRequest::default()
+            .alter_system_sql()
+            .unwrap();
+
+        assert_eq!(statements.len(), 3);
+        assert!(statements
+            .iter()
+            .all(|statement| statement.starts_with("ALTER SYSTEM RESET")));
+    }
+
+    #[test]
+    fn test_out_of_range_limits_are_rejected() {
+        for slots in [0, MAX_REPLICATION_CONNECTIONS + 1] {
+            let request = LogicalReplicationRequest {
+                enabled: true,
+                max_replication_slots: Some(slots),
+                max_wal_senders: None,
+            };
+            assert!(matches!(
+                request.alter_system_sql(),
+                Err(AppError::Validation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_replication_names_follow_slot_rules() {
+        assert!(validate_replication_name("debezium_slot_1", "Slot").is_ok());
+        assert!(validate_replication_name("", "Slot").is_err());
+        assert!(validate_replication_name("Debezium", "Slot").is_err());
+        assert!(validate_replication_name("my-slot", "Slot").is_err());
+        assert!(validate_replication_name(&"a".repeat(64), "Slot").is_err());
+    }
+
+    #[test]
+    fn test_publication_sql_quotes_tables() {
+        assert_eq!(
+            create_publication_sql("cdc", &[]).unwrap(),
+            "CREATE PUBLICATION \"cdc\" FOR ALL TABLES"
+        );
+        assert_eq!(
+            create_publication_sql(
+                "cdc",
+                &["public.orders".to_string(), " Customers ".to_string()]
+            )
+            .unwrap(),
+            "CREATE PUBLICATION \"cdc\" FOR TABLE \"public\".\"orders\", \"Customers\""
+        );
+        assert!(create_publication_sql("cdc", &[" ".to_string()]).is_err());
+        assert!(create_publication_sql("CDC", &[]).is_err());
+    }
+
+    #[test]
+    fn test_status_reads_client_json() {
+        let status: LogicalReplicationStatus = serde_json::from_str(
+            r#"{"walLevel":"logical","maxReplicationSlots":10,"maxWalSenders":10,"pendingRestart":false}"#,
+        )
+        .unwrap();
+        assert!(status.enabled());
+
+        let slot: ReplicationSlot = serde_json::from_str(
+            r#"{"name":"s","plugin":"pgoutput","slotType":"logical","database":"app","active":false,"restartLsn":"0/1A2B3C","confirmedFlushLsn":null,"retainedWalBytes":1024}"#,
+        )
+        .unwrap();
+        assert_eq!(slot.retained_wal_bytes, Some(1024));
+    }
+}
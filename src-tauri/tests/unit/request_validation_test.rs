@@ -0,0 +1,160 @@
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod request_validation_tests {
+    use super::*;
+
+    fn create_test_request(port: i32) -> DockerRunRequest {
+        DockerRunRequest {
+            name: "my-db".to_string(),
+            docker_args: DockerRunArgs {
+                image: "postgres:16".to_string(),
+                env_vars: HashMap::new(),
+                ports: vec![PortMapping::local(port, 5432)],
+                volumes: vec![],
+                command: vec![],
+                shm_size: None,
+            },
+            metadata: ContainerMetadata {
+                id: "new-id".to_string(),
+                db_type: "PostgreSQL".to_string(),
+                version: "16".to_string(),
+                port,
+                password: "secret".to_string(),
+                enable_auth: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn violations(result: Result<(), AppError>) -> Vec<FieldViolation> {
+        match result {
+            Err(AppError::InvalidRequest { violations }) => violations,
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_valid_request_passes() {
+        assert!(validate_run_request(&create_test_request(5432), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_port_out_of_range() {
+        let violations = violations(validate_run_request(&create_test_request(70000), &[]));
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].field, "port");
+    }
+
+    #[test]
+    fn test_privileged_port_only_warns() {
+        let request = create_test_request(80);
+
+        assert!(validate_run_request(&request, &[]).is_ok());
+        let warnings = run_request_warnings(&request);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("privileged"));
+        assert!(run_request_warnings(&create_test_request(5432)).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_port_mapping() {
+        let mut request = create_test_request(5432);
+        request
+            .docker_args
+            .ports
+            .extend([PortMapping::local(8080, 80), PortMapping::local(8080, 443)]);
+
+        let violations = violations(validate_run_request(&request, &[]));
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].field, "ports");
+    }
+
+    #[test]
+    fn test_port_used_by_another_database() {
+        let other = DatabaseContainer {
+            id: "other-id".to_string(),
+            name: "other-db".to_string(),
+            port: 5432,
+            ..Default::default()
+        };
+
+        let violations = violations(validate_run_request(&create_test_request(5432), &[&other]));
+
+        assert!(violations[0].message.contains("other-db"));
+    }
+
+    #[test]
+    fn test_all_violations_are_reported() {
+        let mut request = create_test_request(0);
+        request.metadata.password = " ".to_string();
+        request.metadata.version = "16 alpine".to_string();
+
+        let error = validate_run_request(&request, &[]).unwrap_err();
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(error.code(), "INVALID_REQUEST");
+        let fields: Vec<&str> = json["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|violation| violation["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, vec!["port", "password", "version"]);
+    }
+
+    #[test]
+    fn test_password_not_required_without_auth() {
+        let mut request = create_test_request(6379);
+        request.metadata.enable_auth = false;
+        request.metadata.password = String::new();
+
+        assert!(validate_run_request(&request, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_post_create_sql_needs_a_sql_engine() {
+        let mut request = create_test_request(5432);
+        request.metadata.post_create_sql =
+            vec!["CREATE EXTENSION IF NOT EXISTS vector".to_string()];
+        assert!(validate_run_request(&request, &[]).is_ok());
+
+        request.metadata.db_type = "Redis".to_string();
+        let violations = violations(validate_run_request(&request, &[]));
+
+        assert_eq!(violations[0].field, "postCreateSql");
+    }
+
+    #[test]
+    fn test_extra_args_need_an_engine_that_forwards_them() {
+        let mut request = create_test_request(5432);
+        request.metadata.extra_args = vec!["-c".to_string(), "shared_buffers=256MB".to_string()];
+        assert!(validate_run_request(&request, &[]).is_ok());
+
+        request.metadata.db_type = "SQLServer".to_string();
+        let violations = violations(validate_run_request(&request, &[]));
+
+        assert_eq!(violations[0].field, "extraArgs");
+    }
+
+    #[test]
+    fn test_blank_extra_arg_is_rejected() {
+        let mut request = create_test_request(5432);
+        request.metadata.extra_args = vec!["-c".to_string(), " ".to_string()];
+
+        let violations = violations(validate_run_request(&request, &[]));
+
+        assert_eq!(violations[0].field, "extraArgs");
+    }
+
+    #[test]
+    fn test_stop_timeout_range() {
+        assert!(validate_stop_timeout(None).is_ok());
+        assert!(validate_stop_timeout(Some(120)).is_ok());
+        assert!(validate_stop_timeout(Some(0)).is_err());
+        assert!(validate_stop_timeout(Some(MAX_STOP_TIMEOUT_SECS + 1)).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{Duration, Local, TimeZone, Utc};
+use docker_db_manager_lib::services::SchedulerService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod scheduler_service_tests {
+    use super::*;
+
+    fn schedule(container_ids: Vec<String>) -> ContainerSchedule {
+        ContainerSchedule {
+            id: "schedule-id".to_string(),
+            name: "Work hours".to_string(),
+            container_ids,
+            action: ScheduledAction::Start,
+            cron: "0 9 * * 1-5".to_string(),
+            enabled: true,
+            created_at: "2026-10-12T08:00:00+00:00".to_string(),
+            last_run_at: None,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn test_invalid_cron_is_rejected() {
+        assert!(matches!(
+            SchedulerService::parse("61 * * * *"),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_latest_occurrence_since_last_run() {
+        let cron = SchedulerService::parse("0 9 * * 1-5").unwrap();
+        let since = Utc.with_ymd_and_hms(2026, 10, 12, 8, 0, 0).unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 9, 10, 0).unwrap();
+
+        assert_eq!(
+            SchedulerService::latest_occurrence(&cron, &since, &now),
+            Some(Utc.with_ymd_and_hms(2026, 10, 14, 9, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_handled_occurrence_does_not_run_again() {
+        let cron = SchedulerService::parse("0 9 * * 1-5").unwrap();
+        let last_run = Utc.with_ymd_and_hms(2026, 10, 14, 9, 0, 0).unwrap();
+        let now = last_run + Duration::minutes(10);
+
+        assert!(SchedulerService::latest_occurrence(&cron, &last_run, &now).is_none());
+    }
+
+    #[test]
+    fn test_long_missed_occurrence_is_outside_grace() {
+        let occurrence = Utc.with_ymd_and_hms(2026, 10, 14, 9, 0, 0).unwrap();
+
+        assert!(SchedulerService::within_grace(
+            &occurrence,
+            &(occurrence + Duration::minutes(5))
+        ));
+        assert!(!SchedulerService::within_grace(
+            &occurrence,
+            &(occurrence + Duration::hours(12))
+        ));
+    }
+
+    #[test]
+    fn test_due_occurrence_counts_from_last_run_or_creation() {
+        let cron = SchedulerService::parse("0 * * * *").unwrap();
+        let created = Local.with_ymd_and_hms(2026, 10, 14, 8, 30, 0).unwrap();
+        let now = Local.with_ymd_and_hms(2026, 10, 14, 10, 5, 0).unwrap();
+        let ten = Local.with_ymd_and_hms(2026, 10, 14, 10, 0, 0).unwrap();
+
+        assert_eq!(
+            SchedulerService::due_occurrence(&cron, &created.to_rfc3339(), None, &now),
+            Some((ten, true))
+        );
+        assert_eq!(
+            SchedulerService::due_occurrence(
+                &cron,
+                &created.to_rfc3339(),
+                Some(&ten.to_rfc3339()),
+                &now
+            ),
+            None
+        );
+
+        // Found 45 minutes late, e.g. when the app was closed at 19:00
+        let late = ten + Duration::hours(9) + Duration::minutes(45);
+        assert_eq!(
+            SchedulerService::due_occurrence(
+                &cron,
+                &created.to_rfc3339(),
+                Some(&ten.to_rfc3339()),
+                &late
+            ),
+            Some((ten + Duration::hours(9), false))
+        );
+    }
+
+    #[test]
+    fn test_empty_targets_every_container() {
+        assert!(schedule(Vec::new()).targets("any-db"));
+
+        let scoped = schedule(vec!["db-1".to_string()]);
+        assert!(scoped.targets("db-1"));
+        assert!(!scoped.targets("db-2"));
+    }
+}
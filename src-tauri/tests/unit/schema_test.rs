@@ -0,0 +1,131 @@
+use docker_db_manager_lib::types::*;
+use serde_json::json;
+
+#[cfg(test)]
+mod schema_tests {
+    use super::*;
+
+    fn catalog() -> DatabaseSchema {
+        let columns = vec![
+            json!({"schema": "public", "table": "users", "name": "id", "dataType": "integer", "nullable": false, "primaryKey": true}),
+            json!({"schema": "public", "table": "users", "name": "email", "dataType": "character varying", "nullable": false, "primaryKey": false}),
+            json!({"schema": "public", "table": "orders", "name": "id", "dataType": "integer", "nullable": false, "primaryKey": true}),
+            json!({"schema": "public", "table": "orders", "name": "user_id", "dataType": "integer", "nullable": 1, "primaryKey": 0}),
+        ];
+        let foreign_keys = vec![json!({
+            "schema": "public", "table": "orders", "name": "orders_user_id_fkey", "column": "user_id",
+            "referencedSchema": "public", "referencedTable": "users", "referencedColumn": "id"
+        })];
+        let indexes = vec![
+            json!({"schema": "public", "table": "users", "name": "users_email_key", "column": "email", "unique": true}),
+            json!({"schema": "public", "table": "users", "name": "users_lower_idx", "column": null, "unique": false}),
+        ];
+
+        DatabaseSchema::from_catalog("app", &columns, &foreign_keys, &indexes)
+    }
+
+    #[test]
+    fn test_from_catalog_groups_rows_by_table() {
+        let schema = catalog();
+
+        assert_eq!(schema.database, "app");
+        assert_eq!(schema.tables.len(), 2);
+
+        let users = &schema.tables[0];
+        assert_eq!(users.name, "users");
+        assert_eq!(users.primary_key, vec!["id"]);
+        assert_eq!(
+            users.indexes,
+            vec![IndexInfo {
+                name: "users_email_key".to_string(),
+                columns: vec!["email".to_string()],
+                unique: true,
+            }]
+        );
+
+        let orders = &schema.tables[1];
+        assert!(orders.columns[1].nullable);
+        assert_eq!(
+            orders.foreign_keys,
+            vec![ForeignKey {
+                name: "orders_user_id_fkey".to_string(),
+                columns: vec!["user_id".to_string()],
+                referenced_schema: "public".to_string(),
+                referenced_table: "users".to_string(),
+                referenced_columns: vec!["id".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_composite_foreign_keys_keep_column_order() {
+        let columns = vec![
+            json!({"schema": "s", "table": "line", "name": "a", "dataType": "int", "nullable": false, "primaryKey": false}),
+        ];
+        let foreign_keys = vec![
+            json!({"schema": "s", "table": "line", "name": "fk", "column": "a", "referencedSchema": "s", "referencedTable": "head", "referencedColumn": "x"}),
+            json!({"schema": "s", "table": "line", "name": "fk", "column": "b", "referencedSchema": "s", "referencedTable": "head", "referencedColumn": "y"}),
+            json!({"schema": "s", "table": "gone", "name": "fk2", "column": "c", "referencedSchema": "s", "referencedTable": "head", "referencedColumn": "z"}),
+        ];
+
+        let schema = DatabaseSchema::from_catalog("", &columns, &foreign_keys, &[]);
+
+        assert_eq!(schema.tables.len(), 1);
+        let key = &schema.tables[0].foreign_keys[0];
+        assert_eq!(key.columns, vec!["a", "b"]);
+        assert_eq!(key.referenced_columns, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn test_mermaid_export() {
+        assert_eq!(
+            catalog().render(SchemaFormat::Mermaid),
+            "erDiagram
+    users {
+        integer id PK
+        character_varying email
+    }
+    orders {
+        integer id PK
+        integer user_id FK
+    }
+    orders }o--o| users : \"orders_user_id_fkey\"
+"
+        );
+    }
+
+    #[test]
+    fn test_dbml_export() {
+        assert_eq!(
+            catalog().render(SchemaFormat::Dbml),
+            "Table users {
+  id integer [pk, not null]
+  email \"character varying\" [not null]
+
+  indexes {
+    email [unique, name: 'users_email_key']
+  }
+}
+
+Table orders {
+  id integer [pk, not null]
+  user_id integer
+}
+
+Ref orders_user_id_fkey: orders.user_id > users.id
+"
+        );
+    }
+
+    #[test]
+    fn test_exports_qualify_tables_of_several_schemas() {
+        let columns = vec![
+            json!({"schema": "public", "table": "users", "name": "id", "dataType": "int", "nullable": false, "primaryKey": true}),
+            json!({"schema": "audit", "table": "log", "name": "id", "dataType": "int", "nullable": false, "primaryKey": true}),
+        ];
+        let schema = DatabaseSchema::from_catalog("app", &columns, &[], &[]);
+
+        assert!(schema.to_mermaid().contains("    public_users {"));
+        assert!(schema.to_dbml().contains("Table audit.log {"));
+    }
+}
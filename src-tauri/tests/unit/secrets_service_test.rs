@@ -0,0 +1,125 @@
+use docker_db_manager_lib::services::{SecretsService, PASSWORD_PLACEHOLDER};
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod secrets_service_tests {
+    use super::*;
+
+    #[test]
+    fn test_password_key() {
+        assert_eq!(
+            SecretsService::password_key("db-id"),
+            "database/db-id/password"
+        );
+    }
+
+    #[test]
+    fn test_resolve_keeps_plaintext_password() {
+        let mut database = DatabaseContainer {
+            id: "db-id".to_string(),
+            stored_password: Some("secret".to_string()),
+            stored_password_ref: Some(SecretsService::password_key("db-id")),
+            ..Default::default()
+        };
+
+        SecretsService::new()
+            .resolve_password(&mut database)
+            .unwrap();
+
+        assert_eq!(database.stored_password, Some("secret".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_without_reference_is_noop() {
+        let mut database = DatabaseContainer {
+            id: "db-id".to_string(),
+            ..Default::default()
+        };
+
+        SecretsService::new()
+            .resolve_password(&mut database)
+            .unwrap();
+
+        assert!(database.stored_password.is_none());
+    }
+
+    fn database_with_args(
+        env_vars: HashMap<String, String>,
+        command: Vec<&str>,
+    ) -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-id".to_string(),
+            stored_docker_args: Some(DockerRunArgs {
+                image: "image".to_string(),
+                env_vars,
+                ports: vec![],
+                volumes: vec![],
+                command: command.into_iter().map(String::from).collect(),
+                shm_size: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scrub_and_inject_password_in_env_vars() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("POSTGRES_PASSWORD".to_string(), "secret".to_string());
+        env_vars.insert("POSTGRES_USER".to_string(), "secret".to_string());
+        let mut database = database_with_args(env_vars, vec![]);
+
+        SecretsService::scrub_password(&mut database, "secret");
+
+        let env_vars = &database.stored_docker_args.as_ref().unwrap().env_vars;
+        assert_eq!(env_vars["POSTGRES_PASSWORD"], PASSWORD_PLACEHOLDER);
+        assert_eq!(env_vars["POSTGRES_USER"], "secret");
+        assert!(!SecretsService::holds_password(&database, "secret"));
+
+        SecretsService::inject_password(&mut database, "secret");
+
+        let env_vars = &database.stored_docker_args.as_ref().unwrap().env_vars;
+        assert_eq!(env_vars["POSTGRES_PASSWORD"], "secret");
+        assert!(SecretsService::holds_password(&database, "secret"));
+    }
+
+    #[test]
+    fn test_scrub_and_inject_redis_requirepass() {
+        let mut database = database_with_args(
+            HashMap::new(),
+            vec![
+                "redis-server",
+                "--requirepass",
+                "secret",
+                "--appendonly",
+                "yes",
+            ],
+        );
+
+        SecretsService::scrub_password(&mut database, "secret");
+        assert_eq!(
+            database.stored_docker_args.as_ref().unwrap().command[2],
+            PASSWORD_PLACEHOLDER
+        );
+
+        SecretsService::inject_password(&mut database, "secret");
+        assert_eq!(
+            database.stored_docker_args.as_ref().unwrap().command,
+            vec![
+                "redis-server",
+                "--requirepass",
+                "secret",
+                "--appendonly",
+                "yes"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_secrets_error_code() {
+        let error = AppError::Secrets("locked".to_string());
+
+        assert_eq!(error.code(), "SECRETS_ERROR");
+        assert!(error.hint().is_some());
+    }
+}
@@ -0,0 +1,100 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod share_tests {
+    use super::*;
+
+    fn settings(tool: ShareTool) -> ShareSettings {
+        ShareSettings {
+            tool,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bore_forwards_the_local_port_to_its_server() {
+        let settings = settings(ShareTool::Bore);
+
+        assert_eq!(settings.program(), "bore");
+        assert_eq!(
+            settings.args(5432),
+            vec!["local", "5432", "--to", "bore.pub"]
+        );
+    }
+
+    #[test]
+    fn test_configured_path_replaces_the_program() {
+        let settings = ShareSettings {
+            binary_path: Some("/opt/bin/cloudflared".to_string()),
+            ..settings(ShareTool::Cloudflared)
+        };
+
+        assert_eq!(settings.program(), "/opt/bin/cloudflared");
+        assert!(settings
+            .args(3306)
+            .contains(&"tcp://127.0.0.1:3306".to_string()));
+    }
+
+    #[test]
+    fn test_public_address_of_each_tool() {
+        assert_eq!(
+            settings(ShareTool::Bore).public_address(
+                "2024-05-01T10:00:00Z INFO bore_cli::client: listening at bore.pub:41234"
+            ),
+            Some("bore.pub:41234".to_string())
+        );
+        assert_eq!(
+            settings(ShareTool::Ngrok).public_address(
+                r#"t=2024-05-01 lvl=info msg="started tunnel" addr=//localhost:5432 url=tcp://0.tcp.ngrok.io:12345"#
+            ),
+            Some("tcp://0.tcp.ngrok.io:12345".to_string())
+        );
+        assert_eq!(
+            settings(ShareTool::Cloudflared)
+                .public_address("INF |  https://quiet-lake-fox.trycloudflare.com  |"),
+            Some("https://quiet-lake-fox.trycloudflare.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_other_lines_have_no_address() {
+        assert_eq!(
+            settings(ShareTool::Bore).public_address("connected to server"),
+            None
+        );
+        assert_eq!(
+            settings(ShareTool::Ngrok)
+                .public_address("lvl=info msg=\"client session established\""),
+            None
+        );
+        assert_eq!(
+            settings(ShareTool::Cloudflared).public_address("INF Requesting new quick Tunnel"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_settings_violations() {
+        assert!(ShareSettings::default().violations().is_empty());
+
+        let bad_server = ShareSettings {
+            bore_server: "--to evil".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(bad_server.violations().len(), 1);
+
+        let bad_path = ShareSettings {
+            binary_path: Some(" ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(bad_path.violations().len(), 1);
+    }
+
+    #[test]
+    fn test_share_duration_is_bounded() {
+        assert_eq!(share_duration_minutes(None).unwrap(), DEFAULT_SHARE_MINUTES);
+        assert_eq!(share_duration_minutes(Some(15)).unwrap(), 15);
+        assert!(share_duration_minutes(Some(0)).is_err());
+        assert!(share_duration_minutes(Some(MAX_SHARE_MINUTES + 1)).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use docker_db_manager_lib::services::ShutdownService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod shutdown_service_tests {
+    use super::*;
+
+    fn database(name: &str, status: &str, keep_running_on_exit: bool) -> DatabaseContainer {
+        DatabaseContainer {
+            id: format!("{}-id", name),
+            name: name.to_string(),
+            status: status.to_string(),
+            container_id: Some(format!("{}-container", name)),
+            keep_running_on_exit,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_running_containers_are_stopped() {
+        let databases = vec![
+            database("orders", "running", false),
+            database("reports", "stopped", false),
+        ];
+
+        let names: Vec<String> = ShutdownService::containers_to_stop(&databases)
+            .into_iter()
+            .map(|db| db.name)
+            .collect();
+
+        assert_eq!(names, vec!["orders"]);
+    }
+
+    #[test]
+    fn test_opted_out_containers_keep_running() {
+        let databases = vec![database("cache", "running", true)];
+
+        assert!(ShutdownService::containers_to_stop(&databases).is_empty());
+    }
+
+    #[test]
+    fn test_stop_on_exit_is_off_by_default() {
+        assert!(!AppSettings::default().stop_on_exit);
+    }
+}
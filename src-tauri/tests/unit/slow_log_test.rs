@@ -0,0 +1,98 @@
+use docker_db_manager_lib::services::SlowLogService;
+use docker_db_manager_lib::types::*;
+use std::path::Path;
+
+#[cfg(test)]
+mod slow_log_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_postgres_log_joins_continuation_lines() {
+        let logs = "2024-05-01T10:00:00.000000000Z 2024-05-01 10:00:00.000 UTC [1] LOG:  database system is ready to accept connections\n\
+                    2024-05-01T10:01:00.500000000Z 2024-05-01 10:01:00.500 UTC [57] LOG:  duration: 1002.345 ms  statement: SELECT pg_sleep(1)\n\
+                    2024-05-01T10:02:00.000000000Z 2024-05-01 10:02:00.000 UTC [58] LOG:  duration: 250.5 ms  execute <unnamed>: SELECT *\n\
+                    2024-05-01T10:02:00.000000000Z \tFROM orders\n\
+                    2024-05-01T10:02:00.000000000Z 2024-05-01 10:02:00.000 UTC [58] DETAIL:  parameters: $1 = '1'\n\
+                    2024-05-01T10:03:00.000000000Z 2024-05-01 10:03:00.000 UTC [59] LOG:  duration: 12.000 ms  parse <unnamed>: SELECT 1\n";
+
+        let entries = SlowLogService::parse_postgres_log(logs);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].duration_ms, 1002.345);
+        assert_eq!(entries[0].query, "SELECT pg_sleep(1)");
+        assert_eq!(entries[0].timestamp, "2024-05-01T10:01:00.500+00:00");
+        assert_eq!(entries[1].query, "SELECT *\nFROM orders");
+    }
+
+    #[test]
+    fn test_parse_redis_slowlog() {
+        let output = r#"[[14,1714557600,15000,["KEYS","*"],"172.17.0.1:51234",""],[13,1714557500,2500,["SET","k","v"],"",""]]"#;
+
+        let entries = SlowLogService::parse_redis_slowlog(output).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].query, "KEYS *");
+        assert_eq!(entries[0].duration_ms, 15.0);
+        assert_eq!(entries[0].client.as_deref(), Some("172.17.0.1:51234"));
+        assert_eq!(entries[1].client, None);
+    }
+
+    #[test]
+    fn test_empty_redis_slowlog_is_an_object() {
+        assert!(SlowLogService::parse_redis_slowlog("{}")
+            .unwrap()
+            .is_empty());
+        assert!(SlowLogService::parse_redis_slowlog("ERR unknown").is_err());
+    }
+
+    #[test]
+    fn test_mysql_sql() {
+        assert!(SlowLogService::mysql_enable_sql(1500).contains("long_query_time = 1.5;"));
+
+        let sql = SlowLogService::mysql_select_sql(Some("2024-05-01T12:00:00+02:00"), 50);
+        assert!(sql.starts_with("SET time_zone = '+00:00'; SELECT JSON_OBJECT("));
+        assert!(sql.contains("WHERE start_time > '2024-05-01 10:00:00.000000'"));
+        assert!(sql.ends_with("ORDER BY start_time DESC LIMIT 50"));
+    }
+
+    #[test]
+    fn test_mysql_rows_deserialize() {
+        let row = r#"{"timestamp": "2024-05-01T10:00:00.000000Z", "durationMs": 2000.5000, "query": "SELECT SLEEP(2)", "database": null, "client": "root[root] @ localhost []", "rowsExamined": 0}"#;
+
+        let entry: SlowQuery = serde_json::from_str(row).unwrap();
+
+        assert_eq!(entry.duration_ms, 2000.5);
+        assert_eq!(entry.database, None);
+        assert_eq!(entry.rows_examined, Some(0));
+    }
+
+    #[test]
+    fn test_redis_args_pass_the_password_in_an_env_file() {
+        let database = DatabaseContainer {
+            name: "cache".to_string(),
+            stored_password: Some("secret".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            SlowLogService::redis_args(
+                &database,
+                Some(Path::new("/tmp/db.env")),
+                &["SLOWLOG", "RESET"]
+            ),
+            [
+                "exec",
+                "--env-file",
+                "/tmp/db.env",
+                "cache",
+                "redis-cli",
+                "SLOWLOG",
+                "RESET"
+            ]
+        );
+        assert_eq!(
+            SlowLogService::redis_credentials(&database),
+            vec![("REDISCLI_AUTH", "secret".to_string())]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use docker_db_manager_lib::services::SnapshotService;
+use docker_db_manager_lib::types::AppError;
+use std::cell::RefCell;
+
+#[cfg(test)]
+mod snapshot_service_tests {
+    use super::*;
+
+    #[test]
+    fn test_data_volume_name() {
+        assert_eq!(SnapshotService::data_volume_name("my-db"), "my-db-data");
+    }
+
+    #[test]
+    fn test_snapshot_volume_name_uses_short_id() {
+        assert_eq!(
+            SnapshotService::snapshot_volume_name("my-db", "0b8f7c2e-5d1a-4c3e-9f2b-6a7d8e9f0a1b"),
+            "my-db-snap-0b8f7c2e"
+        );
+    }
+
+    fn step<'a>(
+        steps: &'a RefCell<Vec<&'static str>>,
+        name: &'static str,
+        result: Result<(), AppError>,
+    ) -> impl std::future::Future<Output = Result<(), AppError>> + 'a {
+        async move {
+            steps.borrow_mut().push(name);
+            result
+        }
+    }
+
+    #[tokio::test]
+    async fn test_running_container_is_restarted_after_the_operation() {
+        let steps = RefCell::new(Vec::new());
+
+        let result = SnapshotService::run_stopped(
+            true,
+            step(&steps, "stop", Ok(())),
+            step(&steps, "copy", Ok(())),
+            step(&steps, "start", Ok(())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(*steps.borrow(), vec!["stop", "copy", "start"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_operation_still_restarts_and_keeps_its_error() {
+        let steps = RefCell::new(Vec::new());
+
+        let result = SnapshotService::run_stopped(
+            true,
+            step(&steps, "stop", Ok(())),
+            step(
+                &steps,
+                "copy",
+                Err(AppError::Internal("copy failed".to_string())),
+            ),
+            step(
+                &steps,
+                "start",
+                Err(AppError::Internal("start failed".to_string())),
+            ),
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Internal(message)) if message == "copy failed"));
+        assert_eq!(*steps.borrow(), vec!["stop", "copy", "start"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_restart_is_reported() {
+        let steps = RefCell::new(Vec::new());
+
+        let result = SnapshotService::run_stopped(
+            true,
+            step(&steps, "stop", Ok(())),
+            step(&steps, "copy", Ok(())),
+            step(
+                &steps,
+                "start",
+                Err(AppError::Internal("start failed".to_string())),
+            ),
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Internal(message)) if message == "start failed"));
+    }
+
+    #[tokio::test]
+    async fn test_stopped_container_is_left_stopped() {
+        let steps = RefCell::new(Vec::new());
+
+        let result = SnapshotService::run_stopped(
+            false,
+            step(&steps, "stop", Ok(())),
+            step(&steps, "copy", Ok(())),
+            step(&steps, "start", Ok(())),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(*steps.borrow(), vec!["copy"]);
+    }
+}
@@ -0,0 +1,119 @@
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod snippet_tests {
+    use super::*;
+
+    fn postgres() -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-1".to_string(),
+            name: "orders-db".to_string(),
+            db_type: "PostgreSQL".to_string(),
+            stored_username: Some("app".to_string()),
+            stored_database_name: Some("orders".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn snippet(name: &str, body: &str) -> Snippet {
+        Snippet {
+            id: String::new(),
+            name: name.to_string(),
+            kind: SnippetKind::Sql,
+            body: body.to_string(),
+            database_id: None,
+            db_type: Some("PostgreSQL".to_string()),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_variables_in_order_of_first_use() {
+        let query = snippet(
+            "Rows",
+            "SELECT {{ column }} FROM {{table}} WHERE {{column}} > 0 -- {{not a var}}",
+        );
+        assert_eq!(query.variables(), vec!["column", "table"]);
+    }
+
+    #[test]
+    fn test_render_fills_values_and_builtins() {
+        let query = snippet("Size", "SELECT pg_size_pretty(pg_total_relation_size('{{table}}')) -- {{database}} as {{username}}");
+        let values = HashMap::from([("table".to_string(), "users".to_string())]);
+
+        assert_eq!(
+            query.render(&postgres(), &values).unwrap(),
+            "SELECT pg_size_pretty(pg_total_relation_size('users')) -- orders as app"
+        );
+    }
+
+    #[test]
+    fn test_render_reports_missing_values() {
+        let query = snippet("Rows", "SELECT * FROM {{table}} LIMIT {{limit}}");
+        let error = query.render(&postgres(), &HashMap::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Fill in table, limit to use 'Rows'");
+    }
+
+    #[test]
+    fn test_scope_is_a_container_or_a_type() {
+        let mut both = snippet("Rows", "SELECT 1");
+        both.database_id = Some("db-1".to_string());
+        assert_eq!(both.violations().len(), 1);
+
+        let mut mysql_only = snippet("Rows", "SELECT 1");
+        mysql_only.db_type = Some("MySQL".to_string());
+        assert!(!mysql_only.applies_to(&postgres()));
+        assert!(snippet("Rows", "SELECT 1").applies_to(&postgres()));
+    }
+
+    #[test]
+    fn test_library_save_and_list() {
+        let mut library = SnippetLibrary::default();
+        let shared = library
+            .save(snippet("Locks", "SELECT * FROM pg_locks"), "t1".to_string())
+            .unwrap();
+        assert!(!shared.id.is_empty());
+        assert_eq!(shared.created_at, "t1");
+
+        let mut own = snippet("activity", "SELECT * FROM pg_stat_activity");
+        own.db_type = None;
+        own.database_id = Some("db-1".to_string());
+        library.save(own, "t2".to_string()).unwrap();
+
+        let names: Vec<String> = library
+            .for_database(&postgres())
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["activity", "Locks"]);
+
+        let duplicate = library.save(snippet("locks", "SELECT 1"), "t3".to_string());
+        assert!(duplicate.is_err());
+
+        let mut renamed = shared.clone();
+        renamed.body = "SELECT count(*) FROM pg_locks".to_string();
+        let updated = library.save(renamed, "t4".to_string()).unwrap();
+        assert_eq!(updated.created_at, "t1");
+        assert_eq!(updated.updated_at, "t4");
+        assert_eq!(library.snippets.len(), 2);
+    }
+
+    #[test]
+    fn test_library_remove_and_retain() {
+        let mut library = SnippetLibrary::default();
+        let mut own = snippet("Own", "SELECT 1");
+        own.db_type = None;
+        own.database_id = Some("gone".to_string());
+        let own = library.save(own, "t".to_string()).unwrap();
+        let shared = library
+            .save(snippet("Shared", "SELECT 2"), "t".to_string())
+            .unwrap();
+
+        library.retain_databases(&HashMap::new());
+        assert!(library.get(&own.id).is_none());
+        assert!(library.remove(&shared.id));
+        assert!(!library.remove(&shared.id));
+    }
+}
@@ -0,0 +1,108 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod sql_guard_tests {
+    use super::*;
+
+    fn read_only(sql: &str) -> bool {
+        is_read_only_statement(sql, SqlDialect::Postgres)
+    }
+
+    #[test]
+    fn test_reads_are_allowed() {
+        assert!(read_only("SELECT * FROM users"));
+        assert!(read_only(
+            "  with recent AS (SELECT 1) SELECT * FROM recent"
+        ));
+        assert!(read_only("EXPLAIN ANALYZE SELECT 1"));
+        assert!(read_only("(SELECT 1) UNION (SELECT 2)"));
+        assert!(read_only("SELECT replace(name, 'a', 'b') FROM users"));
+        assert!(is_read_only_statement("SHOW TABLES", SqlDialect::MySql));
+    }
+
+    #[test]
+    fn test_writes_are_refused() {
+        assert!(!read_only("UPDATE users SET admin = true"));
+        assert!(!read_only("delete from users"));
+        assert!(!read_only(
+            "WITH gone AS (DELETE FROM users RETURNING *) SELECT * FROM gone"
+        ));
+        assert!(!read_only("SELECT * INTO backup FROM users"));
+        assert!(!read_only("EXPLAIN ANALYZE DELETE FROM users"));
+        assert!(!read_only("SET default_transaction_read_only = off"));
+        assert!(!read_only("START TRANSACTION READ WRITE"));
+    }
+
+    #[test]
+    fn test_keywords_in_strings_and_comments_are_ignored() {
+        assert!(read_only("SELECT 'DELETE FROM users' AS text"));
+        assert!(read_only(
+            "SELECT \"update\" FROM audit -- DROP TABLE audit"
+        ));
+        assert!(read_only("/* TRUNCATE */ SELECT $body$ INSERT $body$"));
+        assert!(is_read_only_statement(
+            "SELECT 'it\\'s; DROP TABLE x' # DELETE",
+            SqlDialect::MySql
+        ));
+    }
+
+    #[test]
+    fn test_split_statements() {
+        let statements = split_statements(
+            "SELECT 1; SELECT ';' ;\n-- only a comment\n; SELECT 2",
+            SqlDialect::Postgres,
+        );
+        assert_eq!(statements, vec!["SELECT 1", "SELECT ';'", "SELECT 2"]);
+    }
+
+    #[test]
+    fn test_first_write_statement_is_reported() {
+        assert_eq!(
+            first_write_statement(
+                "SELECT 1; UPDATE  users\n SET admin = true",
+                SqlDialect::MySql
+            ),
+            Some("UPDATE users SET admin = true".to_string())
+        );
+        assert_eq!(first_write_statement("SELECT 1", SqlDialect::MySql), None);
+
+        let long = format!("DELETE FROM t WHERE id IN ({})", "1, ".repeat(50));
+        let quoted = first_write_statement(&long, SqlDialect::Postgres).unwrap();
+        assert!(quoted.ends_with("..."));
+        assert_eq!(quoted.chars().count(), 83);
+    }
+
+    #[test]
+    fn test_guard_blocks_writes_until_unlocked() {
+        let guard = SqlGuardStatus {
+            enforced: true,
+            unlocked: false,
+        };
+        assert!(guard.blocks_writes());
+        assert!(!SqlGuardStatus {
+            unlocked: true,
+            ..guard
+        }
+        .blocks_writes());
+        assert!(!SqlGuardStatus {
+            enforced: false,
+            unlocked: false,
+        }
+        .blocks_writes());
+    }
+
+    #[test]
+    fn test_console_output_is_parsed() {
+        let (columns, rows) = SqlConsoleResult::parse_output("id\tname\n1\tAda\n2\t\n");
+        assert_eq!(columns, vec!["id", "name"]);
+        assert_eq!(rows, vec![vec!["1", "Ada"], vec!["2", ""]]);
+        assert_eq!(SqlConsoleResult::parse_output(""), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_guard_is_on_by_default() {
+        assert!(AppSettings::default().sql_read_only_guard);
+        let settings: AppSettings = serde_json::from_str("{}").unwrap();
+        assert!(settings.sql_read_only_guard);
+    }
+}
@@ -0,0 +1,119 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod sql_job_tests {
+    use super::*;
+
+    fn job(name: &str) -> SqlJob {
+        SqlJob {
+            id: String::new(),
+            name: name.to_string(),
+            database_id: "db-1".to_string(),
+            sql: "REFRESH MATERIALIZED VIEW daily_totals".to_string(),
+            cron: "0 3 * * *".to_string(),
+            enabled: true,
+            notify_on_failure: true,
+            created_at: String::new(),
+            last_run_at: None,
+            last_error: None,
+            runs: Vec::new(),
+        }
+    }
+
+    fn run(error: Option<&str>) -> SqlJobRun {
+        SqlJobRun {
+            started_at: "2026-10-14T03:00:00+00:00".to_string(),
+            finished_at: "2026-10-14T03:00:02+00:00".to_string(),
+            success: error.is_none(),
+            statements: 1,
+            error: error.map(str::to_owned),
+            manual: false,
+        }
+    }
+
+    #[test]
+    fn test_incomplete_jobs_are_rejected() {
+        assert!(job("Refresh totals").violations().is_empty());
+
+        let mut empty = job(" ");
+        empty.sql = "  ".to_string();
+        empty.cron = String::new();
+        assert_eq!(empty.violations().len(), 3);
+    }
+
+    #[test]
+    fn test_new_job_gets_id_and_clean_state() {
+        let mut jobs = Vec::new();
+        let mut new_job = job("  Refresh totals ");
+        new_job.runs = vec![run(None)];
+        new_job.last_error = Some("stale".to_string());
+
+        let saved = upsert_sql_job(&mut jobs, new_job, "now".to_string()).unwrap();
+        assert!(!saved.id.is_empty());
+        assert_eq!(saved.name, "Refresh totals");
+        assert_eq!(saved.created_at, "now");
+        assert!(saved.runs.is_empty());
+        assert!(saved.last_error.is_none());
+        assert_eq!(jobs.len(), 1);
+    }
+
+    #[test]
+    fn test_names_are_unique_per_container() {
+        let mut jobs = Vec::new();
+        upsert_sql_job(&mut jobs, job("Purge"), "now".to_string()).unwrap();
+
+        assert!(matches!(
+            upsert_sql_job(&mut jobs, job("purge"), "now".to_string()),
+            Err(AppError::Validation(_))
+        ));
+
+        let mut other = job("Purge");
+        other.database_id = "db-2".to_string();
+        assert!(upsert_sql_job(&mut jobs, other, "now".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_update_keeps_history_and_restarts_changed_schedule() {
+        let mut jobs = Vec::new();
+        let saved = upsert_sql_job(&mut jobs, job("Purge"), "created".to_string()).unwrap();
+        jobs[0].runs.push(run(Some("boom")));
+        jobs[0].last_error = Some("boom".to_string());
+        jobs[0].last_run_at = Some("03:00".to_string());
+
+        let mut edited = saved.clone();
+        edited.sql = "DELETE FROM events WHERE created_at < now() - interval '30 days'".to_string();
+        let updated = upsert_sql_job(&mut jobs, edited, "later".to_string()).unwrap();
+        assert_eq!(updated.created_at, "created");
+        assert_eq!(updated.runs.len(), 1);
+        assert_eq!(updated.last_error.as_deref(), Some("boom"));
+        assert_eq!(updated.last_run_at.as_deref(), Some("03:00"));
+
+        let mut rescheduled = updated.clone();
+        rescheduled.cron = "0 4 * * 0".to_string();
+        let updated = upsert_sql_job(&mut jobs, rescheduled, "later".to_string()).unwrap();
+        assert_eq!(updated.last_run_at.as_deref(), Some("later"));
+
+        let mut moved = updated;
+        moved.database_id = "db-2".to_string();
+        assert!(upsert_sql_job(&mut jobs, moved, "later".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_record_tracks_last_error_and_caps_history() {
+        let mut job = job("Purge");
+        job.record(run(Some(&"x".repeat(SQL_JOB_ERROR_LIMIT + 10))));
+        assert_eq!(
+            job.last_error.as_ref().map(|error| error.len()),
+            Some(SQL_JOB_ERROR_LIMIT)
+        );
+
+        job.record(run(None));
+        assert!(job.last_error.is_none());
+
+        for _ in 0..MAX_SQL_JOB_RUNS {
+            job.record(run(None));
+        }
+        assert_eq!(job.runs.len(), MAX_SQL_JOB_RUNS);
+        assert!(job.runs.iter().all(|run| run.success));
+    }
+}
@@ -0,0 +1,183 @@
+use docker_db_manager_lib::services::SqlService;
+use docker_db_manager_lib::types::*;
+use serde_json::json;
+use std::path::Path;
+
+#[cfg(test)]
+mod sql_service_tests {
+    use super::*;
+
+    fn column(name: &str) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            data_type: "text".to_string(),
+            nullable: true,
+            primary_key: false,
+        }
+    }
+
+    fn query() -> TableRowsQuery {
+        TableRowsQuery {
+            schema: "public".to_string(),
+            table: "users".to_string(),
+            offset: 50,
+            limit: 25,
+            sort: None,
+            filters: Vec::new(),
+        }
+    }
+
+    fn database(db_type: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            name: "app-db".to_string(),
+            db_type: db_type.to_string(),
+            stored_password: Some("secret".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dialect_only_covers_sql_engines() {
+        assert_eq!(SqlDialect::of("PostgreSQL").unwrap(), SqlDialect::Postgres);
+        assert_eq!(SqlDialect::of("MariaDB").unwrap(), SqlDialect::MySql);
+        assert!(matches!(
+            SqlDialect::of("Redis"),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_quoting_escapes_delimiters() {
+        assert_eq!(
+            SqlDialect::Postgres.quote_identifier("we\"ird"),
+            "\"we\"\"ird\""
+        );
+        assert_eq!(SqlDialect::MySql.quote_identifier("we`ird"), "`we``ird`");
+        assert_eq!(SqlDialect::Postgres.quote_literal("it's"), "'it''s'");
+        assert_eq!(SqlDialect::MySql.quote_literal("a\\'b"), "'a\\\\''b'");
+    }
+
+    #[test]
+    fn test_select_sql_pages_sorts_and_filters() {
+        let mut query = query();
+        query.sort = Some(TableSort {
+            column: "email".to_string(),
+            descending: true,
+        });
+        query.filters = vec![
+            TableFilter {
+                column: "email".to_string(),
+                operator: FilterOperator::Contains,
+                value: "50%".to_string(),
+            },
+            TableFilter {
+                column: "id".to_string(),
+                operator: FilterOperator::IsNotNull,
+                value: String::new(),
+            },
+        ];
+        let columns = vec![column("id"), column("email")];
+
+        assert_eq!(
+            query.select_sql(SqlDialect::Postgres, &columns).unwrap(),
+            "SELECT row_to_json(t) FROM \"public\".\"users\" AS t \
+WHERE CAST(\"email\" AS text) ILIKE '%50\\%%' AND \"id\" IS NOT NULL \
+ORDER BY \"email\" DESC LIMIT 25 OFFSET 50"
+        );
+        assert_eq!(
+            query.count_sql(SqlDialect::MySql, &columns).unwrap(),
+            "SELECT count(*) FROM `public`.`users` \
+WHERE CAST(`email` AS CHAR) LIKE '%50\\\\%%' AND `id` IS NOT NULL"
+        );
+    }
+
+    #[test]
+    fn test_mysql_rows_are_built_from_known_columns() {
+        let sql = query()
+            .select_sql(SqlDialect::MySql, &[column("id"), column("email")])
+            .unwrap();
+
+        assert!(sql.starts_with("SELECT JSON_OBJECT('id', t.`id`, 'email', t.`email`) FROM"));
+    }
+
+    #[test]
+    fn test_unknown_columns_and_page_sizes_are_rejected() {
+        let columns = vec![column("id")];
+
+        let mut unknown_filter = query();
+        unknown_filter.filters.push(TableFilter {
+            column: "id; DROP TABLE users".to_string(),
+            operator: FilterOperator::Equals,
+            value: "1".to_string(),
+        });
+        assert!(unknown_filter
+            .select_sql(SqlDialect::Postgres, &columns)
+            .is_err());
+
+        let mut unknown_sort = query();
+        unknown_sort.sort = Some(TableSort {
+            column: "missing".to_string(),
+            descending: false,
+        });
+        assert!(unknown_sort
+            .select_sql(SqlDialect::MySql, &columns)
+            .is_err());
+
+        let mut too_large = query();
+        too_large.limit = MAX_PAGE_SIZE + 1;
+        assert!(too_large
+            .select_sql(SqlDialect::Postgres, &columns)
+            .is_err());
+    }
+
+    #[test]
+    fn test_client_args_use_engine_client() {
+        let postgres =
+            SqlService::client_args(&database("PostgreSQL"), None, None, "SELECT 1").unwrap();
+        assert_eq!(postgres[0..3], ["exec", "app-db", "psql"]);
+        assert_eq!(postgres.last().unwrap(), "SELECT 1");
+
+        let env_file = Path::new("/tmp/db.env");
+        let mariadb =
+            SqlService::client_args(&database("MariaDB"), Some(env_file), None, "SELECT 1")
+                .unwrap();
+        assert_eq!(
+            mariadb[0..5],
+            ["exec", "--env-file", "/tmp/db.env", "app-db", "mariadb"]
+        );
+        assert!(!mariadb.iter().any(|arg| arg.contains("secret")));
+        assert_eq!(
+            SqlService::exec_credentials(&database("MariaDB")),
+            vec![("MYSQL_PWD", "secret".to_string())]
+        );
+        assert!(SqlService::exec_credentials(&database("PostgreSQL")).is_empty());
+
+        let other =
+            SqlService::client_args(&database("PostgreSQL"), None, Some("analytics"), "SELECT 1")
+                .unwrap();
+        assert!(other.windows(2).any(|pair| pair == ["-d", "analytics"]));
+
+        assert!(SqlService::client_args(&database("MongoDB"), None, None, "SELECT 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_column_accepts_numeric_flags() {
+        let column = SqlService::parse_column(
+            r#"{"name": "id", "dataType": "int", "nullable": 0, "primaryKey": 1}"#,
+        )
+        .unwrap();
+
+        assert!(!column.nullable);
+        assert!(column.primary_key);
+    }
+
+    #[test]
+    fn test_parse_rows_follows_column_order() {
+        let lines = vec![r#"{"email": "a@b.c", "id": 1}"#.to_string()];
+        let rows = SqlService::parse_rows(&lines, &[column("id"), column("email"), column("gone")])
+            .unwrap();
+
+        assert_eq!(rows, vec![vec![json!(1), json!("a@b.c"), json!(null)]]);
+        assert!(SqlService::parse_rows(&["not json".to_string()], &[column("id")]).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod stack_tests {
+    use super::*;
+
+    fn service(service: &str, db_type: &str, port: i32) -> StackService {
+        StackService {
+            service: service.to_string(),
+            request: DockerRunRequest {
+                name: String::new(),
+                docker_args: DockerRunArgs {
+                    image: "postgres:16".to_string(),
+                    env_vars: HashMap::new(),
+                    ports: vec![PortMapping::local(port, 5432)],
+                    volumes: vec![],
+                    command: vec![],
+                    shm_size: None,
+                },
+                metadata: ContainerMetadata {
+                    db_type: db_type.to_string(),
+                    port,
+                    ..Default::default()
+                },
+            },
+        }
+    }
+
+    fn stack(services: Vec<StackService>) -> StackRequest {
+        StackRequest {
+            name: "My Shop".to_string(),
+            services,
+        }
+    }
+
+    #[test]
+    fn test_names_derive_from_the_stack() {
+        let stack = stack(vec![service("postgres", "PostgreSQL", 5432)]);
+
+        assert_eq!(stack.container_name("postgres"), "my-shop-postgres");
+        assert_eq!(stack.network_name(), "my-shop-stack");
+    }
+
+    #[test]
+    fn test_valid_stack_passes() {
+        let stack = stack(vec![
+            service("postgres", "PostgreSQL", 5432),
+            service("cache", "Redis", 6379),
+        ]);
+
+        assert!(stack.validate().is_ok());
+    }
+
+    #[test]
+    fn test_empty_stack_is_rejected() {
+        assert!(stack(vec![]).validate().is_err());
+    }
+
+    #[test]
+    fn test_duplicate_service_is_rejected() {
+        let stack = stack(vec![
+            service("postgres", "PostgreSQL", 5432),
+            service("postgres", "PostgreSQL", 5433),
+        ]);
+
+        assert!(stack.validate().is_err());
+    }
+
+    #[test]
+    fn test_shared_host_port_is_rejected() {
+        let stack = stack(vec![
+            service("postgres", "PostgreSQL", 5432),
+            service("cache", "Redis", 5432),
+        ]);
+
+        assert!(stack.validate().is_err());
+    }
+
+    #[test]
+    fn test_services_on_different_hosts_are_rejected() {
+        let mut remote = service("cache", "Redis", 6379);
+        remote.request.metadata.host = Some("build-server".to_string());
+        let stack = stack(vec![service("postgres", "PostgreSQL", 5432), remote]);
+
+        assert_eq!(stack.host(), None);
+        assert!(stack.validate().is_err());
+    }
+}
@@ -0,0 +1,18 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod startup_mode_tests {
+    use super::*;
+
+    #[test]
+    fn test_regular_launch_shows_window() {
+        let args = ["docker-db-manager"];
+        assert_eq!(StartupMode::from_args(args), StartupMode::Window);
+    }
+
+    #[test]
+    fn test_login_launch_is_headless() {
+        let args = ["docker-db-manager", HEADLESS_ARG];
+        assert_eq!(StartupMode::from_args(args), StartupMode::Headless);
+    }
+}
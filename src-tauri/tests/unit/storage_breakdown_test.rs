@@ -0,0 +1,81 @@
+use docker_db_manager_lib::services::StorageBreakdownService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod storage_breakdown_tests {
+    use super::*;
+
+    fn table(name: &str, size_bytes: u64) -> TableSize {
+        TableSize {
+            name: name.to_string(),
+            size_bytes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_breakdown_sorts_and_totals() {
+        let breakdown = StorageBreakdown::new(
+            vec![
+                DatabaseSize {
+                    name: "shop".to_string(),
+                    size_bytes: 300,
+                    tables: vec![table("a", 100), table("b", 200)],
+                    partial: false,
+                },
+                DatabaseSize {
+                    name: "app".to_string(),
+                    size_bytes: 50,
+                    ..Default::default()
+                },
+            ],
+            None,
+        );
+
+        assert_eq!(breakdown.total_bytes, 350);
+        assert_eq!(breakdown.databases[0].name, "app");
+        assert_eq!(breakdown.databases[1].tables[0].name, "b");
+        assert_eq!(StorageBreakdown::new(Vec::new(), Some(42)).total_bytes, 42);
+    }
+
+    #[test]
+    fn test_mysql_rows_group_by_schema() {
+        let lines = vec![
+            r#"{"database": "empty", "name": null, "sizeBytes": 0, "rowCount": null}"#.to_string(),
+            r#"{"database": "shop", "name": "orders", "sizeBytes": 16384, "rowCount": 10}"#
+                .to_string(),
+            r#"{"database": "shop", "name": "users", "sizeBytes": 32768, "rowCount": 3}"#
+                .to_string(),
+        ];
+
+        let databases = StorageBreakdownService::parse_mysql_rows(&lines).unwrap();
+
+        assert_eq!(databases.len(), 2);
+        assert!(databases[0].tables.is_empty());
+        assert_eq!(databases[1].size_bytes, 49152);
+        assert_eq!(databases[1].tables[1].row_count, Some(3));
+    }
+
+    #[test]
+    fn test_redis_reply_names_prefixes() {
+        let output = r#"{"usedMemory":1048576,"databases":[{"name":"db0","sizeBytes":300,"partial":true,"tables":[{"name":"user","sizeBytes":200,"rowCount":2},{"name":"","sizeBytes":100,"rowCount":1}]},{"name":"db1","sizeBytes":0,"partial":false,"tables":{}}]}"#;
+
+        let (used_memory, databases) = StorageBreakdownService::parse_redis_reply(output).unwrap();
+
+        assert_eq!(used_memory, Some(1048576));
+        assert!(databases[0].partial);
+        assert_eq!(databases[0].tables[0].name, "user:*");
+        assert_eq!(databases[0].tables[1].name, "(no prefix)");
+        assert!(databases[1].tables.is_empty());
+    }
+
+    #[test]
+    fn test_empty_redis_reply() {
+        let (used_memory, databases) =
+            StorageBreakdownService::parse_redis_reply(r#"{"usedMemory":900000,"databases":{}}"#)
+                .unwrap();
+
+        assert_eq!(used_memory, Some(900000));
+        assert!(databases.is_empty());
+    }
+}
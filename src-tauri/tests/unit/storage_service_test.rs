@@ -0,0 +1,207 @@
+use docker_db_manager_lib::services::{StorageService, StoreKey};
+use docker_db_manager_lib::types::{AppError, DEFAULT_WORKSPACE_ID};
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+
+#[cfg(test)]
+mod storage_service_tests {
+    use super::*;
+
+    fn temp_store(test_name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "docker-db-manager-{}-{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("databases.json")
+    }
+
+    fn store_with(key: &str, value: Value) -> Map<String, Value> {
+        let mut store = Map::new();
+        store.insert(key.to_string(), value);
+        store
+    }
+
+    #[test]
+    fn test_missing_store_reads_empty() {
+        let path = temp_store("missing");
+
+        let store = StorageService::read_store_file(&path).unwrap();
+
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_write_then_read_round_trip() {
+        let path = temp_store("round-trip");
+        let store = store_with("databases", json!([{ "id": "1" }]));
+
+        StorageService::write_store_file(&path, &store).unwrap();
+
+        assert_eq!(StorageService::read_store_file(&path).unwrap(), store);
+        assert!(!path.with_file_name("databases.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_write_keeps_previous_store_as_backup() {
+        let path = temp_store("backup");
+        let first = store_with("databases", json!([{ "id": "1" }]));
+        let second = store_with("databases", json!([{ "id": "2" }]));
+
+        StorageService::write_store_file(&path, &first).unwrap();
+        StorageService::write_store_file(&path, &second).unwrap();
+
+        let backup = std::fs::read(StorageService::backup_path(&path)).unwrap();
+        let backup: Map<String, Value> = serde_json::from_slice(&backup).unwrap();
+        assert_eq!(backup, first);
+    }
+
+    #[test]
+    fn test_truncated_store_recovers_from_backup() {
+        let path = temp_store("truncated");
+        let first = store_with("databases", json!([{ "id": "1" }]));
+        let second = store_with("databases", json!([{ "id": "2" }]));
+
+        StorageService::write_store_file(&path, &first).unwrap();
+        StorageService::write_store_file(&path, &second).unwrap();
+        std::fs::write(&path, b"{\"databases\": [").unwrap();
+
+        let store = StorageService::read_store_file(&path).unwrap();
+
+        assert_eq!(store, first);
+        assert!(StorageService::corrupt_path(&path).exists());
+
+        // The backup was put back, a second read does not depend on it
+        std::fs::remove_file(StorageService::backup_path(&path)).unwrap();
+        let store = StorageService::read_store_file(&path).unwrap();
+        assert_eq!(store, first);
+    }
+
+    #[test]
+    fn test_corrupted_store_without_backup_reads_empty() {
+        let path = temp_store("no-backup");
+        std::fs::write(&path, b"").unwrap();
+
+        let store = StorageService::read_store_file(&path).unwrap();
+
+        assert!(store.is_empty());
+        assert!(StorageService::corrupt_path(&path).exists());
+    }
+
+    #[test]
+    fn test_corrupted_store_does_not_replace_backup() {
+        let path = temp_store("keep-backup");
+        let first = store_with("databases", json!([{ "id": "1" }]));
+        let second = store_with("databases", json!([{ "id": "2" }]));
+
+        StorageService::write_store_file(&path, &first).unwrap();
+        StorageService::write_store_file(&path, &first).unwrap();
+        std::fs::write(&path, b"not json").unwrap();
+        StorageService::write_store_file(&path, &second).unwrap();
+
+        let backup = std::fs::read(StorageService::backup_path(&path)).unwrap();
+        let backup: Map<String, Value> = serde_json::from_slice(&backup).unwrap();
+        assert_eq!(backup, first);
+        assert_eq!(StorageService::read_store_file(&path).unwrap(), second);
+    }
+
+    #[test]
+    fn test_encrypted_store_round_trip() {
+        let store = store_with("databases", json!([{ "id": "1" }]));
+        let key = StoreKey::from_passphrase("correct horse", b"0123456789abcdef").unwrap();
+
+        let encrypted = StorageService::encrypt_store(&store, &key).unwrap();
+
+        assert!(encrypted.contains_key("encrypted"));
+        assert!(!encrypted.contains_key("databases"));
+        assert_eq!(
+            StorageService::decrypt_store(encrypted, Some(&key)).unwrap(),
+            store
+        );
+    }
+
+    #[test]
+    fn test_encrypted_store_rejects_wrong_passphrase() {
+        let store = store_with("databases", json!([]));
+        let salt = b"0123456789abcdef";
+        let key = StoreKey::from_passphrase("correct horse", salt).unwrap();
+        let wrong_key = StoreKey::from_passphrase("battery staple", salt).unwrap();
+
+        let encrypted = StorageService::encrypt_store(&store, &key).unwrap();
+
+        assert!(matches!(
+            StorageService::decrypt_store(encrypted, Some(&wrong_key)),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_encrypted_store_without_key_is_locked() {
+        let store = store_with("databases", json!([]));
+        let encrypted = StorageService::encrypt_store(&store, &StoreKey::generate()).unwrap();
+
+        assert!(matches!(
+            StorageService::decrypt_store(encrypted, None),
+            Err(AppError::StoreLocked)
+        ));
+    }
+
+    #[test]
+    fn test_plaintext_store_is_not_decrypted() {
+        let store = store_with("databases", json!([{ "id": "1" }]));
+
+        assert_eq!(
+            StorageService::decrypt_store(store.clone(), None).unwrap(),
+            store
+        );
+    }
+
+    #[test]
+    fn test_workspace_store_files() {
+        assert_eq!(
+            StorageService::workspace_databases_file(DEFAULT_WORKSPACE_ID),
+            PathBuf::from("databases.json")
+        );
+        assert_eq!(
+            StorageService::workspace_databases_file("client-x"),
+            PathBuf::from("workspaces")
+                .join("client-x")
+                .join("databases.json")
+        );
+    }
+
+    #[test]
+    fn test_merge_database_entries_replaces_in_place() {
+        let stored = json!([{ "id": "1", "port": 5432 }, { "id": "2", "port": 3306 }]);
+
+        let merged = StorageService::merge_database_entries(
+            Some(stored),
+            vec![json!({ "id": "1", "port": 5433 }), json!({ "id": "3" })],
+            &[],
+        );
+
+        assert_eq!(
+            merged,
+            json!([{ "id": "1", "port": 5433 }, { "id": "2", "port": 3306 }, { "id": "3" }])
+        );
+    }
+
+    #[test]
+    fn test_merge_database_entries_drops_removed() {
+        let stored = json!([{ "id": "1" }, { "id": "2" }]);
+
+        let merged =
+            StorageService::merge_database_entries(Some(stored), Vec::new(), &["1".to_string()]);
+
+        assert_eq!(merged, json!([{ "id": "2" }]));
+    }
+
+    #[test]
+    fn test_merge_database_entries_without_store() {
+        let merged = StorageService::merge_database_entries(None, vec![json!({ "id": "1" })], &[]);
+
+        assert_eq!(merged, json!([{ "id": "1" }]));
+    }
+}
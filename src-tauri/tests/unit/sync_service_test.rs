@@ -0,0 +1,76 @@
+use docker_db_manager_lib::services::SyncService;
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg(test)]
+mod sync_service_tests {
+    use super::*;
+
+    fn database(id: &str, status: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: status.to_string(),
+            container_id: Some(format!("{}-container", id)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_interval_defaults_and_floor() {
+        let settings: AppSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(SyncService::interval(&settings), Duration::from_secs(5));
+
+        let zero = AppSettings {
+            sync_interval_secs: 0,
+            ..AppSettings::default()
+        };
+        assert_eq!(SyncService::interval(&zero), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_merge_records_outside_start_and_stop() {
+        let mut stopped = database("a", "stopped");
+        SyncService::merge_synced(&mut stopped, &database("a", "running"));
+        assert_eq!(stopped.status, "running");
+        assert!(stopped.last_started_at.is_some());
+
+        let mut running = database("a", "running");
+        let mut exited = database("a", "exited");
+        exited.exit_code = Some(137);
+        SyncService::merge_synced(&mut running, &exited);
+        assert_eq!(running.status, "exited");
+        assert_eq!(running.exit_code, Some(137));
+        assert!(running.last_stopped_at.is_some());
+    }
+
+    #[test]
+    fn test_merge_keeps_starting_until_ready() {
+        let mut starting = database("a", "starting");
+
+        SyncService::merge_synced(&mut starting, &database("a", "running"));
+
+        assert_eq!(starting.status, "starting");
+    }
+
+    #[test]
+    fn test_changed_databases() {
+        let previous: HashMap<String, DatabaseContainer> = [
+            ("a".to_string(), database("a", "running")),
+            ("b".to_string(), database("b", "running")),
+        ]
+        .into();
+        let mut current = previous.clone();
+        current.get_mut("b").unwrap().container_id = Some("recreated".to_string());
+        current.insert("c".to_string(), database("c", "stopped"));
+
+        let mut changed: Vec<String> = SyncService::changed_databases(&previous, &current)
+            .into_iter()
+            .map(|database| database.id)
+            .collect();
+        changed.sort();
+
+        assert_eq!(changed, vec!["b".to_string(), "c".to_string()]);
+    }
+}
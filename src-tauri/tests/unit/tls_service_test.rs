@@ -0,0 +1,70 @@
+use docker_db_manager_lib::services::{TlsService, CERTS_MOUNT_PATH};
+use docker_db_manager_lib::types::docker::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tls_service_tests {
+    use super::*;
+
+    fn create_test_docker_args(command: Vec<String>) -> DockerRunArgs {
+        DockerRunArgs {
+            image: "redis:7".to_string(),
+            env_vars: HashMap::new(),
+            ports: vec![PortMapping::local(6379, 6379)],
+            volumes: vec![],
+            command,
+            shm_size: None,
+        }
+    }
+
+    #[test]
+    fn test_certs_volume_name() {
+        assert_eq!(TlsService::certs_volume_name("my-db"), "my-db-certs");
+    }
+
+    #[test]
+    fn test_postgres_tls_args() {
+        let args = TlsService::build_tls_command_args("PostgreSQL").unwrap();
+        let command = args.join(" ");
+
+        assert!(command.contains("ssl=on"));
+        assert!(command.contains("ssl_cert_file=/certs/server.crt"));
+        assert!(command.contains("ssl_key_file=/certs/server.key"));
+    }
+
+    #[test]
+    fn test_mysql_and_mariadb_tls_args() {
+        for db_type in ["MySQL", "MariaDB"] {
+            let args = TlsService::build_tls_command_args(db_type).unwrap();
+            assert!(args.contains(&"--ssl-ca=/certs/ca.crt".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_mongodb_tls_args_use_combined_pem() {
+        let args = TlsService::build_tls_command_args("MongoDB").unwrap();
+        assert!(args.contains(&"/certs/server.pem".to_string()));
+    }
+
+    #[test]
+    fn test_unsupported_engine_returns_error() {
+        assert!(TlsService::build_tls_command_args("Elasticsearch").is_err());
+    }
+
+    #[test]
+    fn test_apply_tls_appends_volume_and_command() {
+        let service = TlsService::new();
+        let mut args = create_test_docker_args(vec!["redis-server".to_string()]);
+
+        service
+            .apply_tls_to_docker_args("test-redis", "Redis", &mut args)
+            .unwrap();
+
+        assert_eq!(args.volumes.len(), 1);
+        assert_eq!(args.volumes[0].name, "test-redis-certs");
+        assert_eq!(args.volumes[0].path, CERTS_MOUNT_PATH);
+        // Existing command is kept and TLS flags are appended after it
+        assert_eq!(args.command[0], "redis-server");
+        assert!(args.command.contains(&"--tls-port".to_string()));
+    }
+}
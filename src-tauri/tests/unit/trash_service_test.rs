@@ -0,0 +1,51 @@
+use chrono::{Duration, Utc};
+use docker_db_manager_lib::services::TrashService;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod trash_service_tests {
+    use super::*;
+
+    fn trashed_at(deleted_at: String) -> TrashedDatabase {
+        TrashedDatabase {
+            database: DatabaseContainer {
+                id: "db-id".to_string(),
+                name: "my-db".to_string(),
+                ..Default::default()
+            },
+            deleted_at,
+            final_snapshot_id: None,
+        }
+    }
+
+    #[test]
+    fn test_entry_within_retention_is_kept() {
+        let now = Utc::now();
+        let entry = trashed_at((now - Duration::days(2)).to_rfc3339());
+
+        assert!(!TrashService::is_expired(&entry, 7, now));
+    }
+
+    #[test]
+    fn test_entry_past_retention_expires() {
+        let now = Utc::now();
+        let entry = trashed_at((now - Duration::days(8)).to_rfc3339());
+
+        assert!(TrashService::is_expired(&entry, 7, now));
+    }
+
+    #[test]
+    fn test_zero_retention_expires_immediately() {
+        let now = Utc::now();
+        let entry = trashed_at(now.to_rfc3339());
+
+        assert!(TrashService::is_expired(&entry, 0, now));
+    }
+
+    #[test]
+    fn test_unreadable_date_expires() {
+        let entry = trashed_at("yesterday".to_string());
+
+        assert!(TrashService::is_expired(&entry, 7, Utc::now()));
+    }
+}
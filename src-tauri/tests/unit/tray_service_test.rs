@@ -0,0 +1,55 @@
+use docker_db_manager_lib::services::TrayService;
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tray_service_tests {
+    use super::*;
+
+    fn database(name: &str, status: &str) -> DatabaseContainer {
+        DatabaseContainer {
+            id: format!("{}-id", name),
+            name: name.to_string(),
+            status: status.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_menu_ids_round_trip() {
+        let actions = [
+            TrayAction::ShowWindow,
+            TrayAction::Start("db-1".to_string()),
+            TrayAction::Stop("db-1".to_string()),
+            TrayAction::StopAll,
+            TrayAction::Quit,
+        ];
+
+        for action in actions {
+            assert_eq!(TrayAction::from_menu_id(&action.menu_id()), Some(action));
+        }
+    }
+
+    #[test]
+    fn test_unknown_menu_id_is_ignored() {
+        assert_eq!(TrayAction::from_menu_id("restart:db-1"), None);
+    }
+
+    #[test]
+    fn test_entries_are_sorted_with_status() {
+        let databases: HashMap<String, DatabaseContainer> = [
+            database("users", "stopped"),
+            database("accounts", "running"),
+        ]
+        .into_iter()
+        .map(|db| (db.id.clone(), db))
+        .collect();
+
+        let entries = TrayService::entries(&databases);
+
+        assert_eq!(entries[0].name, "accounts");
+        assert!(entries[0].running);
+        assert_eq!(entries[1].name, "users");
+        assert!(!entries[1].running);
+    }
+}
@@ -0,0 +1,145 @@
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tuning_tests {
+    use super::*;
+
+    fn docker_args(command: &[&str]) -> DockerRunArgs {
+        DockerRunArgs {
+            image: "postgres:16".to_string(),
+            env_vars: HashMap::new(),
+            ports: vec![],
+            volumes: vec![],
+            command: command.iter().map(|arg| arg.to_string()).collect(),
+            shm_size: None,
+        }
+    }
+
+    fn tuning(preset: TuningPreset, memory_mb: u64, cpus: u32) -> TuningSettings {
+        TuningSettings {
+            preset,
+            host: HostResources { memory_mb, cpus },
+        }
+    }
+
+    fn value(settings: &[TunedSetting], name: &str) -> String {
+        settings
+            .iter()
+            .find(|setting| setting.name == name)
+            .map(|setting| setting.value.clone())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_parse_host_resources() {
+        let host = HostResources::parse("8589934592 4\n").unwrap();
+        assert_eq!(
+            host,
+            HostResources {
+                memory_mb: 8192,
+                cpus: 4
+            }
+        );
+
+        assert!(HostResources::parse("").is_err());
+        assert!(HostResources::parse("<no value> 4").is_err());
+        assert!(HostResources::parse("8589934592 0").is_err());
+    }
+
+    #[test]
+    fn test_budget_follows_preset_with_minimum() {
+        assert_eq!(tuning(TuningPreset::Small, 8192, 4).budget_mb(), 819);
+        assert_eq!(tuning(TuningPreset::Medium, 8192, 4).budget_mb(), 2048);
+        assert_eq!(tuning(TuningPreset::Large, 8192, 4).budget_mb(), 4096);
+        assert_eq!(tuning(TuningPreset::Small, 1024, 1).budget_mb(), 256);
+    }
+
+    #[test]
+    fn test_settings_per_engine() {
+        let medium = tuning(TuningPreset::Medium, 8192, 8);
+
+        let postgres = medium.settings("PostgreSQL");
+        assert_eq!(value(&postgres, "shared_buffers"), "512MB");
+        assert_eq!(value(&postgres, "effective_cache_size"), "1536MB");
+        assert_eq!(value(&postgres, "work_mem"), "32MB");
+        assert_eq!(value(&postgres, "max_parallel_workers_per_gather"), "2");
+
+        let mysql = medium.settings("MariaDB");
+        assert_eq!(value(&mysql, "innodb_buffer_pool_size"), "1536M");
+        assert_eq!(value(&mysql, "tmp_table_size"), "64M");
+
+        let redis = medium.settings("Redis");
+        assert_eq!(value(&redis, "maxmemory"), "1536mb");
+        assert_eq!(redis.len(), 1);
+
+        assert_eq!(
+            value(&medium.settings("MongoDB"), "wiredTigerCacheSizeGB"),
+            "1.00"
+        );
+        assert!(medium.settings("SQLite").is_empty());
+    }
+
+    #[test]
+    fn test_command_args_syntax() {
+        let small = tuning(TuningPreset::Small, 4096, 2);
+
+        assert_eq!(
+            small.command_args("PostgreSQL")[..2],
+            ["-c", "shared_buffers=102MB"]
+        );
+        assert_eq!(
+            small.command_args("MySQL")[0],
+            "--innodb-buffer-pool-size=306M"
+        );
+        assert_eq!(small.command_args("Redis"), vec!["--maxmemory", "306mb"]);
+    }
+
+    #[test]
+    fn test_apply_replaces_earlier_settings() {
+        let mut args = docker_args(&[
+            "--appendonly",
+            "yes",
+            "--maxmemory",
+            "100mb",
+            "--maxmemory-policy",
+            "volatile-lru",
+        ]);
+        tuning(TuningPreset::Small, 4096, 2).apply("Redis", &mut args);
+        tuning(TuningPreset::Large, 4096, 2).apply("Redis", &mut args);
+
+        assert_eq!(
+            args.command,
+            vec![
+                "--appendonly",
+                "yes",
+                "--maxmemory-policy",
+                "volatile-lru",
+                "--maxmemory",
+                "1536mb",
+            ]
+        );
+        assert_eq!(args.shm_size, None);
+
+        let mut args = docker_args(&["-c", "max_connections=50", "-c", "work_mem=1MB"]);
+        tuning(TuningPreset::Medium, 8192, 4).apply("PostgreSQL", &mut args);
+        assert_eq!(args.command[..2], ["-c", "max_connections=50"]);
+        assert_eq!(args.shm_size.as_deref(), Some("512m"));
+        assert_eq!(
+            args.command
+                .iter()
+                .filter(|arg| arg.starts_with("work_mem="))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_preview_lists_settings() {
+        let preview = TuningPreview::new("PostgreSQL", tuning(TuningPreset::Large, 2048, 2));
+
+        assert_eq!(preview.budget_mb, 1024);
+        assert_eq!(preview.settings.len(), 5);
+        assert_eq!(preview.tuning.preset, TuningPreset::Large);
+    }
+}
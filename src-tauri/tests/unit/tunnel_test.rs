@@ -0,0 +1,99 @@
+use docker_db_manager_lib::types::*;
+use std::time::Duration;
+
+#[cfg(test)]
+mod tunnel_tests {
+    use super::*;
+
+    fn create_test_tunnel() -> TunnelDefinition {
+        TunnelDefinition {
+            id: "tunnel-1".to_string(),
+            database_id: "db-1".to_string(),
+            ssh_host: "deploy@db.example.com".to_string(),
+            ssh_port: 22,
+            identity_file: None,
+            local_port: 15432,
+            remote_host: "127.0.0.1".to_string(),
+            remote_port: 5432,
+            auto_start: false,
+        }
+    }
+
+    #[test]
+    fn test_ssh_args_forward_local_port() {
+        let args = create_test_tunnel().ssh_args();
+
+        assert_eq!(args[0], "-N");
+        assert!(args.contains(&"ExitOnForwardFailure=yes".to_string()));
+        assert!(args.contains(&"BatchMode=yes".to_string()));
+        assert!(args
+            .windows(2)
+            .any(|pair| pair == ["-L", "127.0.0.1:15432:127.0.0.1:5432"]));
+        assert_eq!(args.last().unwrap(), "deploy@db.example.com");
+    }
+
+    #[test]
+    fn test_ssh_args_with_identity_and_ipv6_remote() {
+        let mut tunnel = create_test_tunnel();
+        tunnel.identity_file = Some("/home/me/.ssh/id_ed25519".to_string());
+        tunnel.remote_host = "::1".to_string();
+
+        let args = tunnel.ssh_args();
+
+        assert!(args
+            .windows(2)
+            .any(|pair| pair == ["-i", "/home/me/.ssh/id_ed25519"]));
+        assert!(args.contains(&"127.0.0.1:15432:[::1]:5432".to_string()));
+    }
+
+    #[test]
+    fn test_validate_rejects_option_like_hosts() {
+        assert!(create_test_tunnel().validate().is_ok());
+
+        let mut tunnel = create_test_tunnel();
+        tunnel.ssh_host = "-oProxyCommand=evil".to_string();
+        assert!(tunnel.validate().is_err());
+
+        let mut tunnel = create_test_tunnel();
+        tunnel.remote_host = "db host".to_string();
+        assert!(tunnel.validate().is_err());
+
+        let mut tunnel = create_test_tunnel();
+        tunnel.identity_file = Some("-i".to_string());
+        assert!(tunnel.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_port_zero() {
+        let mut tunnel = create_test_tunnel();
+        tunnel.local_port = 0;
+
+        assert!(tunnel.validate().is_err());
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_to_maximum() {
+        assert_eq!(tunnel_retry_delay(1), Duration::from_secs(2));
+        assert_eq!(tunnel_retry_delay(3), Duration::from_secs(8));
+        assert_eq!(
+            tunnel_retry_delay(20),
+            Duration::from_secs(MAX_TUNNEL_RETRY_SECS)
+        );
+    }
+
+    #[test]
+    fn test_definition_defaults() {
+        let tunnel: TunnelDefinition = serde_json::from_value(serde_json::json!({
+            "id": "tunnel-1",
+            "databaseId": "db-1",
+            "sshHost": "db.example.com",
+            "localPort": 15432,
+            "remotePort": 5432
+        }))
+        .unwrap();
+
+        assert_eq!(tunnel.ssh_port, 22);
+        assert_eq!(tunnel.remote_host, "127.0.0.1");
+        assert!(!tunnel.auto_start);
+    }
+}
@@ -0,0 +1,243 @@
+use docker_db_manager_lib::types::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod update_plan_tests {
+    use super::*;
+
+    fn create_test_database(db_type: &str, persist_data: bool) -> DatabaseContainer {
+        DatabaseContainer {
+            id: "db-id".to_string(),
+            name: "my-db".to_string(),
+            db_type: db_type.to_string(),
+            version: "16".to_string(),
+            status: "running".to_string(),
+            port: 5432,
+            max_connections: 100,
+            stored_username: Some("app".to_string()),
+            stored_password: Some("secret".to_string()),
+            stored_persist_data: persist_data,
+            ..Default::default()
+        }
+    }
+
+    /// Request that keeps every setting of `database`
+    fn create_test_request(database: &DatabaseContainer) -> DockerRunRequest {
+        DockerRunRequest {
+            name: database.name.clone(),
+            docker_args: DockerRunArgs {
+                image: "postgres:16".to_string(),
+                env_vars: HashMap::new(),
+                ports: vec![PortMapping::local(database.port, 5432)],
+                volumes: vec![],
+                command: vec![],
+                shm_size: None,
+            },
+            metadata: ContainerMetadata {
+                id: database.id.clone(),
+                db_type: database.db_type.clone(),
+                version: database.version.clone(),
+                port: database.port,
+                username: database.stored_username.clone(),
+                password: String::new(),
+                persist_data: database.stored_persist_data,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_unchanged_request_does_nothing() {
+        let database = create_test_database("PostgreSQL", true);
+
+        let plan = UpdatePlan::new(&database, &create_test_request(&database));
+
+        assert!(plan.changes.is_empty());
+        assert!(!plan.needs_recreation);
+        assert!(!plan.data_loss);
+    }
+
+    #[test]
+    fn test_port_change_recreates_and_carries_version() {
+        let database = create_test_database("PostgreSQL", true);
+        let mut request = create_test_request(&database);
+        request.metadata.port = 5433;
+        request.metadata.version = "17".to_string();
+
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert!(plan.needs_recreation);
+        assert_eq!(plan.effect(UpdateField::Port), Some(UpdateEffect::Recreate));
+        assert_eq!(
+            plan.effect(UpdateField::Version),
+            Some(UpdateEffect::Recreate)
+        );
+        assert!(!plan.data_loss);
+    }
+
+    #[test]
+    fn test_recreating_without_volume_loses_data() {
+        let database = create_test_database("PostgreSQL", false);
+        let mut request = create_test_request(&database);
+        request.name = "renamed-db".to_string();
+
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert!(plan.needs_recreation);
+        assert!(plan.data_loss);
+    }
+
+    #[test]
+    fn test_disabling_persistence_loses_data() {
+        let database = create_test_database("PostgreSQL", true);
+        let mut request = create_test_request(&database);
+        request.metadata.persist_data = false;
+
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert_eq!(
+            plan.effect(UpdateField::PersistData),
+            Some(UpdateEffect::Recreate)
+        );
+        assert!(plan.data_loss);
+    }
+
+    #[test]
+    fn test_port_change_of_ephemeral_database_needs_confirmation() {
+        let database = create_test_database("PostgreSQL", false);
+        let mut request = create_test_request(&database);
+        request.metadata.port = 5433;
+
+        let error = UpdatePlan::new(&database, &request)
+            .data_loss_error(&database)
+            .unwrap();
+
+        assert_eq!(error.code(), "DATA_LOSS");
+        assert_eq!(error.http_status(), 409);
+        assert!(error.to_string().contains("not persisted"));
+
+        let persistent = create_test_database("PostgreSQL", true);
+        let mut request = create_test_request(&persistent);
+        request.metadata.port = 5433;
+        assert!(UpdatePlan::new(&persistent, &request)
+            .data_loss_error(&persistent)
+            .is_none());
+    }
+
+    #[test]
+    fn test_password_change_applies_live() {
+        let database = create_test_database("PostgreSQL", true);
+        let mut request = create_test_request(&database);
+        request.metadata.password = "new-secret".to_string();
+
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert!(!plan.needs_recreation);
+        assert_eq!(
+            plan.effect(UpdateField::Credentials),
+            Some(UpdateEffect::Live)
+        );
+        assert!(!plan.requires_running);
+    }
+
+    #[test]
+    fn test_live_credentials_need_running_engine() {
+        let mut database = create_test_database("PostgreSQL", true);
+        database.status = "stopped".to_string();
+        let mut request = create_test_request(&database);
+        request.metadata.password = "new-secret".to_string();
+
+        assert!(UpdatePlan::new(&database, &request).requires_running);
+    }
+
+    #[test]
+    fn test_redis_password_recreates() {
+        let database = create_test_database("Redis", true);
+        let mut request = create_test_request(&database);
+        request.metadata.password = "new-secret".to_string();
+
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert!(plan.needs_recreation);
+        assert_eq!(
+            plan.effect(UpdateField::Credentials),
+            Some(UpdateEffect::Recreate)
+        );
+    }
+
+    #[test]
+    fn test_version_alone_is_stored() {
+        let database = create_test_database("PostgreSQL", true);
+        let mut request = create_test_request(&database);
+        request.metadata.version = "17".to_string();
+        request.metadata.max_connections = Some(200);
+
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert!(!plan.needs_recreation);
+        assert_eq!(
+            plan.effect(UpdateField::Version),
+            Some(UpdateEffect::Stored)
+        );
+        assert_eq!(
+            plan.effect(UpdateField::MaxConnections),
+            Some(UpdateEffect::Stored)
+        );
+    }
+
+    #[test]
+    fn test_extra_args_change_recreates() {
+        let database = create_test_database("PostgreSQL", true);
+        let mut request = create_test_request(&database);
+        request.metadata.extra_args = vec!["-c".to_string(), "work_mem=64MB".to_string()];
+
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert!(plan.needs_recreation);
+        assert!(!plan.data_loss);
+        assert_eq!(
+            plan.effect(UpdateField::ExtraArgs),
+            Some(UpdateEffect::Recreate)
+        );
+    }
+
+    #[test]
+    fn test_timezone_change_recreates() {
+        let database = create_test_database("PostgreSQL", true);
+        let mut request = create_test_request(&database);
+        request.metadata.region = Some(RegionSettings::default());
+        assert!(UpdatePlan::new(&database, &request).changes.is_empty());
+
+        request.metadata.region = Some(RegionSettings {
+            timezone: Some("Europe/Madrid".to_string()),
+            lang: None,
+        });
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert_eq!(
+            plan.effect(UpdateField::Region),
+            Some(UpdateEffect::Recreate)
+        );
+    }
+
+    #[test]
+    fn test_tuning_change_recreates() {
+        let database = create_test_database("PostgreSQL", true);
+        let mut request = create_test_request(&database);
+        request.metadata.tuning = Some(TuningSettings {
+            preset: TuningPreset::Medium,
+            host: HostResources {
+                memory_mb: 8192,
+                cpus: 4,
+            },
+        });
+
+        let plan = UpdatePlan::new(&database, &request);
+
+        assert!(plan.needs_recreation);
+        assert_eq!(
+            plan.effect(UpdateField::Tuning),
+            Some(UpdateEffect::Recreate)
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod uptime_tests {
+    use super::*;
+
+    fn running(started_at: &str, restart_count: u32) -> DockerContainerState {
+        DockerContainerState {
+            id: "4f1c2d3e".to_string(),
+            running: true,
+            started_at: Some(started_at.to_string()),
+            restart_count,
+            ..Default::default()
+        }
+    }
+
+    fn exited(finished_at: &str, exit_code: i32, oom_killed: bool) -> DockerContainerState {
+        DockerContainerState {
+            id: "4f1c2d3e".to_string(),
+            exit_code: Some(exit_code),
+            started_at: Some("2024-05-10T09:00:00Z".to_string()),
+            finished_at: Some(finished_at.to_string()),
+            oom_killed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_exit_reason() {
+        assert_eq!(
+            exit_reason(Some(137), true).as_deref(),
+            Some("Killed for running out of memory")
+        );
+        assert_eq!(
+            exit_reason(Some(137), false).as_deref(),
+            Some("Killed (SIGKILL)")
+        );
+        assert_eq!(
+            exit_reason(Some(2), false).as_deref(),
+            Some("Exited with code 2")
+        );
+        assert_eq!(exit_reason(None, false), None);
+    }
+
+    #[test]
+    fn test_first_sighting_records_no_events() {
+        let mut history = UptimeHistory::default();
+
+        history.observe("db", &running("2024-05-10T09:00:00Z", 0));
+
+        assert!(history.containers["db"].events.is_empty());
+        assert!(history.containers["db"].last_seen.is_some());
+    }
+
+    #[test]
+    fn test_crash_and_restart_are_recorded() {
+        let mut history = UptimeHistory::default();
+        history.observe("db", &running("2024-05-10T09:00:00Z", 0));
+
+        history.observe("db", &exited("2024-05-10T23:10:00Z", 137, true));
+        history.observe("db", &running("2024-05-10T23:10:05Z", 1));
+
+        let events = &history.containers["db"].events;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, LifecycleEventKind::Exited);
+        assert_eq!(events[0].exit_code, Some(137));
+        assert!(events[0].oom_killed);
+        assert_eq!(
+            events[0].reason.as_deref(),
+            Some("Killed for running out of memory")
+        );
+        assert_eq!(events[1].kind, LifecycleEventKind::Restarted);
+        assert_eq!(events[1].restart_count, 1);
+    }
+
+    #[test]
+    fn test_restart_between_syncs_records_exit_without_code() {
+        let mut history = UptimeHistory::default();
+        history.observe("db", &running("2024-05-10T09:00:00Z", 0));
+
+        let mut restarted = running("2024-05-10T23:10:05Z", 1);
+        restarted.finished_at = Some("2024-05-10T23:10:00Z".to_string());
+        history.observe("db", &restarted);
+
+        let events = &history.containers["db"].events;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, LifecycleEventKind::Exited);
+        assert_eq!(events[0].exit_code, None);
+        assert_eq!(events[0].reason, None);
+        assert_eq!(events[1].kind, LifecycleEventKind::Restarted);
+    }
+
+    #[test]
+    fn test_several_restarts_between_syncs_are_counted() {
+        let mut history = UptimeHistory::default();
+        history.observe("db", &running("2024-05-10T09:00:00Z", 0));
+        history.observe("db", &running("2024-05-10T09:05:00Z", 3));
+
+        let events = &history.containers["db"].events;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, LifecycleEventKind::Restarted);
+        assert_eq!(events[0].at, "2024-05-10T09:05:00Z");
+        assert_eq!(
+            events[0].reason.as_deref(),
+            Some("Restarted 3 times since the previous check")
+        );
+    }
+
+    #[test]
+    fn test_manual_start_is_not_a_restart() {
+        let mut history = UptimeHistory::default();
+        history.observe("db", &exited("2024-05-10T18:00:00Z", 0, false));
+
+        history.observe("db", &running("2024-05-11T08:00:00Z", 0));
+
+        let events = &history.containers["db"].events;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, LifecycleEventKind::Started);
+    }
+
+    #[test]
+    fn test_unchanged_state_adds_nothing() {
+        let mut history = UptimeHistory::default();
+        let state = running("2024-05-10T09:00:00Z", 0);
+
+        history.observe("db", &state);
+        history.observe("db", &state);
+
+        assert!(history.containers["db"].events.is_empty());
+    }
+
+    #[test]
+    fn test_events_are_capped() {
+        let mut history = UptimeHistory::default();
+        history.observe("db", &running("2024-05-10T00:00:00Z", 0));
+
+        for minute in 1..=MAX_LIFECYCLE_EVENTS + 5 {
+            let started_at = format!("2024-05-10T{:02}:{:02}:00Z", minute / 60 + 1, minute % 60);
+            history.observe("db", &running(&started_at, 0));
+        }
+
+        let events = &history.containers["db"].events;
+        assert_eq!(events.len(), MAX_LIFECYCLE_EVENTS);
+        assert_eq!(events.last().unwrap().at, "2024-05-10T02:45:00Z");
+    }
+
+    #[test]
+    fn test_report_of_running_container() {
+        let now = DateTime::parse_from_rfc3339("2024-05-10T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut stopped = exited("2024-05-10T08:59:00Z", 1, false);
+        stopped.started_at = Some("2024-05-10T08:00:00Z".to_string());
+        let mut restarted = running("2024-05-10T09:00:00Z", 3);
+        restarted.finished_at = stopped.finished_at.clone();
+
+        let mut history = UptimeHistory::default();
+        history.observe("db", &running("2024-05-10T08:00:00Z", 2));
+        history.observe("db", &stopped);
+        history.observe("db", &restarted);
+
+        let report = UptimeReport::new(Some(&restarted), history.containers.get("db"), now);
+
+        assert!(report.running);
+        assert_eq!(report.uptime_secs, Some(3600));
+        assert_eq!(report.restart_count, 3);
+        // The live exit code is gone once running, the recorded one remains
+        assert_eq!(report.last_exit.unwrap().exit_code, Some(1));
+        assert_eq!(report.events[0].kind, LifecycleEventKind::Restarted);
+    }
+
+    #[test]
+    fn test_report_of_stopped_container_uses_live_exit() {
+        let report = UptimeReport::new(
+            Some(&exited("2024-05-10T23:10:00Z", 139, false)),
+            None,
+            Utc::now(),
+        );
+
+        assert!(!report.running);
+        assert_eq!(report.uptime_secs, None);
+        assert_eq!(
+            report.last_exit.unwrap().reason.as_deref(),
+            Some("Crashed with a segmentation fault")
+        );
+    }
+
+    #[test]
+    fn test_report_without_container() {
+        let report = UptimeReport::new(None, None, Utc::now());
+
+        assert!(!report.running);
+        assert!(report.last_exit.is_none());
+        assert!(report.events.is_empty());
+    }
+}
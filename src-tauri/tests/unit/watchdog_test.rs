@@ -0,0 +1,178 @@
+use chrono::{Duration, Utc};
+use docker_db_manager_lib::services::Watchdog;
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod watchdog_tests {
+    use super::*;
+
+    fn policy() -> WatchdogPolicy {
+        WatchdogPolicy {
+            max_attempts: 2,
+            initial_delay_secs: 5,
+            max_delay_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_delay_doubles_up_to_the_maximum() {
+        let policy = policy();
+
+        assert_eq!(policy.delay_secs(1), 5);
+        assert_eq!(policy.delay_secs(2), 10);
+        assert_eq!(policy.delay_secs(4), 40);
+        assert_eq!(policy.delay_secs(5), 60);
+        assert_eq!(policy.delay_secs(40), 60);
+    }
+
+    #[test]
+    fn test_policy_violations() {
+        assert!(WatchdogPolicy::default().violations().is_empty());
+
+        let no_attempts = WatchdogPolicy {
+            max_attempts: 0,
+            ..policy()
+        };
+        assert_eq!(no_attempts.violations().len(), 1);
+
+        let inverted = WatchdogPolicy {
+            initial_delay_secs: 120,
+            ..policy()
+        };
+        assert_eq!(inverted.violations().len(), 1);
+    }
+
+    #[test]
+    fn test_crash_is_restarted_after_backoff_then_gives_up() {
+        let mut watchdog = Watchdog::default();
+        let policy = policy();
+        let now = Utc::now();
+
+        assert_eq!(
+            watchdog.observe("db-1", &policy, "exited", true, now),
+            Some(WatchdogAction::Scheduled {
+                attempt: 1,
+                delay_secs: 5
+            })
+        );
+        // Still waiting for the delay
+        assert_eq!(
+            watchdog.observe("db-1", &policy, "exited", false, now + Duration::seconds(2)),
+            None
+        );
+        assert_eq!(
+            watchdog.observe("db-1", &policy, "exited", false, now + Duration::seconds(5)),
+            Some(WatchdogAction::Restart { attempt: 1 })
+        );
+
+        // Crashed again right after the restart
+        let later = now + Duration::seconds(10);
+        assert_eq!(
+            watchdog.observe("db-1", &policy, "exited", false, later),
+            Some(WatchdogAction::Scheduled {
+                attempt: 2,
+                delay_secs: 10
+            })
+        );
+        assert_eq!(
+            watchdog.observe(
+                "db-1",
+                &policy,
+                "exited",
+                false,
+                later + Duration::seconds(10)
+            ),
+            Some(WatchdogAction::Restart { attempt: 2 })
+        );
+        assert_eq!(
+            watchdog.observe(
+                "db-1",
+                &policy,
+                "exited",
+                false,
+                later + Duration::seconds(20)
+            ),
+            Some(WatchdogAction::GaveUp { attempts: 2 })
+        );
+        assert_eq!(
+            watchdog.observe(
+                "db-1",
+                &policy,
+                "exited",
+                false,
+                later + Duration::seconds(90)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_exit_caused_by_the_app_is_ignored() {
+        let mut watchdog = Watchdog::default();
+
+        assert_eq!(
+            watchdog.observe("db-1", &policy(), "exited", false, Utc::now()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stable_run_resets_attempts() {
+        let mut watchdog = Watchdog::default();
+        let policy = policy();
+        let now = Utc::now();
+
+        watchdog.observe("db-1", &policy, "exited", true, now);
+        watchdog.observe("db-1", &policy, "exited", false, now + Duration::seconds(5));
+        watchdog.observe(
+            "db-1",
+            &policy,
+            "running",
+            false,
+            now + Duration::seconds(400),
+        );
+
+        assert_eq!(
+            watchdog.observe(
+                "db-1",
+                &policy,
+                "exited",
+                true,
+                now + Duration::seconds(500)
+            ),
+            Some(WatchdogAction::Scheduled {
+                attempt: 1,
+                delay_secs: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_manual_start_closes_the_breaker() {
+        let mut watchdog = Watchdog::default();
+        let policy = WatchdogPolicy {
+            max_attempts: 1,
+            ..policy()
+        };
+        let now = Utc::now();
+
+        watchdog.observe("db-1", &policy, "exited", true, now);
+        watchdog.observe("db-1", &policy, "exited", false, now + Duration::seconds(5));
+        assert_eq!(
+            watchdog.observe("db-1", &policy, "exited", false, now + Duration::seconds(6)),
+            Some(WatchdogAction::GaveUp { attempts: 1 })
+        );
+
+        watchdog.observe(
+            "db-1",
+            &policy,
+            "running",
+            false,
+            now + Duration::seconds(30),
+        );
+        assert!(matches!(
+            watchdog.observe("db-1", &policy, "exited", true, now + Duration::seconds(40)),
+            Some(WatchdogAction::Scheduled { attempt: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+use docker_db_manager_lib::types::*;
+
+#[cfg(test)]
+mod window_geometry_tests {
+    use super::*;
+
+    fn monitor(x: f64, width: f64) -> MonitorArea {
+        MonitorArea {
+            x,
+            y: 0.0,
+            width,
+            height: 1080.0,
+        }
+    }
+
+    fn geometry(x: f64, y: f64, width: f64, height: f64) -> WindowGeometry {
+        WindowGeometry {
+            x,
+            y,
+            width,
+            height,
+            maximized: false,
+        }
+    }
+
+    #[test]
+    fn test_geometry_keys() {
+        assert_eq!(window_geometry_key("main"), Some("main"));
+        assert_eq!(
+            window_geometry_key("container-creation"),
+            Some("container-creation")
+        );
+        assert_eq!(
+            window_geometry_key("container-edit-abc123"),
+            Some("container-edit")
+        );
+        assert_eq!(window_geometry_key("metrics"), Some("metrics"));
+        assert_eq!(window_geometry_key("metrics-abc123"), Some("metrics"));
+        assert_eq!(window_geometry_key("mainframe"), None);
+    }
+
+    #[test]
+    fn test_visible_geometry_is_kept() {
+        let saved = geometry(2200.0, 100.0, 900.0, 700.0);
+        let monitors = [monitor(0.0, 1920.0), monitor(1920.0, 2560.0)];
+
+        assert_eq!(saved.restore_on(&monitors), (saved, true));
+    }
+
+    #[test]
+    fn test_unplugged_monitor_centers_window() {
+        let saved = geometry(2200.0, 100.0, 900.0, 700.0);
+
+        let (restored, keep_position) = saved.restore_on(&[monitor(0.0, 1920.0)]);
+
+        assert!(!keep_position);
+        assert_eq!(restored.width, 900.0);
+    }
+
+    #[test]
+    fn test_size_is_clamped_to_monitor() {
+        let saved = geometry(0.0, 0.0, 2400.0, 1400.0);
+
+        let (restored, keep_position) = saved.restore_on(&[monitor(0.0, 1920.0)]);
+
+        assert!(keep_position);
+        assert_eq!((restored.width, restored.height), (1920.0, 1080.0));
+    }
+
+    #[test]
+    fn test_window_off_the_edge_is_pulled_back() {
+        let saved = geometry(1700.0, 900.0, 600.0, 500.0);
+
+        let (restored, _) = saved.restore_on(&[monitor(0.0, 1920.0)]);
+
+        assert_eq!((restored.x, restored.y), (1320.0, 580.0));
+    }
+
+    #[test]
+    fn test_title_bar_above_screen_is_not_reachable() {
+        let saved = geometry(100.0, -300.0, 600.0, 500.0);
+
+        let (_, keep_position) = saved.restore_on(&[monitor(0.0, 1920.0)]);
+
+        assert!(!keep_position);
+    }
+
+    #[test]
+    fn test_unknown_monitors_keep_geometry() {
+        let saved = geometry(-5000.0, 0.0, 600.0, 500.0);
+
+        assert_eq!(saved.restore_on(&[]), (saved, true));
+    }
+}
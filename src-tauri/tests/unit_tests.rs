@@ -6,9 +6,289 @@
 /// Tests are organized by component:
 /// - docker_service_test: Tests for DockerService methods
 /// - generic_commands_test: Tests for generic command structures (DockerRunRequest, DockerRunArgs, etc.)
+/// - tls_service_test: Tests for TLS argument generation
+/// - companion_service_test: Tests for companion admin tool configuration
+/// - app_error_test: Tests for error classification and serialization
+/// - storage_service_test: Tests for atomic store writes, corruption recovery, encryption, workspace files and entry merges
+/// - config_service_test: Tests for configuration export bundles
+/// - secrets_service_test: Tests for keychain password references
+/// - group_service_test: Tests for container group ordering
+/// - database_container_test: Tests for stored container defaults and tags
+/// - database_filter_test: Tests for database listing filters, sorting and pagination
+/// - snapshot_service_test: Tests for snapshot volume naming
+/// - trash_service_test: Tests for trash retention
+/// - bulk_operation_test: Tests for bulk operation actions and results
+/// - autostart_service_test: Tests for selecting containers to start on launch
+/// - startup_mode_test: Tests for headless startup detection
+/// - shutdown_service_test: Tests for stopping containers on exit
+/// - scheduler_service_test: Tests for cron schedules and missed occurrences
+/// - tray_service_test: Tests for tray menu entries and actions
+/// - notification_service_test: Tests for Docker state parsing and health alerts
+/// - local_store_test: Tests for the store and Docker core shared with the ddm CLI
+/// - deep_link_test: Tests for parsing dbmanager:// links
+/// - api_test: Tests for HTTP API routing and token checks
+/// - mcp_test: Tests for MCP tools and connection strings
+/// - connection_service_test: Tests for .env snippets and framework presets
+/// - client_service_test: Tests for external client and terminal arguments
+/// - metrics_service_test: Tests for parsing docker stats samples
+/// - window_geometry_test: Tests for restoring window geometry across monitors
+/// - log_pager_test: Tests for cursor-based log pagination
+/// - docker_runtime_test: Tests for Docker runtime detection and launch commands
+/// - readiness_service_test: Tests for engine readiness probes
+/// - container_name_test: Tests for container name validation and normalization
+/// - request_validation_test: Tests for create and update request validation
+/// - credential_service_test: Tests for in-engine credential changes
+/// - image_update_test: Image reference parsing and update detection
+/// - orphan_test: Management labels and orphan container adoption
+/// - sql_service_test: SQL table browser queries
+/// - schema_test: Schema introspection and ERD export
+/// - csv_service_test: CSV table export
+/// - benchmark_test: Benchmark parameters, report parsing and plans
+/// - slow_log_test: Slow query log statements and parsing
+/// - storage_breakdown_test: Database and table size parsing
+/// - metrics_history_test: Metrics history compaction, queries and probes
+/// - alert_test: Alert rule conditions, duration tracking and history trimming
+/// - uptime_test: Container start, restart and exit history
+/// - stack_test: Stack template validation and naming tests
+/// - tunnel_test: SSH tunnel definition and backoff tests
+/// - kubernetes_service_test: Kubernetes manifest export tests
+/// - recovery_test: Interrupted creation recovery tests
+/// - update_plan_test: Update impact plan tests
+/// - log_parser_test: Engine log parsing tests
+/// - locale_test: Locale and collation tests
+/// - data_dir_test: Data directory browser tests
+/// - file_copy_test: File copy tests
+/// - integrity_test: Integrity check tests
+/// - env_drift_test: Environment drift tests
+/// - image_cleanup_test: Image cleanup tests
+/// - bundle_test: Database bundle tests
+/// - graceful_stop_test: Graceful stop tests
+/// - region_test: Region settings tests
+/// - watchdog_test: Watchdog tests
+/// - exec_history_test: Exec history tests
+/// - docker_host_test: Docker host tests
+/// - share_test: Public share tests
+/// - sql_guard_test: SQL guard tests
+/// - snippet_test: Snippet tests
+/// - query_export_test: Query export tests
+/// - sql_job_test: SQL job tests
+/// - replication_test: Logical replication tests
+/// - tuning_test: Tuning tests
+/// - event_service_test: Tests for status change events and their payloads
+/// - sync_service_test: Tests for the background sync merge, change detection and interval
 
 #[path = "unit/docker_service_test.rs"]
 mod docker_service_test;
 
 #[path = "unit/generic_commands_test.rs"]
 mod generic_commands_test;
+
+#[path = "unit/tls_service_test.rs"]
+mod tls_service_test;
+
+#[path = "unit/companion_service_test.rs"]
+mod companion_service_test;
+
+#[path = "unit/app_error_test.rs"]
+mod app_error_test;
+
+#[path = "unit/storage_service_test.rs"]
+mod storage_service_test;
+
+#[path = "unit/config_service_test.rs"]
+mod config_service_test;
+
+#[path = "unit/secrets_service_test.rs"]
+mod secrets_service_test;
+
+#[path = "unit/group_service_test.rs"]
+mod group_service_test;
+
+#[path = "unit/database_container_test.rs"]
+mod database_container_test;
+
+#[path = "unit/database_filter_test.rs"]
+mod database_filter_test;
+
+#[path = "unit/snapshot_service_test.rs"]
+mod snapshot_service_test;
+
+#[path = "unit/trash_service_test.rs"]
+mod trash_service_test;
+
+#[path = "unit/bulk_operation_test.rs"]
+mod bulk_operation_test;
+
+#[path = "unit/autostart_service_test.rs"]
+mod autostart_service_test;
+
+#[path = "unit/startup_mode_test.rs"]
+mod startup_mode_test;
+
+#[path = "unit/shutdown_service_test.rs"]
+mod shutdown_service_test;
+
+#[path = "unit/scheduler_service_test.rs"]
+mod scheduler_service_test;
+
+#[path = "unit/tray_service_test.rs"]
+mod tray_service_test;
+
+#[path = "unit/notification_service_test.rs"]
+mod notification_service_test;
+
+#[path = "unit/local_store_test.rs"]
+mod local_store_test;
+
+#[path = "unit/deep_link_test.rs"]
+mod deep_link_test;
+
+#[path = "unit/api_test.rs"]
+mod api_test;
+
+#[path = "unit/mcp_test.rs"]
+mod mcp_test;
+
+#[path = "unit/connection_service_test.rs"]
+mod connection_service_test;
+
+#[path = "unit/client_service_test.rs"]
+mod client_service_test;
+
+#[path = "unit/metrics_service_test.rs"]
+mod metrics_service_test;
+
+#[path = "unit/window_geometry_test.rs"]
+mod window_geometry_test;
+
+#[path = "unit/log_pager_test.rs"]
+mod log_pager_test;
+
+#[path = "unit/docker_runtime_test.rs"]
+mod docker_runtime_test;
+
+#[path = "unit/readiness_service_test.rs"]
+mod readiness_service_test;
+
+#[path = "unit/container_name_test.rs"]
+mod container_name_test;
+
+#[path = "unit/request_validation_test.rs"]
+mod request_validation_test;
+
+#[path = "unit/credential_service_test.rs"]
+mod credential_service_test;
+
+#[path = "unit/image_update_test.rs"]
+mod image_update_test;
+
+#[path = "unit/orphan_test.rs"]
+mod orphan_test;
+
+#[path = "unit/sql_service_test.rs"]
+mod sql_service_test;
+
+#[path = "unit/schema_test.rs"]
+mod schema_test;
+
+#[path = "unit/csv_service_test.rs"]
+mod csv_service_test;
+
+#[path = "unit/benchmark_test.rs"]
+mod benchmark_test;
+
+#[path = "unit/slow_log_test.rs"]
+mod slow_log_test;
+
+#[path = "unit/storage_breakdown_test.rs"]
+mod storage_breakdown_test;
+
+#[path = "unit/metrics_history_test.rs"]
+mod metrics_history_test;
+
+#[path = "unit/alert_test.rs"]
+mod alert_test;
+
+#[path = "unit/uptime_test.rs"]
+mod uptime_test;
+
+#[path = "unit/stack_test.rs"]
+mod stack_test;
+
+#[path = "unit/tunnel_test.rs"]
+mod tunnel_test;
+
+#[path = "unit/kubernetes_service_test.rs"]
+mod kubernetes_service_test;
+
+#[path = "unit/recovery_test.rs"]
+mod recovery_test;
+
+#[path = "unit/update_plan_test.rs"]
+mod update_plan_test;
+
+#[path = "unit/log_parser_test.rs"]
+mod log_parser_test;
+
+#[path = "unit/locale_test.rs"]
+mod locale_test;
+
+#[path = "unit/data_dir_test.rs"]
+mod data_dir_test;
+
+#[path = "unit/file_copy_test.rs"]
+mod file_copy_test;
+
+#[path = "unit/integrity_test.rs"]
+mod integrity_test;
+
+#[path = "unit/env_drift_test.rs"]
+mod env_drift_test;
+
+#[path = "unit/image_cleanup_test.rs"]
+mod image_cleanup_test;
+
+#[path = "unit/bundle_test.rs"]
+mod bundle_test;
+
+#[path = "unit/graceful_stop_test.rs"]
+mod graceful_stop_test;
+
+#[path = "unit/region_test.rs"]
+mod region_test;
+
+#[path = "unit/watchdog_test.rs"]
+mod watchdog_test;
+
+#[path = "unit/exec_history_test.rs"]
+mod exec_history_test;
+
+#[path = "unit/docker_host_test.rs"]
+mod docker_host_test;
+
+#[path = "unit/share_test.rs"]
+mod share_test;
+
+#[path = "unit/sql_guard_test.rs"]
+mod sql_guard_test;
+
+#[path = "unit/snippet_test.rs"]
+mod snippet_test;
+
+#[path = "unit/query_export_test.rs"]
+mod query_export_test;
+
+#[path = "unit/sql_job_test.rs"]
+mod sql_job_test;
+
+#[path = "unit/replication_test.rs"]
+mod replication_test;
+
+#[path = "unit/tuning_test.rs"]
+mod tuning_test;
+
+#[path = "unit/event_service_test.rs"]
+mod event_service_test;
+
+#[path = "unit/sync_service_test.rs"]
+mod sync_service_test;
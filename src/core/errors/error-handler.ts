@@ -1,34 +1,85 @@
 import { toast } from 'sonner';
 
+export interface FieldViolation {
+  field: string;
+  message: string;
+}
+
 export interface AppError {
   type:
     | 'PORT_IN_USE'
     | 'NAME_IN_USE'
+    | 'NOT_FOUND'
+    | 'DOCKER_UNAVAILABLE'
+    | 'HOST_UNREACHABLE'
+    | 'DOCKER_PERMISSION_DENIED'
+    | 'IMAGE_NOT_FOUND'
+    | 'DISK_FULL'
     | 'DOCKER_ERROR'
     | 'VALIDATION_ERROR'
+    | 'INVALID_NAME'
+    | 'INVALID_REQUEST'
+    | 'STORAGE_ERROR'
+    | 'IO_ERROR'
+    | 'SECRETS_ERROR'
+    | 'STORE_LOCKED'
+    | 'READ_ONLY'
+    | 'WRITE_BLOCKED'
+    | 'DATA_LOSS'
+    | 'SETTING_CHANGE'
+    | 'INTERNAL_ERROR'
     | 'UNKNOWN';
   message: string;
   details?: string;
+  hint?: string;
+  port?: number;
+  suggestion?: string;
+  violations?: FieldViolation[];
+}
+
+/**
+ * Shape of the errors returned by every Tauri command
+ */
+interface CommandError {
+  code: AppError['type'];
+  message: string;
+  details?: string | null;
+  hint?: string | null;
+  port?: number | null;
+  suggestion?: string | null;
+  violations?: FieldViolation[] | null;
+}
+
+function isCommandError(error: unknown): error is CommandError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    'code' in error &&
+    'message' in error
+  );
 }
 
 /**
- * Parse Tauri errors (which come as JSON strings)
+ * Parse Tauri errors (structured objects with a `code`)
  */
 export function parseError(error: unknown): AppError {
+  if (isCommandError(error)) {
+    return {
+      type: error.code || 'UNKNOWN',
+      message: error.message,
+      details: error.details ?? undefined,
+      hint: error.hint ?? undefined,
+      port: error.port ?? undefined,
+      suggestion: error.suggestion ?? undefined,
+      violations: error.violations ?? undefined,
+    };
+  }
+
   if (typeof error === 'string') {
-    try {
-      const parsed = JSON.parse(error);
-      return {
-        type: parsed.error_type || 'UNKNOWN',
-        message: parsed.message || error,
-        details: parsed.details,
-      };
-    } catch {
-      return {
-        type: 'UNKNOWN',
-        message: error,
-      };
-    }
+    return {
+      type: 'UNKNOWN',
+      message: error,
+    };
   }
 
   if (error instanceof Error) {
@@ -53,13 +104,32 @@ export function showErrorToast(error: unknown): void {
   const errorMessages: Record<AppError['type'], string> = {
     PORT_IN_USE: 'Port already in use',
     NAME_IN_USE: 'A container with that name already exists',
+    NOT_FOUND: 'Not found',
+    DOCKER_UNAVAILABLE: 'Docker is not running',
+    HOST_UNREACHABLE: 'Docker host unreachable',
+    DOCKER_PERMISSION_DENIED: 'Docker permission denied',
+    IMAGE_NOT_FOUND: 'Image not found',
+    DISK_FULL: 'Disk full',
     DOCKER_ERROR: 'Docker error',
     VALIDATION_ERROR: 'Validation error',
+    INVALID_NAME: 'Invalid container name',
+    INVALID_REQUEST: 'Invalid configuration',
+    STORAGE_ERROR: 'Storage error',
+    IO_ERROR: 'File system error',
+    SECRETS_ERROR: 'Keychain error',
+    STORE_LOCKED: 'Store locked',
+    READ_ONLY: 'Read-only mode',
+    WRITE_BLOCKED: 'SQL console is read-only',
+    DATA_LOSS: 'Data would be lost',
+    SETTING_CHANGE: 'Server setting change needed',
+    INTERNAL_ERROR: 'Unexpected error',
     UNKNOWN: 'Unexpected error',
   };
 
   toast.error(errorMessages[appError.type], {
-    description: appError.message,
+    description: appError.hint
+      ? `${appError.message}. ${appError.hint}`
+      : appError.message,
   });
 }
 
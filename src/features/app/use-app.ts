@@ -2,6 +2,7 @@ import { useCallback, useEffect } from 'react';
 import { useDatabaseActions } from '../databases/hooks/use-database-actions';
 import { useDatabaseList } from '../databases/hooks/use-database-list';
 import { useDockerStatus } from '../docker/hooks/use-docker-status';
+import { isContainerRunning } from '../../shared/types/container';
 
 /**
  * Main orchestration hook
@@ -36,7 +37,7 @@ export function useApp() {
       );
       if (!container) return;
 
-      if (container.status === 'running') {
+      if (isContainerRunning(container.status)) {
         await containerActions.stop(containerId);
       } else {
         await containerActions.start(containerId);
@@ -76,6 +77,8 @@ export function useApp() {
     dockerStatus: docker.dockerStatus,
     dockerRefreshing: docker.isRefreshing,
     refreshDockerStatus: docker.refreshStatus,
+    startDocker: docker.startDocker,
+    dockerStartProgress: docker.startProgress,
     isDockerAvailable: docker.isDockerAvailable,
     showDockerOverlay: docker.shouldShowOverlay,
   };
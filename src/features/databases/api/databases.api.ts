@@ -1,6 +1,748 @@
 import { invoke } from '@/core/tauri/invoke';
-import type { Container } from '@/shared/types/container';
-import type { DockerRunRequest } from '../types/docker.types';
+import type {
+  Container,
+  TuningPreset,
+  TuningSettings,
+} from '@/shared/types/container';
+import type { DockerRunRequest, PortMapping } from '../types/docker.types';
+
+export interface ImportReport {
+  imported: Container[];
+  skipped: { name: string; reason: string }[];
+  warnings: { name: string; reason: string }[];
+  settingsApplied: boolean;
+}
+
+export interface BundleFile {
+  name: string;
+  sha256: string;
+  sizeBytes: number;
+}
+
+export interface BundleManifest {
+  version: number;
+  appVersion: string;
+  exportedAt: string;
+  dbType: string;
+  engineVersion: string;
+  image: string;
+  name: string;
+  files: BundleFile[];
+}
+
+export interface BundleExport {
+  path: string;
+  manifest: BundleManifest;
+}
+
+export interface BundleImport {
+  database: Container;
+  manifest: BundleManifest;
+  renamedFrom: string | null;
+  remappedPorts: { from: number; to: number }[];
+}
+
+export interface LogQuery {
+  since?: string;
+  until?: string;
+  cursor?: string;
+  limit?: number;
+}
+
+export interface LogPage {
+  lines: string[];
+  nextCursor: string | null;
+  hasMore: boolean;
+}
+
+export type LogSeverity =
+  | 'debug'
+  | 'info'
+  | 'notice'
+  | 'warning'
+  | 'error'
+  | 'fatal';
+
+export interface LogEntry {
+  timestamp: string | null;
+  severity: LogSeverity;
+  source: string | null;
+  message: string;
+}
+
+export interface ParsedLogPage {
+  entries: LogEntry[];
+  counts: Partial<Record<LogSeverity, number>>;
+  nextCursor: string | null;
+  hasMore: boolean;
+}
+
+export interface DatabaseFilter {
+  dbType?: string;
+  status?: string;
+  tag?: string;
+  name?: string;
+  port?: number;
+  favorite?: boolean;
+}
+
+export interface DatabaseSort {
+  key: 'name' | 'createdAt' | 'lastStarted' | 'dbType' | 'status';
+  descending?: boolean;
+}
+
+export interface DatabasePage {
+  items: Container[];
+  total: number;
+  offset: number;
+}
+
+export type BulkAction = 'start' | 'stop' | 'remove';
+
+export type EnvPreset = 'generic' | 'rails' | 'django' | 'laravel' | 'prisma';
+
+export type Framework = 'django' | 'rails' | 'laravel' | 'spring' | 'prisma';
+
+export interface FrameworkPreset {
+  framework: Framework;
+  name: string;
+  dbType: string;
+  version: string;
+  databaseName: string;
+  username: string;
+  /** Where the generated snippet goes in the project */
+  configFile: string;
+}
+
+export type DatabaseClient =
+  | 'tablePlus'
+  | 'dBeaver'
+  | 'pgAdmin'
+  | 'beekeeper'
+  | 'cli';
+
+export interface OrphanContainer {
+  containerId: string;
+  name: string;
+  image: string;
+  running: boolean;
+  labels: {
+    databaseId: string;
+    dbType: string;
+    workspace: string;
+  };
+}
+
+/**
+ * Creation cut short by a crash, undone at launch
+ */
+export interface RecoveredCreate {
+  databaseId: string;
+  name: string;
+  removedContainer: boolean;
+  removedEntry: boolean;
+  removedVolumes: string[];
+  /** Set when a leftover could not be removed, retried on the next launch */
+  error: string | null;
+}
+
+export type OrphanResolution = 'adopt' | 'remove';
+
+export interface TableInfo {
+  schema: string;
+  name: string;
+  kind: 'table' | 'view';
+  rowCount: number | null;
+}
+
+export interface ColumnInfo {
+  name: string;
+  dataType: string;
+  nullable: boolean;
+  primaryKey: boolean;
+}
+
+export interface TableFilter {
+  column: string;
+  operator:
+    | 'equals'
+    | 'notEquals'
+    | 'greaterThan'
+    | 'lessThan'
+    | 'contains'
+    | 'isNull'
+    | 'isNotNull';
+  value?: string;
+}
+
+export interface TableRowsQuery {
+  schema: string;
+  table: string;
+  offset?: number;
+  limit: number;
+  sort?: { column: string; descending?: boolean };
+  filters?: TableFilter[];
+}
+
+export interface TableRowsPage {
+  columns: ColumnInfo[];
+  rows: unknown[][];
+  total: number;
+  offset: number;
+  limit: number;
+}
+
+export interface DatabaseSchema {
+  database: string;
+  tables: {
+    schema: string;
+    name: string;
+    columns: ColumnInfo[];
+    primaryKey: string[];
+    foreignKeys: {
+      name: string;
+      columns: string[];
+      referencedSchema: string;
+      referencedTable: string;
+      referencedColumns: string[];
+    }[];
+    indexes: { name: string; columns: string[]; unique: boolean }[];
+  }[];
+}
+
+export type SchemaFormat = 'mermaid' | 'dbml';
+
+export interface TableExportOptions {
+  schema?: string;
+  columns?: string[];
+  header?: boolean;
+}
+
+export interface TableExportResult {
+  path: string;
+  bytesWritten: number;
+}
+
+export interface CsvImportMapping {
+  schema?: string;
+  columns?: string[];
+  delimiter?: string;
+  header?: boolean;
+  // Confirms turning on MySQL's local_infile for the import, it is restored after
+  enableLocalInfile?: boolean;
+}
+
+export interface CsvImportReport {
+  rowsImported: number;
+  errors: { line: number | null; message: string }[];
+}
+
+export type BenchmarkTool = 'pgbench' | 'sysbench' | 'memtier' | 'ycsb';
+
+export interface BenchmarkParams {
+  tool?: BenchmarkTool;
+  clients?: number;
+  threads?: number;
+  durationSecs?: number;
+  scale?: number;
+  workload?: string;
+}
+
+export interface BenchmarkRun {
+  id: string;
+  databaseId: string;
+  tool: BenchmarkTool;
+  params: Required<Omit<BenchmarkParams, 'tool' | 'workload'>> &
+    Pick<BenchmarkParams, 'tool' | 'workload'>;
+  startedAt: string;
+  finishedAt: string;
+  metrics: {
+    throughput: number | null;
+    latencyAvgMs: number | null;
+    latencyP95Ms: number | null;
+    latencyP99Ms: number | null;
+  };
+  output: string;
+}
+
+export interface SlowQuery {
+  timestamp: string;
+  durationMs: number;
+  query: string;
+  database: string | null;
+  client: string | null;
+  rowsExamined: number | null;
+}
+
+export interface TableSize {
+  schema: string | null;
+  name: string;
+  sizeBytes: number;
+  rowCount: number | null;
+}
+
+export interface StorageBreakdown {
+  totalBytes: number;
+  databases: {
+    name: string;
+    sizeBytes: number;
+    tables: TableSize[];
+    partial: boolean;
+  }[];
+  collectedAt: string;
+}
+
+export interface DataDirEntry {
+  name: string;
+  kind: 'file' | 'directory' | 'symlink' | 'other';
+  // Disk usage of a directory, length of a file
+  sizeBytes: number;
+  modifiedAt: string | null;
+}
+
+export interface DataDirListing {
+  root: string;
+  // Relative to root, empty for the data directory itself
+  path: string;
+  entries: DataDirEntry[];
+  totalBytes: number;
+  // Read from the volume through a helper container while the database is stopped
+  fromVolume: boolean;
+}
+
+export interface MetricsPoint {
+  timestamp: string;
+  cpuPercent: number;
+  memoryUsedBytes: number;
+  connections: number | null;
+  diskBytes: number | null;
+  samples: number;
+}
+
+export type AlertCondition =
+  | { kind: 'memoryPercent'; percent: number }
+  | { kind: 'cpuPercent'; percent: number }
+  | { kind: 'diskBytes'; bytes: number }
+  | { kind: 'connectionsPercent'; percent: number }
+  | { kind: 'restarted' }
+  | { kind: 'integrityFailed' };
+
+export interface AlertRule {
+  /** Empty when creating a rule */
+  id: string;
+  databaseId: string;
+  condition: AlertCondition;
+  /** Seconds the condition must hold before the alert fires */
+  durationSecs: number;
+  notify: boolean;
+  webhookUrl: string | null;
+  enabled: boolean;
+}
+
+export interface AlertEvent {
+  id: string;
+  ruleId: string;
+  databaseId: string;
+  databaseName: string;
+  message: string;
+  value: number | null;
+  firedAt: string;
+  resolvedAt: string | null;
+}
+
+export interface LifecycleEvent {
+  kind: 'started' | 'restarted' | 'exited';
+  at: string;
+  exitCode: number | null;
+  oomKilled: boolean;
+  restartCount: number;
+  reason: string | null;
+}
+
+export interface UptimeReport {
+  running: boolean;
+  startedAt: string | null;
+  uptimeSecs: number | null;
+  restartCount: number;
+  lastExit: LifecycleEvent | null;
+  /** Newest first */
+  events: LifecycleEvent[];
+}
+
+export interface ContainerDetails {
+  container: Container;
+  uptime: UptimeReport;
+  // Set while the ports are published to the local network
+  lanWarning: string | null;
+  tunnels: TunnelStatus[];
+}
+
+export type EnvDriftKind = 'missing' | 'changed' | 'unexpected';
+
+export interface LiveEnvVar {
+  key: string;
+  /** Null for credentials */
+  value: string | null;
+  /** Defined by the image, not by the app */
+  fromImage: boolean;
+}
+
+export interface EnvVarDrift {
+  key: string;
+  kind: EnvDriftKind;
+  /** Values are left out for credentials */
+  expected: string | null;
+  actual: string | null;
+  secret: boolean;
+}
+
+export interface EnvDriftReport {
+  variables: LiveEnvVar[];
+  drift: EnvVarDrift[];
+  inSync: boolean;
+}
+
+/**
+ * SSH local forward to a database on a remote Docker host
+ */
+export interface TunnelDefinition {
+  /** Empty when saving a new tunnel */
+  id: string;
+  databaseId: string;
+  /** `host` or `user@host`, aliases from ~/.ssh/config work too */
+  sshHost: string;
+  sshPort: number;
+  identityFile?: string | null;
+  /** Port on 127.0.0.1 clients connect to */
+  localPort: number;
+  remoteHost: string;
+  remotePort: number;
+  autoStart: boolean;
+}
+
+export type TunnelState =
+  | 'stopped'
+  | 'connecting'
+  | 'connected'
+  | 'reconnecting';
+
+export interface TunnelStatus {
+  tunnelId: string;
+  databaseId: string;
+  state: TunnelState;
+  localPort: number;
+  /** Failed attempts since the tunnel was last connected */
+  attempts: number;
+  lastError: string | null;
+  connectedAt: string | null;
+}
+
+export type PoolerKind = 'pgbouncer' | 'proxysql';
+
+/**
+ * Container deployed next to a database, an admin tool or a connection pooler
+ * Serialized with the backend field names
+ */
+export interface CompanionContainer {
+  id: string;
+  kind: string;
+  name: string;
+  database_id: string;
+  container_id: string | null;
+  status: string;
+  port: number;
+  /** Page of a web tool, connection URL without the password for a pooler */
+  url: string;
+  created_at: string;
+}
+
+/**
+ * Whether the SQL console refuses statements that change data
+ */
+export interface SqlGuardStatus {
+  enforced: boolean;
+  /** Writes were allowed for this database until the app quits */
+  unlocked: boolean;
+}
+
+/**
+ * Output of a script run in the SQL console, the rows of its last statement returning any
+ */
+export interface SqlConsoleResult {
+  columns: string[];
+  rows: string[][];
+  statements: number;
+  /** Ran in a read-only transaction */
+  readOnly: boolean;
+  elapsedMs: number;
+}
+
+export type QueryExportFormat = 'csv' | 'json';
+
+export interface QueryExportResult {
+  path: string;
+  rows: number;
+  bytesWritten: number;
+}
+
+export interface QueryExportProgress {
+  containerId: string;
+  rowsWritten: number;
+  bytesWritten: number;
+  done: boolean;
+}
+
+export interface LogicalReplicationRequest {
+  enabled: boolean;
+  maxReplicationSlots?: number;
+  maxWalSenders?: number;
+}
+
+export interface TunedSetting {
+  name: string;
+  value: string;
+}
+
+export interface TuningPreview {
+  tuning: TuningSettings;
+  budgetMb: number;
+  settings: TunedSetting[];
+}
+
+export interface LogicalReplicationStatus {
+  walLevel: string;
+  maxReplicationSlots: number;
+  maxWalSenders: number;
+  pendingRestart: boolean;
+}
+
+export interface Publication {
+  name: string;
+  allTables: boolean;
+  /** "schema.table" of each published table */
+  tables: string[];
+}
+
+export interface ReplicationSlot {
+  name: string;
+  plugin?: string | null;
+  slotType: string;
+  database?: string | null;
+  active: boolean;
+  restartLsn?: string | null;
+  confirmedFlushLsn?: string | null;
+  /** WAL kept for the slot, grows while nothing consumes it */
+  retainedWalBytes?: number | null;
+}
+
+export interface SqlJobRun {
+  startedAt: string;
+  finishedAt: string;
+  success: boolean;
+  statements: number;
+  error?: string | null;
+  /** Started by hand rather than by the scheduler */
+  manual: boolean;
+}
+
+/**
+ * SQL script run against one database on a five-field cron schedule
+ */
+export interface SqlJob {
+  /** Empty when saving a new job */
+  id: string;
+  name: string;
+  databaseId: string;
+  sql: string;
+  cron: string;
+  enabled: boolean;
+  notifyOnFailure: boolean;
+  createdAt: string;
+  lastRunAt?: string | null;
+  lastError?: string | null;
+  /** Latest runs, oldest first */
+  runs: SqlJobRun[];
+}
+
+export type SnippetKind = 'sql' | 'command';
+
+/**
+ * Saved SQL query or shell command with `{{variable}}` placeholders
+ * Scoped to one container (`databaseId`) or to every database of a type (`dbType`)
+ */
+export interface Snippet {
+  /** Empty when saving a new snippet */
+  id: string;
+  name: string;
+  kind: SnippetKind;
+  body: string;
+  databaseId: string | null;
+  dbType: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/** Variables filled from the database when no value is given */
+export const BUILTIN_SNIPPET_VARIABLES = ['container', 'database', 'username'];
+
+/**
+ * Placeholders of a snippet the user has to fill in, in order of first use
+ */
+export function snippetVariables(body: string): string[] {
+  const names: string[] = [];
+  for (const match of body.matchAll(/\{\{\s*(\w+)\s*\}\}/g)) {
+    const name = match[1];
+    if (!names.includes(name) && !BUILTIN_SNIPPET_VARIABLES.includes(name)) {
+      names.push(name);
+    }
+  }
+  return names;
+}
+
+export type ShareTool = 'cloudflared' | 'ngrok' | 'bore';
+
+export type ShareState = 'starting' | 'active' | 'stopped';
+
+/**
+ * Database exposed on a public address through a tunneling tool
+ */
+export interface ShareStatus {
+  databaseId: string;
+  tool: ShareTool;
+  state: ShareState;
+  /** Address the collaborator connects to, e.g. `bore.pub:41234` */
+  publicAddress: string | null;
+  startedAt: string | null;
+  /** The tunnel is closed at this time */
+  expiresAt: string | null;
+  lastError: string | null;
+}
+
+/**
+ * Where the image of a new container comes from, nothing is pulled to find out
+ */
+export interface ImageResolution {
+  reference: string;
+  /** `os/arch` of the local copy, null until the image is pulled */
+  localPlatform: string | null;
+  hostPlatform: string | null;
+  pullRequired: boolean;
+  /** The local copy is built for another architecture */
+  emulated: boolean;
+}
+
+/**
+ * What creating a container would run and create
+ */
+export interface CreationPreview {
+  /** Arguments after `docker` as creation passes them, with the password masked */
+  args: string[];
+  /** Copyable command line of the same arguments */
+  command: string;
+  image: ImageResolution;
+  volumes: { name: string; path: string; exists: boolean }[];
+  network: string;
+  ports: PortMapping[];
+}
+
+export type UpdateField =
+  | 'name'
+  | 'port'
+  | 'persistData'
+  | 'enableTls'
+  | 'exposeToLan'
+  | 'credentials'
+  | 'version'
+  | 'maxConnections'
+  | 'extraArgs'
+  | 'region'
+  | 'tuning';
+
+/**
+ * recreate: the container is created again, live: applied to the running
+ * engine, stored: saved for the next recreation
+ */
+export type UpdateEffect = 'recreate' | 'live' | 'stored';
+
+/**
+ * What saving an edit would do to a database
+ */
+export interface UpdatePlan {
+  changes: { field: UpdateField; effect: UpdateEffect }[];
+  needsRecreation: boolean;
+  /** Recreated without a volume, or its volume is removed */
+  dataLoss: boolean;
+  /** Credentials change in the engine, which has to be running */
+  requiresRunning: boolean;
+}
+
+export interface ContainerGroup {
+  name: string;
+  /** Container ids in start order */
+  order: string[];
+  createdAt: string;
+  /** Network shared by the members of a stack */
+  network: string | null;
+  /** Container ids each member waits for, started first and stopped after */
+  dependencies: Record<string, string[]>;
+}
+
+export interface StackRequest {
+  name: string;
+  /** Created and started in this order */
+  services: { service: string; request: DockerRunRequest }[];
+}
+
+export interface TableExportProgress {
+  containerId: string;
+  table: string;
+  bytesWritten: number;
+  done: boolean;
+}
+
+export type CopyDirection = 'toContainer' | 'fromContainer';
+
+export interface FileCopyResult {
+  direction: CopyDirection;
+  source: string;
+  destination: string;
+  bytesCopied: number;
+}
+
+export interface FileCopyProgress {
+  containerId: string;
+  direction: CopyDirection;
+  source: string;
+  bytesCopied: number;
+  /** Unknown when copying out of a stopped container */
+  totalBytes: number | null;
+  done: boolean;
+}
+
+export type IntegrityStatus = 'passed' | 'failed' | 'error';
+
+export interface IntegrityCheckRun {
+  id: string;
+  databaseId: string;
+  databaseName: string;
+  /** Checker that ran, e.g. pg_amcheck */
+  tool: string;
+  status: IntegrityStatus;
+  /** Output lines describing what is damaged */
+  problems: string[];
+  /** End of the checker's output */
+  output: string;
+  startedAt: string;
+  finishedAt: string;
+  /** Null for manual runs */
+  scheduleId: string | null;
+}
+
+export interface BulkResult {
+  containerId: string;
+  success: boolean;
+  error?: string;
+}
 
 const containerFromJSON = (data: any): Container => ({
   id: data.id,
@@ -8,6 +750,7 @@ const containerFromJSON = (data: any): Container => ({
   dbType: data.db_type,
   version: data.version,
   status: data.status,
+  exitCode: data.exit_code ?? undefined,
   port: data.port,
   createdAt: new Date(data.created_at),
   maxConnections: data.max_connections,
@@ -17,6 +760,39 @@ const containerFromJSON = (data: any): Container => ({
   databaseName: data.stored_database_name,
   persistData: data.stored_persist_data,
   enableAuth: data.stored_enable_auth,
+  enableTls: data.stored_enable_tls ?? false,
+  caCertPath: data.ca_cert_path ?? undefined,
+  project: data.project ?? undefined,
+  tags: data.tags ?? [],
+  favorite: data.favorite ?? false,
+  lastStartedAt: data.last_started_at
+    ? new Date(data.last_started_at)
+    : undefined,
+  lastStoppedAt: data.last_stopped_at
+    ? new Date(data.last_stopped_at)
+    : undefined,
+  updatesAvailable: data.updates_available ?? false,
+  storageState: data.storage_state ?? 'ok',
+  autoStart: data.auto_start ?? false,
+  keepRunningOnExit: data.keep_running_on_exit ?? false,
+  exposeToLan: data.stored_expose_to_lan ?? false,
+  framework: data.framework ?? undefined,
+  locale: data.locale ?? undefined,
+  extraArgs: data.extra_args ?? [],
+  stopTimeout: data.stop_timeout ?? undefined,
+  region: data.region ?? undefined,
+  tuning: data.tuning ?? undefined,
+  watchdog: data.watchdog ?? undefined,
+  host: data.host ?? undefined,
+  setupWarnings: data.setup_warnings ?? [],
+});
+
+const groupFromJSON = (data: any): ContainerGroup => ({
+  name: data.name,
+  order: data.order,
+  createdAt: data.created_at,
+  network: data.network ?? null,
+  dependencies: data.dependencies ?? {},
 });
 
 /**
@@ -38,17 +814,91 @@ export const databasesApi = {
     return containerFromJSON(result);
   },
 
+  /**
+   * Validate a creation request and show what it would run, without creating anything
+   */
+  async previewCreate(request: DockerRunRequest): Promise<CreationPreview> {
+    return invoke<CreationPreview>('preview_container_creation', { request });
+  },
+
+  /**
+   * Create linked containers on a shared network as one group
+   * Each service is reachable from the others as `<stack>-<service>`
+   */
+  async createStack(request: StackRequest): Promise<ContainerGroup> {
+    const result = await invoke<unknown>('create_stack', { request });
+    return groupFromJSON(result);
+  },
+
+  async getGroups(): Promise<ContainerGroup[]> {
+    const result = await invoke<unknown[]>('get_groups');
+    return result.map(groupFromJSON);
+  },
+
+  /**
+   * Set the members of its group a container waits for when the group starts
+   * Rejects dependencies that would form a cycle
+   */
+  async setContainerDependencies(
+    id: string,
+    dependsOn: string[],
+  ): Promise<ContainerGroup> {
+    const result = await invoke<unknown>('set_container_dependencies', {
+      containerId: id,
+      dependsOn,
+    });
+    return groupFromJSON(result);
+  },
+
+  /**
+   * Check a container name before creating or renaming
+   * Rejects with INVALID_NAME and a suggested name when it cannot be used
+   */
+  async checkName(name: string, containerId?: string): Promise<void> {
+    await invoke<void>('check_container_name', { name, containerId });
+  },
+
   /**
    * Update an existing database container from generic Docker run request
    * Uses provider-generated Docker args
+   * Fails with DATA_LOSS when the update deletes data, unless `force` confirms it
    */
   async update(
     containerId: string,
     request: DockerRunRequest,
+    force = false,
   ): Promise<Container> {
     const result = await invoke<unknown>('update_container_from_docker_args', {
       containerId,
       request,
+      force,
+    });
+    return containerFromJSON(result);
+  },
+
+  /**
+   * Which changes of an edit recreate the container, lose data or apply live
+   */
+  async previewUpdate(
+    containerId: string,
+    request: DockerRunRequest,
+  ): Promise<UpdatePlan> {
+    return invoke<UpdatePlan>('preview_update', { containerId, request });
+  },
+
+  /**
+   * Live environment of the container compared with its stored configuration
+   */
+  async getEnvDrift(id: string): Promise<EnvDriftReport> {
+    return invoke<EnvDriftReport>('get_env_drift', { containerId: id });
+  },
+
+  /**
+   * Recreate the container from its stored configuration, keeping its data volume
+   */
+  async reapplyConfig(id: string): Promise<Container> {
+    const result = await invoke<unknown>('reapply_container_config', {
+      containerId: id,
     });
     return containerFromJSON(result);
   },
@@ -61,6 +911,47 @@ export const databasesApi = {
     return result.map(containerFromJSON);
   },
 
+  /**
+   * Container with its uptime, restart count and why it last exited
+   */
+  async getDetails(id: string): Promise<ContainerDetails> {
+    const result = await invoke<{
+      database: unknown;
+      uptime: UptimeReport;
+      lanWarning: string | null;
+      tunnels: TunnelStatus[];
+    }>('get_container_details', { containerId: id });
+    return {
+      container: containerFromJSON(result.database),
+      uptime: result.uptime,
+      lanWarning: result.lanWarning,
+      tunnels: result.tunnels,
+    };
+  },
+
+  /**
+   * Get a sorted page of database containers matching a filter
+   */
+  async query(
+    filter: DatabaseFilter,
+    options: {
+      sort?: DatabaseSort;
+      offset?: number;
+      limit?: number;
+    } = {},
+  ): Promise<DatabasePage> {
+    const result = await invoke<{
+      items: unknown[];
+      total: number;
+      offset: number;
+    }>('query_databases', { filter, ...options });
+    return {
+      items: result.items.map(containerFromJSON),
+      total: result.total,
+      offset: result.offset,
+    };
+  },
+
   /**
    * Get a database container by ID
    */
@@ -88,10 +979,30 @@ export const databasesApi = {
   },
 
   /**
-   * Remove a database container
+   * Remove a database container, moving it to the trash
    */
-  async remove(id: string): Promise<void> {
-    await invoke('remove_container', { containerId: id });
+  async remove(id: string, keepSnapshot = false): Promise<void> {
+    await invoke('remove_container', { containerId: id, keepSnapshot });
+  },
+
+  /**
+   * Restore a removed database container from the trash
+   */
+  async restoreFromTrash(id: string): Promise<Container> {
+    const result = await invoke<unknown>('restore_from_trash', {
+      containerId: id,
+    });
+    return containerFromJSON(result);
+  },
+
+  /**
+   * Start, stop or remove several database containers at once
+   */
+  async bulk(ids: string[], action: BulkAction): Promise<BulkResult[]> {
+    return invoke<BulkResult[]>('bulk_operation', {
+      containerIds: ids,
+      action,
+    });
   },
 
   /**
@@ -101,4 +1012,696 @@ export const databasesApi = {
     const result = await invoke<unknown[]>('sync_containers_with_docker');
     return result.map(containerFromJSON);
   },
+
+  /**
+   * Recreate a missing data volume or turn persistence off
+   */
+  async repairStorage(
+    id: string,
+    repair: 'recreateVolume' | 'disablePersistence',
+  ): Promise<Container> {
+    const result = await invoke<unknown>('repair_storage', {
+      containerId: id,
+      repair,
+    });
+    return containerFromJSON(result);
+  },
+
+  /**
+   * Database containers labelled by the app that the current store does not know
+   */
+  async listOrphans(): Promise<OrphanContainer[]> {
+    return invoke<OrphanContainer[]>('list_orphans');
+  },
+
+  /**
+   * Adopt orphan containers into the store or remove them, volumes are kept
+   */
+  async reconcileOrphans(
+    actions: { name: string; resolution: OrphanResolution }[],
+  ): Promise<BulkResult[]> {
+    return invoke<BulkResult[]>('reconcile_orphans', { actions });
+  },
+
+  /**
+   * Tables and views of a running PostgreSQL, MySQL or MariaDB database
+   */
+  async listTables(id: string): Promise<TableInfo[]> {
+    return invoke<TableInfo[]>('list_tables', { containerId: id });
+  },
+
+  /**
+   * One page of a table's rows, values ordered like the returned columns
+   */
+  async fetchTableRows(
+    id: string,
+    query: TableRowsQuery,
+  ): Promise<TableRowsPage> {
+    return invoke<TableRowsPage>('fetch_table_rows', {
+      containerId: id,
+      query,
+    });
+  },
+
+  /**
+   * Tables, keys and indexes of a SQL database, defaults to the one created with it
+   */
+  async getSchema(id: string, database?: string): Promise<DatabaseSchema> {
+    return invoke<DatabaseSchema>('get_schema', { containerId: id, database });
+  },
+
+  /**
+   * Schema of a SQL database as Mermaid or DBML text
+   */
+  async exportSchema(
+    id: string,
+    format: SchemaFormat,
+    database?: string,
+  ): Promise<string> {
+    return invoke<string>('export_schema', {
+      containerId: id,
+      database,
+      format,
+    });
+  },
+
+  /**
+   * Write a table or collection to a CSV file, progress arrives on `table-export-progress`
+   */
+  async exportTable(
+    id: string,
+    table: string,
+    path: string,
+    options?: TableExportOptions,
+  ): Promise<TableExportResult> {
+    return invoke<TableExportResult>('export_table', {
+      containerId: id,
+      table,
+      path,
+      options,
+    });
+  },
+
+  /**
+   * Load a local CSV file into an existing table, data problems come back in the report
+   * Fails with SETTING_CHANGE when MySQL refuses local loads, unless the mapping's
+   * `enableLocalInfile` confirms turning them on for the import
+   */
+  async importCsv(
+    id: string,
+    table: string,
+    path: string,
+    mapping?: CsvImportMapping,
+  ): Promise<CsvImportReport> {
+    return invoke<CsvImportReport>('import_csv', {
+      containerId: id,
+      table,
+      path,
+      mapping,
+    });
+  },
+
+  /**
+   * Run the engine's load generator in a helper container and keep the result
+   */
+  async runBenchmark(
+    id: string,
+    params?: BenchmarkParams,
+  ): Promise<BenchmarkRun> {
+    return invoke<BenchmarkRun>('run_benchmark', { containerId: id, params });
+  },
+
+  async listBenchmarks(id: string): Promise<BenchmarkRun[]> {
+    return invoke<BenchmarkRun[]>('list_benchmarks', { containerId: id });
+  },
+
+  async deleteBenchmark(benchmarkId: string): Promise<void> {
+    await invoke('delete_benchmark', { benchmarkId });
+  },
+
+  /**
+   * Log statements slower than the threshold, 0 logs every statement
+   */
+  async enableSlowLog(id: string, thresholdMs: number): Promise<void> {
+    await invoke('enable_slow_log', { containerId: id, thresholdMs });
+  },
+
+  async disableSlowLog(id: string): Promise<void> {
+    await invoke('disable_slow_log', { containerId: id });
+  },
+
+  /**
+   * Slow statements logged since the last reset, newest first
+   */
+  async fetchSlowLog(id: string, limit?: number): Promise<SlowQuery[]> {
+    return invoke<SlowQuery[]>('fetch_slow_log', { containerId: id, limit });
+  },
+
+  async resetSlowLog(id: string): Promise<void> {
+    await invoke('reset_slow_log', { containerId: id });
+  },
+
+  /**
+   * Size of every database and table, cached for a few minutes unless refreshed
+   */
+  async getStorageBreakdown(
+    id: string,
+    refresh = false,
+  ): Promise<StorageBreakdown> {
+    return invoke<StorageBreakdown>('get_storage_breakdown', {
+      containerId: id,
+      refresh,
+    });
+  },
+
+  /**
+   * Files and sizes in the data directory, largest first, without changing anything
+   */
+  async browseDataDir(id: string, path = ''): Promise<DataDirListing> {
+    return invoke<DataDirListing>('browse_data_dir', {
+      containerId: id,
+      path,
+    });
+  },
+
+  /**
+   * Copy a host file or directory into the container, progress arrives on `file-copy-progress`
+   */
+  async copyToContainer(
+    id: string,
+    localPath: string,
+    containerPath: string,
+  ): Promise<FileCopyResult> {
+    return invoke<FileCopyResult>('copy_to_container', {
+      containerId: id,
+      localPath,
+      containerPath,
+    });
+  },
+
+  /**
+   * Copy a file or directory out of the container, progress arrives on `file-copy-progress`
+   */
+  async copyFromContainer(
+    id: string,
+    containerPath: string,
+    localPath: string,
+  ): Promise<FileCopyResult> {
+    return invoke<FileCopyResult>('copy_from_container', {
+      containerId: id,
+      containerPath,
+      localPath,
+    });
+  },
+
+  /**
+   * Export all containers and settings to a JSON bundle
+   */
+  async exportConfig(path: string, includeSecrets = false): Promise<void> {
+    await invoke('export_config', { path, includeSecrets });
+  },
+
+  /**
+   * StatefulSet, Service, Secret and volume claims running a database in Kubernetes, as YAML
+   */
+  async exportKubernetes(id: string): Promise<string> {
+    return invoke<string>('export_kubernetes', { containerId: id });
+  },
+
+  /**
+   * Archive the configuration, a snapshot of the data and a manifest in one file
+   */
+  async exportBundle(id: string, path: string): Promise<BundleExport> {
+    return invoke<BundleExport>('export_bundle', { containerId: id, path });
+  },
+
+  /**
+   * Recreate a database with its data from a bundle, moving off taken names and ports
+   */
+  async importBundle(path: string): Promise<BundleImport> {
+    const result = await invoke<{
+      database: unknown;
+      manifest: BundleManifest;
+      renamedFrom: string | null;
+      remappedPorts: BundleImport['remappedPorts'];
+    }>('import_bundle', { path });
+    return {
+      database: containerFromJSON(result.database),
+      manifest: result.manifest,
+      renamedFrom: result.renamedFrom,
+      remappedPorts: result.remappedPorts,
+    };
+  },
+
+  /**
+   * Recreate containers from a JSON bundle
+   */
+  async importConfig(path: string): Promise<ImportReport> {
+    const result = await invoke<{
+      imported: unknown[];
+      skipped: ImportReport['skipped'];
+      warnings: ImportReport['warnings'];
+      settingsApplied: boolean;
+    }>('import_config', { path });
+    return {
+      imported: result.imported.map(containerFromJSON),
+      skipped: result.skipped,
+      warnings: result.warnings,
+      settingsApplied: result.settingsApplied,
+    };
+  },
+
+  /**
+   * Equivalent `docker run` command, also copied to the clipboard
+   */
+  async copyDockerRunCommand(
+    id: string,
+    redactPassword = true,
+  ): Promise<string> {
+    return invoke<string>('get_docker_run_command', {
+      containerId: id,
+      redactPassword,
+    });
+  },
+
+  /**
+   * .env block with the connection credentials, using a framework's variable names
+   */
+  async getEnvSnippet(
+    id: string,
+    preset: EnvPreset = 'generic',
+  ): Promise<string> {
+    return invoke<string>('get_env_snippet', { containerId: id, preset });
+  },
+
+  /**
+   * Framework presets offered when creating a database
+   */
+  async listFrameworkPresets(): Promise<FrameworkPreset[]> {
+    return invoke<FrameworkPreset[]>('list_framework_presets');
+  },
+
+  /**
+   * Framework config for a database, defaults to the preset it was created for
+   */
+  async getFrameworkSnippet(id: string, framework?: Framework): Promise<string> {
+    return invoke<string>('get_framework_snippet', {
+      containerId: id,
+      framework,
+    });
+  },
+
+  /**
+   * SSH tunnels, only those of one database when an id is given
+   */
+  async listTunnels(id?: string): Promise<TunnelDefinition[]> {
+    return invoke<TunnelDefinition[]>('list_tunnels', { containerId: id });
+  },
+
+  /**
+   * Live state of the tunnels, only those of one database when an id is given
+   */
+  async getTunnelStatuses(id?: string): Promise<TunnelStatus[]> {
+    return invoke<TunnelStatus[]>('get_tunnel_statuses', { containerId: id });
+  },
+
+  /**
+   * Create or update a tunnel, a running tunnel is reopened with the new settings
+   */
+  async saveTunnel(tunnel: TunnelDefinition): Promise<TunnelDefinition> {
+    return invoke<TunnelDefinition>('save_tunnel', { tunnel });
+  },
+
+  /**
+   * Close and forget a tunnel
+   */
+  async deleteTunnel(tunnelId: string): Promise<void> {
+    await invoke<void>('delete_tunnel', { tunnelId });
+  },
+
+  /**
+   * Open a tunnel, it reconnects on its own until stopped
+   */
+  async startTunnel(tunnelId: string): Promise<TunnelStatus> {
+    return invoke<TunnelStatus>('start_tunnel', { tunnelId });
+  },
+
+  async stopTunnel(tunnelId: string): Promise<TunnelStatus> {
+    return invoke<TunnelStatus>('stop_tunnel', { tunnelId });
+  },
+
+  /**
+   * Run SQL typed in the console, writes are refused while the guard is locked
+   */
+  async runSql(id: string, sql: string): Promise<SqlConsoleResult> {
+    return invoke<SqlConsoleResult>('run_sql', { containerId: id, sql });
+  },
+
+  /**
+   * Write the rows of one query to a file, progress arrives on `query-export-progress`
+   */
+  async exportQuery(
+    id: string,
+    sql: string,
+    path: string,
+    format?: QueryExportFormat,
+  ): Promise<QueryExportResult> {
+    return invoke<QueryExportResult>('export_query', {
+      containerId: id,
+      sql,
+      path,
+      format,
+    });
+  },
+
+  async getLogicalReplication(id: string): Promise<LogicalReplicationStatus> {
+    return invoke<LogicalReplicationStatus>('get_logical_replication', {
+      containerId: id,
+    });
+  },
+
+  /**
+   * Turn logical replication on or off, the database restarts to apply it
+   */
+  async setLogicalReplication(
+    id: string,
+    request: LogicalReplicationRequest,
+  ): Promise<LogicalReplicationStatus> {
+    return invoke<LogicalReplicationStatus>('set_logical_replication', {
+      containerId: id,
+      request,
+    });
+  },
+
+  async listPublications(id: string): Promise<Publication[]> {
+    return invoke<Publication[]>('list_publications', { containerId: id });
+  },
+
+  /**
+   * Publish tables given as "schema.table", every table when `tables` is empty
+   */
+  async createPublication(
+    id: string,
+    name: string,
+    tables?: string[],
+  ): Promise<Publication[]> {
+    return invoke<Publication[]>('create_publication', {
+      containerId: id,
+      name,
+      tables,
+    });
+  },
+
+  async dropPublication(id: string, name: string): Promise<Publication[]> {
+    return invoke<Publication[]>('drop_publication', { containerId: id, name });
+  },
+
+  async listReplicationSlots(id: string): Promise<ReplicationSlot[]> {
+    return invoke<ReplicationSlot[]>('list_replication_slots', {
+      containerId: id,
+    });
+  },
+
+  async createReplicationSlot(
+    id: string,
+    name: string,
+    plugin?: string,
+  ): Promise<ReplicationSlot[]> {
+    return invoke<ReplicationSlot[]>('create_replication_slot', {
+      containerId: id,
+      name,
+      plugin,
+    });
+  },
+
+  async dropReplicationSlot(
+    id: string,
+    name: string,
+  ): Promise<ReplicationSlot[]> {
+    return invoke<ReplicationSlot[]>('drop_replication_slot', {
+      containerId: id,
+      name,
+    });
+  },
+
+  /**
+   * Settings a preset gives a database of `dbType` on a Docker host, measured
+   * now. Put the returned tuning in the creation request to apply them
+   */
+  async previewTuning(
+    dbType: string,
+    preset: TuningPreset,
+    hostId?: string,
+  ): Promise<TuningPreview> {
+    return invoke<TuningPreview>('get_tuning_preview', {
+      dbType,
+      preset,
+      hostId,
+    });
+  },
+
+  /**
+   * Measure the host again and recreate the container with the new settings,
+   * keeping its preset unless another one is given
+   * Fails with DATA_LOSS when the data is not persisted, unless `force`
+   */
+  async retuneContainer(
+    id: string,
+    preset?: TuningPreset,
+    force = false,
+  ): Promise<Container> {
+    const result = await invoke<unknown>('retune_container', {
+      containerId: id,
+      preset,
+      force,
+    });
+    return containerFromJSON(result);
+  },
+
+  async listSqlJobs(id?: string): Promise<SqlJob[]> {
+    return invoke<SqlJob[]>('list_sql_jobs', { containerId: id });
+  },
+
+  /**
+   * Create a job when its id is empty, otherwise update it
+   */
+  async saveSqlJob(job: SqlJob): Promise<SqlJob> {
+    return invoke<SqlJob>('save_sql_job', { job });
+  },
+
+  async deleteSqlJob(jobId: string): Promise<void> {
+    return invoke<void>('delete_sql_job', { jobId });
+  },
+
+  /**
+   * Run a job now, resolves once the run is recorded
+   */
+  async runSqlJob(jobId: string): Promise<SqlJob> {
+    return invoke<SqlJob>('run_sql_job', { jobId });
+  },
+
+  async getSqlGuard(id: string): Promise<SqlGuardStatus> {
+    return invoke<SqlGuardStatus>('get_sql_guard', { containerId: id });
+  },
+
+  /**
+   * Allow writes from the console until the app quits, or lock it again
+   */
+  async setSqlWritesUnlocked(
+    id: string,
+    unlocked: boolean,
+  ): Promise<SqlGuardStatus> {
+    return invoke<SqlGuardStatus>('set_sql_writes_unlocked', {
+      containerId: id,
+      unlocked,
+    });
+  },
+
+  /**
+   * Saved queries and commands of a container and of its database type
+   */
+  async listSnippets(id: string): Promise<Snippet[]> {
+    return invoke<Snippet[]>('list_snippets', { containerId: id });
+  },
+
+  async saveSnippet(snippet: Snippet): Promise<Snippet> {
+    return invoke<Snippet>('save_snippet', { snippet });
+  },
+
+  async deleteSnippet(snippetId: string): Promise<void> {
+    await invoke<void>('delete_snippet', { snippetId });
+  },
+
+  /**
+   * Text of a snippet for a container, with its variables filled in
+   */
+  async renderSnippet(
+    snippetId: string,
+    id: string,
+    values: Record<string, string> = {},
+  ): Promise<string> {
+    return invoke<string>('render_snippet', {
+      snippetId,
+      containerId: id,
+      values,
+    });
+  },
+
+  /**
+   * Companions of a database, their status is synced with Docker first
+   */
+  async getCompanions(id: string): Promise<CompanionContainer[]> {
+    return invoke<CompanionContainer[]>('get_companions', { databaseId: id });
+  },
+
+  /**
+   * Deploy a companion on the database network, config generated from its stored credentials
+   */
+  async deployCompanion(
+    id: string,
+    kind: string,
+  ): Promise<CompanionContainer> {
+    return invoke<CompanionContainer>('deploy_companion', {
+      databaseId: id,
+      kind,
+    });
+  },
+
+  async removeCompanion(companionId: string): Promise<void> {
+    await invoke<void>('remove_companion', { companionId });
+  },
+
+  async getShareStatus(id: string): Promise<ShareStatus> {
+    return invoke<ShareStatus>('get_share_status', { containerId: id });
+  },
+
+  /**
+   * Expose a running database on a public address, an hour when no duration is given
+   */
+  async startShare(id: string, minutes?: number): Promise<ShareStatus> {
+    return invoke<ShareStatus>('start_share', { containerId: id, minutes });
+  },
+
+  async stopShare(id: string): Promise<ShareStatus> {
+    return invoke<ShareStatus>('stop_share', { containerId: id });
+  },
+
+  /**
+   * External database clients installed on this machine
+   */
+  async listClients(): Promise<DatabaseClient[]> {
+    return invoke<DatabaseClient[]>('list_database_clients');
+  },
+
+  /**
+   * Open a container in an external client
+   * Resolves to true when the connection string was copied to paste instead
+   */
+  async openInClient(id: string, client: DatabaseClient): Promise<boolean> {
+    return invoke<boolean>('open_in_client', { containerId: id, client });
+  },
+
+  /**
+   * Open the system terminal with a shell inside the container
+   */
+  async openTerminal(id: string): Promise<void> {
+    await invoke('open_container_terminal', { containerId: id });
+  },
+
+  /**
+   * Open the metrics dashboard, for one container or all of them
+   */
+  async openMetrics(id?: string): Promise<void> {
+    await invoke('open_metrics_window', { containerId: id ?? null });
+  },
+
+  /**
+   * Recorded metrics of a container, averaged per bucket when `bucketSecs` is set
+   */
+  async getMetricsHistory(
+    id: string,
+    range: { since?: string; until?: string; bucketSecs?: number } = {},
+  ): Promise<MetricsPoint[]> {
+    return invoke<MetricsPoint[]>('get_metrics_history', {
+      containerId: id,
+      ...range,
+    });
+  },
+
+  async listAlertRules(containerId?: string): Promise<AlertRule[]> {
+    return invoke<AlertRule[]>('list_alert_rules', { containerId });
+  },
+
+  /**
+   * Create a rule when its id is empty, update it otherwise
+   */
+  async saveAlertRule(rule: AlertRule): Promise<AlertRule> {
+    return invoke<AlertRule>('save_alert_rule', { rule });
+  },
+
+  async deleteAlertRule(ruleId: string): Promise<void> {
+    return invoke<void>('delete_alert_rule', { ruleId });
+  },
+
+  /**
+   * Fired alerts, newest first
+   */
+  async listAlertHistory(
+    containerId?: string,
+    limit?: number,
+  ): Promise<AlertEvent[]> {
+    return invoke<AlertEvent[]>('list_alert_history', { containerId, limit });
+  },
+
+  /**
+   * Run the engine's consistency checker in a running container at low priority
+   * `installMissing` lets pg_amcheck create the amcheck extension where it is missing
+   */
+  async runIntegrityCheck(
+    id: string,
+    installMissing?: boolean,
+  ): Promise<IntegrityCheckRun> {
+    return invoke<IntegrityCheckRun>('run_integrity_check', {
+      containerId: id,
+      installMissing,
+    });
+  },
+
+  /**
+   * Integrity checks, newest first
+   */
+  async listIntegrityChecks(
+    containerId?: string,
+    limit?: number,
+  ): Promise<IntegrityCheckRun[]> {
+    return invoke<IntegrityCheckRun[]>('list_integrity_checks', {
+      containerId,
+      limit,
+    });
+  },
+
+  /**
+   * One page of container logs, oldest first
+   * Pass nextCursor back to read the following page or poll for new lines
+   */
+  async getLogsPage(id: string, query: LogQuery = {}): Promise<LogPage> {
+    return invoke<LogPage>('get_container_logs_page', {
+      containerId: id,
+      query,
+    });
+  },
+
+  /**
+   * One page of container logs split into entries with their severity
+   * Multi-line messages come back as a single entry
+   */
+  async getParsedLogs(
+    id: string,
+    query: LogQuery = {},
+  ): Promise<ParsedLogPage> {
+    return invoke<ParsedLogPage>('get_parsed_logs', {
+      containerId: id,
+      query,
+    });
+  },
 };
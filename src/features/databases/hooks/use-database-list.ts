@@ -28,11 +28,12 @@ export function useDatabaseList() {
   }, []);
 
   /**
-   * Synchronize database containers with Docker
+   * Refresh the list from backend state
+   * Docker reconciliation runs in the backend sync worker, so this is a cheap read
    */
   const sync = useCallback(async () => {
     try {
-      const data = await databasesApi.sync();
+      const data = await databasesApi.getAll();
       setContainers(data);
     } catch (error) {
       console.error('Error syncing containers:', error);
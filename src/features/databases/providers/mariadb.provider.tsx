@@ -144,34 +144,42 @@ export class MariaDBDatabaseProvider implements DatabaseProvider {
     ];
   }
 
-  getAdvancedFields(): FieldGroup[] {
+  getAdvancedFields({
+    isEditMode = false,
+  }: FieldsOptions = {}): FieldGroup[] {
     return [
       {
         label: 'Character Set & Collation',
         description: 'Configure default character encoding and collation',
         fields: [
           {
-            name: 'mariadbSettings.characterSet',
+            name: 'locale.encoding',
             label: 'Character Set',
             type: 'select',
-            options: ['utf8mb4', 'utf8', 'latin1', 'ascii'],
+            options: ['utf8mb4', 'utf8mb3', 'latin1', 'ascii'],
             defaultValue: 'utf8mb4',
+            readonly: isEditMode,
             helpText:
               'Default character set. utf8mb4 is recommended for full Unicode support.',
           },
           {
-            name: 'mariadbSettings.collation',
+            name: 'locale.collation',
             label: 'Collation',
             type: 'select',
             options: [
               'utf8mb4_unicode_ci',
               'utf8mb4_general_ci',
-              'utf8_unicode_ci',
-              'utf8_general_ci',
+              'utf8mb4_uca1400_ai_ci',
+              'utf8mb4_0900_ai_ci',
+              'utf8mb3_unicode_ci',
+              'utf8mb3_general_ci',
               'latin1_swedish_ci',
             ],
             defaultValue: 'utf8mb4_unicode_ci',
-            helpText: 'Default collation for string comparisons',
+            readonly: isEditMode,
+            helpText: isEditMode
+              ? 'Collation cannot be changed after creation'
+              : 'Default collation for string comparisons',
           },
         ],
       },
@@ -264,17 +272,7 @@ export class MariaDBDatabaseProvider implements DatabaseProvider {
     // Command arguments for advanced settings
     const command: string[] = [];
 
-    // Character set
-    if (config.mariadbSettings?.characterSet) {
-      command.push(
-        `--character-set-server=${config.mariadbSettings.characterSet}`,
-      );
-    }
-
-    // Collation
-    if (config.mariadbSettings?.collation) {
-      command.push(`--collation-server=${config.mariadbSettings.collation}`);
-    }
+    // Character set and collation are sent as the metadata locale
 
     // Max connections
     if (config.mariadbSettings?.maxConnections) {
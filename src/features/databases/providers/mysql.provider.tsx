@@ -124,26 +124,32 @@ export class MySQLDatabaseProvider implements DatabaseProvider {
     ];
   }
 
-  getAdvancedFields(): FieldGroup[] {
+  getAdvancedFields({
+    isEditMode = false,
+  }: FieldsOptions = {}): FieldGroup[] {
     return [
       {
         label: 'Character Set & Collation',
         description: 'Configure default character encoding and collation',
         fields: [
           {
-            name: 'mysqlSettings.characterSet',
+            name: 'locale.encoding',
             label: 'Character Set',
             type: 'select',
-            options: ['utf8mb4', 'utf8', 'latin1'],
+            options: ['utf8mb4', 'utf8mb3', 'latin1', 'ascii'],
             defaultValue: 'utf8mb4',
+            readonly: isEditMode,
             helpText: 'Default character set for databases',
           },
           {
-            name: 'mysqlSettings.collation',
+            name: 'locale.collation',
             label: 'Collation',
             type: 'text',
-            defaultValue: 'utf8mb4_unicode_ci',
-            helpText: 'Default collation for string comparisons',
+            defaultValue: 'utf8mb4_0900_ai_ci',
+            readonly: isEditMode,
+            helpText: isEditMode
+              ? 'Collation cannot be changed after creation'
+              : 'Default collation, e.g. utf8mb4_0900_ai_ci to match MySQL 8 servers',
           },
         ],
       },
@@ -185,15 +191,7 @@ export class MySQLDatabaseProvider implements DatabaseProvider {
     // Advanced settings via mysqld command flags (official mysql image doesn't support these as env vars)
     const command: string[] = [];
 
-    if (config.mysqlSettings?.characterSet) {
-      command.push(
-        `--character-set-server=${config.mysqlSettings.characterSet}`,
-      );
-    }
-
-    if (config.mysqlSettings?.collation) {
-      command.push(`--collation-server=${config.mysqlSettings.collation}`);
-    }
+    // Character set and collation are sent as the metadata locale
 
     if (config.mysqlSettings?.sqlMode) {
       command.push(`--sql-mode=${config.mysqlSettings.sqlMode}`);
@@ -146,8 +146,36 @@ export class PostgresDatabaseProvider implements DatabaseProvider {
     ];
   }
 
-  getAdvancedFields(): FieldGroup[] {
+  getAdvancedFields({
+    isEditMode = false,
+  }: FieldsOptions = {}): FieldGroup[] {
     return [
+      {
+        label: 'Locale & Encoding',
+        description:
+          'Match the collation of your production database, set once at creation',
+        fields: [
+          {
+            name: 'locale.encoding',
+            label: 'Encoding',
+            type: 'select',
+            options: ['UTF8', 'LATIN1', 'LATIN9', 'WIN1252', 'SQL_ASCII'],
+            defaultValue: 'UTF8',
+            readonly: isEditMode,
+            helpText: 'Character encoding of the databases',
+          },
+          {
+            name: 'locale.locale',
+            label: 'Locale',
+            type: 'text',
+            placeholder: 'en_US.utf8',
+            readonly: isEditMode,
+            helpText: isEditMode
+              ? 'Locale cannot be changed after creation'
+              : 'Sort order and character classes, e.g. de_DE.utf8. Locales the image lacks use ICU (PostgreSQL 15+)',
+          },
+        ],
+      },
       {
         label: 'Authentication & Security',
         description: 'Configure how PostgreSQL handles authentication',
@@ -171,7 +199,7 @@ export class PostgresDatabaseProvider implements DatabaseProvider {
             name: 'postgresSettings.initdbArgs',
             label: 'INITDB Arguments',
             type: 'text',
-            placeholder: '--encoding=UTF8 --locale=en_US.utf8',
+            placeholder: '--data-checksums',
             helpText:
               'Additional arguments passed to initdb during initialization',
           },
@@ -185,6 +213,20 @@ export class PostgresDatabaseProvider implements DatabaseProvider {
           },
         ],
       },
+      {
+        label: 'Vector Search',
+        description: 'Store and query embeddings for AI applications',
+        fields: [
+          {
+            name: 'postgresSettings.pgvector',
+            label: 'Enable pgvector',
+            type: 'checkbox',
+            defaultValue: false,
+            helpText:
+              'Use the pgvector image and create the vector extension once the database is ready',
+          },
+        ],
+      },
     ];
   }
 
@@ -220,7 +262,9 @@ export class PostgresDatabaseProvider implements DatabaseProvider {
     }
 
     return {
-      image: `postgres:${config.version}`,
+      image: config.postgresSettings?.pgvector
+        ? this.pgvectorImage(config.version)
+        : `postgres:${config.version}`,
       envVars,
       ports: [{ host: config.port, container: this.containerPort }],
       volumes: config.persistData
@@ -230,6 +274,20 @@ export class PostgresDatabaseProvider implements DatabaseProvider {
     };
   }
 
+  getPostCreateSql(config: any): string[] {
+    return config.postgresSettings?.pgvector
+      ? ['CREATE EXTENSION IF NOT EXISTS vector']
+      : [];
+  }
+
+  /**
+   * pgvector publishes one Debian image per PostgreSQL major version
+   */
+  private pgvectorImage(version: string): string {
+    const major = version.split(/[.-]/)[0];
+    return `pgvector/pgvector:pg${major}`;
+  }
+
   // ==================== Utilities ====================
   getConnectionString(container: Container): string {
     const username = container.username || 'postgres';
@@ -50,6 +50,11 @@ export interface DatabaseProvider {
    */
   buildDockerArgs(config: any): DockerRunArgs;
 
+  /**
+   * SQL statements to run once a new container accepts connections
+   */
+  getPostCreateSql?(config: any): string[];
+
   // ==================== Utilities ====================
   /**
    * Generate connection string for this database type
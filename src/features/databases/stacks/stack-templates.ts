@@ -0,0 +1,128 @@
+import type { StackRequest } from '../api/databases.api';
+import { databaseRegistry } from '../registry/database-registry';
+
+export interface StackTemplateService {
+  /** Role in the stack, the container is named `<stack>-<service>` */
+  service: string;
+  dbType: string;
+  /** Provider form values on top of the defaults */
+  config?: Record<string, unknown>;
+}
+
+export interface StackTemplate {
+  id: string;
+  name: string;
+  description: string;
+  services: StackTemplateService[];
+}
+
+/**
+ * Built-in stacks, services are created in order and stopped in reverse
+ */
+export const stackTemplates: StackTemplate[] = [
+  {
+    id: 'web-app',
+    name: 'Web App',
+    description: 'PostgreSQL for data and Redis for cache and sessions',
+    services: [
+      { service: 'postgres', dbType: 'PostgreSQL' },
+      { service: 'redis', dbType: 'Redis' },
+    ],
+  },
+  {
+    id: 'ai-app',
+    name: 'AI App',
+    description: 'PostgreSQL with pgvector for embeddings and Redis for cache',
+    services: [
+      {
+        service: 'postgres',
+        dbType: 'PostgreSQL',
+        config: { postgresSettings: { pgvector: true } },
+      },
+      { service: 'redis', dbType: 'Redis' },
+    ],
+  },
+  {
+    id: 'document-store',
+    name: 'Document Store',
+    description: 'MongoDB for documents and Redis for cache',
+    services: [
+      { service: 'mongo', dbType: 'MongoDB' },
+      { service: 'redis', dbType: 'Redis' },
+    ],
+  },
+  {
+    id: 'search',
+    name: 'Search',
+    description: 'PostgreSQL as the source of truth and Elasticsearch for search',
+    services: [
+      { service: 'postgres', dbType: 'PostgreSQL' },
+      { service: 'search', dbType: 'Elasticsearch' },
+    ],
+  },
+];
+
+/**
+ * Stack name as used in container names, matches the backend
+ */
+export function stackSlug(name: string): string {
+  return name
+    .trim()
+    .toLowerCase()
+    .replace(/[^a-z0-9_.-]+/g, '-')
+    .replace(/^[^a-z0-9]+|-+$/g, '');
+}
+
+/**
+ * Build the request of a stack with each provider's defaults
+ * `ports` overrides the host port of a service, keyed by service name
+ */
+export function buildStackRequest(
+  template: StackTemplate,
+  name: string,
+  password: string,
+  ports: Record<string, number> = {},
+): StackRequest {
+  const slug = stackSlug(name);
+
+  return {
+    name,
+    services: template.services.map(({ service, dbType, config }) => {
+      const provider = databaseRegistry.get(dbType);
+      if (!provider) {
+        throw new Error(`No provider found for database type: ${dbType}`);
+      }
+
+      const containerConfiguration = {
+        name: `${slug}-${service}`,
+        port: ports[service] ?? provider.defaultPort,
+        version: provider.versions[0],
+        username: provider.getDefaultUsername?.(),
+        password,
+        persistData: true,
+        enableAuth: provider.requiresAuth() || password.length > 0,
+        ...config,
+      };
+
+      return {
+        service,
+        request: {
+          name: containerConfiguration.name,
+          dockerArgs: provider.buildDockerArgs(containerConfiguration),
+          metadata: {
+            id: crypto.randomUUID(),
+            dbType,
+            version: containerConfiguration.version,
+            port: containerConfiguration.port,
+            username: containerConfiguration.username,
+            password,
+            persistData: containerConfiguration.persistData,
+            enableAuth: containerConfiguration.enableAuth,
+            postCreateSql: provider.getPostCreateSql?.(containerConfiguration),
+            locale: containerConfiguration.locale,
+          },
+        },
+      };
+    }),
+  };
+}
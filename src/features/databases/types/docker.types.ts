@@ -1,3 +1,9 @@
+import type {
+  LocaleSettings,
+  RegionSettings,
+  TuningSettings,
+} from '@/shared/types/container';
+
 /**
  * Docker configuration types
  * These types represent the structure sent to the backend
@@ -6,6 +12,8 @@
 export interface PortMapping {
   host: number;
   container: number;
+  // Defaults to 127.0.0.1, 0.0.0.0 when published to the local network
+  hostAddress?: string;
 }
 
 export interface VolumeMount {
@@ -19,6 +27,8 @@ export interface DockerRunArgs {
   ports: PortMapping[];
   volumes: VolumeMount[];
   command: string[];
+  // `--shm-size` of the container, set by tuning presets
+  shmSize?: string;
 }
 
 export interface DockerRunRequest {
@@ -38,8 +48,24 @@ export interface ContainerMetadata {
   persistData: boolean;
   enableAuth: boolean;
   maxConnections?: number;
+  enableTls?: boolean;
+  // Publish the ports on every interface instead of 127.0.0.1
+  exposeToLan?: boolean;
+  // Statements run once the new database accepts connections
+  postCreateSql?: string[];
+  // Framework preset the database is created for
+  framework?: string;
+  // Encoding and collation of SQL engines, fixed once the database exists
+  locale?: LocaleSettings;
+  // Server arguments appended to the provider's command, changing them recreates the container
+  extraArgs?: string[];
+  // Timezone and system locale, unset fields follow the app default at creation
+  region?: RegionSettings;
+  // Memory settings from a tuning preview, they win over the provider's
+  tuning?: TuningSettings;
 }
 
+
 export interface ValidationResult {
   valid: boolean;
   errors: string[];
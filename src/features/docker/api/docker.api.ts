@@ -1,5 +1,11 @@
 import { invoke } from '../../../core/tauri/invoke';
-import type { DockerStatus } from '../../../shared/types/docker';
+import type {
+  DockerHost,
+  DockerHostStatus,
+  DockerStatus,
+  ImageCleanupReport,
+  ImageUpdateCheck,
+} from '../../../shared/types/docker';
 
 /**
  * API Layer - All Tauri calls for Docker
@@ -12,6 +18,57 @@ export const dockerApi = {
     return await invoke<DockerStatus>('get_docker_status');
   },
 
+  /**
+   * Launch the Docker runtime and wait until the daemon answers
+   */
+  async start(): Promise<DockerStatus> {
+    return await invoke<DockerStatus>('start_docker');
+  },
+
+  /**
+   * Ask the registry for newer images, of all databases when no ids are given
+   */
+  async checkImageUpdates(containerIds?: string[]): Promise<ImageUpdateCheck[]> {
+    return await invoke<ImageUpdateCheck[]>('check_image_updates', {
+      containerIds,
+    });
+  },
+
+  /**
+   * Remove images of removed databases nothing uses anymore, dryRun only lists them
+   */
+  async cleanupImages(dryRun: boolean): Promise<ImageCleanupReport> {
+    return await invoke<ImageCleanupReport>('cleanup_images', { dryRun });
+  },
+
+  /**
+   * Docker hosts with their databases, the local daemon first
+   */
+  async listHosts(): Promise<DockerHostStatus[]> {
+    return await invoke<DockerHostStatus[]>('list_docker_hosts');
+  },
+
+  /**
+   * Add a remote Docker host, or update it when it has an id
+   */
+  async saveHost(host: DockerHost): Promise<DockerHost> {
+    return await invoke<DockerHost>('save_docker_host', { host });
+  },
+
+  /**
+   * Forget a remote Docker host, refused while databases run on it
+   */
+  async removeHost(hostId: string): Promise<void> {
+    await invoke('remove_docker_host', { hostId });
+  },
+
+  /**
+   * Status of a host before saving it, to check its URL
+   */
+  async checkHost(host: DockerHost): Promise<DockerStatus> {
+    return await invoke<DockerStatus>('check_docker_host', { host });
+  },
+
   /**
    * Check if Docker is available
    */
@@ -0,0 +1,30 @@
+import { useCallback, useEffect, useState } from 'react';
+import type { DockerHostStatus } from '../../../shared/types/docker';
+import { dockerApi } from '../api/docker.api';
+
+// Reachability is refreshed by the background sync, polled at a similar pace
+const REFRESH_INTERVAL_MS = 10000;
+
+/**
+ * Hook to list the Docker hosts databases run on
+ * Responsibility: Host names and reachability for the dashboard
+ */
+export function useDockerHosts() {
+  const [hosts, setHosts] = useState<DockerHostStatus[]>([]);
+
+  const refresh = useCallback(async () => {
+    try {
+      setHosts(await dockerApi.listHosts());
+    } catch (error) {
+      console.error('Error loading Docker hosts:', error);
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    const interval = setInterval(refresh, REFRESH_INTERVAL_MS);
+    return () => clearInterval(interval);
+  }, [refresh]);
+
+  return { hosts, refresh };
+}
@@ -1,6 +1,10 @@
 import { useCallback, useEffect, useRef, useState } from 'react';
 import { toast } from 'sonner';
-import type { DockerStatus } from '../../../shared/types/docker';
+import { showErrorToast } from '@/core/errors/error-handler';
+import type {
+  DockerStartProgress,
+  DockerStatus,
+} from '../../../shared/types/docker';
 import { dockerApi } from '../api/docker.api';
 
 /**
@@ -10,6 +14,8 @@ import { dockerApi } from '../api/docker.api';
 export function useDockerStatus() {
   const [dockerStatus, setDockerStatus] = useState<DockerStatus | null>(null);
   const [isRefreshing, setIsRefreshing] = useState(false);
+  const [startProgress, setStartProgress] =
+    useState<DockerStartProgress | null>(null);
   const [shouldShowOverlay, setShouldShowOverlay] = useState(false);
   const intervalRef = useRef<NodeJS.Timeout | null>(null);
   const retryTimeoutRef = useRef<NodeJS.Timeout | null>(null);
@@ -60,6 +66,31 @@ export function useDockerStatus() {
     setIsRefreshing(false);
   }, [checkDockerStatus]);
 
+  /**
+   * Launch Docker and wait until it is ready
+   */
+  const startDocker = useCallback(async () => {
+    let unlisten: (() => void) | undefined;
+    try {
+      const { listen } = await import('@tauri-apps/api/event');
+      unlisten = await listen<DockerStartProgress>(
+        'docker-start-progress',
+        (event) => setStartProgress(event.payload),
+      );
+
+      const status = await dockerApi.start();
+      setDockerStatus(status);
+      setShouldShowOverlay(status.status !== 'running');
+      toast.success('Docker is available');
+    } catch (error) {
+      console.error('Error starting Docker:', error);
+      showErrorToast(error);
+    } finally {
+      unlisten?.();
+      setStartProgress(null);
+    }
+  }, []);
+
   /**
    * Start periodic check (every 30 seconds)
    */
@@ -116,6 +147,8 @@ export function useDockerStatus() {
     isRefreshing,
     shouldShowOverlay,
     refreshStatus,
+    startDocker,
+    startProgress,
     isDockerAvailable: dockerStatus ? dockerStatus.status === 'running' : false,
   };
 }
@@ -0,0 +1,10 @@
+import React from 'react';
+import ReactDOM from 'react-dom/client';
+import { MetricsPage } from './pages/metrics/MetricsPage';
+import './App.css';
+
+ReactDOM.createRoot(document.getElementById('root') as HTMLElement).render(
+  <React.StrictMode>
+    <MetricsPage />
+  </React.StrictMode>,
+);
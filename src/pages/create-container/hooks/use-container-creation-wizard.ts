@@ -1,6 +1,5 @@
-import { emit } from '@tauri-apps/api/event';
 import { getCurrentWindow } from '@tauri-apps/api/window';
-import { useCallback, useEffect, useState } from 'react';
+import { useCallback, useEffect, useRef, useState } from 'react';
 import { useForm } from 'react-hook-form';
 import { databasesApi } from '@/features/databases/api/databases.api';
 import { databaseRegistry } from '@/features/databases/registry/database-registry';
@@ -18,20 +17,57 @@ export interface CreateDatabaseFormData {
   containerConfiguration: Record<string, any>;
 }
 
+/**
+ * Values passed by a dbmanager://create deep link
+ */
+interface LinkPrefill {
+  dbType: string;
+  version?: string;
+  port?: number;
+  name?: string;
+  databaseName?: string;
+  username?: string;
+  framework?: string;
+  direct: boolean;
+}
+
+function readLinkPrefill(): LinkPrefill | null {
+  const params = new URLSearchParams(window.location.search);
+  const dbType = params.get('type');
+  if (!dbType || !databaseRegistry.get(dbType)) return null;
+
+  const port = Number(params.get('port'));
+  return {
+    dbType,
+    version: params.get('version') ?? undefined,
+    port: Number.isInteger(port) && port > 0 ? port : undefined,
+    name: params.get('name') ?? undefined,
+    databaseName: params.get('database') ?? undefined,
+    username: params.get('user') ?? undefined,
+    framework: params.get('preset') ?? undefined,
+    direct: params.get('direct') === '1',
+  };
+}
+
 /**
  * Hook to manage the container creation wizard
  * Responsibility: Wizard logic (steps, validation, submit)
  */
 export function useContainerCreationWizard() {
-  const [currentStep, setCurrentStep] = useState(1);
-  const [completedSteps, setCompletedSteps] = useState<number[]>([]);
+  // Deep links pick the database type, so the wizard starts at configuration
+  const [linkPrefill] = useState(readLinkPrefill);
+  const linkPrefillApplied = useRef(false);
+  const [currentStep, setCurrentStep] = useState(linkPrefill ? 2 : 1);
+  const [completedSteps, setCompletedSteps] = useState<number[]>(
+    linkPrefill ? [1] : [],
+  );
 
   // Form setup - NO ZOD RESOLVER
   // Validation is handled by individual field rules from providers
   const form = useForm<CreateDatabaseFormData>({
     defaultValues: {
       databaseSelection: {
-        dbType: undefined,
+        dbType: linkPrefill?.dbType,
       },
       containerConfiguration: {
         name: '',
@@ -92,7 +128,40 @@ export function useContainerCreationWizard() {
     console.log(
       `✅ Applied defaults for ${provider.name}: port=${provider.defaultPort}, version=${provider.versions[0]}`,
     );
-  }, [selectedDbType, setValue]);
+
+    // Deep link values override the defaults once
+    if (linkPrefill && !linkPrefillApplied.current) {
+      linkPrefillApplied.current = true;
+
+      if (linkPrefill.version) {
+        setValue('containerConfiguration.version', linkPrefill.version);
+      }
+      if (linkPrefill.port) {
+        setValue('containerConfiguration.port', linkPrefill.port);
+      }
+      if (linkPrefill.name) {
+        setValue('containerConfiguration.name', linkPrefill.name);
+      }
+      if (linkPrefill.databaseName) {
+        setValue(
+          'containerConfiguration.databaseName',
+          linkPrefill.databaseName,
+        );
+      }
+      if (linkPrefill.username) {
+        setValue('containerConfiguration.username', linkPrefill.username);
+      }
+
+      // Direct links go to review, creating still needs the user's confirmation
+      if (
+        linkPrefill.direct &&
+        provider.validateConfig(form.getValues('containerConfiguration')).valid
+      ) {
+        setCompletedSteps([1, 2]);
+        setCurrentStep(3);
+      }
+    }
+  }, [selectedDbType, setValue, linkPrefill, form]);
 
   /**
    * Advance to next step - Uses react-hook-form validation and provider validation
@@ -221,10 +290,13 @@ export function useContainerCreationWizard() {
           persistData: containerConfiguration.persistData ?? true,
           enableAuth: containerConfiguration.enableAuth ?? true,
           maxConnections: containerConfiguration.maxConnections,
+          postCreateSql: provider.getPostCreateSql?.(containerConfiguration),
+          framework: linkPrefill?.framework,
+          locale: containerConfiguration.locale,
         },
       };
     },
-    [],
+    [linkPrefill],
   );
 
   /**
@@ -266,13 +338,15 @@ export function useContainerCreationWizard() {
         // Mark all steps as completed
         setCompletedSteps([1, 2, 3]);
 
-        // Emit event to notify main window
-        try {
-          await emit('container-created', { container: newContainer });
-        } catch (eventError) {
-          console.warn('Error emitting event:', eventError);
+        // Created, but not everything after the creation worked
+        if (newContainer.setupWarnings.length > 0) {
+          alert(
+            `${newContainer.name} was created with warnings:\n${newContainer.setupWarnings.join('\n')}`,
+          );
         }
 
+        // The backend emits 'container-created' to every window
+
         // Close window
         const currentWindow = getCurrentWindow();
         await currentWindow.close();
@@ -22,11 +22,14 @@ import {
 import { ContainerDashboard } from './components/ContainerDashboard';
 import { ContainerHeader } from './components/ContainerHeader';
 import { LogsTab } from './components/LogsTab';
+import { SqlTab } from './components/SqlTab';
 import { TerminalTab } from './components/TerminalTab';
 import { useDatabaseEditWizard } from './hooks/use-database-edit-wizard';
 import { ContainerTab, TAB_LABELS } from './types/tabs';
 import { isTabAvailable, type TabConfig } from './utils/container-actions';
 
+const SQL_DB_TYPES = ['PostgreSQL', 'MySQL', 'MariaDB'];
+
 export function EditContainerPage() {
   // Extract container ID from query parameters
   const containerId = new URLSearchParams(window.location.search).get('id');
@@ -147,6 +150,16 @@ export function EditContainerPage() {
       label: TAB_LABELS[ContainerTab.Terminal],
       requiresRunning: true, // Terminal only available when container is running
     },
+    // The SQL console only exists for SQL engines
+    ...(SQL_DB_TYPES.includes(container?.dbType ?? '')
+      ? [
+          {
+            id: ContainerTab.Sql,
+            label: TAB_LABELS[ContainerTab.Sql],
+            requiresRunning: true,
+          },
+        ]
+      : []),
   ];
 
   /**
@@ -211,7 +224,7 @@ export function EditContainerPage() {
         onValueChange={handleTabChange}
         className="flex-1 flex flex-col overflow-hidden"
       >
-        <TabsList className="w-full rounded-none border-b px-6 bg-card grid grid-cols-4">
+        <TabsList className={`w-full rounded-none border-b px-6 bg-card grid ${tabs.length > 4 ? 'grid-cols-5' : 'grid-cols-4'}`}>
           {tabs.map((tab) => {
             const isAvailable = isTabAvailable(tab, container.status);
 
@@ -255,7 +268,10 @@ export function EditContainerPage() {
           value={ContainerTab.Dashboard}
           className="flex-1 overflow-auto m-0 pt-0"
         >
-          <ContainerDashboard container={container} />
+          <ContainerDashboard
+            container={container}
+            onContainerChange={refetch}
+          />
         </TabsContent>
 
         {/* Configuration Tab */}
@@ -287,6 +303,14 @@ export function EditContainerPage() {
         >
           <TerminalTab container={container} />
         </TabsContent>
+
+        {/* SQL Tab */}
+        <TabsContent
+          value={ContainerTab.Sql}
+          className="flex-1 overflow-hidden m-0 pt-0"
+        >
+          <SqlTab container={container} />
+        </TabsContent>
       </Tabs>
 
       {/* Unsaved changes dialog */}
@@ -1,4 +1,4 @@
-import { Copy, Database, Settings } from 'lucide-react';
+import { Copy, Database, Globe, Settings } from 'lucide-react';
 import { toast } from 'sonner';
 import { databaseRegistry } from '@/features/databases/registry/database-registry';
 import { Badge } from '@/shared/components/ui/badge';
@@ -6,14 +6,37 @@ import { Button } from '@/shared/components/ui/button';
 import { Card } from '@/shared/components/ui/card';
 import { Input } from '@/shared/components/ui/input';
 import { Separator } from '@/shared/components/ui/separator';
-import type { Container } from '@/shared/types/container';
+import {
+  type Container,
+  formatContainerStatus,
+  isContainerRunning,
+} from '@/shared/types/container';
+import { useConnectionPooler } from '../hooks/use-connection-pooler';
+import { useContainerShare } from '../hooks/use-container-share';
+import { GroupDependenciesCard } from './GroupDependenciesCard';
+import { ReplicationCard } from './ReplicationCard';
+import { TuningCard } from './TuningCard';
 
 interface ContainerDashboardProps {
   container: Container;
+  // Called after an action recreated the container
+  onContainerChange?: () => void;
 }
 
-export function ContainerDashboard({ container }: ContainerDashboardProps) {
+const TUNABLE_DB_TYPES = ['PostgreSQL', 'MySQL', 'MariaDB', 'Redis', 'MongoDB'];
+
+export function ContainerDashboard({
+  container,
+  onContainerChange,
+}: ContainerDashboardProps) {
   const provider = databaseRegistry.get(container.dbType);
+  const share = useContainerShare(container.id);
+  const pooler = useConnectionPooler(container.id, container.dbType);
+  const pooledConnectionString =
+    provider && pooler.pooler
+      ? provider.getConnectionString({ ...container, port: pooler.pooler.port })
+      : null;
+  const shareState = share.status?.state ?? 'stopped';
 
   const handleCopyConnectionString = async () => {
     if (!provider) return;
@@ -29,11 +52,37 @@ export function ContainerDashboard({ container }: ContainerDashboardProps) {
     }
   };
 
+  const handleCopyPooledConnectionString = async () => {
+    if (!pooledConnectionString) return;
+
+    try {
+      await navigator.clipboard.writeText(pooledConnectionString);
+      toast.success('Pooled connection string copied to clipboard');
+    } catch (error) {
+      console.error('Error copying to clipboard:', error);
+      toast.error('Error copying connection string');
+    }
+  };
+
+  const handleCopyPublicAddress = async () => {
+    if (!share.status?.publicAddress) return;
+
+    try {
+      await navigator.clipboard.writeText(share.status.publicAddress);
+      toast.success('Public address copied to clipboard');
+    } catch (error) {
+      console.error('Error copying to clipboard:', error);
+      toast.error('Error copying public address');
+    }
+  };
+
   const formatDate = (date: Date) => {
     return new Intl.DateTimeFormat('en-US', {
       year: 'numeric',
       month: 'short',
       day: 'numeric',
+      hour: '2-digit',
+      minute: '2-digit',
     }).format(date);
   };
 
@@ -50,6 +99,11 @@ export function ContainerDashboard({ container }: ContainerDashboardProps) {
             <span className="text-muted-foreground">Type:</span>
             <span className="font-medium">
               {container.dbType} {container.version}
+              {container.updatesAvailable && (
+                <Badge variant="outline" className="ml-2">
+                  Update available
+                </Badge>
+              )}
             </span>
           </div>
           <Separator />
@@ -64,16 +118,26 @@ export function ContainerDashboard({ container }: ContainerDashboardProps) {
             <span className="text-muted-foreground">Status:</span>
             <Badge
               variant={
-                container.status === 'running'
+                isContainerRunning(container.status)
                   ? 'default'
-                  : container.status === 'error'
+                  : container.status === 'error' ||
+                      container.status === 'exited'
                     ? 'destructive'
                     : 'secondary'
               }
             >
-              {container.status}
+              {formatContainerStatus(container)}
             </Badge>
           </div>
+          {container.storageState === 'volumeMissing' && (
+            <>
+              <Separator />
+              <div className="flex justify-between items-center">
+                <span className="text-muted-foreground">Storage:</span>
+                <Badge variant="destructive">Data volume missing</Badge>
+              </div>
+            </>
+          )}
           <Separator />
           <div className="flex justify-between items-center">
             <span className="text-muted-foreground">Created:</span>
@@ -81,6 +145,28 @@ export function ContainerDashboard({ container }: ContainerDashboardProps) {
               {formatDate(container.createdAt)}
             </span>
           </div>
+          {container.lastStartedAt && (
+            <>
+              <Separator />
+              <div className="flex justify-between items-center">
+                <span className="text-muted-foreground">Last started:</span>
+                <span className="font-medium">
+                  {formatDate(container.lastStartedAt)}
+                </span>
+              </div>
+            </>
+          )}
+          {container.lastStoppedAt && (
+            <>
+              <Separator />
+              <div className="flex justify-between items-center">
+                <span className="text-muted-foreground">Last stopped:</span>
+                <span className="font-medium">
+                  {formatDate(container.lastStoppedAt)}
+                </span>
+              </div>
+            </>
+          )}
         </div>
       </Card>
 
@@ -107,9 +193,171 @@ export function ContainerDashboard({ container }: ContainerDashboardProps) {
               <Copy className="h-4 w-4" />
             </Button>
           </div>
+          {pooler.kind && !container.host && (
+            <div className="mt-3 space-y-2 text-sm">
+              <div className="flex justify-between items-center">
+                <span className="text-muted-foreground">
+                  {pooler.kind === 'pgbouncer' ? 'PgBouncer' : 'ProxySQL'}:
+                </span>
+                {pooler.pooler ? (
+                  <div className="flex items-center gap-2">
+                    <Badge variant="outline" className="font-mono">
+                      {pooler.pooler.port}
+                    </Badge>
+                    <Button
+                      type="button"
+                      variant="ghost"
+                      size="sm"
+                      disabled={pooler.busy}
+                      onClick={pooler.remove}
+                    >
+                      Remove
+                    </Button>
+                  </div>
+                ) : (
+                  <Button
+                    type="button"
+                    variant="outline"
+                    size="sm"
+                    disabled={
+                      pooler.busy || !isContainerRunning(container.status)
+                    }
+                    onClick={pooler.deploy}
+                  >
+                    Deploy
+                  </Button>
+                )}
+              </div>
+              {pooledConnectionString && (
+                <div className="flex gap-2">
+                  <Input
+                    value={pooledConnectionString}
+                    readOnly
+                    className="font-mono text-xs"
+                  />
+                  <Button
+                    type="button"
+                    variant="outline"
+                    size="icon"
+                    onClick={handleCopyPooledConnectionString}
+                    className="flex-shrink-0"
+                  >
+                    <Copy className="h-4 w-4" />
+                  </Button>
+                </div>
+              )}
+            </div>
+          )}
         </Card>
       )}
 
+      {/* Share Section */}
+      {!container.host && (
+        <Card className="p-4">
+          <div className="flex items-center gap-2 mb-3">
+            <Globe className="h-4 w-4 text-primary" />
+            <h3 className="text-sm font-semibold">Share</h3>
+            {shareState !== 'stopped' && (
+              <Badge
+                variant={shareState === 'active' ? 'default' : 'secondary'}
+                className="ml-auto"
+              >
+                {shareState === 'active' ? 'Public' : 'Starting'}
+              </Badge>
+            )}
+          </div>
+          {shareState === 'stopped' ? (
+            <div className="space-y-2 text-sm">
+              <p className="text-muted-foreground">
+                Give a collaborator temporary access through a public tunnel.
+                It closes on its own after an hour.
+              </p>
+              {share.status?.lastError && (
+                <p className="text-destructive text-xs font-mono break-all">
+                  {share.status.lastError}
+                </p>
+              )}
+              <Button
+                type="button"
+                variant="outline"
+                size="sm"
+                disabled={
+                  share.busy ||
+                  !isContainerRunning(container.status) ||
+                  !container.enableAuth
+                }
+                onClick={() => share.start()}
+              >
+                Share for 1 hour
+              </Button>
+              {!container.enableAuth && (
+                <p className="text-muted-foreground text-xs">
+                  Only databases with authentication enabled can be shared.
+                </p>
+              )}
+            </div>
+          ) : (
+            <div className="space-y-2 text-sm">
+              {share.status?.publicAddress ? (
+                <div className="flex gap-2">
+                  <Input
+                    value={share.status.publicAddress}
+                    readOnly
+                    className="font-mono text-xs"
+                  />
+                  <Button
+                    type="button"
+                    variant="outline"
+                    size="icon"
+                    onClick={handleCopyPublicAddress}
+                    className="flex-shrink-0"
+                  >
+                    <Copy className="h-4 w-4" />
+                  </Button>
+                </div>
+              ) : (
+                <p className="text-muted-foreground">
+                  Waiting for the public address...
+                </p>
+              )}
+              {share.status?.expiresAt && (
+                <div className="flex justify-between items-center">
+                  <span className="text-muted-foreground">Closes at:</span>
+                  <span className="font-medium">
+                    {formatDate(new Date(share.status.expiresAt))}
+                  </span>
+                </div>
+              )}
+              <Button
+                type="button"
+                variant="destructive"
+                size="sm"
+                disabled={share.busy}
+                onClick={share.stop}
+              >
+                Stop sharing
+              </Button>
+            </div>
+          )}
+        </Card>
+      )}
+
+      {/* Replication Section */}
+      {container.dbType === 'PostgreSQL' && (
+        <ReplicationCard
+          containerId={container.id}
+          running={isContainerRunning(container.status)}
+        />
+      )}
+
+      {/* Group Dependencies Section */}
+      {container.project && <GroupDependenciesCard container={container} />}
+
+      {/* Tuning Section */}
+      {TUNABLE_DB_TYPES.includes(container.dbType) && (
+        <TuningCard container={container} onRetuned={onContainerChange} />
+      )}
+
       {/* Settings Section */}
       <Card className="p-4">
         <div className="flex items-center gap-2 mb-3">
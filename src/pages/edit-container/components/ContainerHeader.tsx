@@ -6,7 +6,11 @@ import {
   TooltipContent,
   TooltipTrigger,
 } from '@/shared/components/ui/tooltip';
-import type { Container } from '@/shared/types/container';
+import {
+  type Container,
+  formatContainerStatus,
+  isContainerRunning,
+} from '@/shared/types/container';
 
 interface ContainerHeaderProps {
   container: Container;
@@ -26,16 +30,20 @@ export function ContainerHeader({
   ): 'default' | 'secondary' | 'destructive' => {
     switch (status) {
       case 'running':
+      case 'starting':
         return 'default';
       case 'error':
+      case 'unhealthy':
+      case 'exited':
         return 'destructive';
       default:
         return 'secondary';
     }
   };
 
-  const isRunning = container.status === 'running';
-  const isStopped = container.status === 'stopped';
+  const isRunning = isContainerRunning(container.status);
+  const isStopped =
+    container.status === 'stopped' || container.status === 'exited';
 
   return (
     <div className="flex-shrink-0 px-6 py-4 border-b border-border">
@@ -51,7 +59,7 @@ export function ContainerHeader({
               variant={getStatusVariant(container.status)}
               className="text-xs"
             >
-              {container.status}
+              {formatContainerStatus(container)}
             </Badge>
           </div>
         </div>
@@ -78,7 +86,7 @@ export function ContainerHeader({
           )}
 
           {/* Stop button - only shown when running */}
-          {isRunning && (
+          {(isRunning || container.status === 'restarting') && (
             <Tooltip delayDuration={500}>
               <TooltipTrigger asChild>
                 <Button
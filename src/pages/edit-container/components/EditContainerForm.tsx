@@ -14,10 +14,12 @@ import { Checkbox } from '@/shared/components/ui/checkbox';
 import {
   Form,
   FormControl,
+  FormDescription,
   FormField,
   FormItem,
   FormLabel,
 } from '@/shared/components/ui/form';
+import { Input } from '@/shared/components/ui/input';
 import type { Container } from '@/shared/types/container';
 
 interface EditContainerFormProps {
@@ -42,7 +44,13 @@ export function EditContainerForm({
       <div className="space-y-4">
         <Accordion
           type="multiple"
-          defaultValue={['container', 'auth', 'advanced']}
+          defaultValue={[
+            'container',
+            'auth',
+            'advanced',
+            'serverArgs',
+            'region',
+          ]}
           className="w-full"
         >
           {/* Section 1: Basic Container Configuration */}
@@ -113,6 +121,80 @@ export function EditContainerForm({
               </AccordionContent>
             </AccordionItem>
           )}
+
+          {/* Section 4: Extra arguments appended to the server command */}
+          <AccordionItem value="serverArgs">
+            <AccordionTrigger className="text-sm font-medium">
+              Server Arguments
+            </AccordionTrigger>
+            <AccordionContent className="space-y-4 pt-4">
+              <FormField
+                control={form.control}
+                name="containerConfiguration.extraArgs"
+                render={({ field }) => (
+                  <FormItem>
+                    <FormLabel>Extra arguments</FormLabel>
+                    <FormControl>
+                      <Input
+                        {...field}
+                        value={field.value ?? ''}
+                        placeholder="-c shared_buffers=256MB"
+                      />
+                    </FormControl>
+                    <FormDescription>
+                      Appended to the server command, separated by spaces.
+                      Changing them recreates the container.
+                    </FormDescription>
+                  </FormItem>
+                )}
+              />
+            </AccordionContent>
+          </AccordionItem>
+
+          {/* Section 5: Timezone and system locale */}
+          <AccordionItem value="region">
+            <AccordionTrigger className="text-sm font-medium">
+              Timezone & Locale
+            </AccordionTrigger>
+            <AccordionContent className="space-y-4 pt-4">
+              <FormField
+                control={form.control}
+                name="containerConfiguration.region.timezone"
+                render={({ field }) => (
+                  <FormItem>
+                    <FormLabel>Timezone</FormLabel>
+                    <FormControl>
+                      <Input
+                        {...field}
+                        value={field.value ?? ''}
+                        placeholder="Europe/Madrid"
+                      />
+                    </FormControl>
+                  </FormItem>
+                )}
+              />
+              <FormField
+                control={form.control}
+                name="containerConfiguration.region.lang"
+                render={({ field }) => (
+                  <FormItem>
+                    <FormLabel>Locale</FormLabel>
+                    <FormControl>
+                      <Input
+                        {...field}
+                        value={field.value ?? ''}
+                        placeholder="en_US.UTF-8"
+                      />
+                    </FormControl>
+                    <FormDescription>
+                      Sets TZ and LANG, and the server timezone of PostgreSQL.
+                      Changing them recreates the container.
+                    </FormDescription>
+                  </FormItem>
+                )}
+              />
+            </AccordionContent>
+          </AccordionItem>
         </Accordion>
       </div>
     </Form>
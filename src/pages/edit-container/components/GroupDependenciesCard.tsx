@@ -0,0 +1,53 @@
+import { Network } from 'lucide-react';
+import { Card } from '@/shared/components/ui/card';
+import type { Container } from '@/shared/types/container';
+import { useGroupDependencies } from '../hooks/use-group-dependencies';
+
+interface GroupDependenciesCardProps {
+  container: Container;
+}
+
+/**
+ * Members of the container's group it depends on: they start first and must
+ * accept connections before it starts, and stop after it
+ */
+export function GroupDependenciesCard({
+  container,
+}: GroupDependenciesCardProps) {
+  const dependencies = useGroupDependencies(container.id, container.project);
+
+  return (
+    <Card className="p-4">
+      <div className="flex items-center gap-2 mb-3">
+        <Network className="h-4 w-4 text-primary" />
+        <h3 className="text-sm font-semibold">Depends on</h3>
+      </div>
+      {dependencies.members.length === 0 ? (
+        <p className="text-sm text-muted-foreground">
+          No other containers in {container.project}.
+        </p>
+      ) : (
+        <div className="space-y-2 text-sm">
+          {dependencies.members.map((member) => (
+            <label key={member.id} className="flex items-center gap-2">
+              <input
+                type="checkbox"
+                checked={dependencies.dependsOn.includes(member.id)}
+                disabled={dependencies.busy}
+                onChange={() => dependencies.toggle(member.id)}
+              />
+              <span className="font-medium">{member.name}</span>
+              <span className="text-xs text-muted-foreground">
+                {member.dbType}
+              </span>
+            </label>
+          ))}
+          <p className="text-xs text-muted-foreground">
+            Starting {container.project} waits for these to accept connections
+            first.
+          </p>
+        </div>
+      )}
+    </Card>
+  );
+}
@@ -1,17 +1,29 @@
-import { ArrowDown, Loader2, Trash2 } from 'lucide-react';
+import { ArrowDown, Loader2, Trash2, WrapText } from 'lucide-react';
 import { useEffect, useRef, useState } from 'react';
 import { Button } from '@/shared/components/ui/button';
-import type { Container } from '@/shared/types/container';
+import {
+  type Container,
+  isContainerRunning,
+} from '@/shared/types/container';
 import { useContainerLogs } from '../hooks/use-container-logs';
+import { useLogPreferences } from '../hooks/use-log-preferences';
+
+const TAIL_SIZES = [100, 500, 1000, 5000];
 
 interface LogsTabProps {
   container: Container;
 }
 
 export function LogsTab({ container }: LogsTabProps) {
+  // The window reopens the way it was left for this container
+  const { preferences, loaded, updatePreferences } = useLogPreferences(
+    container.id,
+  );
   const { logs, loading, clearLogs } = useContainerLogs(
     container.containerId,
-    container.status === 'running', // Only poll if container is running
+    // Only poll if container is running, with the saved tail size
+    loaded && isContainerRunning(container.status),
+    preferences.tailLines,
   );
 
   const logsEndRef = useRef<HTMLDivElement>(null);
@@ -19,6 +31,13 @@ export function LogsTab({ container }: LogsTabProps) {
   const [autoScroll, setAutoScroll] = useState(true);
   const [userScrolled, setUserScrolled] = useState(false);
 
+  useEffect(() => {
+    if (loaded) {
+      setAutoScroll(preferences.follow);
+      setUserScrolled(!preferences.follow);
+    }
+  }, [loaded]);
+
   /**
    * Auto-scroll to bottom when new logs arrive (only if autoScroll is enabled)
    */
@@ -41,6 +60,7 @@ export function LogsTab({ container }: LogsTabProps) {
       // User scrolled up, disable auto-scroll
       setAutoScroll(false);
       setUserScrolled(true);
+      updatePreferences({ follow: false });
     } else if (isAtBottom && !autoScroll) {
       // User scrolled to bottom, keep auto-scroll disabled until they click the button
       setUserScrolled(true);
@@ -53,6 +73,7 @@ export function LogsTab({ container }: LogsTabProps) {
   const handleEnableAutoScroll = () => {
     setAutoScroll(true);
     setUserScrolled(false);
+    updatePreferences({ follow: true });
     if (logsEndRef.current) {
       logsEndRef.current.scrollIntoView({ behavior: 'smooth' });
     }
@@ -76,6 +97,31 @@ export function LogsTab({ container }: LogsTabProps) {
         </div>
 
         <div className="flex items-center gap-2">
+          <select
+            value={preferences.tailLines}
+            onChange={(e) =>
+              updatePreferences({ tailLines: Number(e.target.value) })
+            }
+            className="h-7 rounded-md border border-border bg-transparent px-2 text-xs"
+            aria-label="Lines to load"
+          >
+            {TAIL_SIZES.map((size) => (
+              <option key={size} value={size}>
+                Last {size} lines
+              </option>
+            ))}
+          </select>
+
+          <Button
+            variant={preferences.wrap ? 'secondary' : 'ghost'}
+            size="sm"
+            onClick={() => updatePreferences({ wrap: !preferences.wrap })}
+            className="h-7 px-2 text-xs"
+          >
+            <WrapText className="h-3 w-3 mr-1" />
+            Wrap
+          </Button>
+
           {/* Auto-scroll button (shown when disabled) */}
           {userScrolled && !autoScroll && (
             <Button
@@ -133,7 +179,7 @@ export function LogsTab({ container }: LogsTabProps) {
             {logs.map((log, index) => (
               <div
                 key={`${index}-${log.substring(0, 20)}`}
-                className="whitespace-pre-wrap break-all text-foreground/80"
+                className={`${preferences.wrap ? 'whitespace-pre-wrap break-all' : 'whitespace-pre'} text-foreground/80`}
               >
                 {log}
               </div>
@@ -0,0 +1,66 @@
+import { Download } from 'lucide-react';
+import { useState } from 'react';
+import type { QueryExportFormat } from '@/features/databases/api/databases.api';
+import { Button } from '@/shared/components/ui/button';
+import { Input } from '@/shared/components/ui/input';
+import { useQueryExport } from '../hooks/use-query-export';
+
+interface QueryExportProps {
+  containerId: string;
+  sql: string;
+}
+
+/**
+ * Writes the rows of the console query straight to a local file, for results too large
+ * to show
+ */
+export function QueryExport({ containerId, sql }: QueryExportProps) {
+  const { exporting, rowsWritten, result, exportQuery } =
+    useQueryExport(containerId);
+  const [path, setPath] = useState('');
+  const [format, setFormat] = useState<QueryExportFormat>('csv');
+
+  return (
+    <div className="space-y-1">
+      <div className="flex items-center gap-2">
+        <Input
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+          placeholder={`/path/to/result.${format}`}
+          className="h-8 flex-1 text-xs font-mono"
+          aria-label="Export file"
+        />
+        <select
+          value={format}
+          onChange={(e) => setFormat(e.target.value as QueryExportFormat)}
+          className="h-8 rounded-md border border-border bg-transparent px-2 text-xs"
+          aria-label="Export format"
+        >
+          <option value="csv">CSV</option>
+          <option value="json">JSON</option>
+        </select>
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          disabled={exporting || !sql.trim() || !path.trim()}
+          onClick={() => exportQuery(sql, path.trim(), format)}
+        >
+          <Download className="h-4 w-4 mr-1" />
+          {exporting ? 'Exporting...' : 'Export'}
+        </Button>
+      </div>
+      {exporting && rowsWritten > 0 && (
+        <p className="text-xs text-muted-foreground">
+          {rowsWritten.toLocaleString()} rows written
+        </p>
+      )}
+      {result && !exporting && (
+        <p className="text-xs text-muted-foreground">
+          {result.rows.toLocaleString()} rows written to{' '}
+          <span className="font-mono">{result.path}</span>
+        </p>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,199 @@
+import { GitBranch, Trash2 } from 'lucide-react';
+import { useState } from 'react';
+import { Badge } from '@/shared/components/ui/badge';
+import { Button } from '@/shared/components/ui/button';
+import { Card } from '@/shared/components/ui/card';
+import { Input } from '@/shared/components/ui/input';
+import { Separator } from '@/shared/components/ui/separator';
+import { useLogicalReplication } from '../hooks/use-logical-replication';
+
+interface ReplicationCardProps {
+  containerId: string;
+  running: boolean;
+}
+
+function formatWal(bytes?: number | null) {
+  if (bytes == null) return '-';
+  if (bytes < 1024 * 1024) return `${Math.round(bytes / 1024)} KB`;
+  return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+}
+
+/**
+ * Logical replication of a PostgreSQL database, to try CDC tools like Debezium
+ * against it: wal_level, publications and replication slots
+ */
+export function ReplicationCard({
+  containerId,
+  running,
+}: ReplicationCardProps) {
+  const replication = useLogicalReplication(containerId, running);
+  const [publicationName, setPublicationName] = useState('');
+  const [tables, setTables] = useState('');
+  const [slotName, setSlotName] = useState('');
+  const enabled = replication.status?.walLevel === 'logical';
+
+  const handleCreatePublication = async () => {
+    const list = tables
+      .split(',')
+      .map((table) => table.trim())
+      .filter(Boolean);
+    if (await replication.createPublication(publicationName.trim(), list)) {
+      setPublicationName('');
+      setTables('');
+    }
+  };
+
+  const handleCreateSlot = async () => {
+    if (await replication.createSlot(slotName.trim())) {
+      setSlotName('');
+    }
+  };
+
+  return (
+    <Card className="p-4">
+      <div className="flex items-center gap-2 mb-3">
+        <GitBranch className="h-4 w-4 text-primary" />
+        <h3 className="text-sm font-semibold">Logical replication</h3>
+        {replication.status && (
+          <Badge
+            variant={enabled ? 'default' : 'secondary'}
+            className="ml-auto"
+          >
+            wal_level={replication.status.walLevel}
+          </Badge>
+        )}
+      </div>
+      {!running ? (
+        <p className="text-sm text-muted-foreground">
+          Start the database to manage replication.
+        </p>
+      ) : (
+        <div className="space-y-3 text-sm">
+          <div className="flex items-center gap-2">
+            <p className="text-muted-foreground flex-1">
+              {enabled
+                ? `${replication.status?.maxReplicationSlots} slots, ${replication.status?.maxWalSenders} WAL senders.`
+                : 'Turn on wal_level=logical to stream changes to CDC tools.'}{' '}
+              Changing it restarts the database.
+            </p>
+            <Button
+              type="button"
+              variant="outline"
+              size="sm"
+              disabled={replication.busy || !replication.status}
+              onClick={() => replication.setReplication(!enabled)}
+            >
+              {enabled ? 'Turn off' : 'Turn on'}
+            </Button>
+          </div>
+
+          {enabled && (
+            <>
+              <Separator />
+              <div className="space-y-2">
+                <span className="text-xs font-medium">Publications</span>
+                {replication.publications.map((publication) => (
+                  <div
+                    key={publication.name}
+                    className="flex items-center gap-2 text-xs"
+                  >
+                    <span className="font-mono">{publication.name}</span>
+                    <span className="text-muted-foreground truncate">
+                      {publication.allTables
+                        ? 'all tables'
+                        : publication.tables.join(', ')}
+                    </span>
+                    <Button
+                      type="button"
+                      variant="ghost"
+                      size="icon"
+                      className="ml-auto"
+                      disabled={replication.busy}
+                      onClick={() =>
+                        replication.dropPublication(publication.name)
+                      }
+                      aria-label="Drop publication"
+                    >
+                      <Trash2 className="h-4 w-4" />
+                    </Button>
+                  </div>
+                ))}
+                <div className="flex gap-2">
+                  <Input
+                    value={publicationName}
+                    onChange={(e) => setPublicationName(e.target.value)}
+                    placeholder="Name"
+                    className="h-8 w-32 text-xs font-mono"
+                  />
+                  <Input
+                    value={tables}
+                    onChange={(e) => setTables(e.target.value)}
+                    placeholder="public.orders, ... (all tables when empty)"
+                    className="h-8 flex-1 text-xs font-mono"
+                  />
+                  <Button
+                    type="button"
+                    variant="outline"
+                    size="sm"
+                    disabled={replication.busy || !publicationName.trim()}
+                    onClick={handleCreatePublication}
+                  >
+                    Create
+                  </Button>
+                </div>
+              </div>
+
+              <Separator />
+              <div className="space-y-2">
+                <span className="text-xs font-medium">Replication slots</span>
+                {replication.slots.map((slot) => (
+                  <div
+                    key={slot.name}
+                    className="flex items-center gap-2 text-xs"
+                  >
+                    <span className="font-mono">{slot.name}</span>
+                    <Badge variant={slot.active ? 'default' : 'secondary'}>
+                      {slot.active ? 'Active' : 'Idle'}
+                    </Badge>
+                    <span className="text-muted-foreground">
+                      {slot.plugin ?? slot.slotType}, WAL kept{' '}
+                      {formatWal(slot.retainedWalBytes)}
+                    </span>
+                    <Button
+                      type="button"
+                      variant="ghost"
+                      size="icon"
+                      className="ml-auto"
+                      disabled={replication.busy || slot.active}
+                      onClick={() => replication.dropSlot(slot.name)}
+                      aria-label="Drop replication slot"
+                    >
+                      <Trash2 className="h-4 w-4" />
+                    </Button>
+                  </div>
+                ))}
+                <div className="flex gap-2">
+                  <Input
+                    value={slotName}
+                    onChange={(e) => setSlotName(e.target.value)}
+                    placeholder="debezium_slot"
+                    className="h-8 flex-1 text-xs font-mono"
+                  />
+                  <Button
+                    type="button"
+                    variant="outline"
+                    size="sm"
+                    disabled={replication.busy || !slotName.trim()}
+                    onClick={handleCreateSlot}
+                  >
+                    Create pgoutput slot
+                  </Button>
+                </div>
+              </div>
+            </>
+          )}
+        </div>
+      )}
+    </Card>
+  );
+}
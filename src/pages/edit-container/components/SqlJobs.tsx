@@ -0,0 +1,148 @@
+import { CalendarClock, Play, Trash2 } from 'lucide-react';
+import { useState } from 'react';
+import { Badge } from '@/shared/components/ui/badge';
+import { Button } from '@/shared/components/ui/button';
+import { Input } from '@/shared/components/ui/input';
+import { useSqlJobs } from '../hooks/use-sql-jobs';
+
+interface SqlJobsProps {
+  containerId: string;
+  sql: string;
+}
+
+/**
+ * Recurring jobs of the SQL console: schedule the current script, run or pause jobs,
+ * and see how their latest runs went
+ */
+export function SqlJobs({ containerId, sql }: SqlJobsProps) {
+  const { jobs, runningId, create, update, remove, runNow } =
+    useSqlJobs(containerId);
+  const [open, setOpen] = useState(false);
+  const [name, setName] = useState('');
+  const [cron, setCron] = useState('0 3 * * *');
+
+  const handleCreate = async () => {
+    if (await create(name.trim(), sql, cron.trim())) {
+      setName('');
+      setOpen(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <span className="text-xs font-medium">Scheduled jobs</span>
+        <Button
+          type="button"
+          variant="ghost"
+          size="sm"
+          className="ml-auto"
+          disabled={!sql.trim()}
+          onClick={() => setOpen((previous) => !previous)}
+        >
+          <CalendarClock className="h-4 w-4 mr-1" />
+          Schedule query
+        </Button>
+      </div>
+
+      {open && (
+        <div className="flex items-center gap-2">
+          <Input
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Job name"
+            className="h-8 flex-1 text-xs"
+          />
+          <Input
+            value={cron}
+            onChange={(e) => setCron(e.target.value)}
+            placeholder="0 3 * * *"
+            className="h-8 w-32 text-xs font-mono"
+            aria-label="Cron schedule"
+          />
+          <Button
+            type="button"
+            size="sm"
+            disabled={!name.trim() || !cron.trim()}
+            onClick={handleCreate}
+          >
+            Save
+          </Button>
+        </div>
+      )}
+
+      {jobs.map((job) => {
+        const lastRun = job.runs[job.runs.length - 1];
+        return (
+          <div
+            key={job.id}
+            className="rounded-md border px-3 py-2 text-xs space-y-1"
+          >
+            <div className="flex items-center gap-2">
+              <span className="font-medium">{job.name}</span>
+              <span className="font-mono text-muted-foreground">
+                {job.cron}
+              </span>
+              {lastRun && (
+                <Badge variant={lastRun.success ? 'secondary' : 'destructive'}>
+                  {lastRun.success ? 'Succeeded' : 'Failed'}{' '}
+                  {new Date(lastRun.finishedAt).toLocaleString()}
+                </Badge>
+              )}
+              <label className="ml-auto flex items-center gap-1">
+                <input
+                  type="checkbox"
+                  checked={job.enabled}
+                  onChange={(e) =>
+                    update({ ...job, enabled: e.target.checked })
+                  }
+                />
+                Enabled
+              </label>
+              <label className="flex items-center gap-1">
+                <input
+                  type="checkbox"
+                  checked={job.notifyOnFailure}
+                  onChange={(e) =>
+                    update({ ...job, notifyOnFailure: e.target.checked })
+                  }
+                />
+                Notify on failure
+              </label>
+              <Button
+                type="button"
+                variant="ghost"
+                size="icon"
+                disabled={runningId === job.id}
+                onClick={() => runNow(job.id)}
+                aria-label="Run now"
+              >
+                <Play className="h-4 w-4" />
+              </Button>
+              <Button
+                type="button"
+                variant="ghost"
+                size="icon"
+                onClick={() => remove(job.id)}
+                aria-label="Delete job"
+              >
+                <Trash2 className="h-4 w-4" />
+              </Button>
+            </div>
+            {job.lastError && (
+              <p className="text-destructive font-mono whitespace-pre-wrap break-all">
+                {job.lastError}
+              </p>
+            )}
+            {job.runs.length > 1 && (
+              <p className="text-muted-foreground">
+                Last {job.runs.length} runs:{' '}
+                {job.runs.filter((run) => !run.success).length} failed
+              </p>
+            )}
+          </div>
+        );
+      })}
+    </div>
+  );
+}
@@ -0,0 +1,152 @@
+import { Save, Trash2 } from 'lucide-react';
+import { useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import { snippetVariables } from '@/features/databases/api/databases.api';
+import { Button } from '@/shared/components/ui/button';
+import { Input } from '@/shared/components/ui/input';
+import type { Container } from '@/shared/types/container';
+import { useSnippets } from '../hooks/use-snippets';
+
+interface SqlSnippetsProps {
+  container: Container;
+  sql: string;
+  onInsert: (sql: string) => void;
+}
+
+/**
+ * Saved queries of the SQL console: pick one to insert it, or save the current script
+ */
+export function SqlSnippets({ container, sql, onInsert }: SqlSnippetsProps) {
+  const { snippets, save, remove, render } = useSnippets(container.id, 'sql');
+  const [selectedId, setSelectedId] = useState('');
+  const [values, setValues] = useState<Record<string, string>>({});
+  const [saving, setSaving] = useState(false);
+  const [name, setName] = useState('');
+  const [shared, setShared] = useState(false);
+
+  const selected = snippets.find((snippet) => snippet.id === selectedId);
+  const variables = selected ? snippetVariables(selected.body) : [];
+
+  const handleInsert = async () => {
+    if (!selected) return;
+
+    try {
+      onInsert(await render(selected.id, values));
+    } catch (error) {
+      showErrorToast(error);
+    }
+  };
+
+  const handleSave = async () => {
+    const saved = await save(
+      name.trim(),
+      sql,
+      shared ? container.dbType : undefined,
+    );
+    if (saved) {
+      setSaving(false);
+      setName('');
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <select
+          value={selectedId}
+          onChange={(e) => {
+            setSelectedId(e.target.value);
+            setValues({});
+          }}
+          className="h-8 rounded-md border border-border bg-transparent px-2 text-xs"
+          aria-label="Saved queries"
+        >
+          <option value="">Saved queries...</option>
+          {snippets.map((snippet) => (
+            <option key={snippet.id} value={snippet.id}>
+              {snippet.name}
+              {snippet.dbType ? ` (all ${snippet.dbType})` : ''}
+            </option>
+          ))}
+        </select>
+        {variables.map((variable) => (
+          <Input
+            key={variable}
+            value={values[variable] ?? ''}
+            onChange={(e) =>
+              setValues((previous) => ({
+                ...previous,
+                [variable]: e.target.value,
+              }))
+            }
+            placeholder={variable}
+            className="h-8 w-32 text-xs font-mono"
+          />
+        ))}
+        {selected && (
+          <>
+            <Button
+              type="button"
+              variant="outline"
+              size="sm"
+              onClick={handleInsert}
+            >
+              Insert
+            </Button>
+            <Button
+              type="button"
+              variant="ghost"
+              size="icon"
+              onClick={() => {
+                remove(selected.id);
+                setSelectedId('');
+              }}
+              aria-label="Delete saved query"
+            >
+              <Trash2 className="h-4 w-4" />
+            </Button>
+          </>
+        )}
+        <Button
+          type="button"
+          variant="ghost"
+          size="sm"
+          className="ml-auto"
+          disabled={!sql.trim()}
+          onClick={() => setSaving((open) => !open)}
+        >
+          <Save className="h-4 w-4 mr-1" />
+          Save query
+        </Button>
+      </div>
+
+      {saving && (
+        <div className="flex items-center gap-2">
+          <Input
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Name, use {{variable}} in the query for values to fill in"
+            className="h-8 text-xs"
+          />
+          <select
+            value={shared ? 'type' : 'container'}
+            onChange={(e) => setShared(e.target.value === 'type')}
+            className="h-8 rounded-md border border-border bg-transparent px-2 text-xs"
+            aria-label="Saved query scope"
+          >
+            <option value="container">This database</option>
+            <option value="type">All {container.dbType} databases</option>
+          </select>
+          <Button
+            type="button"
+            size="sm"
+            disabled={!name.trim()}
+            onClick={handleSave}
+          >
+            Save
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,146 @@
+import { Lock, LockOpen, Play } from 'lucide-react';
+import { type KeyboardEvent, useState } from 'react';
+import { Badge } from '@/shared/components/ui/badge';
+import { Button } from '@/shared/components/ui/button';
+import type { Container } from '@/shared/types/container';
+import { useSqlConsole } from '../hooks/use-sql-console';
+import { QueryExport } from './QueryExport';
+import { SqlJobs } from './SqlJobs';
+import { SqlSnippets } from './SqlSnippets';
+
+interface SqlTabProps {
+  container: Container;
+}
+
+export function SqlTab({ container }: SqlTabProps) {
+  const [sql, setSql] = useState('');
+  const { guard, result, error, running, run, setWritesUnlocked } =
+    useSqlConsole(container.id);
+  const writesBlocked = guard ? guard.enforced && !guard.unlocked : true;
+
+  const handleKeyDown = (event: KeyboardEvent<HTMLTextAreaElement>) => {
+    // Ctrl/Cmd+Enter runs the script
+    if (event.key === 'Enter' && (event.metaKey || event.ctrlKey)) {
+      event.preventDefault();
+      run(sql);
+    }
+  };
+
+  return (
+    <div className="flex flex-col h-full gap-3 px-6 pt-4 pb-6">
+      <div className="flex items-center gap-2">
+        <Button
+          type="button"
+          size="sm"
+          disabled={running || !sql.trim()}
+          onClick={() => run(sql)}
+        >
+          <Play className="h-4 w-4 mr-1" />
+          {running ? 'Running...' : 'Run'}
+        </Button>
+        {guard?.enforced && (
+          <Button
+            type="button"
+            variant="outline"
+            size="sm"
+            onClick={() => setWritesUnlocked(writesBlocked)}
+          >
+            {writesBlocked ? (
+              <Lock className="h-4 w-4 mr-1" />
+            ) : (
+              <LockOpen className="h-4 w-4 mr-1" />
+            )}
+            {writesBlocked ? 'Unlock writes' : 'Lock writes'}
+          </Button>
+        )}
+        <Badge
+          variant={writesBlocked ? 'secondary' : 'destructive'}
+          className="ml-auto"
+        >
+          {writesBlocked ? 'Read-only' : 'Writes allowed'}
+        </Badge>
+      </div>
+
+      <SqlSnippets container={container} sql={sql} onInsert={setSql} />
+
+      <textarea
+        value={sql}
+        onChange={(event) => setSql(event.target.value)}
+        onKeyDown={handleKeyDown}
+        placeholder="SELECT * FROM ..."
+        spellCheck={false}
+        className="min-h-32 rounded-md border bg-background p-3 font-mono text-xs resize-y focus:outline-none focus:ring-1 focus:ring-ring"
+      />
+
+      <QueryExport containerId={container.id} sql={sql} />
+
+      <SqlJobs containerId={container.id} sql={sql} />
+
+      {error && (
+        <div className="rounded-md border border-destructive/50 p-3 text-xs">
+          <p className="text-destructive font-mono whitespace-pre-wrap break-all">
+            {error.message}
+          </p>
+          {error.blocked && (
+            <Button
+              type="button"
+              variant="outline"
+              size="sm"
+              className="mt-2"
+              onClick={() => setWritesUnlocked(true)}
+            >
+              <LockOpen className="h-4 w-4 mr-1" />
+              Unlock writes for this session
+            </Button>
+          )}
+        </div>
+      )}
+
+      {result && (
+        <div className="flex-1 overflow-auto rounded-md border">
+          {result.columns.length > 0 ? (
+            <table className="w-full text-xs font-mono">
+              <thead className="bg-muted sticky top-0">
+                <tr>
+                  {result.columns.map((column, index) => (
+                    <th
+                      key={`${column}-${index}`}
+                      className="text-left font-semibold px-2 py-1 border-b"
+                    >
+                      {column}
+                    </th>
+                  ))}
+                </tr>
+              </thead>
+              <tbody>
+                {result.rows.map((row, rowIndex) => (
+                  <tr key={rowIndex} className="border-b last:border-0">
+                    {row.map((value, columnIndex) => (
+                      <td
+                        key={columnIndex}
+                        className="px-2 py-1 whitespace-nowrap"
+                      >
+                        {value}
+                      </td>
+                    ))}
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          ) : (
+            <p className="p-3 text-xs text-muted-foreground">
+              {result.statements === 1
+                ? 'Statement ran'
+                : `${result.statements} statements ran`}
+              , nothing returned.
+            </p>
+          )}
+          <p className="px-2 py-1 text-xs text-muted-foreground border-t">
+            {result.rows.length} rows in {result.elapsedMs} ms
+            {result.readOnly && ' · read-only transaction'}
+          </p>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,6 +1,8 @@
 import { FitAddon } from '@xterm/addon-fit';
 import { Terminal } from '@xterm/xterm';
 import { useEffect, useRef, useState } from 'react';
+import { parseError } from '@/core/errors/error-handler';
+import { databasesApi } from '@/features/databases/api/databases.api';
 import type { Container } from '@/shared/types/container';
 import { useContainerTerminal } from '../hooks/use-container-terminal';
 
@@ -13,7 +15,11 @@ export function TerminalTab({ container }: TerminalTabProps) {
   const xtermRef = useRef<Terminal | null>(null);
   const fitAddonRef = useRef<FitAddon | null>(null);
   const currentLineRef = useRef<string>('');
-  const { executeCommand } = useContainerTerminal(container.containerId);
+  // Saved commands, newest first, browsed with the arrow keys
+  const savedCommandsRef = useRef<string[]>([]);
+  const historyIndexRef = useRef<number>(-1);
+  const { executeCommand, loadSavedCommands, clearSavedCommands } =
+    useContainerTerminal(container.containerId, container.id);
   const [isReady, setIsReady] = useState(false);
 
   useEffect(() => {
@@ -74,6 +80,18 @@ export function TerminalTab({ container }: TerminalTabProps) {
     writePrompt(terminal);
     setIsReady(true);
 
+    loadSavedCommands()
+      .then((entries) => {
+        savedCommandsRef.current = entries.map((entry) => entry.command);
+      })
+      .catch((error) => console.error('Failed to load history:', error));
+
+    const replaceLine = (line: string) => {
+      terminal.write('\b \b'.repeat(currentLineRef.current.length));
+      currentLineRef.current = line;
+      terminal.write(line);
+    };
+
     // Handle user input
     terminal.onData((data) => {
       const code = data.charCodeAt(0);
@@ -84,12 +102,39 @@ export function TerminalTab({ container }: TerminalTabProps) {
         terminal.write('\r\n');
 
         if (command) {
-          executeCommandInTerminal(terminal, command);
+          if (/^history(\s|$)/.test(command)) {
+            runHistoryCommand(terminal, command);
+          } else if (/^snippet(\s|$)/.test(command)) {
+            runSnippetCommand(terminal, command);
+          } else {
+            savedCommandsRef.current = [
+              command,
+              ...savedCommandsRef.current.filter((saved) => saved !== command),
+            ];
+            executeCommandInTerminal(terminal, command);
+          }
         } else {
           writePrompt(terminal);
         }
 
         currentLineRef.current = '';
+        historyIndexRef.current = -1;
+      }
+      // Arrow up (older command)
+      else if (data === '\x1b[A') {
+        const next = historyIndexRef.current + 1;
+        if (next < savedCommandsRef.current.length) {
+          historyIndexRef.current = next;
+          replaceLine(savedCommandsRef.current[next]);
+        }
+      }
+      // Arrow down (newer command)
+      else if (data === '\x1b[B') {
+        if (historyIndexRef.current >= 0) {
+          const next = historyIndexRef.current - 1;
+          historyIndexRef.current = next;
+          replaceLine(next >= 0 ? savedCommandsRef.current[next] : '');
+        }
       }
       // Backspace key
       else if (code === 127) {
@@ -137,7 +182,7 @@ export function TerminalTab({ container }: TerminalTabProps) {
       xtermRef.current = null;
       fitAddonRef.current = null;
     };
-  }, [container.containerId, container.name]);
+  }, [container.containerId, container.name, loadSavedCommands]);
 
   /**
    * Write command prompt
@@ -146,6 +191,117 @@ export function TerminalTab({ container }: TerminalTabProps) {
     terminal.write(`\x1b[1;36m$\x1b[0m `);
   };
 
+  /**
+   * `history [text]` lists saved commands containing text, `history -c` clears them
+   */
+  const runHistoryCommand = async (terminal: Terminal, command: string) => {
+    const argument = command.slice('history'.length).trim();
+    try {
+      if (argument === '-c') {
+        await clearSavedCommands();
+        savedCommandsRef.current = [];
+      } else {
+        const entries = await loadSavedCommands(argument || undefined);
+        entries
+          .slice()
+          .reverse()
+          .forEach((entry, index) => {
+            terminal.write(
+              `\x1b[90m${String(index + 1).padStart(5)}\x1b[0m  ${entry.command}\r\n`,
+            );
+          });
+      }
+    } catch (error) {
+      const errorMessage =
+        error instanceof Error ? error.message : 'Unknown error';
+      terminal.write(`\x1b[1;31mError: ${errorMessage}\x1b[0m\r\n`);
+    } finally {
+      writePrompt(terminal);
+    }
+  };
+
+  /**
+   * `snippet` lists saved commands, `snippet <name> [variable=value...]` runs one and
+   * `snippet save <name> <command>` saves one for this container
+   * Names with spaces are quoted
+   */
+  const runSnippetCommand = async (terminal: Terminal, command: string) => {
+    const [name, ...assignments] = Array.from(
+      command.slice('snippet'.length).matchAll(/"([^"]*)"|(\S+)/g),
+      (match) => match[1] ?? match[2],
+    );
+
+    try {
+      const save = command.match(
+        /^snippet\s+save\s+("[^"]*"|\S+)\s+([\s\S]+)$/,
+      );
+      if (save) {
+        const saved = await databasesApi.saveSnippet({
+          id: '',
+          name: save[1].replace(/^"|"$/g, ''),
+          kind: 'command',
+          body: save[2],
+          databaseId: container.id,
+          dbType: null,
+          createdAt: '',
+          updatedAt: '',
+        });
+        terminal.write(`\x1b[90mSaved '${saved.name}'\x1b[0m\r\n`);
+        writePrompt(terminal);
+        return;
+      }
+
+      const snippets = (await databasesApi.listSnippets(container.id)).filter(
+        (snippet) => snippet.kind === 'command',
+      );
+
+      if (!name) {
+        if (snippets.length === 0) {
+          terminal.write('\x1b[90mNo saved commands\x1b[0m\r\n');
+        }
+        for (const snippet of snippets) {
+          terminal.write(
+            `\x1b[1m${snippet.name}\x1b[0m  \x1b[90m${snippet.body}\x1b[0m\r\n`,
+          );
+        }
+        writePrompt(terminal);
+        return;
+      }
+
+      const snippet = snippets.find(
+        (s) => s.name.toLowerCase() === name.toLowerCase(),
+      );
+      if (!snippet) {
+        terminal.write(
+          `\x1b[1;31mNo saved command named '${name}'\x1b[0m\r\n`,
+        );
+        writePrompt(terminal);
+        return;
+      }
+
+      const values = Object.fromEntries(
+        assignments
+          .filter((assignment) => assignment.includes('='))
+          .map((assignment) => {
+            const index = assignment.indexOf('=');
+            return [assignment.slice(0, index), assignment.slice(index + 1)];
+          }),
+      );
+      const rendered = await databasesApi.renderSnippet(
+        snippet.id,
+        container.id,
+        values,
+      );
+      terminal.write(`\x1b[90m${rendered}\x1b[0m\r\n`);
+      await executeCommandInTerminal(terminal, rendered);
+    } catch (error) {
+      terminal.write(
+        `\x1b[1;31mError: ${parseError(error).message}\x1b[0m\r\n`,
+      );
+      writePrompt(terminal);
+    }
+  };
+
   /**
    * Execute command and display output in terminal
    */
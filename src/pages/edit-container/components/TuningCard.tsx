@@ -0,0 +1,76 @@
+import { Gauge } from 'lucide-react';
+import { Badge } from '@/shared/components/ui/badge';
+import { Button } from '@/shared/components/ui/button';
+import { Card } from '@/shared/components/ui/card';
+import type { Container, TuningPreset } from '@/shared/types/container';
+import { useTuning } from '../hooks/use-tuning';
+
+interface TuningCardProps {
+  container: Container;
+  onRetuned?: () => void;
+}
+
+/**
+ * Memory settings of a database sized for its Docker host with a preset
+ */
+export function TuningCard({ container, onRetuned }: TuningCardProps) {
+  const tuning = useTuning(container, onRetuned);
+  const host = tuning.preview?.tuning.host;
+
+  return (
+    <Card className="p-4">
+      <div className="flex items-center gap-2 mb-3">
+        <Gauge className="h-4 w-4 text-primary" />
+        <h3 className="text-sm font-semibold">Tuning</h3>
+        <Badge
+          variant={container.tuning ? 'default' : 'secondary'}
+          className="ml-auto"
+        >
+          {container.tuning?.preset ?? 'untuned'}
+        </Badge>
+      </div>
+      <div className="space-y-3 text-sm">
+        <div className="flex items-center gap-2">
+          <select
+            value={tuning.preset}
+            onChange={(e) => tuning.setPreset(e.target.value as TuningPreset)}
+            className="h-8 rounded-md border border-border bg-transparent px-2 text-xs"
+            aria-label="Tuning preset"
+          >
+            <option value="small">Small</option>
+            <option value="medium">Medium</option>
+            <option value="large">Large</option>
+          </select>
+          <p className="text-xs text-muted-foreground flex-1">
+            {host && tuning.preview
+              ? `${tuning.preview.budgetMb} MB of ${host.memoryMb} MB, ${host.cpus} CPUs on the host.`
+              : 'Measuring the host...'}
+          </p>
+          <Button
+            type="button"
+            variant="outline"
+            size="sm"
+            disabled={tuning.busy || !tuning.preview}
+            onClick={tuning.retune}
+          >
+            {container.tuning ? 'Re-tune' : 'Tune'}
+          </Button>
+        </div>
+        {tuning.preview?.settings.map((setting) => (
+          <div
+            key={setting.name}
+            className="flex justify-between items-center text-xs"
+          >
+            <span className="font-mono text-muted-foreground">
+              {setting.name}
+            </span>
+            <span className="font-mono">{setting.value}</span>
+          </div>
+        ))}
+        <p className="text-xs text-muted-foreground">
+          Applying the settings recreates the container.
+        </p>
+      </div>
+    </Card>
+  );
+}
@@ -0,0 +1,81 @@
+import { useCallback, useEffect, useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import {
+  type CompanionContainer,
+  databasesApi,
+  type PoolerKind,
+} from '@/features/databases/api/databases.api';
+
+const POOLER_KINDS: PoolerKind[] = ['pgbouncer', 'proxysql'];
+
+/**
+ * Pooler each engine can sit behind, null for engines without one
+ */
+export function poolerKindFor(dbType: string): PoolerKind | null {
+  switch (dbType) {
+    case 'PostgreSQL':
+      return 'pgbouncer';
+    case 'MySQL':
+    case 'MariaDB':
+      return 'proxysql';
+    default:
+      return null;
+  }
+}
+
+/**
+ * Hook for the connection pooler deployed in front of a database
+ */
+export function useConnectionPooler(containerId: string, dbType: string) {
+  const kind = poolerKindFor(dbType);
+  const [pooler, setPooler] = useState<CompanionContainer | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const refresh = useCallback(async () => {
+    if (!kind) return;
+
+    try {
+      const companions = await databasesApi.getCompanions(containerId);
+      setPooler(
+        companions.find((c) =>
+          POOLER_KINDS.includes(c.kind as PoolerKind),
+        ) ?? null,
+      );
+    } catch (error) {
+      console.error('Error loading connection pooler:', error);
+    }
+  }, [containerId, kind]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const deploy = useCallback(async () => {
+    if (!kind) return;
+
+    setBusy(true);
+    try {
+      setPooler(await databasesApi.deployCompanion(containerId, kind));
+    } catch (error) {
+      showErrorToast(error);
+    } finally {
+      setBusy(false);
+    }
+  }, [containerId, kind]);
+
+  const remove = useCallback(async () => {
+    if (!pooler) return;
+
+    setBusy(true);
+    try {
+      await databasesApi.removeCompanion(pooler.id);
+      setPooler(null);
+    } catch (error) {
+      showErrorToast(error);
+    } finally {
+      setBusy(false);
+    }
+  }, [pooler]);
+
+  return { kind, pooler, busy, deploy, remove };
+}
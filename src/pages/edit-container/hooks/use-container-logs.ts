@@ -27,7 +27,11 @@ function trimLogsToLimit(logs: string[], maxLines: number): string[] {
   return logs.slice(logs.length - maxLines);
 }
 
-export function useContainerLogs(containerId?: string, enabled = true) {
+export function useContainerLogs(
+  containerId?: string,
+  enabled = true,
+  tailLines = 500,
+) {
   const [logs, setLogs] = useState<string[]>([]);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -55,7 +59,7 @@ export function useContainerLogs(containerId?: string, enabled = true) {
       // Call Tauri command to get logs
       const logsString = await invoke<string>('get_container_logs', {
         containerId,
-        tailLines: 500, // Get last 500 lines
+        tailLines,
       });
 
       if (!isMounted.current) {
@@ -120,7 +124,7 @@ export function useContainerLogs(containerId?: string, enabled = true) {
         intervalRef.current = null;
       }
     }
-  }, [containerId, enabled, error]);
+  }, [containerId, enabled, tailLines, error]);
 
   const startPolling = useCallback(() => {
     if (!containerId || !enabled || isPolling) {
@@ -166,7 +170,7 @@ export function useContainerLogs(containerId?: string, enabled = true) {
       isMounted.current = false;
       stopPolling();
     };
-  }, [containerId, enabled]);
+  }, [containerId, enabled, tailLines]);
 
   return {
     logs,
@@ -0,0 +1,70 @@
+import { useCallback, useEffect, useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import {
+  databasesApi,
+  type ShareStatus,
+} from '@/features/databases/api/databases.api';
+
+/**
+ * Hook for the public share of a database, kept current through `share-status-changed`
+ */
+export function useContainerShare(containerId: string) {
+  const [status, setStatus] = useState<ShareStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    databasesApi
+      .getShareStatus(containerId)
+      .then(setStatus)
+      .catch((error) => console.error('Error loading share status:', error));
+
+    const setupListener = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+
+        unlisten = await listen<ShareStatus>('share-status-changed', (event) => {
+          if (event.payload.databaseId === containerId) {
+            setStatus(event.payload);
+          }
+        });
+      } catch (error) {
+        console.error('Error setting up share listener:', error);
+      }
+    };
+
+    setupListener();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [containerId]);
+
+  const start = useCallback(
+    async (minutes?: number) => {
+      setBusy(true);
+      try {
+        setStatus(await databasesApi.startShare(containerId, minutes));
+      } catch (error) {
+        showErrorToast(error);
+      } finally {
+        setBusy(false);
+      }
+    },
+    [containerId],
+  );
+
+  const stop = useCallback(async () => {
+    setBusy(true);
+    try {
+      setStatus(await databasesApi.stopShare(containerId));
+    } catch (error) {
+      showErrorToast(error);
+    } finally {
+      setBusy(false);
+    }
+  }, [containerId]);
+
+  return { status, busy, start, stop };
+}
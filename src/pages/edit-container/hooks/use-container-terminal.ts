@@ -7,6 +7,15 @@ interface CommandOutput {
   exitCode: number;
 }
 
+/**
+ * Command saved in the terminal history of a database, secrets scrubbed
+ */
+export interface ExecHistoryEntry {
+  command: string;
+  exitCode: number;
+  ranAt: string;
+}
+
 interface TerminalHistoryEntry {
   command: string;
   stdout: string;
@@ -21,7 +30,10 @@ interface TerminalHistoryEntry {
  */
 const MAX_HISTORY = 100;
 
-export function useContainerTerminal(containerId?: string) {
+export function useContainerTerminal(
+  containerId?: string,
+  databaseId?: string,
+) {
   const [history, setHistory] = useState<TerminalHistoryEntry[]>([]);
   const [isExecuting, setIsExecuting] = useState(false);
 
@@ -81,10 +93,31 @@ export function useContainerTerminal(containerId?: string) {
     setHistory([]);
   }, []);
 
+  /**
+   * Commands saved across sessions, newest first
+   */
+  const loadSavedCommands = useCallback(
+    async (query?: string): Promise<ExecHistoryEntry[]> => {
+      if (!databaseId) return [];
+      return invoke<ExecHistoryEntry[]>('get_exec_history', {
+        containerId: databaseId,
+        query,
+      });
+    },
+    [databaseId],
+  );
+
+  const clearSavedCommands = useCallback(async () => {
+    if (!databaseId) return;
+    await invoke<void>('clear_exec_history', { containerId: databaseId });
+  }, [databaseId]);
+
   return {
     executeCommand,
     isExecuting,
     history,
     clearHistory,
+    loadSavedCommands,
+    clearSavedCommands,
   };
 }
@@ -1,7 +1,7 @@
-import { emit } from '@tauri-apps/api/event';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { useCallback, useEffect, useState } from 'react';
 import { useForm } from 'react-hook-form';
+import { parseError } from '@/core/errors/error-handler';
 import { databasesApi } from '@/features/databases/api/databases.api';
 import { databaseRegistry } from '@/features/databases/registry/database-registry';
 import type { DockerRunRequest } from '@/features/databases/types/docker.types';
@@ -68,6 +68,11 @@ export function useDatabaseEditWizard(containerId: string) {
         'containerConfiguration.maxConnections',
         loadedContainer.maxConnections,
       );
+      setValue(
+        'containerConfiguration.extraArgs',
+        loadedContainer.extraArgs.join(' '),
+      );
+      setValue('containerConfiguration.region', loadedContainer.region ?? {});
 
       console.log('✅ Loaded container for editing:', loadedContainer);
     } catch (error) {
@@ -102,6 +107,9 @@ export function useDatabaseEditWizard(containerId: string) {
           password: container.password,
           databaseName: container.databaseName,
           maxConnections: container.maxConnections,
+          locale: container.locale,
+          extraArgs: container.extraArgs.join(' '),
+          region: container.region ?? {},
         },
       });
     }
@@ -160,6 +168,13 @@ export function useDatabaseEditWizard(containerId: string) {
           persistData: containerConfiguration.persistData ?? true,
           enableAuth: containerConfiguration.enableAuth ?? true,
           maxConnections: containerConfiguration.maxConnections,
+          locale: containerConfiguration.locale,
+          enableTls: container.enableTls ?? false,
+          extraArgs: String(containerConfiguration.extraArgs ?? '')
+            .split(/\s+/)
+            .filter(Boolean),
+          region: containerConfiguration.region,
+          tuning: container.tuning,
         },
       };
     },
@@ -201,10 +216,27 @@ export function useDatabaseEditWizard(containerId: string) {
 
         // Use the new unified databases API
         console.log('🔄 Updating container with Docker args:', dockerRequest);
-        const updatedContainer = await databasesApi.update(
-          container.id,
-          dockerRequest,
-        );
+        let updatedContainer: Container;
+        try {
+          updatedContainer = await databasesApi.update(
+            container.id,
+            dockerRequest,
+          );
+        } catch (error) {
+          // Updates that delete data only go ahead once confirmed
+          const appError = parseError(error);
+          if (appError.type !== 'DATA_LOSS') {
+            throw error;
+          }
+          if (!window.confirm(`${appError.message}.\n\nSave anyway?`)) {
+            return;
+          }
+          updatedContainer = await databasesApi.update(
+            container.id,
+            dockerRequest,
+            true,
+          );
+        }
 
         // Update local container state
         setContainer(updatedContainer);
@@ -221,15 +253,13 @@ export function useDatabaseEditWizard(containerId: string) {
             password: updatedContainer.password,
             databaseName: updatedContainer.databaseName,
             maxConnections: updatedContainer.maxConnections,
+            locale: updatedContainer.locale,
+            extraArgs: updatedContainer.extraArgs.join(' '),
+            region: updatedContainer.region ?? {},
           },
         });
 
-        // Emit event to notify main window
-        try {
-          await emit('container-updated', { container: updatedContainer });
-        } catch (eventError) {
-          console.warn('Error emitting event:', eventError);
-        }
+        // The backend emits 'container-updated' to every window
 
         console.log('✅ Container updated successfully');
       } catch (error) {
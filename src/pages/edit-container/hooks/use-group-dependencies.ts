@@ -0,0 +1,55 @@
+import { useCallback, useEffect, useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import {
+  type ContainerGroup,
+  databasesApi,
+} from '@/features/databases/api/databases.api';
+import type { Container } from '@/shared/types/container';
+
+/**
+ * Hook for the members of its group a container waits for when the group
+ * starts
+ */
+export function useGroupDependencies(containerId: string, project?: string) {
+  const [group, setGroup] = useState<ContainerGroup | null>(null);
+  const [members, setMembers] = useState<Container[]>([]);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    if (!project) return;
+
+    Promise.all([databasesApi.getGroups(), databasesApi.getAll()])
+      .then(([groups, containers]) => {
+        setGroup(groups.find((g) => g.name === project) ?? null);
+        setMembers(
+          containers.filter(
+            (c) => c.project === project && c.id !== containerId,
+          ),
+        );
+      })
+      .catch((error) => console.error('Error loading group:', error));
+  }, [containerId, project]);
+
+  const dependsOn = group?.dependencies[containerId] ?? [];
+
+  const toggle = useCallback(
+    async (memberId: string) => {
+      const next = dependsOn.includes(memberId)
+        ? dependsOn.filter((id) => id !== memberId)
+        : [...dependsOn, memberId];
+      setBusy(true);
+      try {
+        setGroup(
+          await databasesApi.setContainerDependencies(containerId, next),
+        );
+      } catch (error) {
+        showErrorToast(error);
+      } finally {
+        setBusy(false);
+      }
+    },
+    [containerId, dependsOn],
+  );
+
+  return { group, members, dependsOn, busy, toggle };
+}
@@ -0,0 +1,67 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@/core/tauri/invoke';
+
+export type LogSeverity =
+  | 'debug'
+  | 'info'
+  | 'notice'
+  | 'warning'
+  | 'error'
+  | 'fatal';
+
+/**
+ * How the logs of a container are shown, saved per container
+ */
+export interface LogViewPreferences {
+  tailLines: number;
+  wrap: boolean;
+  follow: boolean;
+  // Entries below this severity are hidden
+  minSeverity?: LogSeverity | null;
+}
+
+const DEFAULT_LOG_PREFERENCES: LogViewPreferences = {
+  tailLines: 500,
+  wrap: true,
+  follow: true,
+  minSeverity: null,
+};
+
+/**
+ * Load the log preferences of a container and save every change
+ */
+export function useLogPreferences(containerId?: string) {
+  const [preferences, setPreferences] = useState<LogViewPreferences>(
+    DEFAULT_LOG_PREFERENCES,
+  );
+  const [loaded, setLoaded] = useState(false);
+
+  useEffect(() => {
+    if (!containerId) return;
+
+    invoke<LogViewPreferences>('get_log_preferences', { containerId })
+      .then(setPreferences)
+      .catch((error) => console.error('Failed to load log preferences:', error))
+      .finally(() => setLoaded(true));
+  }, [containerId]);
+
+  const updatePreferences = useCallback(
+    async (changes: Partial<LogViewPreferences>) => {
+      const next = { ...preferences, ...changes };
+      setPreferences(next);
+      if (!containerId) return;
+
+      try {
+        await invoke<LogViewPreferences>('set_log_preferences', {
+          containerId,
+          preferences: next,
+        });
+      } catch (error) {
+        console.error('Failed to save log preferences:', error);
+      }
+    },
+    [containerId, preferences],
+  );
+
+  return { preferences, loaded, updatePreferences };
+}
@@ -0,0 +1,111 @@
+import { useCallback, useEffect, useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import {
+  databasesApi,
+  type LogicalReplicationStatus,
+  type Publication,
+  type ReplicationSlot,
+} from '@/features/databases/api/databases.api';
+
+/**
+ * Hook for the logical replication settings, publications and slots of a
+ * running PostgreSQL database
+ */
+export function useLogicalReplication(containerId: string, enabled: boolean) {
+  const [status, setStatus] = useState<LogicalReplicationStatus | null>(null);
+  const [publications, setPublications] = useState<Publication[]>([]);
+  const [slots, setSlots] = useState<ReplicationSlot[]>([]);
+  const [busy, setBusy] = useState(false);
+
+  const reload = useCallback(async () => {
+    try {
+      const [nextStatus, nextPublications, nextSlots] = await Promise.all([
+        databasesApi.getLogicalReplication(containerId),
+        databasesApi.listPublications(containerId),
+        databasesApi.listReplicationSlots(containerId),
+      ]);
+      setStatus(nextStatus);
+      setPublications(nextPublications);
+      setSlots(nextSlots);
+    } catch (error) {
+      console.error('Error loading logical replication:', error);
+    }
+  }, [containerId]);
+
+  useEffect(() => {
+    if (enabled) reload();
+  }, [enabled, reload]);
+
+  // Runs one change, errors end up in a toast
+  const apply = useCallback(async (change: () => Promise<void>) => {
+    setBusy(true);
+    try {
+      await change();
+      return true;
+    } catch (error) {
+      showErrorToast(error);
+      return false;
+    } finally {
+      setBusy(false);
+    }
+  }, []);
+
+  const setReplication = useCallback(
+    (on: boolean) =>
+      apply(async () => {
+        setStatus(
+          await databasesApi.setLogicalReplication(containerId, {
+            enabled: on,
+          }),
+        );
+      }),
+    [apply, containerId],
+  );
+
+  const createPublication = useCallback(
+    (name: string, tables: string[]) =>
+      apply(async () => {
+        setPublications(
+          await databasesApi.createPublication(containerId, name, tables),
+        );
+      }),
+    [apply, containerId],
+  );
+
+  const dropPublication = useCallback(
+    (name: string) =>
+      apply(async () => {
+        setPublications(await databasesApi.dropPublication(containerId, name));
+      }),
+    [apply, containerId],
+  );
+
+  const createSlot = useCallback(
+    (name: string) =>
+      apply(async () => {
+        setSlots(await databasesApi.createReplicationSlot(containerId, name));
+      }),
+    [apply, containerId],
+  );
+
+  const dropSlot = useCallback(
+    (name: string) =>
+      apply(async () => {
+        setSlots(await databasesApi.dropReplicationSlot(containerId, name));
+      }),
+    [apply, containerId],
+  );
+
+  return {
+    status,
+    publications,
+    slots,
+    busy,
+    reload,
+    setReplication,
+    createPublication,
+    dropPublication,
+    createSlot,
+    dropSlot,
+  };
+}
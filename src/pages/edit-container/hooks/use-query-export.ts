@@ -0,0 +1,65 @@
+import { useCallback, useEffect, useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import {
+  databasesApi,
+  type QueryExportFormat,
+  type QueryExportProgress,
+  type QueryExportResult,
+} from '@/features/databases/api/databases.api';
+
+/**
+ * Hook exporting console query results to a file, rows written arrive through
+ * `query-export-progress`
+ */
+export function useQueryExport(containerId: string) {
+  const [exporting, setExporting] = useState(false);
+  const [rowsWritten, setRowsWritten] = useState(0);
+  const [result, setResult] = useState<QueryExportResult | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupListener = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+
+        unlisten = await listen<QueryExportProgress>(
+          'query-export-progress',
+          (event) => {
+            if (event.payload.containerId === containerId) {
+              setRowsWritten(event.payload.rowsWritten);
+            }
+          },
+        );
+      } catch (error) {
+        console.error('Error setting up query export listener:', error);
+      }
+    };
+
+    setupListener();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [containerId]);
+
+  const exportQuery = useCallback(
+    async (sql: string, path: string, format: QueryExportFormat) => {
+      setExporting(true);
+      setRowsWritten(0);
+      setResult(null);
+      try {
+        setResult(
+          await databasesApi.exportQuery(containerId, sql, path, format),
+        );
+      } catch (error) {
+        showErrorToast(error);
+      } finally {
+        setExporting(false);
+      }
+    },
+    [containerId],
+  );
+
+  return { exporting, rowsWritten, result, exportQuery };
+}
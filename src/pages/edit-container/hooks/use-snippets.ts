@@ -0,0 +1,73 @@
+import { useCallback, useEffect, useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import {
+  databasesApi,
+  type Snippet,
+  type SnippetKind,
+} from '@/features/databases/api/databases.api';
+
+/**
+ * Hook for the saved snippets of one kind usable with a container
+ */
+export function useSnippets(containerId: string, kind: SnippetKind) {
+  const [snippets, setSnippets] = useState<Snippet[]>([]);
+
+  const reload = useCallback(async () => {
+    try {
+      const all = await databasesApi.listSnippets(containerId);
+      setSnippets(all.filter((snippet) => snippet.kind === kind));
+    } catch (error) {
+      console.error('Error loading snippets:', error);
+    }
+  }, [containerId, kind]);
+
+  useEffect(() => {
+    reload();
+  }, [reload]);
+
+  /**
+   * Save `body` under `name`, for this container or for every database of `dbType`
+   */
+  const save = useCallback(
+    async (name: string, body: string, dbType?: string) => {
+      try {
+        await databasesApi.saveSnippet({
+          id: '',
+          name,
+          kind,
+          body,
+          databaseId: dbType ? null : containerId,
+          dbType: dbType ?? null,
+          createdAt: '',
+          updatedAt: '',
+        });
+        await reload();
+        return true;
+      } catch (error) {
+        showErrorToast(error);
+        return false;
+      }
+    },
+    [containerId, kind, reload],
+  );
+
+  const remove = useCallback(
+    async (snippetId: string) => {
+      try {
+        await databasesApi.deleteSnippet(snippetId);
+        await reload();
+      } catch (error) {
+        showErrorToast(error);
+      }
+    },
+    [reload],
+  );
+
+  const render = useCallback(
+    (snippetId: string, values: Record<string, string> = {}) =>
+      databasesApi.renderSnippet(snippetId, containerId, values),
+    [containerId],
+  );
+
+  return { snippets, reload, save, remove, render };
+}
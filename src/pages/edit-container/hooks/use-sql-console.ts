@@ -0,0 +1,65 @@
+import { useCallback, useEffect, useState } from 'react';
+import { parseError, showErrorToast } from '@/core/errors/error-handler';
+import {
+  databasesApi,
+  type SqlConsoleResult,
+  type SqlGuardStatus,
+} from '@/features/databases/api/databases.api';
+
+/**
+ * Hook running SQL in a database's console, with its read-only guard
+ */
+export function useSqlConsole(containerId: string) {
+  const [guard, setGuard] = useState<SqlGuardStatus | null>(null);
+  const [result, setResult] = useState<SqlConsoleResult | null>(null);
+  const [error, setError] = useState<{
+    message: string;
+    blocked: boolean;
+  } | null>(null);
+  const [running, setRunning] = useState(false);
+
+  useEffect(() => {
+    databasesApi
+      .getSqlGuard(containerId)
+      .then(setGuard)
+      .catch((e) => console.error('Error loading SQL guard:', e));
+  }, [containerId]);
+
+  const run = useCallback(
+    async (sql: string) => {
+      if (!sql.trim()) return;
+
+      setRunning(true);
+      setError(null);
+      try {
+        setResult(await databasesApi.runSql(containerId, sql));
+      } catch (e) {
+        const appError = parseError(e);
+        setResult(null);
+        setError({
+          message: appError.details
+            ? `${appError.message}\n${appError.details}`
+            : appError.message,
+          blocked: appError.type === 'WRITE_BLOCKED',
+        });
+      } finally {
+        setRunning(false);
+      }
+    },
+    [containerId],
+  );
+
+  const setWritesUnlocked = useCallback(
+    async (unlocked: boolean) => {
+      try {
+        setGuard(await databasesApi.setSqlWritesUnlocked(containerId, unlocked));
+        setError(null);
+      } catch (e) {
+        showErrorToast(e);
+      }
+    },
+    [containerId],
+  );
+
+  return { guard, result, error, running, run, setWritesUnlocked };
+}
@@ -0,0 +1,119 @@
+import { useCallback, useEffect, useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import {
+  databasesApi,
+  type SqlJob,
+} from '@/features/databases/api/databases.api';
+
+/**
+ * Hook for the scheduled SQL jobs of a container, runs arrive through `sql-job-completed`
+ */
+export function useSqlJobs(containerId: string) {
+  const [jobs, setJobs] = useState<SqlJob[]>([]);
+  const [runningId, setRunningId] = useState<string | null>(null);
+
+  const reload = useCallback(async () => {
+    try {
+      setJobs(await databasesApi.listSqlJobs(containerId));
+    } catch (error) {
+      console.error('Error loading SQL jobs:', error);
+    }
+  }, [containerId]);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    reload();
+
+    const setupListener = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+
+        unlisten = await listen<SqlJob>('sql-job-completed', (event) => {
+          if (event.payload.databaseId === containerId) {
+            setJobs((previous) =>
+              previous.map((job) =>
+                job.id === event.payload.id ? event.payload : job,
+              ),
+            );
+          }
+        });
+      } catch (error) {
+        console.error('Error setting up SQL job listener:', error);
+      }
+    };
+
+    setupListener();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [containerId, reload]);
+
+  /**
+   * Create a job running `sql` on the `cron` schedule
+   */
+  const create = useCallback(
+    async (name: string, sql: string, cron: string) => {
+      try {
+        await databasesApi.saveSqlJob({
+          id: '',
+          name,
+          databaseId: containerId,
+          sql,
+          cron,
+          enabled: true,
+          notifyOnFailure: true,
+          createdAt: '',
+          runs: [],
+        });
+        await reload();
+        return true;
+      } catch (error) {
+        showErrorToast(error);
+        return false;
+      }
+    },
+    [containerId, reload],
+  );
+
+  const update = useCallback(
+    async (job: SqlJob) => {
+      try {
+        await databasesApi.saveSqlJob(job);
+        await reload();
+      } catch (error) {
+        showErrorToast(error);
+      }
+    },
+    [reload],
+  );
+
+  const remove = useCallback(
+    async (jobId: string) => {
+      try {
+        await databasesApi.deleteSqlJob(jobId);
+        await reload();
+      } catch (error) {
+        showErrorToast(error);
+      }
+    },
+    [reload],
+  );
+
+  const runNow = useCallback(async (jobId: string) => {
+    setRunningId(jobId);
+    try {
+      const updated = await databasesApi.runSqlJob(jobId);
+      setJobs((previous) =>
+        previous.map((job) => (job.id === updated.id ? updated : job)),
+      );
+    } catch (error) {
+      showErrorToast(error);
+    } finally {
+      setRunningId(null);
+    }
+  }, []);
+
+  return { jobs, runningId, create, update, remove, runNow };
+}
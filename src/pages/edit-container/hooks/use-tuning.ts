@@ -0,0 +1,40 @@
+import { useCallback, useEffect, useState } from 'react';
+import { showErrorToast } from '@/core/errors/error-handler';
+import {
+  databasesApi,
+  type TuningPreview,
+} from '@/features/databases/api/databases.api';
+import type { Container, TuningPreset } from '@/shared/types/container';
+
+/**
+ * Hook for the memory tuning of a database: settings a preset would give it on
+ * its host now, and re-tuning, which recreates the container
+ */
+export function useTuning(container: Container, onRetuned?: () => void) {
+  const [preset, setPreset] = useState<TuningPreset>(
+    container.tuning?.preset ?? 'medium',
+  );
+  const [preview, setPreview] = useState<TuningPreview | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    databasesApi
+      .previewTuning(container.dbType, preset, container.host)
+      .then(setPreview)
+      .catch((error) => console.error('Error loading tuning preview:', error));
+  }, [container.dbType, container.host, preset]);
+
+  const retune = useCallback(async () => {
+    setBusy(true);
+    try {
+      await databasesApi.retuneContainer(container.id, preset);
+      onRetuned?.();
+    } catch (error) {
+      showErrorToast(error);
+    } finally {
+      setBusy(false);
+    }
+  }, [container.id, preset, onRetuned]);
+
+  return { preset, setPreset, preview, busy, retune };
+}
@@ -3,6 +3,7 @@ export enum ContainerTab {
   Configuration = 'configuration',
   Logs = 'logs',
   Terminal = 'terminal',
+  Sql = 'sql',
 }
 
 export const TAB_LABELS: Record<ContainerTab, string> = {
@@ -10,4 +11,5 @@ export const TAB_LABELS: Record<ContainerTab, string> = {
   [ContainerTab.Configuration]: 'Configuration',
   [ContainerTab.Logs]: 'Logs',
   [ContainerTab.Terminal]: 'Terminal',
+  [ContainerTab.Sql]: 'SQL',
 };
@@ -1,5 +1,8 @@
 import type { ComponentType } from 'react';
-import type { ContainerStatus } from '@/shared/types/container';
+import {
+  type ContainerStatus,
+  isContainerRunning,
+} from '@/shared/types/container';
 
 export interface ActionConfig {
   id: string;
@@ -21,7 +24,7 @@ export function isActionAvailable(
   action: ActionConfig,
   containerStatus: ContainerStatus,
 ): boolean {
-  const isRunning = containerStatus === 'running';
+  const isRunning = isContainerRunning(containerStatus);
 
   // If action requires running container but it's not running
   if (action.requiresRunning && !isRunning) {
@@ -41,7 +44,7 @@ export function isTabAvailable(
   tab: TabConfig,
   containerStatus: ContainerStatus,
 ): boolean {
-  const isRunning = containerStatus === 'running';
+  const isRunning = isContainerRunning(containerStatus);
 
   // If tab requires running container but it's not running
   if (tab.requiresRunning && !isRunning) {
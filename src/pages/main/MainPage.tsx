@@ -1,6 +1,7 @@
 import { useEffect } from 'react';
 import { Toaster } from 'sonner';
 import { useAppUpdater } from '../../features/app/hooks/use-app-updater';
+import { useDockerHosts } from '../../features/docker/hooks/use-docker-hosts';
 import { DeleteConfirmationDialog } from '../../shared/components/DeleteConfirmationDialog';
 import { DockerUnavailableOverlay } from '../../shared/components/DockerUnavailableOverlay';
 import { DatabaseManager } from './components/DatabaseManager';
@@ -13,6 +14,7 @@ export function MainPage() {
   const search = useContainerSearch(page.containers);
   const stats = useContainerStats(page.containers);
   const updater = useAppUpdater();
+  const { hosts } = useDockerHosts();
 
   /**
    * Auto-check for updates on app start (silent mode)
@@ -26,6 +28,7 @@ export function MainPage() {
     <div className="h-screen w-full bg-background">
       <DatabaseManager
         containers={search.filteredContainers}
+        hosts={hosts}
         stats={stats}
         searchQuery={search.searchQuery}
         onSearchChange={search.setSearchQuery}
@@ -58,7 +61,10 @@ export function MainPage() {
               : page.dockerStatus?.status || 'error'
           }
           error={page.dockerStatus?.error}
+          guidance={page.dockerStatus?.guidance}
           onRetry={page.refreshDockerStatus}
+          onStart={page.startDocker}
+          startProgress={page.dockerStartProgress}
           isRetrying={page.dockerRefreshing}
         />
       )}
@@ -16,11 +16,17 @@ import { useAppVersion } from '../../../features/app/hooks/use-app-version';
 import { Badge } from '../../../shared/components/ui/badge';
 import { Button } from '../../../shared/components/ui/button';
 import { Input } from '../../../shared/components/ui/input';
-import { Container } from '../../../shared/types/container';
+import {
+  Container,
+  formatContainerStatus,
+  isContainerRunning,
+} from '../../../shared/types/container';
+import type { DockerHostStatus } from '../../../shared/types/docker';
 import type { ContainerStats } from '../hooks/use-container-stats';
 
 interface DatabaseManagerProps {
   containers: Container[];
+  hosts: DockerHostStatus[];
   stats: ContainerStats;
   searchQuery: string;
   onSearchChange: (query: string) => void;
@@ -43,6 +49,7 @@ interface DatabaseManagerProps {
  */
 export function DatabaseManager({
   containers,
+  hosts,
   stats,
   searchQuery,
   onSearchChange,
@@ -64,10 +71,15 @@ export function DatabaseManager({
     switch (status) {
       case 'running':
         return 'bg-green-500/10 text-green-500 border-green-500/20';
+      case 'unhealthy':
+      case 'restarting':
+        return 'bg-yellow-500/10 text-yellow-500 border-yellow-500/20';
       case 'creating':
+      case 'starting':
       case 'removing':
         return 'bg-muted text-muted-foreground border-border';
       case 'error':
+      case 'exited':
         return 'bg-destructive/10 text-destructive border-destructive/20';
       default:
         return 'bg-muted text-muted-foreground border-border';
@@ -79,15 +91,25 @@ export function DatabaseManager({
       case 'running':
         return <Activity className="w-3 h-3 animate-pulse text-green-500" />;
       case 'creating':
+      case 'starting':
       case 'removing':
         return <Square className="w-3 h-3" />;
+      case 'unhealthy':
+      case 'restarting':
       case 'error':
+      case 'exited':
         return <Zap className="w-3 h-3" />;
       default:
         return <Square className="w-3 h-3" />;
     }
   };
 
+  // Only shown once remote hosts are configured
+  const showHosts = hosts.length > 1;
+
+  const getHost = (container: Container) =>
+    hosts.find((host) => host.id === (container.host ?? 'local'));
+
   const getDatabaseIcon = (_type: string) => {
     return <Database className="w-6 h-6 text-primary" />;
   };
@@ -234,18 +256,33 @@ export function DatabaseManager({
                         className={`text-xs px-2 py-0.5 gap-1 ${getStatusColor(container.status)}`}
                       >
                         {getStatusIcon(container.status)}
-                        {container.status}
+                        {formatContainerStatus(container)}
                       </Badge>
                     </div>
                     <p className="text-sm text-muted-foreground truncate">
-                      localhost:{container.port} • {container.dbType}{' '}
+                      {container.host ? 'port ' : 'localhost:'}
+                      {container.port} • {container.dbType}{' '}
                       {container.version}
+                      {showHosts && (
+                        <span
+                          className={
+                            getHost(container)?.reachable === false
+                              ? 'text-destructive'
+                              : undefined
+                          }
+                        >
+                          {' '}
+                          • {getHost(container)?.name ?? container.host}
+                          {getHost(container)?.reachable === false &&
+                            ' (unreachable)'}
+                        </span>
+                      )}
                     </p>
                   </div>
 
                   {/* Action Buttons - always visible */}
                   <div className="flex items-center gap-2">
-                    {container.status === 'running' ? (
+                    {isContainerRunning(container.status) ? (
                       <Button
                         size="sm"
                         variant="outline"
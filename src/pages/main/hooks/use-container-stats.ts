@@ -1,5 +1,8 @@
 import { useMemo } from 'react';
-import type { Container } from '../../../shared/types/container';
+import {
+  type Container,
+  isContainerRunning,
+} from '../../../shared/types/container';
 
 export interface ContainerStats {
   total: number;
@@ -13,11 +16,13 @@ export interface ContainerStats {
  */
 export function useContainerStats(containers: Container[]): ContainerStats {
   return useMemo(() => {
-    const running = containers.filter((c) => c.status === 'running').length;
-    const errors = containers.filter((c) => c.status === 'error').length;
-    const stopped = containers.filter(
-      (c) => c.status !== 'running' && c.status !== 'error',
+    const running = containers.filter((c) =>
+      isContainerRunning(c.status),
     ).length;
+    const errors = containers.filter(
+      (c) => c.status === 'error' || c.status === 'exited',
+    ).length;
+    const stopped = containers.length - running - errors;
 
     return {
       total: containers.length,
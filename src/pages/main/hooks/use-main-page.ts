@@ -1,12 +1,23 @@
 import { invoke } from '@tauri-apps/api/core';
 import { useCallback, useEffect, useState } from 'react';
+import { toast } from 'sonner';
 import { useApp } from '../../../features/app/use-app';
-import type { Container } from '../../../shared/types/container';
+import {
+  databasesApi,
+  type OrphanContainer,
+  type RecoveredCreate,
+} from '../../../features/databases/api/databases.api';
+import {
+  type Container,
+  isContainerRunning,
+} from '../../../shared/types/container';
 
 /**
  * Main hook for MainPage
  * Handles:
- * - Tauri event listeners (container-created, container-updated)
+ * - Tauri event listeners (container-created, container-updated,
+ *   container-removed, container-status-changed, workspace-changed,
+ *   orphans-detected, recovery-completed, background-error)
  * - Dialog state (config, delete)
  * - Navigation to creation/edit windows
  * - Container actions (start, stop, delete)
@@ -26,6 +37,12 @@ export function useMainPage() {
   useEffect(() => {
     let unlistenContainerCreated: (() => void) | undefined;
     let unlistenContainerUpdated: (() => void) | undefined;
+    let unlistenContainerRemoved: (() => void) | undefined;
+    let unlistenStatusChanged: (() => void) | undefined;
+    let unlistenWorkspaceChanged: (() => void) | undefined;
+    let unlistenOrphansDetected: (() => void) | undefined;
+    let unlistenRecoveryCompleted: (() => void) | undefined;
+    let unlistenBackgroundError: (() => void) | undefined;
 
     const setupListeners = async () => {
       try {
@@ -38,6 +55,72 @@ export function useMainPage() {
         unlistenContainerUpdated = await listen('container-updated', () => {
           app.loadContainers();
         });
+
+        unlistenContainerRemoved = await listen('container-removed', () => {
+          app.loadContainers();
+        });
+
+        unlistenStatusChanged = await listen(
+          'container-status-changed',
+          () => {
+            app.loadContainers();
+          },
+        );
+
+        unlistenWorkspaceChanged = await listen('workspace-changed', () => {
+          app.loadContainers();
+        });
+
+        unlistenOrphansDetected = await listen<{ orphans: OrphanContainer[] }>(
+          'orphans-detected',
+          ({ payload }) => {
+            if (payload.orphans.length === 0) return;
+
+            const names = payload.orphans.map((orphan) => orphan.name);
+            toast.warning(`${names.length} unmanaged database container(s)`, {
+              description: `${names.join(', ')} are missing from this workspace`,
+              action: {
+                label: 'Adopt',
+                onClick: async () => {
+                  await databasesApi.reconcileOrphans(
+                    names.map((name) => ({ name, resolution: 'adopt' })),
+                  );
+                  app.loadContainers();
+                },
+              },
+              duration: 15000,
+            });
+          },
+        );
+
+        unlistenRecoveryCompleted = await listen<{
+          recovered: RecoveredCreate[];
+        }>('recovery-completed', ({ payload }) => {
+          const failed = payload.recovered.filter((item) => item.error);
+          const names = payload.recovered.map((item) => item.name);
+          const description = `Leftovers of ${names.join(', ')} were removed`;
+
+          if (failed.length > 0) {
+            toast.error('Some interrupted creations could not be cleaned up', {
+              description: failed
+                .map((item) => `${item.name}: ${item.error}`)
+                .join('\n'),
+              duration: 15000,
+            });
+          } else {
+            toast.info(`Cleaned up ${names.length} interrupted creation(s)`, {
+              description,
+            });
+          }
+          app.loadContainers();
+        });
+
+        unlistenBackgroundError = await listen<{
+          source: string;
+          message: string;
+        }>('background-error', ({ payload }) => {
+          toast.error(payload.message, { description: payload.source });
+        });
       } catch (error) {
         console.error('Error setting up container listeners:', error);
       }
@@ -48,6 +131,12 @@ export function useMainPage() {
     return () => {
       unlistenContainerCreated?.();
       unlistenContainerUpdated?.();
+      unlistenContainerRemoved?.();
+      unlistenStatusChanged?.();
+      unlistenWorkspaceChanged?.();
+      unlistenOrphansDetected?.();
+      unlistenRecoveryCompleted?.();
+      unlistenBackgroundError?.();
     };
   }, [app.loadContainers]);
 
@@ -82,7 +171,7 @@ export function useMainPage() {
       if (!container) return;
 
       try {
-        if (container.status === 'running') {
+        if (isContainerRunning(container.status)) {
           await app.stopContainer(containerId);
         } else {
           await app.startContainer(containerId);
@@ -133,9 +222,11 @@ export function useMainPage() {
     dockerRefreshing: app.dockerRefreshing,
     isDockerAvailable: app.isDockerAvailable,
     showDockerOverlay: app.showDockerOverlay,
+    dockerStartProgress: app.dockerStartProgress,
 
     // App actions
     refreshDockerStatus: app.refreshDockerStatus,
+    startDocker: app.startDocker,
 
     // Window navigation
     openCreateWindow,
@@ -0,0 +1,48 @@
+import { MetricsCard } from './components/MetricsCard';
+import { useContainerMetrics } from './hooks/use-container-metrics';
+
+export function MetricsPage() {
+  // Without an id the window shows every running container
+  const containerId = new URLSearchParams(window.location.search).get('id');
+  const { metrics, history, updatedAt } = useContainerMetrics(containerId);
+
+  return (
+    <div className="h-screen flex flex-col">
+      {/* Titlebar space */}
+      <div className="h-6 bg-transparent" />
+
+      <div className="flex items-center justify-between px-6 pb-3 border-b border-border">
+        <h1 className="text-sm font-semibold">
+          {containerId ? 'Container metrics' : 'All containers'}
+        </h1>
+        <span className="text-xs text-muted-foreground">
+          {updatedAt
+            ? `Updated ${updatedAt.toLocaleTimeString()}`
+            : 'Waiting for data…'}
+        </span>
+      </div>
+
+      <div className="flex-1 overflow-auto p-6">
+        {updatedAt && metrics.length === 0 ? (
+          <p className="text-sm text-muted-foreground text-center mt-12">
+            No running containers
+          </p>
+        ) : (
+          <div
+            className={
+              containerId ? 'max-w-md mx-auto' : 'grid grid-cols-2 gap-4'
+            }
+          >
+            {metrics.map((m) => (
+              <MetricsCard
+                key={m.id}
+                metrics={m}
+                cpuHistory={history[m.id] ?? []}
+              />
+            ))}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
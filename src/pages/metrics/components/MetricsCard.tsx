@@ -0,0 +1,92 @@
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from '@/shared/components/ui/card';
+import { Progress } from '@/shared/components/ui/progress';
+import type { ContainerMetrics } from '../hooks/use-container-metrics';
+
+function formatBytes(bytes: number): string {
+  const units = ['B', 'KB', 'MB', 'GB', 'TB'];
+  let value = bytes;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${value.toFixed(unit === 0 ? 0 : 1)} ${units[unit]}`;
+}
+
+function Sparkline({ values }: { values: number[] }) {
+  if (values.length < 2) return <div className="h-10" />;
+
+  const max = Math.max(100, ...values);
+  const points = values
+    .map(
+      (v, i) => `${(i / (values.length - 1)) * 100},${40 - (v / max) * 40}`,
+    )
+    .join(' ');
+
+  return (
+    <svg
+      viewBox="0 0 100 40"
+      preserveAspectRatio="none"
+      className="h-10 w-full text-primary"
+      aria-hidden="true"
+    >
+      <polyline
+        points={points}
+        fill="none"
+        stroke="currentColor"
+        strokeWidth="1.5"
+        vectorEffect="non-scaling-stroke"
+      />
+    </svg>
+  );
+}
+
+interface MetricsCardProps {
+  metrics: ContainerMetrics;
+  cpuHistory: number[];
+}
+
+export function MetricsCard({ metrics, cpuHistory }: MetricsCardProps) {
+  return (
+    <Card className="gap-3 py-4">
+      <CardHeader className="px-4">
+        <CardTitle className="text-sm">{metrics.name}</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-3 px-4 text-xs">
+        <div>
+          <div className="flex justify-between text-muted-foreground">
+            <span>CPU</span>
+            <span>{metrics.cpuPercent.toFixed(1)}%</span>
+          </div>
+          <Sparkline values={cpuHistory} />
+        </div>
+        <div className="space-y-1">
+          <div className="flex justify-between text-muted-foreground">
+            <span>Memory</span>
+            <span>
+              {formatBytes(metrics.memoryUsedBytes)} /{' '}
+              {formatBytes(metrics.memoryLimitBytes)}
+            </span>
+          </div>
+          <Progress value={Math.min(metrics.memoryPercent, 100)} />
+        </div>
+        <div className="grid grid-cols-2 gap-2 text-muted-foreground">
+          <span>
+            Net ↓ {formatBytes(metrics.netRxBytes)} ↑{' '}
+            {formatBytes(metrics.netTxBytes)}
+          </span>
+          <span>
+            Disk R {formatBytes(metrics.blockReadBytes)} W{' '}
+            {formatBytes(metrics.blockWriteBytes)}
+          </span>
+          <span>Processes {metrics.pids}</span>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,74 @@
+import { useEffect, useState } from 'react';
+
+export interface ContainerMetrics {
+  id: string;
+  name: string;
+  cpuPercent: number;
+  memoryUsedBytes: number;
+  memoryLimitBytes: number;
+  memoryPercent: number;
+  netRxBytes: number;
+  netTxBytes: number;
+  blockReadBytes: number;
+  blockWriteBytes: number;
+  pids: number;
+}
+
+interface ContainerMetricsPayload {
+  metrics: ContainerMetrics[];
+  timestamp: string;
+}
+
+/** Samples kept per container for the sparklines */
+const HISTORY_LENGTH = 60;
+
+/**
+ * Hook listening to the `container-metrics` stream
+ * With a container id only that container is kept
+ */
+export function useContainerMetrics(containerId: string | null) {
+  const [metrics, setMetrics] = useState<ContainerMetrics[]>([]);
+  const [history, setHistory] = useState<Record<string, number[]>>({});
+  const [updatedAt, setUpdatedAt] = useState<Date | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupListener = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+
+        unlisten = await listen<ContainerMetricsPayload>(
+          'container-metrics',
+          (event) => {
+            const sample = containerId
+              ? event.payload.metrics.filter((m) => m.id === containerId)
+              : event.payload.metrics;
+
+            setMetrics(sample);
+            setUpdatedAt(new Date(event.payload.timestamp));
+            setHistory((previous) => {
+              const next: Record<string, number[]> = {};
+              for (const m of sample) {
+                next[m.id] = [...(previous[m.id] ?? []), m.cpuPercent].slice(
+                  -HISTORY_LENGTH,
+                );
+              }
+              return next;
+            });
+          },
+        );
+      } catch (error) {
+        console.error('Error setting up metrics listener:', error);
+      }
+    };
+
+    setupListener();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [containerId]);
+
+  return { metrics, history, updatedAt };
+}
@@ -2,25 +2,38 @@ import {
   AlertTriangle,
   Container,
   ExternalLink,
+  Play,
   RefreshCw,
   Terminal,
 } from 'lucide-react';
+import type { DockerStartProgress } from '../types/docker';
 import { Button } from './ui/button';
 import { Card, CardContent } from './ui/card';
 
 interface DockerUnavailableOverlayProps {
   status: 'stopped' | 'error' | 'connecting';
   error?: string;
+  guidance?: string;
   onRetry: () => void;
   isRetrying: boolean;
+  onStart?: () => void;
+  startProgress?: DockerStartProgress | null;
 }
 
 export function DockerUnavailableOverlay({
   status,
   error,
+  guidance,
   onRetry,
   isRetrying,
+  onStart,
+  startProgress,
 }: DockerUnavailableOverlayProps) {
+  const isStarting =
+    !!startProgress &&
+    startProgress.stage !== 'ready' &&
+    startProgress.stage !== 'failed';
+
   const getStatusInfo = () => {
     switch (status) {
       case 'connecting':
@@ -35,6 +48,7 @@ export function DockerUnavailableOverlay({
         return {
           title: 'Docker Not Running',
           description:
+            guidance ||
             'Docker Desktop needs to be started to manage containers',
           icon: <Container className="w-6 h-6 text-muted-foreground" />,
           showRetry: true,
@@ -73,6 +87,29 @@ export function DockerUnavailableOverlay({
 
             {/* Actions */}
             <div className="w-full space-y-3 pt-2">
+              {status === 'stopped' && onStart && (
+                <Button
+                  onClick={onStart}
+                  disabled={isStarting}
+                  className="w-full"
+                  size="sm"
+                >
+                  {isStarting ? (
+                    <>
+                      <RefreshCw className="w-4 h-4 mr-2 animate-spin" />
+                      {startProgress?.stage === 'launching'
+                        ? 'Launching Docker...'
+                        : 'Waiting for Docker...'}
+                    </>
+                  ) : (
+                    <>
+                      <Play className="w-4 h-4 mr-2" />
+                      Start Docker
+                    </>
+                  )}
+                </Button>
+              )}
+
               {statusInfo.showRetry && (
                 <Button
                   onClick={onRetry}
@@ -3,10 +3,76 @@ export type ContainerStatus =
   | 'stopped'
   | 'error'
   | 'creating'
-  | 'removing';
+  | 'starting'
+  | 'removing'
+  // Health and exit state reported by Docker
+  | 'unhealthy'
+  | 'restarting'
+  | 'exited';
+
+/**
+ * Whether the engine process is up, healthy or not
+ */
+export const isContainerRunning = (status: ContainerStatus): boolean =>
+  status === 'running' || status === 'starting' || status === 'unhealthy';
+
+/**
+ * Status label, with the exit code of a crashed container
+ */
+export const formatContainerStatus = (container: Container): string =>
+  container.status === 'exited' && container.exitCode !== undefined
+    ? `exited (${container.exitCode})`
+    : container.status;
 
 export type DatabaseType = 'PostgreSQL' | 'MySQL' | 'Redis' | 'MongoDB';
 
+/**
+ * Encoding and collation of a SQL database, fixed once it is created
+ */
+export interface LocaleSettings {
+  // PostgreSQL encoding or MySQL/MariaDB character set
+  encoding?: string;
+  // PostgreSQL locale, e.g. de_DE.utf8 or de-DE
+  locale?: string;
+  // MySQL/MariaDB collation
+  collation?: string;
+}
+
+export interface RegionSettings {
+  // IANA timezone, e.g. Europe/Madrid
+  timezone?: string;
+  // LANG of the container, e.g. en_US.UTF-8
+  lang?: string;
+}
+
+/**
+ * Share of the Docker host a database is tuned to use: a tenth, a quarter or
+ * half of its memory
+ */
+export type TuningPreset = 'small' | 'medium' | 'large';
+
+/**
+ * Preset a database was tuned with and the host it was measured on
+ */
+export interface TuningSettings {
+  preset: TuningPreset;
+  host: {
+    memoryMb: number;
+    cpus: number;
+  };
+}
+
+/**
+ * How the app restarts a container that exited unexpectedly
+ */
+export interface WatchdogPolicy {
+  // Restarts tried before giving up until the container is started again
+  maxAttempts: number;
+  // Wait before the first restart, doubled after each one that did not last
+  initialDelaySecs: number;
+  maxDelaySecs: number;
+}
+
 /**
  * Container/Database representation
  * This is what gets stored and displayed
@@ -17,6 +83,8 @@ export interface Container {
   dbType: DatabaseType;
   version: string;
   status: ContainerStatus;
+  // Exit code of the last run, set while the status is 'exited'
+  exitCode?: number;
   port: number;
   createdAt: Date;
   maxConnections: number;
@@ -26,4 +94,35 @@ export interface Container {
   databaseName?: string;
   persistData: boolean;
   enableAuth: boolean;
+  enableTls?: boolean;
+  caCertPath?: string;
+  project?: string;
+  tags: string[];
+  favorite: boolean;
+  lastStartedAt?: Date;
+  lastStoppedAt?: Date;
+  updatesAvailable: boolean;
+  // 'volumeMissing' when the data volume was removed outside the app
+  storageState: 'ok' | 'volumeMissing';
+  autoStart: boolean;
+  keepRunningOnExit: boolean;
+  exposeToLan: boolean;
+  // Framework preset the database was created for
+  framework?: string;
+  // Encoding and collation chosen at creation
+  locale?: LocaleSettings;
+  // Server arguments appended to the provider's command
+  extraArgs: string[];
+  // Seconds a stop waits before Docker kills the engine, Docker's default when unset
+  stopTimeout?: number;
+  // Timezone and system locale, the app default when created without one
+  region?: RegionSettings;
+  // Memory settings sized for the host, untuned when unset
+  tuning?: TuningSettings;
+  // Restarts after crashes, off when unset
+  watchdog?: WatchdogPolicy;
+  // Id of the remote Docker host it runs on, the local daemon when unset
+  host?: string;
+  // Problems setting it up after creation, such as an engine that never became ready
+  setupWarnings: string[];
 }
@@ -6,6 +6,54 @@ export interface DockerContainerStats {
   stopped: number;
 }
 
+export type DockerRuntime =
+  | 'dockerDesktop'
+  | 'colima'
+  | 'orbStack'
+  | 'rancherDesktop'
+  | 'dockerEngine';
+
+export interface DockerEnvironment {
+  runtime: DockerRuntime | null;
+  socket: string | null;
+  context: string | null;
+  installed: DockerRuntime[];
+}
+
+export interface DockerStartProgress {
+  stage: 'launching' | 'waiting' | 'ready' | 'failed';
+  runtime: DockerRuntime;
+  attempt: number;
+  message: string | null;
+}
+
+export interface ImageUpdateCheck {
+  databaseId: string;
+  image: string;
+  localDigest: string | null;
+  remoteDigest: string | null;
+  newerVersion: string | null;
+  updatesAvailable: boolean;
+  error: string | null;
+}
+
+export interface UnusedImage {
+  image: string;
+  imageId: string;
+  sizeBytes: number;
+  /** When the last database using it was removed */
+  releasedAt: string;
+}
+
+export interface ImageCleanupReport {
+  dryRun: boolean;
+  /** Removed images, or the ones a real run would remove */
+  images: UnusedImage[];
+  totalBytes: number;
+  /** Images Docker refused to remove */
+  errors: string[];
+}
+
 export interface DockerStatus {
   status: DockerStatusType;
   version?: string;
@@ -14,4 +62,27 @@ export interface DockerStatus {
   images?: number;
   uptime?: string;
   error?: string;
+  guidance?: string;
+  environment?: DockerEnvironment;
+}
+
+/**
+ * Remote Docker daemon whose databases are listed with the local ones
+ */
+export interface DockerHost {
+  /** Empty to add a new host */
+  id: string;
+  name: string;
+  /** DOCKER_HOST URL, e.g. ssh://deploy@build-server */
+  url: string;
+}
+
+export interface DockerHostStatus {
+  /** 'local' for the daemon of this machine */
+  id: string;
+  name: string;
+  url: string | null;
+  /** False when the last sync could not reach it */
+  reachable: boolean;
+  databaseCount: number;
 }
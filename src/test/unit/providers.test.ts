@@ -79,6 +79,23 @@ describe('Database Providers', () => {
         expect(args.ports[0].container).toBe(5432);
       });
 
+      it('should use the pgvector image and enable the extension', () => {
+        const config = createMockFormConfig('postgres', {
+          name: 'test-postgres',
+          port: 5432,
+          version: '17-alpine',
+          password: 'testpass123',
+          postgresSettings: { pgvector: true },
+        });
+
+        const args = provider.buildDockerArgs(config);
+
+        expect(args.image).toBe('pgvector/pgvector:pg17');
+        expect(provider.getPostCreateSql(config)).toEqual([
+          'CREATE EXTENSION IF NOT EXISTS vector',
+        ]);
+      });
+
       it('should include username env var when not default', () => {
         const config = createMockFormConfig('postgres', {
           username: 'customuser',
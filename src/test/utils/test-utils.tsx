@@ -24,6 +24,15 @@ export function createMockContainer(
     enableAuth: true,
     maxConnections: 100,
     createdAt: new Date(),
+    tags: [],
+    favorite: false,
+    updatesAvailable: false,
+    storageState: 'ok',
+    autoStart: false,
+    keepRunningOnExit: false,
+    exposeToLan: false,
+    extraArgs: [],
+    setupWarnings: [],
     ...overrides,
   };
 }
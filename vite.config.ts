@@ -19,6 +19,7 @@ export default defineConfig(async () => ({
         main: path.resolve(__dirname, 'index.html'),
         'create-container': path.resolve(__dirname, 'create-container.html'),
         'edit-container': path.resolve(__dirname, 'edit-container.html'),
+        metrics: path.resolve(__dirname, 'metrics.html'),
       },
     },
   },